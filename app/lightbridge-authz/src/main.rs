@@ -3,6 +3,10 @@ mod utils;
 
 use clap::Parser;
 use lightbridge_authz_core::Result;
+use lightbridge_authz_rest::budget_rollover::run_budget_rollover;
+use lightbridge_authz_rest::extauthz::start_extauthz_server;
+use lightbridge_authz_rest::key_encryption::run_signing_key_reencrypt;
+use lightbridge_authz_rest::signing_rotation::run_emergency_rotation;
 use lightbridge_authz_rest::{
     start_api_server, start_budget_server, start_idp_server, start_opa_server,
 };
@@ -11,7 +15,7 @@ use tokio::sync::mpsc;
 use tracing::{error, info};
 
 use crate::utils::banner::BANNER;
-use crate::utils::cli::{Cli, Commands};
+use crate::utils::cli::{BudgetCommands, Cli, Commands, SigningKeyCommands};
 use lightbridge_authz_core::config::load_from_path;
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
 use mimalloc::MiMalloc;
@@ -32,8 +36,18 @@ async fn main() -> Result<()> {
         Some(Commands::Api { config_path }) => Some(config_path),
         Some(Commands::Opa { config_path }) => Some(config_path),
         Some(Commands::Idp { config_path }) => Some(config_path),
-        Some(Commands::Budget { config_path }) => Some(config_path),
+        Some(Commands::Budget {
+            command: Some(BudgetCommands::Rollover { config_path, .. }),
+            ..
+        }) => Some(config_path),
+        Some(Commands::Budget { config_path, .. }) => config_path.as_ref(),
+        Some(Commands::ExtAuthz { config_path }) => Some(config_path),
         Some(Commands::Migrate { config_path }) => Some(config_path),
+        Some(Commands::SigningKeys {
+            command:
+                SigningKeyCommands::Reencrypt { config_path }
+                | SigningKeyCommands::EmergencyRotate { config_path },
+        }) => Some(config_path),
         Some(Commands::Config { config_path }) => Some(config_path),
         None => None,
     };
@@ -67,6 +81,11 @@ async fn main() -> Result<()> {
             let opa = config.clone().server.opa;
             let opa_billing = config.billing.clone();
             let opa_oauth2 = config.oauth2.clone();
+            let opa_introspection_cache = config.introspection_cache.clone();
+            let opa_redis = config.redis.clone();
+            let opa_introspection_budget = config.introspection_budget.clone();
+            let opa_usage_service = config.usage_service.clone();
+            let opa_api_key_hashing = config.api_key_hashing.clone();
 
             let config_clone = config.clone();
             let tx_clone = tx.clone();
@@ -82,6 +101,8 @@ async fn main() -> Result<()> {
                     &config_clone.api_key_expiry,
                     &config_clone.redis,
                     &config_clone.usage_service,
+                    &config_clone.api_key_hashing,
+                    &config_clone.api_key_lifecycle,
                 )
                 .await
                 {
@@ -94,7 +115,18 @@ async fn main() -> Result<()> {
             let tx_clone = tx.clone();
             let pool_clone = pool.clone();
             tokio::spawn(async move {
-                if let Err(e) = start_opa_server(&opa, pool_clone, &opa_billing, &opa_oauth2).await
+                if let Err(e) = start_opa_server(
+                    &opa,
+                    pool_clone,
+                    &opa_billing,
+                    &opa_oauth2,
+                    &opa_introspection_cache,
+                    &opa_redis,
+                    &opa_introspection_budget,
+                    &opa_usage_service,
+                    &opa_api_key_hashing,
+                )
+                .await
                 {
                     let _ = tx_clone
                         .send(format!("OPA server failed to start: {}", e))
@@ -123,6 +155,8 @@ async fn main() -> Result<()> {
                 &config.api_key_expiry,
                 &config.redis,
                 &config.usage_service,
+                &config.api_key_hashing,
+                &config.api_key_lifecycle,
             )
             .await?;
             Ok(())
@@ -135,7 +169,18 @@ async fn main() -> Result<()> {
             info!("Connecting to DB...");
             let pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::new(&config.database).await?);
 
-            start_opa_server(&config.server.opa, pool, &config.billing, &config.oauth2).await?;
+            start_opa_server(
+                &config.server.opa,
+                pool,
+                &config.billing,
+                &config.oauth2,
+                &config.introspection_cache,
+                &config.redis,
+                &config.introspection_budget,
+                &config.usage_service,
+                &config.api_key_hashing,
+            )
+            .await?;
             Ok(())
         }
         Some(Commands::Idp { config_path }) => {
@@ -154,7 +199,35 @@ async fn main() -> Result<()> {
             start_idp_server(idp, pool, &config.oauth2, &config.redis).await?;
             Ok(())
         }
-        Some(Commands::Budget { config_path }) => {
+        Some(Commands::Budget {
+            command:
+                Some(BudgetCommands::Rollover {
+                    config_path,
+                    period,
+                }),
+            ..
+        }) => {
+            let config = load_from_path(&config_path)?;
+            let pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::new(&config.database).await?);
+
+            let report = run_budget_rollover(pool, period.as_deref()).await?;
+            if report.failed > 0 {
+                Err(lightbridge_authz_core::Error::Server(format!(
+                    "budget rollover for {} failed for {} account(s)",
+                    report.period, report.failed
+                )))
+            } else {
+                info!(
+                    "budget rollover for {}: {} opening grant(s) written",
+                    report.period, report.granted
+                );
+                Ok(())
+            }
+        }
+        Some(Commands::Budget {
+            config_path: Some(config_path),
+            command: None,
+        }) => {
             info!("{}", BANNER);
 
             let config = load_from_path(&config_path)?;
@@ -181,11 +254,72 @@ async fn main() -> Result<()> {
             .await?;
             Ok(())
         }
+        Some(Commands::ExtAuthz { config_path }) => {
+            info!("{}", BANNER);
+
+            let config = load_from_path(&config_path)?;
+
+            info!("Connecting to DB...");
+            let pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::new(&config.database).await?);
+
+            let extauthz = config.server.extauthz.as_ref().ok_or_else(|| {
+                lightbridge_authz_core::Error::Server(
+                    "server.extauthz config is required to run the extauthz command".to_string(),
+                )
+            })?;
+            start_extauthz_server(
+                extauthz,
+                pool,
+                &config.server.opa.basic_auth,
+                &config.billing,
+                &config.oauth2,
+                &config.introspection_cache,
+                &config.redis,
+                &config.introspection_budget,
+                &config.usage_service,
+                &config.api_key_hashing,
+            )
+            .await?;
+            Ok(())
+        }
+        // clap requires `--config-path` whenever no subcommand is given.
+        Some(Commands::Budget {
+            config_path: None,
+            command: None,
+        }) => Err(lightbridge_authz_core::Error::Server(
+            "--config-path is required to run the budget command".to_string(),
+        )),
         Some(Commands::Migrate { config_path }) => {
             let config = load_from_path(&config_path)?;
             migrate::migrate(&config.database.url).await?;
             Ok(())
         }
+        Some(Commands::SigningKeys {
+            command: SigningKeyCommands::Reencrypt { config_path },
+        }) => {
+            let config = load_from_path(&config_path)?;
+            let pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::new(&config.database).await?);
+
+            let report = run_signing_key_reencrypt(pool, &config.oauth2).await?;
+            info!(
+                "signing keys: {} re-encrypted, {} already under the active KEK",
+                report.reencrypted, report.unchanged
+            );
+            Ok(())
+        }
+        Some(Commands::SigningKeys {
+            command: SigningKeyCommands::EmergencyRotate { config_path },
+        }) => {
+            let config = load_from_path(&config_path)?;
+            let pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::new(&config.database).await?);
+
+            let (kid, revoked) = run_emergency_rotation(pool, &config.oauth2).await?;
+            info!(
+                "signing key {kid} now active; revoked {}",
+                revoked.as_deref().unwrap_or("nothing (no key was active)")
+            );
+            Ok(())
+        }
         Some(Commands::Config { config_path }) => {
             let _ = load_from_path(&config_path)?;
             Ok(())
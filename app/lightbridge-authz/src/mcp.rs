@@ -9,19 +9,26 @@ use axum::{
 };
 use chrono::{DateTime, Utc};
 use cratestack::{CratestackContext, CratestackError, Value as CratestackValue};
-use lightbridge_authz_api::schema;
+use lightbridge_authz_api::schema::{self, procedures::ProcedureRegistry};
 use lightbridge_authz_api_key::repo::StoreRepo;
 use lightbridge_authz_bearer::{BearerTokenService, BearerTokenServiceTrait, TokenInfo};
 use lightbridge_authz_core::{
-    Config, CreateAccount, CreateApiKey, DefaultLimits, Error, Permission, Result, RotateApiKey,
-    config::{ApiKeyExpiry, ApiServer, BasicAuth, Billing, ModelCatalog, Oauth2, QuotaTiers},
+    ApiKeyHasher, Config, CreateAccount, CreateApiKey, DefaultLimits, Error, Permission, Result,
+    RotateApiKey,
+    config::{
+        ApiKeyExpiry, ApiKeyHashing, ApiServer, BasicAuth, Billing, ModelCatalog, Oauth2,
+        QuotaTiers, Redis, UsageServiceClient,
+    },
     cuid::cuid2,
     db::{DbPoolTrait, is_database_ready},
     server::serve_tls,
 };
 use lightbridge_authz_rest::{
-    OpaRepoTrait, OpaState,
+    BudgetServices, OpaRepoTrait, OpaState, Procedures,
+    api_key_usage::{ApiKeyUsageTracker, flush_on_shutdown, spawn_api_key_usage_flusher},
     handlers::{AuthzStoreImpl, opa::validate_api_key_context},
+    introspection_cache::InvalidationPublisher,
+    load_budget_services,
     middleware::bearer_auth,
     models::authorino::AuthorinoMetadata,
     rpc_authorize::RpcScope,
@@ -110,13 +117,19 @@ impl From<DefaultLimitsInput> for DefaultLimits {
 /// sqlx the RPC `ProcedureRegistry` in `lightbridge-authz-rest` delegates to (see that crate's
 /// `Procedures`). Calling the issuer directly rather than through `Procedures` keeps the richer
 /// `rotate-api-key` capability (name / expires_at / grace_period_seconds) that the RPC
-/// `rotateApiKey` procedure's `keyId`-only input cannot express. Validation tools stay on the
-/// hand-written OPA path (`OpaState`) — outside the cratestack CRUD migration's scope.
+/// `rotateApiKey` procedure's `keyId`-only input cannot express. Budget and session tools
+/// (balance/grants, self-service refill, the review queue, policy simulation, session
+/// revocation) go through `Procedures` itself, via each procedure's generated `invoke_with_db`:
+/// they have no richer issuer-level shape to preserve, and that path evaluates the schema's
+/// `@allow` and records the mutations in `audit_events` exactly as an RPC call would. Validation
+/// tools stay on the hand-written OPA path (`OpaState`) — outside the cratestack CRUD migration's
+/// scope.
 #[derive(Clone)]
 pub struct LightbridgeMcpHandler {
     tool_router: ToolRouter<Self>,
     cratestack_db: schema::Cratestack,
     issuer: Arc<AuthzStoreImpl>,
+    procedures: Arc<Procedures>,
     opa_state: Arc<OpaState>,
     billing: Arc<Billing>,
 }
@@ -138,13 +151,16 @@ impl LightbridgeMcpHandler {
     /// here. Kept correct anyway rather than hardcoding `None`, so a future MCP tool that does
     /// reach the exchange-token path inherits the same `azp` gate by construction instead of
     /// silently getting a `None` that would fail closed on every `azp`-bearing token.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         cratestack_db: schema::Cratestack,
         issuer: Arc<AuthzStoreImpl>,
+        procedures: Arc<Procedures>,
         opa_repo: Arc<dyn OpaRepoTrait>,
         basic_auth: BasicAuth,
         billing: &Billing,
         api_key_audience: Option<String>,
+        api_key_usage: Arc<ApiKeyUsageTracker>,
     ) -> Self {
         let billing = Arc::new(billing.clone());
         let opa_state = Arc::new(OpaState {
@@ -152,12 +168,19 @@ impl LightbridgeMcpHandler {
             basic_auth,
             billing: billing.clone(),
             api_key_audience,
+            // MCP never calls `introspect_token`, the only reader of these two.
+            introspection_cache: None,
+            introspection_budget: None,
+            // Keys are looked up under the same scheme this server issues them under.
+            api_key_hasher: issuer.api_key_hasher(),
+            api_key_usage,
         });
 
         Self {
             tool_router: Self::tool_router(),
             cratestack_db,
             issuer,
+            procedures,
             opa_state,
             billing,
         }
@@ -316,12 +339,10 @@ fn cratestack_error_to_tool_error(error: CratestackError) -> ErrorData {
 /// `@allow(auth() != null)` clauses but silently failed every clause #383 added (`auth().rpcScope`,
 /// `auth().perm<Permission>`) — found by CI's `integration-test` (`it-servers`) failing the very
 /// first MCP tool call after that PR merged, not by any local test, because nothing exercised this
-/// second entry point at all. `RpcScope::Crud` because the MCP surface only ever exposes the CRUD
-/// tool set (accounts/projects/api-keys) — confirmed against `app/lightbridge-authz/src/bin/
-/// lightbridge-mcp.rs` (a dedicated binary, entirely separate from `authz-budget`'s `command:
-/// budget` subcommand on the main `lightbridge-authz` binary) and `compose.yaml`'s `authz-mcp`
-/// service (own image/port, no budget-scoped env or command, same `DATABASE_URL` as `authz-api`)
-/// — `mcp.rs` has no `RpcScope`/budget-procedure concept anywhere in it.
+/// second entry point at all. `RpcScope::Crud` for every tool whose RPC counterpart is served
+/// by `authz-api` (accounts/projects/api-keys/sessions); the budget tools use
+/// [`budget_context_from_token_info`] instead, since their procedures' `@allow` clauses require
+/// `auth().rpcScope == "budget"`.
 ///
 /// See `cratestack_context_from_token_info_matches_the_shared_helper` below for the regression
 /// test pinning this delegation — it fails immediately if a future edit reintroduces a
@@ -330,6 +351,12 @@ fn cratestack_context_from_token_info(info: &TokenInfo) -> CratestackContext {
     lightbridge_authz_rest::auth_provider::build_context(info, RpcScope::Crud)
 }
 
+/// [`cratestack_context_from_token_info`] for the budget tools: the same delegation, scoped to
+/// `RpcScope::Budget` as `authz-budget`'s RPC surface is.
+fn budget_context_from_token_info(info: &TokenInfo) -> CratestackContext {
+    lightbridge_authz_rest::auth_provider::build_context(info, RpcScope::Budget)
+}
+
 /// A `find_unique` that returned `None` (row absent, or hidden by the membership read policy) is a
 /// uniform not-found for the caller, matching the RPC surface's policy-driven behavior.
 fn require_found<T>(value: Option<T>) -> std::result::Result<T, ErrorData> {
@@ -409,11 +436,14 @@ fn required_tool_permission(tool: &str) -> Option<Permission> {
         | "set-project-member-role"
         | "set-project-member-quota-tier" => Permission::ProjectMember,
         "create-project" => Permission::ProjectCreate,
-        "list-projects" | "get-project" => Permission::ProjectRead,
+        "list-projects" | "get-project" | "get-project-key-lifecycle-policy" => {
+            Permission::ProjectRead
+        }
         "update-project"
         | "set-project-quota"
         | "set-project-allowed-models"
-        | "set-project-model-policy" => Permission::ProjectUpdate,
+        | "set-project-model-policy"
+        | "set-project-key-lifecycle-policy" => Permission::ProjectUpdate,
         "delete-project" => Permission::ProjectDelete,
         "disable-project" | "enable-project" => Permission::ProjectDisable,
         "set-default-project" => Permission::ProjectUpdate,
@@ -423,7 +453,18 @@ fn required_tool_permission(tool: &str) -> Option<Permission> {
         "delete-api-key" => Permission::ApiKeyDelete,
         "revoke-api-key" => Permission::ApiKeyRevoke,
         "rotate-api-key" => Permission::ApiKeyRotate,
+        "set-api-key-allowed-cidrs" => Permission::ApiKeyUpdate,
         "validate-api-key" | "validate-authorino-api-key" => Permission::ApiKeyValidate,
+        "get-my-budget-balance" | "list-my-budget-grants" => Permission::BudgetReadOwn,
+        // `get-my-budget-refill-ladder` is the read companion of `request-budget-refill`, gated at
+        // the mutation's own permission exactly like `procedure.getMyBudgetRefillLadder`.
+        "request-budget-refill" | "get-my-budget-refill-ladder" => Permission::BudgetSelfRefill,
+        "list-pending-augmentation-requests"
+        | "approve-augmentation-request"
+        | "reject-augmentation-request" => Permission::BudgetReview,
+        "simulate-budget-policy" => Permission::BudgetPolicySimulate,
+        "revoke-own-sessions" => Permission::SessionRevokeOwn,
+        "revoke-subject-sessions" => Permission::SessionRevoke,
         _ => return None,
     })
 }
@@ -749,6 +790,18 @@ struct SetProjectModelPolicyParams {
     model_policy: String,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct SetProjectKeyLifecyclePolicyParams {
+    project_id: String,
+    /// Days without use after which a key in this project counts as dormant; omitted/`null`
+    /// inherits the deployment's `api_key_lifecycle.dormant_after_days`. Must be positive.
+    #[serde(default)]
+    dormant_after_days: Option<i64>,
+    /// Revoke dormant keys (reason `dormant`) instead of only reporting them.
+    #[serde(default)]
+    auto_revoke_dormant: bool,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 struct ListProjectsParams {
     account_id: String,
@@ -783,6 +836,14 @@ struct CreateApiKeyParams {
     /// regardless -- see `AuthzStoreImpl::validate_expires_at`.
     expires_at: String,
     billing_plan: String,
+    /// Optional key-level model subset, narrower than the project: every id must be in the
+    /// catalogue (`list-model-catalog`) and allowed by the project's `model_policy`. Omit to
+    /// inherit the project.
+    #[serde(default)]
+    allowed_models: Option<Vec<String>>,
+    /// Optional scope restriction, any of `"inference"`, `"embeddings"`, `"batch"`. Omit for all.
+    #[serde(default)]
+    scopes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -819,8 +880,25 @@ struct RotateApiKeyParams {
     name: Option<String>,
     #[serde(default)]
     expires_at: Option<String>,
+    /// Seconds the old key stays active next to its successor (capped at its own expiry); omit
+    /// or 0 to revoke it at once.
     #[serde(default)]
     grace_period_seconds: Option<i64>,
+    /// Replaces the successor's model subset (same rules as `create-api-key`); omit to carry the
+    /// old key's forward.
+    #[serde(default)]
+    allowed_models: Option<Vec<String>>,
+    /// Replaces the successor's scopes; omit to carry the old key's forward.
+    #[serde(default)]
+    scopes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct SetApiKeyAllowedCidrsParams {
+    key_id: String,
+    /// CIDRs or bare addresses the key may be used from; omit or `null` to clear the allowlist.
+    #[serde(default)]
+    allowed_cidrs: Option<Vec<String>>,
 }
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -839,6 +917,86 @@ struct ValidateAuthorinoApiKeyParams {
     metadata: HashMap<String, Value>,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct BudgetPeriodParams {
+    /// The `YYYY-MM` calendar month.
+    period: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct ListMyBudgetGrantsParams {
+    /// The `YYYY-MM` calendar month; omit to list across every period.
+    #[serde(default)]
+    period: Option<String>,
+    /// RFC3339 cursor: only grants created strictly before it (the previous page's `nextCursor`).
+    #[serde(default)]
+    before: Option<String>,
+    #[serde(default)]
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct RequestBudgetRefillParams {
+    budget_account_id: String,
+    account_id: String,
+    #[serde(default)]
+    project_id: Option<String>,
+    /// The `YYYY-MM` calendar month.
+    period: String,
+    /// Micro-USD as a decimal string; must be one of the ladder's `allowedAmountsMicros`
+    /// (`get-my-budget-refill-ladder`).
+    requested_amount_micros: String,
+    /// Makes a retry return the original request instead of evaluating a second one.
+    #[serde(default)]
+    idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct ListPendingAugmentationRequestsParams {
+    #[serde(default)]
+    budget_account_id: Option<String>,
+    /// RFC3339 cursor: only requests created strictly after it (the previous page's `nextCursor`).
+    #[serde(default)]
+    after: Option<String>,
+    #[serde(default)]
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct AugmentationRequestByIdParams {
+    request_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct RejectAugmentationRequestParams {
+    request_id: String,
+    reason: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct SimulateBudgetPolicyParams {
+    /// The proposed rule data, JSON-encoded; never persisted or activated.
+    rule_data_json: String,
+    /// A JSON-encoded `Facts` scenario to evaluate it against.
+    scenario_json: String,
+    /// Micro-USD as a decimal string.
+    requested_amount_micros: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct RevokeOwnSessionsParams {
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct RevokeSubjectSessionsParams {
+    /// The account (JWT subject) whose sessions are revoked.
+    account_id: String,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
 #[tool_router(router = tool_router)]
 impl LightbridgeMcpHandler {
     #[tool(
@@ -1174,6 +1332,49 @@ impl LightbridgeMcpHandler {
         to_json_value(project)
     }
 
+    #[tool(
+        name = "get-project-key-lifecycle-policy",
+        description = "Get a project's dormant-key policy (RPC procedure.getProjectKeyLifecyclePolicy); defaults when never set"
+    )]
+    async fn get_project_key_lifecycle_policy_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<ProjectByIdParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let subject = subject_from_request_context(&context)?;
+        let policy = self
+            .issuer
+            .get_project_key_lifecycle_policy(&subject, &params.project_id)
+            .await
+            .map_err(to_tool_error)?;
+
+        to_json_value(policy)
+    }
+
+    #[tool(
+        name = "set-project-key-lifecycle-policy",
+        description = "Set a project's dormant-key policy (RPC procedure.setProjectKeyLifecyclePolicy); owner or any roster member. auto_revoke_dormant revokes keys unused for dormant_after_days instead of only reporting them"
+    )]
+    async fn set_project_key_lifecycle_policy_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<SetProjectKeyLifecyclePolicyParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let subject = subject_from_request_context(&context)?;
+        let policy = self
+            .issuer
+            .set_project_key_lifecycle_policy(
+                &subject,
+                &params.project_id,
+                params.dormant_after_days,
+                params.auto_revoke_dormant,
+            )
+            .await
+            .map_err(to_tool_error)?;
+
+        to_json_value(policy)
+    }
+
     #[tool(
         name = "create-project",
         description = "Create a project (RPC model.Project.create); allowedModels is set afterward via set-project-allowed-models, projectQuota via set-project-quota"
@@ -1404,6 +1605,8 @@ impl LightbridgeMcpHandler {
                     name: params.name,
                     expires_at: Some(expires_at),
                     billing_plan: params.billing_plan,
+                    allowed_models: params.allowed_models,
+                    scopes: params.scopes,
                 },
             )
             .await
@@ -1532,6 +1735,25 @@ impl LightbridgeMcpHandler {
         to_json_value(api_key)
     }
 
+    #[tool(
+        name = "set-api-key-allowed-cidrs",
+        description = "Set or clear an API key's client IP allowlist (RPC procedure.setApiKeyAllowedCidrs)"
+    )]
+    async fn set_api_key_allowed_cidrs_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<SetApiKeyAllowedCidrsParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let subject = subject_from_request_context(&context)?;
+        let api_key = self
+            .issuer
+            .set_api_key_allowed_cidrs(&subject, &params.key_id, params.allowed_cidrs)
+            .await
+            .map_err(to_tool_error)?;
+
+        to_json_value(api_key)
+    }
+
     #[tool(
         name = "rotate-api-key",
         description = "Rotate an API key (RPC procedure.rotateApiKey)"
@@ -1554,6 +1776,8 @@ impl LightbridgeMcpHandler {
                     name: params.name,
                     expires_at,
                     grace_period_seconds: params.grace_period_seconds,
+                    allowed_models: params.allowed_models,
+                    scopes: params.scopes,
                 },
             )
             .await
@@ -1583,6 +1807,359 @@ impl LightbridgeMcpHandler {
     ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
         run_validate_authorino(&self.opa_state, params).await
     }
+
+    #[tool(
+        name = "get-my-budget-balance",
+        description = "Get the caller's own budget balance for a period (RPC procedure.getMyBudgetBalance)"
+    )]
+    async fn get_my_budget_balance_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<BudgetPeriodParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let ctx = budget_context_from_token_info(&token_info_from_request_context(&context)?);
+        let args = schema::procedures::get_my_budget_balance::Args {
+            args: schema::GetMyBudgetBalanceInput {
+                period: params.period,
+            },
+        };
+        let balance = schema::procedures::get_my_budget_balance::invoke_with_db(
+            &self.cratestack_db,
+            &args,
+            &ctx,
+            |authorized| {
+                self.procedures.get_my_budget_balance(
+                    &self.cratestack_db,
+                    &ctx,
+                    args.clone(),
+                    authorized,
+                )
+            },
+        )
+        .await
+        .map_err(cratestack_error_to_tool_error)?;
+
+        to_json_value(balance)
+    }
+
+    #[tool(
+        name = "list-my-budget-grants",
+        description = "List the caller's own budget grants, newest first (RPC procedure.listMyBudgetGrants)"
+    )]
+    async fn list_my_budget_grants_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<ListMyBudgetGrantsParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let ctx = budget_context_from_token_info(&token_info_from_request_context(&context)?);
+        let args = schema::procedures::list_my_budget_grants::Args {
+            args: schema::ListMyBudgetGrantsInput {
+                period: params.period,
+                before: parse_optional_datetime(params.before, "before")?,
+                limit: params.limit,
+            },
+        };
+        let page = schema::procedures::list_my_budget_grants::invoke_with_db(
+            &self.cratestack_db,
+            &args,
+            &ctx,
+            |authorized| {
+                self.procedures.list_my_budget_grants(
+                    &self.cratestack_db,
+                    &ctx,
+                    args.clone(),
+                    authorized,
+                )
+            },
+        )
+        .await
+        .map_err(cratestack_error_to_tool_error)?;
+
+        to_json_value(page)
+    }
+
+    #[tool(
+        name = "request-budget-refill",
+        description = "Ask for more budget for a period (RPC procedure.requestBudgetRefill); granted at once or queued for review by the active policy"
+    )]
+    async fn request_budget_refill_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<RequestBudgetRefillParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let ctx = budget_context_from_token_info(&token_info_from_request_context(&context)?);
+        let args = schema::procedures::request_budget_refill::Args {
+            args: schema::RequestBudgetRefillInput {
+                budgetAccountId: params.budget_account_id,
+                accountId: params.account_id,
+                projectId: params.project_id,
+                period: params.period,
+                idempotencyKey: params.idempotency_key,
+                requestedAmountMicros: params.requested_amount_micros,
+            },
+        };
+        let request = schema::procedures::request_budget_refill::invoke_with_db(
+            &self.cratestack_db,
+            &args,
+            &ctx,
+            |authorized| {
+                self.procedures.request_budget_refill(
+                    &self.cratestack_db,
+                    &ctx,
+                    args.clone(),
+                    authorized,
+                )
+            },
+        )
+        .await
+        .map_err(cratestack_error_to_tool_error)?;
+
+        to_json_value(request)
+    }
+
+    #[tool(
+        name = "get-my-budget-refill-ladder",
+        description = "Preview the refill amounts the active policy offers the caller for a period (RPC procedure.getMyBudgetRefillLadder)"
+    )]
+    async fn get_my_budget_refill_ladder_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<BudgetPeriodParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let ctx = budget_context_from_token_info(&token_info_from_request_context(&context)?);
+        let args = schema::procedures::get_my_budget_refill_ladder::Args {
+            args: schema::GetMyBudgetRefillLadderInput {
+                period: params.period,
+            },
+        };
+        let ladder = schema::procedures::get_my_budget_refill_ladder::invoke_with_db(
+            &self.cratestack_db,
+            &args,
+            &ctx,
+            |authorized| {
+                self.procedures.get_my_budget_refill_ladder(
+                    &self.cratestack_db,
+                    &ctx,
+                    args.clone(),
+                    authorized,
+                )
+            },
+        )
+        .await
+        .map_err(cratestack_error_to_tool_error)?;
+
+        to_json_value(ladder)
+    }
+
+    #[tool(
+        name = "list-pending-augmentation-requests",
+        description = "List refill requests awaiting review, oldest first (RPC procedure.listPendingAugmentationRequests)"
+    )]
+    async fn list_pending_augmentation_requests_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<ListPendingAugmentationRequestsParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let ctx = budget_context_from_token_info(&token_info_from_request_context(&context)?);
+        let args = schema::procedures::list_pending_augmentation_requests::Args {
+            args: schema::ListPendingAugmentationRequestsInput {
+                budgetAccountId: params.budget_account_id,
+                after: parse_optional_datetime(params.after, "after")?,
+                limit: params.limit,
+            },
+        };
+        let page = schema::procedures::list_pending_augmentation_requests::invoke_with_db(
+            &self.cratestack_db,
+            &args,
+            &ctx,
+            |authorized| {
+                self.procedures.list_pending_augmentation_requests(
+                    &self.cratestack_db,
+                    &ctx,
+                    args.clone(),
+                    authorized,
+                )
+            },
+        )
+        .await
+        .map_err(cratestack_error_to_tool_error)?;
+
+        to_json_value(page)
+    }
+
+    #[tool(
+        name = "approve-augmentation-request",
+        description = "Approve a pending refill request and grant its amount (RPC procedure.approveAugmentationRequest)"
+    )]
+    async fn approve_augmentation_request_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<AugmentationRequestByIdParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let ctx = budget_context_from_token_info(&token_info_from_request_context(&context)?);
+        let args = schema::procedures::approve_augmentation_request::Args {
+            args: schema::ApproveAugmentationRequestInput {
+                requestId: params.request_id,
+            },
+        };
+        let request = schema::procedures::approve_augmentation_request::invoke_with_db(
+            &self.cratestack_db,
+            &args,
+            &ctx,
+            |authorized| {
+                self.procedures.approve_augmentation_request(
+                    &self.cratestack_db,
+                    &ctx,
+                    args.clone(),
+                    authorized,
+                )
+            },
+        )
+        .await
+        .map_err(cratestack_error_to_tool_error)?;
+
+        to_json_value(request)
+    }
+
+    #[tool(
+        name = "reject-augmentation-request",
+        description = "Reject a pending refill request with a reason (RPC procedure.rejectAugmentationRequest)"
+    )]
+    async fn reject_augmentation_request_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<RejectAugmentationRequestParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let ctx = budget_context_from_token_info(&token_info_from_request_context(&context)?);
+        let args = schema::procedures::reject_augmentation_request::Args {
+            args: schema::RejectAugmentationRequestInput {
+                requestId: params.request_id,
+                reason: params.reason,
+            },
+        };
+        let request = schema::procedures::reject_augmentation_request::invoke_with_db(
+            &self.cratestack_db,
+            &args,
+            &ctx,
+            |authorized| {
+                self.procedures.reject_augmentation_request(
+                    &self.cratestack_db,
+                    &ctx,
+                    args.clone(),
+                    authorized,
+                )
+            },
+        )
+        .await
+        .map_err(cratestack_error_to_tool_error)?;
+
+        to_json_value(request)
+    }
+
+    #[tool(
+        name = "simulate-budget-policy",
+        description = "Evaluate proposed budget rule data against a scenario without persisting or activating it (RPC procedure.simulateBudgetPolicy)"
+    )]
+    async fn simulate_budget_policy_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<SimulateBudgetPolicyParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let ctx = budget_context_from_token_info(&token_info_from_request_context(&context)?);
+        let args = schema::procedures::simulate_budget_policy::Args {
+            args: schema::SimulateBudgetPolicyInput {
+                ruleDataJson: params.rule_data_json,
+                scenarioJson: params.scenario_json,
+                requestedAmountMicros: params.requested_amount_micros,
+            },
+        };
+        let decision = schema::procedures::simulate_budget_policy::invoke_with_db(
+            &self.cratestack_db,
+            &args,
+            &ctx,
+            |authorized| {
+                self.procedures.simulate_budget_policy(
+                    &self.cratestack_db,
+                    &ctx,
+                    args.clone(),
+                    authorized,
+                )
+            },
+        )
+        .await
+        .map_err(cratestack_error_to_tool_error)?;
+
+        to_json_value(decision)
+    }
+
+    #[tool(
+        name = "revoke-own-sessions",
+        description = "Revoke every active refresh-token session of the caller (RPC procedure.revokeOwnSessions)"
+    )]
+    async fn revoke_own_sessions_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<RevokeOwnSessionsParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let ctx = cratestack_context_from_token_info(&token_info_from_request_context(&context)?);
+        let args = schema::procedures::revoke_own_sessions::Args {
+            args: schema::RevokeOwnSessionsInput {
+                reason: params.reason,
+            },
+        };
+        let result = schema::procedures::revoke_own_sessions::invoke_with_db(
+            &self.cratestack_db,
+            &args,
+            &ctx,
+            |authorized| {
+                self.procedures.revoke_own_sessions(
+                    &self.cratestack_db,
+                    &ctx,
+                    args.clone(),
+                    authorized,
+                )
+            },
+        )
+        .await
+        .map_err(cratestack_error_to_tool_error)?;
+
+        to_json_value(result)
+    }
+
+    #[tool(
+        name = "revoke-subject-sessions",
+        description = "Revoke every active refresh-token session of another subject, the offboarding kill switch (RPC procedure.revokeSubjectSessions)"
+    )]
+    async fn revoke_subject_sessions_tool(
+        &self,
+        context: RequestContext<RoleServer>,
+        Parameters(params): Parameters<RevokeSubjectSessionsParams>,
+    ) -> std::result::Result<Json<EndpointResponse>, ErrorData> {
+        let ctx = cratestack_context_from_token_info(&token_info_from_request_context(&context)?);
+        let args = schema::procedures::revoke_subject_sessions::Args {
+            args: schema::RevokeSubjectSessionsInput {
+                accountId: params.account_id,
+                reason: params.reason,
+            },
+        };
+        let result = schema::procedures::revoke_subject_sessions::invoke_with_db(
+            &self.cratestack_db,
+            &args,
+            &ctx,
+            |authorized| {
+                self.procedures.revoke_subject_sessions(
+                    &self.cratestack_db,
+                    &ctx,
+                    args.clone(),
+                    authorized,
+                )
+            },
+        )
+        .await
+        .map_err(cratestack_error_to_tool_error)?;
+
+        to_json_value(result)
+    }
 }
 
 /// Core of the `validate-api-key` tool, factored out of the RBAC-gated tool method (which takes
@@ -1684,9 +2261,11 @@ fn build_mcp_router(
     billing: &Billing,
     cratestack_db: schema::Cratestack,
     issuer: Arc<AuthzStoreImpl>,
+    procedures: Arc<Procedures>,
     opa_repo: Arc<dyn OpaRepoTrait>,
     bearer_service: Arc<dyn BearerTokenServiceTrait>,
     readiness_pool: Arc<dyn DbPoolTrait>,
+    api_key_usage: Arc<ApiKeyUsageTracker>,
 ) -> Router {
     let app_state = Arc::new(lightbridge_authz_api::AppState {
         bearer: bearer_service,
@@ -1699,10 +2278,12 @@ fn build_mcp_router(
     let handler = LightbridgeMcpHandler::new(
         cratestack_db,
         issuer,
+        procedures,
         opa_repo,
         basic_auth,
         billing,
         api_key_audience,
+        api_key_usage,
     );
     let oauth_proxy_state = Arc::new(OauthProxyState {
         client: Client::new(),
@@ -1729,6 +2310,10 @@ fn build_mcp_router(
             .route("/", get(root_handler))
             .route("/healthz", get(health_handler))
             .route("/healthz/startup", get(startup_handler))
+            .route(
+                "/metrics",
+                get(lightbridge_authz_core::metrics::metrics_handler),
+            )
             .route(
                 "/.well-known/oauth-authorization-server",
                 get(move |headers: HeaderMap| {
@@ -1786,6 +2371,9 @@ pub async fn start_mcp_server(
     quota_tiers: &QuotaTiers,
     models: &ModelCatalog,
     api_key_expiry: &ApiKeyExpiry,
+    redis: Option<&Redis>,
+    usage_service: Option<&UsageServiceClient>,
+    api_key_hashing: Option<&ApiKeyHashing>,
     pool: Arc<dyn DbPoolTrait>,
 ) -> Result<()> {
     billing.validate()?;
@@ -1796,20 +2384,48 @@ pub async fn start_mcp_server(
         let signing = oauth2.signing.as_ref().ok_or_else(|| {
             Error::Server("oauth2.type is 'self' but oauth2.signing is missing".to_string())
         })?;
-        let signing_repo = StoreRepo::new(pool.clone());
+        let signing_repo = Arc::new(StoreRepo::new(pool.clone()));
         lightbridge_authz_rest::signing::bootstrap_signing_key(&signing_repo, signing).await?;
+        lightbridge_authz_rest::signing_rotation::spawn_signing_key_rotation(signing_repo, oauth2)?;
     }
     // Secret-issuance + membership operations reused by the procedure-backed tools (hand-written
     // sqlx on the core `DbPool`, sqlx 0.9) — the same `AuthzStoreImpl` the RPC procedures delegate
     // to in `lightbridge-authz-rest`.
-    let issuer = Arc::new(AuthzStoreImpl::with_pool_and_oauth2(
+    let mut issuer = AuthzStoreImpl::with_pool_and_oauth2(
         pool.clone(),
         oauth2,
         billing,
         quota_tiers,
         models,
         api_key_expiry,
-    )?);
+    )?
+    .with_api_key_hasher(Arc::new(ApiKeyHasher::from_config(api_key_hashing)?));
+    // Redis stays optional here. When it is configured, MCP mutations (revoke-api-key,
+    // disable-project, ...) publish introspection invalidations exactly as authz-api's do;
+    // without it, subscribers fall back to `max_staleness_seconds`. The publisher connects
+    // lazily, so an unreachable Redis never blocks startup.
+    if let Some(redis) = redis {
+        issuer = issuer.with_introspection_invalidation(InvalidationPublisher::connect(
+            &redis.url,
+            redis.ca_bundle_path.as_deref(),
+        )?);
+    }
+    let issuer = Arc::new(issuer);
+    // The budget and session tools run the RPC procedures themselves, so they need the same
+    // budget-domain services `authz-api`/`authz-budget` build -- see `load_budget_services`.
+    let BudgetServices {
+        policy_store,
+        budget_repo,
+        refill_service,
+        review_service,
+    } = load_budget_services(pool.clone(), usage_service).await?;
+    let procedures = Arc::new(Procedures::new(
+        issuer.clone(),
+        policy_store,
+        refill_service,
+        review_service,
+        budget_repo,
+    ));
     let opa_repo: Arc<dyn OpaRepoTrait> = Arc::new(StoreRepo::new(pool));
     let bearer_service: Arc<dyn BearerTokenServiceTrait> =
         Arc::new(BearerTokenService::new(oauth2.clone()));
@@ -1830,6 +2446,9 @@ pub async fn start_mcp_server(
         .map_err(|e| Error::Server(format!("failed to open cratestack Postgres pool: {e}")))?;
     let cratestack_db = schema::Cratestack::builder(cratestack_pool).build();
 
+    let api_key_usage = Arc::new(ApiKeyUsageTracker::default());
+    spawn_api_key_usage_flusher(api_key_usage.clone(), opa_repo.clone());
+
     let app = build_mcp_router(
         api,
         oauth2,
@@ -1837,9 +2456,11 @@ pub async fn start_mcp_server(
         billing,
         cratestack_db,
         issuer,
-        opa_repo,
+        procedures,
+        opa_repo.clone(),
         bearer_service,
         readiness_pool,
+        api_key_usage.clone(),
     );
 
     let signing_enabled = oauth2.is_self_signed();
@@ -1854,7 +2475,9 @@ pub async fn start_mcp_server(
         "starting mcp server"
     );
 
-    serve_tls("MCP", &api.address, api.port, &api.tls, app).await
+    let served = serve_tls("MCP", &api.address, api.port, &api.tls, app).await;
+    flush_on_shutdown(&api_key_usage, opa_repo.as_ref()).await;
+    served
 }
 
 pub async fn start_mcp_server_from_config(config: &Config) -> Result<()> {
@@ -1868,6 +2491,9 @@ pub async fn start_mcp_server_from_config(config: &Config) -> Result<()> {
         &config.quota_tiers,
         &config.models,
         &config.api_key_expiry,
+        config.redis.as_ref(),
+        config.usage_service.as_ref(),
+        config.api_key_hashing.as_ref(),
         pool,
     )
     .await
@@ -1904,6 +2530,7 @@ mod tests {
     use super::*;
     use axum::body::to_bytes;
     use chrono::Utc;
+    use lightbridge_authz_api_key::entities::api_key_row::ApiKeyUsage;
     use lightbridge_authz_core::{Account, ApiKey, ApiKeyStatus, Project, async_trait};
     use sqlx::postgres::PgPoolOptions;
 
@@ -1941,13 +2568,17 @@ mod tests {
 
     #[async_trait]
     impl OpaRepoTrait for MockOpaRepo {
-        async fn record_api_key_usage(&self, _key_id: &str, _ip: Option<String>) -> Result<ApiKey> {
-            Ok(self.api_key.clone())
+        async fn get_api_key_by_id(&self, _key_id: &str) -> Result<Option<ApiKey>> {
+            Ok(Some(self.api_key.clone()))
+        }
+
+        async fn record_api_key_usage_batch(&self, usages: &[ApiKeyUsage]) -> Result<u64> {
+            Ok(usages.len() as u64)
         }
 
         async fn find_api_key_validation_by_hash(
             &self,
-            _key_hash: &str,
+            _key_hashes: &[String],
         ) -> Result<Option<lightbridge_authz_core::ApiKeyValidation>> {
             Ok(Some(lightbridge_authz_core::ApiKeyValidation {
                 api_key_id: self.api_key.id.clone(),
@@ -1964,9 +2595,39 @@ mod tests {
                 account_status: self.account.status.to_string(),
                 expires_at: self.api_key.expires_at,
                 effective_status: "active".to_string(),
+                allowed_cidrs: None,
             }))
         }
 
+        async fn upgrade_api_key_hash(
+            &self,
+            _id: &str,
+            _old_hash: &str,
+            _new_hash: &str,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn set_api_key_status(
+            &self,
+            _subject: &str,
+            _key_id: &str,
+            _status: ApiKeyStatus,
+            _revoked_at: Option<chrono::DateTime<Utc>>,
+            _revocation_reason: Option<&str>,
+        ) -> Result<ApiKey> {
+            Err(lightbridge_authz_core::error::Error::NotFound)
+        }
+
+        // No MCP tool introspects in batches; `find_api_key_validation_by_hash` above is the only
+        // lookup these tests exercise.
+        async fn find_api_key_validations_by_hashes(
+            &self,
+            _key_hashes: &[String],
+        ) -> Result<Vec<lightbridge_authz_core::ApiKeyValidation>> {
+            Ok(Vec::new())
+        }
+
         async fn get_project(&self, _subject: &str, project_id: &str) -> Result<Option<Project>> {
             if project_id == self.project.id {
                 return Ok(Some(self.project.clone()));
@@ -2038,7 +2699,13 @@ mod tests {
             last_ip: None,
             revoked_at: None,
             billing_plan: "free".to_string(),
+            allowed_models: None,
+            scopes: None,
+            allowed_cidrs: None,
             updated_at: Utc::now(),
+            rotating_until: None,
+            successor_id: None,
+            predecessor_id: None,
         }
     }
 
@@ -2083,17 +2750,48 @@ mod tests {
 
     #[async_trait]
     impl OpaRepoTrait for NotFoundOpaRepo {
-        async fn record_api_key_usage(&self, _key_id: &str, _ip: Option<String>) -> Result<ApiKey> {
-            Err(Error::NotFound)
+        async fn get_api_key_by_id(&self, _key_id: &str) -> Result<Option<ApiKey>> {
+            Ok(None)
+        }
+
+        async fn record_api_key_usage_batch(&self, _usages: &[ApiKeyUsage]) -> Result<u64> {
+            Ok(0)
         }
 
         async fn find_api_key_validation_by_hash(
             &self,
-            _key_hash: &str,
+            _key_hashes: &[String],
         ) -> Result<Option<lightbridge_authz_core::ApiKeyValidation>> {
             Ok(None)
         }
 
+        async fn upgrade_api_key_hash(
+            &self,
+            _id: &str,
+            _old_hash: &str,
+            _new_hash: &str,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn set_api_key_status(
+            &self,
+            _subject: &str,
+            _key_id: &str,
+            _status: ApiKeyStatus,
+            _revoked_at: Option<chrono::DateTime<Utc>>,
+            _revocation_reason: Option<&str>,
+        ) -> Result<ApiKey> {
+            Err(lightbridge_authz_core::error::Error::NotFound)
+        }
+
+        async fn find_api_key_validations_by_hashes(
+            &self,
+            _key_hashes: &[String],
+        ) -> Result<Vec<lightbridge_authz_core::ApiKeyValidation>> {
+            Ok(Vec::new())
+        }
+
         async fn get_project(&self, _subject: &str, _project_id: &str) -> Result<Option<Project>> {
             Ok(None)
         }
@@ -2171,8 +2869,8 @@ mod tests {
 
     /// Issue #383 follow-up regression: MCP's `cratestack_context_from_token_info` must build the
     /// context by delegating to the SAME `lightbridge_authz_rest::auth_provider::build_context`
-    /// the RPC surface uses (`RpcScope::Crud` — MCP only ever exposes the CRUD tool set, see that
-    /// function's own doc comment for what was confirmed and where). This is the test that fails
+    /// the RPC surface uses (`RpcScope::Crud` for every non-budget tool, see that function's own
+    /// doc comment). This is the test that fails
     /// immediately if a future edit reintroduces a hand-rolled, independently-drifting copy here
     /// instead of updating (or continuing to call) the shared helper — exactly the failure mode
     /// that shipped once already: MCP's pre-fix copy set only `auth().id`, satisfied the schema's
@@ -2217,7 +2915,7 @@ mod tests {
         assert_eq!(
             ctx.auth_field("rpcScope"),
             Some(&cratestack::Value::String("crud".to_owned())),
-            "MCP must scope its context to RpcScope::Crud (it never dispatches budget:* op-ids)"
+            "MCP's CRUD tools must scope their context to RpcScope::Crud, as authz-api does"
         );
         assert_eq!(
             ctx.auth_field("permAccountRead"),
@@ -2267,6 +2965,43 @@ mod tests {
         Arc::new(AuthzStoreImpl::with_pool(lazy_pool()).with_billing(sample_billing()))
     }
 
+    /// `Procedures` for the budget and session tools over the same unreachable pool, with the
+    /// default rule set loaded in memory (`PolicyStore::from_engine`) instead of from the database.
+    fn lazy_procedures() -> Arc<Procedures> {
+        let pool = lazy_pool();
+        let engine = lightbridge_authz_budget::RuleDataEngine::new(
+            lightbridge_authz_budget::default_rule_set_json(),
+            10_000,
+        )
+        .expect("default rule set is valid");
+        let policy_store = Arc::new(lightbridge_authz_budget::PolicyStore::from_engine(
+            pool.clone(),
+            "budget-refill",
+            engine,
+        ));
+        let budget_repo = Arc::new(lightbridge_authz_budget::repo::BudgetRepo::new(
+            pool.clone(),
+        ));
+        let augmentation_repo = Arc::new(lightbridge_authz_budget::AugmentationRepo::new(pool));
+        let refill_service = Arc::new(lightbridge_authz_budget::RefillService::new(
+            budget_repo.clone(),
+            augmentation_repo.clone(),
+            policy_store.engine(),
+            Arc::new(lightbridge_authz_budget::UnavailableSpendReader),
+        ));
+        let review_service = Arc::new(lightbridge_authz_budget::ReviewService::new(
+            budget_repo.clone(),
+            augmentation_repo,
+        ));
+        Arc::new(Procedures::new(
+            lazy_issuer(),
+            policy_store,
+            refill_service,
+            review_service,
+            budget_repo,
+        ))
+    }
+
     /// Build a handler over the lazy (unreachable) cratestack client + issuer. Sufficient for the
     /// non-DB tests (tool listing, schema shape, billing advertisement, permission mapping) and for
     /// the dead-pool dispatch/error-mapping test; DB-touching tool bodies error at first query.
@@ -2274,10 +3009,12 @@ mod tests {
         LightbridgeMcpHandler::new(
             lazy_cratestack_db(),
             lazy_issuer(),
+            lazy_procedures(),
             sample_repo(),
             basic_auth(),
             &sample_billing(),
             None,
+            Arc::new(ApiKeyUsageTracker::default()),
         )
     }
 
@@ -2303,9 +3040,11 @@ mod tests {
             &sample_billing(),
             lazy_cratestack_db(),
             lazy_issuer(),
+            lazy_procedures(),
             opa_repo,
             Arc::new(MockBearer { token_info }),
             lazy_pool(),
+            Arc::new(ApiKeyUsageTracker::default()),
         )
     }
 
@@ -2401,6 +3140,14 @@ mod tests {
                 "set-project-model-policy",
                 json!({ "project_id": "proj_1", "model_policy": "deny_all" }),
             ),
+            (
+                "get-project-key-lifecycle-policy",
+                json!({ "project_id": "proj_1" }),
+            ),
+            (
+                "set-project-key-lifecycle-policy",
+                json!({ "project_id": "proj_1", "dormant_after_days": 90, "auto_revoke_dormant": true }),
+            ),
             ("list-projects", json!({ "account_id": "acct_1" })),
             ("get-project", json!({ "project_id": "proj_1" })),
             (
@@ -2423,6 +3170,10 @@ mod tests {
                 json!({ "key_id": "key_1", "name": "key2" }),
             ),
             ("revoke-api-key", json!({ "key_id": "key_1" })),
+            (
+                "set-api-key-allowed-cidrs",
+                json!({ "key_id": "key_1", "allowed_cidrs": ["10.0.0.0/8"] }),
+            ),
             (
                 "rotate-api-key",
                 json!({ "key_id": "key_1", "grace_period_seconds": 60 }),
@@ -2430,6 +3181,35 @@ mod tests {
             ("delete-api-key", json!({ "key_id": "key_1" })),
             ("delete-project", json!({ "project_id": "proj_1" })),
             ("delete-account", json!({ "account_id": "acct_1" })),
+            ("get-my-budget-balance", json!({ "period": "2026-10" })),
+            ("list-my-budget-grants", json!({ "period": "2026-10" })),
+            (
+                "request-budget-refill",
+                json!({ "budget_account_id": "acct_1", "account_id": "acct_1", "period": "2026-10", "requested_amount_micros": "5000000" }),
+            ),
+            (
+                "get-my-budget-refill-ladder",
+                json!({ "period": "2026-10" }),
+            ),
+            ("list-pending-augmentation-requests", json!({})),
+            (
+                "approve-augmentation-request",
+                json!({ "request_id": "req_1" }),
+            ),
+            (
+                "reject-augmentation-request",
+                json!({ "request_id": "req_1", "reason": "over budget" }),
+            ),
+            // Simulation never touches the database; malformed rule data is its error path.
+            (
+                "simulate-budget-policy",
+                json!({ "rule_data_json": "not json", "scenario_json": "{}", "requested_amount_micros": "1" }),
+            ),
+            ("revoke-own-sessions", json!({ "reason": "lost laptop" })),
+            (
+                "revoke-subject-sessions",
+                json!({ "account_id": "acct_2", "reason": "offboarded" }),
+            ),
             (
                 "validate-api-key",
                 json!({ "api_key": "lbk_secret_sample" }),
@@ -2487,6 +3267,8 @@ mod tests {
             audience: None,
             signing: None,
             token_exchange: None,
+            device_authorization: None,
+            authorization_code: None,
             rbac: Default::default(),
             clients: Vec::new(),
         }
@@ -2626,16 +3408,19 @@ mod tests {
             "get-account",
             "get-api-key",
             "get-project",
+            "get-project-key-lifecycle-policy",
             "list-accounts",
             "list-api-keys",
             "list-project-roster",
             "list-projects",
             "remove-project-member",
             "revoke-api-key",
+            "set-api-key-allowed-cidrs",
             "rotate-api-key",
             "set-default-project",
             "set-project-allowed-models",
             "set-project-member-quota-tier",
+            "set-project-key-lifecycle-policy",
             "set-project-member-role",
             "set-project-model-policy",
             "set-project-quota",
@@ -2644,6 +3429,16 @@ mod tests {
             "update-project",
             "validate-authorino-api-key",
             "validate-api-key",
+            "get-my-budget-balance",
+            "list-my-budget-grants",
+            "request-budget-refill",
+            "get-my-budget-refill-ladder",
+            "list-pending-augmentation-requests",
+            "approve-augmentation-request",
+            "reject-augmentation-request",
+            "simulate-budget-policy",
+            "revoke-own-sessions",
+            "revoke-subject-sessions",
         ];
         expected.sort();
 
@@ -2684,10 +3479,12 @@ mod tests {
         let handler = LightbridgeMcpHandler::new(
             lazy_cratestack_db(),
             lazy_issuer(),
+            lazy_procedures(),
             sample_repo(),
             basic_auth(),
             &Billing::default(),
             None,
+            Arc::new(ApiKeyUsageTracker::default()),
         );
         let tools = handler.advertised_tools();
         let create = tools
@@ -2724,6 +3521,46 @@ mod tests {
     /// ADR-0006 removed account-level membership and the default-*account* feature. Their tool
     /// names must not linger as mapped permissions, or a stale client would fail open into
     /// `call_tool` rather than being rejected by the fail-closed default.
+    #[test]
+    fn budget_and_session_tools_are_gated_like_their_rpc_procedures() {
+        for (tool, permission) in [
+            ("get-my-budget-balance", Permission::BudgetReadOwn),
+            ("list-my-budget-grants", Permission::BudgetReadOwn),
+            ("request-budget-refill", Permission::BudgetSelfRefill),
+            ("get-my-budget-refill-ladder", Permission::BudgetSelfRefill),
+            (
+                "list-pending-augmentation-requests",
+                Permission::BudgetReview,
+            ),
+            ("approve-augmentation-request", Permission::BudgetReview),
+            ("reject-augmentation-request", Permission::BudgetReview),
+            ("simulate-budget-policy", Permission::BudgetPolicySimulate),
+            ("revoke-own-sessions", Permission::SessionRevokeOwn),
+            ("revoke-subject-sessions", Permission::SessionRevoke),
+        ] {
+            assert_eq!(
+                required_tool_permission(tool),
+                Some(permission),
+                "tool `{tool}` should require {permission:?}"
+            );
+        }
+    }
+
+    /// The budget procedures' `@allow` clauses require `auth().rpcScope == "budget"`; a CRUD-scoped
+    /// context would refuse every budget tool for a caller holding the permission.
+    #[test]
+    fn budget_context_from_token_info_is_budget_scoped() {
+        let ctx = budget_context_from_token_info(&full_access_token_info());
+        assert_eq!(
+            ctx.auth_field("rpcScope"),
+            Some(&cratestack::Value::String("budget".to_owned()))
+        );
+        assert_eq!(
+            ctx.auth_field("permBudgetReadOwn"),
+            Some(&cratestack::Value::Bool(true))
+        );
+    }
+
     #[test]
     fn removed_account_member_tools_are_unmapped() {
         for tool in [
@@ -2884,6 +3721,10 @@ mod tests {
             basic_auth: basic_auth(),
             billing: Arc::new(sample_billing()),
             api_key_audience: None,
+            introspection_cache: None,
+            introspection_budget: None,
+            api_key_hasher: Arc::new(ApiKeyHasher::default()),
+            api_key_usage: Arc::new(ApiKeyUsageTracker::default()),
         });
 
         let result = run_validate_api_key(
@@ -2908,6 +3749,10 @@ mod tests {
             basic_auth: basic_auth(),
             billing: Arc::new(sample_billing()),
             api_key_audience: None,
+            introspection_cache: None,
+            introspection_budget: None,
+            api_key_hasher: Arc::new(ApiKeyHasher::default()),
+            api_key_usage: Arc::new(ApiKeyUsageTracker::default()),
         });
 
         let result = run_validate_authorino(
@@ -25,10 +25,26 @@ pub enum Commands {
         #[arg(long, short, env = "CONFIG_PATH")]
         config_path: String,
     },
+    /// Runs `authz-budget`, or one of its maintenance jobs when a subcommand is given.
+    #[command(subcommand_negates_reqs = true)]
     Budget {
+        #[arg(long, short, env = "CONFIG_PATH", required = true)]
+        config_path: Option<String>,
+        #[command(subcommand)]
+        command: Option<BudgetCommands>,
+    },
+    /// Native Envoy `ext_authz` gRPC listener (`server.extauthz`).
+    #[command(name = "extauthz")]
+    ExtAuthz {
         #[arg(long, short, env = "CONFIG_PATH")]
         config_path: String,
     },
+    /// Maintenance jobs for the API-key signing keys (`signing_keys`).
+    #[command(name = "signing-keys")]
+    SigningKeys {
+        #[command(subcommand)]
+        command: SigningKeyCommands,
+    },
     Config {
         #[arg(long, short, env = "CONFIG_PATH")]
         config_path: String,
@@ -38,3 +54,34 @@ pub enum Commands {
         config_path: String,
     },
 }
+
+#[derive(Subcommand)]
+pub enum BudgetCommands {
+    /// Writes the period's opening `base` budget grant to every active account that has none yet.
+    /// Idempotent; `authz-budget` also runs this in the background.
+    Rollover {
+        #[arg(long, short, env = "CONFIG_PATH")]
+        config_path: String,
+        /// `YYYY-MM`; defaults to the current UTC month.
+        #[arg(long)]
+        period: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SigningKeyCommands {
+    /// Seals every stored private key under `oauth2.signing.key_encryption`'s active KEK: keys
+    /// written before encryption was enabled, and keys still under a previous KEK after a KEK
+    /// rotation. Idempotent and safe while services are running.
+    Reencrypt {
+        #[arg(long, short, env = "CONFIG_PATH")]
+        config_path: String,
+    },
+    /// Revokes the active signing key and starts signing with a fresh one immediately, skipping
+    /// the usual pre-publication lead time. Every token the old key signed stops verifying once
+    /// relying parties refresh the JWKS. For a key that may have leaked.
+    EmergencyRotate {
+        #[arg(long, short, env = "CONFIG_PATH")]
+        config_path: String,
+    },
+}
@@ -8,10 +8,11 @@
 //! of AGENTS.md's "Redis is a mandatory dependency" house rule: `authz-api`, `authz-idp`, and
 //! `authz-budget` all now hard-require `Config.redis`, but `authz-opa` and `lightbridge-mcp` stay
 //! exempt. `start_mcp_server` (unlike `start_api_server`/`start_idp_server`/`start_budget_server`)
-//! doesn't take a `redis` parameter at all, so this proves the whole startup sequence -- billing/
-//! rbac validation, optional signing-key bootstrap, cratestack pool, router assembly, TLS load --
-//! still runs to completion with no Redis configured anywhere, failing only for a reason this test
-//! deliberately induces, never anything Redis-shaped.
+//! takes `redis` as an `Option` -- only used to publish introspection invalidations -- so this
+//! proves the whole startup sequence -- billing/rbac validation, optional signing-key bootstrap,
+//! cratestack pool, router assembly, TLS load -- still runs to completion with no Redis configured
+//! anywhere, failing only for a reason this test deliberately induces, never anything
+//! Redis-shaped.
 
 use std::sync::Arc;
 
@@ -53,6 +54,8 @@ fn external_oauth2() -> Oauth2 {
         audience: None,
         signing: None,
         token_exchange: None,
+        device_authorization: None,
+        authorization_code: None,
         rbac: Default::default(),
         clients: Vec::new(),
     }
@@ -68,11 +71,11 @@ fn sample_billing() -> Billing {
     }
 }
 
-/// `lightbridge-mcp` never needed Redis in the first place -- `start_mcp_server`'s signature has
-/// no `redis` parameter, unlike its `authz-api`/`authz-idp`/`authz-budget` siblings. Whatever this
-/// deliberately-offline call eventually fails on (an unset/unreachable `DATABASE_URL`, or the
-/// bogus TLS cert paths once a database is reachable), it must never be Redis: nothing in this
-/// call graph reads `Config.redis` at all.
+/// `lightbridge-mcp` never needs Redis -- `start_mcp_server`'s `redis` is optional, unlike its
+/// `authz-api`/`authz-idp`/`authz-budget` siblings'. Whatever this deliberately-offline call
+/// eventually fails on (the unreachable pool's budget-policy load, an unset/unreachable
+/// `DATABASE_URL`, or the bogus TLS cert paths once a database is reachable), it must never be
+/// Redis when none is configured.
 #[tokio::test]
 async fn start_mcp_server_runs_without_redis_and_never_fails_on_it() {
     let api = ApiServer {
@@ -94,6 +97,9 @@ async fn start_mcp_server_runs_without_redis_and_never_fails_on_it() {
         &QuotaTiers::default(),
         &ModelCatalog::default(),
         &ApiKeyExpiry::default(),
+        None,
+        None,
+        None,
         lazy_pool(),
     )
     .await;
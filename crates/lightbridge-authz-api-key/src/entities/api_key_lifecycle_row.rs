@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// One `api_key_lifecycle_events` row
+/// (`migrations/20261018000004_api_key_lifecycle_events.sql`).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApiKeyLifecycleEventRow {
+    pub id: i64,
+    pub api_key_id: String,
+    pub project_id: String,
+    /// `expiring`, `dormant`, or `dormant_revoked`.
+    pub event_type: String,
+    pub threshold_days: i32,
+    pub occurs_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub delivery_attempts: i32,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ProjectKeyLifecyclePolicyRow {
+    pub project_id: String,
+    pub dormant_after_days: Option<i32>,
+    pub auto_revoke_dormant: bool,
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sqlx::FromRow;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -16,7 +17,22 @@ pub struct ApiKeyRow {
     pub last_ip: Option<String>,
     pub revoked_at: Option<DateTime<Utc>>,
     pub billing_plan: String,
+    pub allowed_models: Option<Value>,
+    pub scopes: Option<Value>,
+    pub allowed_cidrs: Option<Value>,
     pub updated_at: DateTime<Utc>,
+    pub rotating_until: Option<DateTime<Utc>>,
+    pub successor_id: Option<String>,
+    pub predecessor_id: Option<String>,
+}
+
+/// The latest use of one key, as coalesced in memory and written by
+/// `StoreRepo::record_api_key_usage_batch`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyUsage {
+    pub key_id: String,
+    pub last_used_at: DateTime<Utc>,
+    pub last_ip: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sqlx::FromRow;
 
 /// One row of the `api_key_validation` view (see the migration): the effective validity of an API
@@ -24,4 +25,5 @@ pub struct ApiKeyValidationRow {
     pub account_status: String,
     pub expires_at: Option<DateTime<Utc>>,
     pub effective_status: String,
+    pub allowed_cidrs: Option<Value>,
 }
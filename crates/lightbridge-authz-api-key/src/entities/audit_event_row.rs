@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use lightbridge_authz_core::AuditEvent;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+
+/// One `audit_events` row (`migrations/20261018000005_audit_events.sql`).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditEventRow {
+    pub id: i64,
+    pub actor_subject: String,
+    pub op_id: String,
+    pub request_id: Option<String>,
+    pub target_type: String,
+    pub target_id: String,
+    pub action: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AuditEventRow> for AuditEvent {
+    fn from(row: AuditEventRow) -> Self {
+        Self {
+            id: row.id,
+            actor_subject: row.actor_subject,
+            op_id: row.op_id,
+            request_id: row.request_id,
+            target_type: row.target_type,
+            target_id: row.target_id,
+            action: row.action,
+            before: row.before,
+            after: row.after,
+            created_at: row.created_at,
+        }
+    }
+}
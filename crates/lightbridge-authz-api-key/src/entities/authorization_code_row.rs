@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// A `/authorize` request waiting on the user's Keycloak sign-in (`authorization_requests`). See
+/// the table's migration for what each column means.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuthorizationRequestRow {
+    pub id: String,
+    pub state_hash: String,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scope: String,
+    /// The client's own `state`, echoed back to it verbatim on the final redirect.
+    pub client_state: Option<String>,
+    pub code_challenge: String,
+    pub code_challenge_method: String,
+    pub nonce: Option<String>,
+    pub project_id: Option<String>,
+    /// The PKCE verifier toward Keycloak, spent when the callback redeems Keycloak's code.
+    pub upstream_code_verifier: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A single-use authorization code issued to a registered client (`authorization_codes`).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuthorizationCodeRow {
+    pub id: String,
+    pub code_hash: String,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub code_challenge: String,
+    pub code_challenge_method: String,
+    /// Reflected verbatim into the issued `id_token`; never synthesized.
+    pub nonce: Option<String>,
+    /// Resolved (or defaulted) only when the code is redeemed, exactly like the token endpoint's
+    /// own `project_id` extension.
+    pub project_id: Option<String>,
+    /// The Keycloak `sub` the browser leg authenticated.
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub auth_time: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub consumed_at: Option<DateTime<Utc>>,
+}
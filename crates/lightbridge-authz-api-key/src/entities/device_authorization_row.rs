@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// One RFC 8628 pairing session (`device_authorizations`, ADR-0012 Decision 7). See the table's
+/// migration for what each column means and when it is written.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DeviceAuthorizationRow {
+    pub id: String,
+    pub device_code_hash: String,
+    pub user_code: String,
+    pub client_id: String,
+    pub scope: String,
+    /// The project the client asked to be scoped to on the device-authorization request, if any.
+    /// Resolved (or defaulted) only when the redeeming poll mints, exactly like the token
+    /// endpoint's own `project_id` extension.
+    pub project_id: Option<String>,
+    /// Reflected verbatim into the issued `id_token` (ADR-0012 Decision 6); never synthesized.
+    pub nonce: Option<String>,
+    pub status: String,
+    /// The Keycloak `sub` the browser leg authenticated, set only on approval.
+    pub subject: Option<String>,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub auth_time: Option<i64>,
+    pub verification_code_verifier: Option<String>,
+    pub interval_secs: i32,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_polled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewDeviceAuthorization {
+    pub id: String,
+    pub device_code_hash: String,
+    pub user_code: String,
+    pub client_id: String,
+    pub scope: String,
+    pub project_id: Option<String>,
+    pub nonce: Option<String>,
+    pub interval_secs: i32,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// What the browser leg learned from Keycloak, written onto the row by
+/// `StoreRepo::approve_device_authorization`.
+#[derive(Debug, Clone)]
+pub struct DeviceApproval {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub auth_time: Option<i64>,
+}
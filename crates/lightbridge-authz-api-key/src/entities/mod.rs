@@ -1,6 +1,10 @@
 pub mod account_row;
+pub mod api_key_lifecycle_row;
 pub mod api_key_row;
 pub mod api_key_validation_row;
+pub mod audit_event_row;
+pub mod authorization_code_row;
+pub mod device_authorization_row;
 pub mod exchange_refresh_token_row;
 pub mod new_account_row;
 pub mod new_api_key_row;
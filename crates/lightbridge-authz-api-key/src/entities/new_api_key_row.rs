@@ -15,4 +15,7 @@ pub struct NewApiKeyRow {
     pub last_ip: Option<String>,
     pub revoked_at: Option<DateTime<Utc>>,
     pub billing_plan: String,
+    pub allowed_models: Option<Vec<String>>,
+    pub scopes: Option<Vec<String>>,
+    pub allowed_cidrs: Option<Vec<String>>,
 }
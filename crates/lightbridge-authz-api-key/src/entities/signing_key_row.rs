@@ -6,11 +6,17 @@ use sqlx::FromRow;
 pub struct SigningKeyRow {
     pub kid: String,
     pub algorithm: String,
+    /// PKCS#8 PEM, or its sealed envelope when `oauth2.signing.key_encryption` is configured
+    /// (`lightbridge_authz_rest::key_encryption`).
     pub private_key_pem: String,
     pub public_jwk: serde_json::Value,
+    /// `pending`, `active`, `stale`, or `revoked`
+    /// (`migrations/20261018000001_signing_keys_pending_and_revoked.sql`).
     pub status: String,
     pub created_at: DateTime<Utc>,
     pub retired_at: Option<DateTime<Utc>>,
+    /// When a `pending` key starts signing; `None` for keys that were never pending.
+    pub activates_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone)]
@@ -1,20 +1,29 @@
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
+use lightbridge_authz_core::audit;
 use lightbridge_authz_core::db::DbPoolTrait;
 use lightbridge_authz_core::error::{Error, Result};
 use lightbridge_authz_core::{
-    Account, ApiKey, ApiKeyStatus, ApiKeyValidation, CreateAccount, CreateProject, DefaultLimits,
-    ModelPolicy, Project, ProjectMember, ResolvedContext, ResourceStatus, UpdateAccount,
-    UpdateApiKey, UpdateProject,
+    Account, ApiKey, ApiKeyStatus, ApiKeyValidation, AuditEvent, AuditEventFilter, CreateAccount,
+    CreateProject, DefaultLimits, ModelPolicy, Project, ProjectKeyLifecyclePolicy, ProjectMember,
+    ResolvedContext, ResourceStatus, UpdateAccount, UpdateApiKey, UpdateProject,
 };
 use serde_json::Value;
 use sqlx::{PgPool, Postgres, Transaction};
 use tracing::instrument;
 
 use crate::entities::account_row::AccountRow;
-use crate::entities::api_key_row::{ApiKeyChangeset, ApiKeyRow};
+use crate::entities::api_key_lifecycle_row::{
+    ApiKeyLifecycleEventRow, ProjectKeyLifecyclePolicyRow,
+};
+use crate::entities::api_key_row::{ApiKeyChangeset, ApiKeyRow, ApiKeyUsage};
 use crate::entities::api_key_validation_row::ApiKeyValidationRow;
+use crate::entities::audit_event_row::AuditEventRow;
+use crate::entities::authorization_code_row::{AuthorizationCodeRow, AuthorizationRequestRow};
+use crate::entities::device_authorization_row::{
+    DeviceApproval, DeviceAuthorizationRow, NewDeviceAuthorization,
+};
 use crate::entities::exchange_refresh_token_row::{
     ExchangeRefreshTokenRow, NewExchangeRefreshToken,
 };
@@ -25,6 +34,9 @@ use crate::entities::project_member_row::ProjectMemberRow;
 use crate::entities::project_row::{ProjectChangeset, ProjectRow};
 use crate::entities::signing_key_row::{NewSigningKey, SigningKeyRow};
 
+/// The hand-written sqlx behind the authz procedures. Every write backing a mutation procedure runs
+/// in a transaction opened with [`audit::begin`], so the `audit_events` trigger can attribute it
+/// to the procedure call that made it (see `lightbridge_authz_core::audit`).
 #[derive(Debug, Clone)]
 pub struct StoreRepo {
     pub pool: Arc<dyn DbPoolTrait>,
@@ -220,7 +232,13 @@ impl StoreRepo {
             last_ip: row.last_ip,
             revoked_at: row.revoked_at,
             billing_plan: row.billing_plan,
+            allowed_models: Self::json_to_vec(&row.allowed_models),
+            scopes: Self::json_to_vec(&row.scopes),
+            allowed_cidrs: Self::json_to_vec(&row.allowed_cidrs),
             updated_at: row.updated_at,
+            rotating_until: row.rotating_until,
+            successor_id: row.successor_id,
+            predecessor_id: row.predecessor_id,
         }
     }
 
@@ -239,6 +257,7 @@ impl StoreRepo {
             updated_at: now,
         };
 
+        let mut tx = audit::begin(self.pool()).await?;
         sqlx::query(
             r#"
             INSERT INTO accounts (id, default_quota, created_at, updated_at)
@@ -249,7 +268,7 @@ impl StoreRepo {
         .bind(new_account.default_quota.clone())
         .bind(new_account.created_at)
         .bind(new_account.updated_at)
-        .execute(self.pool())
+        .execute(&mut *tx)
         .await
         .map_err(|e| {
             if let sqlx::Error::Database(db_err) = &e
@@ -259,6 +278,7 @@ impl StoreRepo {
             }
             Error::from(e)
         })?;
+        tx.commit().await?;
 
         let account = self.load_account_row(&new_account.id).await?;
         Ok(Self::to_account(account))
@@ -352,6 +372,7 @@ impl StoreRepo {
     /// here.
     #[instrument(skip(self))]
     pub async fn delete_account(&self, subject: &str, account_id: &str) -> Result<Account> {
+        let mut tx = audit::begin(self.pool()).await?;
         let row: Option<AccountRow> = sqlx::query_as(
             r#"
             DELETE FROM accounts
@@ -361,8 +382,9 @@ impl StoreRepo {
         )
         .bind(account_id)
         .bind(subject)
-        .fetch_optional(self.pool())
+        .fetch_optional(&mut *tx)
         .await?;
+        tx.commit().await?;
         let row = row.ok_or(Error::NotFound)?;
         Ok(Self::to_account(row))
     }
@@ -384,6 +406,7 @@ impl StoreRepo {
         Self::validate_project_role(role)?;
         self.authorize_project_lead(project_id, subject).await?;
 
+        let mut tx = audit::begin(self.pool()).await?;
         sqlx::query(
             r#"
             INSERT INTO project_members (project_id, account_id, role)
@@ -394,8 +417,9 @@ impl StoreRepo {
         .bind(project_id)
         .bind(target_account_id)
         .bind(role)
-        .execute(self.pool())
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         let project = self.get_project_by_id(project_id).await?;
         project.ok_or(Error::NotFound)
@@ -468,11 +492,13 @@ impl StoreRepo {
     ) -> Result<Project> {
         self.authorize_project_lead(project_id, subject).await?;
 
+        let mut tx = audit::begin(self.pool()).await?;
         sqlx::query(r#"DELETE FROM project_members WHERE project_id = $1 AND account_id = $2"#)
             .bind(project_id)
             .bind(target_account_id)
-            .execute(self.pool())
+            .execute(&mut *tx)
             .await?;
+        tx.commit().await?;
 
         let project = self.get_project_by_id(project_id).await?;
         project.ok_or(Error::NotFound)
@@ -495,14 +521,16 @@ impl StoreRepo {
         Self::validate_project_role(role)?;
         self.authorize_project_lead(project_id, subject).await?;
 
+        let mut tx = audit::begin(self.pool()).await?;
         let result = sqlx::query(
             r#"UPDATE project_members SET role = $1 WHERE project_id = $2 AND account_id = $3"#,
         )
         .bind(role)
         .bind(project_id)
         .bind(target_account_id)
-        .execute(self.pool())
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
         if result.rows_affected() == 0 {
             return Err(Error::NotFound);
         }
@@ -532,14 +560,16 @@ impl StoreRepo {
     ) -> Result<Project> {
         self.authorize_project_lead(project_id, subject).await?;
 
+        let mut tx = audit::begin(self.pool()).await?;
         let result = sqlx::query(
             r#"UPDATE project_members SET quota_tier = $1 WHERE project_id = $2 AND account_id = $3"#,
         )
         .bind(quota_tier)
         .bind(project_id)
         .bind(target_account_id)
-        .execute(self.pool())
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
         if result.rows_affected() == 0 {
             return Err(Error::NotFound);
         }
@@ -909,6 +939,7 @@ impl StoreRepo {
         &self,
         subject: &str,
     ) -> Result<u64> {
+        let mut tx = audit::begin(self.pool()).await?;
         let result = sqlx::query(
             r#"
             UPDATE exchange_refresh_tokens
@@ -918,11 +949,375 @@ impl StoreRepo {
             "#,
         )
         .bind(subject)
-        .execute(self.pool())
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(result.rows_affected())
     }
 
+    pub async fn create_device_authorization(
+        &self,
+        input: NewDeviceAuthorization,
+    ) -> Result<DeviceAuthorizationRow> {
+        let row: DeviceAuthorizationRow = sqlx::query_as(
+            r#"
+            INSERT INTO device_authorizations
+              (id, device_code_hash, user_code, client_id, scope, project_id, nonce, status, interval_secs, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, 'pending', $8, $9, $10)
+            RETURNING id, device_code_hash, user_code, client_id, scope, project_id, nonce, status, subject, email, email_verified, auth_time, verification_code_verifier, interval_secs, created_at, expires_at, last_polled_at
+            "#,
+        )
+        .bind(input.id)
+        .bind(input.device_code_hash)
+        .bind(input.user_code)
+        .bind(input.client_id)
+        .bind(input.scope)
+        .bind(input.project_id)
+        .bind(input.nonce)
+        .bind(input.interval_secs)
+        .bind(input.created_at)
+        .bind(input.expires_at)
+        .fetch_one(self.pool())
+        .await?;
+        Ok(row)
+    }
+
+    /// Unconditional lookup by device-code hash -- no `status`/`expires_at` filter, so the polling
+    /// grant can tell "expired" (`expired_token`) and "issued to another client" apart from "no
+    /// such code" (`invalid_grant`). Never used to decide whether a code may be redeemed; that is
+    /// [`Self::consume_device_authorization`]'s CAS alone.
+    pub async fn find_device_authorization_by_code_hash(
+        &self,
+        device_code_hash: &str,
+    ) -> Result<Option<DeviceAuthorizationRow>> {
+        let row = sqlx::query_as(
+            r#"
+            SELECT id, device_code_hash, user_code, client_id, scope, project_id, nonce, status, subject, email, email_verified, auth_time, verification_code_verifier, interval_secs, created_at, expires_at, last_polled_at
+            FROM device_authorizations
+            WHERE device_code_hash = $1
+            "#,
+        )
+        .bind(device_code_hash)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row)
+    }
+
+    /// The still-`pending`, unexpired session a user code names -- the only state the
+    /// verification page ever acts on. `user_code` must already be normalised by the caller.
+    pub async fn find_pending_device_authorization_by_user_code(
+        &self,
+        user_code: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>> {
+        let row = sqlx::query_as(
+            r#"
+            SELECT id, device_code_hash, user_code, client_id, scope, project_id, nonce, status, subject, email, email_verified, auth_time, verification_code_verifier, interval_secs, created_at, expires_at, last_polled_at
+            FROM device_authorizations
+            WHERE user_code = $1
+              AND status = 'pending'
+              AND expires_at > $2
+            "#,
+        )
+        .bind(user_code)
+        .bind(now)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row)
+    }
+
+    /// Stamps a poll on a still-`pending` session and returns the poll time it replaced (`None`
+    /// inside the `Some` on the first poll), so the `slow_down` check compares against a value no
+    /// concurrent poll can have overwritten in between. `Ok(None)` when the row is no longer
+    /// `pending` -- approved, denied, or redeemed since the caller read it.
+    pub async fn record_device_authorization_poll(
+        &self,
+        device_code_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Option<DateTime<Utc>>>> {
+        let previous: Option<Option<DateTime<Utc>>> = sqlx::query_scalar(
+            r#"
+            UPDATE device_authorizations d
+            SET last_polled_at = $2
+            FROM (
+              SELECT id, last_polled_at
+              FROM device_authorizations
+              WHERE device_code_hash = $1
+                AND status = 'pending'
+              FOR UPDATE
+            ) previous
+            WHERE d.id = previous.id
+            RETURNING previous.last_polled_at
+            "#,
+        )
+        .bind(device_code_hash)
+        .bind(now)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(previous)
+    }
+
+    /// The user confirmed `user_code` at the verification page: records the browser leg's
+    /// `state` (hashed) and PKCE verifier toward Keycloak on the still-`pending` session. A second
+    /// confirmation overwrites both, so only the most recent Keycloak round trip can approve.
+    pub async fn begin_device_verification(
+        &self,
+        user_code: &str,
+        state_hash: &str,
+        code_verifier: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>> {
+        let row = sqlx::query_as(
+            r#"
+            UPDATE device_authorizations
+            SET verification_state_hash = $2, verification_code_verifier = $3
+            WHERE user_code = $1
+              AND status = 'pending'
+              AND expires_at > $4
+            RETURNING id, device_code_hash, user_code, client_id, scope, project_id, nonce, status, subject, email, email_verified, auth_time, verification_code_verifier, interval_secs, created_at, expires_at, last_polled_at
+            "#,
+        )
+        .bind(user_code)
+        .bind(state_hash)
+        .bind(code_verifier)
+        .bind(now)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row)
+    }
+
+    /// The still-`pending`, unexpired session a Keycloak callback's `state` belongs to.
+    pub async fn find_pending_device_authorization_by_state(
+        &self,
+        state_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>> {
+        let row = sqlx::query_as(
+            r#"
+            SELECT id, device_code_hash, user_code, client_id, scope, project_id, nonce, status, subject, email, email_verified, auth_time, verification_code_verifier, interval_secs, created_at, expires_at, last_polled_at
+            FROM device_authorizations
+            WHERE verification_state_hash = $1
+              AND status = 'pending'
+              AND expires_at > $2
+            "#,
+        )
+        .bind(state_hash)
+        .bind(now)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row)
+    }
+
+    /// Flips a `pending` session to `approved` with the Keycloak identity the browser leg
+    /// resolved. Keyed on the callback's `state` and a compare-and-swap on `status = 'pending'`,
+    /// and clears the state/verifier in the same statement, so a replayed callback finds nothing
+    /// to approve. `Ok(None)` when the session expired, was denied, or was already approved.
+    pub async fn approve_device_authorization(
+        &self,
+        state_hash: &str,
+        approval: DeviceApproval,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>> {
+        let row = sqlx::query_as(
+            r#"
+            UPDATE device_authorizations
+            SET status = 'approved',
+                subject = $2,
+                email = $3,
+                email_verified = $4,
+                auth_time = $5,
+                verification_state_hash = NULL,
+                verification_code_verifier = NULL
+            WHERE verification_state_hash = $1
+              AND status = 'pending'
+              AND expires_at > $6
+            RETURNING id, device_code_hash, user_code, client_id, scope, project_id, nonce, status, subject, email, email_verified, auth_time, verification_code_verifier, interval_secs, created_at, expires_at, last_polled_at
+            "#,
+        )
+        .bind(state_hash)
+        .bind(approval.subject)
+        .bind(approval.email)
+        .bind(approval.email_verified)
+        .bind(approval.auth_time)
+        .bind(now)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row)
+    }
+
+    /// The user refused `user_code` at the verification page. Same `pending`-only CAS as
+    /// [`Self::approve_device_authorization`]; the next poll redeems the refusal as
+    /// `access_denied`.
+    pub async fn deny_device_authorization(
+        &self,
+        user_code: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>> {
+        let row = sqlx::query_as(
+            r#"
+            UPDATE device_authorizations
+            SET status = 'denied',
+                verification_state_hash = NULL,
+                verification_code_verifier = NULL
+            WHERE user_code = $1
+              AND status = 'pending'
+              AND expires_at > $2
+            RETURNING id, device_code_hash, user_code, client_id, scope, project_id, nonce, status, subject, email, email_verified, auth_time, verification_code_verifier, interval_secs, created_at, expires_at, last_polled_at
+            "#,
+        )
+        .bind(user_code)
+        .bind(now)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row)
+    }
+
+    /// Atomically redeems a decided session exactly once (ADR-0012 Decision 7): claims the
+    /// `approved`/`denied` row with `SELECT ... FOR UPDATE`, flips it to `consumed`, and returns it
+    /// carrying the status it was claimed in -- so the caller still knows whether to mint or to
+    /// answer `access_denied`. Two concurrent polls with the same device code serialize on the row
+    /// lock; the second re-evaluates the `status IN (...)` filter after the first commits and
+    /// claims nothing. Expired rows are never claimed.
+    pub async fn consume_device_authorization(
+        &self,
+        device_code_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>> {
+        let row = sqlx::query_as(
+            r#"
+            WITH claimed AS (
+              SELECT id, status
+              FROM device_authorizations
+              WHERE device_code_hash = $1
+                AND status IN ('approved', 'denied')
+                AND expires_at > $2
+              FOR UPDATE
+            )
+            UPDATE device_authorizations d
+            SET status = 'consumed', last_polled_at = $2
+            FROM claimed
+            WHERE d.id = claimed.id
+            RETURNING d.id, d.device_code_hash, d.user_code, d.client_id, d.scope, d.project_id, d.nonce, claimed.status, d.subject, d.email, d.email_verified, d.auth_time, d.verification_code_verifier, d.interval_secs, d.created_at, d.expires_at, d.last_polled_at
+            "#,
+        )
+        .bind(device_code_hash)
+        .bind(now)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row)
+    }
+
+    /// Deletes a session outright, whatever its state. A no-op (not an error) when nothing
+    /// matches.
+    pub async fn delete_device_authorization(&self, device_code_hash: &str) -> Result<()> {
+        sqlx::query(r#"DELETE FROM device_authorizations WHERE device_code_hash = $1"#)
+            .bind(device_code_hash)
+            .execute(self.pool())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn create_authorization_request(&self, input: AuthorizationRequestRow) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO authorization_requests
+              (id, state_hash, client_id, redirect_uri, scope, client_state, code_challenge, code_challenge_method, nonce, project_id, upstream_code_verifier, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            "#,
+        )
+        .bind(input.id)
+        .bind(input.state_hash)
+        .bind(input.client_id)
+        .bind(input.redirect_uri)
+        .bind(input.scope)
+        .bind(input.client_state)
+        .bind(input.code_challenge)
+        .bind(input.code_challenge_method)
+        .bind(input.nonce)
+        .bind(input.project_id)
+        .bind(input.upstream_code_verifier)
+        .bind(input.created_at)
+        .bind(input.expires_at)
+        .execute(self.pool())
+        .await?;
+        Ok(())
+    }
+
+    /// Takes the pending `/authorize` request a Keycloak callback's `state` names, deleting it in
+    /// the same statement so a replayed callback finds nothing. `Ok(None)` when no such request
+    /// exists or it has expired (an expired row is deleted all the same).
+    pub async fn take_authorization_request(
+        &self,
+        state_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<AuthorizationRequestRow>> {
+        let row: Option<AuthorizationRequestRow> = sqlx::query_as(
+            r#"
+            DELETE FROM authorization_requests
+            WHERE state_hash = $1
+            RETURNING id, state_hash, client_id, redirect_uri, scope, client_state, code_challenge, code_challenge_method, nonce, project_id, upstream_code_verifier, created_at, expires_at
+            "#,
+        )
+        .bind(state_hash)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row.filter(|r| r.expires_at > now))
+    }
+
+    pub async fn create_authorization_code(&self, input: AuthorizationCodeRow) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO authorization_codes
+              (id, code_hash, client_id, redirect_uri, scope, code_challenge, code_challenge_method, nonce, project_id, subject, email, email_verified, auth_time, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            "#,
+        )
+        .bind(input.id)
+        .bind(input.code_hash)
+        .bind(input.client_id)
+        .bind(input.redirect_uri)
+        .bind(input.scope)
+        .bind(input.code_challenge)
+        .bind(input.code_challenge_method)
+        .bind(input.nonce)
+        .bind(input.project_id)
+        .bind(input.subject)
+        .bind(input.email)
+        .bind(input.email_verified)
+        .bind(input.auth_time)
+        .bind(input.created_at)
+        .bind(input.expires_at)
+        .execute(self.pool())
+        .await?;
+        Ok(())
+    }
+
+    /// Redeems an authorization code exactly once: a compare-and-swap on `consumed_at IS NULL`
+    /// that also refuses an expired code. Two concurrent redemptions of the same code serialize
+    /// on the row; the second finds `consumed_at` already set and claims nothing. The consumed row
+    /// is kept (not deleted) so the audit trail of who redeemed what survives.
+    pub async fn consume_authorization_code(
+        &self,
+        code_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<AuthorizationCodeRow>> {
+        let row = sqlx::query_as(
+            r#"
+            UPDATE authorization_codes
+            SET consumed_at = $2
+            WHERE code_hash = $1
+              AND consumed_at IS NULL
+              AND expires_at > $2
+            RETURNING id, code_hash, client_id, redirect_uri, scope, code_challenge, code_challenge_method, nonce, project_id, subject, email, email_verified, auth_time, created_at, expires_at, consumed_at
+            "#,
+        )
+        .bind(code_hash)
+        .bind(now)
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row)
+    }
+
     /// Project-scoped rule (see the module-level mechanical rescoping this whole file follows):
     /// visible when `subject` owns the project's account OR holds ANY `project_members` row on it,
     /// matching the schema's `@@allow("read", account.id==auth().id || members.some.accountId==
@@ -1152,16 +1547,19 @@ impl StoreRepo {
     pub async fn create_api_key(&self, subject: &str, input: NewApiKeyRow) -> Result<ApiKey> {
         self.authorize_project_lead(&input.project_id, subject)
             .await?;
+        let mut tx = audit::begin(self.pool()).await?;
         let row: ApiKeyRow = sqlx::query_as(
             r#"
             INSERT INTO api_keys (
               id, project_id, name, key_prefix, key_hash, created_at, expires_at, status,
-              last_used_at, last_ip, revoked_at, billing_plan, owner_account_id
+              last_used_at, last_ip, revoked_at, billing_plan, owner_account_id, allowed_models,
+              scopes, allowed_cidrs
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
             RETURNING
               id, project_id, name, key_prefix, key_hash, created_at, expires_at, status,
-              last_used_at, last_ip, revoked_at, billing_plan, updated_at
+              last_used_at, last_ip, revoked_at, billing_plan, allowed_models, scopes, allowed_cidrs, updated_at,
+              rotating_until, successor_id, predecessor_id
             "#,
         )
         .bind(input.id)
@@ -1179,8 +1577,12 @@ impl StoreRepo {
         // The acting subject, not the project's owning account: a lead who is not the owner may
         // mint keys, and it is THEIR per-member ceiling that should bound the key.
         .bind(subject)
-        .fetch_one(self.pool())
+        .bind(Self::vec_to_json(&input.allowed_models))
+        .bind(Self::vec_to_json(&input.scopes))
+        .bind(Self::vec_to_json(&input.allowed_cidrs))
+        .fetch_one(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(Self::to_api_key(row))
     }
 
@@ -1208,7 +1610,13 @@ impl StoreRepo {
               api_keys.last_ip,
               api_keys.revoked_at,
               api_keys.billing_plan,
-              api_keys.updated_at
+              api_keys.allowed_models,
+              api_keys.scopes,
+              api_keys.allowed_cidrs,
+              api_keys.updated_at,
+              api_keys.rotating_until,
+              api_keys.successor_id,
+              api_keys.predecessor_id
             FROM api_keys
             JOIN projects ON projects.id = api_keys.project_id
             WHERE api_keys.project_id = $1
@@ -1250,7 +1658,13 @@ impl StoreRepo {
               api_keys.last_ip,
               api_keys.revoked_at,
               api_keys.billing_plan,
-              api_keys.updated_at
+              api_keys.allowed_models,
+              api_keys.scopes,
+              api_keys.allowed_cidrs,
+              api_keys.updated_at,
+              api_keys.rotating_until,
+              api_keys.successor_id,
+              api_keys.predecessor_id
             FROM api_keys
             JOIN projects ON projects.id = api_keys.project_id
             WHERE api_keys.id = $1
@@ -1303,7 +1717,8 @@ impl StoreRepo {
               )
             RETURNING
               api_keys.id, api_keys.project_id, api_keys.name, api_keys.key_prefix, api_keys.key_hash, api_keys.created_at, api_keys.expires_at, api_keys.status,
-              api_keys.last_used_at, api_keys.last_ip, api_keys.revoked_at, api_keys.billing_plan, api_keys.updated_at
+              api_keys.last_used_at, api_keys.last_ip, api_keys.revoked_at, api_keys.billing_plan, api_keys.allowed_models, api_keys.scopes, api_keys.allowed_cidrs, api_keys.updated_at,
+              api_keys.rotating_until, api_keys.successor_id, api_keys.predecessor_id
             "#,
         )
         .bind(changes.name)
@@ -1327,6 +1742,7 @@ impl StoreRepo {
         account_id: &str,
         status: ResourceStatus,
     ) -> Result<Account> {
+        let mut tx = audit::begin(self.pool()).await?;
         let row: Option<AccountRow> = sqlx::query_as(
             r#"
             UPDATE accounts
@@ -1339,8 +1755,9 @@ impl StoreRepo {
         .bind(Utc::now())
         .bind(account_id)
         .bind(subject)
-        .fetch_optional(self.pool())
+        .fetch_optional(&mut *tx)
         .await?;
+        tx.commit().await?;
         let row = row.ok_or(Error::NotFound)?;
         Ok(Self::to_account(row))
     }
@@ -1355,6 +1772,7 @@ impl StoreRepo {
         project_id: &str,
         status: ResourceStatus,
     ) -> Result<Project> {
+        let mut tx = audit::begin(self.pool()).await?;
         let row: Option<ProjectRow> = sqlx::query_as(
             r#"
             UPDATE projects
@@ -1387,8 +1805,9 @@ impl StoreRepo {
         .bind(Utc::now())
         .bind(project_id)
         .bind(subject)
-        .fetch_optional(self.pool())
+        .fetch_optional(&mut *tx)
         .await?;
+        tx.commit().await?;
         let row = row.ok_or(Error::NotFound)?;
         Ok(Self::to_project(row))
     }
@@ -1411,6 +1830,7 @@ impl StoreRepo {
         account_id: &str,
         default_quota: Option<&str>,
     ) -> Result<Account> {
+        let mut tx = audit::begin(self.pool()).await?;
         let row: Option<AccountRow> = sqlx::query_as(
             r#"
             UPDATE accounts
@@ -1423,8 +1843,9 @@ impl StoreRepo {
         .bind(Utc::now())
         .bind(account_id)
         .bind(subject)
-        .fetch_optional(self.pool())
+        .fetch_optional(&mut *tx)
         .await?;
+        tx.commit().await?;
         let row = row.ok_or(Error::NotFound)?;
         Ok(Self::to_account(row))
     }
@@ -1445,6 +1866,7 @@ impl StoreRepo {
         project_id: &str,
         project_quota: Option<&str>,
     ) -> Result<Project> {
+        let mut tx = audit::begin(self.pool()).await?;
         let row: Option<ProjectRow> = sqlx::query_as(
             r#"
             UPDATE projects
@@ -1477,8 +1899,9 @@ impl StoreRepo {
         .bind(Utc::now())
         .bind(project_id)
         .bind(subject)
-        .fetch_optional(self.pool())
+        .fetch_optional(&mut *tx)
         .await?;
+        tx.commit().await?;
         let row = row.ok_or(Error::NotFound)?;
         Ok(Self::to_project(row))
     }
@@ -1498,6 +1921,7 @@ impl StoreRepo {
         allowed_models: Option<Vec<String>>,
     ) -> Result<Project> {
         let allowed_models_json = Self::vec_to_json(&allowed_models);
+        let mut tx = audit::begin(self.pool()).await?;
         let row: Option<ProjectRow> = sqlx::query_as(
             r#"
             UPDATE projects
@@ -1530,8 +1954,9 @@ impl StoreRepo {
         .bind(Utc::now())
         .bind(project_id)
         .bind(subject)
-        .fetch_optional(self.pool())
+        .fetch_optional(&mut *tx)
         .await?;
+        tx.commit().await?;
         let row = row.ok_or(Error::NotFound)?;
         Ok(Self::to_project(row))
     }
@@ -1559,7 +1984,7 @@ impl StoreRepo {
         project_id: &str,
         model_policy: &str,
     ) -> Result<Project> {
-        let mut tx: Transaction<'_, Postgres> = self.pool().begin().await?;
+        let mut tx: Transaction<'_, Postgres> = audit::begin(self.pool()).await?;
 
         let current: Option<ProjectRow> = sqlx::query_as(
             r#"
@@ -1641,24 +2066,23 @@ impl StoreRepo {
         Ok(Self::to_project(row))
     }
 
-    /// Promote `project_id` to be its account's new default project, atomically demoting whichever
-    /// project is currently default for that account. Relies on `projects_account_id_default_uidx`
-    /// (a partial unique index on `(account_id) WHERE is_default`) to guarantee the invariant even
-    /// under a race -- a concurrent reassignment targeting a different project for the same account
-    /// fails the unset-then-set with a unique-violation instead of silently producing two defaults.
-    /// Project-scoped rule, same as `set_project_status`: the project's account owner or ANY
-    /// `project_members` row authorizes this; a non-authorized subject or unknown project is
-    /// `NotFound`. (The deleted `set_default_account` had no such column left to reassign at all --
-    /// ADR-0006 dropped `accounts.is_default` outright once one subject could only ever have one
-    /// account, so "default account" stopped being a meaningful concept.)
+    /// `project_id`'s dormant-key policy, or the defaults for a project that never set one.
+    /// Visible to the project's account owner or any roster member, like `get_project`; anyone
+    /// else, or an unknown project, is `NotFound`.
     #[instrument(skip(self))]
-    pub async fn set_default_project(&self, subject: &str, project_id: &str) -> Result<Project> {
-        let mut tx: Transaction<'_, Postgres> = self.pool().begin().await?;
-
-        let account_id: Option<String> = sqlx::query_scalar(
+    pub async fn get_project_key_lifecycle_policy(
+        &self,
+        subject: &str,
+        project_id: &str,
+    ) -> Result<ProjectKeyLifecyclePolicy> {
+        let row: Option<ProjectKeyLifecyclePolicyRow> = sqlx::query_as(
             r#"
-            SELECT projects.account_id
+            SELECT
+              projects.id AS project_id,
+              pol.dormant_after_days,
+              COALESCE(pol.auto_revoke_dormant, false) AS auto_revoke_dormant
             FROM projects
+            LEFT JOIN project_key_lifecycle_policies pol ON pol.project_id = projects.id
             WHERE projects.id = $1
               AND (
                 projects.account_id = $2
@@ -1671,15 +2095,107 @@ impl StoreRepo {
         )
         .bind(project_id)
         .bind(subject)
-        .fetch_optional(&mut *tx)
+        .fetch_optional(self.pool())
         .await?;
-        let account_id = account_id.ok_or(Error::NotFound)?;
+        let row = row.ok_or(Error::NotFound)?;
+        Ok(Self::to_project_key_lifecycle_policy(row))
+    }
 
-        sqlx::query(
+    /// Replaces `project_id`'s dormant-key policy. Project-scoped rule, identical to
+    /// `set_project_quota` (owner or any roster member; otherwise `NotFound`). `dormant_after_days`
+    /// is validated positive by `AuthzStoreImpl::set_project_key_lifecycle_policy` first.
+    #[instrument(skip(self))]
+    pub async fn set_project_key_lifecycle_policy(
+        &self,
+        subject: &str,
+        project_id: &str,
+        dormant_after_days: Option<i32>,
+        auto_revoke_dormant: bool,
+    ) -> Result<ProjectKeyLifecyclePolicy> {
+        let mut tx = audit::begin(self.pool()).await?;
+        let row: Option<ProjectKeyLifecyclePolicyRow> = sqlx::query_as(
             r#"
-            UPDATE projects
-            SET is_default = false, updated_at = $1
-            WHERE account_id = $2 AND is_default = true AND id != $3
+            INSERT INTO project_key_lifecycle_policies
+              (project_id, dormant_after_days, auto_revoke_dormant, updated_at)
+            SELECT projects.id, $1, $2, $3
+            FROM projects
+            WHERE projects.id = $4
+              AND (
+                projects.account_id = $5
+                OR EXISTS (
+                  SELECT 1 FROM project_members pm
+                  WHERE pm.project_id = projects.id AND pm.account_id = $5
+                )
+              )
+            ON CONFLICT (project_id) DO UPDATE
+            SET
+              dormant_after_days = EXCLUDED.dormant_after_days,
+              auto_revoke_dormant = EXCLUDED.auto_revoke_dormant,
+              updated_at = EXCLUDED.updated_at
+            RETURNING project_id, dormant_after_days, auto_revoke_dormant
+            "#,
+        )
+        .bind(dormant_after_days)
+        .bind(auto_revoke_dormant)
+        .bind(Utc::now())
+        .bind(project_id)
+        .bind(subject)
+        .fetch_optional(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        let row = row.ok_or(Error::NotFound)?;
+        Ok(Self::to_project_key_lifecycle_policy(row))
+    }
+
+    fn to_project_key_lifecycle_policy(
+        row: ProjectKeyLifecyclePolicyRow,
+    ) -> ProjectKeyLifecyclePolicy {
+        ProjectKeyLifecyclePolicy {
+            project_id: row.project_id,
+            dormant_after_days: row.dormant_after_days,
+            auto_revoke_dormant: row.auto_revoke_dormant,
+        }
+    }
+
+    /// Promote `project_id` to be its account's new default project, atomically demoting whichever
+    /// project is currently default for that account. Relies on `projects_account_id_default_uidx`
+    /// (a partial unique index on `(account_id) WHERE is_default`) to guarantee the invariant even
+    /// under a race -- a concurrent reassignment targeting a different project for the same account
+    /// fails the unset-then-set with a unique-violation instead of silently producing two defaults.
+    /// Project-scoped rule, same as `set_project_status`: the project's account owner or ANY
+    /// `project_members` row authorizes this; a non-authorized subject or unknown project is
+    /// `NotFound`. (The deleted `set_default_account` had no such column left to reassign at all --
+    /// ADR-0006 dropped `accounts.is_default` outright once one subject could only ever have one
+    /// account, so "default account" stopped being a meaningful concept.)
+    #[instrument(skip(self))]
+    pub async fn set_default_project(&self, subject: &str, project_id: &str) -> Result<Project> {
+        let mut tx: Transaction<'_, Postgres> = audit::begin(self.pool()).await?;
+
+        let account_id: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT projects.account_id
+            FROM projects
+            WHERE projects.id = $1
+              AND (
+                projects.account_id = $2
+                OR EXISTS (
+                  SELECT 1 FROM project_members pm
+                  WHERE pm.project_id = projects.id AND pm.account_id = $2
+                )
+              )
+            "#,
+        )
+        .bind(project_id)
+        .bind(subject)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let account_id = account_id.ok_or(Error::NotFound)?;
+
+        sqlx::query(
+            r#"
+            UPDATE projects
+            SET is_default = false, updated_at = $1
+            WHERE account_id = $2 AND is_default = true AND id != $3
             "#,
         )
         .bind(Utc::now())
@@ -1708,10 +2224,14 @@ impl StoreRepo {
 
     /// Read the effective validity of an API key from the `api_key_validation` view (one indexed
     /// lookup by `key_hash`), with the account -> project -> key status cascade resolved by the DB.
-    #[instrument(skip(self, key_hash))]
+    ///
+    /// `key_hashes` are the hashes the presented secret may be stored under, most current scheme
+    /// first (`ApiKeyHasher::candidates`); the row matching the earliest one wins. Its `key_hash`
+    /// tells the caller which scheme matched, and so whether to [`Self::upgrade_api_key_hash`].
+    #[instrument(skip(self, key_hashes))]
     pub async fn find_api_key_validation_by_hash(
         &self,
-        key_hash: &str,
+        key_hashes: &[String],
     ) -> Result<Option<ApiKeyValidation>> {
         let row: Option<ApiKeyValidationRow> = sqlx::query_as(
             r#"
@@ -1727,15 +2247,79 @@ impl StoreRepo {
               project_status,
               account_status,
               expires_at,
-              effective_status
+              effective_status,
+              allowed_cidrs
             FROM api_key_validation
-            WHERE key_hash = $1
+            WHERE key_hash = ANY($1)
+            ORDER BY array_position($1, key_hash)
+            LIMIT 1
             "#,
         )
-        .bind(key_hash)
+        .bind(key_hashes)
         .fetch_optional(self.pool())
         .await?;
-        Ok(row.map(|row| ApiKeyValidation {
+        Ok(row.map(Self::to_api_key_validation))
+    }
+
+    /// Re-stores key `id`'s hash as `new_hash`, but only while it is still `old_hash`: a key
+    /// validated under a previous pepper or the legacy SHA-256 is upgraded in place, and a
+    /// concurrent rotation or upgrade is never overwritten. Returns whether the row changed.
+    #[instrument(skip(self, old_hash, new_hash))]
+    pub async fn upgrade_api_key_hash(
+        &self,
+        id: &str,
+        old_hash: &str,
+        new_hash: &str,
+    ) -> Result<bool> {
+        let result =
+            sqlx::query("UPDATE api_keys SET key_hash = $3 WHERE id = $1 AND key_hash = $2")
+                .bind(id)
+                .bind(old_hash)
+                .bind(new_hash)
+                .execute(self.pool())
+                .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Batch form of [`Self::find_api_key_validation_by_hash`]: one `key_hash = ANY($1)` read of
+    /// the same view for every hash in `key_hashes`. Returns only the rows that exist, in no
+    /// particular order -- callers match them back to their input by `key_hash`.
+    #[instrument(skip(self, key_hashes), fields(count = key_hashes.len()))]
+    pub async fn find_api_key_validations_by_hashes(
+        &self,
+        key_hashes: &[String],
+    ) -> Result<Vec<ApiKeyValidation>> {
+        if key_hashes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows: Vec<ApiKeyValidationRow> = sqlx::query_as(
+            r#"
+            SELECT
+              api_key_id,
+              key_hash,
+              project_id,
+              account_id,
+              owner_account_id,
+              owner_role,
+              owner_quota_tier,
+              api_key_status,
+              project_status,
+              account_status,
+              expires_at,
+              effective_status,
+              allowed_cidrs
+            FROM api_key_validation
+            WHERE key_hash = ANY($1)
+            "#,
+        )
+        .bind(key_hashes)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(rows.into_iter().map(Self::to_api_key_validation).collect())
+    }
+
+    fn to_api_key_validation(row: ApiKeyValidationRow) -> ApiKeyValidation {
+        ApiKeyValidation {
             api_key_id: row.api_key_id,
             key_hash: row.key_hash,
             project_id: row.project_id,
@@ -1748,11 +2332,13 @@ impl StoreRepo {
             account_status: row.account_status,
             expires_at: row.expires_at,
             effective_status: row.effective_status,
-        }))
+            allowed_cidrs: Self::json_to_vec(&row.allowed_cidrs),
+        }
     }
 
     /// Project-scoped rule (not lead-gated, unlike `create_api_key`) -- this backs both direct
     /// revoke/reactivate and the "revoke the old key" half of `rotate_api_key_transaction` below.
+    /// `revocation_reason` replaces the stored one, so a status change without a reason clears it.
     #[instrument(skip(self))]
     pub async fn set_api_key_status(
         &self,
@@ -1761,14 +2347,17 @@ impl StoreRepo {
         status: ApiKeyStatus,
         revoked_at: Option<DateTime<Utc>>,
         expires_at: Option<DateTime<Utc>>,
+        revocation_reason: Option<&str>,
     ) -> Result<ApiKey> {
+        let mut tx = audit::begin(self.pool()).await?;
         let row: Option<ApiKeyRow> = sqlx::query_as(
             r#"
             UPDATE api_keys
             SET
               status = $1,
               revoked_at = COALESCE($2, revoked_at),
-              expires_at = COALESCE($3, expires_at)
+              expires_at = COALESCE($3, expires_at),
+              revocation_reason = $6
             FROM projects
             WHERE api_keys.project_id = projects.id
               AND api_keys.id = $4
@@ -1781,7 +2370,8 @@ impl StoreRepo {
               )
             RETURNING
               api_keys.id, api_keys.project_id, api_keys.name, api_keys.key_prefix, api_keys.key_hash, api_keys.created_at, api_keys.expires_at, api_keys.status,
-              api_keys.last_used_at, api_keys.last_ip, api_keys.revoked_at, api_keys.billing_plan, api_keys.updated_at
+              api_keys.last_used_at, api_keys.last_ip, api_keys.revoked_at, api_keys.billing_plan, api_keys.allowed_models, api_keys.scopes, api_keys.allowed_cidrs, api_keys.updated_at,
+              api_keys.rotating_until, api_keys.successor_id, api_keys.predecessor_id
             "#,
         )
         .bind(status.to_string())
@@ -1789,56 +2379,78 @@ impl StoreRepo {
         .bind(expires_at)
         .bind(key_id)
         .bind(subject)
-        .fetch_optional(self.pool())
+        .bind(revocation_reason)
+        .fetch_optional(&mut *tx)
         .await?;
+        tx.commit().await?;
         let row = row.ok_or(Error::NotFound)?;
         Ok(Self::to_api_key(row))
     }
 
-    /// Project-scoped rule for both halves (not lead-gated, unlike `create_api_key`): revoking the
-    /// presented key and minting its successor both require `subject` to own the project's account
-    /// or hold ANY `project_members` row on it.
+    /// Replace a key's IP allowlist (`None` clears it). **Lead-gated** like `create_api_key`, not
+    /// project-scoped like `set_api_key_status`: clearing or widening the list extends where the
+    /// key's spending power can be used from, which is the same decision as minting it. Enforced
+    /// in the `WHERE` clause, so a non-lead, a non-member and a missing key are all the same
+    /// `NotFound`.
     #[instrument(skip(self))]
-    pub async fn rotate_api_key_transaction(
+    pub async fn set_api_key_allowed_cidrs(
         &self,
         subject: &str,
         key_id: &str,
-        status: ApiKeyStatus,
-        revoked_at: Option<DateTime<Utc>>,
-        expires_at: Option<DateTime<Utc>>,
-        new_key: NewApiKeyRow,
+        allowed_cidrs: Option<Vec<String>>,
     ) -> Result<ApiKey> {
-        let mut tx = self.pool().begin().await?;
-        let existing_update = sqlx::query_as::<_, ApiKeyRow>(
+        let mut tx = audit::begin(self.pool()).await?;
+        let row: Option<ApiKeyRow> = sqlx::query_as(
             r#"
             UPDATE api_keys
-            SET
-              status = $1,
-              revoked_at = COALESCE($2, revoked_at),
-              expires_at = COALESCE($3, expires_at)
+            SET allowed_cidrs = $1
             FROM projects
             WHERE api_keys.project_id = projects.id
-              AND api_keys.id = $4
+              AND api_keys.id = $2
+              AND api_keys.deleted_at IS NULL
               AND (
-                projects.account_id = $5
+                projects.account_id = $3
                 OR EXISTS (
                   SELECT 1 FROM project_members pm
-                  WHERE pm.project_id = projects.id AND pm.account_id = $5
+                  WHERE pm.project_id = projects.id AND pm.account_id = $3 AND pm.role = 'lead'
                 )
               )
             RETURNING
               api_keys.id, api_keys.project_id, api_keys.name, api_keys.key_prefix, api_keys.key_hash, api_keys.created_at, api_keys.expires_at, api_keys.status,
-              api_keys.last_used_at, api_keys.last_ip, api_keys.revoked_at, api_keys.billing_plan, api_keys.updated_at
+              api_keys.last_used_at, api_keys.last_ip, api_keys.revoked_at, api_keys.billing_plan, api_keys.allowed_models, api_keys.scopes, api_keys.allowed_cidrs, api_keys.updated_at,
+              api_keys.rotating_until, api_keys.successor_id, api_keys.predecessor_id
             "#,
         )
-        .bind(status.to_string())
-        .bind(revoked_at)
-        .bind(expires_at)
+        .bind(Self::vec_to_json(&allowed_cidrs))
         .bind(key_id)
         .bind(subject)
         .fetch_optional(&mut *tx)
         .await?;
-        existing_update.ok_or(Error::NotFound)?;
+        tx.commit().await?;
+        let row = row.ok_or(Error::NotFound)?;
+        Ok(Self::to_api_key(row))
+    }
+
+    /// Project-scoped rule for both halves (not lead-gated, unlike `create_api_key`): retiring the
+    /// presented key and minting its successor both require `subject` to own the project's account
+    /// or hold ANY `project_members` row on it.
+    ///
+    /// The successor is inserted first (with `predecessor_id = key_id`) so the old key can point
+    /// at it through `successor_id` in the same transaction. The old key takes `status`,
+    /// `revoked_at` and `rotating_until` as given: `revoked`/now/`None` for an immediate
+    /// rotation, `active`/`None`/cut-off for a grace-period overlap. A key that already has a
+    /// successor is not matched, so two concurrent rotations of one key cannot both succeed.
+    #[instrument(skip(self))]
+    pub async fn rotate_api_key_transaction(
+        &self,
+        subject: &str,
+        key_id: &str,
+        status: ApiKeyStatus,
+        revoked_at: Option<DateTime<Utc>>,
+        rotating_until: Option<DateTime<Utc>>,
+        new_key: NewApiKeyRow,
+    ) -> Result<ApiKey> {
+        let mut tx = audit::begin(self.pool()).await?;
         let new_row = sqlx::query_as::<_, ApiKeyRow>(
             r#"
             WITH project_auth AS (
@@ -1855,16 +2467,19 @@ impl StoreRepo {
             )
             INSERT INTO api_keys (
               id, project_id, name, key_prefix, key_hash, created_at, expires_at, status,
-              last_used_at, last_ip, revoked_at, billing_plan, owner_account_id
+              last_used_at, last_ip, revoked_at, billing_plan, owner_account_id, allowed_models,
+              scopes, allowed_cidrs, predecessor_id
             )
             -- `$2` is the rotating subject, reused as the new key's owner: rotation re-mints for
             -- whoever performs it, so the per-member ceiling follows the rotator rather than being
             -- inherited from the key being replaced.
-            SELECT $3, project_auth.project_id, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $2
+            SELECT $3, project_auth.project_id, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $2, $14,
+              $15, $16, $17
             FROM project_auth
             RETURNING
               api_keys.id, api_keys.project_id, api_keys.name, api_keys.key_prefix, api_keys.key_hash, api_keys.created_at, api_keys.expires_at, api_keys.status,
-              api_keys.last_used_at, api_keys.last_ip, api_keys.revoked_at, api_keys.billing_plan, api_keys.updated_at
+              api_keys.last_used_at, api_keys.last_ip, api_keys.revoked_at, api_keys.billing_plan, api_keys.allowed_models, api_keys.scopes, api_keys.allowed_cidrs, api_keys.updated_at,
+              api_keys.rotating_until, api_keys.successor_id, api_keys.predecessor_id
             "#,
         )
         .bind(new_key.project_id)
@@ -1880,13 +2495,275 @@ impl StoreRepo {
         .bind(new_key.last_ip)
         .bind(new_key.revoked_at)
         .bind(new_key.billing_plan)
+        .bind(Self::vec_to_json(&new_key.allowed_models))
+        .bind(Self::vec_to_json(&new_key.scopes))
+        .bind(Self::vec_to_json(&new_key.allowed_cidrs))
+        .bind(key_id)
         .fetch_optional(&mut *tx)
         .await?;
         let row = new_row.ok_or(Error::NotFound)?;
+        let retired = sqlx::query_scalar::<_, String>(
+            r#"
+            UPDATE api_keys
+            SET
+              status = $1,
+              revoked_at = COALESCE($2, revoked_at),
+              rotating_until = $3,
+              successor_id = $4,
+              revocation_reason = CASE WHEN $1 = 'revoked' THEN 'rotated' ELSE revocation_reason END
+            FROM projects
+            WHERE api_keys.project_id = projects.id
+              AND api_keys.id = $5
+              AND api_keys.successor_id IS NULL
+              AND (
+                projects.account_id = $6
+                OR EXISTS (
+                  SELECT 1 FROM project_members pm
+                  WHERE pm.project_id = projects.id AND pm.account_id = $6
+                )
+              )
+            RETURNING api_keys.id
+            "#,
+        )
+        .bind(status.to_string())
+        .bind(revoked_at)
+        .bind(rotating_until)
+        .bind(&row.id)
+        .bind(key_id)
+        .bind(subject)
+        .fetch_optional(&mut *tx)
+        .await?;
+        retired.ok_or(Error::NotFound)?;
         tx.commit().await?;
         Ok(Self::to_api_key(row))
     }
 
+    /// Revokes every key whose rotation overlap ended at or before `now`, recording
+    /// `revocation_reason = 'rotated'`, and returns their ids. `revoked_at` is the cut-off itself,
+    /// not `now`, so it does not drift with how late the sweep ran.
+    #[instrument(skip(self))]
+    pub async fn finalize_rotated_api_keys(&self, now: DateTime<Utc>) -> Result<Vec<String>> {
+        let ids = sqlx::query_scalar::<_, String>(
+            r#"
+            UPDATE api_keys
+            SET
+              status = 'revoked',
+              revoked_at = rotating_until,
+              revocation_reason = 'rotated'
+            WHERE status = 'active'
+              AND rotating_until IS NOT NULL
+              AND rotating_until <= $1
+            RETURNING id
+            "#,
+        )
+        .bind(now)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(ids)
+    }
+
+    /// Records an `expiring` lifecycle event for every active key that expires within one of
+    /// `warning_days` of `now`, under the narrowest such window, and returns how many were new.
+    /// Keys already superseded by a rotation are skipped; their successor is what matters. Safe to
+    /// run repeatedly and concurrently: an event already recorded is left alone.
+    #[instrument(skip(self))]
+    pub async fn record_expiring_api_keys(
+        &self,
+        now: DateTime<Utc>,
+        warning_days: &[i32],
+    ) -> Result<u64> {
+        if warning_days.is_empty() {
+            return Ok(0);
+        }
+        let result = sqlx::query(
+            r#"
+            INSERT INTO api_key_lifecycle_events
+              (api_key_id, project_id, event_type, threshold_days, occurs_at)
+            SELECT k.id, k.project_id, 'expiring', w.days, k.expires_at
+            FROM api_keys k
+            CROSS JOIN LATERAL (
+              SELECT min(t) AS days
+              FROM UNNEST($2::int[]) AS t
+              WHERE k.expires_at <= $1 + make_interval(days => t)
+            ) w
+            WHERE k.deleted_at IS NULL
+              AND k.status = 'active'
+              AND k.successor_id IS NULL
+              AND k.expires_at > $1
+              AND w.days IS NOT NULL
+            ON CONFLICT (api_key_id, event_type, threshold_days, occurs_at) DO NOTHING
+            "#,
+        )
+        .bind(now)
+        .bind(warning_days)
+        .execute(self.pool())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Records a lifecycle event for every active key unused for its project's
+    /// `dormant_after_days` (falling back to `default_days`), and revokes it with
+    /// `revocation_reason = 'dormant'` when the project's policy says so -- those record
+    /// `dormant_revoked` instead of `dormant`. Returns the ids of the keys revoked. One statement,
+    /// so a key is never revoked without its event or reported without its revocation.
+    #[instrument(skip(self))]
+    pub async fn record_dormant_api_keys(
+        &self,
+        now: DateTime<Utc>,
+        default_days: Option<i32>,
+    ) -> Result<Vec<String>> {
+        let ids = sqlx::query_scalar::<_, String>(
+            r#"
+            WITH candidates AS (
+              SELECT
+                k.id,
+                k.project_id,
+                COALESCE(pol.dormant_after_days, $2::int) AS days,
+                COALESCE(pol.auto_revoke_dormant, false) AS auto_revoke,
+                COALESCE(k.last_used_at, k.created_at) AS idle_since
+              FROM api_keys k
+              LEFT JOIN project_key_lifecycle_policies pol ON pol.project_id = k.project_id
+              WHERE k.deleted_at IS NULL
+                AND k.status = 'active'
+                AND k.successor_id IS NULL
+            ),
+            due AS (
+              SELECT * FROM candidates
+              WHERE days IS NOT NULL
+                AND idle_since <= $1 - make_interval(days => days)
+            ),
+            revoked AS (
+              UPDATE api_keys
+              SET
+                status = 'revoked',
+                revoked_at = $1,
+                revocation_reason = 'dormant'
+              FROM due
+              WHERE api_keys.id = due.id
+                AND due.auto_revoke
+                AND api_keys.status = 'active'
+              RETURNING api_keys.id
+            ),
+            recorded AS (
+              INSERT INTO api_key_lifecycle_events
+                (api_key_id, project_id, event_type, threshold_days, occurs_at)
+              SELECT
+                due.id,
+                due.project_id,
+                CASE WHEN due.auto_revoke THEN 'dormant_revoked' ELSE 'dormant' END,
+                due.days,
+                due.idle_since
+              FROM due
+              ON CONFLICT (api_key_id, event_type, threshold_days, occurs_at) DO NOTHING
+            )
+            SELECT id FROM revoked
+            "#,
+        )
+        .bind(now)
+        .bind(default_days)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(ids)
+    }
+
+    /// Up to `limit` lifecycle events not yet delivered, oldest first.
+    #[instrument(skip(self))]
+    pub async fn list_undelivered_api_key_lifecycle_events(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<ApiKeyLifecycleEventRow>> {
+        let rows = sqlx::query_as::<_, ApiKeyLifecycleEventRow>(
+            r#"
+            SELECT
+              id, api_key_id, project_id, event_type, threshold_days, occurs_at, created_at,
+              delivered_at, delivery_attempts, last_error
+            FROM api_key_lifecycle_events
+            WHERE delivered_at IS NULL
+            ORDER BY id
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(self.pool())
+        .await?;
+        Ok(rows)
+    }
+
+    /// Stamps lifecycle event `id` delivered.
+    #[instrument(skip(self))]
+    pub async fn mark_api_key_lifecycle_event_delivered(
+        &self,
+        id: i64,
+        delivered_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE api_key_lifecycle_events
+            SET delivered_at = $2, delivery_attempts = delivery_attempts + 1, last_error = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(delivered_at)
+        .execute(self.pool())
+        .await?;
+        Ok(())
+    }
+
+    /// Counts a failed delivery of lifecycle event `id`, which stays pending for the next scan.
+    #[instrument(skip(self))]
+    pub async fn record_api_key_lifecycle_delivery_failure(
+        &self,
+        id: i64,
+        error: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE api_key_lifecycle_events
+            SET delivery_attempts = delivery_attempts + 1, last_error = $2
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .execute(self.pool())
+        .await?;
+        Ok(())
+    }
+
+    /// `listAuditEvents`' read path: up to `filter.limit` rows matching every supplied filter,
+    /// newest first, strictly older than `filter.before` when supplied. Paginated by `created_at`
+    /// like the budget ledger (ADR-0039); `limit` is clamped to `[1, MAX_LIST_AUDIT_EVENTS_LIMIT]`.
+    #[instrument(skip(self))]
+    pub async fn list_audit_events(&self, filter: &AuditEventFilter) -> Result<Vec<AuditEvent>> {
+        let rows = sqlx::query_as::<_, AuditEventRow>(
+            r#"
+            SELECT
+              id, actor_subject, op_id, request_id, target_type, target_id, action, before, after,
+              created_at
+            FROM audit_events
+            WHERE ($1::text IS NULL OR actor_subject = $1)
+              AND ($2::text IS NULL OR target_type = $2)
+              AND ($3::text IS NULL OR target_id = $3)
+              AND ($4::timestamptz IS NULL OR created_at >= $4)
+              AND ($5::timestamptz IS NULL OR created_at <= $5)
+              AND ($6::timestamptz IS NULL OR created_at < $6)
+            ORDER BY created_at DESC, id DESC
+            LIMIT $7
+            "#,
+        )
+        .bind(filter.actor_subject.as_deref())
+        .bind(filter.target_type.as_deref())
+        .bind(filter.target_id.as_deref())
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(filter.before)
+        .bind(filter.limit.clamp(1, MAX_LIST_AUDIT_EVENTS_LIMIT))
+        .fetch_all(self.pool())
+        .await?;
+        Ok(rows.into_iter().map(AuditEvent::from).collect())
+    }
+
     // `delete_api_key` (a hand-written hard `DELETE FROM api_keys`) was removed here (PR #429
     // follow-up): it had no production caller -- `delete-api-key`'s MCP tool and the RPC
     // `model.ApiKey.delete` verb both go through cratestack's generated soft-delete
@@ -1906,7 +2783,8 @@ impl StoreRepo {
             r#"
             SELECT
               id, project_id, name, key_prefix, key_hash, created_at, expires_at, status,
-              last_used_at, last_ip, revoked_at, billing_plan, updated_at
+              last_used_at, last_ip, revoked_at, billing_plan, allowed_models, scopes, allowed_cidrs, updated_at,
+              rotating_until, successor_id, predecessor_id
             FROM api_keys
             WHERE key_hash = $1
             "#,
@@ -1917,45 +2795,64 @@ impl StoreRepo {
         Ok(row.map(Self::to_api_key))
     }
 
+    /// Key `key_id` regardless of caller, or `None` if there is no such key. Only for callers that
+    /// already authenticated the key itself, such as introspection after its hash matched.
     #[instrument(skip(self))]
-    pub async fn record_api_key_usage(
-        &self,
-        key_id: &str,
-        last_ip: Option<String>,
-    ) -> Result<ApiKey> {
-        let changes = ApiKeyChangeset {
-            name: None,
-            expires_at: None,
-            status: None,
-            last_used_at: Some(Utc::now()),
-            last_ip,
-            revoked_at: None,
-        };
-        let row: ApiKeyRow = sqlx::query_as(
+    pub async fn get_api_key_by_id(&self, key_id: &str) -> Result<Option<ApiKey>> {
+        let row = sqlx::query_as(
             r#"
-            UPDATE api_keys
-            SET
-              last_used_at = $1,
-              last_ip = $2
-            WHERE id = $3
-            RETURNING
+            SELECT
               id, project_id, name, key_prefix, key_hash, created_at, expires_at, status,
-              last_used_at, last_ip, revoked_at, billing_plan, updated_at
+              last_used_at, last_ip, revoked_at, billing_plan, allowed_models, scopes, allowed_cidrs, updated_at,
+              rotating_until, successor_id, predecessor_id
+            FROM api_keys
+            WHERE id = $1
             "#,
         )
-        .bind(changes.last_used_at)
-        .bind(changes.last_ip)
         .bind(key_id)
-        .fetch_one(self.pool())
+        .fetch_optional(self.pool())
         .await?;
-        Ok(Self::to_api_key(row))
+        Ok(row.map(Self::to_api_key))
+    }
+
+    /// Writes `last_used_at`/`last_ip` for every key in `usages` in one `UPDATE`. A usage older
+    /// than what the row already holds is skipped, so replicas flushing out of order never move
+    /// `last_used_at` backwards. Returns the number of rows updated.
+    #[instrument(skip(self, usages), fields(count = usages.len()))]
+    pub async fn record_api_key_usage_batch(&self, usages: &[ApiKeyUsage]) -> Result<u64> {
+        if usages.is_empty() {
+            return Ok(0);
+        }
+        let ids: Vec<&str> = usages.iter().map(|usage| usage.key_id.as_str()).collect();
+        let used_at: Vec<DateTime<Utc>> = usages.iter().map(|usage| usage.last_used_at).collect();
+        let ips: Vec<Option<&str>> = usages
+            .iter()
+            .map(|usage| usage.last_ip.as_deref())
+            .collect();
+        let result = sqlx::query(
+            r#"
+            UPDATE api_keys k
+            SET
+              last_used_at = u.last_used_at,
+              last_ip = u.last_ip
+            FROM UNNEST($1::text[], $2::timestamptz[], $3::text[]) AS u(id, last_used_at, last_ip)
+            WHERE k.id = u.id
+              AND (k.last_used_at IS NULL OR k.last_used_at < u.last_used_at)
+            "#,
+        )
+        .bind(&ids)
+        .bind(&used_at)
+        .bind(&ips)
+        .execute(self.pool())
+        .await?;
+        Ok(result.rows_affected())
     }
 
     #[instrument(skip(self))]
     pub async fn get_active_signing_key(&self) -> Result<Option<SigningKeyRow>> {
         let row = sqlx::query_as::<_, SigningKeyRow>(
             r#"
-            SELECT kid, algorithm, private_key_pem, public_jwk, status, created_at, retired_at
+            SELECT kid, algorithm, private_key_pem, public_jwk, status, created_at, retired_at, activates_at
             FROM signing_keys
             WHERE status = 'active'
             LIMIT 1
@@ -1966,12 +2863,56 @@ impl StoreRepo {
         Ok(row)
     }
 
+    /// Every signing key, active first then newest first. Private material included -- only for
+    /// maintenance jobs such as re-encryption, never for serving.
+    #[instrument(skip(self))]
+    pub async fn list_signing_keys(&self) -> Result<Vec<SigningKeyRow>> {
+        let rows = sqlx::query_as::<_, SigningKeyRow>(
+            r#"
+            SELECT kid, algorithm, private_key_pem, public_jwk, status, created_at, retired_at, activates_at
+            FROM signing_keys
+            ORDER BY status = 'active' DESC, created_at DESC
+            "#,
+        )
+        .fetch_all(self.pool())
+        .await?;
+        Ok(rows)
+    }
+
+    /// Swaps a key's stored private material, but only if it still holds `expected` (so two
+    /// concurrent re-encryptions of the same row never interleave). Returns whether it swapped.
+    #[instrument(skip(self, expected, replacement))]
+    pub async fn replace_signing_key_private_pem(
+        &self,
+        kid: &str,
+        expected: &str,
+        replacement: &str,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE signing_keys
+            SET private_key_pem = $3
+            WHERE kid = $1 AND private_key_pem = $2
+            "#,
+        )
+        .bind(kid)
+        .bind(expected)
+        .bind(replacement)
+        .execute(self.pool())
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Every key a relying party should trust: the active one first, then the pre-published
+    /// `pending` key and the `stale` keys older tokens were signed with. `revoked` keys are left
+    /// out, which is what makes an emergency rotation invalidate their tokens.
     #[instrument(skip(self))]
     pub async fn list_verification_jwks(&self) -> Result<Vec<Value>> {
         let rows: Vec<(Value,)> = sqlx::query_as(
             r#"
             SELECT public_jwk
             FROM signing_keys
+            WHERE status IN ('pending', 'active', 'stale')
             ORDER BY status = 'active' DESC, created_at DESC
             "#,
         )
@@ -1982,6 +2923,8 @@ impl StoreRepo {
 
     /// Idempotently ensures there is an active signing key, rotating (marking the current
     /// active stale + activating `candidate`) when it is missing or older than `max_age_cutoff`.
+    /// Activating a key the moment it is created is only right for an empty table; scheduled
+    /// rotation goes through [`Self::prepublish_signing_key`] instead.
     /// A transaction-scoped advisory lock serializes this across replicas so only one key wins --
     /// this is the chokepoint every bootstrapping caller shares (`authz-api`, `lightbridge-mcp`,
     /// and, since ADR-0012, `authz-idp`; see `lightbridge_authz_rest::signing::bootstrap_signing_key`'s
@@ -1992,16 +2935,12 @@ impl StoreRepo {
         candidate: NewSigningKey,
         max_age_cutoff: DateTime<Utc>,
     ) -> Result<SigningKeyRow> {
-        const SIGNING_KEY_LOCK: i64 = 0x5369_676E_4B65_7973;
         let mut tx = self.pool().begin().await?;
-        sqlx::query("SELECT pg_advisory_xact_lock($1)")
-            .bind(SIGNING_KEY_LOCK)
-            .execute(&mut *tx)
-            .await?;
+        lock_signing_keys(&mut tx).await?;
 
         let active: Option<SigningKeyRow> = sqlx::query_as(
             r#"
-            SELECT kid, algorithm, private_key_pem, public_jwk, status, created_at, retired_at
+            SELECT kid, algorithm, private_key_pem, public_jwk, status, created_at, retired_at, activates_at
             FROM signing_keys
             WHERE status = 'active'
             LIMIT 1
@@ -2039,7 +2978,7 @@ impl StoreRepo {
             r#"
             INSERT INTO signing_keys (kid, algorithm, private_key_pem, public_jwk, status, created_at)
             VALUES ($1, $2, $3, $4, 'active', $5)
-            RETURNING kid, algorithm, private_key_pem, public_jwk, status, created_at, retired_at
+            RETURNING kid, algorithm, private_key_pem, public_jwk, status, created_at, retired_at, activates_at
             "#,
         )
         .bind(candidate.kid)
@@ -2053,4 +2992,195 @@ impl StoreRepo {
         tx.commit().await?;
         Ok(inserted)
     }
+
+    #[instrument(skip(self))]
+    pub async fn get_pending_signing_key(&self) -> Result<Option<SigningKeyRow>> {
+        let row = sqlx::query_as::<_, SigningKeyRow>(
+            r#"
+            SELECT kid, algorithm, private_key_pem, public_jwk, status, created_at, retired_at, activates_at
+            FROM signing_keys
+            WHERE status = 'pending'
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(self.pool())
+        .await?;
+        Ok(row)
+    }
+
+    /// Inserts `candidate` as the `pending` key that starts signing at `activates_at`, but only
+    /// if no key is pending yet and the active key has been signing since `due_cutoff` or
+    /// earlier. Under the same advisory lock as [`Self::ensure_active_signing_key`], so of
+    /// several replicas ticking at once exactly one pre-publishes. Returns the inserted row, or
+    /// `None` when there was nothing to do.
+    #[instrument(skip(self, candidate))]
+    pub async fn prepublish_signing_key(
+        &self,
+        candidate: NewSigningKey,
+        activates_at: DateTime<Utc>,
+        due_cutoff: DateTime<Utc>,
+    ) -> Result<Option<SigningKeyRow>> {
+        let mut tx = self.pool().begin().await?;
+        lock_signing_keys(&mut tx).await?;
+
+        let due: bool = sqlx::query_scalar(
+            r#"
+            SELECT NOT EXISTS (SELECT 1 FROM signing_keys WHERE status = 'pending')
+               AND EXISTS (
+                   SELECT 1 FROM signing_keys
+                   WHERE status = 'active' AND COALESCE(activates_at, created_at) <= $1
+               )
+            "#,
+        )
+        .bind(due_cutoff)
+        .fetch_one(&mut *tx)
+        .await?;
+        if !due {
+            tx.commit().await?;
+            return Ok(None);
+        }
+
+        let inserted: SigningKeyRow = sqlx::query_as(
+            r#"
+            INSERT INTO signing_keys
+                (kid, algorithm, private_key_pem, public_jwk, status, created_at, activates_at)
+            VALUES ($1, $2, $3, $4, 'pending', $5, $6)
+            RETURNING kid, algorithm, private_key_pem, public_jwk, status, created_at, retired_at, activates_at
+            "#,
+        )
+        .bind(candidate.kid)
+        .bind(candidate.algorithm)
+        .bind(candidate.private_key_pem)
+        .bind(candidate.public_jwk)
+        .bind(candidate.created_at)
+        .bind(activates_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(inserted))
+    }
+
+    /// Swaps in the pending key once its `activates_at` has passed: the active key goes `stale`
+    /// (retired at `now`, still published for verification) and the pending one becomes active.
+    /// Returns the newly active row, or `None` when no pending key is due.
+    #[instrument(skip(self))]
+    pub async fn promote_pending_signing_key(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Option<SigningKeyRow>> {
+        let mut tx = self.pool().begin().await?;
+        lock_signing_keys(&mut tx).await?;
+
+        let pending: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT kid FROM signing_keys
+            WHERE status = 'pending' AND activates_at <= $1
+            "#,
+        )
+        .bind(now)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(kid) = pending else {
+            tx.commit().await?;
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE signing_keys
+            SET status = 'stale', retired_at = $1
+            WHERE status = 'active'
+            "#,
+        )
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        let promoted: SigningKeyRow = sqlx::query_as(
+            r#"
+            UPDATE signing_keys
+            SET status = 'active'
+            WHERE kid = $1
+            RETURNING kid, algorithm, private_key_pem, public_jwk, status, created_at, retired_at, activates_at
+            "#,
+        )
+        .bind(kid)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(promoted))
+    }
+
+    /// Deletes `stale` and `revoked` keys retired at or before `retired_before`, i.e. once every
+    /// token they signed has expired. Returns how many were deleted.
+    #[instrument(skip(self))]
+    pub async fn prune_retired_signing_keys(&self, retired_before: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM signing_keys
+            WHERE status IN ('stale', 'revoked') AND retired_at <= $1
+            "#,
+        )
+        .bind(retired_before)
+        .execute(self.pool())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Emergency rotation: the active key is `revoked` (dropped from the JWKS, private material
+    /// wiped), any pending key is discarded, and `candidate` starts signing at once. Returns the
+    /// revoked key's kid, if there was an active key.
+    #[instrument(skip(self, candidate))]
+    pub async fn revoke_active_signing_key(
+        &self,
+        candidate: NewSigningKey,
+    ) -> Result<Option<String>> {
+        let mut tx = self.pool().begin().await?;
+        lock_signing_keys(&mut tx).await?;
+
+        let revoked: Option<String> = sqlx::query_scalar(
+            r#"
+            UPDATE signing_keys
+            SET status = 'revoked', retired_at = $1, private_key_pem = ''
+            WHERE status = 'active'
+            RETURNING kid
+            "#,
+        )
+        .bind(candidate.created_at)
+        .fetch_optional(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM signing_keys WHERE status = 'pending'")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO signing_keys (kid, algorithm, private_key_pem, public_jwk, status, created_at)
+            VALUES ($1, $2, $3, $4, 'active', $5)
+            "#,
+        )
+        .bind(candidate.kid)
+        .bind(candidate.algorithm)
+        .bind(candidate.private_key_pem)
+        .bind(candidate.public_jwk)
+        .bind(candidate.created_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(revoked)
+    }
+}
+
+/// Upper bound on [`StoreRepo::list_audit_events`]' page size, whatever the caller asks for.
+const MAX_LIST_AUDIT_EVENTS_LIMIT: i64 = 200;
+
+/// Serializes every `signing_keys` status change across replicas for the rest of `tx`.
+async fn lock_signing_keys(tx: &mut Transaction<'_, Postgres>) -> Result<()> {
+    const SIGNING_KEY_LOCK: i64 = 0x5369_676E_4B65_7973;
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(SIGNING_KEY_LOCK)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
 }
@@ -1,6 +1,7 @@
 #![cfg(feature = "it-tests")]
 
 use chrono::Utc;
+use lightbridge_authz_api_key::entities::api_key_row::ApiKeyUsage;
 use lightbridge_authz_api_key::entities::new_api_key_row::NewApiKeyRow;
 use lightbridge_authz_api_key::repo::StoreRepo;
 use lightbridge_authz_core::cuid::cuid2;
@@ -33,6 +34,9 @@ fn build_new_api_key_row(project_id: &str, name: &str, key_hash: &str) -> NewApi
         last_ip: None,
         revoked_at: None,
         billing_plan: "free".to_string(),
+        allowed_models: None,
+        scopes: None,
+        allowed_cidrs: None,
     }
 }
 
@@ -336,6 +340,7 @@ async fn access_control_allows_project_members_and_rejects_non_members(pool: PgP
             ApiKeyStatus::Revoked,
             Some(Utc::now()),
             None,
+            None,
         )
         .await
         .unwrap_err();
@@ -348,6 +353,7 @@ async fn access_control_allows_project_members_and_rejects_non_members(pool: PgP
             ApiKeyStatus::Revoked,
             Some(Utc::now()),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -360,10 +366,16 @@ async fn access_control_allows_project_members_and_rejects_non_members(pool: PgP
         .unwrap();
     assert_eq!(by_hash.id, api_key.id);
 
-    let usage = repo
-        .record_api_key_usage(&api_key.id, Some("203.0.113.5".to_string()))
+    let updated = repo
+        .record_api_key_usage_batch(&[ApiKeyUsage {
+            key_id: api_key.id.clone(),
+            last_used_at: Utc::now(),
+            last_ip: Some("203.0.113.5".to_string()),
+        }])
         .await
         .unwrap();
+    assert_eq!(updated, 1);
+    let usage = repo.get_api_key_by_id(&api_key.id).await.unwrap().unwrap();
     assert_eq!(usage.last_ip.as_deref(), Some("203.0.113.5"));
     assert!(usage.last_used_at.is_some());
 
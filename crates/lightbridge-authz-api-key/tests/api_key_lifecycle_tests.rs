@@ -0,0 +1,251 @@
+#![cfg(feature = "it-tests")]
+
+use chrono::{DateTime, Duration, Utc};
+use lightbridge_authz_api_key::entities::new_api_key_row::NewApiKeyRow;
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_core::cuid::cuid2;
+use lightbridge_authz_core::db::DbPool;
+use lightbridge_authz_core::error::Error;
+use lightbridge_authz_core::{ApiKeyStatus, CreateAccount, CreateProject};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+/// Seeds `subject`'s account with one project per id in `project_ids`.
+async fn seed_projects(repo: &StoreRepo, subject: &str, project_ids: &[&str]) {
+    let account = repo
+        .create_account(
+            subject,
+            CreateAccount {
+                default_quota: None,
+            },
+        )
+        .await
+        .expect("account creation should succeed");
+    for project_id in project_ids {
+        repo.create_project(
+            subject,
+            &account.id,
+            CreateProject {
+                name: "lifecycle-project".to_string(),
+                allowed_models: None,
+                default_limits: None,
+                billing_plan: "starter".to_string(),
+                billing_identity: format!("bill-{}", cuid2()),
+                project_quota: None,
+            },
+            project_id.to_string(),
+        )
+        .await
+        .expect("project creation should succeed");
+    }
+}
+
+async fn seed_key(
+    repo: &StoreRepo,
+    subject: &str,
+    project_id: &str,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+) -> String {
+    let key = repo
+        .create_api_key(
+            subject,
+            NewApiKeyRow {
+                id: cuid2(),
+                project_id: project_id.to_string(),
+                name: "lifecycle".to_string(),
+                key_prefix: "lbk_life".to_string(),
+                key_hash: format!("hash_{}", cuid2()),
+                created_at,
+                expires_at: Some(expires_at),
+                status: ApiKeyStatus::Active.to_string(),
+                last_used_at: None,
+                last_ip: None,
+                revoked_at: None,
+                billing_plan: "starter".to_string(),
+                allowed_models: None,
+                scopes: None,
+                allowed_cidrs: None,
+            },
+        )
+        .await
+        .expect("api key creation should succeed");
+    key.id
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn expiring_keys_are_recorded_once_per_narrowest_window(pool: PgPool) {
+    let repo = StoreRepo::new(Arc::new(DbPool::from_pool(pool)));
+    let subject = "test-lifecycle-expiring";
+    seed_projects(&repo, subject, &["proj_expiring"]).await;
+    let now = Utc::now();
+    let soon = seed_key(
+        &repo,
+        subject,
+        "proj_expiring",
+        now,
+        now + Duration::days(2),
+    )
+    .await;
+    let later = seed_key(
+        &repo,
+        subject,
+        "proj_expiring",
+        now,
+        now + Duration::days(10),
+    )
+    .await;
+    seed_key(
+        &repo,
+        subject,
+        "proj_expiring",
+        now,
+        now + Duration::days(60),
+    )
+    .await;
+
+    let windows = [14, 3, 1];
+    assert_eq!(
+        repo.record_expiring_api_keys(now, &windows).await.unwrap(),
+        2
+    );
+    // A second scan in the same windows records nothing new.
+    assert_eq!(
+        repo.record_expiring_api_keys(now, &windows).await.unwrap(),
+        0
+    );
+
+    let events = repo
+        .list_undelivered_api_key_lifecycle_events(100)
+        .await
+        .unwrap();
+    let threshold = |key: &str| {
+        events
+            .iter()
+            .find(|e| e.api_key_id == key)
+            .map(|e| (e.event_type.as_str(), e.threshold_days))
+    };
+    assert_eq!(events.len(), 2);
+    assert_eq!(threshold(&soon), Some(("expiring", 3)));
+    assert_eq!(threshold(&later), Some(("expiring", 14)));
+
+    // Crossing into the next window warns again.
+    let tomorrow = now + Duration::days(1) + Duration::minutes(1);
+    assert_eq!(
+        repo.record_expiring_api_keys(tomorrow, &windows)
+            .await
+            .unwrap(),
+        1
+    );
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn dormant_keys_are_reported_and_revoked_only_under_project_policy(pool: PgPool) {
+    let repo = StoreRepo::new(Arc::new(DbPool::from_pool(pool)));
+    let subject = "test-lifecycle-dormant";
+    seed_projects(&repo, subject, &["proj_report", "proj_revoke"]).await;
+    let now = Utc::now();
+    let old = now - Duration::days(120);
+    let expires = now + Duration::days(30);
+    let reported = seed_key(&repo, subject, "proj_report", old, expires).await;
+    let revoked = seed_key(&repo, subject, "proj_revoke", old, expires).await;
+    let fresh = seed_key(&repo, subject, "proj_revoke", now, expires).await;
+
+    repo.set_project_key_lifecycle_policy(subject, "proj_revoke", Some(30), true)
+        .await
+        .unwrap();
+
+    // No deployment default: only the project with its own `dormant_after_days` is scanned.
+    assert_eq!(
+        repo.record_dormant_api_keys(now, None).await.unwrap(),
+        vec![revoked.clone()]
+    );
+    let key = repo.get_api_key(subject, &revoked).await.unwrap().unwrap();
+    assert_eq!(key.status, ApiKeyStatus::Revoked);
+    let key = repo.get_api_key(subject, &fresh).await.unwrap().unwrap();
+    assert_eq!(key.status, ApiKeyStatus::Active);
+
+    // With a deployment default the other project's key is reported, never revoked.
+    assert!(
+        repo.record_dormant_api_keys(now, Some(90))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let key = repo.get_api_key(subject, &reported).await.unwrap().unwrap();
+    assert_eq!(key.status, ApiKeyStatus::Active);
+
+    let events = repo
+        .list_undelivered_api_key_lifecycle_events(100)
+        .await
+        .unwrap();
+    let kinds: Vec<_> = events
+        .iter()
+        .map(|e| {
+            (
+                e.api_key_id.as_str(),
+                e.event_type.as_str(),
+                e.threshold_days,
+            )
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (revoked.as_str(), "dormant_revoked", 30),
+            (reported.as_str(), "dormant", 90),
+        ]
+    );
+
+    // Delivered events leave the outbox; failed ones stay with their error.
+    repo.mark_api_key_lifecycle_event_delivered(events[0].id, now)
+        .await
+        .unwrap();
+    repo.record_api_key_lifecycle_delivery_failure(events[1].id, "503")
+        .await
+        .unwrap();
+    let pending = repo
+        .list_undelivered_api_key_lifecycle_events(100)
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].delivery_attempts, 1);
+    assert_eq!(pending[0].last_error.as_deref(), Some("503"));
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn key_lifecycle_policy_defaults_and_is_project_scoped(pool: PgPool) {
+    let repo = StoreRepo::new(Arc::new(DbPool::from_pool(pool)));
+    let subject = "test-lifecycle-policy";
+    seed_projects(&repo, subject, &["proj_policy"]).await;
+
+    let policy = repo
+        .get_project_key_lifecycle_policy(subject, "proj_policy")
+        .await
+        .unwrap();
+    assert_eq!(policy.dormant_after_days, None);
+    assert!(!policy.auto_revoke_dormant);
+
+    let policy = repo
+        .set_project_key_lifecycle_policy(subject, "proj_policy", Some(45), true)
+        .await
+        .unwrap();
+    assert_eq!(policy.dormant_after_days, Some(45));
+    assert!(policy.auto_revoke_dormant);
+    let policy = repo
+        .get_project_key_lifecycle_policy(subject, "proj_policy")
+        .await
+        .unwrap();
+    assert_eq!(policy.dormant_after_days, Some(45));
+
+    let err = repo
+        .set_project_key_lifecycle_policy("someone-else", "proj_policy", None, false)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::NotFound));
+    let err = repo
+        .get_project_key_lifecycle_policy("someone-else", "proj_policy")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::NotFound));
+}
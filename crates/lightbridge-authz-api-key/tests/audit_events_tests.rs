@@ -0,0 +1,206 @@
+#![cfg(feature = "it-tests")]
+
+use chrono::Utc;
+use lightbridge_authz_api_key::entities::new_api_key_row::NewApiKeyRow;
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_core::audit::{self, AuditContext};
+use lightbridge_authz_core::cuid::cuid2;
+use lightbridge_authz_core::db::DbPool;
+use lightbridge_authz_core::{
+    ApiKeyStatus, AuditEventFilter, CreateAccount, CreateProject, ResourceStatus,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+fn filter_for(actor: &str) -> AuditEventFilter {
+    AuditEventFilter {
+        actor_subject: Some(actor.to_string()),
+        limit: 100,
+        ..AuditEventFilter::default()
+    }
+}
+
+async fn seed_project(repo: &StoreRepo, subject: &str, project_id: &str) {
+    repo.create_account(
+        subject,
+        CreateAccount {
+            default_quota: None,
+        },
+    )
+    .await
+    .expect("account creation should succeed");
+    repo.create_project(
+        subject,
+        subject,
+        CreateProject {
+            name: "audit-project".to_string(),
+            allowed_models: None,
+            default_limits: None,
+            billing_plan: "starter".to_string(),
+            billing_identity: format!("bill-{}", cuid2()),
+            project_quota: None,
+        },
+        project_id.to_string(),
+    )
+    .await
+    .expect("project creation should succeed");
+}
+
+fn new_key(project_id: &str) -> NewApiKeyRow {
+    NewApiKeyRow {
+        id: cuid2(),
+        project_id: project_id.to_string(),
+        name: "audited".to_string(),
+        key_prefix: "lbk_audt".to_string(),
+        key_hash: format!("hash_{}", cuid2()),
+        created_at: Utc::now(),
+        expires_at: Some(Utc::now() + chrono::Duration::days(30)),
+        status: ApiKeyStatus::Active.to_string(),
+        last_used_at: None,
+        last_ip: None,
+        revoked_at: None,
+        billing_plan: "starter".to_string(),
+        allowed_models: None,
+        scopes: None,
+        allowed_cidrs: None,
+    }
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn scoped_writes_are_recorded_with_their_caller_and_snapshots(pool: PgPool) {
+    let repo = StoreRepo::new(Arc::new(DbPool::from_pool(pool)));
+    let subject = "test-audit-scoped";
+    seed_project(&repo, subject, "proj_audit_scoped").await;
+
+    let key = audit::scope(
+        AuditContext::new(
+            subject,
+            "procedure.createApiKey",
+            Some(TRACEPARENT.to_string()),
+        ),
+        repo.create_api_key(subject, new_key("proj_audit_scoped")),
+    )
+    .await
+    .expect("api key creation should succeed");
+    audit::scope(
+        AuditContext::new(subject, "procedure.disableAccount", None),
+        repo.set_account_status(subject, subject, ResourceStatus::Suspended),
+    )
+    .await
+    .expect("disabling the account should succeed");
+
+    // Newest first; the unscoped seeding above left nothing behind.
+    let events = repo
+        .list_audit_events(&filter_for(subject))
+        .await
+        .expect("listing should succeed");
+    assert_eq!(events.len(), 2, "{events:?}");
+
+    let disabled = &events[0];
+    assert_eq!(disabled.op_id, "procedure.disableAccount");
+    assert_eq!(disabled.target_type, "accounts");
+    assert_eq!(disabled.target_id, subject);
+    assert_eq!(disabled.action, "update");
+    assert_eq!(disabled.request_id, None);
+    let status = |side: &Option<serde_json::Value>| {
+        side.as_ref()
+            .and_then(|row| row.get("status"))
+            .and_then(|status| status.as_str())
+            .map(str::to_owned)
+    };
+    assert_eq!(status(&disabled.before).as_deref(), Some("active"));
+    assert_eq!(status(&disabled.after).as_deref(), Some("suspended"));
+
+    let created = &events[1];
+    assert_eq!(created.op_id, "procedure.createApiKey");
+    assert_eq!(created.request_id.as_deref(), Some(TRACEPARENT));
+    assert_eq!(created.target_type, "api_keys");
+    assert_eq!(created.target_id, key.id);
+    assert_eq!(created.action, "insert");
+    assert_eq!(created.before, None);
+    let after = created.after.as_ref().expect("an insert has an after side");
+    assert_eq!(after.get("project_id"), Some(&"proj_audit_scoped".into()));
+    assert!(after.get("key_hash").is_none(), "the key hash is redacted");
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn filters_narrow_by_target_and_page_by_created_at(pool: PgPool) {
+    let repo = StoreRepo::new(Arc::new(DbPool::from_pool(pool)));
+    let subject = "test-audit-filters";
+    seed_project(&repo, subject, "proj_audit_filters").await;
+    for status in [
+        ResourceStatus::Suspended,
+        ResourceStatus::Active,
+        ResourceStatus::Suspended,
+    ] {
+        audit::scope(
+            AuditContext::new(subject, "procedure.disableProject", None),
+            repo.set_project_status(subject, "proj_audit_filters", status),
+        )
+        .await
+        .expect("project status change should succeed");
+    }
+
+    let by_target = AuditEventFilter {
+        target_type: Some("projects".to_string()),
+        target_id: Some("proj_audit_filters".to_string()),
+        limit: 2,
+        ..AuditEventFilter::default()
+    };
+    let first = repo
+        .list_audit_events(&by_target)
+        .await
+        .expect("listing should succeed");
+    assert_eq!(first.len(), 2);
+    let second = repo
+        .list_audit_events(&AuditEventFilter {
+            before: first.last().map(|e| e.created_at),
+            ..by_target.clone()
+        })
+        .await
+        .expect("listing should succeed");
+    assert_eq!(second.len(), 1);
+    assert!(second[0].id < first[1].id);
+
+    let none_in_range = repo
+        .list_audit_events(&AuditEventFilter {
+            to: Some(Utc::now() - chrono::Duration::days(1)),
+            ..filter_for(subject)
+        })
+        .await
+        .expect("listing should succeed");
+    assert!(none_in_range.is_empty());
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn audit_events_cannot_be_rewritten(pool: PgPool) {
+    let repo = StoreRepo::new(Arc::new(DbPool::from_pool(pool.clone())));
+    let subject = "test-audit-append-only";
+    audit::scope(
+        AuditContext::new(subject, "procedure.createAccount", None),
+        repo.create_account(
+            subject,
+            CreateAccount {
+                default_quota: None,
+            },
+        ),
+    )
+    .await
+    .expect("account creation should succeed");
+
+    let update = sqlx::query("UPDATE audit_events SET actor_subject = 'someone-else'")
+        .execute(&pool)
+        .await;
+    assert!(update.is_err(), "audit_events must reject UPDATE");
+    let delete = sqlx::query("DELETE FROM audit_events").execute(&pool).await;
+    assert!(delete.is_err(), "audit_events must reject DELETE");
+
+    let events = repo
+        .list_audit_events(&filter_for(subject))
+        .await
+        .expect("listing should succeed");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].action, "insert");
+}
@@ -61,6 +61,9 @@ async fn rotate_rolls_back_on_create_failure(pool: PgPool) {
         last_ip: None,
         revoked_at: None,
         billing_plan: "starter".to_string(),
+        allowed_models: None,
+        scopes: None,
+        allowed_cidrs: None,
     };
 
     let api_key = repo.create_api_key(subject, initial_row).await.unwrap();
@@ -78,6 +81,9 @@ async fn rotate_rolls_back_on_create_failure(pool: PgPool) {
         last_ip: None,
         revoked_at: None,
         billing_plan: "starter".to_string(),
+        allowed_models: None,
+        scopes: None,
+        allowed_cidrs: None,
     };
 
     let err = repo
@@ -103,3 +109,341 @@ async fn rotate_rolls_back_on_create_failure(pool: PgPool) {
     assert_eq!(reloaded.status, ApiKeyStatus::Active);
     assert!(reloaded.revoked_at.is_none());
 }
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn key_level_restrictions_survive_create_and_rotate(pool: PgPool) {
+    let db_pool = Arc::new(DbPool::from_pool(pool));
+    let repo = StoreRepo::new(db_pool);
+
+    let subject = "test-key-restrictions";
+    let account = repo
+        .create_account(
+            subject,
+            CreateAccount {
+                default_quota: None,
+            },
+        )
+        .await
+        .unwrap();
+    let project = repo
+        .create_project(
+            subject,
+            &account.id,
+            CreateProject {
+                name: "restricted-project".to_string(),
+                allowed_models: None,
+                default_limits: None,
+                billing_plan: "starter".to_string(),
+                billing_identity: format!("bill-{}", cuid2()),
+                project_quota: None,
+            },
+            "proj_restricted".to_string(),
+        )
+        .await
+        .unwrap();
+
+    let far_future = Utc::now() + chrono::Duration::days(30);
+    let row =
+        |key_hash: &str, models: Option<Vec<String>>, scopes: Option<Vec<String>>| NewApiKeyRow {
+            id: cuid2(),
+            project_id: project.id.clone(),
+            name: "ci".to_string(),
+            key_prefix: "lbk_ci".to_string(),
+            key_hash: key_hash.to_string(),
+            created_at: Utc::now(),
+            expires_at: Some(far_future),
+            status: ApiKeyStatus::Active.to_string(),
+            last_used_at: None,
+            last_ip: None,
+            revoked_at: None,
+            billing_plan: "starter".to_string(),
+            allowed_models: models,
+            scopes,
+            allowed_cidrs: None,
+        };
+
+    let created = repo
+        .create_api_key(
+            subject,
+            row(
+                "hash_restricted",
+                Some(vec!["cheap-model".to_string()]),
+                Some(vec!["inference".to_string(), "batch".to_string()]),
+            ),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        created.allowed_models,
+        Some(vec!["cheap-model".to_string()])
+    );
+    assert_eq!(
+        created.scopes,
+        Some(vec!["inference".to_string(), "batch".to_string()])
+    );
+
+    let rotated = repo
+        .rotate_api_key_transaction(
+            subject,
+            &created.id,
+            ApiKeyStatus::Revoked,
+            Some(Utc::now()),
+            None,
+            row("hash_rotated", None, Some(vec!["embeddings".to_string()])),
+        )
+        .await
+        .unwrap();
+    let reloaded = repo
+        .find_api_key_by_hash("hash_rotated")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reloaded.id, rotated.id);
+    assert_eq!(
+        reloaded.allowed_models, None,
+        "SQL NULL must read back as no key-level restriction"
+    );
+    assert_eq!(reloaded.scopes, Some(vec!["embeddings".to_string()]));
+}
+
+/// `set_api_key_allowed_cidrs` is lead-gated like `create_api_key`: a plain member (and an
+/// outsider) gets the same `NotFound` as for a missing key, and `None` writes SQL NULL back.
+#[sqlx::test(migrations = "../../migrations")]
+async fn allowed_cidrs_are_lead_gated_and_cleared_by_none(pool: PgPool) {
+    let db_pool = Arc::new(DbPool::from_pool(pool.clone()));
+    let repo = StoreRepo::new(db_pool);
+    let owner = "test-cidr-owner";
+    let member = "test-cidr-member";
+
+    let account = repo
+        .create_account(
+            owner,
+            CreateAccount {
+                default_quota: None,
+            },
+        )
+        .await
+        .unwrap();
+    repo.create_account(
+        member,
+        CreateAccount {
+            default_quota: None,
+        },
+    )
+    .await
+    .unwrap();
+    let project = repo
+        .create_project(
+            owner,
+            &account.id,
+            CreateProject {
+                name: "cidr-project".to_string(),
+                allowed_models: None,
+                default_limits: None,
+                billing_plan: "starter".to_string(),
+                billing_identity: format!("bill-{}", cuid2()),
+                project_quota: None,
+            },
+            "proj_cidr".to_string(),
+        )
+        .await
+        .unwrap();
+    repo.add_project_member(owner, &project.id, member, Some("member"))
+        .await
+        .unwrap();
+
+    let created = repo
+        .create_api_key(
+            owner,
+            NewApiKeyRow {
+                id: cuid2(),
+                project_id: project.id.clone(),
+                name: "edge".to_string(),
+                key_prefix: "lbk_edge".to_string(),
+                key_hash: "hash_cidr".to_string(),
+                created_at: Utc::now(),
+                expires_at: Some(Utc::now() + chrono::Duration::days(30)),
+                status: ApiKeyStatus::Active.to_string(),
+                last_used_at: None,
+                last_ip: None,
+                revoked_at: None,
+                billing_plan: "starter".to_string(),
+                allowed_models: None,
+                scopes: None,
+                allowed_cidrs: Some(vec!["10.0.0.0/8".to_string()]),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(created.allowed_cidrs, Some(vec!["10.0.0.0/8".to_string()]));
+
+    let by_member = repo
+        .set_api_key_allowed_cidrs(member, &created.id, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(by_member, Error::NotFound), "got {by_member:?}");
+    let by_outsider = repo
+        .set_api_key_allowed_cidrs("test-cidr-outsider", &created.id, None)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(by_outsider, Error::NotFound),
+        "got {by_outsider:?}"
+    );
+
+    let validation = repo
+        .find_api_key_validation_by_hash(&["hash_cidr".to_string()])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        validation.allowed_cidrs,
+        Some(vec!["10.0.0.0/8".to_string()]),
+        "a refused write must leave the allowlist in place"
+    );
+
+    let cleared = repo
+        .set_api_key_allowed_cidrs(owner, &created.id, None)
+        .await
+        .unwrap();
+    assert_eq!(cleared.allowed_cidrs, None);
+    let is_sql_null: bool =
+        sqlx::query_scalar("SELECT allowed_cidrs IS NULL FROM api_keys WHERE id = $1")
+            .bind(&created.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(
+        is_sql_null,
+        "clearing must write SQL NULL, not a jsonb null"
+    );
+}
+
+/// A grace-period rotation leaves both keys valid and linked until the cut-off; past it the view
+/// refuses the old key before any sweep, and the sweep then revokes it exactly once.
+#[sqlx::test(migrations = "../../migrations")]
+async fn grace_rotation_overlaps_until_the_cut_off_then_is_swept(pool: PgPool) {
+    let db_pool = Arc::new(DbPool::from_pool(pool.clone()));
+    let repo = StoreRepo::new(db_pool);
+    let subject = "test-rotate-overlap";
+    let account = repo
+        .create_account(
+            subject,
+            CreateAccount {
+                default_quota: None,
+            },
+        )
+        .await
+        .unwrap();
+    let project = repo
+        .create_project(
+            subject,
+            &account.id,
+            CreateProject {
+                name: "overlap-project".to_string(),
+                allowed_models: None,
+                default_limits: None,
+                billing_plan: "starter".to_string(),
+                billing_identity: format!("bill-{}", cuid2()),
+                project_quota: None,
+            },
+            "proj_overlap".to_string(),
+        )
+        .await
+        .unwrap();
+    let row = |key_hash: &str| NewApiKeyRow {
+        id: cuid2(),
+        project_id: project.id.clone(),
+        name: "deploy".to_string(),
+        key_prefix: "lbk_deploy".to_string(),
+        key_hash: key_hash.to_string(),
+        created_at: Utc::now(),
+        expires_at: Some(Utc::now() + chrono::Duration::days(30)),
+        status: ApiKeyStatus::Active.to_string(),
+        last_used_at: None,
+        last_ip: None,
+        revoked_at: None,
+        billing_plan: "starter".to_string(),
+        allowed_models: None,
+        scopes: None,
+        allowed_cidrs: None,
+    };
+    let old = repo.create_api_key(subject, row("hash_old")).await.unwrap();
+
+    let cut_off = Utc::now() + chrono::Duration::hours(1);
+    let new = repo
+        .rotate_api_key_transaction(
+            subject,
+            &old.id,
+            ApiKeyStatus::Active,
+            None,
+            Some(cut_off),
+            row("hash_new"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(new.predecessor_id.as_deref(), Some(old.id.as_str()));
+    let old_now = repo.get_api_key(subject, &old.id).await.unwrap().unwrap();
+    assert_eq!(old_now.status, ApiKeyStatus::Active);
+    assert_eq!(old_now.successor_id.as_deref(), Some(new.id.as_str()));
+    assert!(old_now.rotating_until.is_some());
+
+    let hashes = ["hash_old".to_string(), "hash_new".to_string()];
+    let both = repo
+        .find_api_key_validations_by_hashes(&hashes)
+        .await
+        .unwrap();
+    assert_eq!(both.len(), 2);
+    assert!(both.iter().all(|validation| validation.is_active()));
+
+    let again = repo
+        .rotate_api_key_transaction(
+            subject,
+            &old.id,
+            ApiKeyStatus::Revoked,
+            Some(Utc::now()),
+            None,
+            row("hash_again"),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(again, Error::NotFound),
+        "a rotated key has one successor"
+    );
+
+    assert!(
+        repo.finalize_rotated_api_keys(Utc::now())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    sqlx::query("UPDATE api_keys SET rotating_until = now() - interval '1 second' WHERE id = $1")
+        .bind(&old.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let past = repo
+        .find_api_key_validation_by_hash(&["hash_old".to_string()])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(past.effective_status, "key_rotated");
+
+    let swept = repo.finalize_rotated_api_keys(Utc::now()).await.unwrap();
+    assert_eq!(swept, vec![old.id.clone()]);
+    assert!(
+        repo.finalize_rotated_api_keys(Utc::now())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let (status, reason): (String, Option<String>) =
+        sqlx::query_as("SELECT status, revocation_reason FROM api_keys WHERE id = $1")
+            .bind(&old.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(status, "revoked");
+    assert_eq!(reason.as_deref(), Some("rotated"));
+}
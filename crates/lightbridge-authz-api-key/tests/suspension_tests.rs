@@ -69,6 +69,9 @@ async fn seed_key(
             last_ip: None,
             revoked_at: None,
             billing_plan: "free".to_string(),
+            allowed_models: None,
+            scopes: None,
+            allowed_cidrs: None,
         },
     )
     .await
@@ -77,7 +80,7 @@ async fn seed_key(
 }
 
 async fn effective_status(repo: &StoreRepo, key_hash: &str) -> String {
-    repo.find_api_key_validation_by_hash(key_hash)
+    repo.find_api_key_validation_by_hash(&[key_hash.to_string()])
         .await
         .expect("validation lookup should succeed")
         .expect("validation row should exist")
@@ -147,3 +150,57 @@ async fn set_account_status_requires_membership(pool: PgPool) {
         lightbridge_authz_core::error::Error::NotFound
     ));
 }
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn batch_validation_lookup_returns_only_existing_rows(pool: PgPool) {
+    let repo = build_repo(pool);
+    let (_account_id, _project_id, key_hash) = seed_key(&repo, "user-1", far_future()).await;
+
+    let found = repo
+        .find_api_key_validations_by_hashes(&[key_hash.clone(), "no-such-hash".to_string()])
+        .await
+        .expect("batch validation lookup should succeed");
+
+    assert_eq!(found.len(), 1, "an unknown hash must simply be absent");
+    assert_eq!(found[0].key_hash, key_hash);
+    assert_eq!(found[0].effective_status, "active");
+    assert!(
+        repo.find_api_key_validations_by_hashes(&[])
+            .await
+            .expect("an empty batch should succeed")
+            .is_empty()
+    );
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn legacy_hash_matches_as_a_later_candidate_and_upgrades_in_place(pool: PgPool) {
+    let repo = build_repo(pool);
+    let (_account_id, _project_id, legacy_hash) = seed_key(&repo, "user-1", far_future()).await;
+    let current_hash = "v1:p1:current".to_string();
+
+    let found = repo
+        .find_api_key_validation_by_hash(&[current_hash.clone(), legacy_hash.clone()])
+        .await
+        .expect("candidate lookup should succeed")
+        .expect("the legacy candidate should match");
+    assert_eq!(found.key_hash, legacy_hash);
+
+    assert!(
+        repo.upgrade_api_key_hash(&found.api_key_id, &legacy_hash, &current_hash)
+            .await
+            .expect("upgrade should succeed")
+    );
+    assert!(
+        !repo
+            .upgrade_api_key_hash(&found.api_key_id, &legacy_hash, "v1:p1:other")
+            .await
+            .expect("a stale upgrade should still succeed"),
+        "an upgrade from a hash the row no longer carries must not apply"
+    );
+    let upgraded = repo
+        .find_api_key_validation_by_hash(&[current_hash.clone(), legacy_hash])
+        .await
+        .expect("candidate lookup should succeed")
+        .expect("the upgraded row should match its current hash");
+    assert_eq!(upgraded.key_hash, current_hash);
+}
@@ -66,6 +66,8 @@ fn oauth2_config(jwks_url: String, audience: Option<Vec<String>>, rbac: Rbac) ->
         audience,
         signing: None,
         token_exchange: None,
+        device_authorization: None,
+        authorization_code: None,
         rbac,
         clients: Vec::new(),
     }
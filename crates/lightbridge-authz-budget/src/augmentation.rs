@@ -14,6 +14,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
+use lightbridge_authz_core::audit;
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::db::DbPoolTrait;
 use sqlx::PgPool;
@@ -21,7 +22,6 @@ use sqlx::PgPool;
 use crate::decision::Effect;
 use crate::error::BudgetError;
 use crate::period::Period;
-use crate::tier::BudgetTier;
 
 /// The augmentation-request state machine, quoted verbatim from
 /// `docs/rfc/0001-budget-refill.md`'s "Domain (ADR-0009)" section: "`budget_augmentation_requests`
@@ -95,7 +95,11 @@ pub struct AugmentationRequest {
     pub account_id: String,
     pub project_id: Option<String>,
     pub period: Period,
-    pub requested_tier: BudgetTier,
+    /// The `x-budget-tier` label for `requested_amount_micros` under the ladder active when the
+    /// request was made ([`crate::tier::TierLadder::label_for_amount_micros`]). Display only --
+    /// `requested_amount_micros` is authoritative, and the label is stored as text so a request
+    /// stays readable whatever ladder is active later.
+    pub requested_tier: String,
     pub requested_amount_micros: i64,
     pub status: AugmentationStatus,
     pub policy_effect: Option<Effect>,
@@ -120,7 +124,7 @@ pub struct NewAugmentationRequest {
     pub account_id: String,
     pub project_id: Option<String>,
     pub period: Period,
-    pub requested_tier: BudgetTier,
+    pub requested_tier: String,
     pub requested_amount_micros: i64,
     pub idempotency_key: Option<String>,
 }
@@ -227,7 +231,7 @@ impl RecordedDecision {
 /// Renders an [`Effect`] to the exact string [`AugmentationRequestRow::policy_effect`] stores,
 /// reusing `Effect`'s own `#[serde(rename_all = "snake_case")]` mapping rather than duplicating
 /// a second hand-written match against the same variants (which could silently drift from it).
-fn effect_to_db(effect: Effect) -> String {
+pub(crate) fn effect_to_db(effect: Effect) -> String {
     match serde_json::to_value(effect).expect("Effect always serializes") {
         serde_json::Value::String(s) => s,
         other => unreachable!("Effect must serialize to a JSON string, got {other:?}"),
@@ -376,11 +380,6 @@ impl TryFrom<AugmentationRequestRow> for AugmentationRequest {
                 "stored budget_augmentation_requests.period is invalid: {err}"
             ))
         })?;
-        let requested_tier = BudgetTier::from_str(&row.requested_tier).map_err(|err| {
-            BudgetError::StorageFailed(format!(
-                "stored budget_augmentation_requests.requested_tier is invalid: {err}"
-            ))
-        })?;
         let status = AugmentationStatus::from_str(&row.status).map_err(|err| {
             BudgetError::StorageFailed(format!(
                 "stored budget_augmentation_requests.status is invalid: {err}"
@@ -398,7 +397,7 @@ impl TryFrom<AugmentationRequestRow> for AugmentationRequest {
             account_id: row.account_id,
             project_id: row.project_id,
             period,
-            requested_tier,
+            requested_tier: row.requested_tier,
             requested_amount_micros: row.requested_amount_micros,
             status,
             policy_effect,
@@ -451,9 +450,10 @@ impl AugmentationRepo {
     ) -> Result<AugmentationRequest, BudgetError> {
         let id = cuid2();
         let period_str = request.period.to_string();
-        let requested_tier_str = request.requested_tier.label();
+        let requested_tier_str = request.requested_tier.as_str();
         let status_str = AugmentationStatus::Created.as_str();
 
+        let mut tx = audit::begin(self.pool()).await.map_err(storage_failed)?;
         let inserted: Option<AugmentationRequestRow> = sqlx::query_as(REQUEST_INSERT_SQL)
             .bind(&id)
             .bind(&request.budget_account_id)
@@ -464,7 +464,7 @@ impl AugmentationRepo {
             .bind(request.requested_amount_micros)
             .bind(status_str)
             .bind(&request.idempotency_key)
-            .fetch_optional(self.pool())
+            .fetch_optional(&mut *tx)
             .await
             .map_err(storage_failed)?;
 
@@ -472,10 +472,11 @@ impl AugmentationRepo {
             Some(row) => row,
             None => sqlx::query_as(REQUEST_SELECT_BY_IDEMPOTENCY_KEY_SQL)
                 .bind(&request.idempotency_key)
-                .fetch_one(self.pool())
+                .fetch_one(&mut *tx)
                 .await
                 .map_err(storage_failed)?,
         };
+        tx.commit().await.map_err(storage_failed)?;
 
         AugmentationRequest::try_from(row)
     }
@@ -511,6 +512,7 @@ impl AugmentationRepo {
         let decomposed = decision.decompose();
         let policy_effect_str = effect_to_db(decomposed.policy_effect);
 
+        let mut tx = audit::begin(self.pool()).await.map_err(storage_failed)?;
         let updated: Option<AugmentationRequestRow> = sqlx::query_as(REQUEST_UPDATE_DECISION_SQL)
             .bind(id)
             .bind(decomposed.status.as_str())
@@ -520,9 +522,10 @@ impl AugmentationRepo {
             .bind(&decomposed.policy_revision)
             .bind(decomposed.approved_amount_micros)
             .bind(&decomposed.grant_id)
-            .fetch_optional(self.pool())
+            .fetch_optional(&mut *tx)
             .await
             .map_err(storage_failed)?;
+        tx.commit().await.map_err(storage_failed)?;
 
         let row =
             updated.ok_or_else(|| BudgetError::NotFound(format!("augmentation request '{id}'")))?;
@@ -572,15 +575,17 @@ impl AugmentationRepo {
             }
         }
 
+        let mut tx = audit::begin(self.pool()).await.map_err(storage_failed)?;
         let updated: Option<AugmentationRequestRow> = sqlx::query_as(REQUEST_UPDATE_REVIEW_SQL)
             .bind(id)
             .bind(status.as_str())
             .bind(reviewed_by)
             .bind(rejection_reason)
             .bind(grant_id)
-            .fetch_optional(self.pool())
+            .fetch_optional(&mut *tx)
             .await
             .map_err(storage_failed)?;
+        tx.commit().await.map_err(storage_failed)?;
 
         let row = updated.ok_or_else(|| BudgetError::AlreadyReviewed(id.to_string()))?;
 
@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::BudgetError;
 use crate::facts::Facts;
+use crate::tier::TierLadder;
 
 /// What a policy decision resolves to. Per ADR-0007, `AutoApprove`/`AutoApproveCapped` are the
 /// only effects that authorize a grant; everything else must not result in one.
@@ -87,6 +88,12 @@ pub trait PolicyEngine: Send + Sync + std::fmt::Debug {
     /// never greater than `starting_amount_micros` (see `rule_data::validate`), so an outage can
     /// never grant more than a legitimate new signup would get.
     fn fail_closed_floor_micros(&self) -> i64;
+
+    /// The active policy's budget-tier ladder (ADR-0008), read by everything that resolves or
+    /// stamps an `x-budget-tier` label. Synchronous and infallible for the same reason as
+    /// [`Self::allowed_amounts_micros`]; returned by value since the ladder is small and a caller
+    /// must not observe it changing mid-request when a new revision is activated.
+    fn tier_ladder(&self) -> TierLadder;
 }
 
 #[cfg(test)]
@@ -168,6 +175,11 @@ mod tests {
             self_service_grant_count: 0,
             spend_this_period: Spend::Unavailable,
             spend_last_period: Spend::Known(5_000_000),
+            billing_plan: None,
+            quota_tier: None,
+            account_age_days: None,
+            day_of_period: None,
+            days_in_period: None,
         };
         assert!(matches!(facts.spend_this_period, Spend::Unavailable));
     }
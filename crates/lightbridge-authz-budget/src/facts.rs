@@ -40,6 +40,28 @@ pub struct Facts {
     /// Spend for the immediately preceding period (ADR-0007's own example: "approve up to 20% of
     /// last period's consumption"). Same `Spend`/`Unavailable` discipline applies.
     pub spend_last_period: Spend,
+    /// The billing plan of the project the refill is for (`projects.billing_plan`). `None` when
+    /// the request names no project.
+    #[serde(default)]
+    pub billing_plan: Option<String>,
+    /// The requesting account's quota tier on that project (`project_members.quota_tier`). `None`
+    /// when there is no project, the account is not on its roster, or no tier is assigned.
+    #[serde(default)]
+    pub quota_tier: Option<String>,
+    /// Whole days since the requesting account was created (`accounts.created_at`). `None` when
+    /// the account row could not be found -- a rule comparing it then fails closed, exactly like
+    /// `Spend::Unavailable`.
+    #[serde(default)]
+    pub account_age_days: Option<i64>,
+    /// The 1-based day of the requested period on which the request was made. `None` when the
+    /// request is for a period other than the one containing its own timestamp, where "how far
+    /// into the period" has no meaning.
+    #[serde(default)]
+    pub day_of_period: Option<i32>,
+    /// How many days the requested period has (28..=31). Together with `day_of_period` this
+    /// lets a rule say "the last N days of the month" regardless of month length.
+    #[serde(default)]
+    pub days_in_period: Option<i32>,
 }
 
 #[cfg(test)]
@@ -53,6 +75,11 @@ mod tests {
             self_service_grant_count: 1,
             spend_this_period: Spend::Known(10_000_000),
             spend_last_period: Spend::Known(20_000_000),
+            billing_plan: None,
+            quota_tier: None,
+            account_age_days: None,
+            day_of_period: None,
+            days_in_period: None,
         };
 
         assert_eq!(facts.effective_balance_micros, 42_000_000);
@@ -68,6 +95,11 @@ mod tests {
             self_service_grant_count: 0,
             spend_this_period: Spend::Unavailable,
             spend_last_period: Spend::Unavailable,
+            billing_plan: None,
+            quota_tier: None,
+            account_age_days: None,
+            day_of_period: None,
+            days_in_period: None,
         };
 
         assert!(matches!(facts.spend_this_period, Spend::Unavailable));
@@ -81,6 +113,11 @@ mod tests {
             self_service_grant_count: 1,
             spend_this_period: Spend::Known(10_000_000),
             spend_last_period: Spend::Unavailable,
+            billing_plan: None,
+            quota_tier: None,
+            account_age_days: None,
+            day_of_period: None,
+            days_in_period: None,
         };
 
         let json = serde_json::to_string(&facts).expect("facts must serialize");
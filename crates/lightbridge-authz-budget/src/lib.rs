@@ -5,20 +5,24 @@
 //! policy engine code directly instead of relying on generated CRUD.
 //!
 //! Per ADR-0007, [`decision`]/[`facts`] define the decision contract and fact set that any
-//! policy engine sits behind (the [`decision::PolicyEngine`] trait); [`rule_data`] is the first
-//! (rule-data-driven) evaluator against it, with an OPA-Wasm evaluator planned for a later PR --
-//! see `docs/budget-decision-contract.md`.
+//! policy engine sits behind (the [`decision::PolicyEngine`] trait); [`rule_data`] is the
+//! rule-data-driven evaluator against it and [`opa_wasm`] runs compiled Rego, with
+//! [`policy_document`] selecting between them per policy revision -- see
+//! `docs/budget-decision-contract.md`.
 
 pub mod amount;
 pub mod augmentation;
 pub mod decision;
 pub mod error;
 pub mod facts;
+pub mod opa_wasm;
 pub mod period;
+pub mod policy_document;
 pub mod policy_store;
 pub mod refill;
 pub mod repo;
 pub mod review;
+pub mod rollover;
 pub mod rule_data;
 pub mod source;
 pub mod spend;
@@ -32,14 +36,17 @@ pub use augmentation::{
 pub use decision::{Decision, Effect, Obligations, PolicyEngine};
 pub use error::BudgetError;
 pub use facts::Facts;
+pub use opa_wasm::{OPA_WASM_EVALUATION_FUEL, OpaWasmEngine, OpaWasmPolicy};
 pub use period::Period;
+pub use policy_document::{ActivePolicyEngine, PolicyDocument, validate_policy_document};
 pub use policy_store::PolicyStore;
 pub use refill::{RefillRequest, RefillService, RefillStatus};
 pub use review::ReviewService;
+pub use rollover::{RolloverReport, RolloverService};
 pub use rule_data::{
-    Condition, Field, Operator, Rule, RuleDataEngine, RuleSet, default_rule_set_json,
+    Condition, Field, Operator, Rule, RuleDataEngine, RuleSet, StringField, default_rule_set_json,
     validate_rule_data,
 };
 pub use source::GrantSource;
 pub use spend::{Spend, SpendReader, UnavailableSpendReader, UsageServiceSpendReader};
-pub use tier::BudgetTier;
+pub use tier::{BudgetTier, TierLadder};
@@ -0,0 +1,838 @@
+//! The OPA-Wasm policy evaluator ADR-0007 planned from the start: "the path administrators who
+//! outgrow threshold rules use". An administrator writes real Rego, compiles it with
+//! `opa build -t wasm -e <entrypoint>`, and activates the resulting `policy.wasm` (plus, when the
+//! bundle has one, its `data.json`) as an ordinary `budget_policy_revisions` row -- see
+//! [`OpaWasmPolicy`] for the document shape and `docs/budget-decision-contract.md` for what the
+//! policy receives and must return.
+//!
+//! ## The ABI subset this host implements
+//!
+//! OPA's Wasm ABI (<https://www.openpolicyagent.org/docs/latest/wasm/>) from version 1.2 onward
+//! exposes a single-call `opa_eval` export, which is the only evaluation path used here. The host
+//! provides `env.memory` and the `opa_abort`/`opa_println` callbacks; it does NOT implement any
+//! non-native builtins (`opa_builtin0`..`opa_builtin4`). A module whose `builtins()` export lists
+//! any is rejected at activation rather than trapping on its first evaluation, so "this policy
+//! needs a builtin we don't provide" is an activation error an administrator sees immediately,
+//! never a production refill that silently fails closed forever.
+//!
+//! ## Why fuel, not a wall-clock timeout
+//!
+//! The same reasoning [`crate::rule_data::RuleDataEngine`] gives for its node-count budget
+//! applies: evaluation is synchronous, in-process computation with no I/O, so a deterministic
+//! instruction budget bounds it exactly as a timeout would while staying reproducible in tests.
+//! Every evaluation runs in a fresh [`Store`] with [`OPA_WASM_EVALUATION_FUEL`] units of fuel and
+//! a [`OPA_WASM_MEMORY_LIMIT_BYTES`] cap on linear memory; exhausting either aborts evaluation.
+//!
+//! ## Failing closed
+//!
+//! Anything short of a well-formed decision -- fuel exhausted, a trap or `opa_abort`, an undefined
+//! entrypoint result, a result that is not a [`Decision`]-shaped object, or an approval larger
+//! than what was requested -- resolves to `Effect::Deny` with `maximum_amount_micros` capped at
+//! the policy's `fail_closed_floor_micros`, never an `Err` and never an approval.
+
+use std::collections::HashMap;
+
+use base64::Engine as _;
+use serde::Deserialize;
+use wasmi::core::{Pages, TrapCode};
+use wasmi::{Caller, Config, Engine, ExternType, Linker, Memory, Module, Store, StoreLimits};
+
+use crate::decision::{Decision, Effect, Obligations, PolicyEngine};
+use crate::error::BudgetError;
+use crate::facts::Facts;
+use crate::rule_data::validate_amounts;
+use crate::tier::TierLadder;
+
+/// Fuel granted to one evaluation. A compiled Rego decision over this crate's handful of facts
+/// costs in the tens of thousands of units; this leaves two orders of magnitude of headroom while
+/// still bounding a runaway policy to well under a second.
+pub const OPA_WASM_EVALUATION_FUEL: u64 = 10_000_000;
+
+/// Ceiling on one evaluation's linear memory. OPA modules start at two 64 KiB pages and grow on
+/// demand; a decision over a few facts never approaches this.
+pub const OPA_WASM_MEMORY_LIMIT_BYTES: usize = 64 * 1024 * 1024;
+
+/// `opa_eval`'s `format` argument selecting JSON output.
+const OPA_EVAL_FORMAT_JSON: i32 = 0;
+
+/// The stored document for a Wasm policy revision. Distinguished from a rule-data document by
+/// `"engine": "opa_wasm"` (see [`crate::policy_document::PolicyDocument`]). The three amount
+/// fields carry the same meaning and validation as on [`crate::rule_data::RuleSet`] -- they are
+/// host-side configuration, not something the policy computes.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OpaWasmPolicy {
+    pub policy_revision: String,
+    /// The entrypoint the module was built with (`opa build -e`), e.g. `budget/refill/decision`.
+    pub entrypoint: String,
+    /// The compiled `policy.wasm`, standard base64.
+    pub module_base64: String,
+    /// The bundle's `data.json`, handed to every evaluation as `data`. Absent means `{}`.
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+    pub allowed_amounts_micros: Vec<i64>,
+    pub starting_amount_micros: i64,
+    pub fail_closed_floor_micros: i64,
+    /// The budget-tier ladder this revision serves; see [`crate::rule_data::RuleSet::tier_ladder`].
+    #[serde(default)]
+    pub tier_ladder: TierLadder,
+}
+
+/// What the entrypoint must evaluate to. Only `effect` is required; see [`normalize`] for how the
+/// amounts default per effect and which values are refused.
+#[derive(Debug, Deserialize)]
+struct PolicyOutput {
+    effect: Effect,
+    #[serde(default)]
+    approved_amount_micros: Option<i64>,
+    #[serde(default)]
+    maximum_amount_micros: Option<i64>,
+    #[serde(default)]
+    reason_codes: Vec<String>,
+    #[serde(default)]
+    matched_rule_ids: Vec<String>,
+    #[serde(default)]
+    obligations: Obligations,
+}
+
+/// Why an evaluation produced no usable decision.
+#[derive(Debug)]
+enum EvalFailure {
+    FuelExhausted,
+    Failed(String),
+}
+
+impl From<wasmi::Error> for EvalFailure {
+    fn from(err: wasmi::Error) -> Self {
+        if err.as_trap_code() == Some(TrapCode::OutOfFuel) {
+            EvalFailure::FuelExhausted
+        } else {
+            EvalFailure::Failed(err.to_string())
+        }
+    }
+}
+
+/// A compiled, validated Wasm policy. Immutable once built -- hot-swapping to a new revision
+/// builds a new engine (see [`crate::policy_document::ActivePolicyEngine`]).
+pub struct OpaWasmEngine {
+    engine: Engine,
+    module: Module,
+    entrypoint_id: i32,
+    data_json: String,
+    policy: OpaWasmPolicy,
+    fuel: u64,
+}
+
+impl std::fmt::Debug for OpaWasmEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpaWasmEngine")
+            .field("policy_revision", &self.policy.policy_revision)
+            .field("entrypoint", &self.policy.entrypoint)
+            .field("entrypoint_id", &self.entrypoint_id)
+            .field("fuel", &self.fuel)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OpaWasmEngine {
+    /// Validates `policy` and compiles its module: the amounts, the module itself, the ABI
+    /// exports this host calls, that it needs no builtins, and that `entrypoint` exists. Every
+    /// failure is [`BudgetError::InvalidRuleData`], so activation refuses the revision before it
+    /// is ever written.
+    pub fn new(policy: OpaWasmPolicy, fuel: u64) -> Result<Self, BudgetError> {
+        if policy.policy_revision.trim().is_empty() {
+            return Err(invalid("policy_revision must not be empty"));
+        }
+        if policy.entrypoint.trim().is_empty() {
+            return Err(invalid("entrypoint must not be empty"));
+        }
+        validate_amounts(
+            &policy.allowed_amounts_micros,
+            policy.starting_amount_micros,
+            policy.fail_closed_floor_micros,
+        )?;
+        let data_json = match &policy.data {
+            None => "{}".to_string(),
+            Some(data) if data.is_object() => data.to_string(),
+            Some(_) => return Err(invalid("data must be a JSON object")),
+        };
+
+        let wasm = base64::engine::general_purpose::STANDARD
+            .decode(policy.module_base64.trim())
+            .map_err(|err| invalid(&format!("module_base64 is not valid base64: {err}")))?;
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, &wasm[..])
+            .map_err(|err| invalid(&format!("module is not valid WebAssembly: {err}")))?;
+        for export in [
+            "opa_eval",
+            "opa_malloc",
+            "opa_json_parse",
+            "opa_json_dump",
+            "opa_heap_ptr_get",
+            "entrypoints",
+            "builtins",
+        ] {
+            if module.get_export(export).is_none() {
+                return Err(invalid(&format!(
+                    "module does not export `{export}` -- it must be built by `opa build -t wasm` \
+                     with Wasm ABI 1.2 or later"
+                )));
+            }
+        }
+
+        let mut engine = Self {
+            engine,
+            module,
+            entrypoint_id: 0,
+            data_json,
+            policy,
+            fuel,
+        };
+        engine.entrypoint_id = engine.resolve_entrypoint()?;
+        Ok(engine)
+    }
+
+    /// Instantiates the module once to read its `builtins()` and `entrypoints()` maps.
+    fn resolve_entrypoint(&self) -> Result<i32, BudgetError> {
+        let introspect = || -> Result<i32, EvalFailure> {
+            let (mut store, instance, memory) = self.instantiate()?;
+            let builtins_addr = call0(&mut store, &instance, "builtins")?;
+            let builtins: HashMap<String, i32> =
+                dump_json(&mut store, &instance, memory, builtins_addr)?;
+            if !builtins.is_empty() {
+                let mut names: Vec<_> = builtins.into_keys().collect();
+                names.sort();
+                return Err(EvalFailure::Failed(format!(
+                    "module requires builtins this host does not provide: {}",
+                    names.join(", ")
+                )));
+            }
+            let entrypoints_addr = call0(&mut store, &instance, "entrypoints")?;
+            let entrypoints: HashMap<String, i32> =
+                dump_json(&mut store, &instance, memory, entrypoints_addr)?;
+            entrypoints
+                .get(&self.policy.entrypoint)
+                .copied()
+                .ok_or_else(|| {
+                    let mut names: Vec<_> = entrypoints.into_keys().collect();
+                    names.sort();
+                    EvalFailure::Failed(format!(
+                        "entrypoint '{}' is not built into the module (has: {})",
+                        self.policy.entrypoint,
+                        names.join(", ")
+                    ))
+                })
+        };
+        introspect().map_err(|failure| match failure {
+            EvalFailure::FuelExhausted => {
+                invalid("module exhausted its fuel before evaluation could begin")
+            }
+            EvalFailure::Failed(message) => invalid(&message),
+        })
+    }
+
+    /// A fresh store, with the fuel and memory limits applied, and an instance linked against it.
+    fn instantiate(&self) -> Result<(Store<StoreLimits>, wasmi::Instance, Memory), wasmi::Error> {
+        let limits = wasmi::StoreLimitsBuilder::new()
+            .memory_size(OPA_WASM_MEMORY_LIMIT_BYTES)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(self.fuel)?;
+
+        let memory_type = self
+            .module
+            .imports()
+            .find_map(|import| match import.ty() {
+                ExternType::Memory(ty) if import.module() == "env" && import.name() == "memory" => {
+                    Some(*ty)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| wasmi::Error::new("module does not import env.memory"))?;
+        let memory = Memory::new(&mut store, memory_type)?;
+
+        let mut linker = Linker::<StoreLimits>::new(&self.engine);
+        linker.define("env", "memory", memory)?;
+        linker.func_wrap(
+            "env",
+            "opa_abort",
+            move |caller: Caller<'_, StoreLimits>, addr: i32| -> Result<(), wasmi::Error> {
+                let message = read_c_string(memory.data(&caller), addr)
+                    .unwrap_or_else(|| "<unreadable>".to_string());
+                Err(wasmi::Error::new(format!("opa_abort: {message}")))
+            },
+        )?;
+        linker.func_wrap("env", "opa_println", |_addr: i32| {})?;
+        linker.func_wrap(
+            "env",
+            "opa_builtin0",
+            |_id: i32, _ctx: i32| -> Result<i32, wasmi::Error> { Err(unsupported_builtin()) },
+        )?;
+        linker.func_wrap(
+            "env",
+            "opa_builtin1",
+            |_id: i32, _ctx: i32, _a: i32| -> Result<i32, wasmi::Error> {
+                Err(unsupported_builtin())
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "opa_builtin2",
+            |_id: i32, _ctx: i32, _a: i32, _b: i32| -> Result<i32, wasmi::Error> {
+                Err(unsupported_builtin())
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "opa_builtin3",
+            |_id: i32, _ctx: i32, _a: i32, _b: i32, _c: i32| -> Result<i32, wasmi::Error> {
+                Err(unsupported_builtin())
+            },
+        )?;
+        linker.func_wrap(
+            "env",
+            "opa_builtin4",
+            |_id: i32,
+             _ctx: i32,
+             _a: i32,
+             _b: i32,
+             _c: i32,
+             _d: i32|
+             -> Result<i32, wasmi::Error> { Err(unsupported_builtin()) },
+        )?;
+
+        let instance = linker
+            .instantiate(&mut store, &self.module)?
+            .start(&mut store)?;
+        Ok((store, instance, memory))
+    }
+
+    /// One `opa_eval` call: load `data`, place `input` at the heap pointer, evaluate, and parse
+    /// the single result the entrypoint produced.
+    fn run(&self, input: &serde_json::Value) -> Result<PolicyOutput, EvalFailure> {
+        let (mut store, instance, memory) = self.instantiate()?;
+
+        let data_addr = write_bytes(&mut store, &instance, memory, self.data_json.as_bytes())?;
+        let parse = instance
+            .get_typed_func::<(i32, i32), i32>(&store, "opa_json_parse")
+            .map_err(EvalFailure::from)?;
+        let data_value = parse.call(&mut store, (data_addr, len_i32(self.data_json.len())?))?;
+        if data_value == 0 {
+            return Err(EvalFailure::Failed(
+                "opa_json_parse rejected the policy data".to_string(),
+            ));
+        }
+
+        let input = input.to_string();
+        let input_addr = call0(&mut store, &instance, "opa_heap_ptr_get")?;
+        let input_end = usize::try_from(input_addr)
+            .ok()
+            .and_then(|addr| addr.checked_add(input.len()))
+            .ok_or_else(|| EvalFailure::Failed("input does not fit in memory".to_string()))?;
+        ensure_memory(&mut store, memory, input_end)?;
+        memory
+            .write(&mut store, input_end - input.len(), input.as_bytes())
+            .map_err(|err| EvalFailure::Failed(err.to_string()))?;
+
+        let eval = instance
+            .get_typed_func::<(i32, i32, i32, i32, i32, i32, i32), i32>(&store, "opa_eval")
+            .map_err(EvalFailure::from)?;
+        let input_len = len_i32(input.len())?;
+        let result_addr = eval.call(
+            &mut store,
+            (
+                0,
+                self.entrypoint_id,
+                data_value,
+                input_addr,
+                input_len,
+                input_addr + input_len,
+                OPA_EVAL_FORMAT_JSON,
+            ),
+        )?;
+
+        let result = read_c_string(memory.data(&store), result_addr).ok_or_else(|| {
+            EvalFailure::Failed("opa_eval returned an unreadable result".to_string())
+        })?;
+        let mut results: Vec<ResultEntry> = serde_json::from_str(&result)
+            .map_err(|err| EvalFailure::Failed(format!("result is not a result set: {err}")))?;
+        match results.len() {
+            0 => Err(EvalFailure::Failed(
+                "the entrypoint is undefined for this input".to_string(),
+            )),
+            1 => serde_json::from_value(results.remove(0).result).map_err(|err| {
+                EvalFailure::Failed(format!("the entrypoint did not return a decision: {err}"))
+            }),
+            n => Err(EvalFailure::Failed(format!(
+                "the entrypoint produced {n} results, expected exactly one"
+            ))),
+        }
+    }
+
+    fn fail_closed(&self, requested_amount_micros: i64, reason_code: &str) -> Decision {
+        Decision {
+            effect: Effect::Deny,
+            approved_amount_micros: 0,
+            maximum_amount_micros: requested_amount_micros
+                .min(self.policy.fail_closed_floor_micros),
+            reason_codes: vec![reason_code.to_string()],
+            matched_rule_ids: vec![],
+            policy_revision: self.policy.policy_revision.clone(),
+            obligations: Obligations::default(),
+        }
+    }
+
+    pub fn policy_revision(&self) -> &str {
+        &self.policy.policy_revision
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ResultEntry {
+    result: serde_json::Value,
+}
+
+/// Fills in the amounts a policy may leave implicit, mirroring the rule-data engine's
+/// `decision_for_effect`, and refuses the ones the host must never act on: an approval of zero,
+/// a negative amount, or more than was requested.
+fn normalize(
+    output: PolicyOutput,
+    requested_amount_micros: i64,
+    policy_revision: &str,
+) -> Result<Decision, String> {
+    let maximum_amount_micros = output
+        .maximum_amount_micros
+        .unwrap_or(requested_amount_micros);
+    let approved_amount_micros = match output.effect {
+        Effect::AutoApprove => output
+            .approved_amount_micros
+            .unwrap_or(requested_amount_micros),
+        Effect::AutoApproveCapped => output
+            .approved_amount_micros
+            .unwrap_or(requested_amount_micros.min(maximum_amount_micros)),
+        Effect::ManualReview | Effect::Deny | Effect::NoAction => 0,
+    };
+    let approves = matches!(
+        output.effect,
+        Effect::AutoApprove | Effect::AutoApproveCapped
+    );
+    if approves && (approved_amount_micros <= 0 || approved_amount_micros > requested_amount_micros)
+    {
+        return Err(format!(
+            "approved_amount_micros {approved_amount_micros} is outside (0, {requested_amount_micros}]"
+        ));
+    }
+    if maximum_amount_micros < 0 {
+        return Err(format!(
+            "maximum_amount_micros {maximum_amount_micros} is negative"
+        ));
+    }
+
+    Ok(Decision {
+        effect: output.effect,
+        approved_amount_micros,
+        maximum_amount_micros,
+        reason_codes: output.reason_codes,
+        matched_rule_ids: output.matched_rule_ids,
+        policy_revision: policy_revision.to_string(),
+        obligations: output.obligations,
+    })
+}
+
+fn invalid(message: &str) -> BudgetError {
+    BudgetError::InvalidRuleData(format!("opa_wasm policy: {message}"))
+}
+
+fn unsupported_builtin() -> wasmi::Error {
+    wasmi::Error::new("the policy called a builtin this host does not provide")
+}
+
+fn len_i32(len: usize) -> Result<i32, EvalFailure> {
+    i32::try_from(len).map_err(|_| EvalFailure::Failed(format!("{len} bytes do not fit in i32")))
+}
+
+fn call0(
+    store: &mut Store<StoreLimits>,
+    instance: &wasmi::Instance,
+    name: &str,
+) -> Result<i32, EvalFailure> {
+    let func = instance.get_typed_func::<(), i32>(&*store, name)?;
+    Ok(func.call(store, ())?)
+}
+
+/// Copies `bytes` into a fresh `opa_malloc` allocation and returns its address.
+fn write_bytes(
+    store: &mut Store<StoreLimits>,
+    instance: &wasmi::Instance,
+    memory: Memory,
+    bytes: &[u8],
+) -> Result<i32, EvalFailure> {
+    let malloc = instance.get_typed_func::<i32, i32>(&*store, "opa_malloc")?;
+    let addr = malloc.call(&mut *store, len_i32(bytes.len())?)?;
+    let offset = usize::try_from(addr)
+        .map_err(|_| EvalFailure::Failed(format!("opa_malloc returned {addr}")))?;
+    memory
+        .write(store, offset, bytes)
+        .map_err(|err| EvalFailure::Failed(err.to_string()))?;
+    Ok(addr)
+}
+
+/// `opa_json_dump`s the value at `value_addr` and deserializes it.
+fn dump_json<T: serde::de::DeserializeOwned>(
+    store: &mut Store<StoreLimits>,
+    instance: &wasmi::Instance,
+    memory: Memory,
+    value_addr: i32,
+) -> Result<T, EvalFailure> {
+    let dump = instance.get_typed_func::<i32, i32>(&*store, "opa_json_dump")?;
+    let json_addr = dump.call(&mut *store, value_addr)?;
+    let json = read_c_string(memory.data(&*store), json_addr)
+        .ok_or_else(|| EvalFailure::Failed("opa_json_dump returned an unreadable string".into()))?;
+    serde_json::from_str(&json).map_err(|err| EvalFailure::Failed(err.to_string()))
+}
+
+/// Grows `memory` until it is at least `len` bytes long.
+fn ensure_memory(
+    store: &mut Store<StoreLimits>,
+    memory: Memory,
+    len: usize,
+) -> Result<(), EvalFailure> {
+    const PAGE_BYTES: usize = 64 * 1024;
+    let current = memory.data(&*store).len();
+    if len <= current {
+        return Ok(());
+    }
+    let additional = u32::try_from((len - current).div_ceil(PAGE_BYTES))
+        .ok()
+        .and_then(Pages::new)
+        .ok_or_else(|| EvalFailure::Failed("input does not fit in memory".to_string()))?;
+    memory
+        .grow(store, additional)
+        .map(|_| ())
+        .map_err(|err| EvalFailure::Failed(format!("could not grow memory for input: {err}")))
+}
+
+/// The NUL-terminated UTF-8 string at `addr`, or `None` when it runs off the end of memory.
+fn read_c_string(memory: &[u8], addr: i32) -> Option<String> {
+    let start = usize::try_from(addr).ok()?;
+    let tail = memory.get(start..)?;
+    let len = tail.iter().position(|&byte| byte == 0)?;
+    String::from_utf8(tail[..len].to_vec()).ok()
+}
+
+#[lightbridge_authz_core::async_trait]
+impl PolicyEngine for OpaWasmEngine {
+    async fn evaluate(
+        &self,
+        facts: &Facts,
+        requested_amount_micros: i64,
+    ) -> Result<Decision, BudgetError> {
+        let input = serde_json::json!({
+            "facts": facts,
+            "requested_amount_micros": requested_amount_micros,
+        });
+        let outcome = self.run(&input).and_then(|output| {
+            normalize(
+                output,
+                requested_amount_micros,
+                &self.policy.policy_revision,
+            )
+            .map_err(EvalFailure::Failed)
+        });
+
+        Ok(match outcome {
+            Ok(decision) => decision,
+            Err(EvalFailure::FuelExhausted) => {
+                tracing::error!(
+                    policy_revision = %self.policy.policy_revision,
+                    fuel = self.fuel,
+                    "opa_wasm policy exhausted its fuel; failing closed to deny"
+                );
+                self.fail_closed(requested_amount_micros, "evaluation_budget_exceeded")
+            }
+            Err(EvalFailure::Failed(message)) => {
+                tracing::error!(
+                    policy_revision = %self.policy.policy_revision,
+                    error = %message,
+                    "opa_wasm policy evaluation failed; failing closed to deny"
+                );
+                self.fail_closed(requested_amount_micros, "policy_evaluation_failed")
+            }
+        })
+    }
+
+    fn allowed_amounts_micros(&self) -> Vec<i64> {
+        self.policy.allowed_amounts_micros.clone()
+    }
+
+    fn starting_amount_micros(&self) -> i64 {
+        self.policy.starting_amount_micros
+    }
+
+    fn fail_closed_floor_micros(&self) -> i64 {
+        self.policy.fail_closed_floor_micros
+    }
+
+    fn tier_ladder(&self) -> TierLadder {
+        self.policy.tier_ladder.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spend::Spend;
+
+    const ENTRYPOINT: &str = "budget/refill/decision";
+
+    /// A module implementing just enough of OPA's Wasm ABI for the host to drive it: a bump
+    /// allocator, identity `opa_json_parse`/`opa_json_dump` (values are their own JSON text), and
+    /// an `opa_eval` running `eval_body`, which must leave a result-set address on the stack. The
+    /// canned result set lives at 1024.
+    fn module(result_set: &str, entrypoints: &str, builtins: &str, eval_body: &str) -> String {
+        let escape = |json: &str| json.replace('\\', "\\\\").replace('"', "\\\"");
+        let wat = format!(
+            r#"(module
+  (import "env" "memory" (memory 2))
+  (import "env" "opa_abort" (func $abort (param i32)))
+  (global $heap (mut i32) (i32.const 65536))
+  (data (i32.const 1024) "{result}\00")
+  (data (i32.const 4096) "{entrypoints}\00")
+  (data (i32.const 8192) "{builtins}\00")
+  (data (i32.const 12288) "boom\00")
+  (func (export "opa_malloc") (param $n i32) (result i32) (local $p i32)
+    global.get $heap
+    local.set $p
+    global.get $heap
+    local.get $n
+    i32.add
+    global.set $heap
+    local.get $p)
+  (func (export "opa_heap_ptr_get") (result i32) global.get $heap)
+  (func (export "opa_json_parse") (param i32 i32) (result i32) local.get 0)
+  (func (export "opa_json_dump") (param i32) (result i32) local.get 0)
+  (func (export "entrypoints") (result i32) i32.const 4096)
+  (func (export "builtins") (result i32) i32.const 8192)
+  (func (export "opa_eval") (param i32 i32 i32 i32 i32 i32 i32) (result i32) {eval_body}))"#,
+            result = escape(result_set),
+            entrypoints = escape(entrypoints),
+            builtins = escape(builtins),
+        );
+        let wasm = wat::parse_str(&wat).expect("fixture module assembles");
+        base64::engine::general_purpose::STANDARD.encode(wasm)
+    }
+
+    fn policy(module_base64: String) -> OpaWasmPolicy {
+        OpaWasmPolicy {
+            policy_revision: "budget-policy-wasm-v1".to_string(),
+            entrypoint: ENTRYPOINT.to_string(),
+            module_base64,
+            data: None,
+            allowed_amounts_micros: vec![5_000_000, 10_000_000],
+            starting_amount_micros: 5_000_000,
+            fail_closed_floor_micros: 2_000_000,
+            tier_ladder: TierLadder::legacy(),
+        }
+    }
+
+    fn returning(result: &str) -> OpaWasmEngine {
+        let result_set = format!(r#"[{{"result":{result}}}]"#);
+        OpaWasmEngine::new(
+            policy(module(
+                &result_set,
+                r#"{"budget/refill/decision":0}"#,
+                "{}",
+                "i32.const 1024",
+            )),
+            OPA_WASM_EVALUATION_FUEL,
+        )
+        .expect("valid policy")
+    }
+
+    fn facts() -> Facts {
+        Facts {
+            effective_balance_micros: 100_000_000,
+            self_service_grant_count: 0,
+            spend_this_period: Spend::Known(0),
+            spend_last_period: Spend::Known(0),
+            billing_plan: None,
+            quota_tier: None,
+            account_age_days: None,
+            day_of_period: None,
+            days_in_period: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn auto_approve_defaults_the_approval_to_the_request() {
+        let engine = returning(
+            r#"{"effect":"auto_approve","reason_codes":["ok"],"matched_rule_ids":["r1"]}"#,
+        );
+
+        let decision = engine
+            .evaluate(&facts(), 5_000_000)
+            .await
+            .expect("evaluation succeeds");
+
+        assert_eq!(decision.effect, Effect::AutoApprove);
+        assert_eq!(decision.approved_amount_micros, 5_000_000);
+        assert_eq!(decision.maximum_amount_micros, 5_000_000);
+        assert_eq!(decision.reason_codes, vec!["ok"]);
+        assert_eq!(decision.matched_rule_ids, vec!["r1"]);
+        assert_eq!(decision.policy_revision, "budget-policy-wasm-v1");
+    }
+
+    #[tokio::test]
+    async fn capped_approval_within_the_request_is_kept() {
+        let engine =
+            returning(r#"{"effect":"auto_approve_capped","maximum_amount_micros":3000000}"#);
+
+        let decision = engine
+            .evaluate(&facts(), 5_000_000)
+            .await
+            .expect("evaluation succeeds");
+
+        assert_eq!(decision.effect, Effect::AutoApproveCapped);
+        assert_eq!(decision.approved_amount_micros, 3_000_000);
+        assert_eq!(decision.maximum_amount_micros, 3_000_000);
+    }
+
+    #[tokio::test]
+    async fn approval_above_the_request_fails_closed() {
+        let engine = returning(r#"{"effect":"auto_approve","approved_amount_micros":50000000}"#);
+
+        let decision = engine
+            .evaluate(&facts(), 5_000_000)
+            .await
+            .expect("failing closed is not an error");
+
+        assert_eq!(decision.effect, Effect::Deny);
+        assert_eq!(decision.approved_amount_micros, 0);
+        assert_eq!(decision.maximum_amount_micros, 2_000_000);
+        assert_eq!(decision.reason_codes, vec!["policy_evaluation_failed"]);
+    }
+
+    #[tokio::test]
+    async fn undefined_result_fails_closed() {
+        let engine = OpaWasmEngine::new(
+            policy(module(
+                "[]",
+                r#"{"budget/refill/decision":0}"#,
+                "{}",
+                "i32.const 1024",
+            )),
+            OPA_WASM_EVALUATION_FUEL,
+        )
+        .expect("valid policy");
+
+        let decision = engine
+            .evaluate(&facts(), 5_000_000)
+            .await
+            .expect("failing closed is not an error");
+
+        assert_eq!(decision.effect, Effect::Deny);
+        assert_eq!(decision.reason_codes, vec!["policy_evaluation_failed"]);
+    }
+
+    #[tokio::test]
+    async fn opa_abort_fails_closed() {
+        let engine = OpaWasmEngine::new(
+            policy(module(
+                "[]",
+                r#"{"budget/refill/decision":0}"#,
+                "{}",
+                "i32.const 12288 call $abort i32.const 1024",
+            )),
+            OPA_WASM_EVALUATION_FUEL,
+        )
+        .expect("valid policy");
+
+        let decision = engine
+            .evaluate(&facts(), 5_000_000)
+            .await
+            .expect("failing closed is not an error");
+
+        assert_eq!(decision.effect, Effect::Deny);
+        assert_eq!(decision.reason_codes, vec!["policy_evaluation_failed"]);
+    }
+
+    #[tokio::test]
+    async fn exhausting_fuel_denies_at_the_fail_closed_floor() {
+        let engine = OpaWasmEngine::new(
+            policy(module(
+                "[]",
+                r#"{"budget/refill/decision":0}"#,
+                "{}",
+                "(loop $spin (br $spin)) i32.const 1024",
+            )),
+            100_000,
+        )
+        .expect("valid policy");
+
+        let decision = engine
+            .evaluate(&facts(), 10_000_000)
+            .await
+            .expect("failing closed is not an error");
+
+        assert_eq!(decision.effect, Effect::Deny);
+        assert_eq!(decision.approved_amount_micros, 0);
+        assert_eq!(decision.maximum_amount_micros, 2_000_000);
+        assert_eq!(decision.reason_codes, vec!["evaluation_budget_exceeded"]);
+    }
+
+    #[test]
+    fn an_entrypoint_the_module_lacks_is_rejected() {
+        let err = OpaWasmEngine::new(
+            policy(module(
+                "[]",
+                r#"{"other/decision":0}"#,
+                "{}",
+                "i32.const 1024",
+            )),
+            OPA_WASM_EVALUATION_FUEL,
+        )
+        .expect_err("missing entrypoint");
+
+        assert!(
+            matches!(&err, BudgetError::InvalidRuleData(m) if m.contains("budget/refill/decision")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn a_module_needing_builtins_is_rejected() {
+        let err = OpaWasmEngine::new(
+            policy(module(
+                "[]",
+                r#"{"budget/refill/decision":0}"#,
+                r#"{"http.send":0}"#,
+                "i32.const 1024",
+            )),
+            OPA_WASM_EVALUATION_FUEL,
+        )
+        .expect_err("builtins unsupported");
+
+        assert!(
+            matches!(&err, BudgetError::InvalidRuleData(m) if m.contains("http.send")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn invalid_amounts_and_modules_are_rejected() {
+        let mut bad_floor = policy(String::new());
+        bad_floor.fail_closed_floor_micros = 9_000_000;
+        assert!(matches!(
+            OpaWasmEngine::new(bad_floor, OPA_WASM_EVALUATION_FUEL),
+            Err(BudgetError::InvalidRuleData(_))
+        ));
+
+        let not_wasm = policy(base64::engine::general_purpose::STANDARD.encode(b"not wasm"));
+        assert!(matches!(
+            OpaWasmEngine::new(not_wasm, OPA_WASM_EVALUATION_FUEL),
+            Err(BudgetError::InvalidRuleData(_))
+        ));
+    }
+}
@@ -93,6 +93,24 @@ impl Period {
         Period::from_ymd(prev_year, prev_month)
             .expect("Period invariant: stepping one month back always yields a valid period")
     }
+
+    /// How many days this calendar month has (28..=31).
+    pub fn days(&self) -> u32 {
+        let (next_year, next_month) = if self.month() == 12 {
+            (self.year() as i32 + 1, 1)
+        } else {
+            (self.year() as i32, u32::from(self.month()) + 1)
+        };
+        chrono::NaiveDate::from_ymd_opt(next_year, next_month, 1)
+            .and_then(|first_of_next| first_of_next.pred_opt())
+            .map(|last| last.day())
+            .expect("Period invariant: year/month always name a real calendar month")
+    }
+
+    /// The 1-based day of this period `at` falls on, or `None` when `at` lies outside it.
+    pub fn day_of(&self, at: DateTime<Utc>) -> Option<u32> {
+        (Period::current(at) == *self).then(|| at.day())
+    }
 }
 
 impl fmt::Display for Period {
@@ -194,4 +212,26 @@ mod tests {
             Period::parse("2026-12").expect("valid period must parse")
         );
     }
+
+    #[test]
+    fn days_follows_month_length_and_leap_years() {
+        let days = |s: &str| Period::parse(s).expect("valid period must parse").days();
+        assert_eq!(days("2026-01"), 31);
+        assert_eq!(days("2026-02"), 28);
+        assert_eq!(days("2028-02"), 29);
+        assert_eq!(days("2026-04"), 30);
+        assert_eq!(days("2026-12"), 31);
+    }
+
+    #[test]
+    fn day_of_is_none_outside_the_period() {
+        let period = Period::parse("2026-08").expect("valid period must parse");
+        let at = |s: &str| {
+            DateTime::parse_from_rfc3339(s)
+                .expect("valid rfc3339")
+                .with_timezone(&Utc)
+        };
+        assert_eq!(period.day_of(at("2026-08-30T10:00:00Z")), Some(30));
+        assert_eq!(period.day_of(at("2026-09-01T00:00:00Z")), None);
+    }
 }
@@ -0,0 +1,310 @@
+//! Which engine a stored policy revision runs on, and the hot-swappable engine
+//! [`crate::policy_store::PolicyStore`] serves from.
+//!
+//! A `budget_policy_revisions.rule_data_json` document selects its engine with a top-level
+//! `"engine"` field:
+//!
+//! - absent or `"rule_data"`: a [`RuleSet`] for [`RuleDataEngine`]. Every revision written before
+//!   the field existed (including the migration's seed) has no `engine` and keeps meaning this.
+//! - `"opa_wasm"`: an [`OpaWasmPolicy`] for [`OpaWasmEngine`].
+//!
+//! Anything else is rejected, so a typo can never silently fall back to the rule-data engine.
+
+use std::sync::{Arc, RwLock};
+
+use crate::decision::{Decision, PolicyEngine};
+use crate::error::BudgetError;
+use crate::facts::Facts;
+use crate::opa_wasm::{OPA_WASM_EVALUATION_FUEL, OpaWasmEngine, OpaWasmPolicy};
+use crate::rule_data::{RuleDataEngine, RuleSet, validate_rule_data};
+use crate::tier::TierLadder;
+
+/// A parsed, validated policy document of either engine kind.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyDocument {
+    RuleData(RuleSet),
+    OpaWasm(OpaWasmPolicy),
+}
+
+impl PolicyDocument {
+    pub fn policy_revision(&self) -> &str {
+        match self {
+            PolicyDocument::RuleData(rule_set) => &rule_set.policy_revision,
+            PolicyDocument::OpaWasm(policy) => &policy.policy_revision,
+        }
+    }
+
+    pub fn tier_ladder(&self) -> &TierLadder {
+        match self {
+            PolicyDocument::RuleData(rule_set) => &rule_set.tier_ladder,
+            PolicyDocument::OpaWasm(policy) => &policy.tier_ladder,
+        }
+    }
+}
+
+/// Parses and validates a stored policy document of either kind. For an `opa_wasm` document this
+/// compiles the module and checks its entrypoint and builtins too (see [`OpaWasmEngine::new`]),
+/// so a revision that passes here is one [`ActivePolicyEngine::load`] will accept.
+pub fn validate_policy_document(policy_json: &str) -> Result<PolicyDocument, BudgetError> {
+    let value: serde_json::Value = serde_json::from_str(policy_json).map_err(|err| {
+        BudgetError::InvalidRuleData(format!("failed to parse policy document JSON: {err}"))
+    })?;
+    match value.get("engine").map(|engine| engine.as_str()) {
+        None | Some(Some("rule_data")) => {
+            validate_rule_data(policy_json).map(PolicyDocument::RuleData)
+        }
+        Some(Some("opa_wasm")) => {
+            let policy: OpaWasmPolicy = serde_json::from_value(value).map_err(|err| {
+                BudgetError::InvalidRuleData(format!("failed to parse opa_wasm policy: {err}"))
+            })?;
+            OpaWasmEngine::new(policy.clone(), OPA_WASM_EVALUATION_FUEL)?;
+            Ok(PolicyDocument::OpaWasm(policy))
+        }
+        Some(engine) => Err(BudgetError::InvalidRuleData(format!(
+            "unknown policy engine {}; expected \"rule_data\" or \"opa_wasm\"",
+            engine.map_or_else(
+                || "(not a string)".to_string(),
+                |name| format!("\"{name}\"")
+            )
+        ))),
+    }
+}
+
+#[derive(Debug)]
+struct Active {
+    policy_revision: String,
+    engine: Arc<dyn PolicyEngine>,
+}
+
+/// A [`PolicyEngine`] that delegates to whichever engine the last successfully loaded document
+/// selected, swapping between kinds as revisions are activated. Keeps
+/// [`RuleDataEngine::load`]'s last-known-good contract: a document that fails to load is logged
+/// and returned as an error, and the previous engine keeps serving.
+#[derive(Debug)]
+pub struct ActivePolicyEngine {
+    active: RwLock<Arc<Active>>,
+    evaluation_budget: usize,
+}
+
+impl ActivePolicyEngine {
+    /// `evaluation_budget` is the node-count budget each [`RuleDataEngine`] this builds is given;
+    /// Wasm engines run under [`OPA_WASM_EVALUATION_FUEL`] instead.
+    pub fn new(initial_policy_json: &str, evaluation_budget: usize) -> Result<Self, BudgetError> {
+        let active = build(initial_policy_json, evaluation_budget)?;
+        Ok(Self {
+            active: RwLock::new(Arc::new(active)),
+            evaluation_budget,
+        })
+    }
+
+    /// Parses, validates, and swaps in `new_policy_json`, or logs the rejection and leaves the
+    /// active engine unchanged.
+    pub fn load(&self, new_policy_json: &str) -> Result<(), BudgetError> {
+        match build(new_policy_json, self.evaluation_budget) {
+            Ok(active) => {
+                let mut current = self
+                    .active
+                    .write()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                *current = Arc::new(active);
+                Ok(())
+            }
+            Err(err) => {
+                tracing::error!(
+                    error = %err,
+                    "rejected policy document load; keeping last-known-good policy active"
+                );
+                Err(err)
+            }
+        }
+    }
+
+    /// The `policy_revision` currently serving `evaluate` calls.
+    pub fn active_policy_revision(&self) -> String {
+        self.current().policy_revision.clone()
+    }
+
+    fn current(&self) -> Arc<Active> {
+        Arc::clone(
+            &self
+                .active
+                .read()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+        )
+    }
+}
+
+impl From<RuleDataEngine> for ActivePolicyEngine {
+    fn from(engine: RuleDataEngine) -> Self {
+        let evaluation_budget = engine.evaluation_budget();
+        Self {
+            active: RwLock::new(Arc::new(Active {
+                policy_revision: engine.active_policy_revision(),
+                engine: Arc::new(engine),
+            })),
+            evaluation_budget,
+        }
+    }
+}
+
+fn build(policy_json: &str, evaluation_budget: usize) -> Result<Active, BudgetError> {
+    let document = validate_policy_document(policy_json)?;
+    let policy_revision = document.policy_revision().to_string();
+    let engine: Arc<dyn PolicyEngine> = match document {
+        PolicyDocument::RuleData(_) => {
+            Arc::new(RuleDataEngine::new(policy_json, evaluation_budget)?)
+        }
+        PolicyDocument::OpaWasm(policy) => {
+            Arc::new(OpaWasmEngine::new(policy, OPA_WASM_EVALUATION_FUEL)?)
+        }
+    };
+    Ok(Active {
+        policy_revision,
+        engine,
+    })
+}
+
+#[lightbridge_authz_core::async_trait]
+impl PolicyEngine for ActivePolicyEngine {
+    async fn evaluate(
+        &self,
+        facts: &Facts,
+        requested_amount_micros: i64,
+    ) -> Result<Decision, BudgetError> {
+        let active = self.current();
+        active.engine.evaluate(facts, requested_amount_micros).await
+    }
+
+    fn allowed_amounts_micros(&self) -> Vec<i64> {
+        self.current().engine.allowed_amounts_micros()
+    }
+
+    fn starting_amount_micros(&self) -> i64 {
+        self.current().engine.starting_amount_micros()
+    }
+
+    fn fail_closed_floor_micros(&self) -> i64 {
+        self.current().engine.fail_closed_floor_micros()
+    }
+
+    fn tier_ladder(&self) -> TierLadder {
+        self.current().engine.tier_ladder()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decision::Effect;
+    use crate::rule_data::default_rule_set_json;
+    use crate::spend::Spend;
+    use base64::Engine as _;
+
+    /// An `opa_wasm` document whose module always returns `auto_approve_capped` at 1 USD. See
+    /// `opa_wasm::tests::module` for the shape of this fixture.
+    fn opa_wasm_document(policy_revision: &str) -> String {
+        let wasm = wat::parse_str(
+            r#"(module
+  (import "env" "memory" (memory 2))
+  (global $heap (mut i32) (i32.const 65536))
+  (data (i32.const 1024) "[{\"result\":{\"effect\":\"auto_approve_capped\",\"maximum_amount_micros\":1000000}}]\00")
+  (data (i32.const 4096) "{\"budget/refill/decision\":0}\00")
+  (data (i32.const 8192) "{}\00")
+  (func (export "opa_malloc") (param $n i32) (result i32) (local $p i32)
+    global.get $heap
+    local.set $p
+    global.get $heap
+    local.get $n
+    i32.add
+    global.set $heap
+    local.get $p)
+  (func (export "opa_heap_ptr_get") (result i32) global.get $heap)
+  (func (export "opa_json_parse") (param i32 i32) (result i32) local.get 0)
+  (func (export "opa_json_dump") (param i32) (result i32) local.get 0)
+  (func (export "entrypoints") (result i32) i32.const 4096)
+  (func (export "builtins") (result i32) i32.const 8192)
+  (func (export "opa_eval") (param i32 i32 i32 i32 i32 i32 i32) (result i32) i32.const 1024))"#,
+        )
+        .expect("fixture module assembles");
+        serde_json::json!({
+            "engine": "opa_wasm",
+            "policy_revision": policy_revision,
+            "entrypoint": "budget/refill/decision",
+            "module_base64": base64::engine::general_purpose::STANDARD.encode(wasm),
+            "allowed_amounts_micros": [1_000_000, 5_000_000],
+            "starting_amount_micros": 1_000_000,
+            "fail_closed_floor_micros": 1_000_000,
+        })
+        .to_string()
+    }
+
+    fn facts() -> Facts {
+        Facts {
+            effective_balance_micros: 0,
+            self_service_grant_count: 0,
+            spend_this_period: Spend::Known(0),
+            spend_last_period: Spend::Known(0),
+            billing_plan: None,
+            quota_tier: None,
+            account_age_days: None,
+            day_of_period: None,
+            days_in_period: None,
+        }
+    }
+
+    #[test]
+    fn engine_field_selects_the_document_kind() {
+        assert!(matches!(
+            validate_policy_document(default_rule_set_json()),
+            Ok(PolicyDocument::RuleData(_))
+        ));
+        assert!(matches!(
+            validate_policy_document(&opa_wasm_document("wasm-v1")),
+            Ok(PolicyDocument::OpaWasm(_))
+        ));
+
+        let mut explicit: serde_json::Value =
+            serde_json::from_str(default_rule_set_json()).expect("valid JSON");
+        explicit["engine"] = "rule_data".into();
+        assert!(matches!(
+            validate_policy_document(&explicit.to_string()),
+            Ok(PolicyDocument::RuleData(_))
+        ));
+
+        explicit["engine"] = "rego".into();
+        assert!(matches!(
+            validate_policy_document(&explicit.to_string()),
+            Err(BudgetError::InvalidRuleData(m)) if m.contains("unknown policy engine")
+        ));
+    }
+
+    #[tokio::test]
+    async fn load_swaps_between_engine_kinds_and_keeps_last_known_good() {
+        let engine =
+            ActivePolicyEngine::new(default_rule_set_json(), 1_000).expect("valid rule set");
+        assert_eq!(engine.active_policy_revision(), "budget-policy-v1");
+
+        engine
+            .load(&opa_wasm_document("wasm-v1"))
+            .expect("valid wasm policy");
+        assert_eq!(engine.active_policy_revision(), "wasm-v1");
+        assert_eq!(engine.allowed_amounts_micros(), vec![1_000_000, 5_000_000]);
+        let decision = engine
+            .evaluate(&facts(), 5_000_000)
+            .await
+            .expect("evaluation succeeds");
+        assert_eq!(decision.effect, Effect::AutoApproveCapped);
+        assert_eq!(decision.approved_amount_micros, 1_000_000);
+        assert_eq!(decision.policy_revision, "wasm-v1");
+
+        engine
+            .load(r#"{"engine":"opa_wasm","policy_revision":"broken"}"#)
+            .expect_err("incomplete wasm policy");
+        assert_eq!(engine.active_policy_revision(), "wasm-v1");
+
+        engine
+            .load(default_rule_set_json())
+            .expect("valid rule set");
+        assert_eq!(engine.active_policy_revision(), "budget-policy-v1");
+    }
+}
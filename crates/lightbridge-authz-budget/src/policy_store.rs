@@ -1,5 +1,6 @@
 //! Ties one DB-persisted, named policy set (ADR-0007's policy lifecycle) to one long-lived,
-//! hot-swappable [`RuleDataEngine`] instance. This module owns *persistence* and the DB<->engine
+//! hot-swappable [`ActivePolicyEngine`] instance, which runs each revision on whichever engine its
+//! document selects (rule data or OPA-Wasm -- see [`crate::policy_document`]). This module owns *persistence* and the DB<->engine
 //! tie only -- no RPC, no HTTP, no `/health` wiring, and no permission checks live here. Those are
 //! a later PR that depends on this module's exact shape (`PolicyStore::activate`'s signature and
 //! ordering, `PolicyStore::engine`'s accessor) staying stable, per #190.
@@ -15,11 +16,14 @@
 
 use std::sync::Arc;
 
+use lightbridge_authz_core::audit;
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::db::DbPoolTrait;
 
+use crate::decision::PolicyEngine;
 use crate::error::BudgetError;
-use crate::rule_data::{RuleDataEngine, validate_rule_data};
+use crate::policy_document::{ActivePolicyEngine, PolicyDocument, validate_policy_document};
+use crate::rule_data::RuleDataEngine;
 
 fn storage_failed(err: sqlx::Error) -> BudgetError {
     BudgetError::StorageFailed(err.to_string())
@@ -56,18 +60,18 @@ const INSERT_REVISION_RETURNING_ID_SQL: &str = "INSERT INTO budget_policy_revisi
      RETURNING id";
 
 /// Ties one DB-persisted policy set (identified by `policy_set_id`) to one live
-/// [`RuleDataEngine`]. Cheaply `Clone` -- `pool` and `engine` are both `Arc`s, so cloning a
+/// [`ActivePolicyEngine`]. Cheaply `Clone` -- `pool` and `engine` are both `Arc`s, so cloning a
 /// `PolicyStore` shares the same live engine rather than constructing a second, independent one.
 #[derive(Debug, Clone)]
 pub struct PolicyStore {
     pool: Arc<dyn DbPoolTrait>,
     policy_set_id: String,
-    engine: Arc<RuleDataEngine>,
+    engine: Arc<ActivePolicyEngine>,
 }
 
 impl PolicyStore {
     /// Loads the currently active revision for `policy_set_id` from the database and constructs
-    /// a fresh [`RuleDataEngine`] from it. This is what server startup (and, in tests, "simulate a
+    /// a fresh [`ActivePolicyEngine`] from it. This is what server startup (and, in tests, "simulate a
     /// restart") calls -- it is the read path that proves persistence and the in-memory engine
     /// genuinely agree, not just at the moment of the last [`Self::activate`] call but from cold.
     ///
@@ -98,7 +102,7 @@ impl PolicyStore {
             ))
         })?;
 
-        let engine = RuleDataEngine::new(&rule_data_json, evaluation_budget)?;
+        let engine = ActivePolicyEngine::new(&rule_data_json, evaluation_budget)?;
 
         Ok(Self {
             pool,
@@ -118,10 +122,12 @@ impl PolicyStore {
     ///    activated at some point. A later PR must not assume a row's mere existence there implies
     ///    anything other than "this was live at some point"; it does not need to filter out
     ///    "attempted but rejected" rows, because there are none.
+    ///    That includes the tier ladder's append-only rule against the ladder currently being
+    ///    served (see [`Self::check_tier_ladder_extends_active`]).
     /// 2. Insert the new revision and repoint `active_revision_id` at it, in one transaction.
     /// 3. Only after that transaction commits, hot-swap the live in-memory engine via
-    ///    [`RuleDataEngine::load`] -- for real, not skipped. The data was already validated in
-    ///    step 1 with the exact same [`validate_rule_data`] function `load` uses internally, so
+    ///    [`ActivePolicyEngine::load`] -- for real, not skipped. The data was already validated in
+    ///    step 1 with the exact same [`validate_policy_document`] function `load` uses internally, so
     ///    this call should never itself fail from bad data, but it goes through the real
     ///    hot-swap path regardless so the engine's internal state is genuinely updated rather
     ///    than assumed to be.
@@ -140,16 +146,19 @@ impl PolicyStore {
         new_rule_data_json: &str,
         actor_id: Option<&str>,
     ) -> Result<String, BudgetError> {
-        let rule_set = validate_rule_data(new_rule_data_json)?;
+        let document = validate_policy_document(new_rule_data_json)?;
+        self.check_tier_ladder_extends_active(&document)?;
 
         let revision_id = cuid2();
 
-        let mut tx = self.pool.pool().begin().await.map_err(storage_failed)?;
+        let mut tx = audit::begin(self.pool.pool())
+            .await
+            .map_err(storage_failed)?;
 
         sqlx::query(INSERT_REVISION_SQL)
             .bind(&revision_id)
             .bind(&self.policy_set_id)
-            .bind(&rule_set.policy_revision)
+            .bind(document.policy_revision())
             .bind(new_rule_data_json)
             .bind(actor_id)
             .execute(&mut *tx)
@@ -171,11 +180,12 @@ impl PolicyStore {
                  '{}' (id '{revision_id}') committed to the database for policy set '{}' \
                  but the in-memory engine failed to hot-swap to it ({load_err}) -- this is a \
                  serious, unusual state that needs manual reconciliation, not a retry",
-                rule_set.policy_revision, self.policy_set_id
+                document.policy_revision(),
+                self.policy_set_id
             ))
         })?;
 
-        Ok(rule_set.policy_revision)
+        Ok(document.policy_revision().to_string())
     }
 
     /// Reactivates an *existing* revision by id -- the rollback path
@@ -193,10 +203,15 @@ impl PolicyStore {
     /// distinction clearly without growing the enum for one caller).
     ///
     /// Mirrors [`Self::activate`]'s ordering: the DB repoint commits first, then the in-memory
-    /// engine is hot-swapped via [`RuleDataEngine::load`]. If that hot-swap somehow fails despite
+    /// engine is hot-swapped via [`ActivePolicyEngine::load`]. If that hot-swap somehow fails despite
     /// the content having already been validated once (when it was first inserted), the same
     /// "DB and engine now disagree" [`BudgetError::StorageFailed`] is returned as `activate` uses,
     /// for the same reason: the transaction already committed, so there is nothing to roll back to.
+    ///
+    /// A rollback is an activation like any other as far as the tier ladder's append-only rule is
+    /// concerned: rolling back past a revision that added a rung is refused, since tokens stamped
+    /// with that rung's label are still in flight. Roll forward to a revision that keeps the
+    /// current ladder instead.
     pub async fn activate_by_revision_id(&self, revision_id: &str) -> Result<String, BudgetError> {
         let row: Option<(String, String)> = sqlx::query_as(SELECT_REVISION_BY_ID_SQL)
             .bind(revision_id)
@@ -213,12 +228,19 @@ impl PolicyStore {
             ))
         })?;
 
+        let document = validate_policy_document(&rule_data_json)?;
+        self.check_tier_ladder_extends_active(&document)?;
+
+        let mut tx = audit::begin(self.pool.pool())
+            .await
+            .map_err(storage_failed)?;
         sqlx::query(ACTIVATE_REVISION_SQL)
             .bind(revision_id)
             .bind(&self.policy_set_id)
-            .execute(self.pool.pool())
+            .execute(&mut *tx)
             .await
             .map_err(storage_failed)?;
+        tx.commit().await.map_err(storage_failed)?;
 
         self.engine.load(&rule_data_json).map_err(|load_err| {
             BudgetError::StorageFailed(format!(
@@ -236,9 +258,9 @@ impl PolicyStore {
     /// Authors a new revision WITHOUT activating it (ADR-0007's `budget:policy-write` vs
     /// `budget:policy-activate` split -- see that permission's own doc comment for the "writing
     /// means shipping executable code, activation is a separate decision" rationale). Validates
-    /// `new_rule_data_json` with the exact same [`validate_rule_data`] [`Self::activate`] uses,
+    /// `new_rule_data_json` with the exact same [`validate_policy_document`] [`Self::activate`] uses,
     /// then inserts the row and returns, deliberately never touching `active_revision_id` and
-    /// never calling [`RuleDataEngine::load`] -- the live in-memory engine keeps serving whatever
+    /// never calling [`ActivePolicyEngine::load`] -- the live in-memory engine keeps serving whatever
     /// revision was already active. This is what "a bad revision never displaces a good one"
     /// means for the write path specifically: a revision that fails validation here is never
     /// written at all, and a revision that IS written here still never displaces the active one
@@ -248,26 +270,43 @@ impl PolicyStore {
         new_rule_data_json: &str,
         actor_id: Option<&str>,
     ) -> Result<NewRevision, BudgetError> {
-        let rule_set = validate_rule_data(new_rule_data_json)?;
+        let document = validate_policy_document(new_rule_data_json)?;
 
         let revision_id = cuid2();
 
+        let mut tx = audit::begin(self.pool.pool())
+            .await
+            .map_err(storage_failed)?;
         let (id,): (String,) = sqlx::query_as(INSERT_REVISION_RETURNING_ID_SQL)
             .bind(&revision_id)
             .bind(&self.policy_set_id)
-            .bind(&rule_set.policy_revision)
+            .bind(document.policy_revision())
             .bind(new_rule_data_json)
             .bind(actor_id)
-            .fetch_one(self.pool.pool())
+            .fetch_one(&mut *tx)
             .await
             .map_err(storage_failed)?;
+        tx.commit().await.map_err(storage_failed)?;
 
         Ok(NewRevision {
             id,
-            policy_revision: rule_set.policy_revision,
+            policy_revision: document.policy_revision().to_string(),
         })
     }
 
+    /// ADR-0008's append-only rule for the budget-tier ladder: `document`'s ladder must keep
+    /// every rung the live engine is serving, unchanged and in order. Checked against the
+    /// in-memory engine rather than re-read from the database, the same source of truth
+    /// [`Self::active_policy_revision`] reports.
+    fn check_tier_ladder_extends_active(
+        &self,
+        document: &PolicyDocument,
+    ) -> Result<(), BudgetError> {
+        document
+            .tier_ladder()
+            .check_extends(&self.engine.tier_ladder())
+    }
+
     /// Constructs a `PolicyStore` directly from an already-built [`RuleDataEngine`], performing no
     /// I/O and no verification that `engine`'s content matches anything persisted for
     /// `policy_set_id`. Exists for callers that need a `PolicyStore` to satisfy an API surface
@@ -284,19 +323,19 @@ impl PolicyStore {
         Self {
             pool,
             policy_set_id: policy_set_id.to_string(),
-            engine: Arc::new(engine),
+            engine: Arc::new(ActivePolicyEngine::from(engine)),
         }
     }
 
     /// The live, hot-swappable engine this store activates against. A later PR's request-handling
     /// code holds onto this `Arc` and calls `.evaluate()` on it directly -- `PolicyStore` itself is
     /// a lifecycle/activation concern, not something every evaluation caller needs to go through.
-    pub fn engine(&self) -> Arc<RuleDataEngine> {
+    pub fn engine(&self) -> Arc<ActivePolicyEngine> {
         Arc::clone(&self.engine)
     }
 
     /// The `policy_revision` currently serving `engine().evaluate()` calls. Delegates directly to
-    /// [`RuleDataEngine::active_policy_revision`].
+    /// [`ActivePolicyEngine::active_policy_revision`].
     pub fn active_policy_revision(&self) -> String {
         self.engine.active_policy_revision()
     }
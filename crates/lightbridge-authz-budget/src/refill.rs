@@ -11,25 +11,25 @@
 //! internal/API-key clients happens in a later PR, in the RPC procedure that calls into this one,
 //! before this code is ever reached.
 //!
-//! ## The starting-tier gap (ADR-0008, flagged, not solved here)
+//! ## The starting tier (ADR-0008)
 //!
-//! ADR-0008 says "the billing plan determines the starting rung". That `billing_plan` ->
-//! `BudgetTier` mapping does not exist anywhere in this codebase yet. [`BudgetRepo::current_tier`]
-//! (which ADR-0014's token-mint claim reads) defaults every account with no qualifying grant
-//! history this period to [`BudgetTier::B15`], the lowest rung. That default is *safe* (it never
-//! grants -- or claims -- more than the cheapest plan would justify) but it is NOT the real,
-//! intended behavior for e.g. an enterprise-plan account that should start at `B1000` -- such an
-//! account would have to refill repeatedly, one rung at a time, to reach the tier it should have
-//! started at. This is a deliberate, flagged simplification for follow-up, not something this PR
-//! is claiming is fully solved.
+//! ADR-0008 says "the billing plan determines the starting rung". That mapping is policy data:
+//! the active document's `tier_ladder.plan_starting_tiers` (see [`crate::tier::TierLadder`]).
+//! [`BudgetRepo::current_tier`] (which ADR-0014's token-mint claim reads) starts an account with
+//! no qualifying grant history this period on its plan's rung, so an enterprise-plan account
+//! mapped to `b-1000` starts there instead of refilling its way up one rung at a time. A plan
+//! with no mapping still starts on the lowest rung -- the safe default, which never claims more
+//! than the cheapest plan would justify.
 
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Counter;
 
 use crate::augmentation::{
     ApprovedDecision, AugmentationRepo, AugmentationRequest, NewAugmentationRequest,
-    RecordedDecision, UnapprovedDecision,
+    RecordedDecision, UnapprovedDecision, effect_to_db,
 };
 use crate::decision::{Effect, PolicyEngine};
 use crate::error::BudgetError;
@@ -38,7 +38,7 @@ use crate::period::Period;
 use crate::repo::{BudgetRepo, GrantRequest};
 use crate::source::GrantSource;
 use crate::spend::SpendReader;
-use crate::tier::BudgetTier;
+use crate::tier::TierLadder;
 
 /// One refill request. Deliberately caller-supplied `as_of`, not read from the clock internally --
 /// the same discipline the rest of this crate already applies (`Period` is clock-free;
@@ -68,6 +68,9 @@ pub struct RefillStatus {
     /// ascending. This is the source of truth for what [`RefillRequest::requested_amount_micros`]
     /// may legally be.
     pub allowed_amounts_micros: Vec<i64>,
+    /// The active policy's budget-tier ladder, so a caller can render "which rung am I on, what's
+    /// above it" from the same data `x-budget-tier` is stamped from.
+    pub tier_ladder: TierLadder,
 }
 
 /// Orchestrates one refill request end to end: idempotency short-circuit, offered-amount
@@ -80,6 +83,7 @@ pub struct RefillService {
     augmentation_repo: Arc<AugmentationRepo>,
     policy_engine: Arc<dyn PolicyEngine>,
     spend_reader: Arc<dyn SpendReader>,
+    decisions: Counter<u64>,
 }
 
 impl RefillService {
@@ -94,6 +98,12 @@ impl RefillService {
             augmentation_repo,
             policy_engine,
             spend_reader,
+            decisions: lightbridge_authz_core::metrics::meter()
+                .u64_counter("authz_budget_refill_decisions")
+                .with_description(
+                    "Refill requests decided, by the Effect recorded (idempotent replays excluded)",
+                )
+                .build(),
         }
     }
 
@@ -116,7 +126,7 @@ impl RefillService {
     }
 
     /// Read-only companion to [`Self::request_refill`]: the self-service refill amounts currently
-    /// offered by the active policy. Deliberately never calls [`PolicyEngine::evaluate`] and makes
+    /// offered by the active policy, and its budget-tier ladder. Deliberately never calls [`PolicyEngine::evaluate`] and makes
     /// no claim about the outcome of an actual submission -- see [`Self::request_refill`]'s own
     /// doc comment for the evaluation, capping, and denial paths this status intentionally does
     /// not preview. Takes no arguments: [`PolicyEngine::allowed_amounts_micros`] is a flat,
@@ -129,6 +139,7 @@ impl RefillService {
     pub async fn refill_status(&self) -> Result<RefillStatus, BudgetError> {
         Ok(RefillStatus {
             allowed_amounts_micros: self.policy_engine.allowed_amounts_micros(),
+            tier_ladder: self.policy_engine.tier_ladder(),
         })
     }
 
@@ -172,12 +183,13 @@ impl RefillService {
         if !allowed.contains(&requested_amount_micros) {
             return Err(BudgetError::AmountNotOffered(requested_amount_micros));
         }
-        // Best-effort display label only -- an amount outside the `BudgetTier` enum (e.g.
-        // ADR-0015's $6 floor, which predates any tier variant for it) has no exact label and
-        // falls back to `B15` here. `requested_amount_micros` above is the authoritative value;
-        // this label is not.
-        let requested_tier =
-            BudgetTier::from_amount_micros(requested_amount_micros).unwrap_or(BudgetTier::B15);
+        // Display label only -- an amount on no rung (e.g. ADR-0015's $6 floor, below the
+        // legacy ladder's `b-15`) gets a synthesized label rather than the lowest rung's.
+        // `requested_amount_micros` above is the authoritative value; this label is not.
+        let requested_tier = self
+            .policy_engine
+            .tier_ladder()
+            .label_for_amount_micros(requested_amount_micros);
 
         let created = self
             .augmentation_repo
@@ -201,6 +213,7 @@ impl RefillService {
         {
             Ok(decision) => decision,
             Err(_engine_err) => {
+                self.record_effect(Effect::ManualReview);
                 return self
                     .augmentation_repo
                     .record_decision(
@@ -216,6 +229,7 @@ impl RefillService {
             }
         };
 
+        self.record_effect(decision.effect);
         match decision.effect {
             Effect::AutoApprove | Effect::AutoApproveCapped => {
                 let grant = self
@@ -285,6 +299,11 @@ impl RefillService {
         }
     }
 
+    fn record_effect(&self, effect: Effect) {
+        self.decisions
+            .add(1, &[KeyValue::new("effect", effect_to_db(effect))]);
+    }
+
     /// Step 1 of [`Self::request_refill`]: if `idempotency_key` is `Some` and a request with that
     /// key already exists, return it. Called before any other work -- no `BudgetRepo` read, no
     /// request-row creation, no policy evaluation -- so a genuine retry (double-click, client
@@ -321,12 +340,26 @@ impl RefillService {
             .spend_reader
             .spend_for_account(&request.account_id, &request.period.previous())
             .await?;
+        let context = self
+            .budget_repo
+            .account_context(&request.account_id, request.project_id.as_deref())
+            .await?;
 
         Ok(Facts {
             effective_balance_micros,
             self_service_grant_count,
             spend_this_period,
             spend_last_period,
+            billing_plan: context.billing_plan,
+            quota_tier: context.quota_tier,
+            account_age_days: context
+                .account_created_at
+                .map(|created_at| (request.as_of - created_at).num_days().max(0)),
+            day_of_period: request
+                .period
+                .day_of(request.as_of)
+                .and_then(|day| i32::try_from(day).ok()),
+            days_in_period: i32::try_from(request.period.days()).ok(),
         })
     }
 }
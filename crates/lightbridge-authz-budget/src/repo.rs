@@ -19,6 +19,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
+use lightbridge_authz_core::audit;
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::db::DbPoolTrait;
 use sqlx::PgPool;
@@ -26,7 +27,7 @@ use sqlx::PgPool;
 use crate::error::BudgetError;
 use crate::period::Period;
 use crate::source::GrantSource;
-use crate::tier::BudgetTier;
+use crate::tier::{BudgetTier, TierLadder};
 
 #[derive(Debug, Clone)]
 pub struct BudgetRepo {
@@ -82,6 +83,16 @@ pub struct BudgetGrant {
 /// Deliberately does NOT carry `version`/`updated_at` -- those are mutation bookkeeping (how many
 /// times the row changed, and when), not ledger-derived facts, and are out of scope for the
 /// replay equality check (#189).
+/// The result of [`BudgetRepo::account_context`]. Every field is `None` when its row is missing:
+/// no `accounts` row, no project named, or the account not on that project's roster (or on it
+/// with no tier).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountContext {
+    pub account_created_at: Option<DateTime<Utc>>,
+    pub billing_plan: Option<String>,
+    pub quota_tier: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
 pub struct DerivedBalance {
     pub budget_account_id: String,
@@ -145,6 +156,14 @@ const GET_BALANCE_SQL: &str = "SELECT \
      version, updated_at \
      FROM budget_balances WHERE budget_account_id = $1 AND period = $2";
 
+/// The authz-side facts a refill policy may test ([`crate::facts::Facts`]'s plan, tier and
+/// account-age facts). Each subselect is independent, so a missing project or roster row yields
+/// `NULL` for its own column without hiding the others.
+const ACCOUNT_CONTEXT_SQL: &str = "SELECT \
+     (SELECT created_at FROM accounts WHERE id = $1), \
+     (SELECT billing_plan FROM projects WHERE id = $2), \
+     (SELECT quota_tier FROM project_members WHERE project_id = $2 AND account_id = $1)";
+
 const GET_GRANT_BY_ID_SQL: &str = "SELECT \
      id, budget_account_id, account_id, project_id, period, amount_micros, source, \
      actor_id, reason, policy_revision, matched_rule_ids, idempotency_key, trigger_key, \
@@ -366,7 +385,7 @@ impl BudgetRepo {
         let period_str = request.period.to_string();
         let source_str = request.source.to_string();
 
-        let mut tx = self.pool().begin().await.map_err(storage_failed)?;
+        let mut tx = audit::begin(self.pool()).await.map_err(storage_failed)?;
 
         sqlx::query(
             "INSERT INTO budget_balances (budget_account_id, period) VALUES ($1, $2) \
@@ -510,14 +529,19 @@ impl BudgetRepo {
     /// `RefillService` (policy engine, spend reader, augmentation repo) just to read a tier --
     /// can depend on this crate's lightest-weight read primitive instead.
     ///
-    /// Falls back to [`BudgetTier::B15`] (the lowest rung) in two cases, and both are deliberate,
-    /// defensive fallbacks rather than a trusted derivation:
+    /// Rungs are resolved against `ladder` -- the active policy's
+    /// [`crate::decision::PolicyEngine::tier_ladder`] -- never a compile-time list. Two cases
+    /// fall back instead of resolving, and both are deliberate:
     ///
-    /// - No qualifying grant exists yet this period (a genuinely new account/period).
-    /// - A qualifying grant exists, but its `amount_micros` doesn't match any known rung (e.g. a
+    /// - No qualifying grant exists yet this period (a genuinely new account/period): the
+    ///   `billing_plan`'s starting rung ([`TierLadder::starting_tier`], ADR-0008's "the billing
+    ///   plan determines the starting rung"), which is the lowest rung for a plan the ladder
+    ///   has no mapping for.
+    /// - A qualifying grant exists, but its `amount_micros` doesn't match any rung (e.g. a
     ///   `correction` shifted the raw ledger total in a way that makes the *most recent
     ///   tier-grant* no longer reflect current reality -- or just data this service doesn't
-    ///   expect in practice).
+    ///   expect in practice): the lowest rung, never the plan's, since nothing here says the
+    ///   account is still where its plan started it.
     ///
     /// **This does NOT cover a genuine storage failure** (DB unreachable, timeout, etc.) -- that
     /// still surfaces as `Err(BudgetError::StorageFailed)`, same as every other read on this
@@ -525,33 +549,24 @@ impl BudgetRepo {
     /// path -- see the budget-tier-rekey-cutover runbook's "an account with no claim lands on no
     /// matching rule, which is the difference between base budget and unlimited") is responsible
     /// for catching that `Err` itself and choosing its own fail-closed default; this method does
-    /// not silently swallow a storage error into `B15` on its own, since a caller that actually
-    /// wants to distinguish "new account" from "ledger unavailable" (e.g. for an operator alert)
-    /// still can.
-    ///
-    /// **Known simplification, not solved here (ADR-0008):** "the billing plan determines the
-    /// starting rung" has no `billing_plan` -> `BudgetTier` mapping anywhere in this codebase
-    /// yet, so every account with no qualifying grant history this period defaults to `B15`
-    /// regardless of plan. Safe (never grants/claims more than the cheapest plan would justify)
-    /// but not the intended long-run behavior for e.g. an enterprise-plan account.
+    /// not silently swallow a storage error into the lowest rung on its own, since a caller that
+    /// actually wants to distinguish "new account" from "ledger unavailable" (e.g. for an operator
+    /// alert) still can.
     ///
-    /// **ADR-0015 note, so the two `B15` defaults above are never mistaken for the fail-closed
-    /// floor:** neither is [`crate::decision::PolicyEngine::fail_closed_floor_micros`] (ADR-0015
+    /// **ADR-0015 note, so the two fallbacks above are never mistaken for the fail-closed floor:**
+    /// neither is [`crate::decision::PolicyEngine::fail_closed_floor_micros`] (ADR-0015
     /// Decision 6) -- that is a distinct concept (an outage/unresolvable-data fallback) from
-    /// "brand-new account, no grant yet" (Decision 5's `starting_amount_micros`, coincidentally
-    /// $15 too under the shipped default policy, but not derived from it here) or "a grant exists
-    /// but the amount predates/postdates the compile-time ladder." This method has no
-    /// `PolicyEngine` to read either live value from, and after #387 removed the transitional,
-    /// `BudgetTier`-shaped `RefillStatus` fields a live frontend used to read, its only remaining
-    /// caller is the token-mint/refresh path below -- so both defaults deliberately stay `B15`
-    /// rather than being wired to either policy field. The token-mint path that DOES need the real
-    /// fail-closed floor (`TokenExchangeOpStore::resolve_budget_tier` in `lightbridge-authz-rest`)
-    /// reads `fail_closed_floor_micros()` directly on its OWN `Err` branch below, never through
-    /// this method's internal `B15` defaults.
+    /// "brand-new account, no grant yet" or "a grant exists but the amount is on no rung." The
+    /// token-mint path that DOES need the real fail-closed floor
+    /// (`TokenExchangeOpStore::resolve_budget_tier` in `lightbridge-authz-rest`) reads
+    /// `fail_closed_floor_micros()` directly on its OWN `Err` branch, never through this method's
+    /// fallbacks.
     pub async fn current_tier(
         &self,
         budget_account_id: &str,
         period: &Period,
+        ladder: &TierLadder,
+        billing_plan: Option<&str>,
     ) -> Result<BudgetTier, BudgetError> {
         let period_str = period.to_string();
 
@@ -563,10 +578,11 @@ impl BudgetRepo {
             .map_err(storage_failed)?;
 
         Ok(match row {
-            Some((amount_micros,)) => {
-                BudgetTier::from_amount_micros(amount_micros).unwrap_or(BudgetTier::B15)
-            }
-            None => BudgetTier::B15,
+            Some((amount_micros,)) => ladder
+                .from_amount_micros(amount_micros)
+                .unwrap_or_else(|| ladder.lowest())
+                .clone(),
+            None => ladder.starting_tier(billing_plan).clone(),
         })
     }
 
@@ -592,6 +608,30 @@ impl BudgetRepo {
         row.map(BalanceSnapshot::try_from).transpose()
     }
 
+    /// Reads [`AccountContext`] for `account_id`, scoped to `project_id` when there is one.
+    pub async fn account_context(
+        &self,
+        account_id: &str,
+        project_id: Option<&str>,
+    ) -> Result<AccountContext, BudgetError> {
+        let (account_created_at, billing_plan, quota_tier): (
+            Option<DateTime<Utc>>,
+            Option<String>,
+            Option<String>,
+        ) = sqlx::query_as(ACCOUNT_CONTEXT_SQL)
+            .bind(account_id)
+            .bind(project_id)
+            .fetch_one(self.pool())
+            .await
+            .map_err(storage_failed)?;
+
+        Ok(AccountContext {
+            account_created_at,
+            billing_plan,
+            quota_tier,
+        })
+    }
+
     /// Fetches one `budget_grants` row by id, for [`crate::repo`]'s revoke-by-correction callers
     /// that need to read the original grant's exact `(budget_account_id, account_id, project_id,
     /// period, amount_micros)` before writing a compensating row against it. Not-found is a loud,
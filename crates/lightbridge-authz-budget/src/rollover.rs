@@ -0,0 +1,214 @@
+//! Period rollover: the `base` grant every account opens each calendar [`Period`] with.
+//!
+//! Before this module nothing wrote a `base` grant, so every new period each account silently
+//! started from [`PolicyEngine::starting_amount_micros`] with no ledger row saying so -- its
+//! balance read zero, its grant history was empty, and `listBudgetGrants` had nothing to show for
+//! the budget it was actually running on. [`RolloverService::run`] makes that opening explicit:
+//! one `base` grant per active account per period, written through [`BudgetRepo::grant`] like
+//! every other ledger write.
+//!
+//! ## Idempotency
+//!
+//! The grant's idempotency key is derived from `(account, period)` alone
+//! ([`base_grant_idempotency_key`]), so running the rollover any number of times -- from the
+//! `authz-budget` background job on several replicas at once, and from the
+//! `lightbridge-authz budget rollover` CLI on top -- writes each opening exactly once. A replay
+//! resolves to the already-committed row through `grant()`'s own `ON CONFLICT` path; nothing here
+//! takes a lock of its own.
+//!
+//! ## The opening amount
+//!
+//! [`PolicyEngine::starting_amount_micros`], unless one of the account's active projects is on a
+//! billing plan the active tier ladder maps to a starting rung (`plan_starting_tiers`), in which
+//! case the highest such rung's amount. The opening grant is itself tier-representing (see
+//! [`BudgetRepo::current_tier`]), so writing the bare starting amount for an account whose plan
+//! starts higher would demote it to the lowest rung the moment the grant landed.
+//!
+//! Accounts that are `suspended` get no opening grant; one reactivated mid-period gets it on the
+//! next run.
+
+use std::sync::Arc;
+
+use crate::decision::PolicyEngine;
+use crate::error::BudgetError;
+use crate::period::Period;
+use crate::policy_document::ActivePolicyEngine;
+use crate::repo::{BudgetRepo, GrantRequest};
+use crate::source::GrantSource;
+use crate::tier::TierLadder;
+
+/// The `reason` recorded on every opening grant.
+pub const ROLLOVER_GRANT_REASON: &str = "period rollover";
+
+/// Active accounts with no `base` grant yet for `$1`, each with the distinct billing plans of its
+/// active projects. Checked by source rather than by idempotency key so the read is served by
+/// `idx_budget_grants_budget_account_period`; only this module writes `base` grants.
+const ACCOUNTS_WITHOUT_OPENING_GRANT_SQL: &str = "SELECT a.id, \
+     COALESCE(array_agg(DISTINCT p.billing_plan) FILTER (WHERE p.id IS NOT NULL), '{}') \
+     FROM accounts a \
+     LEFT JOIN projects p ON p.account_id = a.id AND p.status = 'active' \
+     WHERE a.status = 'active' \
+       AND NOT EXISTS (SELECT 1 FROM budget_grants g \
+                       WHERE g.budget_account_id = a.id AND g.period = $1 AND g.source = 'base') \
+     GROUP BY a.id \
+     ORDER BY a.id";
+
+/// The idempotency key of `budget_account_id`'s opening grant for `period`.
+pub fn base_grant_idempotency_key(budget_account_id: &str, period: &Period) -> String {
+    format!("base:{budget_account_id}:{period}")
+}
+
+/// The amount an account on `billing_plans` opens a period with -- see the module doc.
+pub fn opening_amount_micros(
+    starting_amount_micros: i64,
+    ladder: &TierLadder,
+    billing_plans: &[String],
+) -> i64 {
+    billing_plans
+        .iter()
+        .filter_map(|plan| ladder.plan_starting_tiers().get(plan))
+        .filter_map(|label| ladder.get(label).ok())
+        .map(|tier| tier.amount().get())
+        .max()
+        .unwrap_or(starting_amount_micros)
+}
+
+/// What one [`RolloverService::run`] did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolloverReport {
+    pub period: Period,
+    /// Accounts that had no opening grant when the run started and have one now.
+    pub granted: usize,
+    /// Accounts whose grant write failed; each is logged, and the next run retries it.
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct RolloverService {
+    budget_repo: Arc<BudgetRepo>,
+    policy_engine: Arc<ActivePolicyEngine>,
+}
+
+impl RolloverService {
+    /// Takes the concrete [`ActivePolicyEngine`] rather than `dyn PolicyEngine` so every opening
+    /// grant records the `policy_revision` its amount came from.
+    pub fn new(budget_repo: Arc<BudgetRepo>, policy_engine: Arc<ActivePolicyEngine>) -> Self {
+        Self {
+            budget_repo,
+            policy_engine,
+        }
+    }
+
+    /// Writes the opening grant for `period` to every active account that does not have one yet.
+    /// Only the account listing can fail the run as a whole; a failed grant write is counted in
+    /// [`RolloverReport::failed`] and the rest still proceed, since one bad row must not hold up
+    /// every other account's opening.
+    pub async fn run(&self, period: &Period) -> Result<RolloverReport, BudgetError> {
+        let accounts: Vec<(String, Vec<String>)> =
+            sqlx::query_as(ACCOUNTS_WITHOUT_OPENING_GRANT_SQL)
+                .bind(period.to_string())
+                .fetch_all(self.budget_repo.pool())
+                .await
+                .map_err(|err| BudgetError::StorageFailed(err.to_string()))?;
+
+        let starting_amount_micros = self.policy_engine.starting_amount_micros();
+        let ladder = self.policy_engine.tier_ladder();
+        let policy_revision = self.policy_engine.active_policy_revision();
+
+        let mut report = RolloverReport {
+            period: period.clone(),
+            granted: 0,
+            failed: 0,
+        };
+        for (account_id, billing_plans) in accounts {
+            let request = GrantRequest {
+                budget_account_id: account_id.clone(),
+                account_id: account_id.clone(),
+                project_id: None,
+                period: period.clone(),
+                amount_micros: opening_amount_micros(
+                    starting_amount_micros,
+                    &ladder,
+                    &billing_plans,
+                ),
+                source: GrantSource::Base,
+                actor_id: None,
+                reason: Some(ROLLOVER_GRANT_REASON.to_string()),
+                policy_revision: Some(policy_revision.clone()),
+                matched_rule_ids: None,
+                idempotency_key: Some(base_grant_idempotency_key(&account_id, period)),
+                trigger_key: None,
+                expires_at: None,
+            };
+            match self.budget_repo.grant(request).await {
+                Ok(_) => report.granted += 1,
+                Err(err) => {
+                    tracing::warn!(
+                        account_id,
+                        %period,
+                        error = %err,
+                        "failed to write opening budget grant"
+                    );
+                    report.failed += 1;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn ladder_with_plans(plans: &[(&str, &str)]) -> TierLadder {
+        TierLadder::new(
+            TierLadder::legacy().tiers().to_vec(),
+            plans
+                .iter()
+                .map(|(plan, label)| (plan.to_string(), label.to_string()))
+                .collect::<BTreeMap<_, _>>(),
+        )
+        .expect("ladder must be valid")
+    }
+
+    #[test]
+    fn idempotency_key_is_derived_from_account_and_period_only() {
+        let period = Period::parse("2026-10").unwrap();
+        assert_eq!(
+            base_grant_idempotency_key("acct-1", &period),
+            "base:acct-1:2026-10"
+        );
+        assert_ne!(
+            base_grant_idempotency_key("acct-1", &period),
+            base_grant_idempotency_key("acct-1", &period.previous())
+        );
+    }
+
+    #[test]
+    fn opening_amount_is_the_starting_amount_without_a_mapped_plan() {
+        let ladder = ladder_with_plans(&[("enterprise", "b-1000")]);
+        assert_eq!(opening_amount_micros(15_000_000, &ladder, &[]), 15_000_000);
+        assert_eq!(
+            opening_amount_micros(15_000_000, &ladder, &["free".to_string()]),
+            15_000_000
+        );
+    }
+
+    #[test]
+    fn opening_amount_is_the_highest_mapped_plan_rung() {
+        let ladder = ladder_with_plans(&[("team", "b-120"), ("enterprise", "b-1000")]);
+        let plans = [
+            "free".to_string(),
+            "team".to_string(),
+            "enterprise".to_string(),
+        ];
+        assert_eq!(
+            opening_amount_micros(15_000_000, &ladder, &plans),
+            1_000_000_000
+        );
+    }
+}
@@ -32,10 +32,13 @@ use crate::decision::{Decision, Effect, Obligations, PolicyEngine};
 use crate::error::BudgetError;
 use crate::facts::Facts;
 use crate::spend::Spend;
+use crate::tier::TierLadder;
 
-/// A fact `Condition::Threshold` can compare against. Mirrors [`Facts`] plus the one value that
-/// isn't a fact at all -- the amount being requested -- so rules like "auto-approve requests
-/// under $5" are expressible without inventing a separate mechanism.
+/// A fact `Condition::Threshold` can compare against. Mirrors [`Facts`]'s numeric facts plus the
+/// one value that isn't a fact at all -- the amount being requested -- so rules like
+/// "auto-approve requests under $5" are expressible without inventing a separate mechanism.
+/// `DaysRemainingInPeriod` is derived (`days_in_period - day_of_period`, so `0` on the period's
+/// last day) because "the last N days of the month" is otherwise unwritable across month lengths.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Field {
@@ -44,6 +47,17 @@ pub enum Field {
     SpendThisPeriodMicros,
     SpendLastPeriodMicros,
     RequestedAmountMicros,
+    AccountAgeDays,
+    DayOfPeriod,
+    DaysRemainingInPeriod,
+}
+
+/// A string fact `Condition::In`/`Condition::NotIn` can test for set membership.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StringField {
+    BillingPlan,
+    QuotaTier,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -57,7 +71,15 @@ pub enum Operator {
 }
 
 /// A boolean expression over [`Facts`]/the requested amount. See the module doc for why `All`/
-/// `Any` are struct variants (`{ conditions: [...] }`) rather than bare tuple variants.
+/// `Any` are struct variants (`{ conditions: [...] }`) rather than bare tuple variants; `Not`
+/// wraps its operand the same way (`{ "type": "not", "condition": {...} }`).
+///
+/// `In`/`NotIn` compare exactly (case-sensitive) against a non-empty `values` set. A string fact
+/// that is absent -- a refill with no project has no `billing_plan`, a member with no assigned
+/// tier has no `quota_tier` -- is a member of no set: `In` is false and `NotIn` is true. That is
+/// absence, not an unknown value, so unlike an unavailable numeric fact it does not abort
+/// evaluation; a rule that must not match absent values should pair `NotIn` with an `In` over
+/// the values it does accept.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Condition {
@@ -66,12 +88,23 @@ pub enum Condition {
         operator: Operator,
         value: i64,
     },
+    In {
+        field: StringField,
+        values: Vec<String>,
+    },
+    NotIn {
+        field: StringField,
+        values: Vec<String>,
+    },
     All {
         conditions: Vec<Condition>,
     },
     Any {
         conditions: Vec<Condition>,
     },
+    Not {
+        condition: Box<Condition>,
+    },
 }
 
 /// One entry in a [`RuleSet`]. Rules are evaluated in order; the first whose `condition` matches
@@ -90,9 +123,9 @@ pub struct Rule {
 }
 
 /// A full, versioned rule-data policy: an ordered list of [`Rule`]s plus the fallback applied
-/// when none match, plus (ADR-0015) the three admin-configured amounts that used to live in the
-/// compile-time `BudgetTier` enum. Deliberately three separate fields, not one -- see ADR-0015
-/// Decisions 5/6 for why "the account's starting budget" and "the fail-closed floor for an
+/// when none match, plus (ADR-0015) the three admin-configured amounts and (ADR-0008) the tier
+/// ladder, all of which used to live in the compile-time `BudgetTier` enum. The amounts are
+/// deliberately three separate fields, not one -- see ADR-0015 Decisions 5/6 for why "the account's starting budget" and "the fail-closed floor for an
 /// outage/unresolvable amount" must never share a value, even though they often will in
 /// practice: conflating them once already meant a lookup failure and a brand-new signup were
 /// indistinguishable, silently.
@@ -118,6 +151,13 @@ pub struct RuleSet {
     /// exceed `starting_amount_micros`: an outage must never grant more than a legitimate new
     /// signup would get.
     pub fail_closed_floor_micros: i64,
+    /// The budget-tier ladder (ADR-0008) and which rung each billing plan starts on. Absent means
+    /// [`TierLadder::legacy`], the seven `b-15` … `b-1000` rungs every revision written before
+    /// this field existed implicitly ran on. Validated for internal consistency on parse; the
+    /// append-only rule against the previously active ladder is checked by
+    /// [`crate::policy_store::PolicyStore`] on activation, since a single document cannot see it.
+    #[serde(default)]
+    pub tier_ladder: TierLadder,
 }
 
 /// ADR-0008's actual policy verbatim: two unaided rungs per period, `manual_review` beyond that.
@@ -180,16 +220,57 @@ fn validate(rule_set: &RuleSet) -> Result<(), BudgetError> {
                 rule.id
             )));
         }
+        validate_condition(&rule.id, &rule.condition)?;
     }
 
-    if rule_set.allowed_amounts_micros.is_empty() {
+    validate_amounts(
+        &rule_set.allowed_amounts_micros,
+        rule_set.starting_amount_micros,
+        rule_set.fail_closed_floor_micros,
+    )
+}
+
+/// Rejects set conditions that could never be meant: an empty `values` list (an `In` that never
+/// matches, a `NotIn` that always does) or a blank entry in one.
+fn validate_condition(rule_id: &str, condition: &Condition) -> Result<(), BudgetError> {
+    match condition {
+        Condition::Threshold { .. } => Ok(()),
+        Condition::In { values, .. } | Condition::NotIn { values, .. } => {
+            if values.is_empty() {
+                return Err(BudgetError::InvalidRuleData(format!(
+                    "rule '{rule_id}' has an in/not_in condition with no values"
+                )));
+            }
+            if values.iter().any(|value| value.trim().is_empty()) {
+                return Err(BudgetError::InvalidRuleData(format!(
+                    "rule '{rule_id}' has an in/not_in condition with an empty value"
+                )));
+            }
+            Ok(())
+        }
+        Condition::All { conditions } | Condition::Any { conditions } => conditions
+            .iter()
+            .try_for_each(|nested| validate_condition(rule_id, nested)),
+        Condition::Not { condition } => validate_condition(rule_id, condition),
+    }
+}
+
+/// The ADR-0015 amount checks every policy document carries, whatever engine evaluates it:
+/// shared with [`crate::opa_wasm`] so a Wasm policy cannot offer an amount ladder a rule-data
+/// policy would have been refused for.
+pub(crate) fn validate_amounts(
+    allowed_amounts_micros: &[i64],
+    starting_amount_micros: i64,
+    fail_closed_floor_micros: i64,
+) -> Result<(), BudgetError> {
+    if allowed_amounts_micros.is_empty() {
         return Err(BudgetError::InvalidRuleData(
             "allowed_amounts_micros must not be empty".to_string(),
         ));
     }
     let mut seen_amounts: HashSet<i64> = HashSet::new();
     let mut previous_amount: Option<i64> = None;
-    for &amount in &rule_set.allowed_amounts_micros {
+    for &amount in allowed_amounts_micros {
         if amount <= 0 {
             return Err(BudgetError::InvalidRuleData(format!(
                 "allowed_amounts_micros entries must be positive, got {amount}"
@@ -210,23 +291,21 @@ fn validate(rule_set: &RuleSet) -> Result<(), BudgetError> {
         previous_amount = Some(amount);
     }
 
-    if rule_set.starting_amount_micros <= 0 {
+    if starting_amount_micros <= 0 {
         return Err(BudgetError::InvalidRuleData(format!(
-            "starting_amount_micros must be positive, got {}",
-            rule_set.starting_amount_micros
+            "starting_amount_micros must be positive, got {starting_amount_micros}"
         )));
     }
-    if rule_set.fail_closed_floor_micros <= 0 {
+    if fail_closed_floor_micros <= 0 {
         return Err(BudgetError::InvalidRuleData(format!(
-            "fail_closed_floor_micros must be positive, got {}",
-            rule_set.fail_closed_floor_micros
+            "fail_closed_floor_micros must be positive, got {fail_closed_floor_micros}"
         )));
     }
-    if rule_set.fail_closed_floor_micros > rule_set.starting_amount_micros {
+    if fail_closed_floor_micros > starting_amount_micros {
         return Err(BudgetError::InvalidRuleData(format!(
-            "fail_closed_floor_micros ({}) must not exceed starting_amount_micros ({}): an \
-             outage must never grant more than a legitimate new signup would get",
-            rule_set.fail_closed_floor_micros, rule_set.starting_amount_micros
+            "fail_closed_floor_micros ({fail_closed_floor_micros}) must not exceed \
+             starting_amount_micros ({starting_amount_micros}): an outage must never grant more \
+             than a legitimate new signup would get"
         )));
     }
 
@@ -265,8 +344,9 @@ enum EvalAbort {
     /// `evaluation_budget` allows. See [`RuleDataEngine`]'s doc comment for why this is a
     /// deterministic node-count budget and not a wall-clock timeout.
     BudgetExceeded,
-    /// A `Condition::Threshold` referenced a `Field` backed by a `Spend` fact, and that fact was
-    /// `Spend::Unavailable`. Per the decision contract's fail-closed rule, this must never be
+    /// A `Condition::Threshold` referenced a `Field` whose fact could not be read: a `Spend` fact
+    /// that was `Spend::Unavailable`, or an optional numeric fact (account age, day of period)
+    /// that was `None`. Per the decision contract's fail-closed rule, this must never be
     /// treated as `false` (would silently fall through, possibly to a more permissive default)
     /// or as `0` (could wrongly satisfy a low-spend threshold) -- it aborts the whole evaluation.
     FieldUnavailable,
@@ -285,9 +365,26 @@ fn resolve_field(field: Field, facts: &Facts, requested_amount_micros: i64) -> R
             Spend::Unavailable => Err(field),
         },
         Field::RequestedAmountMicros => Ok(requested_amount_micros),
+        Field::AccountAgeDays => facts.account_age_days.ok_or(field),
+        Field::DayOfPeriod => facts.day_of_period.map(i64::from).ok_or(field),
+        Field::DaysRemainingInPeriod => match (facts.day_of_period, facts.days_in_period) {
+            (Some(day), Some(days)) => Ok(i64::from(days) - i64::from(day)),
+            _ => Err(field),
+        },
     }
 }
 
+fn resolve_string_field(field: StringField, facts: &Facts) -> Option<&str> {
+    match field {
+        StringField::BillingPlan => facts.billing_plan.as_deref(),
+        StringField::QuotaTier => facts.quota_tier.as_deref(),
+    }
+}
+
+fn is_member(field: StringField, values: &[String], facts: &Facts) -> bool {
+    resolve_string_field(field, facts).is_some_and(|actual| values.iter().any(|v| v == actual))
+}
+
 fn compare(actual: i64, operator: Operator, expected: i64) -> bool {
     match operator {
         Operator::Lt => actual < expected,
@@ -320,6 +417,8 @@ fn eval_condition(
                 .map_err(|_field| EvalAbort::FieldUnavailable)?;
             Ok(compare(actual, *operator, *value))
         }
+        Condition::In { field, values } => Ok(is_member(*field, values, facts)),
+        Condition::NotIn { field, values } => Ok(!is_member(*field, values, facts)),
         Condition::All { conditions } => {
             for nested in conditions {
                 if !eval_condition(
@@ -348,6 +447,13 @@ fn eval_condition(
             }
             Ok(false)
         }
+        Condition::Not { condition } => Ok(!eval_condition(
+            condition,
+            facts,
+            requested_amount_micros,
+            nodes_evaluated,
+            evaluation_budget,
+        )?),
     }
 }
 
@@ -430,7 +536,7 @@ fn abort_decision(
 /// against it would be flaky (the computation is too fast to reliably exceed any test-safe
 /// timeout without an artificial `sleep` or an unrealistically tiny duration, both of which make
 /// for a bad, racy test). Instead, `evaluate` counts how many `Condition` nodes it visits while
-/// checking rules against a request (walking into `All`/`Any` counts each nested condition too),
+/// checking rules against a request (walking into `All`/`Any`/`Not` counts each nested condition too),
 /// and aborts to `Effect::Deny` the instant that count would exceed `evaluation_budget`. This is
 /// the literal analog of a wall-clock timeout for a synchronous evaluator: it protects against a
 /// pathologically large or adversarial rule set exactly the way a timeout protects against a slow
@@ -497,6 +603,11 @@ impl RuleDataEngine {
             .policy_revision
             .clone()
     }
+
+    /// The node-count budget each `evaluate` call runs under.
+    pub fn evaluation_budget(&self) -> usize {
+        self.evaluation_budget
+    }
 }
 
 #[lightbridge_authz_core::async_trait]
@@ -575,6 +686,14 @@ impl PolicyEngine for RuleDataEngine {
             .unwrap_or_else(std::sync::PoisonError::into_inner)
             .fail_closed_floor_micros
     }
+
+    fn tier_ladder(&self) -> TierLadder {
+        self.active
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .tier_ladder
+            .clone()
+    }
 }
 
 #[cfg(test)]
@@ -591,6 +710,11 @@ mod tests {
             self_service_grant_count,
             spend_this_period,
             spend_last_period,
+            billing_plan: None,
+            quota_tier: None,
+            account_age_days: None,
+            day_of_period: None,
+            days_in_period: None,
         }
     }
 
@@ -997,4 +1121,136 @@ mod tests {
         assert_eq!(engine.starting_amount_micros(), 9_000_000);
         assert_eq!(engine.fail_closed_floor_micros(), 9_000_000);
     }
+
+    /// A single-rule set whose one rule auto-approves when `condition_json` matches.
+    fn single_rule(condition_json: &str) -> String {
+        format!(
+            r#"{{
+          "policy_revision": "budget-policy-v1",
+          "rules": [
+            {{ "id": "r", "condition": {condition_json}, "effect": "auto_approve", "reason_code": "matched" }}
+          ],
+          "default_effect": "manual_review",
+          "default_reason_code": "default_reason",
+          "allowed_amounts_micros": [6000000, 15000000, 30000000],
+          "starting_amount_micros": 15000000,
+          "fail_closed_floor_micros": 6000000
+        }}"#
+        )
+    }
+
+    fn plan_facts(billing_plan: Option<&str>, quota_tier: Option<&str>) -> Facts {
+        Facts {
+            billing_plan: billing_plan.map(str::to_string),
+            quota_tier: quota_tier.map(str::to_string),
+            ..default_facts(0)
+        }
+    }
+
+    async fn effect_for(engine: &RuleDataEngine, facts: &Facts) -> Effect {
+        engine
+            .evaluate(facts, 5_000_000)
+            .await
+            .expect("evaluation succeeds")
+            .effect
+    }
+
+    #[tokio::test]
+    async fn in_matches_only_listed_string_values() {
+        let engine = RuleDataEngine::new(
+            &single_rule(
+                r#"{ "type": "in", "field": "billing_plan", "values": ["enterprise", "scale"] }"#,
+            ),
+            1_000,
+        )
+        .expect("valid rule set");
+
+        assert_eq!(
+            effect_for(&engine, &plan_facts(Some("enterprise"), None)).await,
+            Effect::AutoApprove
+        );
+        assert_eq!(
+            effect_for(&engine, &plan_facts(Some("Enterprise"), None)).await,
+            Effect::ManualReview
+        );
+        assert_eq!(
+            effect_for(&engine, &plan_facts(None, None)).await,
+            Effect::ManualReview
+        );
+    }
+
+    #[tokio::test]
+    async fn not_in_matches_absent_and_unlisted_values() {
+        let engine = RuleDataEngine::new(
+            &single_rule(r#"{ "type": "not_in", "field": "quota_tier", "values": ["trial"] }"#),
+            1_000,
+        )
+        .expect("valid rule set");
+
+        assert_eq!(
+            effect_for(&engine, &plan_facts(None, Some("trial"))).await,
+            Effect::ManualReview
+        );
+        assert_eq!(
+            effect_for(&engine, &plan_facts(None, Some("gold"))).await,
+            Effect::AutoApprove
+        );
+        assert_eq!(
+            effect_for(&engine, &plan_facts(None, None)).await,
+            Effect::AutoApprove
+        );
+    }
+
+    #[tokio::test]
+    async fn not_negates_its_condition() {
+        let engine = RuleDataEngine::new(
+            &single_rule(
+                r#"{ "type": "not", "condition": { "type": "threshold", "field": "days_remaining_in_period", "operator": "lt", "value": 2 } }"#,
+            ),
+            1_000,
+        )
+        .expect("valid rule set");
+        let on_day = |day| Facts {
+            day_of_period: Some(day),
+            days_in_period: Some(31),
+            ..default_facts(0)
+        };
+
+        assert_eq!(effect_for(&engine, &on_day(29)).await, Effect::AutoApprove);
+        assert_eq!(effect_for(&engine, &on_day(30)).await, Effect::ManualReview);
+        assert_eq!(effect_for(&engine, &on_day(31)).await, Effect::ManualReview);
+    }
+
+    #[tokio::test]
+    async fn not_does_not_turn_an_unavailable_fact_into_a_match() {
+        let engine = RuleDataEngine::new(
+            &single_rule(
+                r#"{ "type": "not", "condition": { "type": "threshold", "field": "account_age_days", "operator": "lt", "value": 30 } }"#,
+            ),
+            1_000,
+        )
+        .expect("valid rule set");
+
+        let decision = engine
+            .evaluate(&default_facts(0), 5_000_000)
+            .await
+            .expect("evaluation succeeds");
+
+        assert_eq!(decision.effect, Effect::ManualReview);
+        assert_eq!(decision.reason_codes, vec!["required_fact_unavailable"]);
+    }
+
+    #[test]
+    fn set_conditions_without_values_rejected() {
+        for condition in [
+            r#"{ "type": "in", "field": "billing_plan", "values": [] }"#,
+            r#"{ "type": "not", "condition": { "type": "not_in", "field": "quota_tier", "values": [" "] } }"#,
+        ] {
+            let err = validate_rule_data(&single_rule(condition)).expect_err("must reject");
+            assert!(
+                matches!(&err, BudgetError::InvalidRuleData(m) if m.contains("in/not_in")),
+                "{err}"
+            );
+        }
+    }
 }
@@ -1,210 +1,414 @@
 //! The append-only budget-tier ladder from ADR-0008. `x-budget-tier` is stamped on every
 //! request; a refill moves an account up one rung. The ladder may grow (`b-2000` may be
-//! added later) but existing rungs are never reordered or removed -- the `Ord` derive below
-//! relies on the variants staying declared in exactly this ascending order.
+//! added later) but existing rungs are never reordered or removed.
+//!
+//! The ladder is data, not code: it lives in the active policy document as `tier_ladder` (see
+//! [`crate::rule_data::RuleSet::tier_ladder`] and
+//! [`crate::opa_wasm::OpaWasmPolicy::tier_ladder`]), so adding a rung -- or moving to a different
+//! currency scale entirely -- is a policy activation, not a redeploy. A document with no
+//! `tier_ladder` gets [`TierLadder::legacy`], the seven rungs that used to be a compile-time enum,
+//! so every revision written before the field existed keeps meaning exactly what it did.
+//!
+//! "Append-only" is enforced at activation, not here: [`TierLadder::check_extends`] is what
+//! [`crate::policy_store::PolicyStore`] runs against the currently active ladder before swapping
+//! a new one in. A single ladder on its own can only be checked for internal consistency
+//! (strictly ascending amounts, unique labels), which [`TierLadder::new`] and deserialization
+//! both enforce.
 
+use std::collections::{BTreeMap, HashSet};
 use std::fmt;
-use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
 
 use crate::amount::AmountMicros;
 use crate::error::BudgetError;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum BudgetTier {
-    B15,
-    B30,
-    B60,
-    B120,
-    B250,
-    B500,
-    B1000,
+/// One rung: the label stamped as `x-budget-tier` and the per-period amount it represents.
+/// Constructed only through [`BudgetTier::new`] (or deserialization, which goes through it), so
+/// a `BudgetTier` in hand always has a non-blank label and a positive amount.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "BudgetTierSpec")]
+pub struct BudgetTier {
+    label: String,
+    amount_micros: i64,
 }
 
-impl BudgetTier {
-    /// Every rung, ascending, in declaration order. The single source of truth for "the whole
-    /// ladder" -- [`crate::refill::RefillService::refill_status`] returns this (paired with each
-    /// rung's amount) so a caller can render "where am I, what's next" without the frontend
-    /// hand-maintaining its own copy of the ladder. Kept as a `const` rather than a `Vec`-building
-    /// function since the ladder itself is a compile-time constant, not configuration.
-    pub const ALL: [BudgetTier; 7] = [
-        BudgetTier::B15,
-        BudgetTier::B30,
-        BudgetTier::B60,
-        BudgetTier::B120,
-        BudgetTier::B250,
-        BudgetTier::B500,
-        BudgetTier::B1000,
-    ];
+#[derive(Deserialize)]
+struct BudgetTierSpec {
+    label: String,
+    amount_micros: i64,
+}
 
-    pub fn amount(&self) -> AmountMicros {
-        let micros = match self {
-            BudgetTier::B15 => 15_000_000,
-            BudgetTier::B30 => 30_000_000,
-            BudgetTier::B60 => 60_000_000,
-            BudgetTier::B120 => 120_000_000,
-            BudgetTier::B250 => 250_000_000,
-            BudgetTier::B500 => 500_000_000,
-            BudgetTier::B1000 => 1_000_000_000,
-        };
-        AmountMicros::new(micros).expect("every budget tier amount is a positive constant")
-    }
-
-    pub fn label(&self) -> &'static str {
-        match self {
-            BudgetTier::B15 => "b-15",
-            BudgetTier::B30 => "b-30",
-            BudgetTier::B60 => "b-60",
-            BudgetTier::B120 => "b-120",
-            BudgetTier::B250 => "b-250",
-            BudgetTier::B500 => "b-500",
-            BudgetTier::B1000 => "b-1000",
-        }
+impl TryFrom<BudgetTierSpec> for BudgetTier {
+    type Error = BudgetError;
+
+    fn try_from(spec: BudgetTierSpec) -> Result<Self, Self::Error> {
+        BudgetTier::new(spec.label, spec.amount_micros)
     }
+}
 
-    pub fn next(&self) -> Option<BudgetTier> {
-        match self {
-            BudgetTier::B15 => Some(BudgetTier::B30),
-            BudgetTier::B30 => Some(BudgetTier::B60),
-            BudgetTier::B60 => Some(BudgetTier::B120),
-            BudgetTier::B120 => Some(BudgetTier::B250),
-            BudgetTier::B250 => Some(BudgetTier::B500),
-            BudgetTier::B500 => Some(BudgetTier::B1000),
-            BudgetTier::B1000 => None,
+impl BudgetTier {
+    pub fn new(label: impl Into<String>, amount_micros: i64) -> Result<Self, BudgetError> {
+        let label = label.into();
+        if label.trim().is_empty() {
+            return Err(BudgetError::InvalidRuleData(
+                "budget tier label must not be empty".to_string(),
+            ));
         }
+        AmountMicros::new(amount_micros).map_err(|_| {
+            BudgetError::InvalidRuleData(format!(
+                "budget tier '{label}' amount_micros must be positive, got {amount_micros}"
+            ))
+        })?;
+        Ok(Self {
+            label,
+            amount_micros,
+        })
     }
 
-    /// The inverse of [`Self::amount`]: the tier whose `.amount().get()` exactly equals
-    /// `amount`, or `None` if `amount` doesn't match any known rung. Used by
-    /// [`crate::refill::RefillService`] to resolve an account's current tier from the raw
-    /// `amount_micros` of its most recent tier-representing grant -- see that module's doc
-    /// comment for why a `None` here is a defensive fallback, not an expected case.
-    pub fn from_amount_micros(amount: i64) -> Option<BudgetTier> {
-        match amount {
-            15_000_000 => Some(BudgetTier::B15),
-            30_000_000 => Some(BudgetTier::B30),
-            60_000_000 => Some(BudgetTier::B60),
-            120_000_000 => Some(BudgetTier::B120),
-            250_000_000 => Some(BudgetTier::B250),
-            500_000_000 => Some(BudgetTier::B500),
-            1_000_000_000 => Some(BudgetTier::B1000),
-            _ => None,
-        }
+    pub fn amount(&self) -> AmountMicros {
+        AmountMicros::new(self.amount_micros).expect("validated positive in BudgetTier::new")
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
     }
 }
 
 impl fmt::Display for BudgetTier {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.label())
+        write!(f, "{}", self.label)
+    }
+}
+
+/// The whole ladder, ascending, plus which rung each billing plan starts on (ADR-0008: "the
+/// billing plan determines the starting rung"). A plan with no entry in `plan_starting_tiers`
+/// -- and an account with no plan at all -- starts on the lowest rung, the same safe default the
+/// compile-time ladder always had.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "TierLadderSpec")]
+pub struct TierLadder {
+    tiers: Vec<BudgetTier>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    plan_starting_tiers: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct TierLadderSpec {
+    tiers: Vec<BudgetTier>,
+    #[serde(default)]
+    plan_starting_tiers: BTreeMap<String, String>,
+}
+
+impl TryFrom<TierLadderSpec> for TierLadder {
+    type Error = BudgetError;
+
+    fn try_from(spec: TierLadderSpec) -> Result<Self, Self::Error> {
+        TierLadder::new(spec.tiers, spec.plan_starting_tiers)
     }
 }
 
-impl FromStr for BudgetTier {
-    type Err = BudgetError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "b-15" => Ok(BudgetTier::B15),
-            "b-30" => Ok(BudgetTier::B30),
-            "b-60" => Ok(BudgetTier::B60),
-            "b-120" => Ok(BudgetTier::B120),
-            "b-250" => Ok(BudgetTier::B250),
-            "b-500" => Ok(BudgetTier::B500),
-            "b-1000" => Ok(BudgetTier::B1000),
-            _ => Err(BudgetError::UnknownTier(s.to_string())),
+impl Default for TierLadder {
+    fn default() -> Self {
+        Self::legacy()
+    }
+}
+
+impl TierLadder {
+    /// Validates and builds a ladder: at least one rung, strictly ascending amounts, unique
+    /// labels, and every `plan_starting_tiers` value naming one of those labels.
+    pub fn new(
+        tiers: Vec<BudgetTier>,
+        plan_starting_tiers: BTreeMap<String, String>,
+    ) -> Result<Self, BudgetError> {
+        if tiers.is_empty() {
+            return Err(BudgetError::InvalidRuleData(
+                "tier_ladder.tiers must not be empty".to_string(),
+            ));
+        }
+        let mut seen_labels: HashSet<&str> = HashSet::new();
+        for tier in &tiers {
+            if !seen_labels.insert(tier.label()) {
+                return Err(BudgetError::InvalidRuleData(format!(
+                    "duplicate budget tier label '{}'",
+                    tier.label()
+                )));
+            }
+        }
+        for pair in tiers.windows(2) {
+            if pair[1].amount_micros <= pair[0].amount_micros {
+                return Err(BudgetError::InvalidRuleData(format!(
+                    "tier_ladder.tiers must be strictly ascending by amount_micros: '{}' ({}) \
+                     does not exceed '{}' ({})",
+                    pair[1].label, pair[1].amount_micros, pair[0].label, pair[0].amount_micros
+                )));
+            }
+        }
+        for (plan, label) in &plan_starting_tiers {
+            if plan.trim().is_empty() {
+                return Err(BudgetError::InvalidRuleData(
+                    "tier_ladder.plan_starting_tiers keys must not be empty".to_string(),
+                ));
+            }
+            if !seen_labels.contains(label.as_str()) {
+                return Err(BudgetError::InvalidRuleData(format!(
+                    "tier_ladder.plan_starting_tiers maps plan '{plan}' to unknown tier '{label}'"
+                )));
+            }
+        }
+        Ok(Self {
+            tiers,
+            plan_starting_tiers,
+        })
+    }
+
+    /// The seven rungs `b-15` … `b-1000` (USD), with no plan mappings. What a policy document
+    /// without a `tier_ladder` field gets.
+    pub fn legacy() -> Self {
+        let tiers = [
+            ("b-15", 15_000_000),
+            ("b-30", 30_000_000),
+            ("b-60", 60_000_000),
+            ("b-120", 120_000_000),
+            ("b-250", 250_000_000),
+            ("b-500", 500_000_000),
+            ("b-1000", 1_000_000_000),
+        ]
+        .into_iter()
+        .map(|(label, amount_micros)| BudgetTier {
+            label: label.to_string(),
+            amount_micros,
+        })
+        .collect();
+        Self {
+            tiers,
+            plan_starting_tiers: BTreeMap::new(),
         }
     }
+
+    /// Every rung, ascending.
+    pub fn tiers(&self) -> &[BudgetTier] {
+        &self.tiers
+    }
+
+    pub fn plan_starting_tiers(&self) -> &BTreeMap<String, String> {
+        &self.plan_starting_tiers
+    }
+
+    pub fn lowest(&self) -> &BudgetTier {
+        self.tiers
+            .first()
+            .expect("validated non-empty in TierLadder::new")
+    }
+
+    /// The rung named `label`, or [`BudgetError::UnknownTier`].
+    pub fn get(&self, label: &str) -> Result<&BudgetTier, BudgetError> {
+        self.tiers
+            .iter()
+            .find(|tier| tier.label() == label)
+            .ok_or_else(|| BudgetError::UnknownTier(label.to_string()))
+    }
+
+    pub fn next(&self, tier: &BudgetTier) -> Option<&BudgetTier> {
+        let position = self.tiers.iter().position(|rung| rung == tier)?;
+        self.tiers.get(position + 1)
+    }
+
+    /// The rung whose amount exactly equals `amount_micros`, or `None` if it matches no rung.
+    /// Used by [`crate::repo::BudgetRepo::current_tier`] to resolve an account's current tier
+    /// from the raw `amount_micros` of its most recent tier-representing grant.
+    pub fn from_amount_micros(&self, amount_micros: i64) -> Option<&BudgetTier> {
+        self.tiers
+            .iter()
+            .find(|tier| tier.amount_micros == amount_micros)
+    }
+
+    /// The rung an account on `billing_plan` starts on with no qualifying grant history: the
+    /// plan's `plan_starting_tiers` entry if it has one, else the lowest rung.
+    pub fn starting_tier(&self, billing_plan: Option<&str>) -> &BudgetTier {
+        billing_plan
+            .and_then(|plan| self.plan_starting_tiers.get(plan))
+            .and_then(|label| self.get(label).ok())
+            .unwrap_or_else(|| self.lowest())
+    }
+
+    /// The `x-budget-tier` label for an amount: the rung's own label if one matches exactly,
+    /// else a synthesized `b-<whole dollars>`. ADR-0015's amounts are admin-configured and need
+    /// not sit on a rung (the shipped default's $6 fail-closed floor is below `b-15`), and
+    /// stamping the lowest rung's label for such an amount would claim more than was actually
+    /// resolved. The synthesized form is a label nothing matches exactly, no different in kind
+    /// from an unknown tier.
+    pub fn label_for_amount_micros(&self, amount_micros: i64) -> String {
+        match self.from_amount_micros(amount_micros) {
+            Some(tier) => tier.label().to_string(),
+            None => format!("b-{}", amount_micros / 1_000_000),
+        }
+    }
+
+    /// The append-only rule: `self` (the ladder about to be activated) must keep every rung of
+    /// `active`, in order, with the same label and amount, and may only add rungs above them.
+    /// A token already stamped with an existing label must keep meaning the same amount.
+    /// `plan_starting_tiers` is free to change between revisions.
+    pub fn check_extends(&self, active: &TierLadder) -> Result<(), BudgetError> {
+        if self.tiers.len() < active.tiers.len() {
+            return Err(BudgetError::InvalidRuleData(format!(
+                "tier_ladder is append-only: the active ladder has {} rungs, the new one has {}",
+                active.tiers.len(),
+                self.tiers.len()
+            )));
+        }
+        for (position, (kept, new)) in active.tiers.iter().zip(&self.tiers).enumerate() {
+            if kept != new {
+                return Err(BudgetError::InvalidRuleData(format!(
+                    "tier_ladder is append-only: rung {position} changed from '{}' ({}) to '{}' \
+                     ({})",
+                    kept.label, kept.amount_micros, new.label, new.amount_micros
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const LADDER: [BudgetTier; 7] = BudgetTier::ALL;
+    fn tier(label: &str, amount_micros: i64) -> BudgetTier {
+        BudgetTier::new(label, amount_micros).expect("valid tier")
+    }
 
     #[test]
-    fn ladder_is_strictly_ascending() {
-        for pair in LADDER.windows(2) {
-            assert!(pair[0] < pair[1], "{:?} must be < {:?}", pair[0], pair[1]);
-        }
-        assert!(BudgetTier::B15 < BudgetTier::B30);
-        assert!(BudgetTier::B1000 > BudgetTier::B15);
+    fn legacy_ladder_is_the_seven_usd_rungs() {
+        let ladder = TierLadder::legacy();
+        let labels: Vec<&str> = ladder.tiers().iter().map(BudgetTier::label).collect();
+        assert_eq!(
+            labels,
+            ["b-15", "b-30", "b-60", "b-120", "b-250", "b-500", "b-1000"]
+        );
+        assert_eq!(ladder.lowest().amount().get(), 15_000_000);
+        assert_eq!(
+            ladder.get("b-1000").expect("known").amount().get(),
+            1_000_000_000
+        );
+        assert_eq!(TierLadder::default(), ladder);
     }
 
     #[test]
     fn unknown_rung_is_a_typed_error_not_a_default() {
+        let ladder = TierLadder::legacy();
         assert!(matches!(
-            "b-2000".parse::<BudgetTier>(),
+            ladder.get("b-2000"),
             Err(BudgetError::UnknownTier(_))
         ));
-        assert!("b-20".parse::<BudgetTier>().is_err());
-        assert!("garbage".parse::<BudgetTier>().is_err());
-        assert!("".parse::<BudgetTier>().is_err());
+        assert!(ladder.get("").is_err());
     }
 
     #[test]
-    fn top_rung_has_no_next() {
-        assert_eq!(BudgetTier::B1000.next(), None);
+    fn next_walks_the_ladder_and_stops_at_the_top() {
+        let ladder = TierLadder::legacy();
+        for pair in ladder.tiers().windows(2) {
+            assert_eq!(ladder.next(&pair[0]), Some(&pair[1]));
+        }
+        assert_eq!(ladder.next(ladder.get("b-1000").expect("known")), None);
     }
 
     #[test]
-    fn every_other_rung_has_the_expected_next() {
-        for pair in LADDER.windows(2) {
-            assert_eq!(pair[0].next(), Some(pair[1]));
+    fn every_tier_round_trips_through_from_amount_micros() {
+        let ladder = TierLadder::legacy();
+        for tier in ladder.tiers() {
+            assert_eq!(ladder.from_amount_micros(tier.amount().get()), Some(tier));
         }
+        assert_eq!(ladder.from_amount_micros(12_345), None);
     }
 
     #[test]
-    fn each_tier_amount_matches_the_dollar_value_table() {
-        assert_eq!(
-            BudgetTier::B15.amount(),
-            AmountMicros::new(15_000_000).expect("valid amount")
-        );
-        assert_eq!(
-            BudgetTier::B30.amount(),
-            AmountMicros::new(30_000_000).expect("valid amount")
-        );
-        assert_eq!(
-            BudgetTier::B60.amount(),
-            AmountMicros::new(60_000_000).expect("valid amount")
-        );
-        assert_eq!(
-            BudgetTier::B120.amount(),
-            AmountMicros::new(120_000_000).expect("valid amount")
-        );
-        assert_eq!(
-            BudgetTier::B250.amount(),
-            AmountMicros::new(250_000_000).expect("valid amount")
+    fn new_rejects_an_inconsistent_ladder() {
+        assert!(TierLadder::new(vec![], BTreeMap::new()).is_err());
+        assert!(
+            TierLadder::new(vec![tier("a", 2), tier("b", 1)], BTreeMap::new()).is_err(),
+            "descending amounts"
         );
-        assert_eq!(
-            BudgetTier::B500.amount(),
-            AmountMicros::new(500_000_000).expect("valid amount")
+        assert!(
+            TierLadder::new(vec![tier("a", 1), tier("a", 2)], BTreeMap::new()).is_err(),
+            "duplicate labels"
         );
-        assert_eq!(
-            BudgetTier::B1000.amount(),
-            AmountMicros::new(1_000_000_000).expect("valid amount")
+        assert!(
+            TierLadder::new(
+                vec![tier("a", 1)],
+                BTreeMap::from([("pro".to_string(), "z".to_string())])
+            )
+            .is_err(),
+            "plan mapped to an unknown rung"
         );
+        assert!(BudgetTier::new(" ", 1).is_err());
+        assert!(BudgetTier::new("a", 0).is_err());
     }
 
     #[test]
-    fn label_matches_wire_form() {
-        assert_eq!(BudgetTier::B15.label(), "b-15");
-        assert_eq!(BudgetTier::B1000.label(), "b-1000");
-        assert_eq!(BudgetTier::B15.to_string(), "b-15");
+    fn deserialization_validates() {
+        let ladder: TierLadder = serde_json::from_str(
+            r#"{"tiers":[{"label":"e-10","amount_micros":10000000},{"label":"e-20","amount_micros":20000000}],
+                "plan_starting_tiers":{"enterprise":"e-20"}}"#,
+        )
+        .expect("valid ladder");
+        assert_eq!(ladder.tiers().len(), 2);
+
+        let descending =
+            r#"{"tiers":[{"label":"a","amount_micros":2},{"label":"b","amount_micros":1}]}"#;
+        assert!(serde_json::from_str::<TierLadder>(descending).is_err());
+        let zero = r#"{"tiers":[{"label":"a","amount_micros":0}]}"#;
+        assert!(serde_json::from_str::<TierLadder>(zero).is_err());
     }
 
     #[test]
-    fn every_tier_round_trips_through_amount_and_from_amount_micros() {
-        for tier in LADDER {
+    fn starting_tier_follows_the_plan_mapping_else_the_lowest_rung() {
+        let ladder = TierLadder::new(
+            TierLadder::legacy().tiers().to_vec(),
+            BTreeMap::from([("enterprise".to_string(), "b-1000".to_string())]),
+        )
+        .expect("valid ladder");
+        assert_eq!(ladder.starting_tier(Some("enterprise")).label(), "b-1000");
+        assert_eq!(ladder.starting_tier(Some("free")).label(), "b-15");
+        assert_eq!(ladder.starting_tier(None).label(), "b-15");
+    }
+
+    #[test]
+    fn label_for_amount_micros_uses_the_rung_label_or_synthesizes_one() {
+        let ladder = TierLadder::legacy();
+        for tier in ladder.tiers() {
             assert_eq!(
-                BudgetTier::from_amount_micros(tier.amount().get()),
-                Some(tier),
-                "{tier:?} must round-trip through .amount() -> from_amount_micros"
+                ladder.label_for_amount_micros(tier.amount().get()),
+                tier.label()
             );
         }
+        // ADR-0015's shipped $6 floor sits below every rung; it must not be stamped as `b-15`.
+        assert_eq!(ladder.label_for_amount_micros(6_000_000), "b-6");
+        // Sub-dollar remainders truncate rather than round up to a claim of more budget.
+        assert_eq!(ladder.label_for_amount_micros(6_500_000), "b-6");
     }
 
     #[test]
-    fn from_amount_micros_rejects_a_non_tier_amount() {
-        assert_eq!(BudgetTier::from_amount_micros(12_345), None);
+    fn check_extends_allows_appending_and_rejects_everything_else() {
+        let active = TierLadder::legacy();
+        let mut appended = active.tiers().to_vec();
+        appended.push(tier("b-2000", 2_000_000_000));
+        let appended = TierLadder::new(appended, BTreeMap::new()).expect("valid ladder");
+        appended.check_extends(&active).expect("appending a rung");
+        active.check_extends(&active).expect("an unchanged ladder");
+
+        let truncated =
+            TierLadder::new(active.tiers()[..6].to_vec(), BTreeMap::new()).expect("valid ladder");
+        assert!(truncated.check_extends(&active).is_err(), "removed rung");
+
+        let mut repriced = active.tiers().to_vec();
+        repriced[0] = tier("b-15", 14_000_000);
+        let repriced = TierLadder::new(repriced, BTreeMap::new()).expect("valid ladder");
+        assert!(repriced.check_extends(&active).is_err(), "repriced rung");
+
+        let mut relabeled = active.tiers().to_vec();
+        relabeled[0] = tier("starter", 15_000_000);
+        let relabeled = TierLadder::new(relabeled, BTreeMap::new()).expect("valid ladder");
+        assert!(relabeled.check_extends(&active).is_err(), "relabeled rung");
     }
 }
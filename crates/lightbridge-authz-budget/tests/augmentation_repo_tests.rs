@@ -10,7 +10,6 @@ use lightbridge_authz_budget::augmentation::{
 use lightbridge_authz_budget::decision::Effect;
 use lightbridge_authz_budget::error::BudgetError;
 use lightbridge_authz_budget::period::Period;
-use lightbridge_authz_budget::tier::BudgetTier;
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
 use sqlx::PgPool;
@@ -47,7 +46,7 @@ fn base_new_request(account_id: &str, amount_micros: i64) -> NewAugmentationRequ
         account_id: account_id.to_string(),
         project_id: None,
         period: Period::parse(PERIOD).expect("valid period"),
-        requested_tier: BudgetTier::B30,
+        requested_tier: "b-30".to_string(),
         requested_amount_micros: amount_micros,
         idempotency_key: None,
     }
@@ -88,7 +87,7 @@ async fn create_inserts_a_fresh_request(pool: PgPool) {
     assert_eq!(created.account_id, account_id);
     assert_eq!(created.project_id, None);
     assert_eq!(created.period, Period::parse(PERIOD).expect("valid period"));
-    assert_eq!(created.requested_tier, BudgetTier::B30);
+    assert_eq!(created.requested_tier, "b-30");
     assert_eq!(created.requested_amount_micros, 30_000_000);
     assert_eq!(created.status, AugmentationStatus::Created);
     assert_eq!(created.policy_effect, None);
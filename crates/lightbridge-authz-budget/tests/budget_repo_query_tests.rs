@@ -6,7 +6,7 @@ use lightbridge_authz_budget::error::BudgetError;
 use lightbridge_authz_budget::period::Period;
 use lightbridge_authz_budget::repo::{BudgetRepo, GrantRequest};
 use lightbridge_authz_budget::source::GrantSource;
-use lightbridge_authz_budget::tier::BudgetTier;
+use lightbridge_authz_budget::tier::{BudgetTier, TierLadder};
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::db::DbPool;
 use sqlx::PgPool;
@@ -228,8 +228,8 @@ async fn list_grants_does_not_leak_another_accounts_entries(pool: PgPool) {
 }
 
 /// ADR-0014 (the token-mint budget-tier claim) reuses this exact resolver, so its fallback
-/// behavior on a brand-new account/period must be `B15`, the lowest rung -- never an error,
-/// never a permissive default.
+/// behavior on a brand-new account/period with no plan mapping must be `b-15`, the lowest rung --
+/// never an error, never a permissive default.
 #[sqlx::test(migrations = "../../migrations")]
 async fn current_tier_defaults_to_b15_with_no_grant(pool: PgPool) {
     let account_id = cuid2();
@@ -239,15 +239,15 @@ async fn current_tier_defaults_to_b15_with_no_grant(pool: PgPool) {
     let period = Period::parse(PERIOD).expect("valid period");
 
     let tier = repo
-        .current_tier(&account_id, &period)
+        .current_tier(&account_id, &period, &TierLadder::legacy(), None)
         .await
         .expect("current_tier must succeed even with zero grants");
 
-    assert_eq!(tier, BudgetTier::B15);
+    assert_eq!(tier.label(), "b-15");
 }
 
 /// The primary correctness case: a real grant on the ledger must be the tier reported back,
-/// proving `current_tier` genuinely reads the ledger rather than always returning its `B15`
+/// proving `current_tier` genuinely reads the ledger rather than always returning its `b-15`
 /// default.
 #[sqlx::test(migrations = "../../migrations")]
 async fn current_tier_resolves_the_most_recent_qualifying_grant(pool: PgPool) {
@@ -259,18 +259,18 @@ async fn current_tier_resolves_the_most_recent_qualifying_grant(pool: PgPool) {
         &account_id,
         PERIOD,
         GrantSource::SelfService,
-        BudgetTier::B120.amount().get(),
+        120_000_000,
     ))
     .await
     .expect("grant must succeed");
 
     let period = Period::parse(PERIOD).expect("valid period");
     let tier = repo
-        .current_tier(&account_id, &period)
+        .current_tier(&account_id, &period, &TierLadder::legacy(), None)
         .await
         .expect("current_tier must succeed");
 
-    assert_eq!(tier, BudgetTier::B120);
+    assert_eq!(tier.label(), "b-120");
 }
 
 /// `correction`/`refund` grants must NOT be read as "the tier this account is on" -- see
@@ -287,7 +287,7 @@ async fn current_tier_ignores_correction_and_refund_sources(pool: PgPool) {
         &account_id,
         PERIOD,
         GrantSource::SelfService,
-        BudgetTier::B60.amount().get(),
+        60_000_000,
     ))
     .await
     .expect("base grant must succeed");
@@ -310,13 +310,13 @@ async fn current_tier_ignores_correction_and_refund_sources(pool: PgPool) {
 
     let period = Period::parse(PERIOD).expect("valid period");
     let tier = repo
-        .current_tier(&account_id, &period)
+        .current_tier(&account_id, &period, &TierLadder::legacy(), None)
         .await
         .expect("current_tier must succeed");
 
     assert_eq!(
-        tier,
-        BudgetTier::B60,
+        tier.label(),
+        "b-60",
         "the correction/refund rows landed after the tier grant but must not be read as the \
          account's current tier"
     );
@@ -324,9 +324,9 @@ async fn current_tier_ignores_correction_and_refund_sources(pool: PgPool) {
 
 /// A qualifying grant whose `amount_micros` doesn't match any known rung (data this service
 /// doesn't expect in practice, e.g. a hand-edited or historically-imported row) must fall back to
-/// `B15` rather than propagating a lookup failure or silently rounding to the nearest rung.
+/// the lowest rung rather than propagating a lookup failure or silently rounding to the nearest rung.
 #[sqlx::test(migrations = "../../migrations")]
-async fn current_tier_falls_back_to_b15_on_unrecognized_amount(pool: PgPool) {
+async fn current_tier_falls_back_to_the_lowest_rung_on_unrecognized_amount(pool: PgPool) {
     let account_id = cuid2();
     insert_account(&pool, &account_id).await;
 
@@ -342,18 +342,75 @@ async fn current_tier_falls_back_to_b15_on_unrecognized_amount(pool: PgPool) {
 
     let period = Period::parse(PERIOD).expect("valid period");
     let tier = repo
-        .current_tier(&account_id, &period)
+        .current_tier(&account_id, &period, &TierLadder::legacy(), None)
         .await
         .expect("current_tier must succeed even on an unrecognized amount");
 
-    assert_eq!(tier, BudgetTier::B15);
+    assert_eq!(tier.label(), "b-15");
 }
 
-/// A genuine storage failure is NOT swallowed into `B15` inside `BudgetRepo::current_tier`
+/// ADR-0008's "the billing plan determines the starting rung": with no qualifying grant yet, an
+/// account starts on its plan's mapped rung; an unmapped plan still starts on the lowest one.
+#[sqlx::test(migrations = "../../migrations")]
+async fn current_tier_with_no_grant_starts_on_the_plans_rung(pool: PgPool) {
+    let account_id = cuid2();
+    insert_account(&pool, &account_id).await;
+
+    let repo = BudgetRepo::new(Arc::new(DbPool::from_pool(pool)));
+    let period = Period::parse(PERIOD).expect("valid period");
+    let ladder = TierLadder::new(
+        TierLadder::legacy().tiers().to_vec(),
+        [("enterprise".to_string(), "b-1000".to_string())].into(),
+    )
+    .expect("valid ladder");
+
+    let enterprise = repo
+        .current_tier(&account_id, &period, &ladder, Some("enterprise"))
+        .await
+        .expect("current_tier must succeed");
+    assert_eq!(enterprise.label(), "b-1000");
+
+    let unmapped = repo
+        .current_tier(&account_id, &period, &ladder, Some("free"))
+        .await
+        .expect("current_tier must succeed");
+    assert_eq!(unmapped.label(), "b-15");
+}
+
+/// The rung is resolved against whatever ladder the caller passes, not a compiled-in one: a
+/// grant for an amount that is a rung only on a custom ladder resolves to that ladder's label.
+#[sqlx::test(migrations = "../../migrations")]
+async fn current_tier_resolves_against_the_given_ladder(pool: PgPool) {
+    let account_id = cuid2();
+    insert_account(&pool, &account_id).await;
+
+    let repo = BudgetRepo::new(Arc::new(DbPool::from_pool(pool)));
+    repo.grant(base_request(
+        &account_id,
+        PERIOD,
+        GrantSource::SelfService,
+        2_000_000_000,
+    ))
+    .await
+    .expect("grant must succeed");
+
+    let mut tiers = TierLadder::legacy().tiers().to_vec();
+    tiers.push(BudgetTier::new("b-2000", 2_000_000_000).expect("valid tier"));
+    let ladder = TierLadder::new(tiers, Default::default()).expect("valid ladder");
+    let period = Period::parse(PERIOD).expect("valid period");
+
+    let tier = repo
+        .current_tier(&account_id, &period, &ladder, None)
+        .await
+        .expect("current_tier must succeed");
+    assert_eq!(tier.label(), "b-2000");
+}
+
+/// A genuine storage failure is NOT swallowed into the lowest rung inside `BudgetRepo::current_tier`
 /// itself -- see that method's doc comment for why (a caller that wants to distinguish "new
 /// account" from "ledger unavailable" still can). The token-mint path
 /// (`TokenExchangeOpStore::resolve_budget_tier`, `lightbridge-authz-rest`) is the layer that
-/// downgrades this `Err` to `B15`; this test pins that `current_tier` itself still surfaces the
+/// downgrades this `Err` to the fail-closed floor; this test pins that `current_tier` itself still surfaces the
 /// error rather than pre-emptively hiding it.
 #[tokio::test]
 async fn current_tier_propagates_a_genuine_storage_failure() {
@@ -364,7 +421,9 @@ async fn current_tier_propagates_a_genuine_storage_failure() {
     let repo = BudgetRepo::new(Arc::new(DbPool::from_pool(pool)));
     let period = Period::parse(PERIOD).expect("valid period");
 
-    let result = repo.current_tier("unreachable-account", &period).await;
+    let result = repo
+        .current_tier("unreachable-account", &period, &TierLadder::legacy(), None)
+        .await;
 
     assert!(
         matches!(result, Err(BudgetError::StorageFailed(_))),
@@ -7,6 +7,7 @@ use lightbridge_authz_budget::error::BudgetError;
 use lightbridge_authz_budget::facts::Facts;
 use lightbridge_authz_budget::policy_store::PolicyStore;
 use lightbridge_authz_budget::spend::Spend;
+use lightbridge_authz_budget::tier::TierLadder;
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
 use sqlx::PgPool;
 
@@ -54,6 +55,11 @@ fn facts_with_grant_count(self_service_grant_count: i32) -> Facts {
         self_service_grant_count,
         spend_this_period: Spend::Known(0),
         spend_last_period: Spend::Known(0),
+        billing_plan: None,
+        quota_tier: None,
+        account_age_days: None,
+        day_of_period: None,
+        days_in_period: None,
     }
 }
 
@@ -447,3 +453,170 @@ async fn create_revision_with_malformed_rule_data_writes_nothing(pool: PgPool) {
         "the live engine must be completely untouched by a rejected write"
     );
 }
+
+/// A minimal module speaking OPA's Wasm ABI whose `budget/refill/decision` entrypoint always
+/// returns `auto_approve_capped` at 6 USD -- enough to prove the stored document round-trips
+/// through activation and a restart onto the Wasm engine.
+fn opa_wasm_policy_document(policy_revision: &str) -> String {
+    use base64::Engine as _;
+
+    let wasm = wat::parse_str(
+        r#"(module
+  (import "env" "memory" (memory 2))
+  (global $heap (mut i32) (i32.const 65536))
+  (data (i32.const 1024) "[{\"result\":{\"effect\":\"auto_approve_capped\",\"maximum_amount_micros\":6000000}}]\00")
+  (data (i32.const 4096) "{\"budget/refill/decision\":0}\00")
+  (data (i32.const 8192) "{}\00")
+  (func (export "opa_malloc") (param $n i32) (result i32) (local $p i32)
+    global.get $heap
+    local.set $p
+    global.get $heap
+    local.get $n
+    i32.add
+    global.set $heap
+    local.get $p)
+  (func (export "opa_heap_ptr_get") (result i32) global.get $heap)
+  (func (export "opa_json_parse") (param i32 i32) (result i32) local.get 0)
+  (func (export "opa_json_dump") (param i32) (result i32) local.get 0)
+  (func (export "entrypoints") (result i32) i32.const 4096)
+  (func (export "builtins") (result i32) i32.const 8192)
+  (func (export "opa_eval") (param i32 i32 i32 i32 i32 i32 i32) (result i32) i32.const 1024))"#,
+    )
+    .expect("fixture module assembles");
+    serde_json::json!({
+        "engine": "opa_wasm",
+        "policy_revision": policy_revision,
+        "entrypoint": "budget/refill/decision",
+        "module_base64": base64::engine::general_purpose::STANDARD.encode(wasm),
+        "allowed_amounts_micros": [6_000_000, 15_000_000, 30_000_000],
+        "starting_amount_micros": 15_000_000,
+        "fail_closed_floor_micros": 6_000_000,
+    })
+    .to_string()
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn activate_with_an_opa_wasm_document_persists_and_survives_a_restart(pool: PgPool) {
+    let db_pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::from_pool(pool));
+
+    let store = PolicyStore::load_active_from_db(
+        Arc::clone(&db_pool),
+        SEEDED_POLICY_SET_ID,
+        EVALUATION_BUDGET,
+    )
+    .await
+    .expect("seeded policy set loads");
+
+    let returned_revision = store
+        .activate(
+            &opa_wasm_policy_document("budget-policy-wasm-test"),
+            Some("tester"),
+        )
+        .await
+        .expect("a valid opa_wasm document must activate");
+    assert_eq!(returned_revision, "budget-policy-wasm-test");
+
+    let restarted_store =
+        PolicyStore::load_active_from_db(db_pool, SEEDED_POLICY_SET_ID, EVALUATION_BUDGET)
+            .await
+            .expect("a fresh load simulating a restart must succeed");
+    assert_eq!(
+        restarted_store.active_policy_revision(),
+        "budget-policy-wasm-test"
+    );
+
+    let decision = restarted_store
+        .engine()
+        .evaluate(&facts_with_grant_count(5), 15_000_000)
+        .await
+        .expect("evaluation succeeds");
+    assert_eq!(decision.effect, Effect::AutoApproveCapped);
+    assert_eq!(decision.approved_amount_micros, 6_000_000);
+    assert_eq!(decision.policy_revision, "budget-policy-wasm-test");
+}
+
+/// `valid_replacement_rule_data` with an explicit `tier_ladder` of the legacy rungs followed by
+/// `extra_rungs` (`(label, amount_micros)` pairs).
+fn rule_data_with_tier_ladder(policy_revision: &str, extra_rungs: &[(&str, i64)]) -> String {
+    let mut document: serde_json::Value =
+        serde_json::from_str(&valid_replacement_rule_data(policy_revision, 2)).expect("valid JSON");
+    let mut tiers =
+        serde_json::to_value(TierLadder::legacy()).expect("ladder serializes")["tiers"].clone();
+    for (label, amount_micros) in extra_rungs {
+        tiers
+            .as_array_mut()
+            .expect("tiers is an array")
+            .push(serde_json::json!({ "label": label, "amount_micros": amount_micros }));
+    }
+    document["tier_ladder"] = serde_json::json!({ "tiers": tiers });
+    document.to_string()
+}
+
+/// ADR-0008's append-only rule, enforced on activation: a revision may add a rung above the
+/// active ladder, but may not drop or reprice one -- and neither may a rollback to a revision from
+/// before the rung was added. A refused activation leaves the database and engine untouched.
+#[sqlx::test(migrations = "../../migrations")]
+async fn activation_enforces_the_append_only_tier_ladder(pool: PgPool) {
+    let db_pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::from_pool(pool.clone()));
+
+    let store = PolicyStore::load_active_from_db(
+        Arc::clone(&db_pool),
+        SEEDED_POLICY_SET_ID,
+        EVALUATION_BUDGET,
+    )
+    .await
+    .expect("seeded policy set loads");
+    assert_eq!(store.engine().tier_ladder(), TierLadder::legacy());
+
+    store
+        .activate(
+            &rule_data_with_tier_ladder("budget-policy-b2000", &[("b-2000", 2_000_000_000)]),
+            Some("tester"),
+        )
+        .await
+        .expect("appending a rung must activate");
+    let ladder = store.engine().tier_ladder();
+    assert_eq!(
+        ladder.get("b-2000").expect("new rung").amount().get(),
+        2_000_000_000
+    );
+
+    let count_before = revision_count(&pool, SEEDED_POLICY_SET_ID).await;
+    let dropped = store
+        .activate(
+            &rule_data_with_tier_ladder("budget-policy-dropped", &[]),
+            Some("tester"),
+        )
+        .await;
+    assert!(
+        matches!(dropped, Err(BudgetError::InvalidRuleData(ref m)) if m.contains("append-only")),
+        "dropping a rung must be refused: {dropped:?}"
+    );
+    let repriced = store
+        .activate(
+            &rule_data_with_tier_ladder("budget-policy-repriced", &[("b-2000", 3_000_000_000)]),
+            Some("tester"),
+        )
+        .await;
+    assert!(
+        matches!(repriced, Err(BudgetError::InvalidRuleData(ref m)) if m.contains("append-only")),
+        "repricing a rung must be refused: {repriced:?}"
+    );
+    assert_eq!(
+        revision_count(&pool, SEEDED_POLICY_SET_ID).await,
+        count_before
+    );
+
+    let rollback = store.activate_by_revision_id(SEEDED_REVISION_ID).await;
+    assert!(
+        matches!(rollback, Err(BudgetError::InvalidRuleData(ref m)) if m.contains("append-only")),
+        "rolling back past the added rung must be refused: {rollback:?}"
+    );
+    assert_eq!(store.active_policy_revision(), "budget-policy-b2000");
+    assert_ne!(
+        active_revision_id(&pool, SEEDED_POLICY_SET_ID)
+            .await
+            .as_deref(),
+        Some(SEEDED_REVISION_ID)
+    );
+}
@@ -15,7 +15,7 @@ use lightbridge_authz_budget::repo::{BudgetRepo, GrantRequest};
 use lightbridge_authz_budget::rule_data::{RuleDataEngine, default_rule_set_json};
 use lightbridge_authz_budget::source::GrantSource;
 use lightbridge_authz_budget::spend::{Spend, SpendReader};
-use lightbridge_authz_budget::tier::BudgetTier;
+use lightbridge_authz_budget::tier::TierLadder;
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
 use sqlx::PgPool;
@@ -160,6 +160,10 @@ impl PolicyEngine for PanicIfCalledPolicyEngine {
     fn fail_closed_floor_micros(&self) -> i64 {
         6_000_000
     }
+
+    fn tier_ladder(&self) -> TierLadder {
+        TierLadder::legacy()
+    }
 }
 
 #[derive(Debug)]
@@ -188,6 +192,10 @@ impl PolicyEngine for AlwaysErrPolicyEngine {
     fn fail_closed_floor_micros(&self) -> i64 {
         6_000_000
     }
+
+    fn tier_ladder(&self) -> TierLadder {
+        TierLadder::legacy()
+    }
 }
 
 /// Counts `evaluate` calls so the idempotency short-circuit's real proof isn't just "the end
@@ -239,6 +247,10 @@ impl PolicyEngine for CountingAutoApprovePolicyEngine {
     fn fail_closed_floor_micros(&self) -> i64 {
         6_000_000
     }
+
+    fn tier_ladder(&self) -> TierLadder {
+        TierLadder::legacy()
+    }
 }
 
 #[sqlx::test(migrations = "../../migrations")]
@@ -255,9 +267,8 @@ async fn first_refill_with_a_named_amount_auto_approves_and_writes_a_grant(pool:
 
     assert_eq!(result.status, AugmentationStatus::AutoApproved);
     assert_eq!(
-        result.requested_tier,
-        BudgetTier::B15,
-        "15_000_000 is an exact BudgetTier label match"
+        result.requested_tier, "b-15",
+        "15_000_000 is an exact rung on the legacy ladder"
     );
     assert_eq!(result.approved_amount_micros, Some(15_000_000));
     let grant_id = result
@@ -288,9 +299,8 @@ async fn second_refill_same_period_with_a_different_named_amount_also_auto_appro
         .await
         .expect("first refill must succeed");
     assert_eq!(
-        first.requested_tier,
-        BudgetTier::B15,
-        "6M has no exact tier label, falls back to B15"
+        first.requested_tier, "b-6",
+        "6M is on no rung, so its label is synthesized rather than the lowest rung's"
     );
     assert_eq!(first.status, AugmentationStatus::AutoApproved);
     assert_eq!(first.approved_amount_micros, Some(6_000_000));
@@ -303,8 +313,7 @@ async fn second_refill_same_period_with_a_different_named_amount_also_auto_appro
         .expect("second refill must succeed");
 
     assert_eq!(
-        second.requested_tier,
-        BudgetTier::B30,
+        second.requested_tier, "b-30",
         "ADR-0015: the caller names the amount directly -- unrelated to the first refill's amount"
     );
     assert_eq!(second.status, AugmentationStatus::AutoApproved);
@@ -323,7 +332,7 @@ async fn exhausting_unaided_allowance_routes_to_pending_review(pool: PgPool) {
         .grant(seed_grant_request(
             &account_id,
             GrantSource::SelfService,
-            BudgetTier::B15.amount().get(),
+            15_000_000,
         ))
         .await
         .expect("seed grant 1 must succeed");
@@ -331,7 +340,7 @@ async fn exhausting_unaided_allowance_routes_to_pending_review(pool: PgPool) {
         .grant(seed_grant_request(
             &account_id,
             GrantSource::SelfService,
-            BudgetTier::B30.amount().get(),
+            30_000_000,
         ))
         .await
         .expect("seed grant 2 must succeed");
@@ -352,8 +361,7 @@ async fn exhausting_unaided_allowance_routes_to_pending_review(pool: PgPool) {
 
     assert_eq!(result.status, AugmentationStatus::PendingReview);
     assert_eq!(
-        result.requested_tier,
-        BudgetTier::B30,
+        result.requested_tier, "b-30",
         "requested_tier is a label for the amount actually requested, not derived from history"
     );
     assert_eq!(result.grant_id, None);
@@ -15,7 +15,6 @@ use lightbridge_authz_budget::review::ReviewService;
 use lightbridge_authz_budget::rule_data::{RuleDataEngine, default_rule_set_json};
 use lightbridge_authz_budget::source::GrantSource;
 use lightbridge_authz_budget::spend::{Spend, SpendReader};
-use lightbridge_authz_budget::tier::BudgetTier;
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
 use sqlx::PgPool;
@@ -59,7 +58,7 @@ async fn seed_pending_review(
             account_id: account_id.to_string(),
             project_id: None,
             period: Period::parse(PERIOD).expect("valid period"),
-            requested_tier: BudgetTier::B30,
+            requested_tier: "b-30".to_string(),
             requested_amount_micros,
             idempotency_key: None,
         })
@@ -123,20 +122,8 @@ impl SpendReader for FixedSpendReader {
 /// not just with hand-seeded rows. Mirrors
 /// `refill_service_tests.rs::exhausting_unaided_allowance_routes_to_pending_review`.
 async fn seed_pending_review_via_refill_service(pool: &PgPool, account_id: &str) -> String {
-    seed_grant(
-        pool,
-        account_id,
-        GrantSource::SelfService,
-        BudgetTier::B15.amount().get(),
-    )
-    .await;
-    seed_grant(
-        pool,
-        account_id,
-        GrantSource::SelfService,
-        BudgetTier::B30.amount().get(),
-    )
-    .await;
+    seed_grant(pool, account_id, GrantSource::SelfService, 15_000_000).await;
+    seed_grant(pool, account_id, GrantSource::SelfService, 30_000_000).await;
 
     let db_pool = db_pool(pool);
     let policy_engine: Arc<dyn lightbridge_authz_budget::decision::PolicyEngine> = Arc::new(
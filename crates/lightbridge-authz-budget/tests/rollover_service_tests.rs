@@ -0,0 +1,192 @@
+#![cfg(feature = "it-tests")]
+
+use std::sync::Arc;
+
+use lightbridge_authz_budget::decision::PolicyEngine;
+use lightbridge_authz_budget::period::Period;
+use lightbridge_authz_budget::policy_document::ActivePolicyEngine;
+use lightbridge_authz_budget::repo::BudgetRepo;
+use lightbridge_authz_budget::rollover::{
+    ROLLOVER_GRANT_REASON, RolloverService, base_grant_idempotency_key,
+};
+use lightbridge_authz_budget::rule_data::default_rule_set_json;
+use lightbridge_authz_budget::source::GrantSource;
+use lightbridge_authz_budget::tier::TierLadder;
+use lightbridge_authz_core::cuid::cuid2;
+use lightbridge_authz_core::db::DbPool;
+use sqlx::PgPool;
+
+const PERIOD: &str = "2026-11";
+const EVALUATION_BUDGET: usize = 1_000;
+
+async fn insert_account(pool: &PgPool, account_id: &str, status: &str) {
+    sqlx::query("INSERT INTO accounts (id, status) VALUES ($1, $2)")
+        .bind(account_id)
+        .bind(status)
+        .execute(pool)
+        .await
+        .expect("inserting a test account must succeed");
+}
+
+async fn insert_project(pool: &PgPool, account_id: &str, billing_plan: &str) {
+    sqlx::query(
+        "INSERT INTO projects (id, account_id, name, billing_plan, billing_identity) \
+         VALUES ($1, $2, 'p', $3, $1)",
+    )
+    .bind(cuid2())
+    .bind(account_id)
+    .bind(billing_plan)
+    .execute(pool)
+    .await
+    .expect("inserting a test project must succeed");
+}
+
+/// The shipped default rule set, plus an `enterprise` plan starting on `b-1000`.
+fn engine_with_enterprise_plan() -> Arc<ActivePolicyEngine> {
+    let mut document: serde_json::Value =
+        serde_json::from_str(default_rule_set_json()).expect("default rule set is valid JSON");
+    document["tier_ladder"] = serde_json::json!({
+        "tiers": TierLadder::legacy().tiers(),
+        "plan_starting_tiers": { "enterprise": "b-1000" }
+    });
+    Arc::new(
+        ActivePolicyEngine::new(&document.to_string(), EVALUATION_BUDGET)
+            .expect("policy document must load"),
+    )
+}
+
+fn service(pool: &PgPool) -> (RolloverService, Arc<BudgetRepo>) {
+    let repo = Arc::new(BudgetRepo::new(Arc::new(DbPool::from_pool(pool.clone()))));
+    (
+        RolloverService::new(repo.clone(), engine_with_enterprise_plan()),
+        repo,
+    )
+}
+
+/// The request's core promise: after a rollover the opening budget is an explicit `base` entry on
+/// the ledger -- visible to `list_grants` and reflected in the stored balance -- instead of an
+/// implicit default nothing records.
+#[sqlx::test(migrations = "../../migrations")]
+async fn rollover_writes_one_explicit_opening_grant_per_active_account(pool: PgPool) {
+    let account_id = cuid2();
+    insert_account(&pool, &account_id, "active").await;
+    let (service, repo) = service(&pool);
+    let period = Period::parse(PERIOD).expect("valid period");
+
+    let report = service.run(&period).await.expect("rollover must succeed");
+    assert_eq!(report.granted, 1);
+    assert_eq!(report.failed, 0);
+
+    let grants = repo
+        .list_grants(&account_id, Some(&period), None, 10)
+        .await
+        .expect("listing grants must succeed");
+    assert_eq!(grants.len(), 1);
+    let opening = &grants[0];
+    assert_eq!(opening.source, GrantSource::Base);
+    assert_eq!(opening.amount_micros, 15_000_000);
+    assert_eq!(opening.reason.as_deref(), Some(ROLLOVER_GRANT_REASON));
+    assert_eq!(
+        opening.idempotency_key.as_deref(),
+        Some(base_grant_idempotency_key(&account_id, &period).as_str())
+    );
+    assert!(opening.policy_revision.is_some());
+
+    let balance = repo
+        .get_balance(&account_id, &period)
+        .await
+        .expect("reading the balance must succeed")
+        .expect("the opening grant must create a balance row");
+    assert_eq!(balance.base_total_micros, 15_000_000);
+    assert_eq!(balance.effective_budget_micros, 15_000_000);
+}
+
+/// Re-running (the background job's every tick, a second replica, the CLI on top) must never
+/// write a second opening grant for the same (account, period).
+#[sqlx::test(migrations = "../../migrations")]
+async fn rollover_is_idempotent_per_account_and_period(pool: PgPool) {
+    let account_id = cuid2();
+    insert_account(&pool, &account_id, "active").await;
+    let (service, repo) = service(&pool);
+    let period = Period::parse(PERIOD).expect("valid period");
+
+    service.run(&period).await.expect("first run must succeed");
+    let (second, third) = tokio::join!(service.run(&period), service.run(&period));
+    assert_eq!(second.expect("concurrent run must succeed").failed, 0);
+    assert_eq!(third.expect("concurrent run must succeed").failed, 0);
+
+    let grants = repo
+        .list_grants(&account_id, Some(&period), None, 10)
+        .await
+        .expect("listing grants must succeed");
+    assert_eq!(grants.len(), 1, "exactly one opening grant: {grants:?}");
+
+    let balance = repo
+        .get_balance(&account_id, &period)
+        .await
+        .expect("reading the balance must succeed")
+        .expect("balance row exists");
+    assert_eq!(balance.effective_budget_micros, 15_000_000);
+
+    // A new period is a new key, so it opens again.
+    let next = Period::parse("2026-12").expect("valid period");
+    let report = service
+        .run(&next)
+        .await
+        .expect("next-period run must succeed");
+    assert_eq!(report.granted, 1);
+}
+
+/// An account on a plan the ladder maps to a higher starting rung opens on that rung, so the
+/// opening grant does not demote it below where `current_tier` would otherwise start it.
+#[sqlx::test(migrations = "../../migrations")]
+async fn rollover_opens_a_mapped_plan_on_its_starting_rung(pool: PgPool) {
+    let account_id = cuid2();
+    insert_account(&pool, &account_id, "active").await;
+    insert_project(&pool, &account_id, "free").await;
+    insert_project(&pool, &account_id, "enterprise").await;
+    let (service, repo) = service(&pool);
+    let period = Period::parse(PERIOD).expect("valid period");
+
+    service.run(&period).await.expect("rollover must succeed");
+
+    let grants = repo
+        .list_grants(&account_id, Some(&period), None, 10)
+        .await
+        .expect("listing grants must succeed");
+    assert_eq!(grants.len(), 1);
+    assert_eq!(grants[0].amount_micros, 1_000_000_000);
+
+    let ladder = engine_with_enterprise_plan().tier_ladder();
+    let tier = repo
+        .current_tier(&account_id, &period, &ladder, Some("free"))
+        .await
+        .expect("current tier must resolve");
+    assert_eq!(tier.label(), "b-1000");
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn rollover_skips_suspended_accounts(pool: PgPool) {
+    let suspended = cuid2();
+    insert_account(&pool, &suspended, "suspended").await;
+    let (service, repo) = service(&pool);
+    let period = Period::parse(PERIOD).expect("valid period");
+
+    let report = service.run(&period).await.expect("rollover must succeed");
+    assert_eq!(report.granted, 0);
+    assert!(
+        repo.list_grants(&suspended, Some(&period), None, 10)
+            .await
+            .expect("listing grants must succeed")
+            .is_empty()
+    );
+
+    // Reactivated mid-period: the next run opens it.
+    sqlx::query("UPDATE accounts SET status = 'active' WHERE id = $1")
+        .bind(&suspended)
+        .execute(&pool)
+        .await
+        .expect("reactivating must succeed");
+    let report = service.run(&period).await.expect("rollover must succeed");
+    assert_eq!(report.granted, 1);
+}
@@ -1,3 +1,3 @@
 pub use crate::dto::{
-    ApiKey, ApiKeySecret, ApiKeyStatus, CreateApiKey, RotateApiKey, UpdateApiKey,
+    ApiKey, ApiKeyScope, ApiKeySecret, ApiKeyStatus, CreateApiKey, RotateApiKey, UpdateApiKey,
 };
@@ -0,0 +1,81 @@
+//! Who is behind a write, for the `audit_events` trail.
+//!
+//! The hand-written mutation procedures do not go through cratestack, so its `@@audit` never sees
+//! them. Their trail is kept by the database instead: an `AFTER` row trigger on every table those
+//! procedures write (migration `20261018000005_audit_events`) appends the before/after snapshot to
+//! `audit_events`, inside the same transaction as the write itself. What the trigger cannot see
+//! is the caller, so the procedure layer supplies it:
+//!
+//! 1. the procedure runs its body inside [`scope`] with an [`AuditContext`] (actor, op-id and the
+//!    request's `traceparent`);
+//! 2. the repository opens its write transaction with [`begin`], which copies that context into
+//!    transaction-local settings (`lightbridge.audit_*`) the trigger reads.
+//!
+//! Writes made outside a scope -- background jobs, the introspection hot path stamping
+//! `last_used_at`, cratestack's own CRUD -- carry no op-id, and the trigger records nothing for
+//! them.
+
+use std::future::Future;
+
+use sqlx::{PgPool, Postgres, Transaction};
+
+tokio::task_local! {
+    static CONTEXT: AuditContext;
+}
+
+/// The caller of one audited procedure call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditContext {
+    /// The authenticated subject (`accounts.id` since ADR-0006).
+    pub actor: String,
+    /// The RPC op-id, e.g. `procedure.addProjectMember`.
+    pub op_id: String,
+    /// The request's W3C `traceparent`, when it carried one.
+    pub request_id: Option<String>,
+}
+
+impl AuditContext {
+    pub fn new(
+        actor: impl Into<String>,
+        op_id: impl Into<String>,
+        request_id: Option<String>,
+    ) -> Self {
+        Self {
+            actor: actor.into(),
+            op_id: op_id.into(),
+            request_id,
+        }
+    }
+}
+
+/// Runs `f` with `context` attributed to every transaction it opens through [`begin`].
+pub async fn scope<F: Future>(context: AuditContext, f: F) -> F::Output {
+    CONTEXT.scope(context, f).await
+}
+
+/// The context of the enclosing [`scope`], if any.
+pub fn current() -> Option<AuditContext> {
+    CONTEXT.try_with(Clone::clone).ok()
+}
+
+/// Begins a transaction on `pool`, stamped with the enclosing [`scope`]'s context so the audit
+/// trigger attributes its writes. Outside a scope this is a plain `BEGIN`.
+pub async fn begin(pool: &PgPool) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    if let Some(context) = current() {
+        sqlx::query(
+            r#"
+            SELECT
+              set_config('lightbridge.audit_actor', $1, true),
+              set_config('lightbridge.audit_op_id', $2, true),
+              set_config('lightbridge.audit_request_id', $3, true)
+            "#,
+        )
+        .bind(&context.actor)
+        .bind(&context.op_id)
+        .bind(context.request_id.as_deref().unwrap_or_default())
+        .execute(&mut *tx)
+        .await?;
+    }
+    Ok(tx)
+}
@@ -123,12 +123,18 @@ pub enum Permission {
     /// that otherwise requires a manual SQL `UPDATE` against prod.
     #[serde(rename = "session:revoke")]
     SessionRevoke,
+
+    /// Read the `audit_events` trail of every hand-written mutation procedure, across every
+    /// tenant (`listAuditEvents`). Admin-only in the default mapping: the snapshots expose other
+    /// accounts' settings and rosters.
+    #[serde(rename = "audit:read")]
+    AuditRead,
 }
 
 impl Permission {
     /// Every permission, in declaration order. The single source of truth for wildcard expansion
     /// and documentation.
-    pub const ALL: [Permission; 31] = [
+    pub const ALL: [Permission; 32] = [
         Permission::AccountCreate,
         Permission::AccountRead,
         Permission::AccountUpdate,
@@ -160,6 +166,7 @@ impl Permission {
         Permission::BudgetPolicyActivate,
         Permission::SessionRevokeOwn,
         Permission::SessionRevoke,
+        Permission::AuditRead,
     ];
 
     /// Canonical `resource:action` string.
@@ -196,6 +203,7 @@ impl Permission {
             Permission::BudgetPolicyActivate => "budget:policy-activate",
             Permission::SessionRevokeOwn => "session:revoke-own",
             Permission::SessionRevoke => "session:revoke",
+            Permission::AuditRead => "audit:read",
         }
     }
 
@@ -0,0 +1,78 @@
+//! Per-key IP allowlists (`api_keys.allowed_cidrs`). Entries are stored in canonical network form
+//! -- a bare address is widened to its host prefix (`/32` or `/128`) and host bits are masked off
+//! -- so the stored list reads back exactly as it is matched.
+
+use std::net::{IpAddr, SocketAddr};
+
+use ipnet::IpNet;
+
+/// Parses one allowlist entry, either CIDR notation (`10.0.0.0/8`, `2001:db8::/32`) or a bare
+/// address, into its canonical form. `None` for anything else.
+pub fn normalize_cidr(entry: &str) -> Option<String> {
+    let entry = entry.trim();
+    let net = match entry.parse::<IpNet>() {
+        Ok(net) => net,
+        Err(_) => IpNet::from(entry.parse::<IpAddr>().ok()?),
+    };
+    Some(net.trunc().to_string())
+}
+
+/// Parses a caller address as transports report it: a bare address, or `address:port` (Envoy's
+/// and most proxies' socket form, including `[v6]:port`).
+pub fn parse_client_ip(ip: &str) -> Option<IpAddr> {
+    let ip = ip.trim();
+    ip.parse::<IpAddr>()
+        .ok()
+        .or_else(|| ip.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
+
+/// Whether `ip` falls inside any of `cidrs`. Fails closed: an absent or unparseable address is
+/// never allowed, and neither is an entry that does not parse (the write path rejects those, so
+/// one can only come from hand-edited data).
+pub fn ip_allowed(cidrs: &[String], ip: Option<&str>) -> bool {
+    let Some(ip) = ip.and_then(parse_client_ip) else {
+        return false;
+    };
+    cidrs
+        .iter()
+        .filter_map(|cidr| cidr.parse::<IpNet>().ok())
+        .any(|net| net.contains(&ip))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_canonicalises_networks_and_widens_bare_addresses() {
+        assert_eq!(normalize_cidr("10.1.2.3/8").as_deref(), Some("10.0.0.0/8"));
+        assert_eq!(
+            normalize_cidr(" 192.0.2.7 ").as_deref(),
+            Some("192.0.2.7/32")
+        );
+        assert_eq!(
+            normalize_cidr("2001:db8::1").as_deref(),
+            Some("2001:db8::1/128")
+        );
+        assert_eq!(normalize_cidr("10.0.0.0/33"), None);
+        assert_eq!(normalize_cidr("example.com"), None);
+        assert_eq!(normalize_cidr(""), None);
+    }
+
+    #[test]
+    fn ip_allowed_matches_bare_and_socket_addresses() {
+        let cidrs = vec!["10.0.0.0/8".to_string(), "2001:db8::/32".to_string()];
+        assert!(ip_allowed(&cidrs, Some("10.20.30.40")));
+        assert!(ip_allowed(&cidrs, Some("10.20.30.40:51234")));
+        assert!(ip_allowed(&cidrs, Some("[2001:db8::5]:443")));
+        assert!(!ip_allowed(&cidrs, Some("192.0.2.1")));
+    }
+
+    #[test]
+    fn ip_allowed_fails_closed_on_missing_or_garbage_input() {
+        let cidrs = vec!["0.0.0.0/0".to_string()];
+        assert!(!ip_allowed(&cidrs, None));
+        assert!(!ip_allowed(&cidrs, Some("not-an-ip")));
+        assert!(!ip_allowed(&["garbage".to_string()], Some("10.0.0.1")));
+    }
+}
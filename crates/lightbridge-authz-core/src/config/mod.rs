@@ -81,6 +81,39 @@ pub struct Config {
     /// reason.
     #[serde(default, deserialize_with = "deserialize_null_default")]
     pub api_key_expiry: ApiKeyExpiry,
+    /// In-process cache of active API-key introspection results, read by `authz-opa` and
+    /// `authz-extauthz` (see `crates/lightbridge-authz-rest/src/introspection_cache.rs`). Optional,
+    /// like `redis` above: absent means every introspection reads Postgres exactly as before, so
+    /// a config file that omits it keeps today's "every revocation is honoured on the very next
+    /// call" behaviour. When present, `max_staleness_seconds` is the hard upper bound on how long a
+    /// revoked key, suspended project/account, or changed model policy can still be served from
+    /// cache -- Redis-delivered invalidations (when `redis` is also configured) normally evict far
+    /// sooner, but the bound holds even when they are lost.
+    #[serde(default)]
+    pub introspection_cache: Option<IntrospectionCache>,
+    /// Budget standing on introspection responses, read by `authz-opa` and `authz-extauthz` (see
+    /// `crates/lightbridge-authz-rest/src/introspection_budget.rs`). Optional: absent means
+    /// introspection carries no budget fields and makes no budget-ledger or usage-service reads,
+    /// so the budget ledger stays advisory exactly as before. Present, every active response
+    /// reports the owning account's current tier, effective balance, spend for the current
+    /// period, and `budget_exhausted`; with `enforce: true` an exhausted account's credentials
+    /// additionally introspect `active: false` -- the switch that makes the refill flow gate
+    /// traffic rather than merely describe it.
+    #[serde(default)]
+    pub introspection_budget: Option<IntrospectionBudget>,
+    /// Server-side pepper for `api_keys.key_hash` (see `crypto::ApiKeyHasher`). Optional: absent
+    /// keeps the bare SHA-256 hash every key was stored under before peppering existed. Present,
+    /// new keys are hashed with HMAC-SHA256 under `active`, and a key still stored under a
+    /// `previous` pepper or the legacy SHA-256 is re-hashed under `active` the next time it
+    /// validates. Every service that hashes API keys (`authz-api`, `authz-opa`,
+    /// `authz-extauthz`, `lightbridge-mcp`) must load the same block.
+    #[serde(default)]
+    pub api_key_hashing: Option<ApiKeyHashing>,
+    /// Expiry warnings and dormant-key detection, run by `authz-api` (see
+    /// `crates/lightbridge-authz-rest/src/api_key_lifecycle.rs`). Optional: absent means no scan
+    /// runs and nothing is written to `api_key_lifecycle_events`.
+    #[serde(default)]
+    pub api_key_lifecycle: Option<ApiKeyLifecycle>,
 }
 
 /// The operator-configured catalogue of billing plans. Populated from env — either a single
@@ -510,6 +543,149 @@ impl ApiKeyExpiry {
     }
 }
 
+/// `api_key_lifecycle`: which API keys the `authz-api` lifecycle job reports. Every finding is
+/// written to the `api_key_lifecycle_events` outbox, then POSTed to `webhook` when one is set.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ApiKeyLifecycle {
+    /// Days before `expires_at` at which a key is reported `expiring`. A key is reported once per
+    /// window, for the narrowest window it is inside when a scan first sees it.
+    #[serde(default = "default_expiry_warning_days")]
+    pub expiry_warning_days: Vec<u32>,
+    /// Days without use (`last_used_at`, or `created_at` for a key never used) after which a key
+    /// is reported `dormant`. A project's own policy (`setProjectKeyLifecyclePolicy`) overrides
+    /// it; absent, only projects with a policy of their own are checked.
+    #[serde(default)]
+    pub dormant_after_days: Option<u32>,
+    #[serde(default = "default_api_key_lifecycle_scan_interval_seconds")]
+    pub scan_interval_seconds: u64,
+    #[serde(default)]
+    pub webhook: Option<ApiKeyLifecycleWebhook>,
+}
+
+/// Where lifecycle events are delivered. Each event is one JSON `POST`; any non-2xx response is
+/// retried on the next scan, so receivers must de-duplicate on the event `id`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ApiKeyLifecycleWebhook {
+    pub url: String,
+    /// Name of the environment variable holding an HMAC-SHA256 key. When set, every request
+    /// carries `X-Lightbridge-Signature: sha256=<hex>` over its body.
+    #[serde(default)]
+    pub secret_env: Option<String>,
+}
+
+impl Default for ApiKeyLifecycle {
+    fn default() -> Self {
+        Self {
+            expiry_warning_days: default_expiry_warning_days(),
+            dormant_after_days: None,
+            scan_interval_seconds: default_api_key_lifecycle_scan_interval_seconds(),
+            webhook: None,
+        }
+    }
+}
+
+fn default_expiry_warning_days() -> Vec<u32> {
+    vec![14, 3, 1]
+}
+
+fn default_api_key_lifecycle_scan_interval_seconds() -> u64 {
+    3_600
+}
+
+impl ApiKeyLifecycle {
+    /// Fails startup on a window or interval that would make the job meaningless.
+    pub fn validate(&self) -> Result<()> {
+        if self.expiry_warning_days.contains(&0) {
+            return Err(Error::Server(
+                "api_key_lifecycle.expiry_warning_days entries must be greater than 0".to_string(),
+            ));
+        }
+        if self.dormant_after_days == Some(0) {
+            return Err(Error::Server(
+                "api_key_lifecycle.dormant_after_days must be greater than 0".to_string(),
+            ));
+        }
+        if self.scan_interval_seconds == 0 {
+            return Err(Error::Server(
+                "api_key_lifecycle.scan_interval_seconds must be greater than 0".to_string(),
+            ));
+        }
+        if let Some(webhook) = &self.webhook
+            && !(webhook.url.starts_with("https://") || webhook.url.starts_with("http://"))
+        {
+            return Err(Error::Server(format!(
+                "api_key_lifecycle.webhook.url must be an http(s) URL, got '{}'",
+                webhook.url
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Settings for the introspection result cache (`Config.introspection_cache`). Both fields have
+/// defaults so an operator can opt in with an empty block (`introspection_cache: {}`).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IntrospectionCache {
+    /// The longest any cached result may be served, in seconds -- and therefore the longest a
+    /// revocation or suspension can go unnoticed if its invalidation event never arrives. An
+    /// entry's lifetime is further capped at the key's own `expires_at`, so caching can never
+    /// extend a key past its expiry.
+    #[serde(default = "default_introspection_cache_max_staleness_seconds")]
+    pub max_staleness_seconds: u64,
+    /// Upper bound on cached keys per process. When full, expired entries are swept first and
+    /// then the entry closest to its deadline is evicted.
+    #[serde(default = "default_introspection_cache_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for IntrospectionCache {
+    fn default() -> Self {
+        Self {
+            max_staleness_seconds: default_introspection_cache_max_staleness_seconds(),
+            max_entries: default_introspection_cache_max_entries(),
+        }
+    }
+}
+
+fn default_introspection_cache_max_staleness_seconds() -> u64 {
+    5
+}
+
+fn default_introspection_cache_max_entries() -> usize {
+    10_000
+}
+
+impl IntrospectionCache {
+    /// Fails startup loudly on a zero bound, mirroring `ApiKeyExpiry::validate`: a zero
+    /// `max_staleness_seconds` would make every entry dead on arrival (a cache that silently
+    /// never hits), and a zero `max_entries` the same -- omit the block instead to disable caching.
+    pub fn validate(&self) -> Result<()> {
+        if self.max_staleness_seconds == 0 {
+            return Err(Error::Server(
+                "introspection_cache.max_staleness_seconds must be greater than 0".to_string(),
+            ));
+        }
+        if self.max_entries == 0 {
+            return Err(Error::Server(
+                "introspection_cache.max_entries must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Settings for budget standing on introspection (`Config.introspection_budget`). An empty block
+/// (`introspection_budget: {}`) reports the budget fields without enforcing them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct IntrospectionBudget {
+    /// When true, introspection resolves `active: false` for a credential whose owning account's
+    /// known spend for the current period exceeds its effective balance. Spend that cannot be
+    /// read never counts as exhausted -- a usage-service outage must not become a traffic outage
+    /// -- so enforcement requires `usage_service` to be configured and fails startup without it.
+    #[serde(default)]
+    pub enforce: bool,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Otel {
     pub enabled: bool,
@@ -540,6 +716,12 @@ pub struct Server {
     /// `app/lightbridge-authz/src/main.rs`).
     #[serde(default)]
     pub budget: Option<BudgetServer>,
+    /// `authz-extauthz`'s server block: address/port/TLS for the native Envoy `ext_authz` gRPC
+    /// listener (`envoy.service.auth.v3.Authorization/Check`) that lets an Envoy gateway call
+    /// authz directly instead of going through Authorino. Optional for the same reason `idp`/
+    /// `budget` are: only `Commands::ExtAuthz` requires it to be `Some`.
+    #[serde(default)]
+    pub extauthz: Option<ExtAuthzServer>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -596,6 +778,17 @@ pub struct BudgetServer {
     pub tls: Tls,
 }
 
+/// `authz-extauthz`'s server block. Shaped like [`IdpServer`] (address/port/TLS, no
+/// `basic_auth`): Envoy's gRPC `ext_authz` client has no Basic-auth knob, so unlike [`OpaServer`]
+/// the caller is authenticated at the transport instead -- set `tls.client_ca_bundle_path` to
+/// require Envoy's client certificate (mTLS) on any listener reachable from outside the mesh.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExtAuthzServer {
+    pub address: String,
+    pub port: u16,
+    pub tls: Tls,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Tls {
     pub cert_path: String,
@@ -766,6 +959,19 @@ pub struct Oauth2 {
     /// service). Independent of `issuance`, which proxies exchange to an upstream IdP.
     #[serde(default)]
     pub token_exchange: Option<Oauth2TokenExchange>,
+    /// Optional RFC 8628 device-authorization grant (ADR-0012): headless clients obtain the same
+    /// tenant-scoped tokens `token_exchange` mints, with the user authenticating at Keycloak
+    /// through this service's own `/device` verification page. Requires `token_exchange` to be
+    /// enabled -- the device grant reuses its TTLs, scope ceiling, and minting path.
+    #[serde(default)]
+    pub device_authorization: Option<Oauth2DeviceAuthorization>,
+    /// Optional brokered authorization-code + PKCE flow: browser-based clients registered with
+    /// `redirect_uris` sign users in through `/authorize`, which round-trips them through
+    /// Keycloak and hands back a single-use code redeemable at the token endpoint for the same
+    /// project-scoped tokens `token_exchange` mints. Requires `token_exchange` to be enabled, for
+    /// the same reason `device_authorization` does.
+    #[serde(default)]
+    pub authorization_code: Option<Oauth2AuthorizationCode>,
     /// Role-based access control: which JWT claim carries the caller's roles and how those roles
     /// map to permissions. When omitted, the built-in default mapping is used
     /// (`crate::authz::default_role_permissions`).
@@ -784,11 +990,9 @@ pub struct Oauth2 {
 }
 
 /// A registered OAuth2/OIDC client (ADR-0011, Decision 5). Mirrors
-/// `authkestra_op::client::ClientRegistration`'s 9 fields minus the two this service never needs:
-/// `client_secret_hash` (always `None` -- Decision 6 bans secret-based client auth outright) and
-/// `redirect_uris` (always empty -- these are machine clients presenting a `subject_token` they
-/// already hold, never a browser running the authorization-code flow this service structurally
-/// never serves; see ADR-0011 Context).
+/// `authkestra_op::client::ClientRegistration`'s fields minus `client_secret_hash` (always `None`
+/// -- Decision 6 bans secret-based client auth outright). `require_pkce` is derived rather than
+/// configured: every client allowed the `authorization_code` grant requires S256 PKCE.
 #[derive(Debug, Clone, Deserialize)]
 pub struct OauthClient {
     pub client_id: String,
@@ -802,13 +1006,20 @@ pub struct OauthClient {
     #[serde(default)]
     pub scopes: Vec<String>,
     /// Grant types this client may use, as raw RFC 8693/OAuth2 grant-type strings (e.g.
-    /// `"urn:ietf:params:oauth:grant-type:token-exchange"`, `"refresh_token"`). Only those two are
-    /// ever meaningful here -- this service never runs `authorization_code` or the device flow (no
-    /// user store, ADR-0011 Context) -- but the list is not restricted at the config-parsing layer
-    /// so an operator typo surfaces as "client not authorized for this grant type" at request time
-    /// rather than a silent config-load failure.
+    /// `"urn:ietf:params:oauth:grant-type:token-exchange"`, `"refresh_token"`,
+    /// `"urn:ietf:params:oauth:grant-type:device_code"`, `"authorization_code"`). The device grant
+    /// is only meaningful once `Oauth2::device_authorization` is enabled and `authorization_code`
+    /// once `Oauth2::authorization_code` is -- but the list is not restricted at the
+    /// config-parsing layer so an operator typo surfaces as "client not authorized for this grant
+    /// type" at request time rather than a silent config-load failure.
     #[serde(default)]
     pub grant_types: Vec<String>,
+    /// Absolute redirect URIs `/authorize` may send this client's authorization codes to,
+    /// compared by exact string match (no wildcards, no prefix matching, no fragments). Required
+    /// -- and checked at startup -- for a client allowed the `authorization_code` grant; ignored
+    /// for every other client.
+    #[serde(default)]
+    pub redirect_uris: Vec<String>,
     /// Downstream audiences this client may request via the token-exchange `audience` parameter.
     /// Per ADR-0011 Decision 5 the minted access token's `aud`/`azp` default to this client's own
     /// `client_id` when no `audience` is requested; requesting anything else requires it to be
@@ -852,10 +1063,96 @@ pub struct JwtSigning {
     /// Default token lifetime in seconds and the hard cap on any frontend-requested expiry.
     #[serde(default = "default_signing_ttl_seconds")]
     pub ttl_seconds: i64,
-    /// Auto-rotate the active signing key once it is older than this many days (checked at
-    /// startup). The rotated-out key is marked stale and kept in the JWKS for verification.
+    /// Rotate the active signing key once it has signed for this many days (checked every few
+    /// minutes by `lightbridge_authz_rest::signing_rotation`). The rotated-out key is marked stale
+    /// and kept in the JWKS until every token it signed has expired.
     #[serde(default = "default_max_key_age_days")]
     pub max_key_age_days: i64,
+    /// How long a rotated-in key is published in the JWKS as `pending` before it starts signing,
+    /// so relying parties that cache the JWKS have it before the first token it signs arrives.
+    #[serde(default = "default_rotation_lead_seconds")]
+    pub rotation_lead_seconds: i64,
+    /// Algorithm of newly generated signing keys. Every stored key keeps the algorithm it was
+    /// generated with, so a change takes effect at the next rotation.
+    #[serde(default)]
+    pub algorithm: SigningAlgorithm,
+    /// Envelope encryption of stored private keys. Absent: keys are stored as plaintext PEM.
+    #[serde(default)]
+    pub key_encryption: Option<SigningKeyEncryption>,
+}
+
+/// JWS algorithm of a signing key, named as in its JWK `alg`. Switching from RS256 to EdDSA
+/// (much smaller tokens) leaves a mixed-algorithm JWKS until the last RS256 key is pruned.
+/// ES256 is not offered: authkestra-engine's `TokenManager`, which mints every token, has no
+/// ECDSA constructor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum SigningAlgorithm {
+    #[default]
+    #[serde(rename = "RS256")]
+    Rs256,
+    /// Ed25519.
+    #[serde(rename = "EdDSA")]
+    EdDsa,
+}
+
+impl SigningAlgorithm {
+    pub const ALL: [SigningAlgorithm; 2] = [SigningAlgorithm::Rs256, SigningAlgorithm::EdDsa];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SigningAlgorithm::Rs256 => "RS256",
+            SigningAlgorithm::EdDsa => "EdDSA",
+        }
+    }
+
+    /// Parses a stored `signing_keys.algorithm` value.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|alg| alg.as_str() == name)
+    }
+}
+
+/// Key-encryption keys (KEKs) sealing `signing_keys.private_key_pem` at rest. New keys are sealed
+/// under `active`; `previous` KEKs only ever unwrap keys not yet re-encrypted, so a KEK rotation is
+/// "make the new KEK `active`, move the old one here, run `signing-keys reencrypt`, drop it".
+#[derive(Debug, Clone, Deserialize)]
+pub struct SigningKeyEncryption {
+    pub active: KeyEncryptionKey,
+    #[serde(default)]
+    pub previous: Vec<KeyEncryptionKey>,
+}
+
+/// One KEK: 32 random bytes, standard-base64 encoded, read at startup from exactly one of `file`
+/// or `env`. Never stored in the database -- only `id` is, next to every key it sealed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeyEncryptionKey {
+    pub id: String,
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Name of the environment variable holding the key, not the key itself.
+    #[serde(default)]
+    pub env: Option<String>,
+}
+
+/// `api_key_hashing`: the pepper new API-key hashes are computed under, plus the ones older hashes
+/// may still be stored under. Drop a pepper from `previous` only once no key is stored under it:
+/// any such key stops validating.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKeyHashing {
+    pub active: ApiKeyPepper,
+    #[serde(default)]
+    pub previous: Vec<ApiKeyPepper>,
+}
+
+/// One pepper: at least 32 bytes of secret, read at startup from exactly one of `file` or `env`.
+/// Never stored in the database -- only `id` is, inside every hash computed under it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiKeyPepper {
+    pub id: String,
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Name of the environment variable holding the pepper, not the pepper itself.
+    #[serde(default)]
+    pub env: Option<String>,
 }
 
 fn default_signing_ttl_seconds() -> i64 {
@@ -866,6 +1163,10 @@ fn default_max_key_age_days() -> i64 {
     30
 }
 
+fn default_rotation_lead_seconds() -> i64 {
+    86_400
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Oauth2Issuance {
     #[serde(default)]
@@ -915,6 +1216,69 @@ pub struct Oauth2TokenExchange {
     pub refresh_absolute_ttl_seconds: i64,
 }
 
+/// The RFC 8628 device-authorization grant on `authz-idp` (ADR-0012). `upstream` is the one
+/// Keycloak client this service signs users in through on the verification page's browser leg --
+/// it is this service acting as a relying party, distinct from every entry in `oauth2.clients`
+/// (those are the CLIs that poll *this* service).
+#[derive(Debug, Clone, Deserialize)]
+pub struct Oauth2DeviceAuthorization {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Lifetime of an issued `device_code`/`user_code` pair, in seconds. RFC 8628 leaves it to the
+    /// server; long enough for a user to switch to a browser and sign in, short enough that an
+    /// unattended code does not sit redeemable for long.
+    #[serde(default = "default_device_code_ttl_seconds")]
+    pub code_ttl_seconds: i64,
+    pub upstream: Oauth2Upstream,
+}
+
+/// The brokered authorization-code flow on `authz-idp` (`/authorize`). `upstream` plays the same
+/// role as `Oauth2DeviceAuthorization::upstream`: the Keycloak client this service signs the user
+/// in through before issuing its own code to the registered client.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Oauth2AuthorizationCode {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Lifetime of an issued authorization code, in seconds. Codes are single-use and redeemed
+    /// by the client immediately after the redirect, so this stays short (RFC 6749 §4.1.2
+    /// recommends at most ten minutes).
+    #[serde(default = "default_authorization_code_ttl_seconds")]
+    pub code_ttl_seconds: i64,
+    /// How long a pending `/authorize` request may wait for the user to finish signing in at
+    /// Keycloak, in seconds.
+    #[serde(default = "default_authorization_request_ttl_seconds")]
+    pub request_ttl_seconds: i64,
+    pub upstream: Oauth2Upstream,
+}
+
+/// A Keycloak client one of this service's browser legs redirects to (authorization-code +
+/// PKCE). Its redirect URI is always `{oauth2.signing.issuer}/device/callback` for the device
+/// grant and `{oauth2.signing.issuer}/authorize/callback` for `/authorize`, and must be
+/// registered on the Keycloak side; the access token it returns is validated through the same
+/// JWKS-backed path as a token-exchange `subject_token`, so its `aud` must satisfy
+/// `oauth2.audience` too.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Oauth2Upstream {
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub client_id: String,
+    /// Absent for a public Keycloak client (PKCE alone); set for a confidential one.
+    #[serde(default)]
+    pub client_secret: Option<String>,
+}
+
+fn default_device_code_ttl_seconds() -> i64 {
+    600
+}
+
+fn default_authorization_code_ttl_seconds() -> i64 {
+    60
+}
+
+fn default_authorization_request_ttl_seconds() -> i64 {
+    600
+}
+
 fn default_exchange_access_ttl_seconds() -> i64 {
     900
 }
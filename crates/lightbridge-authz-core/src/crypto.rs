@@ -1,9 +1,252 @@
+use hmac::{Hmac, KeyInit, Mac};
 use sha2::{Digest, Sha256};
 
-/// Hashes an API key secret using SHA-256 and returns a hex-encoded digest.
+use crate::config::{ApiKeyHashing, ApiKeyPepper};
+use crate::error::{Error, Result};
+
+/// Hashes a secret using SHA-256 and returns a hex-encoded digest.
+///
+/// This is the legacy, unpeppered API-key hash, still the right tool for secrets that live only as
+/// long as a flow (authorization codes, device codes, refresh tokens). Stored API-key hashes go
+/// through [`ApiKeyHasher`], which falls back to this when no pepper is configured.
 pub fn hash_api_key(secret: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(secret.as_bytes());
     let digest = hasher.finalize();
     hex::encode(digest)
 }
+
+/// Version tag of a peppered hash: `v1:<pepper id>:<hex HMAC-SHA256>`. A legacy hash is bare
+/// hex, so the two formats can never collide.
+const PEPPERED_HASH_VERSION: &str = "v1";
+
+/// Shortest pepper accepted, in bytes: the HMAC-SHA256 output size.
+const MIN_PEPPER_BYTES: usize = 32;
+
+/// Computes `api_keys.key_hash` under `Config.api_key_hashing`.
+///
+/// [`Self::hash`] is the current scheme, used for every new key and as the introspection-cache key.
+/// [`Self::candidates`] lists every hash a stored key may still carry -- current pepper, then each
+/// previous pepper, then legacy SHA-256 -- so lookups keep matching keys hashed before the last
+/// pepper rotation until they are upgraded on use.
+#[derive(Clone, Default)]
+pub struct ApiKeyHasher {
+    active: Option<Pepper>,
+    previous: Vec<Pepper>,
+}
+
+#[derive(Clone)]
+struct Pepper {
+    id: String,
+    key: Vec<u8>,
+}
+
+impl std::fmt::Debug for ApiKeyHasher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKeyHasher")
+            .field("active", &self.active.as_ref().map(|p| &p.id))
+            .field(
+                "previous",
+                &self.previous.iter().map(|p| &p.id).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl ApiKeyHasher {
+    /// Loads every configured pepper. `None` yields the legacy, unpeppered hasher. Fails on a
+    /// pepper that cannot be read, is shorter than 32 bytes, or whose id is empty, contains `:`,
+    /// or repeats another's.
+    pub fn from_config(config: Option<&ApiKeyHashing>) -> Result<Self> {
+        let Some(config) = config else {
+            return Ok(Self::default());
+        };
+        let mut seen = std::collections::HashSet::new();
+        let mut load = |pepper: &ApiKeyPepper| -> Result<Pepper> {
+            if pepper.id.is_empty() || pepper.id.contains(':') {
+                return Err(Error::Server(format!(
+                    "api_key_hashing pepper id {:?} must be non-empty and must not contain ':'",
+                    pepper.id
+                )));
+            }
+            if !seen.insert(pepper.id.clone()) {
+                return Err(Error::Server(format!(
+                    "api_key_hashing pepper id {:?} is configured twice",
+                    pepper.id
+                )));
+            }
+            Ok(Pepper {
+                id: pepper.id.clone(),
+                key: read_pepper(pepper)?,
+            })
+        };
+        let active = load(&config.active)?;
+        let previous = config.previous.iter().map(load).collect::<Result<_>>()?;
+        Ok(Self {
+            active: Some(active),
+            previous,
+        })
+    }
+
+    /// The hash a key issued now is stored under.
+    pub fn hash(&self, secret: &str) -> String {
+        match &self.active {
+            Some(pepper) => pepper.hash(secret),
+            None => hash_api_key(secret),
+        }
+    }
+
+    /// Every hash `secret` may be stored under, most current first. The first entry is always
+    /// [`Self::hash`].
+    pub fn candidates(&self, secret: &str) -> Vec<String> {
+        self.active
+            .iter()
+            .chain(&self.previous)
+            .map(|pepper| pepper.hash(secret))
+            .chain(std::iter::once(hash_api_key(secret)))
+            .collect()
+    }
+
+    /// Whether `key_hash` was computed under the current scheme, i.e. needs no upgrade.
+    pub fn is_current(&self, key_hash: &str) -> bool {
+        match &self.active {
+            Some(pepper) => key_hash
+                .strip_prefix(PEPPERED_HASH_VERSION)
+                .and_then(|rest| rest.strip_prefix(':'))
+                .and_then(|rest| rest.strip_prefix(pepper.id.as_str()))
+                .is_some_and(|rest| rest.starts_with(':')),
+            None => !key_hash.contains(':'),
+        }
+    }
+}
+
+impl Pepper {
+    fn hash(&self, secret: &str) -> String {
+        format!(
+            "{PEPPERED_HASH_VERSION}:{}:{}",
+            self.id,
+            hmac_sha256_hex(&self.key, secret.as_bytes())
+        )
+    }
+}
+
+/// Hex HMAC-SHA256 of `message` under `key`. Signs outbound webhook bodies (the API-key lifecycle
+/// notifier) so a receiver can check them with the shared secret.
+pub fn hmac_sha256_hex(key: &[u8], message: &[u8]) -> String {
+    // `new_from_slice` only fails for a fixed-size key; HMAC takes any length.
+    let mut mac =
+        <Hmac<Sha256> as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Prefix of the checksummed API-key secret format. Stable, so secret scanners can match
+/// `lbk_v2_[0-9A-Za-z]{49}` and confirm a hit offline with [`verify_api_key_secret`].
+pub const API_KEY_SECRET_PREFIX: &str = "lbk_v2_";
+
+/// Prefix of the original, unchecksummed format (`lbk_secret_<base64url>`), still accepted.
+pub const LEGACY_API_KEY_SECRET_PREFIX: &str = "lbk_secret_";
+
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+/// 256 bits of entropy in base62 need 43 digits.
+const SECRET_BODY_LEN: usize = 43;
+/// A CRC32 in base62 needs 6 digits.
+const SECRET_CHECKSUM_LEN: usize = 6;
+
+/// What [`verify_api_key_secret`] makes of a presented string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeySecretFormat {
+    /// `lbk_v2_` with a matching checksum.
+    Checksummed,
+    /// `lbk_v2_` whose length, alphabet or checksum is wrong: a typo or a forgery, never a
+    /// secret this service issued.
+    Malformed,
+    /// Anything else: a legacy `lbk_secret_` key, or a JWT under `oauth2.type: self`/`external`.
+    /// Only a lookup can tell.
+    Unchecked,
+}
+
+/// Formats 32 random bytes as `lbk_v2_<43 base62 digits><6 base62 digits of CRC32>`. The CRC
+/// covers the prefix and body, so a scanner can reject a near-miss without calling us.
+pub fn encode_api_key_secret(random: &[u8; 32]) -> String {
+    let body = base62_fixed(random, SECRET_BODY_LEN);
+    let checksum = base62_fixed(&secret_checksum(&body).to_be_bytes(), SECRET_CHECKSUM_LEN);
+    format!("{API_KEY_SECRET_PREFIX}{body}{checksum}")
+}
+
+/// Classifies `secret` by format, checking the embedded checksum of an `lbk_v2_` secret.
+pub fn verify_api_key_secret(secret: &str) -> ApiKeySecretFormat {
+    let Some(rest) = secret.strip_prefix(API_KEY_SECRET_PREFIX) else {
+        return ApiKeySecretFormat::Unchecked;
+    };
+    if rest.len() != SECRET_BODY_LEN + SECRET_CHECKSUM_LEN
+        || !rest.bytes().all(|b| b.is_ascii_alphanumeric())
+    {
+        return ApiKeySecretFormat::Malformed;
+    }
+    let (body, checksum) = rest.split_at(SECRET_BODY_LEN);
+    let expected = base62_fixed(&secret_checksum(body).to_be_bytes(), SECRET_CHECKSUM_LEN);
+    if checksum == expected {
+        ApiKeySecretFormat::Checksummed
+    } else {
+        ApiKeySecretFormat::Malformed
+    }
+}
+
+fn secret_checksum(body: &str) -> u32 {
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(API_KEY_SECRET_PREFIX.as_bytes());
+    crc.update(body.as_bytes());
+    crc.finalize()
+}
+
+/// Big-endian `bytes` as exactly `len` base62 digits, zero-padded. `len` must be large enough
+/// for the value, which both call sites guarantee by construction.
+fn base62_fixed(bytes: &[u8], len: usize) -> String {
+    let mut number = bytes.to_vec();
+    let mut digits = Vec::with_capacity(len);
+    while digits.len() < len {
+        // Long division of `number` by 62; the remainder is the next least-significant digit.
+        let mut remainder = 0u32;
+        for byte in &mut number {
+            let acc = (remainder << 8) | u32::from(*byte);
+            *byte = (acc / 62) as u8;
+            remainder = acc % 62;
+        }
+        digits.push(BASE62[remainder as usize]);
+    }
+    digits.reverse();
+    String::from_utf8(digits).expect("base62 digits are ASCII")
+}
+
+/// Loads one pepper from `file` or `env` (exactly one), surrounding whitespace trimmed.
+fn read_pepper(pepper: &ApiKeyPepper) -> Result<Vec<u8>> {
+    let raw = match (pepper.file.as_deref(), pepper.env.as_deref()) {
+        (Some(path), None) => std::fs::read_to_string(path).map_err(|e| {
+            Error::Server(format!(
+                "failed to read API-key pepper {:?} from {path}: {e}",
+                pepper.id
+            ))
+        })?,
+        (None, Some(var)) => std::env::var(var).map_err(|_| {
+            Error::Server(format!(
+                "API-key pepper {:?}: environment variable {var} is not set",
+                pepper.id
+            ))
+        })?,
+        _ => {
+            return Err(Error::Server(format!(
+                "API-key pepper {:?} needs exactly one of `file` or `env`",
+                pepper.id
+            )));
+        }
+    };
+    let key = raw.trim().as_bytes().to_vec();
+    if key.len() < MIN_PEPPER_BYTES {
+        return Err(Error::Server(format!(
+            "API-key pepper {:?} must be at least {MIN_PEPPER_BYTES} bytes",
+            pepper.id
+        )));
+    }
+    Ok(key)
+}
@@ -1,5 +1,6 @@
 use crate::config::Database;
 use crate::error::Result;
+use opentelemetry::KeyValue;
 use sqlx::{PgPool, postgres::PgPoolOptions};
 use std::time::Duration;
 
@@ -35,6 +36,7 @@ impl DbPool {
                     if attempt > 1 {
                         tracing::info!(attempt, "connected to database after retrying");
                     }
+                    observe_pool(&pool);
                     return Ok(Self { pool });
                 }
                 Err(err) if attempt >= max_attempts => {
@@ -61,6 +63,32 @@ impl DbPool {
     }
 }
 
+/// Pool saturation on `/metrics`: connections in use and idle against the configured maximum.
+/// The callbacks own a clone of the pool handle, so they report for the life of the process.
+fn observe_pool(pool: &PgPool) {
+    let meter = crate::metrics::meter();
+    let connections = pool.clone();
+    meter
+        .u64_observable_gauge("authz_db_pool_connections")
+        .with_description("Open database connections, by state (`in_use` or `idle`)")
+        .with_callback(move |observer| {
+            let idle = connections.num_idle() as u64;
+            let open = u64::from(connections.size());
+            observer.observe(
+                open.saturating_sub(idle),
+                &[KeyValue::new("state", "in_use")],
+            );
+            observer.observe(idle, &[KeyValue::new("state", "idle")]);
+        })
+        .build();
+    let max = u64::from(pool.options().get_max_connections());
+    meter
+        .u64_observable_gauge("authz_db_pool_max_connections")
+        .with_description("The most connections the database pool will open")
+        .with_callback(move |observer| observer.observe(max, &[]))
+        .build();
+}
+
 #[crate::async_trait]
 impl DbPoolTrait for DbPool {
     fn pool(&self) -> &PgPool {
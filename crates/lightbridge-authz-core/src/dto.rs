@@ -292,6 +292,54 @@ pub struct ProjectMember {
     pub created_at: DateTime<Utc>,
 }
 
+/// A project's dormant-key policy (`project_key_lifecycle_policies`), read and written through
+/// `getProjectKeyLifecyclePolicy`/`setProjectKeyLifecyclePolicy`. A project that never set one
+/// reports `dormant_after_days: None` (the deployment's `api_key_lifecycle.dormant_after_days`
+/// applies) and `auto_revoke_dormant: false`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ProjectKeyLifecyclePolicy {
+    pub project_id: String,
+    #[serde(default)]
+    pub dormant_after_days: Option<i32>,
+    #[serde(default)]
+    pub auto_revoke_dormant: bool,
+}
+
+/// One row of the `audit_events` trail: a single row written by a hand-written mutation procedure,
+/// as the audit trigger saw it. `target_type` is the table, `target_id` the row's key (composite
+/// keys joined with `:`); `before` is `None` for an insert and `after` for a delete. Secret
+/// material (`key_hash`, `token_hash`) is never in either snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub actor_subject: String,
+    pub op_id: String,
+    #[serde(default)]
+    pub request_id: Option<String>,
+    pub target_type: String,
+    pub target_id: String,
+    pub action: String,
+    #[serde(default)]
+    pub before: Option<serde_json::Value>,
+    #[serde(default)]
+    pub after: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// `listAuditEvents`' filters. Every field narrows the result; all `None` lists everything,
+/// newest first. `from`/`to` bound `created_at` inclusively; `before` is the exclusive page cursor,
+/// the previous page's last `created_at`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditEventFilter {
+    pub actor_subject: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub before: Option<DateTime<Utc>>,
+    pub limit: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateProject {
     pub name: Option<String>,
@@ -327,6 +375,54 @@ impl From<String> for ApiKeyStatus {
     }
 }
 
+/// A named capability an API key may be restricted to. Stored on `api_keys.scopes` as the wire
+/// strings below; a key with no `scopes` (the default, and every pre-existing key) may do all of
+/// them. The gateway matches these against the kind of request being made -- this service only
+/// validates, stores and reports them.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    Inference,
+    Embeddings,
+    Batch,
+}
+
+const SCOPE_INFERENCE: &str = "inference";
+const SCOPE_EMBEDDINGS: &str = "embeddings";
+const SCOPE_BATCH: &str = "batch";
+
+impl Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let r = match self {
+            ApiKeyScope::Inference => SCOPE_INFERENCE,
+            ApiKeyScope::Embeddings => SCOPE_EMBEDDINGS,
+            ApiKeyScope::Batch => SCOPE_BATCH,
+        };
+        write!(f, "{}", r)
+    }
+}
+
+impl ApiKeyScope {
+    /// Every scope, in wire order. Used to name the valid values in a rejection message.
+    pub const ALL: [ApiKeyScope; 3] = [
+        ApiKeyScope::Inference,
+        ApiKeyScope::Embeddings,
+        ApiKeyScope::Batch,
+    ];
+
+    /// Strict parse of a wire value, same write-side contract as `ModelPolicy::parse_strict`: an
+    /// unrecognized scope is refused, never coerced -- a typo must not mint a key that silently
+    /// lacks (or, worse, has) a capability the caller did not ask for.
+    pub fn parse_strict(s: &str) -> Option<Self> {
+        match s {
+            SCOPE_INFERENCE => Some(ApiKeyScope::Inference),
+            SCOPE_EMBEDDINGS => Some(ApiKeyScope::Embeddings),
+            SCOPE_BATCH => Some(ApiKeyScope::Batch),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ApiKey {
     pub id: String,
@@ -344,7 +440,68 @@ pub struct ApiKey {
     /// Billing plan this key is minted on. Chosen at creation from the operator-configured
     /// (env-driven) plan set; preserved across rotation.
     pub billing_plan: String,
+    /// Key-level model subset. `None` inherits the project's policy unchanged; `Some` can only
+    /// narrow it -- see [`ApiKey::effective_model_access`].
+    #[serde(default)]
+    pub allowed_models: Option<Vec<String>>,
+    /// Named scopes (`ApiKeyScope` wire values) the key is restricted to. `None` means all.
+    #[serde(default)]
+    pub scopes: Option<Vec<String>>,
+    /// Networks (canonical CIDR, see `crate::cidr`) the key may be presented from. `None` means
+    /// anywhere.
+    #[serde(default)]
+    pub allowed_cidrs: Option<Vec<String>>,
     pub updated_at: DateTime<Utc>,
+    /// Hard cut-off of a grace-period rotation. The key stays `active` and keeps validating until
+    /// then; `None` when the key is not being rotated out.
+    #[serde(default)]
+    pub rotating_until: Option<DateTime<Utc>>,
+    /// The key that replaced this one, once it has been rotated.
+    #[serde(default)]
+    pub successor_id: Option<String>,
+    /// The key this one replaced, if it was minted by a rotation.
+    #[serde(default)]
+    pub predecessor_id: Option<String>,
+}
+
+impl ApiKey {
+    /// The instant the key stops validating: `expires_at`, or the rotation cut-off if sooner.
+    pub fn valid_until(&self) -> Option<DateTime<Utc>> {
+        match (self.expires_at, self.rotating_until) {
+            (Some(expires_at), Some(cut_off)) => Some(expires_at.min(cut_off)),
+            (expires_at, cut_off) => expires_at.or(cut_off),
+        }
+    }
+
+    /// The model policy and list a request made with this key is actually bound by: the
+    /// intersection of the key's own `allowed_models` with its project's `model_policy` /
+    /// `allowed_models` (ADR-0018). Computed at read time rather than frozen at mint time, so a
+    /// project-level tightening after the key was created still wins.
+    ///
+    /// - no key-level list: the project's pair, unchanged;
+    /// - project `deny_all`: still `deny_all` -- a key can never widen its project;
+    /// - project `allow_all`: `allowlist` over the key's list (the project's own list is inert
+    ///   under `allow_all`, so it does not participate);
+    /// - project `allowlist`: `allowlist` over the entries present in both lists, in the key's
+    ///   order. This can come out empty, which under `allowlist` genuinely means "nothing".
+    pub fn effective_model_access(&self, project: &Project) -> (ModelPolicy, Option<Vec<String>>) {
+        let Some(key_models) = &self.allowed_models else {
+            return (project.model_policy, project.allowed_models.clone());
+        };
+        match project.model_policy {
+            ModelPolicy::DenyAll => (ModelPolicy::DenyAll, project.allowed_models.clone()),
+            ModelPolicy::AllowAll => (ModelPolicy::Allowlist, Some(key_models.clone())),
+            ModelPolicy::Allowlist => {
+                let project_models = project.allowed_models.as_deref().unwrap_or_default();
+                let narrowed = key_models
+                    .iter()
+                    .filter(|model| project_models.contains(model))
+                    .cloned()
+                    .collect();
+                (ModelPolicy::Allowlist, Some(narrowed))
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -354,6 +511,13 @@ pub struct CreateApiKey {
     /// Billing plan for the key. Required, and must be one of the operator-configured
     /// (env-driven) billing plans, otherwise creation is rejected with `400 Bad Request`.
     pub billing_plan: String,
+    /// Optional key-level model subset. Every entry must be in the configured model catalogue and
+    /// reachable under the project's current `model_policy`.
+    #[serde(default)]
+    pub allowed_models: Option<Vec<String>>,
+    /// Optional scope restriction; each entry must be an `ApiKeyScope` wire value.
+    #[serde(default)]
+    pub scopes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -370,6 +534,13 @@ pub struct RotateApiKey {
     pub name: Option<String>,
     pub expires_at: Option<DateTime<Utc>>,
     pub grace_period_seconds: Option<i64>,
+    /// Replaces the successor's model subset; `None` carries the predecessor's forward. Validated
+    /// exactly like `CreateApiKey::allowed_models`.
+    #[serde(default)]
+    pub allowed_models: Option<Vec<String>>,
+    /// Replaces the successor's scopes; `None` carries the predecessor's forward.
+    #[serde(default)]
+    pub scopes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -421,6 +592,148 @@ mod tests {
     }
 }
 
+#[cfg(test)]
+mod api_key_restriction_tests {
+    use super::*;
+
+    fn project(policy: ModelPolicy, models: Option<&[&str]>) -> Project {
+        Project {
+            id: "proj".to_string(),
+            account_id: "acct".to_string(),
+            name: "p".to_string(),
+            allowed_models: models.map(|m| m.iter().map(|s| s.to_string()).collect()),
+            default_limits: None,
+            billing_plan: "free".to_string(),
+            billing_identity: "acct".to_string(),
+            project_quota: None,
+            status: ResourceStatus::Active,
+            is_default: false,
+            model_policy: policy,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn key(models: Option<&[&str]>) -> ApiKey {
+        ApiKey {
+            id: "key".to_string(),
+            project_id: "proj".to_string(),
+            name: "k".to_string(),
+            key_prefix: "lbk".to_string(),
+            key_hash: "hash".to_string(),
+            created_at: Utc::now(),
+            expires_at: None,
+            status: ApiKeyStatus::Active,
+            last_used_at: None,
+            last_ip: None,
+            revoked_at: None,
+            billing_plan: "free".to_string(),
+            allowed_models: models.map(|m| m.iter().map(|s| s.to_string()).collect()),
+            scopes: None,
+            allowed_cidrs: None,
+            updated_at: Utc::now(),
+            rotating_until: None,
+            successor_id: None,
+            predecessor_id: None,
+        }
+    }
+
+    fn names(models: &[&str]) -> Option<Vec<String>> {
+        Some(models.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn a_key_without_its_own_list_inherits_the_project_unchanged() {
+        let p = project(ModelPolicy::Allowlist, Some(&["a", "b"]));
+        assert_eq!(
+            key(None).effective_model_access(&p),
+            (ModelPolicy::Allowlist, names(&["a", "b"]))
+        );
+    }
+
+    #[test]
+    fn a_key_list_turns_allow_all_into_an_allowlist() {
+        let p = project(ModelPolicy::AllowAll, Some(&["inert"]));
+        assert_eq!(
+            key(Some(&["cheap"])).effective_model_access(&p),
+            (ModelPolicy::Allowlist, names(&["cheap"]))
+        );
+    }
+
+    #[test]
+    fn a_key_list_is_intersected_with_the_project_allowlist() {
+        let p = project(ModelPolicy::Allowlist, Some(&["a", "b"]));
+        assert_eq!(
+            key(Some(&["b", "c"])).effective_model_access(&p),
+            (ModelPolicy::Allowlist, names(&["b"]))
+        );
+        assert_eq!(
+            key(Some(&["c"])).effective_model_access(&p),
+            (ModelPolicy::Allowlist, names(&[])),
+            "an empty intersection must stay an allowlist (nothing), never widen"
+        );
+    }
+
+    #[test]
+    fn a_key_list_can_never_lift_deny_all() {
+        let p = project(ModelPolicy::DenyAll, None);
+        assert_eq!(
+            key(Some(&["a"])).effective_model_access(&p),
+            (ModelPolicy::DenyAll, None)
+        );
+    }
+
+    fn validation(effective_status: &str, cidrs: Option<&[&str]>) -> ApiKeyValidation {
+        ApiKeyValidation {
+            api_key_id: "key".to_string(),
+            key_hash: "hash".to_string(),
+            project_id: "proj".to_string(),
+            account_id: "acct".to_string(),
+            owner_account_id: "acct".to_string(),
+            owner_role: None,
+            owner_quota_tier: None,
+            api_key_status: "active".to_string(),
+            project_status: "active".to_string(),
+            account_status: "active".to_string(),
+            expires_at: None,
+            effective_status: effective_status.to_string(),
+            allowed_cidrs: cidrs.map(|c| c.iter().map(|s| s.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn ip_allowlist_only_narrows_an_active_restricted_row() {
+        let mut unrestricted = validation("active", None);
+        unrestricted.apply_ip_allowlist(None);
+        assert!(unrestricted.is_active());
+
+        let mut inside = validation("active", Some(&["10.0.0.0/8"]));
+        inside.apply_ip_allowlist(Some("10.1.1.1"));
+        assert!(inside.is_active());
+
+        let mut outside = validation("active", Some(&["10.0.0.0/8"]));
+        outside.apply_ip_allowlist(Some("192.0.2.1"));
+        assert_eq!(outside.effective_status, IP_NOT_ALLOWED);
+
+        let mut unknown = validation("active", Some(&["10.0.0.0/8"]));
+        unknown.apply_ip_allowlist(None);
+        assert_eq!(unknown.effective_status, IP_NOT_ALLOWED);
+
+        let mut revoked = validation("key_revoked", Some(&["10.0.0.0/8"]));
+        revoked.apply_ip_allowlist(Some("192.0.2.1"));
+        assert_eq!(revoked.effective_status, "key_revoked");
+    }
+
+    #[test]
+    fn scope_parse_strict_round_trips_display_and_refuses_unknown_values() {
+        for scope in ApiKeyScope::ALL {
+            assert_eq!(ApiKeyScope::parse_strict(&scope.to_string()), Some(scope));
+        }
+        assert_eq!(ApiKeyScope::parse_strict("Inference"), None);
+        assert_eq!(ApiKeyScope::parse_strict("admin"), None);
+    }
+}
+
 /// Business context resolved for a subject + project.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ResolvedContext {
@@ -431,7 +744,7 @@ pub struct ResolvedContext {
 /// One row of the `api_key_validation` view: an API key's effective validity with the full
 /// account -> project -> key status cascade already resolved by the database. `effective_status`
 /// is `"active"` when the key is usable, otherwise it is the deny reason (`key_revoked`,
-/// `key_expired`, `project_suspended`, `account_suspended`).
+/// `key_rotated`, `key_expired`, `project_suspended`, `account_suspended`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiKeyValidation {
     pub api_key_id: String,
@@ -446,11 +759,32 @@ pub struct ApiKeyValidation {
     pub account_status: String,
     pub expires_at: Option<DateTime<Utc>>,
     pub effective_status: String,
+    /// The key's IP allowlist, `None` when it has none. Carried on the view so the network check
+    /// costs no extra read; applied by [`ApiKeyValidation::apply_ip_allowlist`].
+    #[serde(default)]
+    pub allowed_cidrs: Option<Vec<String>>,
 }
 
+/// `effective_status` for an otherwise-active key presented from outside its IP allowlist. Unlike
+/// the view's own deny reasons it depends on the caller, so it is resolved in application code.
+pub const IP_NOT_ALLOWED: &str = "ip_not_allowed";
+
 impl ApiKeyValidation {
     /// Whether the key is usable (the cascade resolved to `active`).
     pub fn is_active(&self) -> bool {
         self.effective_status == ACTIVE
     }
+
+    /// Narrows an `active` row to [`IP_NOT_ALLOWED`] when the key carries an allowlist that `ip`
+    /// is not inside -- including when `ip` is unknown: a network-restricted key is only usable
+    /// through a transport that reports the caller's address. A row already denied for another
+    /// reason keeps that reason, so `key_revoked`/`key_expired` are never masked.
+    pub fn apply_ip_allowlist(&mut self, ip: Option<&str>) {
+        let Some(cidrs) = &self.allowed_cidrs else {
+            return;
+        };
+        if self.is_active() && !crate::cidr::ip_allowed(cidrs, ip) {
+            self.effective_status = IP_NOT_ALLOWED.to_string();
+        }
+    }
 }
@@ -1,25 +1,28 @@
 pub mod api_key;
+pub mod audit;
 pub mod authz;
+pub mod cidr;
 pub mod config;
 pub mod crypto;
 pub mod db;
 pub mod dto;
 pub mod error;
+pub mod metrics;
 pub mod migrate;
 #[cfg(feature = "axum")]
 pub mod server;
 pub mod tracing;
 
 pub use crate::api_key::{
-    ApiKey, ApiKeySecret, ApiKeyStatus, CreateApiKey, RotateApiKey, UpdateApiKey,
+    ApiKey, ApiKeyScope, ApiKeySecret, ApiKeyStatus, CreateApiKey, RotateApiKey, UpdateApiKey,
 };
 pub use crate::authz::{Permission, PermissionSet, Rbac};
 pub use crate::config::{Config, load_from_path};
-pub use crate::crypto::hash_api_key;
+pub use crate::crypto::{ApiKeyHasher, hash_api_key};
 pub use crate::dto::{
-    Account, ApiKeyValidation, CreateAccount, CreateProject, DefaultLimits, ModelPolicy, Project,
-    ProjectMember, ResolveContextRequest, ResolvedContext, ResourceStatus, UpdateAccount,
-    UpdateProject,
+    Account, ApiKeyValidation, AuditEvent, AuditEventFilter, CreateAccount, CreateProject,
+    DefaultLimits, ModelPolicy, Project, ProjectKeyLifecyclePolicy, ProjectMember,
+    ResolveContextRequest, ResolvedContext, ResourceStatus, UpdateAccount, UpdateProject,
 };
 pub use crate::error::{Error, Result};
 
@@ -0,0 +1,274 @@
+//! The process-wide metrics registry, exported two ways: pushed over OTLP next to the spans when
+//! `otel.enabled` is set, and always pulled as Prometheus text from `GET /metrics` on every
+//! service's probe router ([`metrics_handler`]).
+//!
+//! Instruments are created from [`meter`] exactly as before this module existed; they are
+//! no-ops until [`init_metrics`] installs the provider, which `tracing::init_tracing_from` does
+//! for every binary. Create instruments after that call -- one built against the no-op provider
+//! stays a no-op.
+//!
+//! The exposition is the text format (0.0.4) Prometheus scrapes by default, rendered straight
+//! from the SDK's cumulative aggregation by [`render`]:
+//!
+//! - a monotonic sum (`Counter`) becomes a `counter` named `<name>_total`;
+//! - a non-monotonic sum (`UpDownCounter`) or a gauge becomes a `gauge`;
+//! - a histogram becomes cumulative `<name>_bucket{le=..}` series plus `_sum` and `_count`.
+//!
+//! Names and label keys are mapped onto `[a-zA-Z0-9_:]`; label values are escaped, never dropped.
+
+use std::fmt::{Display, Write as _};
+use std::sync::{Arc, OnceLock, Weak};
+use std::time::Duration;
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Meter;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::metrics::data::{AggregatedMetrics, Metric, MetricData, ResourceMetrics};
+use opentelemetry_sdk::metrics::reader::MetricReader;
+use opentelemetry_sdk::metrics::{
+    InstrumentKind, ManualReader, Pipeline, SdkMeterProvider, Temporality,
+};
+
+/// The one meter every crate in the workspace records against.
+pub const METER_NAME: &str = "lightbridge-authz";
+
+/// `Content-Type` of [`metrics_handler`]'s body.
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+struct Registry {
+    provider: SdkMeterProvider,
+    reader: Arc<ManualReader>,
+}
+
+/// Hands the provider a reader while keeping a handle to collect from it on scrape:
+/// `MeterProviderBuilder::with_reader` takes its reader by value.
+#[derive(Debug, Clone)]
+struct ScrapeReader(Arc<ManualReader>);
+
+impl MetricReader for ScrapeReader {
+    fn register_pipeline(&self, pipeline: Weak<Pipeline>) {
+        self.0.register_pipeline(pipeline)
+    }
+
+    fn collect(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
+        self.0.collect(rm)
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    fn temporality(&self, kind: InstrumentKind) -> Temporality {
+        self.0.temporality(kind)
+    }
+}
+
+/// The workspace meter. Call it when building an instrument, not earlier: see the module docs.
+pub fn meter() -> Meter {
+    opentelemetry::global::meter(METER_NAME)
+}
+
+/// Installs the global meter provider: the `/metrics` reader always, plus a periodic OTLP push
+/// when `exporter` is given. Only the first call in a process installs anything, so a test may
+/// call it as often as it likes.
+pub fn init_metrics(resource: Resource, exporter: Option<opentelemetry_otlp::MetricExporter>) {
+    REGISTRY.get_or_init(|| {
+        let reader = Arc::new(ManualReader::builder().build());
+        let mut builder = SdkMeterProvider::builder()
+            .with_reader(ScrapeReader(reader.clone()))
+            .with_resource(resource);
+        if let Some(exporter) = exporter {
+            builder = builder.with_periodic_exporter(exporter);
+        }
+        let provider = builder.build();
+        opentelemetry::global::set_meter_provider(provider.clone());
+        Registry { provider, reader }
+    });
+}
+
+/// Flushes the OTLP push, if any, and stops the provider.
+pub fn shutdown_metrics() {
+    if let Some(registry) = REGISTRY.get() {
+        let _ = registry.provider.force_flush();
+        let _ = registry.provider.shutdown();
+    }
+}
+
+/// Every instrument's current value in the Prometheus text format. Empty before [`init_metrics`]
+/// and after [`shutdown_metrics`].
+pub fn render() -> String {
+    let Some(registry) = REGISTRY.get() else {
+        return String::new();
+    };
+    let mut metrics = ResourceMetrics::default();
+    if let Err(err) = registry.reader.collect(&mut metrics) {
+        tracing::warn!(error = %err, "metrics collection failed");
+        return String::new();
+    }
+    encode(&metrics)
+}
+
+/// `GET /metrics`, mounted unauthenticated next to `/healthz` on every server.
+#[cfg(feature = "axum")]
+pub async fn metrics_handler() -> impl axum::response::IntoResponse {
+    (
+        [(axum::http::header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        render(),
+    )
+}
+
+fn encode(metrics: &ResourceMetrics) -> String {
+    let mut out = String::new();
+    for scope in metrics.scope_metrics() {
+        for metric in scope.metrics() {
+            match metric.data() {
+                AggregatedMetrics::F64(data) => encode_metric(&mut out, metric, data),
+                AggregatedMetrics::U64(data) => encode_metric(&mut out, metric, data),
+                AggregatedMetrics::I64(data) => encode_metric(&mut out, metric, data),
+            }
+        }
+    }
+    out
+}
+
+fn encode_metric<T: Copy + Display>(out: &mut String, metric: &Metric, data: &MetricData<T>) {
+    let base = sanitize_name(metric.name());
+    match data {
+        MetricData::Sum(sum) if sum.is_monotonic() => {
+            let name = if base.ends_with("_total") {
+                base
+            } else {
+                format!("{base}_total")
+            };
+            write_header(out, &name, metric.description(), "counter");
+            for point in sum.data_points() {
+                write_sample(out, &name, point.attributes(), None, point.value());
+            }
+        }
+        MetricData::Sum(sum) => {
+            write_header(out, &base, metric.description(), "gauge");
+            for point in sum.data_points() {
+                write_sample(out, &base, point.attributes(), None, point.value());
+            }
+        }
+        MetricData::Gauge(gauge) => {
+            write_header(out, &base, metric.description(), "gauge");
+            for point in gauge.data_points() {
+                write_sample(out, &base, point.attributes(), None, point.value());
+            }
+        }
+        MetricData::Histogram(histogram) => {
+            write_header(out, &base, metric.description(), "histogram");
+            let bucket = format!("{base}_bucket");
+            for point in histogram.data_points() {
+                // OTLP buckets count their own range only; Prometheus's `le` buckets accumulate.
+                let mut cumulative = 0;
+                let mut counts = point.bucket_counts();
+                for bound in point.bounds() {
+                    cumulative += counts.next().unwrap_or(0);
+                    let le = format_float(bound);
+                    write_sample(out, &bucket, point.attributes(), Some(&le), cumulative);
+                }
+                write_sample(
+                    out,
+                    &bucket,
+                    point.attributes(),
+                    Some("+Inf"),
+                    point.count(),
+                );
+                write_sample(
+                    out,
+                    &format!("{base}_sum"),
+                    point.attributes(),
+                    None,
+                    point.sum(),
+                );
+                write_sample(
+                    out,
+                    &format!("{base}_count"),
+                    point.attributes(),
+                    None,
+                    point.count(),
+                );
+            }
+        }
+        // Nothing in the workspace records one; Prometheus has no text form for it anyway.
+        MetricData::ExponentialHistogram(_) => {}
+    }
+}
+
+fn write_header(out: &mut String, name: &str, description: &str, kind: &str) {
+    if !description.is_empty() {
+        let help = description.replace('\\', r"\\").replace('\n', r"\n");
+        let _ = writeln!(out, "# HELP {name} {help}");
+    }
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn write_sample<'a>(
+    out: &mut String,
+    name: &str,
+    attributes: impl Iterator<Item = &'a KeyValue>,
+    le: Option<&str>,
+    value: impl Display,
+) {
+    let mut labels: Vec<String> = attributes
+        .map(|kv| {
+            format!(
+                "{}=\"{}\"",
+                sanitize_name(kv.key.as_str()),
+                escape_label_value(&kv.value.as_str())
+            )
+        })
+        .collect();
+    if let Some(le) = le {
+        labels.push(format!("le=\"{le}\""));
+    }
+    if labels.is_empty() {
+        let _ = writeln!(out, "{name} {value}");
+    } else {
+        let _ = writeln!(out, "{name}{{{}}} {value}", labels.join(","));
+    }
+}
+
+/// Maps an OTel name onto Prometheus's `[a-zA-Z_:][a-zA-Z0-9_:]*`.
+fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .replace('\n', r"\n")
+}
+
+fn format_float(value: f64) -> String {
+    if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
@@ -1,6 +1,7 @@
 use crate::config::Tls;
 use crate::error::{Error, Result};
 use axum::Router;
+use axum_server::Handle;
 use axum_server::tls_rustls::RustlsConfig;
 use rustls::pki_types::pem::PemObject;
 use rustls::pki_types::{CertificateDer, PrivateKeyDer};
@@ -8,10 +9,15 @@ use rustls::server::WebPkiClientVerifier;
 use rustls::{RootCertStore, ServerConfig};
 use std::net::SocketAddr;
 use std::sync::{Arc, Once};
+use std::time::Duration;
 
 const INSECURE_HTTP_ENV: &str = "AUTHZ_INSECURE_HTTP";
 const DEV_CORS_ENV: &str = "AUTHZ_DEV_CORS";
 
+/// How long a server keeps serving in-flight requests after [`shutdown_signal`] fires before it
+/// drops their connections.
+pub const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
 fn ensure_rustls_provider() {
     static INIT: Once = Once::new();
     INIT.call_once(|| {
@@ -40,6 +46,51 @@ pub fn dev_cors_enabled() -> bool {
     env_flag_enabled(std::env::var(DEV_CORS_ENV).ok().as_deref())
 }
 
+/// Resolves on Ctrl-C or, on Unix, `SIGTERM` -- what Kubernetes sends a pod before killing it.
+/// `serve_tls`/`serve_plain_http` stop accepting connections when it fires and return once
+/// in-flight requests drain, so a caller can run its shutdown work (flushing buffered writes)
+/// after the serve call.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::warn!(error = %err, "failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
+}
+
+/// An `axum-server` handle that starts a graceful shutdown when [`shutdown_signal`] fires.
+fn graceful_shutdown_handle(name: &str) -> Handle<SocketAddr> {
+    let handle = Handle::new();
+    let trigger = handle.clone();
+    let name = name.to_string();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        tracing::info!("Shutting down {name} server");
+        trigger.graceful_shutdown(Some(GRACEFUL_SHUTDOWN_TIMEOUT));
+    });
+    handle
+}
+
 /// Serves `app` over plaintext HTTP. `serve_tls` delegates here when
 /// `AUTHZ_INSECURE_HTTP` is set; public so the plaintext path stays testable.
 pub async fn serve_plain_http(name: &str, address: &str, port: u16, app: Router) -> Result<()> {
@@ -49,6 +100,7 @@ pub async fn serve_plain_http(name: &str, address: &str, port: u16, app: Router)
         "Starting {name} server WITHOUT TLS on {addr} ({INSECURE_HTTP_ENV} is set — dev only)"
     );
     axum_server::bind(addr)
+        .handle(graceful_shutdown_handle(name))
         .serve(app.into_make_service())
         .await
         .map_err(|e| Error::Server(format!("Failed to start {name} server: {e}")))?;
@@ -73,6 +125,7 @@ pub async fn serve_tls(name: &str, address: &str, port: u16, tls: &Tls, app: Rou
 
     tracing::info!("Starting {name} server with TLS on {}", addr);
     axum_server::bind_rustls(addr, rustls_config)
+        .handle(graceful_shutdown_handle(name))
         .serve(app.into_make_service())
         .await
         .map_err(|e| Error::Server(format!("Failed to start {name} server: {e}")))?;
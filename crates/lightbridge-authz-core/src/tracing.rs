@@ -47,6 +47,10 @@ pub fn init_tracing_from<T: TracingConfig>(config: &T) {
 
     let registry = Registry::default().with(env_filter).with(fmt_layer);
 
+    let resource = Resource::builder()
+        .with_service_name(config.service_name().to_string())
+        .build();
+
     if config.otel_enabled() {
         let exporter = opentelemetry_otlp::SpanExporter::builder()
             .with_tonic()
@@ -54,22 +58,28 @@ pub fn init_tracing_from<T: TracingConfig>(config: &T) {
             .build()
             .expect("Failed to build OTLP exporter");
 
-        let resource = Resource::builder()
-            .with_service_name(config.service_name().to_string())
-            .build();
-
         let tracer_provider = SdkTracerProvider::builder()
             .with_batch_exporter(exporter)
-            .with_resource(resource)
+            .with_resource(resource.clone())
             .build();
 
         let _ = OTEL_TRACER_PROVIDER.set(tracer_provider.clone());
         opentelemetry::global::set_tracer_provider(tracer_provider.clone());
 
+        // Metrics ride the same OTLP endpoint as spans, on top of the `/metrics` scrape that
+        // `crate::metrics` serves either way.
+        let metric_exporter = opentelemetry_otlp::MetricExporter::builder()
+            .with_tonic()
+            .with_endpoint(config.otlp_endpoint())
+            .build()
+            .expect("Failed to build OTLP metric exporter");
+        crate::metrics::init_metrics(resource, Some(metric_exporter));
+
         let tracer = tracer_provider.tracer(config.service_name().to_string());
         let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
         registry.with(otel_layer).init();
     } else {
+        crate::metrics::init_metrics(resource, None);
         registry.init();
     }
 }
@@ -79,4 +89,5 @@ pub fn shutdown_tracing() {
         let _ = provider.force_flush();
         let _ = provider.shutdown();
     }
+    crate::metrics::shutdown_metrics();
 }
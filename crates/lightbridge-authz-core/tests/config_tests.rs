@@ -1,5 +1,8 @@
 use lightbridge_authz_core::Config;
-use lightbridge_authz_core::config::{JwtSigning, Oauth2TokenExchange, load_from_path};
+use lightbridge_authz_core::config::{
+    ApiKeyLifecycle, IntrospectionCache, JwtSigning, Oauth2TokenExchange, SigningAlgorithm,
+    load_from_path,
+};
 use std::fs;
 
 fn unique_temp_path(name: &str) -> std::path::PathBuf {
@@ -16,6 +19,23 @@ fn jwt_signing_defaults_ttl_and_max_key_age_when_unset() {
 
     assert_eq!(signing.ttl_seconds, 7_776_000);
     assert_eq!(signing.max_key_age_days, 30);
+    assert_eq!(signing.rotation_lead_seconds, 86_400);
+    assert_eq!(signing.algorithm, SigningAlgorithm::Rs256);
+}
+
+#[test]
+fn jwt_signing_parses_jwk_algorithm_names() {
+    let signing: JwtSigning =
+        serde_yaml::from_str("issuer: \"https://issuer.example\"\nalgorithm: EdDSA\n").unwrap();
+    assert_eq!(signing.algorithm, SigningAlgorithm::EdDsa);
+    assert_eq!(
+        SigningAlgorithm::from_name("EdDSA"),
+        Some(SigningAlgorithm::EdDsa)
+    );
+    assert!(
+        serde_yaml::from_str::<JwtSigning>("issuer: \"x\"\nalgorithm: ES256\n").is_err(),
+        "ES256 is not a supported signing algorithm"
+    );
 }
 
 #[test]
@@ -55,6 +75,50 @@ fn oauth2_token_exchange_honors_explicit_values() {
     assert_eq!(exchange.allowed_scopes, vec!["openid"]);
 }
 
+#[test]
+fn introspection_cache_defaults_when_block_is_empty() {
+    let cache: IntrospectionCache = serde_yaml::from_str("{}\n").unwrap();
+
+    assert_eq!(cache, IntrospectionCache::default());
+    assert_eq!(cache.max_staleness_seconds, 5);
+    assert_eq!(cache.max_entries, 10_000);
+    assert!(cache.validate().is_ok());
+}
+
+#[test]
+fn introspection_cache_validate_rejects_zero_bounds() {
+    let zero_staleness: IntrospectionCache =
+        serde_yaml::from_str("max_staleness_seconds: 0\n").unwrap();
+    let zero_entries: IntrospectionCache = serde_yaml::from_str("max_entries: 0\n").unwrap();
+
+    assert!(zero_staleness.validate().is_err());
+    assert!(zero_entries.validate().is_err());
+}
+
+#[test]
+fn api_key_lifecycle_defaults_when_block_is_empty() {
+    let lifecycle: ApiKeyLifecycle = serde_yaml::from_str("{}\n").unwrap();
+
+    assert_eq!(lifecycle, ApiKeyLifecycle::default());
+    assert_eq!(lifecycle.expiry_warning_days, vec![14, 3, 1]);
+    assert_eq!(lifecycle.dormant_after_days, None);
+    assert_eq!(lifecycle.scan_interval_seconds, 3_600);
+    assert!(lifecycle.validate().is_ok());
+}
+
+#[test]
+fn api_key_lifecycle_validate_rejects_zero_windows_and_non_http_webhooks() {
+    for yaml in [
+        "expiry_warning_days: [14, 0]\n",
+        "dormant_after_days: 0\n",
+        "scan_interval_seconds: 0\n",
+        "webhook:\n  url: ftp://hooks.example.test/keys\n",
+    ] {
+        let lifecycle: ApiKeyLifecycle = serde_yaml::from_str(yaml).unwrap();
+        assert!(lifecycle.validate().is_err(), "{yaml}");
+    }
+}
+
 fn minimal_config_yaml() -> String {
     r#"
 server:
@@ -99,6 +163,10 @@ fn load_from_path_reads_and_interpolates_a_yaml_config_file() {
     assert_eq!(config.server.api.port, 3000);
     assert_eq!(config.server.opa.port, 3001);
     assert!(config.oauth2.is_self_signed());
+    assert!(
+        config.introspection_cache.is_none(),
+        "an omitted introspection_cache block must leave caching off"
+    );
 
     let _ = fs::remove_file(&path);
 }
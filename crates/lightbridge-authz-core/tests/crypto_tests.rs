@@ -0,0 +1,152 @@
+use lightbridge_authz_core::config::{ApiKeyHashing, ApiKeyPepper};
+use lightbridge_authz_core::crypto::{
+    API_KEY_SECRET_PREFIX, ApiKeySecretFormat, encode_api_key_secret, hmac_sha256_hex,
+    verify_api_key_secret,
+};
+use lightbridge_authz_core::{ApiKeyHasher, hash_api_key};
+use std::fs;
+
+fn pepper_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!(
+        "lightbridge-authz-core-pepper-test-{}-{}",
+        std::process::id(),
+        name
+    ));
+    fs::write(&path, contents).expect("temp pepper file should be writable");
+    path.to_string_lossy().into_owned()
+}
+
+fn pepper(id: &str, file: String) -> ApiKeyPepper {
+    ApiKeyPepper {
+        id: id.to_string(),
+        file: Some(file),
+        env: None,
+    }
+}
+
+#[test]
+fn unconfigured_hasher_is_the_legacy_sha256() {
+    let hasher = ApiKeyHasher::from_config(None).unwrap();
+
+    assert_eq!(hasher.hash("lbk_secret"), hash_api_key("lbk_secret"));
+    assert_eq!(
+        hasher.candidates("lbk_secret"),
+        vec![hash_api_key("lbk_secret")]
+    );
+    assert!(hasher.is_current(&hash_api_key("lbk_secret")));
+}
+
+#[test]
+fn peppered_hash_is_versioned_and_lists_every_scheme_most_current_first() {
+    let hasher = ApiKeyHasher::from_config(Some(&ApiKeyHashing {
+        active: pepper("p2", pepper_file("p2", &"b".repeat(32))),
+        previous: vec![pepper("p1", pepper_file("p1", &"a".repeat(32)))],
+    }))
+    .unwrap();
+
+    let current = hasher.hash("lbk_secret");
+    assert!(current.starts_with("v1:p2:"), "got {current}");
+    assert_eq!(
+        current,
+        hasher.hash("lbk_secret"),
+        "hashing is deterministic"
+    );
+
+    let candidates = hasher.candidates("lbk_secret");
+    assert_eq!(candidates.len(), 3);
+    assert_eq!(candidates[0], current);
+    assert!(candidates[1].starts_with("v1:p1:"));
+    assert_eq!(candidates[2], hash_api_key("lbk_secret"));
+
+    assert!(hasher.is_current(&candidates[0]));
+    assert!(!hasher.is_current(&candidates[1]));
+    assert!(!hasher.is_current(&candidates[2]));
+}
+
+#[test]
+fn rejects_short_duplicate_or_unloadable_peppers() {
+    let short = ApiKeyHashing {
+        active: pepper("p1", pepper_file("short", "too-short")),
+        previous: Vec::new(),
+    };
+    assert!(ApiKeyHasher::from_config(Some(&short)).is_err());
+
+    let long = pepper_file("long", &"c".repeat(32));
+    let duplicate = ApiKeyHashing {
+        active: pepper("p1", long.clone()),
+        previous: vec![pepper("p1", long.clone())],
+    };
+    assert!(ApiKeyHasher::from_config(Some(&duplicate)).is_err());
+
+    let colon = ApiKeyHashing {
+        active: pepper("p:1", long),
+        previous: Vec::new(),
+    };
+    assert!(ApiKeyHasher::from_config(Some(&colon)).is_err());
+
+    let neither = ApiKeyHashing {
+        active: ApiKeyPepper {
+            id: "p1".to_string(),
+            file: None,
+            env: None,
+        },
+        previous: Vec::new(),
+    };
+    assert!(ApiKeyHasher::from_config(Some(&neither)).is_err());
+}
+
+#[test]
+fn checksummed_secret_round_trips_and_rejects_any_single_character_change() {
+    let secret = encode_api_key_secret(&[0xA5; 32]);
+    assert!(secret.starts_with(API_KEY_SECRET_PREFIX));
+    assert_eq!(secret.len(), API_KEY_SECRET_PREFIX.len() + 49);
+    assert_eq!(
+        verify_api_key_secret(&secret),
+        ApiKeySecretFormat::Checksummed
+    );
+    assert!(
+        encode_api_key_secret(&[0; 32])
+            .starts_with(&format!("{API_KEY_SECRET_PREFIX}{}", "0".repeat(43))),
+        "the body is zero-padded to a fixed width"
+    );
+
+    for at in API_KEY_SECRET_PREFIX.len()..secret.len() {
+        let mut chars: Vec<char> = secret.chars().collect();
+        chars[at] = if chars[at] == 'a' { 'b' } else { 'a' };
+        let typo: String = chars.into_iter().collect();
+        assert_eq!(
+            verify_api_key_secret(&typo),
+            ApiKeySecretFormat::Malformed,
+            "changed position {at}"
+        );
+    }
+}
+
+#[test]
+fn other_credential_shapes_are_left_to_a_lookup() {
+    assert_eq!(
+        verify_api_key_secret("lbk_secret_c29tZS1sZWdhY3ktc2VjcmV0"),
+        ApiKeySecretFormat::Unchecked
+    );
+    assert_eq!(
+        verify_api_key_secret("eyJhbGciOiJSUzI1NiJ9.e30.sig"),
+        ApiKeySecretFormat::Unchecked
+    );
+    assert_eq!(
+        verify_api_key_secret("lbk_v2_tooshort"),
+        ApiKeySecretFormat::Malformed
+    );
+    assert_eq!(
+        verify_api_key_secret(&format!("lbk_v2_{}", "-".repeat(49))),
+        ApiKeySecretFormat::Malformed
+    );
+}
+
+#[test]
+fn hmac_sha256_hex_matches_the_rfc_4231_vector() {
+    // RFC 4231, test case 2.
+    assert_eq!(
+        hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?"),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
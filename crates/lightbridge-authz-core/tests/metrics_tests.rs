@@ -0,0 +1,72 @@
+use lightbridge_authz_core::metrics::{init_metrics, meter, render};
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::Resource;
+
+/// Installs the registry before any instrument is built; an instrument built first would stay a
+/// no-op. Every test here calls it, and only the first call installs anything.
+fn install() {
+    init_metrics(Resource::builder().build(), None);
+}
+
+#[test]
+fn counters_render_as_prometheus_totals_with_escaped_labels() {
+    install();
+    let counter = meter()
+        .u64_counter("test_counter_decisions")
+        .with_description("Decisions made")
+        .build();
+    counter.add(2, &[KeyValue::new("status", "active")]);
+    counter.add(1, &[KeyValue::new("status", "say \"no\"\\")]);
+
+    let text = render();
+    assert!(text.contains("# HELP test_counter_decisions_total Decisions made\n"));
+    assert!(text.contains("# TYPE test_counter_decisions_total counter\n"));
+    assert!(text.contains("test_counter_decisions_total{status=\"active\"} 2\n"));
+    assert!(text.contains(r#"test_counter_decisions_total{status="say \"no\"\\"} 1"#));
+    assert_eq!(
+        text.matches("# TYPE test_counter_decisions_total").count(),
+        1,
+        "one TYPE line per metric"
+    );
+}
+
+#[test]
+fn histograms_render_cumulative_buckets() {
+    install();
+    let histogram = meter()
+        .f64_histogram("test.latency_seconds")
+        .with_boundaries(vec![0.1, 1.0])
+        .build();
+    let op = [KeyValue::new("op_id", "model.Account.list")];
+    for value in [0.05, 0.5, 0.7, 3.0] {
+        histogram.record(value, &op);
+    }
+
+    let text = render();
+    assert!(text.contains("# TYPE test_latency_seconds histogram\n"));
+    for line in [
+        "test_latency_seconds_bucket{op_id=\"model.Account.list\",le=\"0.1\"} 1\n",
+        "test_latency_seconds_bucket{op_id=\"model.Account.list\",le=\"1\"} 3\n",
+        "test_latency_seconds_bucket{op_id=\"model.Account.list\",le=\"+Inf\"} 4\n",
+        "test_latency_seconds_sum{op_id=\"model.Account.list\"} 4.25\n",
+        "test_latency_seconds_count{op_id=\"model.Account.list\"} 4\n",
+    ] {
+        assert!(text.contains(line), "missing {line:?} in:\n{text}");
+    }
+}
+
+#[test]
+fn up_down_counters_and_gauges_render_as_gauges() {
+    install();
+    let in_flight = meter().i64_up_down_counter("test_in_flight").build();
+    in_flight.add(3, &[]);
+    in_flight.add(-1, &[]);
+    meter()
+        .u64_observable_gauge("test_pool_size")
+        .with_callback(|observer| observer.observe(7, &[]))
+        .build();
+
+    let text = render();
+    assert!(text.contains("# TYPE test_in_flight gauge\ntest_in_flight 2\n"));
+    assert!(text.contains("# TYPE test_pool_size gauge\ntest_pool_size 7\n"));
+}
@@ -0,0 +1,300 @@
+//! Expiry warnings and dormant-key detection for API keys (`Config.api_key_lifecycle`).
+//!
+//! `api_key_expiry` makes every key expire, but until now the first sign of it was a gateway 401.
+//! [`spawn_api_key_lifecycle_job`] runs [`ApiKeyLifecycleJob::run_once`] every
+//! `scan_interval_seconds` on `authz-api`. Each run:
+//!
+//! 1. records an `expiring` event for every active key inside one of `expiry_warning_days`;
+//! 2. records a `dormant` event for every active key unused for its project's
+//!    `dormant_after_days`, revoking it instead (`dormant_revoked`) when the project's policy
+//!    (`setProjectKeyLifecyclePolicy`) asks for that, and publishes an introspection-cache
+//!    invalidation for each key revoked;
+//! 3. hands every undelivered event to the [`ApiKeyLifecycleNotifier`], if one is configured.
+//!
+//! Events land in the `api_key_lifecycle_events` outbox first, so nothing is lost when the
+//! notifier is down; a failed delivery is retried on the next run. Delivery is at-least-once --
+//! replicas running the job concurrently may both deliver an event -- so receivers de-duplicate on
+//! the event `id`. With no notifier the outbox is the integration point.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use lightbridge_authz_api_key::entities::api_key_lifecycle_row::ApiKeyLifecycleEventRow;
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_core::async_trait;
+use lightbridge_authz_core::config::{ApiKeyLifecycle, ApiKeyLifecycleWebhook};
+use lightbridge_authz_core::crypto::hmac_sha256_hex;
+use lightbridge_authz_core::error::{Error, Result};
+use serde::Serialize;
+
+use crate::introspection_cache::{Invalidation, InvalidationPublisher};
+
+/// Most events one run hands to the notifier. The rest wait for the next run.
+pub const API_KEY_LIFECYCLE_DELIVERY_BATCH: i64 = 100;
+
+/// How long one webhook delivery may take before it counts as failed.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The notifier payload: one `api_key_lifecycle_events` row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ApiKeyLifecycleEvent {
+    pub id: i64,
+    /// `expiring`, `dormant`, or `dormant_revoked`.
+    pub event_type: String,
+    pub api_key_id: String,
+    pub project_id: String,
+    /// The window the key fell into: days until expiry, or days without use.
+    pub threshold_days: i32,
+    /// `expires_at` for `expiring`; the last use (or creation) for the dormant kinds.
+    pub occurs_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ApiKeyLifecycleEventRow> for ApiKeyLifecycleEvent {
+    fn from(row: ApiKeyLifecycleEventRow) -> Self {
+        Self {
+            id: row.id,
+            event_type: row.event_type,
+            api_key_id: row.api_key_id,
+            project_id: row.project_id,
+            threshold_days: row.threshold_days,
+            occurs_at: row.occurs_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Where lifecycle events go after the outbox. `Ok` marks the event delivered; `Err` leaves it
+/// for the next run.
+#[async_trait]
+pub trait ApiKeyLifecycleNotifier: Send + Sync {
+    async fn notify(&self, event: &ApiKeyLifecycleEvent) -> Result<()>;
+}
+
+/// `api_key_lifecycle.webhook`: `POST`s each event as JSON, signed when a secret is configured.
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+    secret: Option<Vec<u8>>,
+}
+
+impl std::fmt::Debug for WebhookNotifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookNotifier")
+            .field("url", &self.url)
+            .field("signed", &self.secret.is_some())
+            .finish()
+    }
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String, secret: Option<Vec<u8>>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|e| Error::Server(format!("failed to build lifecycle webhook client: {e}")))?;
+        Ok(Self {
+            client,
+            url,
+            secret,
+        })
+    }
+
+    /// Reads the signing secret from `secret_env` at startup. A named variable that is unset or
+    /// empty fails startup rather than sending unsigned requests a receiver would reject.
+    pub fn from_config(config: &ApiKeyLifecycleWebhook) -> Result<Self> {
+        let secret = match &config.secret_env {
+            Some(name) => match std::env::var(name) {
+                Ok(value) if !value.is_empty() => Some(value.into_bytes()),
+                _ => {
+                    return Err(Error::Server(format!(
+                        "api_key_lifecycle.webhook.secret_env names '{name}', which is unset or empty"
+                    )));
+                }
+            },
+            None => None,
+        };
+        Self::new(config.url.clone(), secret)
+    }
+}
+
+#[async_trait]
+impl ApiKeyLifecycleNotifier for WebhookNotifier {
+    async fn notify(&self, event: &ApiKeyLifecycleEvent) -> Result<()> {
+        let body = serde_json::to_vec(event)
+            .map_err(|e| Error::Server(format!("failed to encode lifecycle event: {e}")))?;
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.secret {
+            request = request.header(
+                "X-Lightbridge-Signature",
+                format!("sha256={}", hmac_sha256_hex(secret, &body)),
+            );
+        }
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| Error::Server(format!("lifecycle webhook request failed: {e}")))?;
+        if !response.status().is_success() {
+            return Err(Error::Server(format!(
+                "lifecycle webhook answered {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// What one [`ApiKeyLifecycleJob::run_once`] did, for logging and tests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApiKeyLifecycleRun {
+    /// New `expiring` events.
+    pub expiring: u64,
+    /// Keys revoked as dormant.
+    pub revoked: Vec<String>,
+    pub delivered: usize,
+    pub failed: usize,
+}
+
+/// One deployment's lifecycle scan: its windows, its notifier, and where revocations are
+/// announced.
+pub struct ApiKeyLifecycleJob {
+    repo: Arc<StoreRepo>,
+    expiry_warning_days: Vec<i32>,
+    dormant_after_days: Option<i32>,
+    scan_interval: Duration,
+    notifier: Option<Arc<dyn ApiKeyLifecycleNotifier>>,
+    invalidations: Option<InvalidationPublisher>,
+}
+
+impl ApiKeyLifecycleJob {
+    pub fn new(
+        repo: Arc<StoreRepo>,
+        config: &ApiKeyLifecycle,
+        notifier: Option<Arc<dyn ApiKeyLifecycleNotifier>>,
+        invalidations: Option<InvalidationPublisher>,
+    ) -> Result<Self> {
+        config.validate()?;
+        let days = |value: u32, field: &str| {
+            i32::try_from(value).map_err(|_| {
+                Error::Server(format!(
+                    "api_key_lifecycle.{field} is out of range: {value}"
+                ))
+            })
+        };
+        Ok(Self {
+            repo,
+            expiry_warning_days: config
+                .expiry_warning_days
+                .iter()
+                .map(|value| days(*value, "expiry_warning_days"))
+                .collect::<Result<_>>()?,
+            dormant_after_days: config
+                .dormant_after_days
+                .map(|value| days(value, "dormant_after_days"))
+                .transpose()?,
+            scan_interval: Duration::from_secs(config.scan_interval_seconds),
+            notifier,
+            invalidations,
+        })
+    }
+
+    /// The production constructor: the webhook from `config`, when there is one.
+    pub fn from_config(
+        repo: Arc<StoreRepo>,
+        config: &ApiKeyLifecycle,
+        invalidations: Option<InvalidationPublisher>,
+    ) -> Result<Self> {
+        let notifier = match &config.webhook {
+            Some(webhook) => Some(Arc::new(WebhookNotifier::from_config(webhook)?)
+                as Arc<dyn ApiKeyLifecycleNotifier>),
+            None => None,
+        };
+        Self::new(repo, config, notifier, invalidations)
+    }
+
+    /// One scan as of `now`. A failed delivery is counted, not returned: the event stays in the
+    /// outbox for the next run.
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<ApiKeyLifecycleRun> {
+        let expiring = self
+            .repo
+            .record_expiring_api_keys(now, &self.expiry_warning_days)
+            .await?;
+        let revoked = self
+            .repo
+            .record_dormant_api_keys(now, self.dormant_after_days)
+            .await?;
+        for id in &revoked {
+            tracing::warn!(api_key_id = %id, "dormant api key revoked by project policy");
+            if let Some(publisher) = &self.invalidations {
+                publisher.publish(Invalidation::ApiKey(id.clone())).await;
+            }
+        }
+
+        let mut run = ApiKeyLifecycleRun {
+            expiring,
+            revoked,
+            ..ApiKeyLifecycleRun::default()
+        };
+        let Some(notifier) = &self.notifier else {
+            return Ok(run);
+        };
+        let pending = self
+            .repo
+            .list_undelivered_api_key_lifecycle_events(API_KEY_LIFECYCLE_DELIVERY_BATCH)
+            .await?;
+        for row in pending {
+            let event = ApiKeyLifecycleEvent::from(row);
+            match notifier.notify(&event).await {
+                Ok(()) => {
+                    self.repo
+                        .mark_api_key_lifecycle_event_delivered(event.id, Utc::now())
+                        .await?;
+                    run.delivered += 1;
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        event_id = event.id,
+                        api_key_id = %event.api_key_id,
+                        error = %err,
+                        "api key lifecycle event delivery failed; retrying on the next run"
+                    );
+                    self.repo
+                        .record_api_key_lifecycle_delivery_failure(event.id, &err.to_string())
+                        .await?;
+                    run.failed += 1;
+                }
+            }
+        }
+        Ok(run)
+    }
+}
+
+/// Runs [`ApiKeyLifecycleJob::run_once`] now and every `scan_interval_seconds` for the life of the
+/// process. A failed run is logged and retried on the next tick.
+pub fn spawn_api_key_lifecycle_job(job: ApiKeyLifecycleJob) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(job.scan_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match job.run_once(Utc::now()).await {
+                Ok(run) => tracing::info!(
+                    expiring = run.expiring,
+                    revoked = run.revoked.len(),
+                    delivered = run.delivered,
+                    failed = run.failed,
+                    "api key lifecycle scan finished"
+                ),
+                Err(err) => tracing::warn!(
+                    error = %err,
+                    "api key lifecycle scan failed; retrying on the next tick"
+                ),
+            }
+        }
+    })
+}
@@ -0,0 +1,50 @@
+//! Finalizes grace-period API-key rotations (`AuthzStoreImpl::rotate_api_key` with
+//! `grace_period_seconds`).
+//!
+//! A rotated key stays `active` until its `rotating_until` cut-off, and the `api_key_validation`
+//! view already resolves it `key_rotated` from that instant, so nothing here is needed for the
+//! cut-off to hold. [`spawn_api_key_rotation_sweeper`] makes it durable: every
+//! [`API_KEY_ROTATION_SWEEP_INTERVAL`] it flips keys past their cut-off to `revoked` (reason
+//! `rotated`) and publishes an introspection-cache invalidation for each. `authz-api` runs it; the
+//! update is idempotent, so any number of replicas can.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use lightbridge_authz_api_key::repo::StoreRepo;
+
+use crate::introspection_cache::{Invalidation, InvalidationPublisher};
+
+/// How often [`spawn_api_key_rotation_sweeper`] re-runs. Only bounds how long a key past its
+/// cut-off still reads `active` in a key listing; it has been refused since the cut-off.
+pub const API_KEY_ROTATION_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Runs [`StoreRepo::finalize_rotated_api_keys`] now and every [`API_KEY_ROTATION_SWEEP_INTERVAL`]
+/// for the life of the process. A failed run is logged and retried on the next tick.
+pub fn spawn_api_key_rotation_sweeper(
+    repo: Arc<StoreRepo>,
+    invalidations: Option<InvalidationPublisher>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(API_KEY_ROTATION_SWEEP_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match repo.finalize_rotated_api_keys(Utc::now()).await {
+                Ok(ids) => {
+                    for id in ids {
+                        tracing::info!(api_key_id = %id, "rotated api key revoked after its grace period");
+                        if let Some(publisher) = &invalidations {
+                            publisher.publish(Invalidation::ApiKey(id)).await;
+                        }
+                    }
+                }
+                Err(err) => tracing::warn!(
+                    error = %err,
+                    "api key rotation sweep failed; retrying on the next tick"
+                ),
+            }
+        }
+    })
+}
@@ -0,0 +1,230 @@
+//! Coalesced `last_used_at`/`last_ip` tracking for validated API keys.
+//!
+//! `handlers::opa::validate_api_key_row` used to write both columns with an `UPDATE api_keys` on
+//! every validated request, so a busy key's row was locked and rewritten at gateway request rates.
+//! It now calls [`ApiKeyUsageTracker::record`], which only keeps the latest use and IP per key in
+//! memory, and [`spawn_api_key_usage_flusher`] writes everything pending every
+//! [`API_KEY_USAGE_FLUSH_INTERVAL`] with one `StoreRepo::record_api_key_usage_batch`.
+//!
+//! What this trades away, deliberately:
+//!
+//! - `last_used_at`/`last_ip` lag real use by up to one flush interval, and a process killed
+//!   without a graceful shutdown loses its last interval. A graceful shutdown flushes after the
+//!   server drains (see `start_opa_server`).
+//! - At most `max_pending` keys are held between flushes. A use of a key not already pending when
+//!   that many are is dropped and counted in `authz_api_key_usage_dropped`; a key already pending
+//!   is always updated in place, so the bound never costs a busy key its latest use.
+//! - A failed flush puts its usages back (bounded the same way) to retry on the next tick.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use lightbridge_authz_api_key::entities::api_key_row::ApiKeyUsage;
+use lightbridge_authz_core::Result;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Counter;
+
+use crate::OpaRepoTrait;
+
+/// How often [`spawn_api_key_usage_flusher`] writes pending usage.
+pub const API_KEY_USAGE_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default bound on keys held between flushes. A pending key costs its id, a timestamp and an IP,
+/// so this is a few MiB at most.
+pub const DEFAULT_API_KEY_USAGE_MAX_PENDING: usize = 50_000;
+
+#[derive(Debug, Clone)]
+struct PendingUsage {
+    last_used_at: DateTime<Utc>,
+    last_ip: Option<String>,
+}
+
+/// The per-process aggregator. One per server, shared through `OpaState::api_key_usage`.
+pub struct ApiKeyUsageTracker {
+    max_pending: usize,
+    pending: Mutex<HashMap<String, PendingUsage>>,
+    dropped: Counter<u64>,
+    flushed: Counter<u64>,
+}
+
+impl std::fmt::Debug for ApiKeyUsageTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKeyUsageTracker")
+            .field("max_pending", &self.max_pending)
+            .finish()
+    }
+}
+
+impl Default for ApiKeyUsageTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_API_KEY_USAGE_MAX_PENDING)
+    }
+}
+
+impl ApiKeyUsageTracker {
+    pub fn new(max_pending: usize) -> Self {
+        let meter = lightbridge_authz_core::metrics::meter();
+        Self {
+            max_pending,
+            pending: Mutex::new(HashMap::new()),
+            dropped: meter
+                .u64_counter("authz_api_key_usage_dropped")
+                .with_description(
+                    "API-key usage updates discarded because the pending set was full",
+                )
+                .build(),
+            flushed: meter
+                .u64_counter("authz_api_key_usage_flushed")
+                .with_description("API-key usage updates written to Postgres")
+                .build(),
+        }
+    }
+
+    /// Records a use of `key_id` from `ip` now. Never blocks on I/O.
+    pub fn record(&self, key_id: &str, ip: Option<String>) {
+        self.record_at(key_id, Utc::now(), ip);
+    }
+
+    /// [`Self::record`] at an explicit instant. Only a use newer than the pending one replaces it.
+    pub fn record_at(&self, key_id: &str, used_at: DateTime<Utc>, ip: Option<String>) {
+        let mut pending = self.lock();
+        if self.merge(&mut pending, key_id, used_at, ip) {
+            return;
+        }
+        drop(pending);
+        self.dropped.add(1, &[KeyValue::new("reason", "capacity")]);
+    }
+
+    /// Number of keys with a use not yet written.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes and returns every pending usage, in no particular order.
+    pub fn take(&self) -> Vec<ApiKeyUsage> {
+        std::mem::take(&mut *self.lock())
+            .into_iter()
+            .map(|(key_id, usage)| ApiKeyUsage {
+                key_id,
+                last_used_at: usage.last_used_at,
+                last_ip: usage.last_ip,
+            })
+            .collect()
+    }
+
+    /// Writes every pending usage in one batch, returning how many rows changed. On failure the
+    /// usages are put back for the next flush and the error is returned.
+    pub async fn flush(&self, repo: &dyn OpaRepoTrait) -> Result<u64> {
+        let usages = self.take();
+        if usages.is_empty() {
+            return Ok(0);
+        }
+        match repo.record_api_key_usage_batch(&usages).await {
+            Ok(updated) => {
+                self.flushed.add(usages.len() as u64, &[]);
+                Ok(updated)
+            }
+            Err(err) => {
+                self.requeue(usages);
+                Err(err)
+            }
+        }
+    }
+
+    fn requeue(&self, usages: Vec<ApiKeyUsage>) {
+        let mut pending = self.lock();
+        let mut dropped = 0u64;
+        for usage in usages {
+            if !self.merge(
+                &mut pending,
+                &usage.key_id,
+                usage.last_used_at,
+                usage.last_ip,
+            ) {
+                dropped += 1;
+            }
+        }
+        drop(pending);
+        if dropped > 0 {
+            self.dropped
+                .add(dropped, &[KeyValue::new("reason", "capacity")]);
+        }
+    }
+
+    /// Folds one use into `pending`. `false` only when `key_id` is new and the set is full.
+    fn merge(
+        &self,
+        pending: &mut HashMap<String, PendingUsage>,
+        key_id: &str,
+        used_at: DateTime<Utc>,
+        ip: Option<String>,
+    ) -> bool {
+        if let Some(existing) = pending.get_mut(key_id) {
+            if existing.last_used_at < used_at {
+                existing.last_used_at = used_at;
+                existing.last_ip = ip;
+            }
+            return true;
+        }
+        if pending.len() >= self.max_pending {
+            return false;
+        }
+        pending.insert(
+            key_id.to_string(),
+            PendingUsage {
+                last_used_at: used_at,
+                last_ip: ip,
+            },
+        );
+        true
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, PendingUsage>> {
+        // A poisoned lock only means another thread panicked mid-insert; every entry is still a
+        // complete value, so keep recording.
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Runs [`ApiKeyUsageTracker::flush`] every [`API_KEY_USAGE_FLUSH_INTERVAL`] for the life of the
+/// process. A failed flush is logged and retried on the next tick with the usages it put back.
+pub fn spawn_api_key_usage_flusher(
+    tracker: Arc<ApiKeyUsageTracker>,
+    repo: Arc<dyn OpaRepoTrait>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(API_KEY_USAGE_FLUSH_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if let Err(err) = tracker.flush(repo.as_ref()).await {
+                tracing::warn!(
+                    error = %err,
+                    pending = tracker.len(),
+                    "api key usage flush failed; retrying on the next tick"
+                );
+            }
+        }
+    })
+}
+
+/// The last flush of a server that has stopped serving. Logged rather than returned, since the
+/// server is exiting either way.
+pub async fn flush_on_shutdown(tracker: &ApiKeyUsageTracker, repo: &dyn OpaRepoTrait) {
+    match tracker.flush(repo).await {
+        Ok(updated) => tracing::info!(updated, "api key usage flushed on shutdown"),
+        Err(err) => tracing::warn!(
+            error = %err,
+            lost = tracker.len(),
+            "api key usage flush on shutdown failed"
+        ),
+    }
+}
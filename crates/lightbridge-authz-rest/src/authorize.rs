@@ -0,0 +1,436 @@
+//! The brokered authorization-code + PKCE flow's browser surface on `authz-idp`: a registered
+//! client sends the user to `/authorize`, this service signs them in at Keycloak, and hands the
+//! client back a single-use code redeemable at `/oauth2/token` for the same project-scoped tokens
+//! the exchange grant mints (`token_exchange::authorization_code_grant`,
+//! `oauth2_op::store::TokenExchangeOpStore::handle_authorization_code`).
+//!
+//! Routes, both public (the client's registered redirect URI and the Keycloak callback are the
+//! only places a browser is ever sent):
+//!
+//! - `GET /authorize` -- RFC 6749 §4.1.1 plus RFC 7636. Validates the request up front, records
+//!   it (`authorization_requests`), and redirects to Keycloak's own hosted login (authorization
+//!   code + PKCE, `state` bound to this browser). This service's own extensions ride along:
+//!   `nonce` is reflected into the `id_token`, and `project_id` picks which project the session
+//!   is sealed to, exactly as on the token endpoint.
+//! - `GET /authorize/callback` -- Keycloak's redirect back. Redeems Keycloak's code, validates
+//!   the access token it returns (`TokenExchangeOpStore::verify_upstream_login`), and issues this
+//!   service's own code through `authkestra_op::handlers::authorize::handle_authorize`, which
+//!   re-validates the stored request and redirects to the client with `code` and `state`.
+//!
+//! **Redirect URIs are compared by exact string match** against `oauth2.clients[].redirect_uris`
+//! (RFC 9700 §4.1.3). An unknown client or an unregistered redirect URI renders an error page
+//! here and never redirects (RFC 6749 §4.1.2.1); every later failure is reported to the client's
+//! registered redirect URI as an RFC 6749 §4.1.2.1 error with its `state`. **PKCE is mandatory
+//! and S256-only** for every client allowed this grant (`oauth2_op::client_store`), so a public
+//! client never needs a secret. As with the device flow, this service renders no login form and
+//! validates no credential of its own: `sub`, `email`, and `auth_time` are read off Keycloak's
+//! tokens, never minted.
+
+use std::sync::Arc;
+
+use authkestra_op::OpError;
+use authkestra_op::client::{ClientStore, GrantType};
+use authkestra_op::config::OpConfig;
+use authkestra_op::handlers::authorize::{AuthorizeOutcome, AuthorizeRequest, handle_authorize};
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
+    routing::get,
+};
+use chrono::{Duration, Utc};
+use lightbridge_authz_api_key::entities::authorization_code_row::AuthorizationRequestRow;
+use lightbridge_authz_core::config::Oauth2Upstream;
+use lightbridge_authz_core::cuid::cuid2;
+use serde::Deserialize;
+
+use crate::browser::{
+    CallbackQuery, cookie_value, page, random_token, redeem_upstream_code, see_other, set_cookie,
+    unavailable_page, upstream_authorization_url,
+};
+use crate::oauth2_op::code_store::{PKCE_METHOD_S256, code_identity};
+use crate::oauth2_op::store::{RequestScopedOpStore, TokenExchangeOpStore};
+
+const STATE_COOKIE: &str = "lb_authorize_state";
+const COOKIE_PATH: &str = "/authorize";
+
+/// Everything the `/authorize` routes need: the OP config `handle_authorize` reads (issuer, code
+/// TTL), the shared store codes and pending requests are persisted through, and the Keycloak
+/// client the flow brokers to. Built by `TokenExchangeState::with_authorization_code`.
+#[derive(Clone)]
+pub struct AuthorizeState {
+    op_config: OpConfig,
+    op_store: Arc<TokenExchangeOpStore>,
+    upstream: Arc<Oauth2Upstream>,
+    http: reqwest::Client,
+    request_ttl_seconds: i64,
+}
+
+impl AuthorizeState {
+    pub fn new(
+        op_config: OpConfig,
+        op_store: Arc<TokenExchangeOpStore>,
+        upstream: Oauth2Upstream,
+        request_ttl_seconds: i64,
+    ) -> Self {
+        Self {
+            op_config,
+            op_store,
+            upstream: Arc::new(upstream),
+            http: reqwest::Client::new(),
+            request_ttl_seconds: request_ttl_seconds.max(0),
+        }
+    }
+
+    /// The Keycloak client's redirect URI. Derived from the issuer, never client-supplied, and
+    /// must be registered verbatim on the Keycloak side.
+    fn callback_uri(&self) -> String {
+        format!("{}/authorize/callback", self.op_config.issuer)
+    }
+}
+
+/// Public `/authorize` routes. Provides its own state so it merges into any parent router,
+/// mirroring `device_authorization_router`.
+pub fn authorize_router<S>(state: AuthorizeState) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/authorize", get(authorize_endpoint))
+        .route("/authorize/callback", get(authorize_callback))
+        .with_state(state)
+}
+
+/// Mirrors `authkestra_op::handlers::authorize::AuthorizeRequest`, every field optional so a
+/// malformed request still reaches the validation below instead of axum's own 400, plus
+/// `project_id` (see the module doc comment).
+#[derive(Debug, Deserialize)]
+struct RawAuthorizeRequest {
+    client_id: Option<String>,
+    redirect_uri: Option<String>,
+    response_type: Option<String>,
+    scope: Option<String>,
+    state: Option<String>,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+    nonce: Option<String>,
+    project_id: Option<String>,
+}
+
+async fn authorize_endpoint(
+    State(state): State<AuthorizeState>,
+    Query(raw): Query<RawAuthorizeRequest>,
+) -> Response {
+    let Some(client_id) = raw.client_id.as_deref().filter(|s| !s.is_empty()) else {
+        return invalid_request_page();
+    };
+    let client = match state.op_store.find_client(client_id).await {
+        Ok(Some(client)) => client,
+        Ok(None) => return invalid_request_page(),
+        Err(_) => return unavailable_page(),
+    };
+    let Some(redirect_uri) = raw
+        .redirect_uri
+        .as_deref()
+        .filter(|uri| client.allows_redirect_uri(uri))
+    else {
+        tracing::warn!(client_id, "authorize: redirect_uri is not registered");
+        return invalid_request_page();
+    };
+
+    // From here on the redirect URI is trusted, so every failure goes back to the client.
+    let client_state = raw.state.as_deref();
+    if raw.response_type.as_deref() != Some("code") {
+        return client_error(
+            redirect_uri,
+            "unsupported_response_type",
+            "Only response_type=code is supported",
+            client_state,
+        );
+    }
+    if !client.allows_grant_type(&GrantType::AuthorizationCode) {
+        return client_error(
+            redirect_uri,
+            "unauthorized_client",
+            "Client is not permitted to use the authorization code grant",
+            client_state,
+        );
+    }
+    let Some(code_challenge) = raw
+        .code_challenge
+        .as_deref()
+        .filter(|challenge| is_pkce_challenge(challenge))
+    else {
+        return client_error(
+            redirect_uri,
+            "invalid_request",
+            "code_challenge is required",
+            client_state,
+        );
+    };
+    if raw.code_challenge_method.as_deref() != Some(PKCE_METHOD_S256) {
+        return client_error(
+            redirect_uri,
+            "invalid_request",
+            "code_challenge_method must be S256",
+            client_state,
+        );
+    }
+
+    let now = Utc::now();
+    let oauth_state = random_token();
+    let verifier = random_token();
+    let request = AuthorizationRequestRow {
+        id: cuid2(),
+        // Filled in from `oauth_state` by `begin_request`.
+        state_hash: String::new(),
+        client_id: client.client_id.clone(),
+        redirect_uri: redirect_uri.to_string(),
+        scope: raw.scope.unwrap_or_default(),
+        client_state: raw.state.clone(),
+        code_challenge: code_challenge.to_string(),
+        code_challenge_method: PKCE_METHOD_S256.to_string(),
+        nonce: raw.nonce.filter(|s| !s.is_empty()),
+        project_id: raw.project_id.filter(|s| !s.trim().is_empty()),
+        upstream_code_verifier: verifier.clone(),
+        created_at: now,
+        expires_at: now + Duration::seconds(state.request_ttl_seconds),
+    };
+    if state
+        .op_store
+        .codes()
+        .begin_request(&oauth_state, request)
+        .await
+        .is_err()
+    {
+        return client_error(
+            redirect_uri,
+            "temporarily_unavailable",
+            "The authorization server is temporarily unavailable",
+            client_state,
+        );
+    }
+
+    let Some(location) = upstream_authorization_url(
+        &state.upstream,
+        &state.callback_uri(),
+        &oauth_state,
+        &verifier,
+    ) else {
+        tracing::error!("authorize: upstream authorization_endpoint is invalid");
+        return unavailable_page();
+    };
+    let mut response = see_other(location);
+    // `Lax`, not `Strict`: Keycloak's redirect back is a cross-site top-level navigation, which
+    // is exactly what `Lax` still sends the cookie on.
+    set_cookie(
+        &mut response,
+        STATE_COOKIE,
+        &oauth_state,
+        COOKIE_PATH,
+        "Lax",
+        state.request_ttl_seconds as u64,
+    );
+    response
+}
+
+async fn authorize_callback(
+    State(state): State<AuthorizeState>,
+    headers: HeaderMap,
+    Query(query): Query<CallbackQuery>,
+) -> Response {
+    let Some(oauth_state) = query.state.as_deref() else {
+        return sign_in_failed_page();
+    };
+    if cookie_value(&headers, STATE_COOKIE) != Some(oauth_state) {
+        tracing::warn!("authorize: callback state is not bound to this browser");
+        return sign_in_failed_page();
+    }
+    let request = match state
+        .op_store
+        .codes()
+        .take_request(oauth_state, Utc::now())
+        .await
+    {
+        Ok(Some(request)) => request,
+        Ok(None) => return sign_in_failed_page(),
+        Err(_) => return unavailable_page(),
+    };
+    let client_state = request.client_state.as_deref();
+
+    if let Some(error) = query.error.as_deref() {
+        tracing::warn!(error, "authorize: Keycloak returned an error");
+        return finish(client_error(
+            &request.redirect_uri,
+            "access_denied",
+            "The user did not complete sign-in",
+            client_state,
+        ));
+    }
+    let Some(code) = query.code.as_deref() else {
+        return finish(client_error(
+            &request.redirect_uri,
+            "access_denied",
+            "The user did not complete sign-in",
+            client_state,
+        ));
+    };
+
+    let upstream = match redeem_upstream_code(
+        &state.http,
+        &state.upstream,
+        &state.callback_uri(),
+        code,
+        &request.upstream_code_verifier,
+    )
+    .await
+    {
+        Ok(upstream) => upstream,
+        Err(message) => {
+            tracing::warn!(error = %message, "authorize: code redemption failed");
+            return finish(client_error(
+                &request.redirect_uri,
+                "temporarily_unavailable",
+                "Sign-in could not be completed",
+                client_state,
+            ));
+        }
+    };
+    let login = match state
+        .op_store
+        .verify_upstream_login(&upstream.access_token, upstream.id_token.as_deref())
+        .await
+    {
+        Ok(login) => login,
+        Err(_) => {
+            return finish(client_error(
+                &request.redirect_uri,
+                "access_denied",
+                "The user could not be authenticated",
+                client_state,
+            ));
+        }
+    };
+
+    let identity = code_identity(
+        login.subject,
+        login.email,
+        login.email_verified,
+        login.auth_time,
+    );
+    let scoped = RequestScopedOpStore {
+        inner: state.op_store.as_ref(),
+        project_id: request.project_id,
+        nonce: None,
+    };
+    let authorize_request = AuthorizeRequest {
+        client_id: request.client_id,
+        redirect_uri: request.redirect_uri,
+        response_type: "code".to_string(),
+        scope: request.scope,
+        state: request.client_state,
+        code_challenge: Some(request.code_challenge),
+        code_challenge_method: Some(request.code_challenge_method),
+        nonce: request.nonce,
+    };
+    match handle_authorize(authorize_request, identity, &state.op_config, &scoped).await {
+        AuthorizeOutcome::Redirect(location) => finish(see_other(location)),
+        // The client or its redirect URI was deregistered while the user was signing in.
+        AuthorizeOutcome::DirectError(OpError::Storage) => unavailable_page(),
+        AuthorizeOutcome::DirectError(_) => invalid_request_page(),
+    }
+}
+
+/// Clears the state cookie on the way out of the callback; the request row it named is already
+/// gone.
+fn finish(mut response: Response) -> Response {
+    set_cookie(&mut response, STATE_COOKIE, "", COOKIE_PATH, "Lax", 0);
+    response
+}
+
+/// An RFC 6749 §4.1.2.1 error redirect to a redirect URI already matched against the client's
+/// registration.
+fn client_error(
+    redirect_uri: &str,
+    error: &str,
+    description: &str,
+    client_state: Option<&str>,
+) -> Response {
+    let Ok(mut url) = reqwest::Url::parse(redirect_uri) else {
+        return invalid_request_page();
+    };
+    {
+        let mut query = url.query_pairs_mut();
+        query.append_pair("error", error);
+        query.append_pair("error_description", description);
+        if let Some(client_state) = client_state {
+            query.append_pair("state", client_state);
+        }
+    }
+    see_other(url.into())
+}
+
+/// RFC 7636 §4.2: an S256 challenge is the unpadded base64url SHA-256 of the verifier, always 43
+/// characters.
+fn is_pkce_challenge(challenge: &str) -> bool {
+    challenge.len() == 43
+        && challenge
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+fn invalid_request_page() -> Response {
+    page(
+        StatusCode::BAD_REQUEST,
+        "Invalid sign-in request",
+        "<h1>Invalid sign-in request</h1><p>The application that sent you here is not \
+         registered for this sign-in, or sent an invalid request. Return to it and try again.</p>",
+    )
+}
+
+fn sign_in_failed_page() -> Response {
+    page(
+        StatusCode::BAD_REQUEST,
+        "Sign-in not completed",
+        "<h1>Sign-in not completed</h1><p>This sign-in request has expired or was already \
+         used. Return to the application and try again.</p>",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pkce_challenges_must_be_s256_shaped() {
+        assert!(is_pkce_challenge(
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        ));
+        assert!(!is_pkce_challenge("short"));
+        assert!(!is_pkce_challenge(
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw+cM"
+        ));
+    }
+
+    #[test]
+    fn client_errors_carry_the_state_back() {
+        let response = client_error(
+            "https://app.example.test/cb?x=1",
+            "invalid_request",
+            "bad",
+            Some("s-1"),
+        );
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let location = response
+            .headers()
+            .get(axum::http::header::LOCATION)
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert_eq!(
+            location,
+            "https://app.example.test/cb?x=1&error=invalid_request&error_description=bad&state=s-1"
+        );
+    }
+}
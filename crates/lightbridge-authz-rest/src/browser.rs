@@ -0,0 +1,238 @@
+//! Shared plumbing for `authz-idp`'s two browser-facing flows -- the device grant's verification
+//! page (`crate::device_authorization`) and the authorization-code broker (`crate::authorize`):
+//! the hardened HTML page shell, the cookies binding a Keycloak round trip to the browser that
+//! started it, and the relying-party leg toward the configured Keycloak client.
+//!
+//! Every page is served `no-store`, unframeable (`X-Frame-Options: DENY` + `frame-ancestors
+//! 'none'`), script-free, and with every interpolated value HTML-escaped (ADR-0012,
+//! Consequences).
+
+use axum::{
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use lightbridge_authz_core::config::Oauth2Upstream;
+use serde::Deserialize;
+
+const RANDOM_TOKEN_BYTES: usize = 32;
+/// No scripts, no subresources, no framing -- the pages below are static HTML with an inline
+/// `<style>` and same-origin forms.
+const CONTENT_SECURITY_POLICY: &str =
+    "default-src 'none'; style-src 'unsafe-inline'; frame-ancestors 'none'; base-uri 'none'";
+
+/// Keycloak's redirect back to one of this service's callbacks.
+#[derive(Debug, Deserialize)]
+pub(crate) struct CallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct UpstreamTokenResponse {
+    pub access_token: String,
+    pub id_token: Option<String>,
+}
+
+/// The Keycloak authorization request for a browser leg: authorization code, `openid` scope (so
+/// Keycloak returns the `id_token` `auth_time` is read from), PKCE S256, and this service's own
+/// fixed `callback_uri`. `None` only when the configured endpoint is not a URL.
+pub(crate) fn upstream_authorization_url(
+    upstream: &Oauth2Upstream,
+    callback_uri: &str,
+    oauth_state: &str,
+    verifier: &str,
+) -> Option<String> {
+    use base64::Engine;
+    use sha2::{Digest, Sha256};
+    let challenge =
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier));
+    let mut url = reqwest::Url::parse(&upstream.authorization_endpoint).ok()?;
+    url.query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &upstream.client_id)
+        .append_pair("redirect_uri", callback_uri)
+        .append_pair("scope", "openid")
+        .append_pair("state", oauth_state)
+        .append_pair("code_challenge", &challenge)
+        .append_pair("code_challenge_method", "S256");
+    Some(url.into())
+}
+
+/// Redeems Keycloak's authorization code at the configured token endpoint (RFC 6749 §4.1.3 +
+/// RFC 7636 §4.5). The error is a log message only, never rendered.
+pub(crate) async fn redeem_upstream_code(
+    http: &reqwest::Client,
+    upstream: &Oauth2Upstream,
+    callback_uri: &str,
+    code: &str,
+    verifier: &str,
+) -> Result<UpstreamTokenResponse, String> {
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", callback_uri),
+        ("client_id", upstream.client_id.as_str()),
+        ("code_verifier", verifier),
+    ];
+    if let Some(secret) = upstream.client_secret.as_deref() {
+        form.push(("client_secret", secret));
+    }
+    let response = http
+        .post(&upstream.token_endpoint)
+        .form(&form)
+        .send()
+        .await
+        .map_err(|e| format!("upstream token request failed: {e}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("upstream token endpoint answered {status}"));
+    }
+    response
+        .json::<UpstreamTokenResponse>()
+        .await
+        .map_err(|e| format!("upstream token response parse failed: {e}"))
+}
+
+pub(crate) fn unavailable_page() -> Response {
+    page(
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Temporarily unavailable",
+        "<h1>Temporarily unavailable</h1><p>Please try again in a moment.</p>",
+    )
+}
+
+/// Wraps `body` (already-escaped HTML) in the shared page shell, with the hardening headers
+/// every browser-facing page carries.
+pub(crate) fn page(status: StatusCode, title: &str, body: &str) -> Response {
+    let html = format!(
+        "<!doctype html><html lang=\"en\"><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <title>{title}</title>\
+         <style>body{{font-family:system-ui,sans-serif;max-width:32rem;margin:4rem auto;\
+         padding:0 1rem;line-height:1.5}}.code{{font-family:monospace;letter-spacing:.15em}}\
+         </style></head><body>{body}</body></html>",
+        title = escape_html(title),
+    );
+    let mut response = (
+        status,
+        [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+        html,
+    )
+        .into_response();
+    apply_page_headers(&mut response);
+    response
+}
+
+/// A `303 See Other` to `location` carrying the same hardening headers as [`page`].
+pub(crate) fn see_other(location: String) -> Response {
+    let mut response = (StatusCode::SEE_OTHER, [(header::LOCATION, location)]).into_response();
+    apply_page_headers(&mut response);
+    response
+}
+
+fn apply_page_headers(response: &mut Response) {
+    let headers = response.headers_mut();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers.insert(header::X_FRAME_OPTIONS, HeaderValue::from_static("DENY"));
+    headers.insert(
+        header::CONTENT_SECURITY_POLICY,
+        HeaderValue::from_static(CONTENT_SECURITY_POLICY),
+    );
+    headers.insert(
+        header::REFERRER_POLICY,
+        HeaderValue::from_static("no-referrer"),
+    );
+    headers.insert(
+        header::X_CONTENT_TYPE_OPTIONS,
+        HeaderValue::from_static("nosniff"),
+    );
+}
+
+/// Appends an `HttpOnly`, `Secure` cookie scoped to `path`. `value` is always one of this
+/// module's own base64url tokens (or empty, to clear it), so it needs no quoting.
+pub(crate) fn set_cookie(
+    response: &mut Response,
+    name: &str,
+    value: &str,
+    path: &str,
+    same_site: &str,
+    max_age: u64,
+) {
+    let cookie = format!(
+        "{name}={value}; Path={path}; Max-Age={max_age}; HttpOnly; Secure; SameSite={same_site}"
+    );
+    if let Ok(value) = HeaderValue::from_str(&cookie) {
+        response.headers_mut().append(header::SET_COOKIE, value);
+    }
+}
+
+pub(crate) fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+pub(crate) fn random_token() -> String {
+    use base64::Engine;
+    use rand_core::{OsRng, RngCore};
+    let mut buf = [0u8; RANDOM_TOKEN_BYTES];
+    OsRng.fill_bytes(&mut buf);
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(buf)
+}
+
+pub(crate) fn escape_html(raw: &str) -> String {
+    let mut escaped = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_markup_and_attribute_quotes() {
+        assert_eq!(
+            escape_html(r#"<script>"x"&'y'</script>"#),
+            "&lt;script&gt;&quot;x&quot;&amp;&#39;y&#39;&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn reads_a_named_cookie_among_several() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("a=1; lb_device_state=abc; b=2"),
+        );
+        assert_eq!(cookie_value(&headers, "lb_device_state"), Some("abc"));
+        assert_eq!(cookie_value(&headers, "lb_device_csrf"), None);
+    }
+
+    #[test]
+    fn random_tokens_are_unpadded_base64url() {
+        let token = random_token();
+        assert_eq!(token.len(), 43);
+        assert!(
+            token
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+        assert_ne!(token, random_token());
+    }
+}
@@ -0,0 +1,92 @@
+//! Period-rollover opening grants (`lightbridge_authz_budget::RolloverService`), run two ways:
+//!
+//! - [`spawn_budget_rollover`]: a background task `authz-budget` starts alongside its listener,
+//!   running the rollover for the current [`Period`] once at startup and then every
+//!   [`BUDGET_ROLLOVER_INTERVAL`]. The interval bounds how long after a month boundary an account
+//!   goes without its opening grant; a run that finds nothing to do is one indexed read.
+//! - [`run_budget_rollover`]: a single run for `lightbridge-authz budget rollover`, for a backfill
+//!   of a past period or a deployment that does not run `authz-budget` continuously.
+//!
+//! Both are safe to run concurrently with each other and across replicas: every opening grant's
+//! idempotency key is derived from `(account, period)`, so at most one is ever written.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use lightbridge_authz_budget::{Period, RolloverReport, RolloverService};
+use lightbridge_authz_core::db::DbPoolTrait;
+use lightbridge_authz_core::error::{Error, Result};
+
+/// How often [`spawn_budget_rollover`] re-runs.
+pub const BUDGET_ROLLOVER_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Runs the rollover for the current period now and every [`BUDGET_ROLLOVER_INTERVAL`] for the
+/// life of the process. A failed run is logged and retried on the next tick, never fatal: the
+/// listener keeps serving, and the accounts it missed are exactly the ones the next run picks up.
+pub fn spawn_budget_rollover(service: Arc<RolloverService>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(BUDGET_ROLLOVER_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            let period = Period::current(Utc::now());
+            match service.run(&period).await {
+                Ok(report) => log_report(&report),
+                Err(err) => tracing::warn!(
+                    %period,
+                    error = %err,
+                    "budget rollover failed; retrying on the next tick"
+                ),
+            }
+        }
+    })
+}
+
+/// One rollover run against the active budget policy, for `period` (`YYYY-MM`) or, when `None`,
+/// the current one.
+pub async fn run_budget_rollover(
+    pool: Arc<dyn DbPoolTrait>,
+    period: Option<&str>,
+) -> Result<RolloverReport> {
+    let period = match period {
+        Some(period) => Period::parse(period)
+            .map_err(|e| Error::Server(format!("invalid rollover period: {e}")))?,
+        None => Period::current(Utc::now()),
+    };
+    let policy_store = lightbridge_authz_budget::PolicyStore::load_active_from_db(
+        pool.clone(),
+        crate::BUDGET_POLICY_SET_ID,
+        crate::BUDGET_POLICY_EVALUATION_BUDGET,
+    )
+    .await
+    .map_err(|e| Error::Server(format!("failed to load active budget policy: {e}")))?;
+    let service = RolloverService::new(
+        Arc::new(lightbridge_authz_budget::repo::BudgetRepo::new(pool)),
+        policy_store.engine(),
+    );
+
+    let report = service
+        .run(&period)
+        .await
+        .map_err(|e| Error::Server(format!("budget rollover failed: {e}")))?;
+    log_report(&report);
+    Ok(report)
+}
+
+fn log_report(report: &RolloverReport) {
+    if report.failed > 0 {
+        tracing::warn!(
+            period = %report.period,
+            granted = report.granted,
+            failed = report.failed,
+            "budget rollover left accounts without an opening grant"
+        );
+    } else if report.granted > 0 {
+        tracing::info!(
+            period = %report.period,
+            granted = report.granted,
+            "budget rollover wrote opening grants"
+        );
+    }
+}
@@ -0,0 +1,402 @@
+//! The RFC 8628 device authorization grant's HTTP surface on `authz-idp` (ADR-0012): the
+//! device-authorization endpoint a CLI starts a pairing with, and the browser-facing verification
+//! page a user finishes it on. The polling half -- the `device_code` grant on `/oauth2/token` --
+//! lives with the other grants (`token_exchange::device_code_grant`,
+//! `oauth2_op::store::TokenExchangeOpStore::handle_device_code`).
+//!
+//! Routes, all public (the presented client credential, user code, or Keycloak callback is the
+//! credential):
+//!
+//! - `POST /oauth2/device_authorization` -- RFC 8628 §3.1/§3.2. Client authentication, code
+//!   generation, and the response shape are `authkestra_op`'s own
+//!   `handle_device_authorization`; this handler adds `verification_uri_complete` and threads the
+//!   request's `project_id`/`nonce` extensions through `RequestScopedOpStore` into the persisted
+//!   session, exactly as the token endpoint threads `project_id` into the exchange grant.
+//! - `GET /device` -- the user-code entry form, or (with `?user_code=`) the confirmation page
+//!   naming the client and scope the user is about to pair.
+//! - `POST /device` -- the user's decision. Deny records the refusal; approve redirects to
+//!   Keycloak's own hosted login (authorization code + PKCE, `state` bound to this browser).
+//! - `GET /device/callback` -- Keycloak's redirect back. Redeems the code, validates the access
+//!   token it returns, and approves the pairing for that Keycloak `sub`
+//!   (`TokenExchangeOpStore::approve_device_verification`).
+//!
+//! This service still renders no login form and validates no credential of its own (ADR-0012
+//! Decision 2): the only redirect it issues is this single, non-client-supplied one to the
+//! configured Keycloak client (Decision 4). Any failure on the browser leg -- Keycloak down, a
+//! refused login, a replayed or cross-browser callback -- renders an error page and leaves the
+//! pairing `pending`, never approved.
+//!
+//! **Browser-facing hardening** (ADR-0012, Consequences): every page goes through
+//! `crate::browser`'s shared, hardened shell. The decision form carries a double-submit token bound to a
+//! `SameSite=Strict` cookie, so a cross-site POST cannot push an already-signed-in Keycloak user
+//! through an approval they never saw; the Keycloak `state` is likewise bound to the browser that
+//! confirmed the code, so a Keycloak login URL lifted from one browser cannot approve a pairing in
+//! another. An unknown, expired, and already-decided user code all render the same page, so the
+//! verification page never tells a guesser which codes exist.
+
+use std::sync::Arc;
+
+use authkestra_op::config::OpConfig;
+use authkestra_op::handlers::device_authorization::{
+    DeviceAuthorizationRequest, handle_device_authorization,
+};
+use axum::{
+    Form, Json, Router,
+    extract::{Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use chrono::Utc;
+use lightbridge_authz_core::config::Oauth2Upstream;
+use serde::Deserialize;
+
+use crate::browser::{
+    CallbackQuery, cookie_value, escape_html, page, random_token, redeem_upstream_code, see_other,
+    set_cookie, unavailable_page, upstream_authorization_url,
+};
+use crate::oauth2_op::store::{RequestScopedOpStore, TokenExchangeOpStore};
+use crate::token_exchange::{oauth_error, status_for_oauth_error};
+
+const CSRF_COOKIE: &str = "lb_device_csrf";
+const STATE_COOKIE: &str = "lb_device_state";
+const COOKIE_PATH: &str = "/device";
+
+/// Everything the device routes need: the OP config `handle_device_authorization` reads (issuer,
+/// device-code TTL), the shared store pairing sessions are persisted through, and the Keycloak
+/// client the verification page brokers to. Built by
+/// `TokenExchangeState::with_device_authorization`.
+#[derive(Clone)]
+pub struct DeviceAuthorizationState {
+    op_config: OpConfig,
+    op_store: Arc<TokenExchangeOpStore>,
+    upstream: Arc<Oauth2Upstream>,
+    http: reqwest::Client,
+}
+
+impl DeviceAuthorizationState {
+    pub fn new(
+        op_config: OpConfig,
+        op_store: Arc<TokenExchangeOpStore>,
+        upstream: Oauth2Upstream,
+    ) -> Self {
+        Self {
+            op_config,
+            op_store,
+            upstream: Arc::new(upstream),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// The Keycloak client's redirect URI. Derived from the issuer, never client-supplied
+    /// (ADR-0012 Decision 4), and must be registered verbatim on the Keycloak side.
+    fn callback_uri(&self) -> String {
+        format!("{}/device/callback", self.op_config.issuer)
+    }
+}
+
+/// Public device-authorization and verification routes. Provides its own state so it merges
+/// into any parent router, mirroring `token_exchange_router`.
+pub fn device_authorization_router<S>(state: DeviceAuthorizationState) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route(
+            "/oauth2/device_authorization",
+            post(device_authorization_endpoint),
+        )
+        .route(
+            "/device",
+            get(verification_page).post(verification_decision),
+        )
+        .route("/device/callback", get(verification_callback))
+        .with_state(state)
+}
+
+/// Mirrors `authkestra_op::handlers::device_authorization::DeviceAuthorizationRequest`
+/// field-for-field, plus this service's two extensions (see the module doc comment).
+#[derive(Debug, Deserialize)]
+struct RawDeviceAuthorizationRequest {
+    client_id: Option<String>,
+    scope: Option<String>,
+    client_secret: Option<String>,
+    client_assertion: Option<String>,
+    client_assertion_type: Option<String>,
+    project_id: Option<String>,
+    nonce: Option<String>,
+}
+
+async fn device_authorization_endpoint(
+    State(state): State<DeviceAuthorizationState>,
+    headers: HeaderMap,
+    Form(raw): Form<RawDeviceAuthorizationRequest>,
+) -> Response {
+    let auth_header = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    let scoped = RequestScopedOpStore {
+        inner: state.op_store.as_ref(),
+        project_id: raw.project_id,
+        nonce: raw.nonce,
+    };
+    let req = DeviceAuthorizationRequest {
+        client_id: raw.client_id,
+        scope: raw.scope,
+        client_secret: raw.client_secret,
+        client_assertion: raw.client_assertion,
+        client_assertion_type: raw.client_assertion_type,
+    };
+
+    match handle_device_authorization(req, auth_header, &state.op_config, &scoped).await {
+        Ok(mut resp) => {
+            // RFC 8628 §3.3.1: lets the CLI render one link (or QR code) that lands the user
+            // straight on the confirmation page. Issued user codes are uppercase hex, so they
+            // need no percent-encoding.
+            resp.verification_uri_complete = Some(format!(
+                "{}?user_code={}",
+                resp.verification_uri, resp.user_code
+            ));
+            let mut response = (StatusCode::OK, Json(resp)).into_response();
+            response
+                .headers_mut()
+                .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+            response
+        }
+        Err(err) => oauth_error(
+            status_for_oauth_error(&err.error),
+            &err.error,
+            &err.error_description,
+        ),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct VerificationQuery {
+    user_code: Option<String>,
+}
+
+async fn verification_page(
+    State(state): State<DeviceAuthorizationState>,
+    Query(query): Query<VerificationQuery>,
+) -> Response {
+    let Some(user_code) = query.user_code.filter(|s| !s.trim().is_empty()) else {
+        return page(StatusCode::OK, "Connect a device", ENTRY_FORM);
+    };
+    let session = match state
+        .op_store
+        .devices()
+        .find_pending_by_user_code(&user_code, Utc::now())
+        .await
+    {
+        Ok(Some(session)) => session,
+        Ok(None) => return invalid_code_page(),
+        Err(_) => return unavailable_page(),
+    };
+
+    let csrf = random_token();
+    let scope = if session.scope.trim().is_empty() {
+        "(default)".to_string()
+    } else {
+        session.scope.clone()
+    };
+    let body = format!(
+        "<h1>Connect a device</h1>\
+         <p>The application <strong>{client}</strong> is asking to sign in as you.</p>\
+         <p>Only continue if you started this on your own device and it shows the code \
+         <strong class=\"code\">{code}</strong>.</p>\
+         <p>Requested access: <code>{scope}</code></p>\
+         <form method=\"post\" action=\"/device\">\
+         <input type=\"hidden\" name=\"user_code\" value=\"{code}\">\
+         <input type=\"hidden\" name=\"csrf\" value=\"{csrf}\">\
+         <button type=\"submit\" name=\"action\" value=\"approve\">Continue to sign in</button> \
+         <button type=\"submit\" name=\"action\" value=\"deny\">Deny</button>\
+         </form>",
+        client = escape_html(&session.client_id),
+        code = escape_html(&session.user_code),
+        scope = escape_html(&scope),
+        csrf = escape_html(&csrf),
+    );
+    let mut response = page(StatusCode::OK, "Connect a device", &body);
+    set_cookie(
+        &mut response,
+        CSRF_COOKIE,
+        &csrf,
+        COOKIE_PATH,
+        "Strict",
+        600,
+    );
+    response
+}
+
+#[derive(Debug, Deserialize)]
+struct VerificationDecision {
+    user_code: String,
+    action: String,
+    csrf: String,
+}
+
+async fn verification_decision(
+    State(state): State<DeviceAuthorizationState>,
+    headers: HeaderMap,
+    Form(form): Form<VerificationDecision>,
+) -> Response {
+    if form.csrf.is_empty() || cookie_value(&headers, CSRF_COOKIE) != Some(form.csrf.as_str()) {
+        return page(
+            StatusCode::FORBIDDEN,
+            "Request expired",
+            "<h1>Request expired</h1><p>Please <a href=\"/device\">enter the code again</a>.</p>",
+        );
+    }
+    let now = Utc::now();
+    let devices = state.op_store.devices();
+
+    match form.action.as_str() {
+        "deny" => match devices.deny(&form.user_code, now).await {
+            Ok(Some(_)) => page(
+                StatusCode::OK,
+                "Request denied",
+                "<h1>Request denied</h1><p>The device was not connected. You can close this \
+                 window.</p>",
+            ),
+            Ok(None) => invalid_code_page(),
+            Err(_) => unavailable_page(),
+        },
+        "approve" => {
+            let verifier = random_token();
+            let oauth_state = random_token();
+            match devices
+                .begin_verification(&form.user_code, &oauth_state, &verifier, now)
+                .await
+            {
+                Ok(Some(_)) => {}
+                Ok(None) => return invalid_code_page(),
+                Err(_) => return unavailable_page(),
+            }
+            let Some(location) = upstream_authorization_url(
+                &state.upstream,
+                &state.callback_uri(),
+                &oauth_state,
+                &verifier,
+            ) else {
+                tracing::error!("device verification: upstream authorization_endpoint is invalid");
+                return unavailable_page();
+            };
+            let mut response = see_other(location);
+            // `Lax`, not `Strict`: Keycloak's redirect back is a cross-site top-level navigation,
+            // which is exactly what `Lax` still sends the cookie on.
+            let max_age = state.op_config.device_code_ttl_secs;
+            set_cookie(
+                &mut response,
+                STATE_COOKIE,
+                &oauth_state,
+                COOKIE_PATH,
+                "Lax",
+                max_age,
+            );
+            response
+        }
+        _ => page(
+            StatusCode::BAD_REQUEST,
+            "Invalid request",
+            "<h1>Invalid request</h1><p>Please <a href=\"/device\">start again</a>.</p>",
+        ),
+    }
+}
+
+async fn verification_callback(
+    State(state): State<DeviceAuthorizationState>,
+    headers: HeaderMap,
+    Query(query): Query<CallbackQuery>,
+) -> Response {
+    if let Some(error) = query.error.as_deref() {
+        tracing::warn!(error, "device verification: Keycloak returned an error");
+        return sign_in_failed_page();
+    }
+    let (Some(code), Some(oauth_state)) = (query.code.as_deref(), query.state.as_deref()) else {
+        return sign_in_failed_page();
+    };
+    if cookie_value(&headers, STATE_COOKIE) != Some(oauth_state) {
+        tracing::warn!("device verification: callback state is not bound to this browser");
+        return sign_in_failed_page();
+    }
+
+    let devices = state.op_store.devices();
+    let session = match devices.find_pending_by_state(oauth_state, Utc::now()).await {
+        Ok(Some(session)) => session,
+        Ok(None) => return invalid_code_page(),
+        Err(_) => return unavailable_page(),
+    };
+    let Some(verifier) = session.verification_code_verifier.as_deref() else {
+        return invalid_code_page();
+    };
+
+    let upstream = match redeem_upstream_code(
+        &state.http,
+        &state.upstream,
+        &state.callback_uri(),
+        code,
+        verifier,
+    )
+    .await
+    {
+        Ok(upstream) => upstream,
+        Err(message) => {
+            tracing::warn!(error = %message, "device verification: code redemption failed");
+            return sign_in_failed_page();
+        }
+    };
+
+    match state
+        .op_store
+        .approve_device_verification(
+            oauth_state,
+            &upstream.access_token,
+            upstream.id_token.as_deref(),
+        )
+        .await
+    {
+        Ok(approved) => {
+            tracing::info!(
+                client_id = %approved.client_id,
+                "device verification approved"
+            );
+            let mut response = page(
+                StatusCode::OK,
+                "Device connected",
+                "<h1>Device connected</h1><p>You can close this window and return to your \
+                 device.</p>",
+            );
+            set_cookie(&mut response, STATE_COOKIE, "", COOKIE_PATH, "Lax", 0);
+            response
+        }
+        Err(authkestra_op::OpError::Storage) => unavailable_page(),
+        Err(_) => sign_in_failed_page(),
+    }
+}
+
+const ENTRY_FORM: &str = "<h1>Connect a device</h1>\
+     <p>Enter the code shown on your device.</p>\
+     <form method=\"get\" action=\"/device\">\
+     <input type=\"text\" name=\"user_code\" autocomplete=\"off\" autocapitalize=\"characters\" \
+     spellcheck=\"false\" required>\
+     <button type=\"submit\">Continue</button>\
+     </form>";
+
+fn invalid_code_page() -> Response {
+    page(
+        StatusCode::NOT_FOUND,
+        "Code not valid",
+        "<h1>Code not valid</h1><p>This code is invalid, has expired, or was already used. \
+         Start again on your device, then <a href=\"/device\">enter the new code</a>.</p>",
+    )
+}
+
+fn sign_in_failed_page() -> Response {
+    page(
+        StatusCode::BAD_REQUEST,
+        "Sign-in not completed",
+        "<h1>Sign-in not completed</h1><p>The device was not connected. Please \
+         <a href=\"/device\">enter the code again</a>.</p>",
+    )
+}
@@ -0,0 +1,244 @@
+//! Native Envoy `ext_authz` gRPC listener (`authz-extauthz`).
+//!
+//! Serves `envoy.service.auth.v3.Authorization/Check` so an Envoy gateway can authorize a request
+//! against authz directly, with no Authorino hop in between. Every `CheckRequest` is resolved
+//! through [`introspect_token`] -- the exact API-key-row-first dispatch
+//! `POST /v1/authorino/validate/introspect` runs -- so the two integrations can never disagree on
+//! whether a credential is live. Only the framing differs: an active credential becomes an
+//! `OkHttpResponse` carrying the same tenant headers Authorino stamps (`x-account-id`,
+//! `x-project-id`, `x-project-role`, `x-quota-tier`, `x-project-quota`, `x-billing-plan`), and
+//! anything else becomes a `401` `DeniedHttpResponse`.
+//!
+//! Every tenant header is written with `OVERWRITE_IF_EXISTS_OR_ADD` and `keep_empty_value`, so a
+//! client that sends its own `x-account-id` (or any sibling) never has it reach the upstream: an
+//! absent value is stamped as `""`, which is also what downstream rate-limit selectors already
+//! treat as "no tier" on the Authorino path.
+//!
+//! The gRPC service is turned into an `axum::Router` and served through the shared
+//! [`serve_tls`] (which negotiates `h2` over ALPN), so this listener gets the same TLS/mTLS
+//! handling as every other server. There is no Basic auth here -- Envoy's gRPC client has no such
+//! knob -- so a deployment reachable from outside the mesh must set `tls.client_ca_bundle_path`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_core::ApiKeyHasher;
+use lightbridge_authz_core::config::{
+    ApiKeyHashing, BasicAuth, Billing, ExtAuthzServer, IntrospectionBudget, IntrospectionCache,
+    Oauth2, Redis, UsageServiceClient,
+};
+use lightbridge_authz_core::db::DbPoolTrait;
+use lightbridge_authz_core::error::Result;
+use lightbridge_authz_core::server::serve_tls;
+use lightbridge_authz_proto::envoy_types::ext_authz::v3::pb::{
+    Authorization, AuthorizationServer, CheckRequest, CheckResponse, HeaderAppendAction,
+    HttpStatusCode,
+};
+use lightbridge_authz_proto::envoy_types::ext_authz::v3::{
+    CheckRequestExt, CheckResponseExt, DeniedHttpResponseBuilder, OkHttpResponseBuilder,
+};
+use tonic::{Request, Response, Status};
+
+use crate::api_key_usage::{ApiKeyUsageTracker, flush_on_shutdown, spawn_api_key_usage_flusher};
+use crate::handlers::introspect::introspect_token;
+use crate::models::IntrospectResponse;
+use crate::{OpaRepoTrait, OpaState};
+
+/// Request headers stamped on an allowed request. Kept in one list so [`allow_response`] and the
+/// spoofing guarantee in the module doc comment cover exactly the same set.
+pub const TENANT_HEADERS: [&str; 6] = [
+    "x-account-id",
+    "x-project-id",
+    "x-project-role",
+    "x-quota-tier",
+    "x-project-quota",
+    "x-billing-plan",
+];
+
+/// The `Authorization` service implementation. Holds the same [`OpaState`] `authz-opa` builds, so
+/// the billing catalogue, self-issued-token audience and repository are shared verbatim.
+#[derive(Clone)]
+pub struct ExtAuthzService {
+    state: Arc<OpaState>,
+}
+
+impl ExtAuthzService {
+    pub fn new(state: Arc<OpaState>) -> Self {
+        Self { state }
+    }
+}
+
+#[tonic::async_trait]
+impl Authorization for ExtAuthzService {
+    async fn check(
+        &self,
+        request: Request<CheckRequest>,
+    ) -> std::result::Result<Response<CheckResponse>, Status> {
+        let request = request.into_inner();
+        let Some(token) = request.get_client_headers().and_then(bearer_token) else {
+            tracing::info!(
+                active = false,
+                reason = "missing_bearer",
+                "ext_authz check denied"
+            );
+            return Ok(Response::new(deny_response()));
+        };
+        let ip = request.get_client_address().cloned();
+
+        let introspection = introspect_token(&self.state, &token, ip)
+            .await
+            .map_err(|err| {
+                // Surfaced as a gRPC error, not a `CheckResponse`: Envoy then applies its own
+                // `status_on_error`/`failure_mode_allow` policy, which is the operator's call to
+                // make, not this service's. The detail stays in our logs only.
+                tracing::error!(error = %err, "ext_authz introspection failed");
+                Status::internal("introspection failed")
+            })?;
+
+        if !introspection.active {
+            return Ok(Response::new(deny_response()));
+        }
+        Ok(Response::new(allow_response(&introspection)))
+    }
+}
+
+/// Extracts the credential from the `authorization` request header (`Bearer <token>`, scheme
+/// matched case-insensitively). Envoy lower-cases header names in `CheckRequest`, so only the
+/// lower-case key is consulted.
+fn bearer_token(headers: &HashMap<String, String>) -> Option<String> {
+    let value = headers.get("authorization")?.trim();
+    let (scheme, token) = value.split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return None;
+    }
+    let token = token.trim();
+    (!token.is_empty()).then(|| token.to_string())
+}
+
+/// Builds the allow response for an active introspection result. See the module doc comment for
+/// why every header in [`TENANT_HEADERS`] is always written, even when empty.
+pub fn allow_response(introspection: &IntrospectResponse) -> CheckResponse {
+    let values = [
+        introspection.account_id.as_deref(),
+        introspection.project_id.as_deref(),
+        introspection.role.as_deref(),
+        introspection.quota_tier.as_deref(),
+        introspection.project_quota.as_deref(),
+        introspection.billing_plan.as_deref(),
+    ];
+
+    let mut ok = OkHttpResponseBuilder::new();
+    for (name, value) in TENANT_HEADERS.iter().zip(values) {
+        ok.add_header(
+            *name,
+            value.unwrap_or_default(),
+            Some(HeaderAppendAction::OverwriteIfExistsOrAdd),
+            true,
+        );
+    }
+
+    let mut response = CheckResponse::with_status(Status::ok("authorized"));
+    response.set_http_response(ok);
+    response
+}
+
+/// Builds the canonical deny response: `UNAUTHENTICATED` with an HTTP `401` and a
+/// `www-authenticate: Bearer` challenge. Deliberately carries no reason -- the deny reason is
+/// logged by the validation path, never echoed to the caller.
+pub fn deny_response() -> CheckResponse {
+    let mut denied = DeniedHttpResponseBuilder::new();
+    denied
+        .set_http_status(HttpStatusCode::Unauthorized)
+        .add_header("www-authenticate", "Bearer", None, false);
+
+    let mut response = CheckResponse::with_status(Status::unauthenticated("unauthorized"));
+    response.set_http_response(denied);
+    response
+}
+
+/// Assembles the `authz-extauthz` router: public probes plus the gRPC `Authorization` service.
+/// Separated from `start_extauthz_server` for testability, mirroring `build_opa_router`.
+pub fn build_extauthz_router(
+    state: Arc<OpaState>,
+    readiness_pool: Arc<dyn DbPoolTrait>,
+) -> axum::Router {
+    let grpc = tonic::service::Routes::new(AuthorizationServer::new(ExtAuthzService::new(state)))
+        .prepare()
+        .into_axum_router();
+    crate::probe_router(readiness_pool).merge(grpc)
+}
+
+/// `basic_auth` is `server.opa.basic_auth`, carried only because [`OpaState`] requires it -- this
+/// listener mounts no Basic-auth route (the same arrangement `lightbridge-mcp` uses).
+/// `introspection_cache`/`redis` and `introspection_budget`/`usage_service` are wired exactly as
+/// `start_opa_server` wires them.
+#[expect(
+    clippy::too_many_arguments,
+    reason = "startup wiring for authz-extauthz -- each parameter is a distinct, \
+              independently-loaded config section, mirroring start_opa_server"
+)]
+pub async fn start_extauthz_server(
+    extauthz: &ExtAuthzServer,
+    pool: Arc<dyn DbPoolTrait>,
+    basic_auth: &BasicAuth,
+    billing: &Billing,
+    oauth2: &Oauth2,
+    introspection_cache: &Option<IntrospectionCache>,
+    redis: &Option<Redis>,
+    introspection_budget: &Option<IntrospectionBudget>,
+    usage_service: &Option<UsageServiceClient>,
+    api_key_hashing: &Option<ApiKeyHashing>,
+) -> Result<()> {
+    let api_key_hasher = Arc::new(ApiKeyHasher::from_config(api_key_hashing.as_ref())?);
+    let introspection_cache = crate::introspection_cache::build_introspection_cache(
+        introspection_cache,
+        redis,
+        "authz-extauthz",
+    )?;
+    let introspection_budget = crate::introspection_budget::build_introspection_budget(
+        introspection_budget,
+        usage_service,
+        pool.clone(),
+        "authz-extauthz",
+    )
+    .await?;
+    let readiness_pool = pool.clone();
+    let repo: Arc<dyn OpaRepoTrait> = Arc::new(StoreRepo::new(pool));
+    let api_key_audience = oauth2
+        .signing
+        .as_ref()
+        .and_then(|signing| signing.audience.clone());
+    let api_key_usage = Arc::new(ApiKeyUsageTracker::default());
+    let state = Arc::new(OpaState {
+        repo: repo.clone(),
+        basic_auth: basic_auth.clone(),
+        billing: Arc::new(billing.clone()),
+        api_key_audience,
+        introspection_cache,
+        introspection_budget,
+        api_key_hasher,
+        api_key_usage: api_key_usage.clone(),
+    });
+    spawn_api_key_usage_flusher(api_key_usage.clone(), repo.clone());
+
+    let app = build_extauthz_router(state, readiness_pool);
+
+    tracing::info!(
+        server = "authz-extauthz",
+        address = %extauthz.address,
+        port = extauthz.port,
+        "starting ext_authz server"
+    );
+
+    let served = serve_tls(
+        "EXTAUTHZ",
+        &extauthz.address,
+        extauthz.port,
+        &extauthz.tls,
+        app,
+    )
+    .await;
+    flush_on_shutdown(&api_key_usage, repo.as_ref()).await;
+    served
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
-use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header, jwk::Jwk};
+use jsonwebtoken::jwk::{Jwk, KeyAlgorithm};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
 use lightbridge_authz_core::{
     Project, ResourceStatus,
     error::{Error, Result},
@@ -133,9 +134,15 @@ async fn verify_self_issued_token(
         tracing::warn!(kid = %kid, "stored signing key JWK is not a usable decoding key");
         return Ok(None);
     };
+    // The stored key, never the token's own header, picks the algorithm (no algorithm
+    // confusion), so a JWKS mixing RS256 and EdDSA keys mid-migration verifies both.
+    let Some(algorithm) = jwk_algorithm(&jwk) else {
+        tracing::warn!(kid = %kid, "stored signing key JWK has no supported alg");
+        return Ok(None);
+    };
 
-    let mut validation = Validation::new(Algorithm::RS256);
-    validation.algorithms = vec![Algorithm::RS256];
+    let mut validation = Validation::new(algorithm);
+    validation.algorithms = vec![algorithm];
     validation.validate_aud = false;
 
     let claims = match decode::<ExchangeClaims>(token, &decoding_key, &validation) {
@@ -159,6 +166,16 @@ async fn verify_self_issued_token(
     Ok(Some(claims))
 }
 
+/// Maps a stored JWK's `alg` to the algorithm its tokens must be verified with. Only the
+/// algorithms `signing::generate_signing_key` produces are accepted.
+fn jwk_algorithm(jwk: &Jwk) -> Option<Algorithm> {
+    match jwk.common.key_algorithm? {
+        KeyAlgorithm::RS256 => Some(Algorithm::RS256),
+        KeyAlgorithm::EdDSA => Some(Algorithm::EdDSA),
+        _ => None,
+    }
+}
+
 /// The `azp` discriminant `verify_self_issued_token` refuses on. Fail-closed on an absent `azp`
 /// (treated as "could be an API key", never as "therefore not one") -- see that function's doc
 /// comment for the full reasoning and why this must not be weakened to "only refuse an exact
@@ -1,13 +1,30 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use axum::{Form, Json, extract::State, http::StatusCode, response::IntoResponse};
-use lightbridge_authz_core::{Result, hash_api_key};
+use chrono::Utc;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use lightbridge_authz_core::error::Error;
+use lightbridge_authz_core::{ApiKeyValidation, Result};
 use tracing::instrument;
 
 use crate::OpaState;
 use crate::handlers::exchange_token::resolve_exchange_token_context;
-use crate::handlers::opa::validate_api_key_context;
-use crate::models::{IntrospectRequest, IntrospectResponse};
+use crate::handlers::opa::validate_api_key_row;
+use crate::models::{
+    BatchIntrospectRequest, BatchIntrospectResponse, IntrospectRequest, IntrospectResponse,
+};
+
+/// Upper bound on tokens per [`introspect_api_keys_batch`] call. Keeps one request's fan-out
+/// (each active key still costs a key read and a project read) and its `ANY($1)` array to a
+/// size a single gateway sidecar refresh plausibly needs.
+pub const MAX_BATCH_INTROSPECT_TOKENS: usize = 100;
+
+/// How many uncached tokens of one batch are introspected at once. Each in-flight token holds at
+/// most one pooled connection at a time (key read, then project read), so this keeps a full
+/// batch to under half of `DbPool::new`'s default 10 connections instead of queueing up to
+/// [`MAX_BATCH_INTROSPECT_TOKENS`] acquires ahead of every single-token introspection.
+pub const BATCH_INTROSPECT_CONCURRENCY: usize = 4;
 
 /// RFC 7662 token introspection. Authorino's `oauth2Introspection` identity calls this to
 /// authenticate a presented bearer and read its authorization context in one call.
@@ -18,7 +35,8 @@ use crate::models::{IntrospectRequest, IntrospectResponse};
 ///
 /// 1. **A row exists.** This is, or was, a real API key (opaque secret or self-signed JWT --
 ///    both are hashed into `key_hash` at mint time, see `handlers::mod::AuthzStoreImpl::
-///    issue_api_key_secret`). Handled EXCLUSIVELY by [`validate_api_key_context`], unchanged from
+///    issue_api_key_secret`). Handled EXCLUSIVELY by [`validate_api_key_row`] (the body of
+///    `validate_api_key_context`, fed the row the dispatch already read), unchanged from
 ///    before this dispatch existed -- a revoked/expired self-signed API-key JWT still verifies
 ///    fine as a JWT (revocation only flips a DB column, it cannot invalidate an already-issued
 ///    signature), so it is critical this branch never falls through to JWT verification below:
@@ -46,27 +64,192 @@ pub async fn introspect_api_key(
     State(state): State<Arc<OpaState>>,
     Form(input): Form<IntrospectRequest>,
 ) -> Result<axum::response::Response> {
-    let key_hash = hash_api_key(&input.token);
-    let is_api_key_credential = state
+    let response = introspect_token(&state, &input.token, input.client_ip).await?;
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+/// The transport-independent core of [`introspect_api_key`]: the API-key-row-first dispatch
+/// described on that handler, returning the response body rather than an HTTP response so the
+/// Envoy `ext_authz` listener (`crate::extauthz`) resolves a credential through exactly the same
+/// path Authorino does. `ip` is the caller's address when the transport knows it (Envoy's
+/// `CheckRequest` carries the downstream peer; over HTTP it is the optional `client_ip` field),
+/// and is what a key's IP allowlist is checked against.
+///
+/// When `state.introspection_cache` is set, a still-fresh active API-key result is returned
+/// before any of the above runs, and a freshly resolved one is stored -- see
+/// `crate::introspection_cache` for what is cached and how long a revocation can go unseen.
+pub async fn introspect_token(
+    state: &Arc<OpaState>,
+    token: &str,
+    ip: Option<String>,
+) -> Result<IntrospectResponse> {
+    let candidates = state.api_key_hasher.candidates(token);
+    if let Some(cached) = state
+        .introspection_cache
+        .as_ref()
+        .and_then(|cache| cache.get(&candidates[0]))
+    {
+        return Ok(cached);
+    }
+
+    let validation = state
         .repo
-        .find_api_key_validation_by_hash(&key_hash)
-        .await?
-        .is_some();
+        .find_api_key_validation_by_hash(&candidates)
+        .await?;
+    let key_hash = candidates[0].clone();
+    introspect_with_row(state, key_hash, token, validation, ip).await
+}
+
+/// Batch form of [`introspect_api_key`] for gateway sidecars holding many credentials at once.
+/// Every token is resolved exactly as the single endpoint would resolve it, and `results` is in
+/// request order. The `api_keys`-row dispatch costs ONE `api_key_validation` read for the whole
+/// batch (`key_hash = ANY($1)`); only tokens with no row fall through to exchange-token
+/// verification. A failure resolving any one token fails the whole call, as it would have failed
+/// that token's single request.
+#[utoipa::path(
+    post,
+    path = "/v1/authorino/validate/introspect/batch",
+    request_body = BatchIntrospectRequest,
+    responses(
+        (status = 200, body = BatchIntrospectResponse),
+        (status = 400, description = "More than MAX_BATCH_INTROSPECT_TOKENS tokens")
+    ),
+    tag = "authorino"
+)]
+#[instrument(skip(state, input), fields(count = input.tokens.len()))]
+pub async fn introspect_api_keys_batch(
+    State(state): State<Arc<OpaState>>,
+    Json(input): Json<BatchIntrospectRequest>,
+) -> Result<axum::response::Response> {
+    if input.tokens.len() > MAX_BATCH_INTROSPECT_TOKENS {
+        return Err(Error::BadRequest(format!(
+            "at most {MAX_BATCH_INTROSPECT_TOKENS} tokens may be introspected per batch, got {}",
+            input.tokens.len()
+        )));
+    }
+    let results = introspect_tokens(&state, &input.tokens, input.client_ip).await?;
+    Ok((StatusCode::OK, Json(BatchIntrospectResponse { results })).into_response())
+}
+
+/// The transport-independent core of [`introspect_api_keys_batch`], mirroring
+/// [`introspect_token`]: cache first, then one batched row read, then the same per-credential
+/// dispatch, at most [`BATCH_INTROSPECT_CONCURRENCY`] at a time and in request order. `ip` applies to every token, as the whole batch comes from one client.
+pub async fn introspect_tokens(
+    state: &Arc<OpaState>,
+    tokens: &[String],
+    ip: Option<String>,
+) -> Result<Vec<IntrospectResponse>> {
+    let candidates: Vec<Vec<String>> = tokens
+        .iter()
+        .map(|token| state.api_key_hasher.candidates(token))
+        .collect();
+    let cached: Vec<Option<IntrospectResponse>> = candidates
+        .iter()
+        .map(|token_hashes| {
+            state
+                .introspection_cache
+                .as_ref()
+                .and_then(|cache| cache.get(&token_hashes[0]))
+        })
+        .collect();
+
+    let uncached_hashes: Vec<String> = candidates
+        .iter()
+        .zip(&cached)
+        .filter(|(_, hit)| hit.is_none())
+        .flat_map(|(token_hashes, _)| token_hashes.iter().cloned())
+        .collect();
+    let rows: HashMap<String, ApiKeyValidation> = if uncached_hashes.is_empty() {
+        HashMap::new()
+    } else {
+        state
+            .repo
+            .find_api_key_validations_by_hashes(&uncached_hashes)
+            .await?
+            .into_iter()
+            .map(|validation| (validation.key_hash.clone(), validation))
+            .collect()
+    };
+
+    // Collected into a `Vec` first: a lazy `Map` iterator inside `buffered` trips the
+    // higher-ranked `Send` check axum's `Handler` bound applies to this handler's future.
+    let lookups: Vec<_> = cached
+        .into_iter()
+        .zip(candidates)
+        .zip(tokens)
+        .map(|((hit, token_hashes), token)| {
+            // The most current scheme a row is stored under wins, as in the single lookup.
+            let validation = token_hashes
+                .iter()
+                .find_map(|key_hash| rows.get(key_hash))
+                .cloned();
+            let key_hash = token_hashes[0].clone();
+            let ip = ip.clone();
+            async move {
+                match hit {
+                    Some(response) => Ok(response),
+                    None => introspect_with_row(state, key_hash, token, validation, ip).await,
+                }
+            }
+        })
+        .collect();
+    stream::iter(lookups)
+        .buffered(BATCH_INTROSPECT_CONCURRENCY)
+        .try_collect()
+        .await
+}
+
+/// The dispatch described on [`introspect_api_key`], given the `api_key_validation` row already
+/// read for `key_hash` (or its absence). An API-key result is offered to the introspection cache,
+/// which keeps only active ones -- and never one for a key with an IP allowlist: a cache hit is
+/// served before the row is read, so caching it would let the next caller skip the IP check.
+/// Only those cacheable credentials count as a cache miss; an exchange token never could hit.
+async fn introspect_with_row(
+    state: &Arc<OpaState>,
+    key_hash: String,
+    token: &str,
+    validation: Option<ApiKeyValidation>,
+    ip: Option<String>,
+) -> Result<IntrospectResponse> {
+    let Some(validation) = validation else {
+        let response = introspect_exchange_token(state, token).await?;
+        return Ok(apply_budget(state, response).await);
+    };
 
-    if is_api_key_credential {
-        return introspect_api_key_row(&state, &input.token).await;
+    let cache = state
+        .introspection_cache
+        .as_ref()
+        .filter(|_| validation.allowed_cidrs.is_none());
+    if let Some(cache) = cache {
+        cache.record_miss();
+    }
+    let response = introspect_api_key_row(state, validation, &key_hash, ip).await?;
+    let response = apply_budget(state, response).await;
+    if let Some(cache) = cache {
+        cache.insert(key_hash, &response);
     }
+    Ok(response)
+}
 
-    introspect_exchange_token(&state, &input.token).await
+/// Annotates `response` with budget standing when `state.introspection_budget` is set (see
+/// `crate::introspection_budget`). Runs before the cache insert, so the standing is cached along
+/// with the rest of an active result, and an enforced-inactive one is never cached at all.
+async fn apply_budget(state: &Arc<OpaState>, response: IntrospectResponse) -> IntrospectResponse {
+    match &state.introspection_budget {
+        Some(budget) => budget.apply(response, Utc::now()).await,
+        None => response,
+    }
 }
 
 async fn introspect_api_key_row(
     state: &Arc<OpaState>,
-    token: &str,
-) -> Result<axum::response::Response> {
-    let Some(validated) = validate_api_key_context(state, token, None).await? else {
+    validation: ApiKeyValidation,
+    current_hash: &str,
+    ip: Option<String>,
+) -> Result<IntrospectResponse> {
+    let Some(validated) = validate_api_key_row(state, validation, current_hash, ip).await? else {
         tracing::info!(active = false, "api key introspection resolved inactive");
-        return Ok((StatusCode::OK, Json(IntrospectResponse::inactive())).into_response());
+        return Ok(IntrospectResponse::inactive());
     };
 
     tracing::info!(
@@ -87,6 +270,8 @@ async fn introspect_api_key_row(
              as having no limits — reconcile the catalogue with keys still in use)"
         );
     }
+    let (model_policy, allowed_models) =
+        validated.api_key.effective_model_access(&validated.project);
     let response = IntrospectResponse {
         active: true,
         sub: Some(validated.api_key.id.clone()),
@@ -97,15 +282,24 @@ async fn introspect_api_key_row(
         billing_plan: Some(validated.api_key.billing_plan.clone()),
         billing_plan_name: plan.map(|p| p.name.clone()),
         billing_plan_limits: plan.and_then(|p| p.limits.clone()),
-        allowed_models: validated.project.allowed_models.clone(),
-        model_policy: Some(validated.project.model_policy.to_string()),
+        allowed_models,
+        model_policy: Some(model_policy.to_string()),
+        scopes: validated.api_key.scopes.clone(),
         project_quota: validated.project.project_quota.clone(),
         role: validated.owner_role.clone(),
         quota_tier: validated.owner_quota_tier.clone(),
-        exp: validated.api_key.expires_at.map(|value| value.timestamp()),
+        // A key mid-rotation stops at its cut-off, which also caps how long it may be cached.
+        exp: validated
+            .api_key
+            .valid_until()
+            .map(|value| value.timestamp()),
+        budget_tier: None,
+        budget_balance_micros: None,
+        budget_spent_micros: None,
+        budget_exhausted: None,
     };
 
-    Ok((StatusCode::OK, Json(response)).into_response())
+    Ok(response)
 }
 
 /// The exchange-token counterpart of [`introspect_api_key_row`]. No `api_key_id`/`api_key_status`
@@ -116,13 +310,13 @@ async fn introspect_api_key_row(
 async fn introspect_exchange_token(
     state: &Arc<OpaState>,
     token: &str,
-) -> Result<axum::response::Response> {
+) -> Result<IntrospectResponse> {
     let Some(ctx) = resolve_exchange_token_context(state, token).await? else {
         tracing::info!(
             active = false,
             "exchange token introspection resolved inactive"
         );
-        return Ok((StatusCode::OK, Json(IntrospectResponse::inactive())).into_response());
+        return Ok(IntrospectResponse::inactive());
     };
 
     let plan = state.billing.get(&ctx.project.billing_plan);
@@ -146,11 +340,16 @@ async fn introspect_exchange_token(
         billing_plan_limits: plan.and_then(|p| p.limits.clone()),
         allowed_models: ctx.project.allowed_models.clone(),
         model_policy: Some(ctx.project.model_policy.to_string()),
+        scopes: None,
         project_quota: ctx.project.project_quota.clone(),
         role: ctx.role,
         quota_tier: ctx.quota_tier,
         exp: None,
+        budget_tier: None,
+        budget_balance_micros: None,
+        budget_spent_micros: None,
+        budget_exhausted: None,
     };
 
-    Ok((StatusCode::OK, Json(response)).into_response())
+    Ok(response)
 }
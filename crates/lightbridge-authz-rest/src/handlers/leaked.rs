@@ -0,0 +1,147 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use axum::{Json, extract::State, http::StatusCode, response::IntoResponse};
+use chrono::Utc;
+use lightbridge_authz_core::crypto::{ApiKeySecretFormat, verify_api_key_secret};
+use lightbridge_authz_core::error::Error;
+use lightbridge_authz_core::{ApiKeyStatus, ApiKeyValidation, Result};
+use tracing::instrument;
+
+use crate::OpaState;
+use crate::introspection_cache::Invalidation;
+use crate::models::{
+    LeakedCredentialReport, LeakedCredentialReportResponse, LeakedCredentialResult,
+};
+
+/// Upper bound on tokens per [`report_leaked_credentials`] call, matching the batch introspection
+/// cap so one report is a single `ANY($1)` read of a bounded size.
+pub const MAX_LEAKED_CREDENTIALS_PER_REPORT: usize = 100;
+
+/// Leaked-credential report. A secret scanner posts the secrets it found in public; every one that
+/// is a live API key is revoked at once, with `leaked: reported by <source>` recorded as its
+/// `revocation_reason`, and the response says which of them were real.
+///
+/// An `lbk_v2_` secret whose checksum does not verify is answered `real: false` without a lookup.
+/// Anything else is looked up under every hash scheme introspection accepts, in one read for the
+/// whole report. A key that is already revoked (or suspended) reports `real: true, revoked:
+/// false` and is left as it is.
+///
+/// Revocation evicts the key from this replica's introspection cache; other replicas stop
+/// answering from theirs within `introspection_cache.max_staleness_seconds`.
+#[utoipa::path(
+    post,
+    path = "/v1/leaked-credentials",
+    request_body = LeakedCredentialReport,
+    responses(
+        (status = 200, body = LeakedCredentialReportResponse),
+        (status = 400, description = "More than MAX_LEAKED_CREDENTIALS_PER_REPORT tokens")
+    ),
+    tag = "leaked-credentials"
+)]
+#[instrument(skip(state, input), fields(source = %input.source, count = input.tokens.len()))]
+pub async fn report_leaked_credentials(
+    State(state): State<Arc<OpaState>>,
+    Json(input): Json<LeakedCredentialReport>,
+) -> Result<axum::response::Response> {
+    if input.tokens.len() > MAX_LEAKED_CREDENTIALS_PER_REPORT {
+        return Err(Error::BadRequest(format!(
+            "at most {MAX_LEAKED_CREDENTIALS_PER_REPORT} credentials may be reported at once, got {}",
+            input.tokens.len()
+        )));
+    }
+    let results = revoke_leaked_credentials(&state, &input.source, &input.tokens).await?;
+    Ok((
+        StatusCode::OK,
+        Json(LeakedCredentialReportResponse { results }),
+    )
+        .into_response())
+}
+
+/// The core of [`report_leaked_credentials`], one result per token in request order.
+pub async fn revoke_leaked_credentials(
+    state: &Arc<OpaState>,
+    source: &str,
+    tokens: &[String],
+) -> Result<Vec<LeakedCredentialResult>> {
+    let candidates: Vec<Vec<String>> = tokens
+        .iter()
+        .map(|token| match verify_api_key_secret(token) {
+            ApiKeySecretFormat::Malformed => Vec::new(),
+            ApiKeySecretFormat::Checksummed | ApiKeySecretFormat::Unchecked => {
+                state.api_key_hasher.candidates(token)
+            }
+        })
+        .collect();
+    let lookup: Vec<String> = candidates.iter().flatten().cloned().collect();
+    let rows: HashMap<String, ApiKeyValidation> = if lookup.is_empty() {
+        HashMap::new()
+    } else {
+        state
+            .repo
+            .find_api_key_validations_by_hashes(&lookup)
+            .await?
+            .into_iter()
+            .map(|validation| (validation.key_hash.clone(), validation))
+            .collect()
+    };
+
+    let reason = format!("leaked: reported by {source}");
+    let mut revoked_ids = HashSet::new();
+    let mut results = Vec::with_capacity(tokens.len());
+    for token_hashes in &candidates {
+        let Some(validation) = token_hashes.iter().find_map(|key_hash| rows.get(key_hash)) else {
+            results.push(LeakedCredentialResult {
+                real: false,
+                revoked: false,
+            });
+            continue;
+        };
+        // The same secret reported twice in one batch is revoked once, and both entries say so.
+        let revoked = revoked_ids.contains(&validation.api_key_id)
+            || (validation.api_key_status == ApiKeyStatus::Active.to_string()
+                && revoke(state, validation, &reason).await?);
+        if revoked {
+            revoked_ids.insert(validation.api_key_id.clone());
+        }
+        results.push(LeakedCredentialResult {
+            real: true,
+            revoked,
+        });
+    }
+    Ok(results)
+}
+
+/// Revokes one reported key on behalf of its project's owning account. `false` when the key
+/// disappeared between the lookup and the update.
+async fn revoke(
+    state: &Arc<OpaState>,
+    validation: &ApiKeyValidation,
+    reason: &str,
+) -> Result<bool> {
+    match state
+        .repo
+        .set_api_key_status(
+            &validation.account_id,
+            &validation.api_key_id,
+            ApiKeyStatus::Revoked,
+            Some(Utc::now()),
+            Some(reason),
+        )
+        .await
+    {
+        Ok(_) => {}
+        Err(Error::NotFound) => return Ok(false),
+        Err(err) => return Err(err),
+    }
+    if let Some(cache) = &state.introspection_cache {
+        cache.invalidate(&Invalidation::ApiKey(validation.api_key_id.clone()));
+    }
+    tracing::warn!(
+        api_key_id = %validation.api_key_id,
+        project_id = %validation.project_id,
+        reason,
+        "leaked api key revoked"
+    );
+    Ok(true)
+}
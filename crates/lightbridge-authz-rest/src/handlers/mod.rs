@@ -1,6 +1,7 @@
 pub mod exchange_token;
 pub mod idp;
 pub mod introspect;
+pub mod leaked;
 pub mod opa;
 
 use std::sync::Arc;
@@ -12,10 +13,14 @@ use lightbridge_authz_api_key::repo::StoreRepo;
 use lightbridge_authz_core::config::{
     ApiKeyExpiry, Billing, ModelCatalog, Oauth2, Oauth2Issuance, QuotaTiers,
 };
+use lightbridge_authz_core::crypto::{
+    API_KEY_SECRET_PREFIX, LEGACY_API_KEY_SECRET_PREFIX, encode_api_key_secret,
+};
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::{
-    Account, ApiKey, ApiKeySecret, ApiKeyStatus, CreateAccount, CreateApiKey, ModelPolicy, Project,
-    ProjectMember, ResourceStatus, RotateApiKey, hash_api_key,
+    Account, ApiKey, ApiKeyHasher, ApiKeyScope, ApiKeySecret, ApiKeyStatus, AuditEvent,
+    AuditEventFilter, CreateAccount, CreateApiKey, ModelPolicy, Project, ProjectKeyLifecyclePolicy,
+    ProjectMember, ResourceStatus, RotateApiKey,
 };
 use lightbridge_authz_core::{
     db::DbPoolTrait,
@@ -24,6 +29,8 @@ use lightbridge_authz_core::{
 use reqwest::Client;
 use serde::Deserialize;
 
+use crate::introspection_cache::{Invalidation, InvalidationPublisher};
+
 #[derive(Clone)]
 pub struct AuthzStoreImpl {
     repo: Arc<StoreRepo>,
@@ -44,6 +51,14 @@ pub struct AuthzStoreImpl {
     /// `models` above, but unlike those, absent config still resolves to a real value (90 days),
     /// never to "no ceiling" -- see `ApiKeyExpiry`'s own doc comment.
     api_key_expiry: Arc<ApiKeyExpiry>,
+    /// Publishes `introspection_cache::Invalidation`s after each mutation that changes what
+    /// introspection would return for a key. `None` (the default, and always the case on
+    /// `lightbridge-mcp`) publishes nothing; caches then catch up through their own
+    /// `max_staleness_seconds` bound instead.
+    invalidations: Option<InvalidationPublisher>,
+    /// `Config.api_key_hashing`: the scheme `create_api_key`/`rotate_api_key` store new hashes
+    /// under. Defaults to the legacy unpeppered SHA-256.
+    api_key_hasher: Arc<ApiKeyHasher>,
 }
 
 impl std::fmt::Debug for AuthzStoreImpl {
@@ -63,6 +78,8 @@ impl AuthzStoreImpl {
             quota_tiers: Arc::new(QuotaTiers::default()),
             models: Arc::new(ModelCatalog::default()),
             api_key_expiry: Arc::new(ApiKeyExpiry::default()),
+            invalidations: None,
+            api_key_hasher: Arc::new(ApiKeyHasher::default()),
         }
     }
 
@@ -124,15 +141,43 @@ impl AuthzStoreImpl {
             quota_tiers: Arc::new(quota_tiers.clone()),
             models: Arc::new(models.clone()),
             api_key_expiry: Arc::new(api_key_expiry.clone()),
+            invalidations: None,
+            api_key_hasher: Arc::new(ApiKeyHasher::default()),
         })
     }
 
+    /// Publish introspection-cache invalidations through `publisher` after every mutation that
+    /// affects an introspection result. Wired by `start_api_server` only.
+    pub fn with_introspection_invalidation(mut self, publisher: InvalidationPublisher) -> Self {
+        self.invalidations = Some(publisher);
+        self
+    }
+
+    /// Hash new and rotated key secrets under `hasher` (`Config.api_key_hashing`). Wired by
+    /// `start_api_server` and `lightbridge-mcp`; must match what `authz-opa` looks keys up with.
+    pub fn with_api_key_hasher(mut self, hasher: Arc<ApiKeyHasher>) -> Self {
+        self.api_key_hasher = hasher;
+        self
+    }
+
+    /// The hasher new key secrets are stored under, for a caller that also looks keys up.
+    pub fn api_key_hasher(&self) -> Arc<ApiKeyHasher> {
+        self.api_key_hasher.clone()
+    }
+
+    async fn invalidate_introspection(&self, event: Invalidation) {
+        if let Some(publisher) = &self.invalidations {
+            publisher.publish(event).await;
+        }
+    }
+
     async fn issue_api_key_secret(
         &self,
         subject: &str,
         bearer_token: Option<&str>,
         project_id: &str,
         api_key_id: &str,
+        key_models: Option<&[String]>,
         requested_expires_at: Option<DateTime<Utc>>,
     ) -> Result<IssuedSecret> {
         if let Some(signer) = &self.jwt_signer {
@@ -153,7 +198,9 @@ impl AuthzStoreImpl {
                     api_key_id,
                     project_id,
                     &project.account_id,
-                    project.allowed_models.clone(),
+                    key_models
+                        .map(<[String]>::to_vec)
+                        .or_else(|| project.allowed_models.clone()),
                     Utc::now(),
                     requested_expires_at,
                 )
@@ -168,21 +215,109 @@ impl AuthzStoreImpl {
         }
     }
 
+    /// Validates a key-level `allowed_models`/`scopes` pair for `create_api_key`/`rotate_api_key`
+    /// and returns it deduplicated (first occurrence wins, caller's order kept). `None` always
+    /// passes -- the key simply inherits the project. A `Some` list must be non-empty (an empty
+    /// one would be a dead key; revoke instead), every scope must be an `ApiKeyScope` wire value,
+    /// and every model must be both in the configured catalogue (`ModelCatalog::invalid_ids`) and
+    /// reachable under the project's CURRENT `model_policy`: nothing under `deny_all`, only
+    /// `allowed_models` entries under `allowlist`. The stateless checks run first, so a malformed
+    /// request never reaches the database; the project is only read when there is a model list
+    /// to check it against.
+    ///
+    /// Rotation carries the predecessor's restrictions forward through this same gate, so a key
+    /// whose models the project has since dropped fails to rotate rather than minting a successor
+    /// that could never be used -- same reasoning as `validate_expires_at`'s re-check.
+    async fn validate_key_restrictions(
+        &self,
+        subject: &str,
+        project_id: &str,
+        allowed_models: Option<Vec<String>>,
+        scopes: Option<Vec<String>>,
+    ) -> Result<(Option<Vec<String>>, Option<Vec<String>>)> {
+        let allowed_models = allowed_models.map(dedup_in_order);
+        let scopes = scopes.map(dedup_in_order);
+        if let Some(scopes) = &scopes {
+            if scopes.is_empty() {
+                return Err(Error::BadRequest(
+                    "scopes must name at least one scope; omit it to allow all scopes".to_string(),
+                ));
+            }
+            if let Some(unknown) = scopes
+                .iter()
+                .find(|scope| ApiKeyScope::parse_strict(scope).is_none())
+            {
+                return Err(Error::BadRequest(format!(
+                    "unknown scope '{unknown}': must be one of [{}]",
+                    ApiKeyScope::ALL.map(|scope| scope.to_string()).join(", ")
+                )));
+            }
+        }
+        let Some(models) = &allowed_models else {
+            return Ok((allowed_models, scopes));
+        };
+        if models.is_empty() {
+            return Err(Error::BadRequest(
+                "allowedModels must name at least one model; omit it to inherit the project's \
+                 model policy"
+                    .to_string(),
+            ));
+        }
+        let invalid = self.models.invalid_ids(Some(models));
+        if !invalid.is_empty() {
+            return Err(Error::BadRequest(format!(
+                "unknown allowedModels entr{} [{}]: must each be one of the configured models [{}]",
+                if invalid.len() == 1 { "y" } else { "ies" },
+                invalid.join(", "),
+                self.models.model_ids().join(", ")
+            )));
+        }
+        let project = self
+            .repo
+            .get_project(subject, project_id)
+            .await?
+            .ok_or(Error::NotFound)?;
+        match project.model_policy {
+            ModelPolicy::AllowAll => {}
+            ModelPolicy::DenyAll => {
+                return Err(Error::BadRequest(
+                    "allowedModels cannot be set: the project's modelPolicy is deny_all"
+                        .to_string(),
+                ));
+            }
+            ModelPolicy::Allowlist => {
+                let project_models = project.allowed_models.as_deref().unwrap_or_default();
+                let outside: Vec<&str> = models
+                    .iter()
+                    .filter(|model| !project_models.contains(model))
+                    .map(String::as_str)
+                    .collect();
+                if !outside.is_empty() {
+                    return Err(Error::BadRequest(format!(
+                        "allowedModels [{}] are outside the project's allowlist [{}]",
+                        outside.join(", "),
+                        project_models.join(", ")
+                    )));
+                }
+            }
+        }
+        Ok((allowed_models, scopes))
+    }
+
+    /// A fresh opaque secret in the checksummed `lbk_v2_` format (`crypto::encode_api_key_secret`).
     fn generate_secret() -> Result<String> {
         let mut bytes = [0u8; 32];
         fill(&mut bytes)
             .map_err(|e| lightbridge_authz_core::error::Error::Database(e.to_string()))?;
-        let encoded = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
-        Ok(format!("lbk_secret_{}", encoded))
+        Ok(encode_api_key_secret(&bytes))
     }
 
     fn key_prefix(secret: &str) -> String {
-        const SECRET_PREFIX: &str = "lbk_secret_";
-        if let Some(after_prefix) = secret.strip_prefix(SECRET_PREFIX) {
-            after_prefix.chars().take(8).collect()
-        } else {
-            secret.chars().take(8).collect()
-        }
+        let after_prefix = secret
+            .strip_prefix(API_KEY_SECRET_PREFIX)
+            .or_else(|| secret.strip_prefix(LEGACY_API_KEY_SECRET_PREFIX))
+            .unwrap_or(secret);
+        after_prefix.chars().take(8).collect()
     }
 
     async fn issue_secret(
@@ -369,6 +504,18 @@ fn resolve_issued_expires_at(
     }
 }
 
+/// Drops repeated entries from a key-level `allowed_models`/`scopes` list, keeping the first
+/// occurrence so the stored order is the caller's.
+fn dedup_in_order(values: Vec<String>) -> Vec<String> {
+    let mut seen = Vec::with_capacity(values.len());
+    for value in values {
+        if !seen.contains(&value) {
+            seen.push(value);
+        }
+    }
+    seen
+}
+
 /// The single gate every `api_keys.expires_at` write funnels through (lightbridge-authz#395: "all
 /// api-keys created from our system MUST have an expiry date... max 90 days"). Called by
 /// `create_api_key` and `rotate_api_key` before any DB write or external issuance call, so a
@@ -500,6 +647,9 @@ impl AuthzStoreImpl {
         let now = Utc::now();
         let requested_expires_at =
             validate_expires_at(input.expires_at, now, &self.api_key_expiry)?;
+        let (allowed_models, scopes) = self
+            .validate_key_restrictions(subject, project_id, input.allowed_models, input.scopes)
+            .await?;
         let id = cuid2();
         let issued = self
             .issue_api_key_secret(
@@ -507,10 +657,11 @@ impl AuthzStoreImpl {
                 bearer_token,
                 project_id,
                 &id,
+                allowed_models.as_deref(),
                 Some(requested_expires_at),
             )
             .await?;
-        let key_hash = hash_api_key(&issued.secret);
+        let key_hash = self.api_key_hasher.hash(&issued.secret);
         let key_prefix = Self::key_prefix(&issued.secret);
         let expires_at = resolve_issued_expires_at(Some(requested_expires_at), issued.expires_at);
         let row = lightbridge_authz_api_key::entities::new_api_key_row::NewApiKeyRow {
@@ -526,6 +677,9 @@ impl AuthzStoreImpl {
             last_ip: None,
             revoked_at: None,
             billing_plan: input.billing_plan,
+            allowed_models,
+            scopes,
+            allowed_cidrs: None,
         };
         let api_key = self.repo.create_api_key(subject, row).await?;
         tracing::info!(
@@ -560,31 +714,47 @@ impl AuthzStoreImpl {
     /// Suspend an account (`status = 'suspended'`). Backs `disableAccount`. Thin wrapper over
     /// `StoreRepo::set_account_status` (membership enforced in SQL).
     pub async fn disable_account(&self, subject: &str, account_id: &str) -> Result<Account> {
-        self.repo
+        let account = self
+            .repo
             .set_account_status(subject, account_id, ResourceStatus::Suspended)
-            .await
+            .await?;
+        self.invalidate_introspection(Invalidation::Account(account.id.clone()))
+            .await;
+        Ok(account)
     }
 
     /// Reactivate a suspended account (`status = 'active'`). Backs `enableAccount`.
     pub async fn enable_account(&self, subject: &str, account_id: &str) -> Result<Account> {
-        self.repo
+        let account = self
+            .repo
             .set_account_status(subject, account_id, ResourceStatus::Active)
-            .await
+            .await?;
+        self.invalidate_introspection(Invalidation::Account(account.id.clone()))
+            .await;
+        Ok(account)
     }
 
     /// Suspend a project (`status = 'suspended'`). Backs `disableProject`. Thin wrapper over
     /// `StoreRepo::set_project_status` (membership enforced in SQL).
     pub async fn disable_project(&self, subject: &str, project_id: &str) -> Result<Project> {
-        self.repo
+        let project = self
+            .repo
             .set_project_status(subject, project_id, ResourceStatus::Suspended)
-            .await
+            .await?;
+        self.invalidate_introspection(Invalidation::Project(project.id.clone()))
+            .await;
+        Ok(project)
     }
 
     /// Reactivate a suspended project (`status = 'active'`). Backs `enableProject`.
     pub async fn enable_project(&self, subject: &str, project_id: &str) -> Result<Project> {
-        self.repo
+        let project = self
+            .repo
             .set_project_status(subject, project_id, ResourceStatus::Active)
-            .await
+            .await?;
+        self.invalidate_introspection(Invalidation::Project(project.id.clone()))
+            .await;
+        Ok(project)
     }
 
     /// Revokes every active refresh-token session for `subject`, returning how many were
@@ -626,9 +796,13 @@ impl AuthzStoreImpl {
                 self.quota_tiers.tier_ids().join(", ")
             )));
         }
-        self.repo
+        let project = self
+            .repo
             .set_project_quota(subject, project_id, project_quota)
-            .await
+            .await?;
+        self.invalidate_introspection(Invalidation::Project(project.id.clone()))
+            .await;
+        Ok(project)
     }
 
     /// Sets `Project.allowedModels` post-creation/update. Backs `setProjectAllowedModels` (#415,
@@ -657,9 +831,13 @@ impl AuthzStoreImpl {
                 self.models.model_ids().join(", ")
             )));
         }
-        self.repo
+        let project = self
+            .repo
             .set_project_allowed_models(subject, project_id, allowed_models)
-            .await
+            .await?;
+        self.invalidate_introspection(Invalidation::Project(project.id.clone()))
+            .await;
+        Ok(project)
     }
 
     /// Sets `Project.modelPolicy` (ADR-0018 Decision 5 follow-up). Backs `setProjectModelPolicy`:
@@ -695,11 +873,66 @@ impl AuthzStoreImpl {
                 "unknown modelPolicy '{model_policy}': must be one of allow_all, allowlist, deny_all"
             ))
         })?;
-        self.repo
+        let project = self
+            .repo
             .set_project_model_policy(subject, project_id, &parsed.to_string())
+            .await?;
+        self.invalidate_introspection(Invalidation::Project(project.id.clone()))
+            .await;
+        Ok(project)
+    }
+
+    /// A project's dormant-key policy (defaults when never set). Backs
+    /// `getProjectKeyLifecyclePolicy`.
+    pub async fn get_project_key_lifecycle_policy(
+        &self,
+        subject: &str,
+        project_id: &str,
+    ) -> Result<ProjectKeyLifecyclePolicy> {
+        self.repo
+            .get_project_key_lifecycle_policy(subject, project_id)
+            .await
+    }
+
+    /// Replaces a project's dormant-key policy. Backs `setProjectKeyLifecyclePolicy`.
+    /// `dormant_after_days` must be a positive `i32` when set; anything else is `BadRequest` rather
+    /// than a CHECK-constraint failure surfacing as a server error. Nothing is invalidated: the
+    /// policy only matters to the lifecycle job, which reads it fresh on every run.
+    pub async fn set_project_key_lifecycle_policy(
+        &self,
+        subject: &str,
+        project_id: &str,
+        dormant_after_days: Option<i64>,
+        auto_revoke_dormant: bool,
+    ) -> Result<ProjectKeyLifecyclePolicy> {
+        let dormant_after_days = dormant_after_days
+            .map(|days| {
+                i32::try_from(days)
+                    .ok()
+                    .filter(|days| *days > 0)
+                    .ok_or_else(|| {
+                        Error::BadRequest(format!(
+                            "dormantAfterDays must be a positive number of days, got {days}"
+                        ))
+                    })
+            })
+            .transpose()?;
+        self.repo
+            .set_project_key_lifecycle_policy(
+                subject,
+                project_id,
+                dormant_after_days,
+                auto_revoke_dormant,
+            )
             .await
     }
 
+    /// A page of the `audit_events` trail. Backs `listAuditEvents`; the permission gate is the
+    /// whole authorization, so no subject is taken.
+    pub async fn list_audit_events(&self, filter: &AuditEventFilter) -> Result<Vec<AuditEvent>> {
+        self.repo.list_audit_events(filter).await
+    }
+
     /// Revoke an API key (business-state transition to `revoked`). Backs `revokeApiKey`.
     pub async fn revoke_api_key(&self, subject: &str, key_id: &str) -> Result<ApiKey> {
         let api_key = self
@@ -710,6 +943,7 @@ impl AuthzStoreImpl {
                 ApiKeyStatus::Revoked,
                 Some(Utc::now()),
                 None,
+                None,
             )
             .await?;
         tracing::info!(
@@ -719,6 +953,57 @@ impl AuthzStoreImpl {
             api_key_id = %api_key.id,
             "api key revoked"
         );
+        self.invalidate_introspection(Invalidation::ApiKey(api_key.id.clone()))
+            .await;
+        Ok(api_key)
+    }
+
+    /// Replace (or, with `None`, clear) an API key's client-IP allowlist. Backs
+    /// `setApiKeyAllowedCidrs`. Entries are parsed as CIDRs or bare addresses and stored in
+    /// canonical network form (`10.1.2.3/8` -> `10.0.0.0/8`, bare `192.0.2.7` -> `192.0.2.7/32`),
+    /// deduplicated in order. An empty list is rejected rather than read as "deny everything" --
+    /// revoking the key is the way to do that. Lead-gated in SQL.
+    pub async fn set_api_key_allowed_cidrs(
+        &self,
+        subject: &str,
+        key_id: &str,
+        allowed_cidrs: Option<Vec<String>>,
+    ) -> Result<ApiKey> {
+        let allowed_cidrs = match allowed_cidrs {
+            None => None,
+            Some(entries) => {
+                if entries.is_empty() {
+                    return Err(Error::BadRequest(
+                        "allowedCidrs must not be empty; pass null to clear the allowlist"
+                            .to_string(),
+                    ));
+                }
+                let mut normalized = Vec::with_capacity(entries.len());
+                for entry in &entries {
+                    let Some(cidr) = lightbridge_authz_core::cidr::normalize_cidr(entry) else {
+                        return Err(Error::BadRequest(format!(
+                            "invalid CIDR in allowedCidrs: {entry}"
+                        )));
+                    };
+                    normalized.push(cidr);
+                }
+                Some(dedup_in_order(normalized))
+            }
+        };
+        let api_key = self
+            .repo
+            .set_api_key_allowed_cidrs(subject, key_id, allowed_cidrs)
+            .await?;
+        tracing::info!(
+            operation = "set_api_key_allowed_cidrs",
+            subject = %subject,
+            project_id = %api_key.project_id,
+            api_key_id = %api_key.id,
+            allowed_cidrs = ?api_key.allowed_cidrs,
+            "api key ip allowlist updated"
+        );
+        self.invalidate_introspection(Invalidation::ApiKey(api_key.id.clone()))
+            .await;
         Ok(api_key)
     }
 
@@ -731,9 +1016,12 @@ impl AuthzStoreImpl {
         target_account_id: &str,
         role: Option<&str>,
     ) -> Result<Project> {
-        self.repo
+        let project = self
+            .repo
             .add_project_member(subject, project_id, target_account_id, role)
-            .await
+            .await?;
+        self.invalidate_roster_change(&project).await;
+        Ok(project)
     }
 
     /// Remove an account from a project's roster. Backs `removeProjectMember`. Lead-gated in SQL.
@@ -746,9 +1034,12 @@ impl AuthzStoreImpl {
         project_id: &str,
         target_account_id: &str,
     ) -> Result<Project> {
-        self.repo
+        let project = self
+            .repo
             .remove_project_member(subject, project_id, target_account_id)
-            .await
+            .await?;
+        self.invalidate_roster_change(&project).await;
+        Ok(project)
     }
 
     /// Change a roster member's role (`lead`/`member`). Backs `setProjectMemberRole`. Lead-gated in
@@ -760,9 +1051,12 @@ impl AuthzStoreImpl {
         target_account_id: &str,
         role: &str,
     ) -> Result<Project> {
-        self.repo
+        let project = self
+            .repo
             .set_project_member_role(subject, project_id, target_account_id, role)
-            .await
+            .await?;
+        self.invalidate_roster_change(&project).await;
+        Ok(project)
     }
 
     /// Set a roster member's per-project spending ceiling. Backs `setProjectMemberQuotaTier`.
@@ -785,9 +1079,20 @@ impl AuthzStoreImpl {
                 self.quota_tiers.tier_ids().join(", ")
             )));
         }
-        self.repo
+        let project = self
+            .repo
             .set_project_member_quota_tier(subject, project_id, target_account_id, quota_tier)
-            .await
+            .await?;
+        self.invalidate_roster_change(&project).await;
+        Ok(project)
+    }
+
+    /// A roster change moves some key owner's `role`/`quota_tier`, which cached introspections
+    /// carry as `x-project-role`/`x-quota-tier`. Which keys that owner holds is not known here,
+    /// so the whole project is evicted -- roster edits are rare next to introspections.
+    async fn invalidate_roster_change(&self, project: &Project) {
+        self.invalidate_introspection(Invalidation::Project(project.id.clone()))
+            .await;
     }
 
     /// List a project's roster. Backs `listProjectRoster`, the roster's only read path (the four
@@ -805,12 +1110,19 @@ impl AuthzStoreImpl {
     /// `deleteAccountPermanently`. Since ADR-0006 the authorization is simply "the caller is this
     /// account" — there is no role concept left to gate on.
     pub async fn delete_account(&self, subject: &str, account_id: &str) -> Result<Account> {
-        self.repo.delete_account(subject, account_id).await
+        let account = self.repo.delete_account(subject, account_id).await?;
+        self.invalidate_introspection(Invalidation::Account(account.id.clone()))
+            .await;
+        Ok(account)
     }
 
     /// Rotate an API key: issue a fresh secret (generation/hashing unchanged from before the
     /// migration) and, in one hand-written transaction (`StoreRepo::rotate_api_key_transaction`,
-    /// NOT `run_in_tx`), revoke the old key and insert its successor. Backs `rotateApiKey`. The
+    /// NOT `run_in_tx`), retire the old key and insert its successor, linked both ways. Without a
+    /// `grace_period_seconds` the old key is revoked at once; with one it stays `active` until
+    /// `rotating_until` (capped at its own expiry) so in-flight deployments can switch over, and
+    /// the `api_key_rotation` sweeper revokes it afterwards. A key that already has a successor is
+    /// a `Conflict`: rotate the successor instead. Backs `rotateApiKey`. The
     /// successor's `expires_at` -- normally the preserved existing value, since
     /// `RotateApiKeyInput` carries no `expiresAt` of its own -- is re-validated against the
     /// operator-configured `ApiKeyExpiry` ceiling the same way `create_api_key` validates it
@@ -829,15 +1141,22 @@ impl AuthzStoreImpl {
             .await?
             .ok_or_else(|| lightbridge_authz_core::error::Error::NotFound)?;
 
+        if let Some(successor_id) = &existing.successor_id {
+            return Err(lightbridge_authz_core::error::Error::Conflict(format!(
+                "api key {key_id} was already rotated; rotate its successor {successor_id} instead"
+            )));
+        }
+
         let now = Utc::now();
-        let (status, revoked_at, old_expires_at) =
+        // A grace period keeps the old key `active` next to its successor until the cut-off (never
+        // past its own expiry); `api_key_rotation` revokes it once the cut-off has passed.
+        let (status, revoked_at, rotating_until) =
             if let Some(grace) = input.grace_period_seconds.filter(|v| *v > 0) {
-                let grace_exp = now + Duration::seconds(grace);
-                let expires_at = match existing.expires_at {
-                    Some(existing_exp) if existing_exp < grace_exp => Some(existing_exp),
-                    _ => Some(grace_exp),
-                };
-                (ApiKeyStatus::Active, None, expires_at)
+                let cut_off = now + Duration::seconds(grace);
+                let cut_off = existing
+                    .expires_at
+                    .map_or(cut_off, |expires_at| expires_at.min(cut_off));
+                (ApiKeyStatus::Active, None, Some(cut_off))
             } else {
                 (ApiKeyStatus::Revoked, Some(now), None)
             };
@@ -847,16 +1166,25 @@ impl AuthzStoreImpl {
             resolve_rotated_expires_at(input.expires_at, existing.expires_at);
         let requested_expires_at =
             validate_expires_at(requested_expires_at, now, &self.api_key_expiry)?;
+        let (allowed_models, scopes) = self
+            .validate_key_restrictions(
+                subject,
+                &existing.project_id,
+                input.allowed_models.or(existing.allowed_models),
+                input.scopes.or(existing.scopes),
+            )
+            .await?;
         let issued = self
             .issue_api_key_secret(
                 subject,
                 bearer_token,
                 existing.project_id.as_str(),
                 &new_id,
+                allowed_models.as_deref(),
                 Some(requested_expires_at),
             )
             .await?;
-        let key_hash = hash_api_key(&issued.secret);
+        let key_hash = self.api_key_hasher.hash(&issued.secret);
         let key_prefix = Self::key_prefix(&issued.secret);
         let expires_at = resolve_issued_expires_at(Some(requested_expires_at), issued.expires_at);
         let row = lightbridge_authz_api_key::entities::new_api_key_row::NewApiKeyRow {
@@ -872,10 +1200,13 @@ impl AuthzStoreImpl {
             last_ip: None,
             revoked_at: None,
             billing_plan: existing.billing_plan,
+            allowed_models,
+            scopes,
+            allowed_cidrs: existing.allowed_cidrs,
         };
         let api_key = self
             .repo
-            .rotate_api_key_transaction(subject, key_id, status, revoked_at, old_expires_at, row)
+            .rotate_api_key_transaction(subject, key_id, status, revoked_at, rotating_until, row)
             .await?;
         tracing::info!(
             operation = "rotate_api_key",
@@ -884,8 +1215,13 @@ impl AuthzStoreImpl {
             previous_api_key_id = %key_id,
             api_key_id = %api_key.id,
             expires_at = ?api_key.expires_at,
+            previous_rotating_until = ?rotating_until,
             "api key rotated and new secret issued"
         );
+        // The predecessor is either revoked or now carries a rotation cut-off that caps its cached
+        // result's lifetime -- either way the cached result is now wrong.
+        self.invalidate_introspection(Invalidation::ApiKey(key_id.to_string()))
+            .await;
         Ok(ApiKeySecret {
             api_key,
             secret: issued.secret,
@@ -921,6 +1257,8 @@ mod tests {
             audience: None,
             signing: None,
             token_exchange: None,
+            device_authorization: None,
+            authorization_code: None,
             rbac: Default::default(),
             clients: Vec::new(),
             issuance: None,
@@ -992,6 +1330,8 @@ mod tests {
                         name: "k".to_string(),
                         expires_at: None,
                         billing_plan: plan.to_string(),
+                        allowed_models: None,
+                        scopes: None,
                     },
                 )
                 .await
@@ -1003,6 +1343,101 @@ mod tests {
         }
     }
 
+    /// A store whose billing plan and expiry checks pass, so `create_api_key` reaches the
+    /// key-level restriction checks. Anything that gets past those hits the dead `lazy_pool()`.
+    fn restriction_store() -> AuthzStoreImpl {
+        use lightbridge_authz_core::config::{BillingPlan, ModelCatalogEntry};
+
+        AuthzStoreImpl::with_pool(lazy_pool())
+            .with_billing(Billing {
+                plans: vec![BillingPlan {
+                    id: "pro".to_string(),
+                    name: "Pro".to_string(),
+                    limits: None,
+                }],
+            })
+            .with_model_catalog(ModelCatalog {
+                models: vec![ModelCatalogEntry {
+                    id: "gpt-4.1-mini".to_string(),
+                    name: "GPT-4.1 Mini".to_string(),
+                }],
+            })
+    }
+
+    fn restricted_create(
+        allowed_models: Option<Vec<&str>>,
+        scopes: Option<Vec<&str>>,
+    ) -> lightbridge_authz_core::CreateApiKey {
+        let owned = |values: Vec<&str>| values.into_iter().map(str::to_string).collect();
+        lightbridge_authz_core::CreateApiKey {
+            name: "ci".to_string(),
+            expires_at: Some(Utc::now() + Duration::days(1)),
+            billing_plan: "pro".to_string(),
+            allowed_models: allowed_models.map(owned),
+            scopes: scopes.map(owned),
+        }
+    }
+
+    // Key-level restrictions: the stateless half of `validate_key_restrictions` must reject a
+    // malformed request before the project read, i.e. without touching the (dead) pool.
+    #[tokio::test]
+    async fn create_api_key_rejects_unknown_or_empty_restrictions_before_any_db_access() {
+        let store = restriction_store();
+        let cases = [
+            (
+                None,
+                Some(vec!["inference", "admin"]),
+                "unknown scope 'admin'",
+            ),
+            (None, Some(vec![]), "scopes must name at least one scope"),
+            (
+                Some(vec![]),
+                None,
+                "allowedModels must name at least one model",
+            ),
+            (
+                Some(vec!["gpt-4.1-mini", "gtp-typo"]),
+                None,
+                "unknown allowedModels entry [gtp-typo]",
+            ),
+        ];
+        for (models, scopes, expected) in cases {
+            let err = store
+                .create_api_key("subject", None, "proj", restricted_create(models, scopes))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, lightbridge_authz_core::error::Error::BadRequest(ref m) if m.contains(expected)),
+                "expected BadRequest containing {expected:?}, got: {err}"
+            );
+        }
+    }
+
+    /// Valid restrictions get past the stateless checks and go on to read the project -- here the
+    /// dead pool, so anything but `BadRequest` proves they were accepted.
+    #[tokio::test]
+    async fn create_api_key_accepts_known_restrictions_and_reaches_the_project_read() {
+        let err = restriction_store()
+            .create_api_key(
+                "subject",
+                None,
+                "proj",
+                restricted_create(Some(vec!["gpt-4.1-mini"]), Some(vec!["batch", "batch"])),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            !matches!(err, lightbridge_authz_core::error::Error::BadRequest(_)),
+            "valid restrictions should not be rejected, got: {err}"
+        );
+    }
+
+    #[test]
+    fn dedup_in_order_keeps_the_first_occurrence() {
+        let values = ["b", "a", "b", "c", "a"].map(str::to_string).to_vec();
+        assert_eq!(super::dedup_in_order(values), ["b", "a", "c"]);
+    }
+
     // #177: `defaultQuota` validation on `createAccount`, same shape as the billing-plan check
     // above -- `AuthzStoreImpl::create_account` rejects a value absent from a non-empty configured
     // catalogue before the DB write. Uses `lazy_pool()` (a dead connection) exactly like the
@@ -1427,6 +1862,8 @@ mod tests {
             audience: None,
             signing: None,
             token_exchange: None,
+            device_authorization: None,
+            authorization_code: None,
             rbac: Default::default(),
             clients: Vec::new(),
             issuance: Some(Oauth2Issuance {
@@ -1468,6 +1905,8 @@ mod tests {
             audience: None,
             signing: None,
             token_exchange: None,
+            device_authorization: None,
+            authorization_code: None,
             rbac: Default::default(),
             clients: Vec::new(),
             issuance: Some(Oauth2Issuance {
@@ -1496,6 +1935,8 @@ mod tests {
             audience: None,
             signing: None,
             token_exchange: None,
+            device_authorization: None,
+            authorization_code: None,
             rbac: Default::default(),
             clients: Vec::new(),
             issuance: None,
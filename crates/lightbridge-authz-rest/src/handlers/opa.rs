@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
-use lightbridge_authz_core::{Result, error::Error, hash_api_key};
+use lightbridge_authz_core::{Result, error::Error};
 use tracing::instrument;
 
 use crate::OpaState;
+use crate::metrics::record_introspection_decision;
 
 /// Context for a validated API key.
 ///
@@ -32,9 +33,10 @@ pub struct ValidatedApiKeyContext {
 /// status cascade (revoked key, expired key, suspended project, suspended account) is resolved by
 /// the database, so disabling an account/project instantly invalidates every key beneath it.
 ///
-/// Three round trips total, deliberately: the indexed view read above, the usage-telemetry UPDATE
-/// (which returns the api-key row, so it doubles as that fetch), and the project read that supplies
-/// `allowed_models`/`project_quota`. Authorino caches the result for 30s per `jti`, so this runs
+/// Three round trips total, deliberately: the indexed view read above, the api-key row read, and
+/// the project read that supplies `allowed_models`/`project_quota`. Usage telemetry
+/// (`last_used_at`/`last_ip`) is not one of them -- it is coalesced in memory and written in
+/// batches (`api_key_usage`). Authorino caches the result for 30s per `jti`, so this runs
 /// roughly twice a minute per active key per replica — cheap enough that keeping the database
 /// authoritative is worth more than shaving it further. Anything moved into JWT claims instead
 /// stops reflecting operator changes until the token is re-minted.
@@ -44,10 +46,10 @@ pub async fn validate_api_key_context(
     raw_api_key: &str,
     ip: Option<String>,
 ) -> Result<Option<ValidatedApiKeyContext>> {
-    let key_hash = hash_api_key(raw_api_key);
+    let candidates = state.api_key_hasher.candidates(raw_api_key);
     let Some(validation) = state
         .repo
-        .find_api_key_validation_by_hash(&key_hash)
+        .find_api_key_validation_by_hash(&candidates)
         .await?
     else {
         tracing::info!(
@@ -55,9 +57,29 @@ pub async fn validate_api_key_context(
             reason = "not_found",
             "api key validation failed"
         );
+        record_introspection_decision(false, "not_found");
         return Ok(None);
     };
 
+    validate_api_key_row(state, validation, &candidates[0], ip).await
+}
+
+/// The half of [`validate_api_key_context`] after the `api_key_validation` read: gate on the
+/// row's `effective_status` (narrowed by the key's IP allowlist, if any, against `ip`), then
+/// load the key and its project and record the use in `OpaState::api_key_usage`. Split out so a
+/// caller that already holds the row -- `handlers::introspect`, which reads it once to dispatch
+/// and, for a batch, reads every row in one query -- does not read it a second time.
+///
+/// `current_hash` is the presented secret under the current hashing scheme
+/// (`ApiKeyHasher::hash`). An active row that matched under an older one is re-hashed to it.
+pub async fn validate_api_key_row(
+    state: &Arc<OpaState>,
+    mut validation: lightbridge_authz_core::ApiKeyValidation,
+    current_hash: &str,
+    ip: Option<String>,
+) -> Result<Option<ValidatedApiKeyContext>> {
+    validation.apply_ip_allowlist(ip.as_deref());
+    record_introspection_decision(validation.is_active(), &validation.effective_status);
     if !validation.is_active() {
         tracing::info!(
             active = false,
@@ -69,11 +91,16 @@ pub async fn validate_api_key_context(
         );
         return Ok(None);
     }
+    if validation.key_hash != current_hash {
+        upgrade_key_hash(state, &validation, current_hash).await;
+    }
 
     let api_key = state
         .repo
-        .record_api_key_usage(&validation.api_key_id, ip)
-        .await?;
+        .get_api_key_by_id(&validation.api_key_id)
+        .await?
+        .ok_or_else(|| Error::NotFound)?;
+    state.api_key_usage.record(&api_key.id, ip);
     let project = state
         .repo
         .get_project_by_id(&validation.project_id)
@@ -97,3 +124,28 @@ pub async fn validate_api_key_context(
         owner_quota_tier: validation.owner_quota_tier.clone(),
     }))
 }
+
+/// Re-stores a key found under a previous pepper or the legacy SHA-256 under `current_hash`. A
+/// failure only means the upgrade is retried on the key's next use, so it never fails validation.
+async fn upgrade_key_hash(
+    state: &Arc<OpaState>,
+    validation: &lightbridge_authz_core::ApiKeyValidation,
+    current_hash: &str,
+) {
+    match state
+        .repo
+        .upgrade_api_key_hash(&validation.api_key_id, &validation.key_hash, current_hash)
+        .await
+    {
+        Ok(upgraded) => tracing::info!(
+            api_key_id = %validation.api_key_id,
+            upgraded,
+            "api key hash upgraded to the current scheme"
+        ),
+        Err(err) => tracing::warn!(
+            api_key_id = %validation.api_key_id,
+            error = %err,
+            "api key hash upgrade failed; retrying on next use"
+        ),
+    }
+}
@@ -0,0 +1,303 @@
+//! Budget standing on introspection responses (`Config.introspection_budget`).
+//!
+//! The budget ledger (`lightbridge_authz_budget::BudgetRepo`) and the refill flow built on it were
+//! advisory until this module: a gateway had no way to see an account's budget, let alone be
+//! stopped by it. When configured, every active introspection -- API key or exchange token, over
+//! `authz-opa` or `authz-extauthz` -- is annotated with the owning account's standing for the
+//! current [`Period`]:
+//!
+//! - `budget_tier`: the account's current rung (`BudgetRepo::current_tier`), resolved against the
+//!   active budget policy's tier ladder, with the introspected `billing_plan` picking the starting
+//!   rung for an account with no grant yet this period.
+//! - `budget_balance_micros`: the expiry- and revocation-aware effective balance
+//!   (`BudgetRepo::effective_balance`).
+//! - `budget_spent_micros`: summed spend from the usage service ([`SpendReader`]).
+//! - `budget_exhausted`: spend strictly exceeds the effective balance.
+//!
+//! With `enforce: true`, an exhausted account's credentials introspect `active: false`.
+//!
+//! What deliberately does NOT count as exhausted:
+//!
+//! - [`Spend::Unavailable`] (the usage service is down, slow, or has no rows for the account).
+//!   Unknown spend routes refill decisions to manual review, the strictest branch there; on this
+//!   path the strictest branch would deny every request the moment the usage service blipped, so
+//!   unknown spend is reported by omitting `budget_spent_micros`, never by denying.
+//! - A ledger read that fails. The balance/tier fields are omitted and a warning logged.
+//!
+//! An account with no grant for the current period has an effective balance of zero, so with
+//! `enforce: true` its first recorded spend exhausts it. `authz-budget`'s period rollover
+//! ([`crate::budget_rollover`]) writes every active account's opening grant within minutes of a
+//! month boundary; enforcing without `authz-budget` running means running
+//! `lightbridge-authz budget rollover` on a schedule instead.
+//!
+//! The tier ladder is read from the active budget policy revision loaded at startup, the same
+//! way `authz-idp` loads it for token minting, so a rung appended by a later activation is only
+//! labelled here after a restart; until then a grant for it resolves to the lowest rung.
+//!
+//! When `introspection_cache` is also configured, the standing is cached with the rest of an
+//! active result, so a newly exhausted account keeps passing for at most
+//! `max_staleness_seconds`, and a refill is likewise seen only once that entry expires.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use lightbridge_authz_budget::repo::BudgetRepo;
+use lightbridge_authz_budget::{
+    BudgetError, BudgetTier, Period, PolicyEngine, PolicyStore, Spend, SpendReader, TierLadder,
+};
+use lightbridge_authz_core::async_trait;
+use lightbridge_authz_core::config::{IntrospectionBudget, UsageServiceClient};
+use lightbridge_authz_core::db::DbPoolTrait;
+use lightbridge_authz_core::error::{Error, Result};
+
+use crate::models::IntrospectResponse;
+
+/// The two budget-ledger reads introspection needs, split out of [`BudgetRepo`] so tests can
+/// stand in for the database.
+#[async_trait]
+pub trait BudgetLedger: Send + Sync {
+    async fn current_tier(
+        &self,
+        budget_account_id: &str,
+        period: &Period,
+        ladder: &TierLadder,
+        billing_plan: Option<&str>,
+    ) -> std::result::Result<BudgetTier, BudgetError>;
+    async fn effective_balance(
+        &self,
+        budget_account_id: &str,
+        period: &Period,
+        as_of: DateTime<Utc>,
+    ) -> std::result::Result<i64, BudgetError>;
+}
+
+#[async_trait]
+impl BudgetLedger for BudgetRepo {
+    async fn current_tier(
+        &self,
+        budget_account_id: &str,
+        period: &Period,
+        ladder: &TierLadder,
+        billing_plan: Option<&str>,
+    ) -> std::result::Result<BudgetTier, BudgetError> {
+        BudgetRepo::current_tier(self, budget_account_id, period, ladder, billing_plan).await
+    }
+
+    async fn effective_balance(
+        &self,
+        budget_account_id: &str,
+        period: &Period,
+        as_of: DateTime<Utc>,
+    ) -> std::result::Result<i64, BudgetError> {
+        BudgetRepo::effective_balance(self, budget_account_id, period, as_of).await
+    }
+}
+
+/// An account's budget standing for one period. Each field is `None` when its source could not be
+/// read; `exhausted` is only ever true when both the balance and a known spend were read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetStanding {
+    pub tier: Option<String>,
+    pub balance_micros: Option<i64>,
+    pub spent_micros: Option<i64>,
+    pub exhausted: bool,
+}
+
+/// Resolves [`BudgetStanding`] for introspection and applies it to a response.
+pub struct IntrospectionBudgetStore {
+    ledger: Arc<dyn BudgetLedger>,
+    spend_reader: Arc<dyn SpendReader>,
+    /// Source of the tier ladder `budget_tier` is resolved against.
+    policy_engine: Arc<dyn PolicyEngine>,
+    enforce: bool,
+}
+
+impl std::fmt::Debug for IntrospectionBudgetStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IntrospectionBudgetStore")
+            .field("spend_reader", &self.spend_reader)
+            .field("enforce", &self.enforce)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IntrospectionBudgetStore {
+    pub fn new(
+        ledger: Arc<dyn BudgetLedger>,
+        spend_reader: Arc<dyn SpendReader>,
+        policy_engine: Arc<dyn PolicyEngine>,
+        enforce: bool,
+    ) -> Self {
+        Self {
+            ledger,
+            spend_reader,
+            policy_engine,
+            enforce,
+        }
+    }
+
+    /// Reads `account_id`'s standing for the period containing `now`. `billing_plan` only picks
+    /// the starting rung for an account with no grant yet. The ledger reads run alongside the
+    /// usage-service call rather than after it, so the added latency is the slower of the two,
+    /// not their sum.
+    pub async fn standing(
+        &self,
+        account_id: &str,
+        billing_plan: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> BudgetStanding {
+        let period = Period::current(now);
+        let ladder = self.policy_engine.tier_ladder();
+        let ledger = async {
+            let tier = self
+                .ledger
+                .current_tier(account_id, &period, &ladder, billing_plan)
+                .await?;
+            let balance = self
+                .ledger
+                .effective_balance(account_id, &period, now)
+                .await?;
+            Ok::<_, BudgetError>((tier, balance))
+        };
+        let (ledger, spend) = tokio::join!(
+            ledger,
+            self.spend_reader.spend_for_account(account_id, &period)
+        );
+
+        let (tier, balance_micros) = match ledger {
+            Ok((tier, balance)) => (Some(tier.label().to_string()), Some(balance)),
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    account_id,
+                    period = %period,
+                    "budget ledger read failed during introspection; budget tier/balance omitted \
+                     and the account is not treated as exhausted"
+                );
+                (None, None)
+            }
+        };
+        let spent_micros = match spend {
+            Ok(Spend::Known(spent)) => Some(spent),
+            Ok(Spend::Unavailable) => None,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    account_id,
+                    period = %period,
+                    "spend read failed during introspection; budget spend omitted and the account \
+                     is not treated as exhausted"
+                );
+                None
+            }
+        };
+        let exhausted = matches!(
+            (balance_micros, spent_micros),
+            (Some(balance), Some(spent)) if spent > balance
+        );
+
+        BudgetStanding {
+            tier,
+            balance_micros,
+            spent_micros,
+            exhausted,
+        }
+    }
+
+    /// Annotates an active `response` with its account's standing, or -- under `enforce` --
+    /// replaces it with [`IntrospectResponse::inactive`] when that account is exhausted. An
+    /// inactive response, or one without an `account_id`, is returned unchanged.
+    pub async fn apply(
+        &self,
+        mut response: IntrospectResponse,
+        now: DateTime<Utc>,
+    ) -> IntrospectResponse {
+        let Some(account_id) = response.account_id.as_deref().filter(|_| response.active) else {
+            return response;
+        };
+
+        let standing = self
+            .standing(account_id, response.billing_plan.as_deref(), now)
+            .await;
+        if standing.exhausted && self.enforce {
+            tracing::info!(
+                active = false,
+                account_id,
+                balance_micros = standing.balance_micros,
+                spent_micros = standing.spent_micros,
+                "introspection resolved inactive: budget exhausted for the current period"
+            );
+            return IntrospectResponse::inactive();
+        }
+
+        response.budget_tier = standing.tier;
+        response.budget_balance_micros = standing.balance_micros;
+        response.budget_spent_micros = standing.spent_micros;
+        response.budget_exhausted = Some(standing.exhausted);
+        response
+    }
+}
+
+/// Builds the store for `authz-opa`/`authz-extauthz` from config. `None` when
+/// `introspection_budget` is unset. Enforcing without `usage_service` fails startup: every spend
+/// read would be unavailable, so enforcement would silently never fire. When enabled, the active
+/// budget policy is loaded off `pool` for its tier ladder, and failing to load it fails startup.
+pub async fn build_introspection_budget(
+    introspection_budget: &Option<IntrospectionBudget>,
+    usage_service: &Option<UsageServiceClient>,
+    pool: Arc<dyn DbPoolTrait>,
+    server: &str,
+) -> Result<Option<Arc<IntrospectionBudgetStore>>> {
+    let Some(config) = introspection_budget else {
+        return Ok(None);
+    };
+
+    let spend_reader: Arc<dyn SpendReader> = match usage_service {
+        Some(usage_service) => Arc::new(
+            lightbridge_authz_budget::UsageServiceSpendReader::new(
+                usage_service.base_url.clone(),
+                usage_service.insecure_skip_verify,
+                usage_service.ca_bundle_path.as_deref(),
+                usage_service.client_cert_path.as_deref(),
+                usage_service.client_key_path.as_deref(),
+                std::time::Duration::from_millis(usage_service.timeout_ms),
+            )
+            .map_err(|e| {
+                Error::Server(format!("failed to build usage-service spend reader: {e}"))
+            })?,
+        ),
+        None if config.enforce => {
+            return Err(Error::Server(
+                "introspection_budget.enforce requires usage_service to be configured".to_string(),
+            ));
+        }
+        None => {
+            tracing::warn!(
+                server,
+                "introspection_budget enabled without usage_service -- budget_spent_micros is \
+                 never reported and no account is ever reported exhausted"
+            );
+            Arc::new(lightbridge_authz_budget::UnavailableSpendReader)
+        }
+    };
+
+    let policy_store = PolicyStore::load_active_from_db(
+        Arc::clone(&pool),
+        crate::BUDGET_POLICY_SET_ID,
+        crate::BUDGET_POLICY_EVALUATION_BUDGET,
+    )
+    .await
+    .map_err(|e| Error::Server(format!("failed to load active budget policy: {e}")))?;
+
+    tracing::info!(
+        server,
+        enforce = config.enforce,
+        "introspection budget standing enabled"
+    );
+    Ok(Some(Arc::new(IntrospectionBudgetStore::new(
+        Arc::new(BudgetRepo::new(pool)),
+        spend_reader,
+        policy_store.engine(),
+        config.enforce,
+    ))))
+}
@@ -0,0 +1,413 @@
+//! Bounded in-process cache of active API-key introspection results (`Config.introspection_cache`).
+//!
+//! `handlers::introspect::introspect_token` -- shared by `authz-opa` and `authz-extauthz` -- runs
+//! `find_api_key_validation_by_hash` plus a key and a project read for every active key it sees,
+//! at gateway request rates. This cache short-circuits that for a key resolved active within the
+//! last `max_staleness_seconds`, keyed by the same `key_hash` the `api_keys` table is.
+//!
+//! What is cached, and what is deliberately not:
+//!
+//! - Only **active API-key** results. An inactive result carries no project/account id, so it
+//!   could not be evicted when a suspension is lifted; and an exchange-token result is already
+//!   verified without a per-call `api_keys` read worth saving. Both always go to Postgres.
+//! - An entry never outlives the key's own `exp` -- see [`IntrospectionCacheStore::insert`].
+//! - A hit skips `api_key_usage::ApiKeyUsageTracker::record`, so `last_used_at`/`last_ip` advance
+//!   at most once per `max_staleness_seconds` per key and process rather than on every call.
+//!
+//! Invalidation has two layers, and correctness only depends on the second:
+//!
+//! 1. **Event-driven.** `authz-api` (and `lightbridge-mcp`, when it has `redis` configured)
+//!    publishes an [`Invalidation`] on [`INVALIDATION_CHANNEL`] after `revoke_api_key`,
+//!    `rotate_api_key`, `set_api_key_allowed_cidrs`, `disable/enable_project`,
+//!    `disable/enable_account`, `delete_account`, `set_project_model_policy`,
+//!    `set_project_allowed_models`, `set_project_quota` and every project-roster change commit
+//!    (see `handlers::AuthzStoreImpl`). Each `authz-opa`/
+//!    `authz-extauthz` process subscribed through [`spawn_invalidation_listener`] evicts every
+//!    entry for that key, project or account. The listener drops the WHOLE cache whenever its
+//!    subscription is (re)established or lost, and on any payload it cannot parse, since events
+//!    published while it was not listening are gone.
+//! 2. **Time-bounded.** Every entry expires `max_staleness_seconds` after it was written,
+//!    regardless of events. Publishing is best-effort, a `lightbridge-mcp` without `redis`
+//!    publishes nothing, and a process without `redis` configured never subscribes -- in all of
+//!    those cases a revocation is still honoured within that bound.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use futures_util::StreamExt;
+use lightbridge_authz_core::config::{IntrospectionCache, Redis};
+use lightbridge_authz_core::error::{Error, Result};
+use opentelemetry::metrics::Counter;
+use redis::AsyncCommands;
+use redis::aio::ConnectionManager;
+use serde::{Deserialize, Serialize};
+
+use crate::models::IntrospectResponse;
+use crate::redis_tls::build_redis_client;
+
+/// Redis pub/sub channel carrying [`Invalidation`] events as JSON.
+pub const INVALIDATION_CHANNEL: &str = "lightbridge-authz:introspection-invalidate";
+
+/// Reconnect backoff bounds for [`spawn_invalidation_listener`]. The cache is empty for as long as
+/// the listener is disconnected (it clears on every disconnect), so a slow reconnect only costs
+/// hit rate, never correctness.
+const LISTENER_MIN_BACKOFF: Duration = Duration::from_millis(250);
+const LISTENER_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A state change that invalidates cached introspection results. Serialized as
+/// `{"kind":"api_key","id":"..."}` on [`INVALIDATION_CHANNEL`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum Invalidation {
+    /// Evict the entry for this API key id (revocation, rotation).
+    ApiKey(String),
+    /// Evict every entry for keys in this project (status, model policy, allowed models, quota).
+    Project(String),
+    /// Evict every entry for keys under this account (status, deletion).
+    Account(String),
+}
+
+struct CachedIntrospection {
+    response: IntrospectResponse,
+    deadline: Instant,
+}
+
+impl CachedIntrospection {
+    fn matches(&self, event: &Invalidation) -> bool {
+        let (field, id) = match event {
+            Invalidation::ApiKey(id) => (&self.response.api_key_id, id),
+            Invalidation::Project(id) => (&self.response.project_id, id),
+            Invalidation::Account(id) => (&self.response.account_id, id),
+        };
+        field.as_deref() == Some(id.as_str())
+    }
+}
+
+/// The entries plus an index of them ordered by deadline, kept in step under one lock. The index
+/// is what makes eviction at `max_entries` a pop of the soonest-expiring entry (`O(log n)`)
+/// rather than a scan of the whole map on every insert into a full cache.
+#[derive(Default)]
+struct Entries {
+    by_hash: HashMap<String, CachedIntrospection>,
+    by_deadline: BTreeSet<(Instant, String)>,
+}
+
+impl Entries {
+    fn remove(&mut self, key_hash: &str) {
+        if let Some(entry) = self.by_hash.remove(key_hash) {
+            self.by_deadline
+                .remove(&(entry.deadline, key_hash.to_string()));
+        }
+    }
+
+    /// Removes the soonest-expiring entry -- an already-expired one whenever there is any.
+    /// `false` once the index is empty.
+    fn evict_first(&mut self) -> bool {
+        let Some((_, key_hash)) = self.by_deadline.pop_first() else {
+            return false;
+        };
+        self.by_hash.remove(&key_hash);
+        true
+    }
+
+    fn clear(&mut self) {
+        self.by_hash.clear();
+        self.by_deadline.clear();
+    }
+}
+
+/// The cache itself. One per process, shared through `OpaState::introspection_cache`.
+pub struct IntrospectionCacheStore {
+    max_staleness: Duration,
+    max_entries: usize,
+    entries: Mutex<Entries>,
+    hits: Counter<u64>,
+    misses: Counter<u64>,
+}
+
+impl std::fmt::Debug for IntrospectionCacheStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IntrospectionCacheStore")
+            .field("max_staleness", &self.max_staleness)
+            .field("max_entries", &self.max_entries)
+            .finish()
+    }
+}
+
+impl IntrospectionCacheStore {
+    /// Builds an empty cache. `max_staleness` is taken as a `Duration` (not seconds) so tests can
+    /// exercise expiry without sleeping for whole seconds; [`Self::from_config`] is the
+    /// production constructor.
+    pub fn new(max_staleness: Duration, max_entries: usize) -> Self {
+        let meter = lightbridge_authz_core::metrics::meter();
+        Self {
+            max_staleness,
+            max_entries,
+            entries: Mutex::new(Entries::default()),
+            hits: meter
+                .u64_counter("authz_introspection_cache_hits")
+                .with_description("Introspections answered from the in-process cache")
+                .build(),
+            misses: meter
+                .u64_counter("authz_introspection_cache_misses")
+                .with_description(
+                    "Introspections of a cacheable credential that had to read Postgres",
+                )
+                .build(),
+        }
+    }
+
+    pub fn from_config(config: &IntrospectionCache) -> Result<Self> {
+        config.validate()?;
+        Ok(Self::new(
+            Duration::from_secs(config.max_staleness_seconds),
+            config.max_entries,
+        ))
+    }
+
+    /// Returns the cached result for `key_hash` if it is still within its deadline, counting a
+    /// hit. A miss is NOT counted here: the caller only learns after the `api_keys` read whether
+    /// the credential could have been cached at all (an exchange token or an IP-restricted key
+    /// never can), and reports it through [`Self::record_miss`] if so.
+    pub fn get(&self, key_hash: &str) -> Option<IntrospectResponse> {
+        let now = Instant::now();
+        let mut entries = self.lock();
+        let hit = match entries.by_hash.get(key_hash) {
+            Some(entry) if entry.deadline > now => Some(entry.response.clone()),
+            Some(_) => {
+                entries.remove(key_hash);
+                None
+            }
+            None => None,
+        };
+        drop(entries);
+
+        if hit.is_some() {
+            self.hits.add(1, &[]);
+        }
+        hit
+    }
+
+    /// Counts a lookup that missed for a credential this cache could have answered.
+    pub fn record_miss(&self) {
+        self.misses.add(1, &[]);
+    }
+
+    /// Caches `response` under `key_hash` until `max_staleness` from now or the key's own `exp`,
+    /// whichever is sooner. Inactive responses and responses without an `api_key_id` are ignored
+    /// -- see the module doc comment for why only active API-key results are cached.
+    pub fn insert(&self, key_hash: String, response: &IntrospectResponse) {
+        if !response.active || response.api_key_id.is_none() {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut ttl = self.max_staleness;
+        if let Some(exp) = response.exp {
+            let remaining = exp.saturating_sub(Utc::now().timestamp());
+            if remaining <= 0 {
+                return;
+            }
+            ttl = ttl.min(Duration::from_secs(remaining as u64));
+        }
+
+        let deadline = now + ttl;
+        let mut entries = self.lock();
+        entries.remove(&key_hash);
+        while entries.by_hash.len() >= self.max_entries.max(1) && entries.evict_first() {}
+        entries.by_deadline.insert((deadline, key_hash.clone()));
+        entries.by_hash.insert(
+            key_hash,
+            CachedIntrospection {
+                response: response.clone(),
+                deadline,
+            },
+        );
+    }
+
+    /// Evicts every entry `event` applies to, returning how many were removed. A full scan, but
+    /// one per published mutation, not per introspection.
+    pub fn invalidate(&self, event: &Invalidation) -> usize {
+        let mut entries = self.lock();
+        let matched: Vec<String> = entries
+            .by_hash
+            .iter()
+            .filter(|(_, entry)| entry.matches(event))
+            .map(|(key_hash, _)| key_hash.clone())
+            .collect();
+        for key_hash in &matched {
+            entries.remove(key_hash);
+        }
+        matched.len()
+    }
+
+    /// Drops every entry.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().by_hash.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        // A poisoned lock only means another thread panicked mid-update; every entry is still a
+        // complete, deadline-bounded value, so keep serving. At worst the index holds a stale
+        // pair whose later eviction is a no-op on the map.
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Builds the cache for `authz-opa`/`authz-extauthz` from config. `None` when
+/// `introspection_cache` is unset (caching off). When `redis` is also configured, spawns the
+/// invalidation listener; without it, eviction relies on `max_staleness_seconds` alone, which is
+/// logged at startup so the weaker guarantee is never silent.
+pub fn build_introspection_cache(
+    introspection_cache: &Option<IntrospectionCache>,
+    redis: &Option<Redis>,
+    server: &str,
+) -> Result<Option<Arc<IntrospectionCacheStore>>> {
+    let Some(config) = introspection_cache else {
+        return Ok(None);
+    };
+    let cache = Arc::new(IntrospectionCacheStore::from_config(config)?);
+
+    match redis {
+        Some(redis) => {
+            let client = build_redis_client(&redis.url, redis.ca_bundle_path.as_deref())?;
+            spawn_invalidation_listener(cache.clone(), client);
+        }
+        None => tracing::warn!(
+            server,
+            max_staleness_seconds = config.max_staleness_seconds,
+            "introspection cache enabled without redis -- revocations and suspensions take effect \
+             only once cached entries expire"
+        ),
+    }
+
+    tracing::info!(
+        server,
+        max_staleness_seconds = config.max_staleness_seconds,
+        max_entries = config.max_entries,
+        "introspection cache enabled"
+    );
+    Ok(Some(cache))
+}
+
+/// Subscribes to [`INVALIDATION_CHANNEL`] in a background task for the life of the process,
+/// reconnecting with capped exponential backoff. See the module doc comment for why the whole
+/// cache is cleared on every (re)subscribe and disconnect.
+pub fn spawn_invalidation_listener(
+    cache: Arc<IntrospectionCacheStore>,
+    client: redis::Client,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut backoff = LISTENER_MIN_BACKOFF;
+        loop {
+            match listen(&cache, &client, &mut backoff).await {
+                Ok(()) => tracing::warn!("introspection invalidation subscription closed"),
+                Err(err) => tracing::warn!(
+                    error = %err,
+                    "introspection invalidation subscription failed"
+                ),
+            }
+            cache.clear();
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(LISTENER_MAX_BACKOFF);
+        }
+    })
+}
+
+async fn listen(
+    cache: &IntrospectionCacheStore,
+    client: &redis::Client,
+    backoff: &mut Duration,
+) -> redis::RedisResult<()> {
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(INVALIDATION_CHANNEL).await?;
+    cache.clear();
+    *backoff = LISTENER_MIN_BACKOFF;
+    tracing::info!(
+        channel = INVALIDATION_CHANNEL,
+        "subscribed to introspection invalidations"
+    );
+
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        let event = message
+            .get_payload::<String>()
+            .ok()
+            .and_then(|payload| serde_json::from_str::<Invalidation>(&payload).ok());
+        match event {
+            Some(event) => {
+                let evicted = cache.invalidate(&event);
+                tracing::debug!(?event, evicted, "introspection cache invalidated");
+            }
+            None => {
+                tracing::warn!("unparseable introspection invalidation; clearing cache");
+                cache.clear();
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `authz-api`'s side of the channel: publishes [`Invalidation`]s after a mutation commits.
+#[derive(Clone)]
+pub struct InvalidationPublisher {
+    manager: ConnectionManager,
+}
+
+impl std::fmt::Debug for InvalidationPublisher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InvalidationPublisher").finish()
+    }
+}
+
+impl InvalidationPublisher {
+    /// Lazy, like `RedisClientAssertionStore::connect`: no connection is made until the first
+    /// publish, so this never blocks or fails server startup on a not-yet-reachable Redis.
+    pub fn connect(redis_url: &str, ca_bundle_path: Option<&str>) -> Result<Self> {
+        let client = build_redis_client(redis_url, ca_bundle_path)?;
+        let manager = client
+            .get_connection_manager_lazy(redis::aio::ConnectionManagerConfig::default())
+            .map_err(|e| {
+                Error::Server(format!(
+                    "failed to build redis connection manager for introspection invalidation: {e}"
+                ))
+            })?;
+        Ok(Self { manager })
+    }
+
+    /// Best-effort: a failure is logged and swallowed. The mutation has already committed, and
+    /// every subscriber still stops serving the stale entry within `max_staleness_seconds`, so
+    /// failing the caller's request here would report an error for a change that did happen.
+    pub async fn publish(&self, event: Invalidation) {
+        let payload = match serde_json::to_string(&event) {
+            Ok(payload) => payload,
+            Err(err) => {
+                tracing::warn!(error = %err, ?event, "failed to encode introspection invalidation");
+                return;
+            }
+        };
+        let mut manager = self.manager.clone();
+        if let Err(err) = manager
+            .publish::<_, _, ()>(INVALIDATION_CHANNEL, payload)
+            .await
+        {
+            tracing::warn!(
+                error = %err,
+                ?event,
+                "failed to publish introspection invalidation; subscribers fall back to expiry"
+            );
+        }
+    }
+}
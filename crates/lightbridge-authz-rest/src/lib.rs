@@ -1,19 +1,33 @@
 use axum::{Json, Router, http::StatusCode, routing::get};
+use lightbridge_authz_core::audit::{self, AuditContext};
 use lightbridge_authz_core::{
-    Account, ApiKey, ApiKeySecret, CreateAccount, CreateApiKey, Project, ProjectMember,
-    RotateApiKey, async_trait,
+    Account, ApiKey, ApiKeyHasher, ApiKeySecret, AuditEvent, AuditEventFilter, CreateAccount,
+    CreateApiKey, Project, ProjectKeyLifecyclePolicy, ProjectMember, RotateApiKey, async_trait,
     config::{
-        ApiKeyExpiry, ApiServer, BasicAuth, Billing, BudgetServer, IdpServer, ModelCatalog, Oauth2,
-        OauthClientType, OpaServer, QuotaTiers, Redis, UsageServiceClient,
+        ApiKeyExpiry, ApiKeyHashing, ApiKeyLifecycle, ApiServer, BasicAuth, Billing, BudgetServer,
+        IdpServer, IntrospectionBudget, IntrospectionCache, ModelCatalog, Oauth2, OauthClientType,
+        OpaServer, QuotaTiers, Redis, UsageServiceClient,
     },
     db::{DbPoolTrait, is_database_ready},
     error::{Error, Result},
     server::{dev_cors_enabled, serve_tls},
 };
 
+pub mod api_key_lifecycle;
+pub mod api_key_rotation;
+pub mod api_key_usage;
 pub mod auth_provider;
+pub mod authorize;
+mod browser;
+pub mod budget_rollover;
 pub mod codec;
+pub mod device_authorization;
+pub mod extauthz;
 pub mod handlers;
+pub mod introspection_budget;
+pub mod introspection_cache;
+pub mod key_encryption;
+pub mod metrics;
 pub mod middleware;
 pub mod models;
 pub mod oauth2_op;
@@ -22,6 +36,7 @@ pub mod redis_tls;
 pub mod routers;
 pub mod rpc_authorize;
 pub mod signing;
+pub mod signing_rotation;
 pub mod token_exchange;
 
 use auth_provider::{ACCESS_TOKEN_CONTEXT_KEY, CratestackAuthProvider};
@@ -37,6 +52,7 @@ use cratestack::{
     CratestackContext, CratestackError, DEFAULT_BODY_LIMIT_BYTES, SqlxIdempotencyStore, Value,
 };
 use lightbridge_authz_api::schema;
+use lightbridge_authz_api_key::entities::api_key_row::ApiKeyUsage;
 use lightbridge_authz_api_key::repo::StoreRepo;
 use lightbridge_authz_bearer::{BearerTokenService, BearerTokenServiceTrait};
 use serde::{Deserialize, Serialize};
@@ -52,8 +68,9 @@ const IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 3600);
 /// limiting (Redis-backed)"). Generous burst with steady refill; tune via deployment as needed.
 const RATE_LIMIT_BURST: u32 = 120;
 const RATE_LIMIT_REFILL_PER_SECOND: f64 = 60.0;
-/// The node-count evaluation budget passed to the [`lightbridge_authz_budget::RuleDataEngine`]
-/// this server's [`lightbridge_authz_budget::PolicyStore`] wraps. See
+/// The node-count evaluation budget passed to every [`lightbridge_authz_budget::RuleDataEngine`]
+/// this server's [`lightbridge_authz_budget::PolicyStore`] builds (OPA-Wasm revisions run under
+/// [`lightbridge_authz_budget::OPA_WASM_EVALUATION_FUEL`] instead). See
 /// [`lightbridge_authz_budget::RuleDataEngine`]'s own doc comment for why this is a deterministic
 /// node-count budget rather than a wall-clock request timeout.
 const BUDGET_POLICY_EVALUATION_BUDGET: usize = 10_000;
@@ -81,19 +98,57 @@ pub struct OpaState {
     /// see that function's doc comment. `None` when `oauth2.type` is `external` (no self-signing
     /// at all) or when `oauth2.signing.audience` is left unconfigured under `type: self`.
     pub api_key_audience: Option<String>,
+    /// Active-API-key introspection cache (`Config.introspection_cache`), consulted by
+    /// `handlers::introspect::introspect_token` before any repository read. `None` disables
+    /// caching entirely -- every introspection reads Postgres, as it always did.
+    pub introspection_cache: Option<Arc<introspection_cache::IntrospectionCacheStore>>,
+    /// Budget standing on introspection (`Config.introspection_budget`), applied by
+    /// `handlers::introspect` to every active result. `None` leaves responses without budget
+    /// fields and never consults the budget ledger or usage service.
+    pub introspection_budget: Option<Arc<introspection_budget::IntrospectionBudgetStore>>,
+    /// `Config.api_key_hashing`: turns a presented secret into the `key_hash` candidates to look
+    /// up, and the current-scheme hash a legacy match is upgraded to.
+    pub api_key_hasher: Arc<ApiKeyHasher>,
+    /// Where `handlers::opa::validate_api_key_row` records each validated key's use; written to
+    /// `api_keys` in batches by `api_key_usage::spawn_api_key_usage_flusher`.
+    pub api_key_usage: Arc<api_key_usage::ApiKeyUsageTracker>,
 }
 
 #[async_trait]
 pub trait OpaRepoTrait: Send + Sync {
-    async fn record_api_key_usage(
+    /// Key `key_id` regardless of caller -- see `StoreRepo::get_api_key_by_id`.
+    async fn get_api_key_by_id(
         &self,
         key_id: &str,
-        ip: Option<String>,
-    ) -> Result<lightbridge_authz_core::ApiKey>;
+    ) -> Result<Option<lightbridge_authz_core::ApiKey>>;
+    /// Writes a batch of coalesced `last_used_at`/`last_ip` updates -- see
+    /// `StoreRepo::record_api_key_usage_batch`.
+    async fn record_api_key_usage_batch(&self, usages: &[ApiKeyUsage]) -> Result<u64>;
+    /// The `api_key_validation` row matching the earliest of `key_hashes` -- see
+    /// `StoreRepo::find_api_key_validation_by_hash`.
     async fn find_api_key_validation_by_hash(
         &self,
-        key_hash: &str,
+        key_hashes: &[String],
     ) -> Result<Option<lightbridge_authz_core::ApiKeyValidation>>;
+    /// Re-stores key `id`'s hash as `new_hash` if it is still `old_hash` -- see
+    /// `StoreRepo::upgrade_api_key_hash`.
+    async fn upgrade_api_key_hash(&self, id: &str, old_hash: &str, new_hash: &str) -> Result<bool>;
+    /// `StoreRepo::set_api_key_status` without an expiry change. Used by
+    /// `handlers::leaked::report_leaked_credentials` to revoke a reported key and record why.
+    async fn set_api_key_status(
+        &self,
+        subject: &str,
+        key_id: &str,
+        status: lightbridge_authz_core::ApiKeyStatus,
+        revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+        revocation_reason: Option<&str>,
+    ) -> Result<lightbridge_authz_core::ApiKey>;
+    /// Every `api_key_validation` row whose `key_hash` is in `key_hashes`, in one read -- see
+    /// `StoreRepo::find_api_key_validations_by_hashes`. Backs batch introspection.
+    async fn find_api_key_validations_by_hashes(
+        &self,
+        key_hashes: &[String],
+    ) -> Result<Vec<lightbridge_authz_core::ApiKeyValidation>>;
     async fn get_project(&self, subject: &str, project_id: &str) -> Result<Option<Project>>;
     async fn get_account(&self, subject: &str, account_id: &str) -> Result<Option<Account>>;
     async fn get_project_by_id(&self, project_id: &str) -> Result<Option<Project>>;
@@ -128,19 +183,53 @@ pub trait OpaRepoTrait: Send + Sync {
 
 #[async_trait]
 impl OpaRepoTrait for StoreRepo {
-    async fn record_api_key_usage(
+    async fn get_api_key_by_id(
         &self,
         key_id: &str,
-        ip: Option<String>,
-    ) -> Result<lightbridge_authz_core::ApiKey> {
-        StoreRepo::record_api_key_usage(self, key_id, ip).await
+    ) -> Result<Option<lightbridge_authz_core::ApiKey>> {
+        StoreRepo::get_api_key_by_id(self, key_id).await
+    }
+
+    async fn record_api_key_usage_batch(&self, usages: &[ApiKeyUsage]) -> Result<u64> {
+        StoreRepo::record_api_key_usage_batch(self, usages).await
     }
 
     async fn find_api_key_validation_by_hash(
         &self,
-        key_hash: &str,
+        key_hashes: &[String],
     ) -> Result<Option<lightbridge_authz_core::ApiKeyValidation>> {
-        StoreRepo::find_api_key_validation_by_hash(self, key_hash).await
+        StoreRepo::find_api_key_validation_by_hash(self, key_hashes).await
+    }
+
+    async fn upgrade_api_key_hash(&self, id: &str, old_hash: &str, new_hash: &str) -> Result<bool> {
+        StoreRepo::upgrade_api_key_hash(self, id, old_hash, new_hash).await
+    }
+
+    async fn set_api_key_status(
+        &self,
+        subject: &str,
+        key_id: &str,
+        status: lightbridge_authz_core::ApiKeyStatus,
+        revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+        revocation_reason: Option<&str>,
+    ) -> Result<lightbridge_authz_core::ApiKey> {
+        StoreRepo::set_api_key_status(
+            self,
+            subject,
+            key_id,
+            status,
+            revoked_at,
+            None,
+            revocation_reason,
+        )
+        .await
+    }
+
+    async fn find_api_key_validations_by_hashes(
+        &self,
+        key_hashes: &[String],
+    ) -> Result<Vec<lightbridge_authz_core::ApiKeyValidation>> {
+        StoreRepo::find_api_key_validations_by_hashes(self, key_hashes).await
     }
 
     async fn get_project(&self, subject: &str, project_id: &str) -> Result<Option<Project>> {
@@ -357,8 +446,8 @@ fn to_schema_budget_balance(
 /// Maps a domain [`lightbridge_authz_budget::RefillStatus`] into the schema's wire
 /// `MyBudgetRefillLadder` shape (see `authz.cstack`'s `type MyBudgetRefillLadder` doc comment).
 /// `budget_account_id`/`period` are threaded through from the call site rather than carried on
-/// `RefillStatus` itself -- the domain type only needs to answer "what amounts are offered", not
-/// echo back the request that produced it.
+/// `RefillStatus` itself -- the domain type only needs to answer "what amounts are offered, on
+/// which ladder", not echo back the request that produced it.
 fn to_schema_my_budget_refill_ladder(
     budget_account_id: String,
     period: String,
@@ -372,6 +461,15 @@ fn to_schema_my_budget_refill_ladder(
             .into_iter()
             .map(|amount| amount.to_string())
             .collect(),
+        tiers: status
+            .tier_ladder
+            .tiers()
+            .iter()
+            .map(|tier| schema::BudgetTierRung {
+                label: tier.label().to_string(),
+                amountMicros: tier.amount().get().to_string(),
+            })
+            .collect(),
     }
 }
 
@@ -426,6 +524,59 @@ fn subject_from_ctx(ctx: &CratestackContext) -> Option<String> {
     }
 }
 
+/// Runs a mutation procedure's body inside an [`audit::scope`] naming its caller, `op_id` and
+/// request, so the `audit_events` trigger attributes every row the body's repository transactions
+/// write (`lightbridge_authz_core::audit`). A missing subject is recorded as empty; the body
+/// rejects that case itself before writing anything.
+fn audited<F: core::future::Future>(
+    ctx: &CratestackContext,
+    op_id: &'static str,
+    body: F,
+) -> impl core::future::Future<Output = F::Output> {
+    let context = AuditContext::new(
+        subject_from_ctx(ctx).unwrap_or_default(),
+        op_id,
+        ctx.request_id().map(str::to_owned),
+    );
+    audit::scope(context, body)
+}
+
+/// Default/max page size for `listAuditEvents`, the same procedure-layer bounds as
+/// [`DEFAULT_BUDGET_GRANTS_PAGE_SIZE`]/[`MAX_BUDGET_GRANTS_PAGE_SIZE`].
+const DEFAULT_AUDIT_EVENTS_PAGE_SIZE: i64 = 20;
+const MAX_AUDIT_EVENTS_PAGE_SIZE: i64 = 50;
+
+fn to_schema_audit_event_page(events: Vec<AuditEvent>, page_size: i64) -> schema::AuditEventPage {
+    let next_cursor = if events.len() == usize::try_from(page_size).unwrap_or(usize::MAX) {
+        events.last().map(|e| e.created_at)
+    } else {
+        None
+    };
+
+    schema::AuditEventPage {
+        entries: events
+            .into_iter()
+            .map(|e| schema::AuditEventEntry {
+                id: e.id,
+                actorSubject: e.actor_subject,
+                opId: e.op_id,
+                requestId: e.request_id,
+                targetType: e.target_type,
+                targetId: e.target_id,
+                action: e.action,
+                before: e
+                    .before
+                    .map(|v| cratestack::Json(json_to_cratestack_value(v))),
+                after: e
+                    .after
+                    .map(|v| cratestack::Json(json_to_cratestack_value(v))),
+                createdAt: e.created_at,
+            })
+            .collect(),
+        nextCursor: next_cursor,
+    }
+}
+
 /// The caller's raw access token, stashed into the context by [`CratestackAuthProvider`] so the
 /// rotate procedure's downstream secret issuance can reuse it (email profile / token exchange).
 fn access_token_from_ctx(ctx: &CratestackContext) -> Option<String> {
@@ -449,11 +600,22 @@ fn to_schema_api_key(k: ApiKey) -> schema::ApiKey {
         lastUsedAt: k.last_used_at,
         lastIp: k.last_ip,
         revokedAt: k.revoked_at,
+        rotatingUntil: k.rotating_until,
+        successorId: k.successor_id,
+        predecessorId: k.predecessor_id,
         deletedAt: None,
         billingPlan: k.billing_plan,
+        allowedModels: k.allowed_models.map(string_list_to_json),
+        scopes: k.scopes.map(string_list_to_json),
+        allowedCidrs: k.allowed_cidrs.map(string_list_to_json),
     }
 }
 
+/// Lifts a string list (`allowedModels`, `scopes`, `allowedCidrs`) into cratestack's `Json` wrapper.
+fn string_list_to_json(values: Vec<String>) -> cratestack::Json<Value> {
+    cratestack::Json(json_to_cratestack_value(serde_json::json!(values)))
+}
+
 fn to_schema_account(a: Account) -> schema::Account {
     schema::Account {
         createdAt: a.created_at,
@@ -545,10 +707,36 @@ fn allowed_models_from_json_arg(value: Option<cratestack::Json<Value>>) -> Optio
     }
 }
 
+/// Reads a key-level restriction list (`createApiKey`/`rotateApiKey`'s `allowedModels`/`scopes`,
+/// `setApiKeyAllowedCidrs`'s `allowedCidrs`). Unlike [`allowed_models_from_json_arg`] this is
+/// strict: a non-array shape or a non-string entry is a `BadRequest`, never read as `null` -- on
+/// these fields `null` means "no key-level restriction", so the permissive reading would turn a
+/// malformed request for a restriction into a key without one.
+fn restriction_list_from_json_arg(
+    value: Option<cratestack::Json<Value>>,
+    field: &str,
+) -> std::result::Result<Option<Vec<String>>, CratestackError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let invalid =
+        || CratestackError::BadRequest(format!("{field} must be an array of strings or null"));
+    match cratestack_value_to_json(value.0) {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                serde_json::Value::String(s) => Ok(s),
+                _ => Err(invalid()),
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map(Some),
+        _ => Err(invalid()),
+    }
+}
+
 fn to_schema_project(p: Project) -> schema::Project {
-    let allowed_models = p
-        .allowed_models
-        .map(|models| cratestack::Json(json_to_cratestack_value(serde_json::json!(models))));
+    let allowed_models = p.allowed_models.map(string_list_to_json);
     let default_limits = cratestack::Json(json_to_cratestack_value(
         serde_json::to_value(&p.default_limits).unwrap_or(serde_json::Value::Null),
     ));
@@ -569,6 +757,16 @@ fn to_schema_project(p: Project) -> schema::Project {
     }
 }
 
+fn to_schema_project_key_lifecycle_policy(
+    p: ProjectKeyLifecyclePolicy,
+) -> schema::ProjectKeyLifecyclePolicy {
+    schema::ProjectKeyLifecyclePolicy {
+        projectId: p.project_id,
+        dormantAfterDays: p.dormant_after_days.map(i64::from),
+        autoRevokeDormant: p.auto_revoke_dormant,
+    }
+}
+
 /// Maps a roster row onto the generated `ProjectMember`, synthesising the `id`.
 ///
 /// `project_members` is keyed `(project_id, account_id)` and has no `id` column -- the schema
@@ -707,7 +905,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let issuer = self.issuer.clone();
         let subject = subject_from_ctx(ctx);
         let default_quota = args.args.defaultQuota;
-        async move {
+        audited(ctx, "procedure.createAccount", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let account = issuer
@@ -715,7 +913,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_account(account))
-        }
+        })
     }
 
     fn update_account_default_quota(
@@ -734,7 +932,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let subject = subject_from_ctx(ctx);
         let account_id = args.args.accountId;
         let default_quota = args.args.defaultQuota;
-        async move {
+        audited(ctx, "procedure.updateAccountDefaultQuota", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let account = issuer
@@ -742,7 +940,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_account(account))
-        }
+        })
     }
 
     fn rotate_api_key(
@@ -758,9 +956,15 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let subject = subject_from_ctx(ctx);
         let access_token = access_token_from_ctx(ctx);
         let key_id = args.args.keyId;
-        async move {
+        let allowed_models =
+            restriction_list_from_json_arg(args.args.allowedModels, "allowedModels");
+        let scopes = restriction_list_from_json_arg(args.args.scopes, "scopes");
+        let grace_period_seconds = args.args.gracePeriodSeconds;
+        audited(ctx, "procedure.rotateApiKey", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
+            let allowed_models = allowed_models?;
+            let scopes = scopes?;
             let secret = issuer
                 .rotate_api_key(
                     &subject,
@@ -769,13 +973,15 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                     RotateApiKey {
                         name: None,
                         expires_at: None,
-                        grace_period_seconds: None,
+                        grace_period_seconds,
+                        allowed_models,
+                        scopes,
                     },
                 )
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_api_key_secret(secret))
-        }
+        })
     }
 
     fn create_api_key(
@@ -791,9 +997,12 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let subject = subject_from_ctx(ctx);
         let access_token = access_token_from_ctx(ctx);
         let input = args.args;
-        async move {
+        audited(ctx, "procedure.createApiKey", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
+            let allowed_models =
+                restriction_list_from_json_arg(input.allowedModels, "allowedModels")?;
+            let scopes = restriction_list_from_json_arg(input.scopes, "scopes")?;
             let secret = issuer
                 .create_api_key(
                     &subject,
@@ -803,12 +1012,14 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                         name: input.name,
                         expires_at: Some(input.expiresAt),
                         billing_plan: input.billingPlan,
+                        allowed_models,
+                        scopes,
                     },
                 )
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_api_key_secret(secret))
-        }
+        })
     }
 
     /// Read-only: the operator-configured billing-plan catalogue `createApiKey` validates
@@ -872,7 +1083,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let issuer = self.issuer.clone();
         let subject = subject_from_ctx(ctx);
         let account_id = args.args.accountId;
-        async move {
+        audited(ctx, "procedure.disableAccount", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let account = issuer
@@ -880,7 +1091,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_account(account))
-        }
+        })
     }
 
     fn enable_account(
@@ -895,7 +1106,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let issuer = self.issuer.clone();
         let subject = subject_from_ctx(ctx);
         let account_id = args.args.accountId;
-        async move {
+        audited(ctx, "procedure.enableAccount", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let account = issuer
@@ -903,7 +1114,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_account(account))
-        }
+        })
     }
 
     fn disable_project(
@@ -918,7 +1129,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let issuer = self.issuer.clone();
         let subject = subject_from_ctx(ctx);
         let project_id = args.args.projectId;
-        async move {
+        audited(ctx, "procedure.disableProject", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let project = issuer
@@ -926,7 +1137,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_project(project))
-        }
+        })
     }
 
     fn enable_project(
@@ -941,7 +1152,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let issuer = self.issuer.clone();
         let subject = subject_from_ctx(ctx);
         let project_id = args.args.projectId;
-        async move {
+        audited(ctx, "procedure.enableProject", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let project = issuer
@@ -949,7 +1160,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_project(project))
-        }
+        })
     }
 
     fn set_default_project(
@@ -967,7 +1178,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let issuer = self.issuer.clone();
         let subject = subject_from_ctx(ctx);
         let project_id = args.args.projectId;
-        async move {
+        audited(ctx, "procedure.setDefaultProject", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let project = issuer
@@ -975,7 +1186,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_project(project))
-        }
+        })
     }
 
     fn set_project_quota(
@@ -994,7 +1205,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let subject = subject_from_ctx(ctx);
         let project_id = args.args.projectId;
         let project_quota = args.args.projectQuota;
-        async move {
+        audited(ctx, "procedure.setProjectQuota", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let project = issuer
@@ -1002,7 +1213,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_project(project))
-        }
+        })
     }
 
     fn set_project_allowed_models(
@@ -1021,7 +1232,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let subject = subject_from_ctx(ctx);
         let project_id = args.args.projectId;
         let allowed_models = allowed_models_from_json_arg(args.args.allowedModels);
-        async move {
+        audited(ctx, "procedure.setProjectAllowedModels", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let project = issuer
@@ -1029,7 +1240,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_project(project))
-        }
+        })
     }
 
     fn set_project_model_policy(
@@ -1048,7 +1259,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let subject = subject_from_ctx(ctx);
         let project_id = args.args.projectId;
         let model_policy = args.args.modelPolicy;
-        async move {
+        audited(ctx, "procedure.setProjectModelPolicy", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let project = issuer
@@ -1056,9 +1267,68 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_project(project))
+        })
+    }
+
+    fn get_project_key_lifecycle_policy(
+        &self,
+        _db: &schema::Cratestack,
+        ctx: &CratestackContext,
+        args: schema::procedures::get_project_key_lifecycle_policy::Args,
+        _authorized: schema::procedures::get_project_key_lifecycle_policy::Authorized,
+    ) -> impl core::future::Future<
+        Output = std::result::Result<
+            schema::procedures::get_project_key_lifecycle_policy::Output,
+            CratestackError,
+        >,
+    > + Send {
+        let issuer = self.issuer.clone();
+        let subject = subject_from_ctx(ctx);
+        let project_id = args.args.projectId;
+        async move {
+            let subject = subject
+                .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
+            let policy = issuer
+                .get_project_key_lifecycle_policy(&subject, &project_id)
+                .await
+                .map_err(to_cratestack_error)?;
+            Ok(to_schema_project_key_lifecycle_policy(policy))
         }
     }
 
+    fn set_project_key_lifecycle_policy(
+        &self,
+        _db: &schema::Cratestack,
+        ctx: &CratestackContext,
+        args: schema::procedures::set_project_key_lifecycle_policy::Args,
+        _authorized: schema::procedures::set_project_key_lifecycle_policy::Authorized,
+    ) -> impl core::future::Future<
+        Output = std::result::Result<
+            schema::procedures::set_project_key_lifecycle_policy::Output,
+            CratestackError,
+        >,
+    > + Send {
+        let issuer = self.issuer.clone();
+        let subject = subject_from_ctx(ctx);
+        let project_id = args.args.projectId;
+        let dormant_after_days = args.args.dormantAfterDays;
+        let auto_revoke_dormant = args.args.autoRevokeDormant;
+        audited(ctx, "procedure.setProjectKeyLifecyclePolicy", async move {
+            let subject = subject
+                .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
+            let policy = issuer
+                .set_project_key_lifecycle_policy(
+                    &subject,
+                    &project_id,
+                    dormant_after_days,
+                    auto_revoke_dormant,
+                )
+                .await
+                .map_err(to_cratestack_error)?;
+            Ok(to_schema_project_key_lifecycle_policy(policy))
+        })
+    }
+
     fn revoke_api_key(
         &self,
         _db: &schema::Cratestack,
@@ -1071,7 +1341,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let issuer = self.issuer.clone();
         let subject = subject_from_ctx(ctx);
         let key_id = args.args.keyId;
-        async move {
+        audited(ctx, "procedure.revokeApiKey", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let key = issuer
@@ -1079,7 +1349,35 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_api_key(key))
-        }
+        })
+    }
+
+    fn set_api_key_allowed_cidrs(
+        &self,
+        _db: &schema::Cratestack,
+        ctx: &CratestackContext,
+        args: schema::procedures::set_api_key_allowed_cidrs::Args,
+        _authorized: schema::procedures::set_api_key_allowed_cidrs::Authorized,
+    ) -> impl core::future::Future<
+        Output = std::result::Result<
+            schema::procedures::set_api_key_allowed_cidrs::Output,
+            CratestackError,
+        >,
+    > + Send {
+        let issuer = self.issuer.clone();
+        let subject = subject_from_ctx(ctx);
+        let key_id = args.args.keyId;
+        let allowed_cidrs = restriction_list_from_json_arg(args.args.allowedCidrs, "allowedCidrs");
+        audited(ctx, "procedure.setApiKeyAllowedCidrs", async move {
+            let subject = subject
+                .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
+            let allowed_cidrs = allowed_cidrs?;
+            let key = issuer
+                .set_api_key_allowed_cidrs(&subject, &key_id, allowed_cidrs)
+                .await
+                .map_err(to_cratestack_error)?;
+            Ok(to_schema_api_key(key))
+        })
     }
 
     fn add_project_member(
@@ -1099,7 +1397,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let project_id = args.args.projectId;
         let target_account_id = args.args.accountId;
         let role = args.args.role;
-        async move {
+        audited(ctx, "procedure.addProjectMember", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let project = issuer
@@ -1107,7 +1405,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_project(project))
-        }
+        })
     }
 
     fn remove_project_member(
@@ -1126,7 +1424,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let subject = subject_from_ctx(ctx);
         let project_id = args.args.projectId;
         let target_account_id = args.args.accountId;
-        async move {
+        audited(ctx, "procedure.removeProjectMember", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let project = issuer
@@ -1134,7 +1432,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_project(project))
-        }
+        })
     }
 
     fn set_project_member_role(
@@ -1154,7 +1452,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let project_id = args.args.projectId;
         let target_account_id = args.args.accountId;
         let role = args.args.role;
-        async move {
+        audited(ctx, "procedure.setProjectMemberRole", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let project = issuer
@@ -1162,7 +1460,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_project(project))
-        }
+        })
     }
 
     fn set_project_member_quota_tier(
@@ -1182,7 +1480,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let project_id = args.args.projectId;
         let target_account_id = args.args.accountId;
         let quota_tier = args.args.quotaTier;
-        async move {
+        audited(ctx, "procedure.setProjectMemberQuotaTier", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let project = issuer
@@ -1195,7 +1493,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_project(project))
-        }
+        })
     }
 
     /// The roster's only read path. Authorization is wider than the four mutations above -- any
@@ -1242,7 +1540,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let issuer = self.issuer.clone();
         let subject = subject_from_ctx(ctx);
         let account_id = args.args.accountId;
-        async move {
+        audited(ctx, "procedure.deleteAccountPermanently", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let account = issuer
@@ -1250,7 +1548,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_account(account))
-        }
+        })
     }
 
     /// Activates a budget policy (ADR-0007): either brand-new rule data (`ruleDataJson`) or a
@@ -1282,7 +1580,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let policy_set_id = args.args.policySetId;
         let rule_data_json = args.args.ruleDataJson;
         let revision_id = args.args.revisionId;
-        async move {
+        audited(ctx, "procedure.activateBudgetPolicy", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
 
@@ -1319,7 +1617,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 policySetId: policy_set_id,
                 activePolicyRevision: active_revision,
             })
-        }
+        })
     }
 
     /// Reports the revision genuinely serving `evaluate()` calls right now -- reads the live
@@ -1359,10 +1657,11 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         }
     }
 
-    /// Evaluates a proposed rule-data policy against a caller-supplied scenario, entirely in
-    /// memory (#190, ADR-0007). Deliberately does NOT touch `self.policy_store` -- unlike every
-    /// other method on this `impl`, this one constructs its own short-lived
-    /// [`lightbridge_authz_budget::RuleDataEngine`] directly from the caller's `ruleDataJson`,
+    /// Evaluates a proposed policy document -- rule data or OPA-Wasm -- against a caller-supplied
+    /// scenario, entirely in memory (#190, ADR-0007). Deliberately does NOT touch
+    /// `self.policy_store` -- unlike every other method on this `impl`, this one constructs its
+    /// own short-lived [`lightbridge_authz_budget::ActivePolicyEngine`] directly from the caller's
+    /// `ruleDataJson`,
     /// calls `evaluate()` on it once, and discards it. There is no code path here capable of
     /// writing to `budget_policy_sets`/`budget_policy_revisions` (no `PolicyStore` reference to
     /// do so through) or to `budget_grants`/`budget_balances` (no repository reference to do so
@@ -1401,7 +1700,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
             // A short-lived engine, constructed and discarded within this one call -- never
             // wired into `self.policy_store`, never persisted, never touches
             // budget_policy_sets/budget_policy_revisions.
-            let engine = lightbridge_authz_budget::RuleDataEngine::new(
+            let engine = lightbridge_authz_budget::ActivePolicyEngine::new(
                 &rule_data_json,
                 BUDGET_POLICY_EVALUATION_BUDGET,
             )
@@ -1465,7 +1764,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let refill_service = self.refill_service.clone();
         let subject = subject_from_ctx(ctx);
         let input = args.args;
-        async move {
+        audited(ctx, "procedure.requestBudgetRefill", async move {
             let _subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
 
@@ -1497,11 +1796,11 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .map_err(budget_error_to_cratestack_error)?;
 
             Ok(to_schema_augmentation_request(created))
-        }
+        })
     }
 
     /// Read-only companion to [`Self::request_budget_refill`]: the self-service refill amounts
-    /// currently offered by the active policy for `period` -- delegating to
+    /// currently offered by the active policy for `period`, and its tier ladder -- delegating to
     /// [`lightbridge_authz_budget::RefillService::refill_status`], which calls no policy engine
     /// and mutates nothing. `budgetAccountId` is derived from the authenticated subject exactly
     /// like [`Self::get_my_budget_balance`] (never a caller-supplied field, the same structural
@@ -1638,7 +1937,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let review_service = self.review_service.clone();
         let subject = subject_from_ctx(ctx);
         let request_id = args.args.requestId;
-        async move {
+        audited(ctx, "procedure.approveAugmentationRequest", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
 
@@ -1648,7 +1947,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .map_err(budget_error_to_cratestack_error)?;
 
             Ok(to_schema_augmentation_request(reviewed))
-        }
+        })
     }
 
     /// Rejects a `pending_review` request (#191, PR 3.4), delegating to
@@ -1672,7 +1971,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let subject = subject_from_ctx(ctx);
         let request_id = args.args.requestId;
         let reason = args.args.reason;
-        async move {
+        audited(ctx, "procedure.rejectAugmentationRequest", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
 
@@ -1682,7 +1981,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .map_err(budget_error_to_cratestack_error)?;
 
             Ok(to_schema_augmentation_request(reviewed))
-        }
+        })
     }
 
     /// "Log out everywhere": revokes every active refresh-token session belonging to the
@@ -1707,7 +2006,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
     > + Send {
         let issuer = self.issuer.clone();
         let subject = subject_from_ctx(ctx);
-        async move {
+        audited(ctx, "procedure.revokeOwnSessions", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let revoked_count = issuer
@@ -1715,7 +2014,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_session_revocation_result(revoked_count))
-        }
+        })
     }
 
     /// The offboarding kill switch: revokes every active refresh-token session for
@@ -1741,7 +2040,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let issuer = self.issuer.clone();
         let subject = subject_from_ctx(ctx);
         let target_account_id = args.args.accountId;
-        async move {
+        audited(ctx, "procedure.revokeSubjectSessions", async move {
             let _subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
             let revoked_count = issuer
@@ -1749,6 +2048,48 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .await
                 .map_err(to_cratestack_error)?;
             Ok(to_schema_session_revocation_result(revoked_count))
+        })
+    }
+
+    /// A page of the `audit_events` trail (see `authz.cstack`'s `AuditEventEntry`). Gated at
+    /// `audit:read`; like `revokeSubjectSessions`, the RBAC gate is the whole authorization.
+    fn list_audit_events(
+        &self,
+        _db: &schema::Cratestack,
+        ctx: &CratestackContext,
+        args: schema::procedures::list_audit_events::Args,
+        _authorized: schema::procedures::list_audit_events::Authorized,
+    ) -> impl core::future::Future<
+        Output = std::result::Result<
+            schema::procedures::list_audit_events::Output,
+            CratestackError,
+        >,
+    > + Send {
+        let issuer = self.issuer.clone();
+        let subject = subject_from_ctx(ctx);
+        let input = args.args;
+        async move {
+            let _subject = subject
+                .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
+            let page_size = input
+                .limit
+                .map_or(DEFAULT_AUDIT_EVENTS_PAGE_SIZE, |requested| {
+                    requested.clamp(1, MAX_AUDIT_EVENTS_PAGE_SIZE)
+                });
+            let filter = AuditEventFilter {
+                actor_subject: input.actorSubject,
+                target_type: input.targetType,
+                target_id: input.targetId,
+                from: input.from,
+                to: input.to,
+                before: input.before,
+                limit: page_size,
+            };
+            let events = issuer
+                .list_audit_events(&filter)
+                .await
+                .map_err(to_cratestack_error)?;
+            Ok(to_schema_audit_event_page(events, page_size))
         }
     }
 
@@ -1909,7 +2250,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let budget_repo = self.budget_repo.clone();
         let subject = subject_from_ctx(ctx);
         let input = args.args;
-        async move {
+        audited(ctx, "procedure.grantBudget", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
 
@@ -1942,7 +2283,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .map_err(budget_error_to_cratestack_error)?;
 
             Ok(to_schema_budget_grant_entry(grant))
-        }
+        })
     }
 
     /// The compensating-correction counterpart to `grantBudget` (ADR-0009: the ledger is
@@ -1966,7 +2307,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let subject = subject_from_ctx(ctx);
         let grant_id = args.args.grantId;
         let reason = args.args.reason;
-        async move {
+        audited(ctx, "procedure.revokeBudgetGrant", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
 
@@ -1995,7 +2336,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 .map_err(budget_error_to_cratestack_error)?;
 
             Ok(to_schema_budget_grant_entry(correction))
-        }
+        })
     }
 
     /// Authors a new budget-policy revision WITHOUT activating it (ADR-0007). Delegates to
@@ -2018,7 +2359,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
         let subject = subject_from_ctx(ctx);
         let policy_set_id = args.args.policySetId;
         let rule_data_json = args.args.ruleDataJson;
-        async move {
+        audited(ctx, "procedure.createBudgetPolicyRevision", async move {
             let subject = subject
                 .ok_or_else(|| CratestackError::Unauthorized("missing subject".to_owned()))?;
 
@@ -2039,7 +2380,7 @@ impl schema::procedures::ProcedureRegistry for Procedures {
                 revisionId: new_revision.id,
                 policyRevision: new_revision.policy_revision,
             })
-        }
+        })
     }
 }
 
@@ -2081,12 +2422,13 @@ async fn list_budget_grants_page(
     })
 }
 
-/// Shared `/`, `/healthz`, `/healthz/startup`, `/healthz/ready` mount, reused by every server
-/// router (`build_api_router`/`build_opa_router`/`build_idp_router`) so the probe surface — and
+/// Shared `/`, `/healthz`, `/healthz/startup`, `/healthz/ready`, `/metrics` mount, reused by every
+/// server router (`build_api_router`/`build_opa_router`/`build_idp_router`/`build_budget_router`,
+/// and `extauthz::build_extauthz_router`) so the probe surface — and
 /// its DB-readiness semantics (`readiness_handler`/`is_database_ready`) — can never drift between
 /// them. Generic over `S` the same way `well_known_router`/`token_exchange_router` are, so it
 /// merges into any router regardless of that router's own state type.
-fn probe_router<S>(readiness_pool: Arc<dyn DbPoolTrait>) -> Router<S>
+pub(crate) fn probe_router<S>(readiness_pool: Arc<dyn DbPoolTrait>) -> Router<S>
 where
     S: Clone + Send + Sync + 'static,
 {
@@ -2101,16 +2443,21 @@ where
                 async move { readiness_handler(readiness_pool).await }
             }),
         )
+        .route(
+            "/metrics",
+            get(lightbridge_authz_core::metrics::metrics_handler),
+        )
 }
 
-/// Derives the two `well_known_router` mount parameters (`token_exchange_scopes`,
-/// `private_key_jwt_supported`) from `oauth2`. Used by `build_idp_router` — `authz-idp` is now the
+/// Derives the four `well_known_router` mount parameters (`token_exchange_scopes`,
+/// `private_key_jwt_supported`, `device_authorization_supported`,
+/// `authorization_code_supported`) from `oauth2`. Used by `build_idp_router` — `authz-idp` is now the
 /// only server that mounts `well_known_router` at all; `authz-api` stopped serving OIDC
 /// discovery/JWKS once the `auth.ai.camer.digital` ingress was repointed at `authz-idp` (see
 /// `build_api_router`'s doc comment). Kept as its own function rather than inlined into
 /// `build_idp_router` so a future second self-signed-JWKS server can reuse it the same way
 /// `build_api_router` used to.
-fn well_known_mount_params(oauth2: &Oauth2) -> (Option<Vec<String>>, bool) {
+fn well_known_mount_params(oauth2: &Oauth2) -> (Option<Vec<String>>, bool, bool, bool) {
     let token_exchange_scopes = oauth2
         .token_exchange
         .as_ref()
@@ -2120,7 +2467,24 @@ fn well_known_mount_params(oauth2: &Oauth2) -> (Option<Vec<String>>, bool) {
         .clients
         .iter()
         .any(|c| c.client_type == OauthClientType::Confidential);
-    (token_exchange_scopes, private_key_jwt_supported)
+    // Only advertised alongside token exchange, since `build_token_exchange_state` refuses to
+    // start the device grant without it.
+    let device_authorization_supported = token_exchange_scopes.is_some()
+        && oauth2
+            .device_authorization
+            .as_ref()
+            .is_some_and(|d| d.enabled);
+    let authorization_code_supported = token_exchange_scopes.is_some()
+        && oauth2
+            .authorization_code
+            .as_ref()
+            .is_some_and(|a| a.enabled);
+    (
+        token_exchange_scopes,
+        private_key_jwt_supported,
+        device_authorization_supported,
+        authorization_code_supported,
+    )
 }
 
 /// Assembles the API server router: public probes plus the generated cratestack RPC CRUD surface
@@ -2199,7 +2563,8 @@ pub fn build_api_router(
             scope: RpcScope::Crud,
         },
         rpc_authorize::rpc_authorize,
-    ));
+    ))
+    .layer(axum::middleware::from_fn(metrics::rpc_metrics));
 
     // Mount the RPC surface at the configured base path (default: root, i.e. `/rpc/<op_id>`). axum's
     // `nest` strips the prefix before the inner router runs, so the gate, idempotency/rate-limit
@@ -2268,7 +2633,24 @@ fn build_token_exchange_state(
     redis_url: &str,
     redis_ca_bundle_path: Option<&str>,
 ) -> Result<Option<token_exchange::TokenExchangeState>> {
+    let device_authorization = oauth2.device_authorization.as_ref().filter(|d| d.enabled);
+    let authorization_code = oauth2.authorization_code.as_ref().filter(|a| a.enabled);
     let Some(cfg) = oauth2.token_exchange.as_ref().filter(|t| t.enabled) else {
+        if device_authorization.is_some() {
+            return Err(Error::Server(
+                "oauth2.device_authorization is enabled but requires oauth2.token_exchange -- \
+                 the device_code grant is redeemed on the token-exchange /oauth2/token endpoint"
+                    .to_string(),
+            ));
+        }
+        if authorization_code.is_some() {
+            return Err(Error::Server(
+                "oauth2.authorization_code is enabled but requires oauth2.token_exchange -- \
+                 the authorization_code grant is redeemed on the token-exchange /oauth2/token \
+                 endpoint"
+                    .to_string(),
+            ));
+        }
         return Ok(None);
     };
     if !oauth2.is_self_signed() {
@@ -2324,43 +2706,157 @@ fn build_token_exchange_state(
             token_exchange::TOKEN_EXCHANGE_GRANT.to_string(),
             token_exchange::REFRESH_TOKEN_GRANT.to_string(),
         ],
-        id_token_signing_alg: "RS256".to_string(),
+        id_token_signing_alg: signing.algorithm.as_str().to_string(),
         authorization_code_ttl_secs: 0,
         access_token_ttl_secs: cfg.access_ttl_seconds.max(0) as u64,
         device_code_ttl_secs: 0,
         token_exchange_enabled: cfg.enabled,
     };
-    Ok(Some(token_exchange::TokenExchangeState::new(
-        signer, op_config, op_store,
-    )))
+    let mut state = token_exchange::TokenExchangeState::new(signer, op_config, op_store);
+    if let Some(device) = device_authorization {
+        validate_device_authorization(device)?;
+        state = state.with_device_authorization(device);
+    }
+    if let Some(authorize) = authorization_code {
+        validate_authorization_code(authorize, &oauth2.clients)?;
+        state = state.with_authorization_code(authorize);
+    }
+    Ok(Some(state))
 }
 
-#[expect(
-    clippy::too_many_arguments,
-    reason = "startup wiring for authz-api -- each parameter is a distinct, independently-loaded \
-              config section (billing/quota_tiers/models catalogues, redis, usage_service); \
-              bundling them into a struct would just move the same count into a constructor call \
-              at the one caller (main.rs) without reducing anything"
-)]
-pub async fn start_api_server(
-    api: &ApiServer,
-    pool: Arc<dyn DbPoolTrait>,
-    oauth2: &Oauth2,
-    billing: &Billing,
-    quota_tiers: &QuotaTiers,
-    models: &ModelCatalog,
-    api_key_expiry: &ApiKeyExpiry,
-    redis: &Option<Redis>,
-    usage_service: &Option<UsageServiceClient>,
+/// Startup checks for `oauth2.device_authorization` (ADR-0012): a zero TTL would issue pairings
+/// that are already expired, and a malformed Keycloak endpoint would only surface once a user is
+/// halfway through the verification page.
+fn validate_device_authorization(
+    cfg: &lightbridge_authz_core::config::Oauth2DeviceAuthorization,
 ) -> Result<()> {
-    billing.validate()?;
-    api_key_expiry.validate()?;
-    oauth2.rbac.validate()?;
+    if cfg.code_ttl_seconds <= 0 {
+        return Err(Error::Server(
+            "device_authorization code_ttl_seconds must be positive".to_string(),
+        ));
+    }
+    validate_upstream("device_authorization", &cfg.upstream)
+}
+
+/// Startup checks for `oauth2.authorization_code`: the same TTL and upstream checks as the device
+/// grant, plus every client allowed the grant must register at least one redirect URI
+/// `/authorize` can match exactly. A wildcard or fragment could never match a real request
+/// byte-for-byte (RFC 6749 §3.1.2 forbids fragments outright), and a plain-`http` URI would send
+/// codes across the network in the clear, so all three are refused here rather than left to fail
+/// at sign-in time. Loopback `http` stays allowed for native apps (RFC 8252 §7.3).
+fn validate_authorization_code(
+    cfg: &lightbridge_authz_core::config::Oauth2AuthorizationCode,
+    clients: &[lightbridge_authz_core::config::OauthClient],
+) -> Result<()> {
+    if cfg.code_ttl_seconds <= 0 || cfg.request_ttl_seconds <= 0 {
+        return Err(Error::Server(
+            "authorization_code code_ttl_seconds and request_ttl_seconds must be positive"
+                .to_string(),
+        ));
+    }
+    validate_upstream("authorization_code", &cfg.upstream)?;
+    for client in clients
+        .iter()
+        .filter(|c| c.grant_types.iter().any(|g| g == "authorization_code"))
+    {
+        if client.redirect_uris.is_empty() {
+            return Err(Error::Server(format!(
+                "oauth2.clients[{}] allows authorization_code but registers no redirect_uris",
+                client.client_id
+            )));
+        }
+        for uri in &client.redirect_uris {
+            if !is_registrable_redirect_uri(uri) {
+                return Err(Error::Server(format!(
+                    "oauth2.clients[{}] redirect_uri is not an absolute, fragment-free, \
+                     wildcard-free https (or loopback http) URL: {uri}",
+                    client.client_id
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_registrable_redirect_uri(uri: &str) -> bool {
+    let Ok(url) = reqwest::Url::parse(uri) else {
+        return false;
+    };
+    if uri.contains('*') || url.fragment().is_some() || url.cannot_be_a_base() {
+        return false;
+    }
+    match url.scheme() {
+        "https" => true,
+        "http" => matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]")),
+        // Private-use URI schemes for native apps (RFC 8252 §7.1), e.g. `com.example.app:/cb`.
+        scheme => scheme.contains('.'),
+    }
+}
+
+/// Shared by both browser flows: the Keycloak client they sign users in through must be fully
+/// specified, or the failure would only surface once a user is halfway through signing in.
+fn validate_upstream(
+    section: &str,
+    upstream: &lightbridge_authz_core::config::Oauth2Upstream,
+) -> Result<()> {
+    if upstream.client_id.trim().is_empty() {
+        return Err(Error::Server(format!(
+            "{section} upstream.client_id must not be empty"
+        )));
+    }
+    for (field, url) in [
+        ("authorization_endpoint", &upstream.authorization_endpoint),
+        ("token_endpoint", &upstream.token_endpoint),
+    ] {
+        if reqwest::Url::parse(url).is_err() {
+            return Err(Error::Server(format!(
+                "{section} upstream.{field} is not a valid URL: {url}"
+            )));
+        }
+    }
+    Ok(())
+}
 
-    // ADR-0007: load whatever is genuinely active in the DB right now, so a fresh startup always
-    // agrees with the last successful activation -- this is what proves "no restart needed to see
-    // a policy change AND still correct if you do restart" holds for the real running server, not
-    // just at the `PolicyStore` unit level.
+/// The budget domain's services, shared by every server that hosts budget procedures
+/// (`authz-api`, `authz-budget`, `lightbridge-mcp`).
+pub struct BudgetServices {
+    pub policy_store: Arc<lightbridge_authz_budget::PolicyStore>,
+    pub budget_repo: Arc<lightbridge_authz_budget::repo::BudgetRepo>,
+    pub refill_service: Arc<lightbridge_authz_budget::RefillService>,
+    pub review_service: Arc<lightbridge_authz_budget::ReviewService>,
+}
+
+/// Loads the active budget policy and builds the refill/review services over `pool`.
+///
+/// ADR-0007: the policy is whatever is genuinely active in the DB right now, so a fresh startup
+/// always agrees with the last successful activation -- this is what proves "no restart needed to
+/// see a policy change AND still correct if you do restart" holds for the real running server, not
+/// just at the `PolicyStore` unit level. A bad load fails startup.
+///
+/// `budget_repo` and the repositories behind the two services are fresh handles against the same
+/// `pool` every other hand-written repository uses; `policy_store.engine()` is the SAME live,
+/// hot-swappable engine `activateBudgetPolicy`/`getBudgetPolicyStatus` read/write, so a policy
+/// activated at runtime takes effect for refills immediately, with no restart.
+///
+/// `usage_service` (`Config.usage_service`) is optional -- see that field's own doc comment. When
+/// it is not configured, this degrades to `UnavailableSpendReader` rather than failing startup:
+/// every spend-dependent policy fact then reads `Spend::Unavailable`, which the rule-data
+/// evaluator already treats as a fail-closed signal (routes to `manual_review`, never
+/// `auto_approve` -- see `UnavailableSpendReader`'s own doc comment for the full reasoning).
+/// Degrading rather than hard-failing (unlike the policy load) is deliberate: a missing
+/// `usage_service` narrows what self-service refill can decide automatically, it does not make
+/// the RPC surface unsafe to serve -- so a deployment that has not wired up the usage service yet
+/// can still start, just with every refill routing to manual review until it does. When it IS
+/// configured, `UsageServiceSpendReader` calls the usage service's `/usage/v1/spend/query` over
+/// HTTP instead of opening a second database connection (see
+/// `crates/lightbridge-authz-budget/src/spend.rs`'s module doc comment for why); every way that
+/// HTTP call can fail -- unreachable, timeout, non-2xx, unparseable body -- also resolves to
+/// `Spend::Unavailable`, never a hard error, so a flaky or down usage service degrades refill
+/// decisions the same way a missing config does, rather than failing the server's own requests.
+pub async fn load_budget_services(
+    pool: Arc<dyn DbPoolTrait>,
+    usage_service: Option<&UsageServiceClient>,
+) -> Result<BudgetServices> {
     let policy_store = Arc::new(
         lightbridge_authz_budget::PolicyStore::load_active_from_db(
             pool.clone(),
@@ -2370,37 +2866,10 @@ pub async fn start_api_server(
         .await
         .map_err(|e| Error::Server(format!("failed to load active budget policy: {e}")))?,
     );
-
-    // Self-service refill and the admin review queue (#191, PR 3.4). `budget_repo`/
-    // `augmentation_repo` are fresh handles against the same `pool` every other hand-written
-    // repository on this server uses; `policy_store.engine()` is the SAME live, hot-swappable
-    // engine `activateBudgetPolicy`/`getBudgetPolicyStatus` above read/write, so a policy
-    // activated at runtime takes effect for refills immediately, with no restart, exactly as it
-    // already does for `simulateBudgetPolicy`'s sibling procedures.
-    //
-    // `usage_service` (`Config.usage_service`) is optional -- see that field's own doc comment.
-    // When it is not configured, this degrades to `UnavailableSpendReader` rather than failing
-    // server startup: every spend-dependent policy fact then reads `Spend::Unavailable`, which
-    // the rule-data evaluator already treats as a fail-closed signal (routes to `manual_review`,
-    // never `auto_approve` -- see `UnavailableSpendReader`'s own doc comment for the full
-    // reasoning). Choosing to degrade rather than hard-fail (unlike `policy_store` above, which
-    // DOES fail startup loudly on a bad load) is deliberate: a missing `usage_service` narrows
-    // what self-service refill can decide automatically, it does not make the RPC surface
-    // unsafe to serve -- so a deployment that has not wired up the usage service yet can still
-    // start, just with every refill routing to manual review until it does. When it IS
-    // configured, `UsageServiceSpendReader` calls the usage service's `/usage/v1/spend/query`
-    // over HTTP instead of opening a second database connection (see
-    // `crates/lightbridge-authz-budget/src/spend.rs`'s module doc comment for why); every way
-    // that HTTP call can fail -- unreachable, timeout, non-2xx, unparseable body -- also resolves
-    // to `Spend::Unavailable`, never a hard error, so a flaky or down usage service degrades
-    // refill decisions the same way a missing config does, rather than failing this server's own
-    // requests.
     let budget_repo = Arc::new(lightbridge_authz_budget::repo::BudgetRepo::new(
         pool.clone(),
     ));
-    let augmentation_repo = Arc::new(lightbridge_authz_budget::AugmentationRepo::new(
-        pool.clone(),
-    ));
+    let augmentation_repo = Arc::new(lightbridge_authz_budget::AugmentationRepo::new(pool));
     let policy_engine: Arc<dyn lightbridge_authz_budget::PolicyEngine> = policy_store.engine();
     let spend_reader: Arc<dyn lightbridge_authz_budget::SpendReader> = match usage_service {
         Some(usage_service) => Arc::new(
@@ -2435,6 +2904,50 @@ pub async fn start_api_server(
         budget_repo.clone(),
         augmentation_repo,
     ));
+    Ok(BudgetServices {
+        policy_store,
+        budget_repo,
+        refill_service,
+        review_service,
+    })
+}
+
+#[expect(
+    clippy::too_many_arguments,
+    reason = "startup wiring for authz-api -- each parameter is a distinct, independently-loaded \
+              config section (billing/quota_tiers/models catalogues, redis, usage_service); \
+              bundling them into a struct would just move the same count into a constructor call \
+              at the one caller (main.rs) without reducing anything"
+)]
+pub async fn start_api_server(
+    api: &ApiServer,
+    pool: Arc<dyn DbPoolTrait>,
+    oauth2: &Oauth2,
+    billing: &Billing,
+    quota_tiers: &QuotaTiers,
+    models: &ModelCatalog,
+    api_key_expiry: &ApiKeyExpiry,
+    redis: &Option<Redis>,
+    usage_service: &Option<UsageServiceClient>,
+    api_key_hashing: &Option<ApiKeyHashing>,
+    api_key_lifecycle: &Option<ApiKeyLifecycle>,
+) -> Result<()> {
+    billing.validate()?;
+    api_key_expiry.validate()?;
+    if let Some(api_key_lifecycle) = api_key_lifecycle {
+        api_key_lifecycle.validate()?;
+    }
+    oauth2.rbac.validate()?;
+    let api_key_hasher = Arc::new(ApiKeyHasher::from_config(api_key_hashing.as_ref())?);
+
+    // ADR-0007 policy load plus self-service refill and the admin review queue (#191, PR 3.4) --
+    // see `load_budget_services`.
+    let BudgetServices {
+        policy_store,
+        budget_repo,
+        refill_service,
+        review_service,
+    } = load_budget_services(pool.clone(), usage_service.as_ref()).await?;
 
     let readiness_pool = pool.clone();
     // Bootstraps (or observes) the active self-signed-JWT signing key so `AuthzStoreImpl`'s own
@@ -2447,17 +2960,19 @@ pub async fn start_api_server(
         })?;
         let signing_repo = Arc::new(StoreRepo::new(pool.clone()));
         signing::bootstrap_signing_key(&signing_repo, signing).await?;
+        signing_rotation::spawn_signing_key_rotation(signing_repo, oauth2)?;
     }
     // Secret-issuance + membership operations reused by the RPC procedures (hand-written sqlx on the
     // core `DbPool`, sqlx 0.9).
-    let issuer = Arc::new(AuthzStoreImpl::with_pool_and_oauth2(
+    let issuer = AuthzStoreImpl::with_pool_and_oauth2(
         pool.clone(),
         oauth2,
         billing,
         quota_tiers,
         models,
         api_key_expiry,
-    )?);
+    )?
+    .with_api_key_hasher(api_key_hasher);
     let bearer_service: Arc<dyn lightbridge_authz_bearer::BearerTokenServiceTrait> =
         Arc::new(BearerTokenService::new(oauth2.clone()));
 
@@ -2468,6 +2983,28 @@ pub async fn start_api_server(
         )
     })?;
 
+    // Always published, whether or not this deployment enables `introspection_cache`: authz-api
+    // cannot see authz-opa's config, and an event nobody subscribes to costs one PUBLISH per
+    // mutation.
+    let invalidations = introspection_cache::InvalidationPublisher::connect(
+        &redis.url,
+        redis.ca_bundle_path.as_deref(),
+    )?;
+    let issuer = Arc::new(issuer.with_introspection_invalidation(invalidations.clone()));
+    api_key_rotation::spawn_api_key_rotation_sweeper(
+        Arc::new(StoreRepo::new(pool.clone())),
+        Some(invalidations.clone()),
+    );
+    if let Some(api_key_lifecycle) = api_key_lifecycle {
+        api_key_lifecycle::spawn_api_key_lifecycle_job(
+            api_key_lifecycle::ApiKeyLifecycleJob::from_config(
+                Arc::new(StoreRepo::new(pool.clone())),
+                api_key_lifecycle,
+                Some(invalidations),
+            )?,
+        );
+    }
+
     // cratestack runs on its own sqlx major (0.8, vs this workspace's 0.9), so its CRUD client and
     // Postgres-backed idempotency store need a separate pool built with cratestack's sqlx. Both talk
     // to the same database as the core `DbPool`; the URL comes from `DATABASE_URL` (the same env the
@@ -2545,24 +3082,55 @@ pub fn build_opa_router(state: Arc<OpaState>, readiness_pool: Arc<dyn DbPoolTrai
     public.merge(protected).with_state(state)
 }
 
+/// `introspection_cache`/`redis` are the top-level `Config` blocks; `redis` is only consulted to
+/// subscribe to cache invalidations and, unlike on `authz-api`, is never required here (see
+/// `introspection_cache::build_introspection_cache`). `introspection_budget`/`usage_service`
+/// likewise only matter when the former is set (see
+/// `introspection_budget::build_introspection_budget`).
+#[expect(
+    clippy::too_many_arguments,
+    reason = "startup wiring for authz-opa -- each parameter is a distinct, independently-loaded \
+              config section, mirroring start_api_server"
+)]
 pub async fn start_opa_server(
     opa: &OpaServer,
     pool: Arc<dyn DbPoolTrait>,
     billing: &Billing,
     oauth2: &Oauth2,
+    introspection_cache: &Option<IntrospectionCache>,
+    redis: &Option<Redis>,
+    introspection_budget: &Option<IntrospectionBudget>,
+    usage_service: &Option<UsageServiceClient>,
+    api_key_hashing: &Option<ApiKeyHashing>,
 ) -> Result<()> {
+    let api_key_hasher = Arc::new(ApiKeyHasher::from_config(api_key_hashing.as_ref())?);
+    let introspection_cache =
+        introspection_cache::build_introspection_cache(introspection_cache, redis, "authz-opa")?;
+    let introspection_budget = introspection_budget::build_introspection_budget(
+        introspection_budget,
+        usage_service,
+        pool.clone(),
+        "authz-opa",
+    )
+    .await?;
     let readiness_pool = pool.clone();
     let repo: Arc<dyn OpaRepoTrait> = Arc::new(StoreRepo::new(pool));
     let api_key_audience = oauth2
         .signing
         .as_ref()
         .and_then(|signing| signing.audience.clone());
+    let api_key_usage = Arc::new(api_key_usage::ApiKeyUsageTracker::default());
     let state = Arc::new(OpaState {
-        repo,
+        repo: repo.clone(),
         basic_auth: opa.basic_auth.clone(),
         billing: Arc::new(billing.clone()),
         api_key_audience,
+        introspection_cache,
+        introspection_budget,
+        api_key_hasher,
+        api_key_usage: api_key_usage.clone(),
     });
+    api_key_usage::spawn_api_key_usage_flusher(api_key_usage.clone(), repo.clone());
 
     let app = build_opa_router(state, readiness_pool);
 
@@ -2573,7 +3141,11 @@ pub async fn start_opa_server(
         "starting opa server"
     );
 
-    serve_tls("OPA", &opa.address, opa.port, &opa.tls, app).await
+    // `serve_tls` returns once a shutdown signal has drained in-flight requests; write the usage
+    // they recorded before the process exits.
+    let served = serve_tls("OPA", &opa.address, opa.port, &opa.tls, app).await;
+    api_key_usage::flush_on_shutdown(&api_key_usage, repo.as_ref()).await;
+    served
 }
 
 /// Assembles the `authz-idp` server router (ADR-0012): public probes plus the OIDC
@@ -2595,7 +3167,12 @@ pub fn build_idp_router(
 ) -> Router {
     let mut router = probe_router(readiness_pool);
 
-    let (token_exchange_scopes, private_key_jwt_supported) = well_known_mount_params(oauth2);
+    let (
+        token_exchange_scopes,
+        private_key_jwt_supported,
+        device_authorization_supported,
+        authorization_code_supported,
+    ) = well_known_mount_params(oauth2);
     if oauth2.is_self_signed()
         && let Some(signing) = oauth2.signing.as_ref()
     {
@@ -2604,10 +3181,20 @@ pub fn build_idp_router(
             signing_repo,
             token_exchange_scopes,
             private_key_jwt_supported,
+            device_authorization_supported,
+            authorization_code_supported,
         ));
     }
 
     if let Some(te_state) = token_exchange {
+        if let Some(device) = te_state.device_authorization() {
+            router = router.merge(device_authorization::device_authorization_router(
+                device.clone(),
+            ));
+        }
+        if let Some(authorize) = te_state.authorization_code() {
+            router = router.merge(authorize::authorize_router(authorize.clone()));
+        }
         router = router.merge(token_exchange::token_exchange_router(te_state));
     }
 
@@ -2677,6 +3264,7 @@ pub async fn start_idp_server(
     let policy_engine: Arc<dyn lightbridge_authz_budget::PolicyEngine> = policy_store.engine();
     let signing_repo = Arc::new(StoreRepo::new(pool));
     signing::bootstrap_signing_key(&signing_repo, signing).await?;
+    signing_rotation::spawn_signing_key_rotation(signing_repo.clone(), oauth2)?;
 
     let bearer_service: Arc<dyn lightbridge_authz_bearer::BearerTokenServiceTrait> =
         Arc::new(BearerTokenService::new(oauth2.clone()));
@@ -2785,7 +3373,8 @@ pub fn build_budget_router(
             scope: RpcScope::Budget,
         },
         rpc_authorize::rpc_authorize,
-    ));
+    ))
+    .layer(axum::middleware::from_fn(metrics::rpc_metrics));
 
     let router = public.nest(BUDGET_RPC_BASE_PATH, rpc);
     if dev_cors {
@@ -2797,13 +3386,13 @@ pub fn build_budget_router(
 
 /// Starts `authz-budget`: the budget-domain microservice carrying every `budget:*`-gated RPC
 /// procedure off `authz-api` (hard cutover — see `build_budget_router`'s own doc comment,
-/// `docs/architecture/budget.md`). Mirrors `start_api_server`'s budget-domain wiring
-/// (`policy_store`/`budget_repo`/`refill_service`/`review_service`/spend-reader selection)
-/// line-for-line intentionally — this server owns exactly that half of what `start_api_server`
-/// used to build, nothing added, nothing dropped. What it deliberately does NOT carry:
-/// `well_known_router`/token-exchange (an `authz-idp` concern, unrelated to budget), and signing-
-/// key bootstrap (this server only ever validates bearer tokens via `oauth2.jwks_url`, never
-/// issues or rotates one — `rotateApiKey`/`createApiKey` are CRUD op-ids, refused here by
+/// `docs/architecture/budget.md`). Shares `start_api_server`'s budget-domain wiring
+/// (`load_budget_services`) — this server owns that half of what `start_api_server` used to
+/// build, plus the period-rollover background job (`budget_rollover`), which has no
+/// `start_api_server` counterpart because it postdates the cutover. What it deliberately does NOT
+/// carry: `well_known_router`/token-exchange (an `authz-idp` concern, unrelated to budget), and
+/// signing-key bootstrap (this server only ever validates bearer tokens via `oauth2.jwks_url`,
+/// never issues or rotates one — `rotateApiKey`/`createApiKey` are CRUD op-ids, refused here by
 /// `RpcScope::Budget` before they could reach `AuthzStoreImpl`'s signer).
 #[expect(
     clippy::too_many_arguments,
@@ -2825,62 +3414,16 @@ pub async fn start_budget_server(
     api_key_expiry.validate()?;
     oauth2.rbac.validate()?;
 
-    // ADR-0007: load whatever is genuinely active in the DB right now, so a fresh startup always
-    // agrees with the last successful activation, exactly like `start_api_server`'s identical load
-    // did before the cutover.
-    let policy_store = Arc::new(
-        lightbridge_authz_budget::PolicyStore::load_active_from_db(
-            pool.clone(),
-            BUDGET_POLICY_SET_ID,
-            BUDGET_POLICY_EVALUATION_BUDGET,
-        )
-        .await
-        .map_err(|e| Error::Server(format!("failed to load active budget policy: {e}")))?,
-    );
-
-    // Self-service refill and the admin review queue (#191, PR 3.4) -- see `start_api_server`'s
-    // identical construction for the full spend-reader degrade-not-fail reasoning
-    // (`UnavailableSpendReader` on a missing/unreachable `usage_service`, never a hard startup
-    // failure or an auto-approve).
-    let budget_repo = Arc::new(lightbridge_authz_budget::repo::BudgetRepo::new(
-        pool.clone(),
-    ));
-    let augmentation_repo = Arc::new(lightbridge_authz_budget::AugmentationRepo::new(
-        pool.clone(),
-    ));
-    let policy_engine: Arc<dyn lightbridge_authz_budget::PolicyEngine> = policy_store.engine();
-    let spend_reader: Arc<dyn lightbridge_authz_budget::SpendReader> = match usage_service {
-        Some(usage_service) => Arc::new(
-            lightbridge_authz_budget::UsageServiceSpendReader::new(
-                usage_service.base_url.clone(),
-                usage_service.insecure_skip_verify,
-                usage_service.ca_bundle_path.as_deref(),
-                usage_service.client_cert_path.as_deref(),
-                usage_service.client_key_path.as_deref(),
-                std::time::Duration::from_millis(usage_service.timeout_ms),
-            )
-            .map_err(|e| {
-                Error::Server(format!("failed to build usage-service spend reader: {e}"))
-            })?,
-        ),
-        None => {
-            tracing::warn!(
-                "usage_service is not configured -- budget refill spend facts will report \
-                 Unavailable, and self-service refill decisions that depend on them will fail \
-                 closed to manual review"
-            );
-            Arc::new(lightbridge_authz_budget::UnavailableSpendReader)
-        }
-    };
-    let refill_service = Arc::new(lightbridge_authz_budget::RefillService::new(
-        budget_repo.clone(),
-        augmentation_repo.clone(),
-        policy_engine,
-        spend_reader,
-    ));
-    let review_service = Arc::new(lightbridge_authz_budget::ReviewService::new(
-        budget_repo.clone(),
-        augmentation_repo,
+    // The same budget-domain services `start_api_server` builds -- see `load_budget_services`.
+    let BudgetServices {
+        policy_store,
+        budget_repo,
+        refill_service,
+        review_service,
+    } = load_budget_services(pool.clone(), usage_service.as_ref()).await?;
+    // Each period's opening `base` grant per account -- see `budget_rollover`'s module doc.
+    budget_rollover::spawn_budget_rollover(Arc::new(
+        lightbridge_authz_budget::RolloverService::new(budget_repo.clone(), policy_store.engine()),
     ));
 
     let readiness_pool = pool.clone();
@@ -2989,12 +3532,19 @@ async fn readiness_handler(pool: Arc<dyn DbPoolTrait>) -> StatusCode {
 #[openapi(
     paths(
         crate::handlers::introspect::introspect_api_key,
-        crate::handlers::idp::resolve_context
+        crate::handlers::introspect::introspect_api_keys_batch,
+        crate::handlers::idp::resolve_context,
+        crate::handlers::leaked::report_leaked_credentials
     ),
     components(
         schemas(
             crate::models::IntrospectRequest,
             crate::models::IntrospectResponse,
+            crate::models::BatchIntrospectRequest,
+            crate::models::BatchIntrospectResponse,
+            crate::models::LeakedCredentialReport,
+            crate::models::LeakedCredentialReportResponse,
+            crate::models::LeakedCredentialResult,
             lightbridge_authz_core::ApiKey,
             lightbridge_authz_core::Project,
             lightbridge_authz_core::Account,
@@ -3004,7 +3554,8 @@ async fn readiness_handler(pool: Arc<dyn DbPoolTrait>) -> StatusCode {
     ),
     tags(
         (name = "authorino", description = "Authorino integration"),
-        (name = "idp", description = "Identity request resolution")
+        (name = "idp", description = "Identity request resolution"),
+        (name = "leaked-credentials", description = "Secret-scanner leak reports")
     )
 )]
 struct OpaDoc;
@@ -3086,12 +3637,43 @@ mod tests {
         fn fail_closed_floor_micros(&self) -> i64 {
             6_000_000
         }
+
+        fn tier_ladder(&self) -> lightbridge_authz_budget::TierLadder {
+            lightbridge_authz_budget::TierLadder::legacy()
+        }
     }
 
     fn lazy_policy_engine() -> Arc<dyn lightbridge_authz_budget::PolicyEngine> {
         Arc::new(UnusedPolicyEngine)
     }
 
+    #[test]
+    fn restriction_lists_refuse_shapes_the_permissive_reader_would_widen_to_unrestricted() {
+        let arg = |json: serde_json::Value| Some(cratestack::Json(json_to_cratestack_value(json)));
+
+        assert_eq!(
+            restriction_list_from_json_arg(arg(serde_json::json!(["batch"])), "scopes")
+                .expect("a string array is accepted"),
+            Some(vec!["batch".to_string()])
+        );
+        assert_eq!(
+            restriction_list_from_json_arg(None, "scopes").expect("absent is accepted"),
+            None
+        );
+        for malformed in [
+            serde_json::json!("batch"),
+            serde_json::json!({"scopes": ["batch"]}),
+            serde_json::json!(["batch", 7]),
+        ] {
+            let err = restriction_list_from_json_arg(arg(malformed.clone()), "scopes")
+                .expect_err("a malformed restriction must not read as unrestricted");
+            assert!(
+                matches!(err, CratestackError::BadRequest(ref m) if m.starts_with("scopes ")),
+                "{malformed}: got {err:?}"
+            );
+        }
+    }
+
     #[test]
     fn normalize_rpc_base_path_handles_unset_and_root() {
         // Unset / empty / bare-slash all mean "root mount" (caller uses `merge`).
@@ -3135,6 +3717,8 @@ mod tests {
             audience: None,
             signing: None,
             token_exchange: None,
+            device_authorization: None,
+            authorization_code: None,
             rbac: Default::default(),
             clients: Vec::new(),
         }
@@ -3160,6 +3744,9 @@ mod tests {
             audience: None,
             ttl_seconds: 7_776_000,
             max_key_age_days: 30,
+            rotation_lead_seconds: 86_400,
+            algorithm: lightbridge_authz_core::config::SigningAlgorithm::Rs256,
+            key_encryption: None,
         }
     }
 
@@ -3326,6 +3913,188 @@ mod tests {
         assert!(result.is_some());
     }
 
+    fn device_cfg() -> lightbridge_authz_core::config::Oauth2DeviceAuthorization {
+        lightbridge_authz_core::config::Oauth2DeviceAuthorization {
+            enabled: true,
+            code_ttl_seconds: 600,
+            upstream: lightbridge_authz_core::config::Oauth2Upstream {
+                authorization_endpoint:
+                    "https://kc.example.test/realms/r/protocol/openid-connect/auth".to_string(),
+                token_endpoint: "https://kc.example.test/realms/r/protocol/openid-connect/token"
+                    .to_string(),
+                client_id: "authz-device".to_string(),
+                client_secret: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn build_token_exchange_state_rejects_device_authorization_without_token_exchange() {
+        let mut oauth2 = base_oauth2(Oauth2Type::SelfSigned);
+        oauth2.signing = Some(signing_cfg());
+        oauth2.device_authorization = Some(device_cfg());
+        let Err(err) = build_token_exchange_state(
+            &oauth2,
+            lazy_signing_repo(),
+            lazy_budget_repo(),
+            lazy_policy_engine(),
+            noop_bearer(),
+            UNREACHABLE_REDIS_URL,
+            None,
+        ) else {
+            panic!("expected an error for the device grant without token exchange");
+        };
+        assert!(format!("{err}").contains("requires oauth2.token_exchange"));
+    }
+
+    #[tokio::test]
+    async fn build_token_exchange_state_rejects_invalid_device_authorization_config() {
+        let mut zero_ttl = device_cfg();
+        zero_ttl.code_ttl_seconds = 0;
+        let mut no_client = device_cfg();
+        no_client.upstream.client_id = " ".to_string();
+        let mut bad_url = device_cfg();
+        bad_url.upstream.token_endpoint = "not a url".to_string();
+
+        for (cfg, expected) in [
+            (zero_ttl, "code_ttl_seconds"),
+            (no_client, "client_id"),
+            (bad_url, "token_endpoint"),
+        ] {
+            let mut oauth2 = base_oauth2(Oauth2Type::SelfSigned);
+            oauth2.signing = Some(signing_cfg());
+            oauth2.token_exchange = Some(exchange_cfg());
+            oauth2.device_authorization = Some(cfg);
+            let Err(err) = build_token_exchange_state(
+                &oauth2,
+                lazy_signing_repo(),
+                lazy_budget_repo(),
+                lazy_policy_engine(),
+                noop_bearer(),
+                UNREACHABLE_REDIS_URL,
+                None,
+            ) else {
+                panic!("expected an error mentioning {expected}");
+            };
+            assert!(format!("{err}").contains(expected), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn build_token_exchange_state_mounts_device_authorization_when_enabled() {
+        let mut oauth2 = base_oauth2(Oauth2Type::SelfSigned);
+        oauth2.signing = Some(signing_cfg());
+        oauth2.token_exchange = Some(exchange_cfg());
+        oauth2.device_authorization = Some(device_cfg());
+        let state = build_token_exchange_state(
+            &oauth2,
+            lazy_signing_repo(),
+            lazy_budget_repo(),
+            lazy_policy_engine(),
+            noop_bearer(),
+            UNREACHABLE_REDIS_URL,
+            None,
+        )
+        .unwrap()
+        .expect("token exchange is enabled");
+        assert!(state.device_authorization().is_some());
+        assert!(well_known_mount_params(&oauth2).2);
+    }
+
+    fn authorization_code_cfg() -> lightbridge_authz_core::config::Oauth2AuthorizationCode {
+        lightbridge_authz_core::config::Oauth2AuthorizationCode {
+            enabled: true,
+            code_ttl_seconds: 60,
+            request_ttl_seconds: 600,
+            upstream: device_cfg().upstream,
+        }
+    }
+
+    fn browser_client(redirect_uris: &[&str]) -> lightbridge_authz_core::config::OauthClient {
+        lightbridge_authz_core::config::OauthClient {
+            client_id: "lightbridge-web".to_string(),
+            client_type: OauthClientType::Public,
+            scopes: vec!["openid".to_string()],
+            grant_types: vec!["authorization_code".to_string()],
+            allowed_audiences: Vec::new(),
+            redirect_uris: redirect_uris.iter().map(|u| u.to_string()).collect(),
+            jwks: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn build_token_exchange_state_rejects_authorization_code_without_token_exchange() {
+        let mut oauth2 = base_oauth2(Oauth2Type::SelfSigned);
+        oauth2.signing = Some(signing_cfg());
+        oauth2.authorization_code = Some(authorization_code_cfg());
+        let Err(err) = build_token_exchange_state(
+            &oauth2,
+            lazy_signing_repo(),
+            lazy_budget_repo(),
+            lazy_policy_engine(),
+            noop_bearer(),
+            UNREACHABLE_REDIS_URL,
+            None,
+        ) else {
+            panic!("expected an error for authorization_code without token exchange");
+        };
+        assert!(format!("{err}").contains("requires oauth2.token_exchange"));
+    }
+
+    #[tokio::test]
+    async fn build_token_exchange_state_rejects_unregistrable_redirect_uris() {
+        for (uris, expected) in [
+            (&[][..], "registers no redirect_uris"),
+            (&["https://app.example.test/*"][..], "redirect_uri"),
+            (&["https://app.example.test/cb#frag"][..], "redirect_uri"),
+            (&["http://app.example.test/cb"][..], "redirect_uri"),
+            (&["/relative/cb"][..], "redirect_uri"),
+        ] {
+            let mut oauth2 = base_oauth2(Oauth2Type::SelfSigned);
+            oauth2.signing = Some(signing_cfg());
+            oauth2.token_exchange = Some(exchange_cfg());
+            oauth2.authorization_code = Some(authorization_code_cfg());
+            oauth2.clients = vec![browser_client(uris)];
+            let Err(err) = build_token_exchange_state(
+                &oauth2,
+                lazy_signing_repo(),
+                lazy_budget_repo(),
+                lazy_policy_engine(),
+                noop_bearer(),
+                UNREACHABLE_REDIS_URL,
+                None,
+            ) else {
+                panic!("expected {uris:?} to be refused");
+            };
+            assert!(format!("{err}").contains(expected), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn build_token_exchange_state_mounts_authorization_code_when_enabled() {
+        let mut oauth2 = base_oauth2(Oauth2Type::SelfSigned);
+        oauth2.signing = Some(signing_cfg());
+        oauth2.token_exchange = Some(exchange_cfg());
+        oauth2.authorization_code = Some(authorization_code_cfg());
+        oauth2.clients = vec![browser_client(&[
+            "https://app.example.test/callback",
+            "http://127.0.0.1:8765/callback",
+        ])];
+        let state = build_token_exchange_state(
+            &oauth2,
+            lazy_signing_repo(),
+            lazy_budget_repo(),
+            lazy_policy_engine(),
+            noop_bearer(),
+            UNREACHABLE_REDIS_URL,
+            None,
+        )
+        .unwrap()
+        .expect("token exchange is enabled");
+        assert!(state.authorization_code().is_some());
+        assert!(well_known_mount_params(&oauth2).3);
+    }
+
     fn opa_openapi() -> Value {
         serde_json::to_value(OpaDoc::openapi()).expect("openapi should serialize")
     }
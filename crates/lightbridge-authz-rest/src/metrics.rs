@@ -0,0 +1,155 @@
+//! The `authz-*` servers' request-path instruments, exported on every probe router's `/metrics`
+//! (`lightbridge_authz_core::metrics`):
+//!
+//! - `authz_introspection_decisions_total{decision, effective_status}` -- every API-key verdict
+//!   reached from the `api_key_validation` view (introspection, the Authorino validate endpoints
+//!   and ext_authz alike), `not_found` for a secret no key matches. A cache hit reaches no
+//!   verdict and is counted by `authz_introspection_cache_hits` instead.
+//! - `authz_token_grants_total{grant_type, outcome}` -- `/oauth2/token` responses: `issued`,
+//!   `rejected` (any 4xx) or `error` (5xx).
+//! - `authz_rpc_duration_seconds{op_id, status}` -- `POST /rpc/<op_id>` on `authz-api` and
+//!   `authz-budget`, measured outside the RBAC gate so refusals are timed too.
+//!
+//! Label values only ever come from closed sets -- an op-id or grant type the server does not
+//! know is reported as `unknown` -- so a caller cannot mint new series by inventing paths.
+
+use std::sync::LazyLock;
+use std::time::Instant;
+
+use axum::{extract::Request, http::StatusCode, middleware::Next, response::Response};
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram};
+
+use crate::rpc_authorize::{BATCH_OP_ID, op_id_from_path, required_permission};
+use crate::token_exchange::{
+    AUTHORIZATION_CODE_GRANT, DEVICE_CODE_GRANT, REFRESH_TOKEN_GRANT, TOKEN_EXCHANGE_GRANT,
+};
+
+/// Upper bounds of `authz_rpc_duration_seconds`' buckets: 5 ms to 10 s.
+const RPC_DURATION_BOUNDARIES: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Built on first use, which is always after `init_tracing` installed the provider.
+static METRICS: LazyLock<AuthzMetrics> = LazyLock::new(AuthzMetrics::new);
+
+struct AuthzMetrics {
+    introspection_decisions: Counter<u64>,
+    token_grants: Counter<u64>,
+    rpc_duration: Histogram<f64>,
+}
+
+impl AuthzMetrics {
+    fn new() -> Self {
+        let meter = lightbridge_authz_core::metrics::meter();
+        Self {
+            introspection_decisions: meter
+                .u64_counter("authz_introspection_decisions")
+                .with_description("API-key verdicts, by decision and effective_status")
+                .build(),
+            token_grants: meter
+                .u64_counter("authz_token_grants")
+                .with_description("Token endpoint responses, by grant type and outcome")
+                .build(),
+            rpc_duration: meter
+                .f64_histogram("authz_rpc_duration_seconds")
+                .with_description("RPC handling time, by op-id and HTTP status")
+                .with_unit("s")
+                .with_boundaries(RPC_DURATION_BOUNDARIES.to_vec())
+                .build(),
+        }
+    }
+}
+
+/// Counts one verdict on an API key. `effective_status` is the view's status after IP
+/// narrowing, or `not_found`.
+pub(crate) fn record_introspection_decision(active: bool, effective_status: &str) {
+    let decision = if active { "allow" } else { "deny" };
+    METRICS.introspection_decisions.add(
+        1,
+        &[
+            KeyValue::new("decision", decision),
+            KeyValue::new("effective_status", effective_status.to_string()),
+        ],
+    );
+}
+
+/// Counts one `/oauth2/token` response by the status it was answered with.
+pub(crate) fn record_token_grant(grant_type: &str, status: StatusCode) {
+    METRICS.token_grants.add(
+        1,
+        &[
+            KeyValue::new("grant_type", grant_type_label(grant_type)),
+            KeyValue::new("outcome", token_outcome(status)),
+        ],
+    );
+}
+
+/// Middleware timing one RPC request. Layer it outermost over the RPC router so the gate's own
+/// refusals are measured along with dispatched calls.
+pub async fn rpc_metrics(request: Request, next: Next) -> Response {
+    let op_id = op_id_label(request.uri().path());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    METRICS.rpc_duration.record(
+        started.elapsed().as_secs_f64(),
+        &[
+            KeyValue::new("op_id", op_id),
+            KeyValue::new("status", i64::from(response.status().as_u16())),
+        ],
+    );
+    response
+}
+
+fn grant_type_label(grant_type: &str) -> &'static str {
+    match grant_type {
+        TOKEN_EXCHANGE_GRANT => TOKEN_EXCHANGE_GRANT,
+        REFRESH_TOKEN_GRANT => REFRESH_TOKEN_GRANT,
+        DEVICE_CODE_GRANT => DEVICE_CODE_GRANT,
+        AUTHORIZATION_CODE_GRANT => AUTHORIZATION_CODE_GRANT,
+        _ => "unknown",
+    }
+}
+
+fn token_outcome(status: StatusCode) -> &'static str {
+    if status.is_success() {
+        "issued"
+    } else if status.is_server_error() {
+        "error"
+    } else {
+        "rejected"
+    }
+}
+
+fn op_id_label(path: &str) -> String {
+    let op_id = op_id_from_path(path);
+    if op_id == BATCH_OP_ID || required_permission(op_id).is_some() {
+        op_id.to_string()
+    } else {
+        "unknown".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_come_from_closed_sets() {
+        assert_eq!(grant_type_label(REFRESH_TOKEN_GRANT), REFRESH_TOKEN_GRANT);
+        assert_eq!(grant_type_label("password"), "unknown");
+
+        assert_eq!(op_id_label("/rpc/model.Account.list"), "model.Account.list");
+        assert_eq!(op_id_label("/rpc/batch"), BATCH_OP_ID);
+        assert_eq!(op_id_label("/rpc/model.Invented.op"), "unknown");
+        assert_eq!(op_id_label("/not-rpc"), "unknown");
+    }
+
+    #[test]
+    fn token_outcomes_follow_the_status_class() {
+        assert_eq!(token_outcome(StatusCode::OK), "issued");
+        assert_eq!(token_outcome(StatusCode::BAD_REQUEST), "rejected");
+        assert_eq!(token_outcome(StatusCode::UNAUTHORIZED), "rejected");
+        assert_eq!(token_outcome(StatusCode::INTERNAL_SERVER_ERROR), "error");
+    }
+}
@@ -11,10 +11,60 @@ pub struct IntrospectRequest {
     /// Optional hint about the token type; ignored (only access tokens are supported).
     #[serde(default)]
     pub token_type_hint: Option<String>,
+    /// The end client's address as seen by the gateway (a bare IP or `ip:port`). Only consulted
+    /// for keys with an IP allowlist, which resolve inactive when it is absent or outside it.
+    #[serde(default)]
+    pub client_ip: Option<String>,
 }
 
-/// RFC 7662 token introspection response. When `active` is false, all other fields are omitted.
+/// Batch introspection request (JSON). At most
+/// [`crate::handlers::introspect::MAX_BATCH_INTROSPECT_TOKENS`] tokens per call.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchIntrospectRequest {
+    /// The credentials to introspect, in the order results should be returned.
+    pub tokens: Vec<String>,
+    /// The end client's address, applied to every token in the batch -- see
+    /// [`IntrospectRequest::client_ip`].
+    #[serde(default)]
+    pub client_ip: Option<String>,
+}
+
+/// Batch introspection response: one entry per requested token, in request order, each exactly
+/// what `POST /v1/authorino/validate/introspect` would have returned for that token alone.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchIntrospectResponse {
+    pub results: Vec<IntrospectResponse>,
+}
+
+/// Leaked-credential report (JSON), sent by a secret scanner that found credentials in public. At
+/// most [`crate::handlers::leaked::MAX_LEAKED_CREDENTIALS_PER_REPORT`] tokens per call.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LeakedCredentialReport {
+    /// Who found them (e.g. `github-secret-scanning`), recorded in each revoked key's
+    /// `revocation_reason`.
+    pub source: String,
+    /// The exposed secrets, in the order results should be returned.
+    pub tokens: Vec<String>,
+}
+
+/// Leaked-credential report response: one entry per reported token, in request order.
 #[derive(Debug, Serialize, ToSchema)]
+pub struct LeakedCredentialReportResponse {
+    pub results: Vec<LeakedCredentialResult>,
+}
+
+/// What this service made of one reported token. Deliberately carries no key or tenant
+/// identifiers: the reporter only learns whether the secret was real.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct LeakedCredentialResult {
+    /// Whether the token is, or was, an API key this service issued (any status).
+    pub real: bool,
+    /// Whether this report revoked it. `false` for a key that was already not `active`.
+    pub revoked: bool,
+}
+
+/// RFC 7662 token introspection response. When `active` is false, all other fields are omitted.
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct IntrospectResponse {
     /// Whether the key is currently valid (exists, `Active`, not expired).
     pub active: bool,
@@ -43,16 +93,23 @@ pub struct IntrospectResponse {
     /// Rate/usage limits of the billing plan, resolved from config.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub billing_plan_limits: Option<lightbridge_authz_core::config::BillingLimits>,
-    /// Models the project is allowed to use (empty/absent means all).
+    /// Models the credential is allowed to use (empty/absent means all). For an API key with its
+    /// own model subset this is the intersection with the project's list (see
+    /// `lightbridge_authz_core::ApiKey::effective_model_access`), otherwise the project's.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub allowed_models: Option<Vec<String>>,
     /// ADR-0018's three-value access-control policy governing which models this project's keys
     /// may reach (`"allow_all"`/`"allowlist"`/`"deny_all"`) -- sourced from the same project row
     /// as `allowed_models` above (no extra query). An unrecognized stored value is parsed
     /// fail-closed to `"deny_all"` by `lightbridge_authz_core::dto::ModelPolicy::from`, never
-    /// silently widened to `"allow_all"`.
+    /// silently widened to `"allow_all"`. Narrowed the same way as `allowed_models` when the key
+    /// carries its own subset: a key list under an `allow_all` project reports `"allowlist"`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model_policy: Option<String>,
+    /// Named scopes the API key is restricted to (`inference`/`embeddings`/`batch`). Absent means
+    /// unrestricted -- always the case for exchange tokens, which have no key row to carry any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
     /// The project's pooled spending ceiling, from the governance tier catalogue (ADR-0006).
     /// Costs no extra query — it rides on the project row already loaded for `allowed_models` —
     /// and keeps the gateway's `x-project-quota` header sourced from the database rather than from
@@ -71,9 +128,27 @@ pub struct IntrospectResponse {
     /// rate-limit rules match with an `Exact` selector — without it those rules can never fire.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quota_tier: Option<String>,
-    /// Expiry as a Unix timestamp, when the key has one.
+    /// Expiry as a Unix timestamp, when the key has one: its `expires_at`, or its rotation
+    /// cut-off if that comes first.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exp: Option<i64>,
+    /// The owning account's budget tier for the current period (e.g. `"b-30"`). This and the
+    /// three fields below are only present when `introspection_budget` is configured; see
+    /// `crate::introspection_budget` for when each is omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_tier: Option<String>,
+    /// The account's effective balance for the current period, in micro-USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_balance_micros: Option<i64>,
+    /// The account's spend for the current period, in micro-USD. Absent when the usage service
+    /// could not report it -- which is not the same as zero.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_spent_micros: Option<i64>,
+    /// Whether known spend exceeds the effective balance. Under `introspection_budget.enforce`
+    /// an exhausted credential introspects inactive instead, so an active response never carries
+    /// `true` there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_exhausted: Option<bool>,
 }
 
 impl IntrospectResponse {
@@ -91,10 +166,15 @@ impl IntrospectResponse {
             billing_plan_limits: None,
             allowed_models: None,
             model_policy: None,
+            scopes: None,
             project_quota: None,
             role: None,
             quota_tier: None,
             exp: None,
+            budget_tier: None,
+            budget_balance_micros: None,
+            budget_spent_micros: None,
+            budget_exhausted: None,
         }
     }
 }
@@ -41,21 +41,25 @@ impl ClientStore for ConfigClientStore {
 }
 
 /// Maps our config shape onto `authkestra_op::client::ClientRegistration`'s 9 fields.
-/// `client_secret_hash` is always `None` and `redirect_uris` is always empty -- see
-/// `OauthClient`'s own doc comment (`lightbridge-authz-core::config`) for why both are permanent,
-/// not placeholders.
+/// `client_secret_hash` is always `None` -- see `OauthClient`'s own doc comment
+/// (`lightbridge-authz-core::config`) for why that is permanent, not a placeholder.
+/// `require_pkce` is set for every client allowed the `authorization_code` grant, which makes
+/// `authkestra_op::handlers::authorize::handle_authorize` insist on an S256 challenge; no other
+/// grant reads it.
 fn to_registration(client: &OauthClient) -> ClientRegistration {
+    let grant_types: Vec<GrantType> = client
+        .grant_types
+        .iter()
+        .map(|g| parse_grant_type(g))
+        .collect();
+    let require_pkce = grant_types.contains(&GrantType::AuthorizationCode);
     ClientRegistration {
         client_id: client.client_id.clone(),
         client_secret_hash: None,
-        redirect_uris: Vec::new(),
-        grant_types: client
-            .grant_types
-            .iter()
-            .map(|g| parse_grant_type(g))
-            .collect(),
+        redirect_uris: client.redirect_uris.clone(),
+        grant_types,
         scopes: client.scopes.clone(),
-        require_pkce: false,
+        require_pkce,
         allowed_audiences: client.allowed_audiences.clone(),
         token_endpoint_auth_method: Some(match client.client_type {
             OauthClientType::Public => TokenEndpointAuthMethod::NoAuth,
@@ -90,6 +94,7 @@ mod tests {
             scopes: vec!["openid".to_string()],
             grant_types: vec!["urn:ietf:params:oauth:grant-type:token-exchange".to_string()],
             allowed_audiences: vec![client_id.to_string()],
+            redirect_uris: Vec::new(),
             jwks: None,
         }
     }
@@ -128,6 +133,25 @@ mod tests {
         assert!(store.has_confidential_client());
     }
 
+    #[tokio::test]
+    async fn authorization_code_clients_require_pkce_and_keep_their_redirect_uris() {
+        let mut browser = client("lightbridge-web", OauthClientType::Public);
+        browser.grant_types = vec!["authorization_code".to_string()];
+        browser.redirect_uris = vec!["https://app.example.test/callback".to_string()];
+        let store = ConfigClientStore::from_config(&[
+            browser,
+            client("lightbridge-ss", OauthClientType::Public),
+        ]);
+
+        let found = store.find_client("lightbridge-web").await.unwrap().unwrap();
+        assert!(found.require_pkce);
+        assert!(found.allows_redirect_uri("https://app.example.test/callback"));
+        assert!(!found.allows_redirect_uri("https://app.example.test/callback/"));
+
+        let machine = store.find_client("lightbridge-ss").await.unwrap().unwrap();
+        assert!(!machine.require_pkce);
+    }
+
     #[tokio::test]
     async fn public_only_registry_has_no_confidential_client() {
         let store =
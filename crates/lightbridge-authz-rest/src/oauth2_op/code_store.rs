@@ -0,0 +1,246 @@
+//! `authkestra_op::code::AuthorizationCodeStore` backed by the `authorization_codes` table,
+//! replacing the permanent `NoAuthorizationCodeStore` stub ADR-0011 Decision 3 shipped, plus the
+//! `authorization_requests` bookkeeping `/authorize` needs while the browser is away at Keycloak.
+//!
+//! Same representational gaps `device_store` documents, handled the same way:
+//!
+//! - **Plaintext vs hash.** Only the code's SHA-256 (`hash_api_key`) is persisted, so a code read
+//!   back out of this store carries the row's hash in `code`, never the plaintext.
+//! - **No room for the request's extensions.** `AuthorizationCode` has no `project_id` field, and
+//!   `store_code` no parameter for one. `/authorize/callback` reaches
+//!   [`DbAuthorizationCodeStore::store_scoped`] through `RequestScopedOpStore` instead.
+//!
+//! The token endpoint's `authorization_code` grant
+//! (`TokenExchangeOpStore::handle_authorization_code`) redeems through the typed
+//! [`DbAuthorizationCodeStore::consume`] rather than the trait's `consume_code`, since it needs
+//! `project_id` and the typed identity columns back.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use authkestra_engine::auth::state::Identity;
+use authkestra_op::OpError;
+use authkestra_op::code::{AuthorizationCode, AuthorizationCodeStore};
+use chrono::{DateTime, Utc};
+use lightbridge_authz_api_key::entities::authorization_code_row::{
+    AuthorizationCodeRow, AuthorizationRequestRow,
+};
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_core::async_trait;
+use lightbridge_authz_core::crypto::hash_api_key;
+use lightbridge_authz_core::cuid::cuid2;
+
+/// The only PKCE method this service accepts, on both the client's leg and its own leg toward
+/// Keycloak.
+pub const PKCE_METHOD_S256: &str = "S256";
+
+/// [`Identity::provider_id`] stamped on a code's identity, mirroring `device_store`'s.
+const IDENTITY_PROVIDER_ID: &str = "keycloak";
+const ATTR_EMAIL_VERIFIED: &str = "email_verified";
+const ATTR_AUTH_TIME: &str = "auth_time";
+
+pub struct DbAuthorizationCodeStore {
+    repo: Arc<StoreRepo>,
+}
+
+impl DbAuthorizationCodeStore {
+    pub fn new(repo: Arc<StoreRepo>) -> Self {
+        Self { repo }
+    }
+
+    /// Persists a freshly issued code together with the `/authorize` request's own `project_id`
+    /// extension (see this module's doc comment). Refuses a code without an S256 challenge: every
+    /// client allowed this grant is registered with `require_pkce`, so one reaching here means
+    /// that invariant broke.
+    pub async fn store_scoped(
+        &self,
+        code: AuthorizationCode,
+        project_id: Option<String>,
+    ) -> Result<(), OpError> {
+        let (Some(code_challenge), Some(PKCE_METHOD_S256)) =
+            (code.code_challenge, code.code_challenge_method.as_deref())
+        else {
+            return Err(OpError::InvalidCode);
+        };
+        let row = AuthorizationCodeRow {
+            id: cuid2(),
+            code_hash: hash_api_key(&code.code),
+            client_id: code.client_id,
+            redirect_uri: code.redirect_uri,
+            scope: code.scope,
+            code_challenge,
+            code_challenge_method: PKCE_METHOD_S256.to_string(),
+            nonce: code.nonce.filter(|s| !s.is_empty()),
+            project_id: project_id.filter(|s| !s.trim().is_empty()),
+            email_verified: code
+                .identity
+                .attributes
+                .get(ATTR_EMAIL_VERIFIED)
+                .and_then(|v| v.parse().ok()),
+            auth_time: code
+                .identity
+                .attributes
+                .get(ATTR_AUTH_TIME)
+                .and_then(|v| v.parse().ok()),
+            subject: code.identity.external_id,
+            email: code.identity.email,
+            created_at: Utc::now(),
+            expires_at: code.expires_at,
+            consumed_at: None,
+        };
+        self.repo
+            .create_authorization_code(row)
+            .await
+            .map_err(storage_error("failed to persist authorization code"))
+    }
+
+    /// Redeems a code exactly once. See `StoreRepo::consume_authorization_code`.
+    pub async fn consume(
+        &self,
+        code: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<AuthorizationCodeRow>, OpError> {
+        self.repo
+            .consume_authorization_code(&hash_api_key(code), now)
+            .await
+            .map_err(storage_error("failed to consume authorization code"))
+    }
+
+    /// Records a validated `/authorize` request under the hashed `state` sent to Keycloak.
+    pub async fn begin_request(
+        &self,
+        state: &str,
+        mut request: AuthorizationRequestRow,
+    ) -> Result<(), OpError> {
+        request.state_hash = hash_api_key(state);
+        self.repo
+            .create_authorization_request(request)
+            .await
+            .map_err(storage_error("failed to persist authorization request"))
+    }
+
+    /// Takes the pending request a Keycloak callback's `state` belongs to, exactly once.
+    pub async fn take_request(
+        &self,
+        state: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<AuthorizationRequestRow>, OpError> {
+        self.repo
+            .take_authorization_request(&hash_api_key(state), now)
+            .await
+            .map_err(storage_error("failed to look up authorization request"))
+    }
+}
+
+fn storage_error(
+    message: &'static str,
+) -> impl Fn(lightbridge_authz_core::error::Error) -> OpError {
+    move |e| {
+        tracing::error!(error = %e, "{message}");
+        OpError::Storage
+    }
+}
+
+/// Builds the `Identity` `/authorize/callback` hands `handle_authorize`, carrying the two claims
+/// `AuthorizationCode` has no typed field for in `attributes`
+/// ([`DbAuthorizationCodeStore::store_scoped`] reads them back out).
+pub fn code_identity(
+    subject: String,
+    email: Option<String>,
+    email_verified: Option<bool>,
+    auth_time: Option<i64>,
+) -> Identity {
+    let mut attributes = HashMap::new();
+    if let Some(verified) = email_verified {
+        attributes.insert(ATTR_EMAIL_VERIFIED.to_string(), verified.to_string());
+    }
+    if let Some(auth_time) = auth_time {
+        attributes.insert(ATTR_AUTH_TIME.to_string(), auth_time.to_string());
+    }
+    Identity {
+        provider_id: IDENTITY_PROVIDER_ID.to_string(),
+        external_id: subject,
+        email,
+        username: None,
+        attributes,
+    }
+}
+
+fn row_to_code(row: AuthorizationCodeRow) -> AuthorizationCode {
+    let identity = code_identity(row.subject, row.email, row.email_verified, row.auth_time);
+    AuthorizationCode {
+        // See this module's doc comment: the plaintext was never stored.
+        code: row.code_hash,
+        client_id: row.client_id,
+        redirect_uri: row.redirect_uri,
+        scope: row.scope,
+        code_challenge: Some(row.code_challenge),
+        code_challenge_method: Some(row.code_challenge_method),
+        nonce: row.nonce,
+        identity,
+        expires_at: row.expires_at,
+        used: row.consumed_at.is_some(),
+    }
+}
+
+#[async_trait]
+impl AuthorizationCodeStore for DbAuthorizationCodeStore {
+    async fn store_code(&self, code: AuthorizationCode) -> Result<(), OpError> {
+        self.store_scoped(code, None).await
+    }
+
+    async fn consume_code(&self, code: &str) -> Result<Option<AuthorizationCode>, OpError> {
+        Ok(self.consume(code, Utc::now()).await?.map(row_to_code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row() -> AuthorizationCodeRow {
+        AuthorizationCodeRow {
+            id: "id".to_string(),
+            code_hash: "hash".to_string(),
+            client_id: "web".to_string(),
+            redirect_uri: "https://app.example.test/callback".to_string(),
+            scope: "openid".to_string(),
+            code_challenge: "challenge".to_string(),
+            code_challenge_method: PKCE_METHOD_S256.to_string(),
+            nonce: Some("n-1".to_string()),
+            project_id: None,
+            subject: "kc-sub".to_string(),
+            email: Some("user@example.test".to_string()),
+            email_verified: Some(true),
+            auth_time: Some(1_700_000_000),
+            created_at: Utc::now(),
+            expires_at: Utc::now(),
+            consumed_at: Some(Utc::now()),
+        }
+    }
+
+    #[test]
+    fn consumed_row_maps_to_a_keycloak_identity_without_the_plaintext() {
+        let code = row_to_code(row());
+        assert_eq!(code.code, "hash");
+        assert!(code.used);
+        assert_eq!(code.identity.provider_id, "keycloak");
+        assert_eq!(code.identity.external_id, "kc-sub");
+        assert_eq!(
+            code.identity.attributes.get("auth_time").unwrap(),
+            "1700000000"
+        );
+        assert_eq!(
+            code.identity.attributes.get("email_verified").unwrap(),
+            "true"
+        );
+        assert_eq!(code.code_challenge_method.as_deref(), Some("S256"));
+    }
+
+    #[test]
+    fn identity_omits_claims_keycloak_did_not_send() {
+        let identity = code_identity("kc-sub".to_string(), None, None, None);
+        assert!(identity.attributes.is_empty());
+        assert!(identity.email.is_none());
+    }
+}
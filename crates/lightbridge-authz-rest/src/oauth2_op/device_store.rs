@@ -0,0 +1,377 @@
+//! `authkestra_op::device::DeviceCodeStore` backed by the `device_authorizations` table (ADR-0012,
+//! Decisions 3 and 7), replacing the permanent `NoDeviceCodeStore` stub ADR-0011 Decision 3
+//! shipped.
+//!
+//! Same two representational gaps `refresh_store` documents, handled the same way:
+//!
+//! - **Plaintext vs hash.** `DeviceCodeSession.device_code` is the bearer secret the CLI polls
+//!   with. Only its SHA-256 (`hash_api_key`) is persisted, so a session read back out of this
+//!   store carries the row's hash in `device_code`, never the plaintext.
+//! - **No room for the request's extensions.** `DeviceCodeSession` has no `project_id`/`nonce`
+//!   fields, and the trait's `store_device_code` no parameter for them. The device-authorization
+//!   endpoint reaches [`DbDeviceCodeStore::store_scoped`] through `RequestScopedOpStore` instead,
+//!   exactly how the token endpoint already threads `project_id` into the exchange grant.
+//!
+//! The trait is implemented in full so `OpStore`'s supertrait bound is satisfied by a real store,
+//! but the token endpoint's device grant (`TokenExchangeOpStore::handle_device_code`) and the
+//! verification page (`crate::device_authorization`) use the typed, CAS-shaped methods below
+//! directly: upstream's own `handle_device_code` re-`store`s a whole session to record a poll and
+//! mints through the plain `issue_user_token` with none of this service's claims, so it is never
+//! reached.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use authkestra_engine::auth::state::Identity;
+use authkestra_op::OpError;
+use authkestra_op::device::{DeviceCodeSession, DeviceCodeStatus, DeviceCodeStore};
+use chrono::{DateTime, Utc};
+use lightbridge_authz_api_key::entities::device_authorization_row::{
+    DeviceApproval, DeviceAuthorizationRow, NewDeviceAuthorization,
+};
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_core::async_trait;
+use lightbridge_authz_core::crypto::hash_api_key;
+use lightbridge_authz_core::cuid::cuid2;
+
+/// The minimum polling interval, in seconds, stored on every session and enforced with
+/// `slow_down`. Matches the `interval` `authkestra_op::handlers::device_authorization` hard-codes
+/// into the device-authorization response, so the value the CLI is told and the value this
+/// service enforces cannot drift apart.
+pub const DEVICE_POLL_INTERVAL_SECS: i32 = 5;
+
+/// [`Identity::provider_id`] stamped on an approved session's identity, mirroring
+/// `refresh_store`'s -- the subject is always a Keycloak login the browser leg brokered.
+const IDENTITY_PROVIDER_ID: &str = "keycloak";
+const ATTR_EMAIL_VERIFIED: &str = "email_verified";
+const ATTR_AUTH_TIME: &str = "auth_time";
+
+/// Canonical form of a user code as typed at `/device`: ASCII-uppercased, with the separators
+/// and whitespace a user might add while transcribing (RFC 8628 §6.1) dropped. Issued codes are
+/// already uppercase and separator-free, so this is the identity on them.
+pub fn normalize_user_code(raw: &str) -> String {
+    raw.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+pub struct DbDeviceCodeStore {
+    repo: Arc<StoreRepo>,
+}
+
+impl DbDeviceCodeStore {
+    pub fn new(repo: Arc<StoreRepo>) -> Self {
+        Self { repo }
+    }
+
+    /// Persists a freshly issued `pending` session together with the device-authorization
+    /// request's own `project_id`/`nonce` extensions (see this module's doc comment).
+    pub async fn store_scoped(
+        &self,
+        session: DeviceCodeSession,
+        project_id: Option<String>,
+        nonce: Option<String>,
+    ) -> Result<(), OpError> {
+        if !matches!(session.status, DeviceCodeStatus::Pending) {
+            return Err(OpError::Storage);
+        }
+        let new = NewDeviceAuthorization {
+            id: cuid2(),
+            device_code_hash: hash_api_key(&session.device_code),
+            user_code: normalize_user_code(&session.user_code),
+            client_id: session.client_id,
+            scope: session.scope,
+            project_id: project_id.filter(|s| !s.trim().is_empty()),
+            nonce: nonce.filter(|s| !s.is_empty()),
+            interval_secs: DEVICE_POLL_INTERVAL_SECS,
+            created_at: Utc::now(),
+            expires_at: session.expires_at,
+        };
+        self.repo
+            .create_device_authorization(new)
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "failed to persist device authorization");
+                OpError::Storage
+            })?;
+        Ok(())
+    }
+
+    /// Unfiltered lookup by plaintext device code; see
+    /// `StoreRepo::find_device_authorization_by_code_hash`.
+    pub async fn find(&self, device_code: &str) -> Result<Option<DeviceAuthorizationRow>, OpError> {
+        self.repo
+            .find_device_authorization_by_code_hash(&hash_api_key(device_code))
+            .await
+            .map_err(storage_error("failed to look up device authorization"))
+    }
+
+    /// Records a poll; returns the poll time it replaced. See
+    /// `StoreRepo::record_device_authorization_poll`.
+    pub async fn record_poll(
+        &self,
+        device_code: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Option<DateTime<Utc>>>, OpError> {
+        self.repo
+            .record_device_authorization_poll(&hash_api_key(device_code), now)
+            .await
+            .map_err(storage_error("failed to record device authorization poll"))
+    }
+
+    /// Redeems a decided session exactly once. See `StoreRepo::consume_device_authorization`.
+    pub async fn consume(
+        &self,
+        device_code: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>, OpError> {
+        self.repo
+            .consume_device_authorization(&hash_api_key(device_code), now)
+            .await
+            .map_err(storage_error("failed to consume device authorization"))
+    }
+
+    /// The `pending` session a typed user code names, if any.
+    pub async fn find_pending_by_user_code(
+        &self,
+        user_code: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>, OpError> {
+        self.repo
+            .find_pending_device_authorization_by_user_code(&normalize_user_code(user_code), now)
+            .await
+            .map_err(storage_error("failed to look up device authorization"))
+    }
+
+    /// Starts the browser leg for `user_code`: stores the hashed `state` and the PKCE verifier
+    /// the Keycloak callback will be checked against.
+    pub async fn begin_verification(
+        &self,
+        user_code: &str,
+        state: &str,
+        code_verifier: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>, OpError> {
+        self.repo
+            .begin_device_verification(
+                &normalize_user_code(user_code),
+                &hash_api_key(state),
+                code_verifier,
+                now,
+            )
+            .await
+            .map_err(storage_error("failed to start device verification"))
+    }
+
+    /// The `pending` session a Keycloak callback's `state` belongs to, if any.
+    pub async fn find_pending_by_state(
+        &self,
+        state: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>, OpError> {
+        self.repo
+            .find_pending_device_authorization_by_state(&hash_api_key(state), now)
+            .await
+            .map_err(storage_error("failed to look up device verification"))
+    }
+
+    pub async fn approve(
+        &self,
+        state: &str,
+        approval: DeviceApproval,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>, OpError> {
+        self.repo
+            .approve_device_authorization(&hash_api_key(state), approval, now)
+            .await
+            .map_err(storage_error("failed to approve device authorization"))
+    }
+
+    pub async fn deny(
+        &self,
+        user_code: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DeviceAuthorizationRow>, OpError> {
+        self.repo
+            .deny_device_authorization(&normalize_user_code(user_code), now)
+            .await
+            .map_err(storage_error("failed to deny device authorization"))
+    }
+}
+
+fn storage_error(
+    message: &'static str,
+) -> impl Fn(lightbridge_authz_core::error::Error) -> OpError {
+    move |e| {
+        tracing::error!(error = %e, "{message}");
+        OpError::Storage
+    }
+}
+
+/// Maps a row back onto the upstream session shape. `consumed`/`expired` rows have no
+/// `DeviceCodeStatus` of their own and read as absent -- upstream's own stores delete a session
+/// on consume, so "gone" is the state its callers expect after one.
+fn row_to_session(row: DeviceAuthorizationRow) -> Option<DeviceCodeSession> {
+    let status = match row.status.as_str() {
+        "pending" => DeviceCodeStatus::Pending,
+        "denied" => DeviceCodeStatus::Denied,
+        "approved" => {
+            let mut attributes = HashMap::new();
+            if let Some(verified) = row.email_verified {
+                attributes.insert(ATTR_EMAIL_VERIFIED.to_string(), verified.to_string());
+            }
+            if let Some(auth_time) = row.auth_time {
+                attributes.insert(ATTR_AUTH_TIME.to_string(), auth_time.to_string());
+            }
+            DeviceCodeStatus::Approved(Identity {
+                provider_id: IDENTITY_PROVIDER_ID.to_string(),
+                external_id: row.subject?,
+                email: row.email,
+                username: None,
+                attributes,
+            })
+        }
+        _ => return None,
+    };
+    Some(DeviceCodeSession {
+        // See this module's doc comment: the plaintext was never stored.
+        device_code: row.device_code_hash,
+        user_code: row.user_code,
+        client_id: row.client_id,
+        scope: row.scope,
+        expires_at: row.expires_at,
+        status,
+        last_polled_at: row.last_polled_at,
+    })
+}
+
+#[async_trait]
+impl DeviceCodeStore for DbDeviceCodeStore {
+    async fn store_device_code(&self, session: DeviceCodeSession) -> Result<(), OpError> {
+        self.store_scoped(session, None, None).await
+    }
+
+    async fn get_device_code(
+        &self,
+        device_code: &str,
+    ) -> Result<Option<DeviceCodeSession>, OpError> {
+        Ok(self.find(device_code).await?.and_then(row_to_session))
+    }
+
+    async fn get_by_user_code(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<DeviceCodeSession>, OpError> {
+        Ok(self
+            .find_pending_by_user_code(user_code, Utc::now())
+            .await?
+            .and_then(row_to_session))
+    }
+
+    /// Only the transitions this table can express without the browser leg's `state`: recording
+    /// a poll on a `pending` session, and denying one. Approval is written exclusively by the
+    /// verification callback ([`DbDeviceCodeStore::approve`]), keyed on the `state` Keycloak
+    /// echoed back, so an `Approved` session here is refused rather than trusted.
+    async fn update_device_code(&self, session: DeviceCodeSession) -> Result<(), OpError> {
+        let now = Utc::now();
+        let updated = match session.status {
+            DeviceCodeStatus::Pending => self
+                .record_poll(&session.device_code, session.last_polled_at.unwrap_or(now))
+                .await?
+                .is_some(),
+            DeviceCodeStatus::Denied => self.deny(&session.user_code, now).await?.is_some(),
+            DeviceCodeStatus::Approved(_) => false,
+        };
+        if updated {
+            Ok(())
+        } else {
+            Err(OpError::Storage)
+        }
+    }
+
+    async fn delete_device_code(&self, device_code: &str) -> Result<(), OpError> {
+        self.repo
+            .delete_device_authorization(&hash_api_key(device_code))
+            .await
+            .map_err(storage_error("failed to delete device authorization"))
+    }
+
+    async fn consume_device_code(
+        &self,
+        device_code: &str,
+    ) -> Result<Option<DeviceCodeSession>, OpError> {
+        // `consume_device_authorization` returns the row in the status it was claimed in, not
+        // `consumed`, so the session handed back still tells approval from refusal.
+        Ok(self
+            .consume(device_code, Utc::now())
+            .await?
+            .and_then(row_to_session))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(status: &str, subject: Option<&str>) -> DeviceAuthorizationRow {
+        DeviceAuthorizationRow {
+            id: "id".to_string(),
+            device_code_hash: "hash".to_string(),
+            user_code: "ABCD1234".to_string(),
+            client_id: "cli".to_string(),
+            scope: "openid".to_string(),
+            project_id: None,
+            nonce: None,
+            status: status.to_string(),
+            subject: subject.map(str::to_string),
+            email: Some("user@example.test".to_string()),
+            email_verified: Some(true),
+            auth_time: Some(1_700_000_000),
+            verification_code_verifier: None,
+            interval_secs: DEVICE_POLL_INTERVAL_SECS,
+            created_at: Utc::now(),
+            expires_at: Utc::now(),
+            last_polled_at: None,
+        }
+    }
+
+    #[test]
+    fn user_codes_normalize_case_and_separators() {
+        assert_eq!(normalize_user_code("abcd-1234"), "ABCD1234");
+        assert_eq!(normalize_user_code(" AbCd 12 34 "), "ABCD1234");
+        assert_eq!(normalize_user_code("ABCD1234"), "ABCD1234");
+    }
+
+    #[test]
+    fn approved_row_maps_to_a_keycloak_identity() {
+        let session = row_to_session(row("approved", Some("kc-sub"))).unwrap();
+        let DeviceCodeStatus::Approved(identity) = session.status else {
+            panic!("expected an approved session");
+        };
+        assert_eq!(identity.provider_id, "keycloak");
+        assert_eq!(identity.external_id, "kc-sub");
+        assert_eq!(identity.attributes.get("auth_time").unwrap(), "1700000000");
+        assert_eq!(identity.attributes.get("email_verified").unwrap(), "true");
+    }
+
+    #[test]
+    fn terminal_rows_read_as_absent() {
+        assert!(row_to_session(row("consumed", Some("kc-sub"))).is_none());
+        assert!(row_to_session(row("expired", None)).is_none());
+        // The table's CHECK makes this unreachable; the mapping still refuses it.
+        assert!(row_to_session(row("approved", None)).is_none());
+    }
+
+    #[test]
+    fn pending_and_denied_rows_keep_their_status() {
+        assert!(matches!(
+            row_to_session(row("pending", None)).unwrap().status,
+            DeviceCodeStatus::Pending
+        ));
+        assert!(matches!(
+            row_to_session(row("denied", None)).unwrap().status,
+            DeviceCodeStatus::Denied
+        ));
+    }
+}
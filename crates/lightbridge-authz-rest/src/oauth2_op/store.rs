@@ -52,16 +52,19 @@ use authkestra_op::handlers::token::{TokenErrorResponse, TokenRequest, TokenResp
 use authkestra_op::refresh::{RefreshToken, RefreshTokenStore};
 use authkestra_op::store::OpStore;
 use chrono::{DateTime, Duration, Utc};
+use lightbridge_authz_api_key::entities::device_authorization_row::{
+    DeviceApproval, DeviceAuthorizationRow,
+};
 use lightbridge_authz_api_key::entities::exchange_refresh_token_row::NewExchangeRefreshToken;
 use lightbridge_authz_api_key::repo::StoreRepo;
 use lightbridge_authz_bearer::BearerTokenServiceTrait;
 use lightbridge_authz_budget::repo::BudgetRepo;
-use lightbridge_authz_budget::{BudgetTier, Period, PolicyEngine};
+use lightbridge_authz_budget::{Period, PolicyEngine};
 use lightbridge_authz_core::async_trait;
 use lightbridge_authz_core::config::Oauth2TokenExchange;
 use lightbridge_authz_core::crypto::hash_api_key;
 use lightbridge_authz_core::cuid::cuid2;
-use lightbridge_authz_core::dto::{ModelPolicy, ResourceStatus};
+use lightbridge_authz_core::dto::{ModelPolicy, ResolvedContext, ResourceStatus};
 use lightbridge_authz_core::error::Error;
 use serde_json::Value;
 
@@ -69,47 +72,22 @@ use crate::signing::{KeyOwner, access_token_extra, id_token_extra, identity_for}
 
 use super::client_assertion_store::RedisClientAssertionStore;
 use super::client_store::ConfigClientStore;
-use super::noop_stores::{NoAuthorizationCodeStore, NoDeviceCodeStore};
+use super::code_store::{DbAuthorizationCodeStore, PKCE_METHOD_S256};
+use super::device_store::DbDeviceCodeStore;
 use super::refresh_store::DbRefreshTokenStore;
 use super::{
     ACCESS_TOKEN_TYPE, OFFLINE_ACCESS_SCOPE, OPENID_SCOPE, decode_auth_time_and_nonce,
     decode_email, generate_refresh_secret, grant_scopes, oauth_err, scope_to_string,
 };
 
-/// The `budget_tier` claim's wire label for an arbitrary amount, in micros. ADR-0008's ladder
-/// used `"b-<dollars>"` (`"b-15"`, `"b-30"`, ...) for its seven compile-time rungs; ADR-0015
-/// moved the amounts that can appear here (notably the fail-closed floor, which now defaults to
-/// $6 -- below `B15`) out of that compile-time enum and onto a policy document a revision can
-/// change without a deploy. Per ADR-0015's own "Neutral / follow-ups": "the wire labels the old
-/// enum used ... may still be a reasonable *display* convention for whatever amounts a policy
-/// happens to configure, but they are no longer a source of truth for which amounts are valid."
-/// This function is that convention, generalized to any amount: prefer the exact legacy label
-/// when `amount_micros` happens to match a known rung (byte-for-byte unchanged claim shape for
-/// every account on today's $15/$30/.../$1000 ladder), otherwise synthesize `"b-<dollars>"`
-/// directly from the amount so a policy-configured floor (or any other new amount) gets an
-/// honest label instead of being misrepresented as the nearest legacy rung.
-///
-/// Assumes whole-dollar amounts (integer micros divisible by `1_000_000`), matching every
-/// `allowed_amounts_micros`/`starting_amount_micros`/`fail_closed_floor_micros` value this
-/// codebase ships today (`rule_data::default_rule_set_json`, the ADR-0015 migration). A
-/// non-whole-dollar amount still produces a label (truncating division), just not one that
-/// round-trips back through [`BudgetTier::from_amount_micros`] -- no different in kind from the
-/// legacy enum, which likewise had no representation for a non-whole-dollar amount.
-fn budget_tier_wire_label(amount_micros: i64) -> String {
-    match BudgetTier::from_amount_micros(amount_micros) {
-        Some(tier) => tier.label().to_string(),
-        None => format!("b-{}", amount_micros / 1_000_000),
-    }
-}
-
 /// Everything the native token-exchange endpoint needs, minus the one per-request field
 /// (`project_id`) `handle_token`'s dispatch has no room to carry -- see `RequestScopedOpStore`.
 /// One instance is built once at server startup and shared (`Arc`) across every request.
 pub struct TokenExchangeOpStore {
     clients: ConfigClientStore,
-    codes: NoAuthorizationCodeStore,
+    codes: DbAuthorizationCodeStore,
     refresh: DbRefreshTokenStore,
-    devices: NoDeviceCodeStore,
+    devices: DbDeviceCodeStore,
     assertions: RedisClientAssertionStore,
     repo: Arc<StoreRepo>,
     /// The `project_members` handle [`Self::resolve_quota_tier`] (ADR-0017) reads from.
@@ -125,11 +103,11 @@ pub struct TokenExchangeOpStore {
     /// and its refresh-grant mirror are exactly that proof.
     quota_repo: Arc<StoreRepo>,
     budget_repo: Arc<BudgetRepo>,
-    /// ADR-0015 Decision 6's fail-closed floor, read live (see [`Self::resolve_budget_tier`]) --
-    /// the SAME hot-swappable engine `authz-api`/`authz-budget` already hold via
-    /// `policy_store.engine()`, not a private copy. `authz-idp` (this store's only production
-    /// constructor, `start_idp_server`) loads its own `PolicyStore` off the shared Postgres
-    /// `budget_policy_sets`/`budget_policy_revisions` tables for exactly this reason.
+    /// The tier ladder and ADR-0015 Decision 6's fail-closed floor, both read live (see
+    /// [`Self::resolve_budget_tier`]) -- the SAME hot-swappable engine `authz-api`/`authz-budget`
+    /// already hold via `policy_store.engine()`, not a private copy. `authz-idp` (this store's
+    /// only production constructor, `start_idp_server`) loads its own `PolicyStore` off the shared
+    /// Postgres `budget_policy_sets`/`budget_policy_revisions` tables for exactly this reason.
     policy_engine: Arc<dyn PolicyEngine>,
     bearer: Arc<dyn BearerTokenServiceTrait>,
     cfg: Oauth2TokenExchange,
@@ -149,9 +127,9 @@ impl TokenExchangeOpStore {
     ) -> Self {
         Self {
             clients,
-            codes: NoAuthorizationCodeStore,
+            codes: DbAuthorizationCodeStore::new(repo.clone()),
             refresh: DbRefreshTokenStore::new(repo.clone()),
-            devices: NoDeviceCodeStore,
+            devices: DbDeviceCodeStore::new(repo.clone()),
             assertions,
             repo,
             quota_repo,
@@ -162,6 +140,18 @@ impl TokenExchangeOpStore {
         }
     }
 
+    /// The `device_authorizations`-backed store the verification page (`crate::device_authorization`)
+    /// reads and writes pairing sessions through.
+    pub fn devices(&self) -> &DbDeviceCodeStore {
+        &self.devices
+    }
+
+    /// The `authorization_codes`-backed store `/authorize` (`crate::authorize`) issues codes and
+    /// tracks its pending requests through.
+    pub fn codes(&self) -> &DbAuthorizationCodeStore {
+        &self.codes
+    }
+
     /// Whether the discovery document should advertise `private_key_jwt`
     /// (`signing::discovery_document`).
     pub fn has_confidential_client(&self) -> bool {
@@ -206,14 +196,14 @@ impl TokenExchangeOpStore {
     /// [`Self::handle_refresh_token`], since ADR-0011 already re-mints both symmetrically through
     /// the same signing calls.
     ///
+    /// The rung comes from the active policy's tier ladder ([`PolicyEngine::tier_ladder`]), and
+    /// an account with no qualifying grant yet this period starts on its project's billing plan's
+    /// rung -- both resolved by [`BudgetRepo::current_tier`], see its doc comment.
+    ///
     /// **Fail-closed, unconditionally, at the policy-configured floor -- not a hard-coded
-    /// rung (ADR-0015 Decision 6).** [`BudgetRepo::current_tier`] handles the ADR-0008 "no grant
-    /// yet this period" / "grant amount doesn't match a known rung" cases internally by
-    /// defaulting to [`BudgetTier::B15`] -- that is the *starting-amount* case (a brand-new
-    /// account), a distinct concept from the fail-closed floor this method is about, and it is
-    /// deliberately left alone here: see `BudgetRepo::current_tier`'s own doc comment for why
-    /// that default stays a flagged, pre-existing simplification rather than being wired to
-    /// [`PolicyEngine::starting_amount_micros`] in this change.
+    /// rung (ADR-0015 Decision 6).** `BudgetRepo::current_tier`'s own fallbacks (no grant yet, a
+    /// grant on no rung) are the *starting-tier* cases, a distinct concept from the fail-closed
+    /// floor this method is about, and are deliberately left to it.
     ///
     /// What `BudgetRepo::current_tier` does NOT swallow is a genuine storage failure
     /// (`Err(BudgetError::StorageFailed)`, e.g. the budget ledger's database being unreachable):
@@ -222,23 +212,39 @@ impl TokenExchangeOpStore {
     /// distinction -- a budget-ledger outage is orthogonal to whether a login/refresh should
     /// succeed, and per the budget-tier-rekey-cutover runbook, "an account with no claim lands on
     /// no matching rule, which is the difference between base budget and unlimited". So any `Err`
-    /// here -- for any reason -- is caught, logged, and downgraded to
+    /// here -- from the plan lookup or the tier lookup -- is caught, logged, and downgraded to
     /// [`PolicyEngine::fail_closed_floor_micros`] (read live off the same hot-swappable engine
     /// `authz-api`/`authz-budget` use, never a private snapshot), and the token mint proceeds.
     /// The claim is never omitted and the exchange/refresh grant never fails because of this
     /// lookup.
     ///
-    /// Returns the wire label to stamp directly (not a [`BudgetTier`]): the floor amount a policy
-    /// revision configures has no guarantee of matching any compile-time rung (the shipped
-    /// default is $6, below `B15`'s $15), so [`budget_tier_wire_label`] is used for both the
-    /// success and fallback paths to produce a consistent `"b-<dollars>"` label either way.
-    async fn resolve_budget_tier(&self, budget_account_id: &str, now: DateTime<Utc>) -> String {
+    /// Returns the wire label to stamp directly: the floor amount a policy revision configures
+    /// has no guarantee of matching any rung (the shipped default is $6, below `b-15`), so
+    /// [`lightbridge_authz_budget::TierLadder::label_for_amount_micros`] labels it honestly
+    /// rather than as the nearest rung.
+    async fn resolve_budget_tier(
+        &self,
+        budget_account_id: &str,
+        project_id: &str,
+        now: DateTime<Utc>,
+    ) -> String {
         let period = Period::current(now);
-        match self
-            .budget_repo
-            .current_tier(budget_account_id, &period)
-            .await
-        {
+        let ladder = self.policy_engine.tier_ladder();
+        let resolved = async {
+            let context = self
+                .budget_repo
+                .account_context(budget_account_id, Some(project_id))
+                .await?;
+            self.budget_repo
+                .current_tier(
+                    budget_account_id,
+                    &period,
+                    &ladder,
+                    context.billing_plan.as_deref(),
+                )
+                .await
+        };
+        match resolved.await {
             Ok(tier) => tier.label().to_string(),
             Err(err) => {
                 let floor_micros = self.policy_engine.fail_closed_floor_micros();
@@ -250,7 +256,7 @@ impl TokenExchangeOpStore {
                      fail-closed floor rather than omitting the claim or failing the token \
                      exchange"
                 );
-                budget_tier_wire_label(floor_micros)
+                ladder.label_for_amount_micros(floor_micros)
             }
         }
     }
@@ -427,16 +433,53 @@ impl TokenExchangeOpStore {
             ));
         }
 
-        // No `project_id` on the request: resolve to the subject's own auto-provisioned default
-        // project instead of rejecting -- see this method's doc comment. A subject with zero
-        // projects yet (a real, reachable state: account creation and the bootstrap "ensure
-        // default project" flow are separate calls) has no default to fall back to; that resolves
-        // identically to `resolve_context`'s own `NotFound` below, not as a distinct error class,
-        // so this endpoint never leaks "you have no projects" any more than it leaks "that project
-        // doesn't exist".
+        let context = self
+            .resolve_session_context(&subject, requested_project_id)
+            .await?;
+        let granted_scopes = grant_scopes(&req.scope, &self.cfg.allowed_scopes, &client.scopes);
+        let (email, email_verified) = decode_email(subject_token);
+        let (auth_time, nonce) = decode_auth_time_and_nonce(subject_token);
+
+        let mut response = self
+            .mint_session(
+                SessionMint {
+                    client_id: &client_id,
+                    owner: KeyOwner {
+                        subject,
+                        email,
+                        email_verified,
+                    },
+                    context,
+                    granted_scopes,
+                    auth_time,
+                    nonce,
+                },
+                tokens,
+                "token-exchange issued access token",
+            )
+            .await?;
+        // RFC 8693 §2.2.1: REQUIRED on a token-exchange grant response, mirroring
+        // `default_handle_token_exchange`'s own value for this field.
+        response.issued_token_type = Some(ACCESS_TOKEN_TYPE.to_string());
+        Ok(response)
+    }
+
+    /// Resolves which project a new session is sealed to, shared by the two grants that start
+    /// one (the exchange grant and the device grant). No `project_id` on the request falls back
+    /// to the subject's own auto-provisioned default project instead of rejecting -- a first-time
+    /// caller has no way to know their project id. A subject with zero projects yet (a real,
+    /// reachable state: account creation and the bootstrap "ensure default project" flow are
+    /// separate calls) has no default to fall back to; that resolves identically to
+    /// `resolve_context`'s own `NotFound` below, not as a distinct error class, so neither grant
+    /// ever leaks "you have no projects" any more than it leaks "that project doesn't exist".
+    async fn resolve_session_context(
+        &self,
+        subject: &str,
+        requested_project_id: Option<&str>,
+    ) -> Result<ResolvedContext, TokenErrorResponse> {
         let effective_project_id = match requested_project_id {
             Some(project_id) => project_id.to_string(),
-            None => match self.repo.find_default_project_id(&subject).await {
+            None => match self.repo.find_default_project_id(subject).await {
                 Ok(Some(project_id)) => project_id,
                 Ok(None) => {
                     return Err(oauth_err(
@@ -450,46 +493,57 @@ impl TokenExchangeOpStore {
             },
         };
 
-        let context = match self
+        match self
             .repo
-            .resolve_context(&subject, &effective_project_id)
+            .resolve_context(subject, &effective_project_id)
             .await
         {
-            Ok(context) => context,
-            Err(Error::NotFound) => {
-                return Err(oauth_err(
-                    "access_denied",
-                    "subject is not a member of the requested project",
-                ));
-            }
-            Err(_) => {
-                return Err(oauth_err("server_error", "context resolution failed"));
-            }
-        };
+            Ok(context) => Ok(context),
+            Err(Error::NotFound) => Err(oauth_err(
+                "access_denied",
+                "subject is not a member of the requested project",
+            )),
+            Err(_) => Err(oauth_err("server_error", "context resolution failed")),
+        }
+    }
 
+    /// Mints the tenant-scoped token set a new session starts with -- access token, `id_token`
+    /// when `openid` was granted, and a fresh refresh-token chain when `offline_access` was --
+    /// for a subject whose project context is already resolved. Shared verbatim by the exchange
+    /// grant and the device grant, so a CLI that paired through `/device` holds exactly the
+    /// claims (`account_id`, `project_id`, `budget_tier`, `quota_tier`, `model_policy`, ...) a
+    /// token-exchange client would. `issued_token_type` is left `None`; only the exchange grant's
+    /// response carries it.
+    async fn mint_session(
+        &self,
+        mint: SessionMint<'_>,
+        tokens: &TokenManager,
+        log_message: &'static str,
+    ) -> Result<TokenResponse, TokenErrorResponse> {
+        let SessionMint {
+            client_id,
+            owner,
+            context,
+            granted_scopes,
+            auth_time,
+            nonce,
+        } = mint;
         let (allowed_models, model_policy) =
             self.resolve_project_model_access(&context.project_id).await;
 
-        let granted_scopes = grant_scopes(&req.scope, &self.cfg.allowed_scopes, &client.scopes);
         let offline = granted_scopes.iter().any(|s| s == OFFLINE_ACCESS_SCOPE);
         let openid = granted_scopes.iter().any(|s| s == OPENID_SCOPE);
 
-        let (email, email_verified) = decode_email(subject_token);
-        let (auth_time, nonce) = decode_auth_time_and_nonce(subject_token);
-        let owner = KeyOwner {
-            subject: subject.clone(),
-            email,
-            email_verified,
-        };
-
         let now = Utc::now();
         let session_id = cuid2();
         let expires_in_secs = self.cfg.access_ttl_seconds.max(0) as u64;
         let scope_str = scope_to_string(&granted_scopes);
 
-        let budget_tier = self.resolve_budget_tier(&context.account_id, now).await;
+        let budget_tier = self
+            .resolve_budget_tier(&context.account_id, &context.project_id, now)
+            .await;
         let quota_tier = self
-            .resolve_quota_tier(&context.project_id, &subject)
+            .resolve_quota_tier(&context.project_id, &owner.subject)
             .await?;
         let mut access_extra = access_token_extra(
             &owner,
@@ -497,7 +551,7 @@ impl TokenExchangeOpStore {
             &context.project_id,
             &context.account_id,
             allowed_models,
-            Some(&client_id),
+            Some(client_id),
         );
         access_extra.insert("budget_tier".to_string(), Value::String(budget_tier));
         if let Some(quota_tier) = quota_tier {
@@ -512,16 +566,16 @@ impl TokenExchangeOpStore {
                 identity_for(&owner),
                 expires_in_secs,
                 scope_str.clone(),
-                Some(client_id.clone()),
+                Some(client_id.to_string()),
                 access_extra,
             )
             .map_err(|_| oauth_err("server_error", "access token signing failed"))?;
 
         let id_token = if openid {
-            let extra = id_token_extra(&owner, &access_token, auth_time, &client_id);
+            let extra = id_token_extra(&owner, &access_token, auth_time, client_id);
             match tokens.issue_id_token_with_extra(
                 identity_for(&owner),
-                &client_id,
+                client_id,
                 nonce,
                 expires_in_secs,
                 extra,
@@ -551,7 +605,7 @@ impl TokenExchangeOpStore {
             );
             let rt = RefreshToken {
                 token: plaintext.clone(),
-                client_id: client_id.clone(),
+                client_id: client_id.to_string(),
                 identity,
                 scope: scope_str.clone().unwrap_or_default(),
                 expires_at: now + Duration::seconds(self.cfg.refresh_ttl_seconds),
@@ -570,13 +624,13 @@ impl TokenExchangeOpStore {
         };
 
         tracing::info!(
-            subject = %subject,
+            subject = %owner.subject,
             account_id = %context.account_id,
             project_id = %context.project_id,
             client_id = %client_id,
             offline,
             openid,
-            "token-exchange issued access token"
+            "{log_message}"
         );
 
         Ok(TokenResponse {
@@ -586,12 +640,287 @@ impl TokenExchangeOpStore {
             id_token,
             refresh_token,
             scope: scope_str,
-            // RFC 8693 §2.2.1: REQUIRED on a token-exchange grant response, mirroring
-            // `default_handle_token_exchange`'s own value for this field.
-            issued_token_type: Some("urn:ietf:params:oauth:token-type:access_token".to_string()),
+            issued_token_type: None,
+        })
+    }
+
+    /// The RFC 8628 device-code grant (ADR-0012, Decisions 1, 5-7): one CLI poll against a
+    /// pairing session the device-authorization endpoint opened. Dispatched here by
+    /// `token_exchange::token_endpoint` itself rather than through `handle_token`, whose built-in
+    /// device arm mints with the plain `issue_user_token` -- none of the tenant claims every other
+    /// token this service issues carries, and no seam on `OpStore` to override it the way the
+    /// exchange/refresh grants are. Client authentication has already happened by the time this
+    /// runs.
+    ///
+    /// RFC 8628 §3.5's error semantics, in the order a poll can hit them: a code that does not
+    /// exist or was issued to another client is `invalid_grant`; a lapsed one `expired_token`; a
+    /// still-`pending` one `authorization_pending`, or `slow_down` when it is polled again within
+    /// its `interval_secs` of the previous poll. A decided session is redeemed through a
+    /// single-use CAS (`StoreRepo::consume_device_authorization`) -- a refusal answers
+    /// `access_denied`, an approval mints through [`Self::mint_session`] for the Keycloak `sub`
+    /// the browser leg recorded, and either way the next poll finds nothing (`invalid_grant`).
+    ///
+    /// Project context is resolved at redemption, not at approval: a subject removed from the
+    /// requested project between approving and the next poll gets `access_denied`, exactly like a
+    /// token exchange would.
+    pub async fn handle_device_code(
+        &self,
+        req: TokenRequest,
+        client_id: String,
+        client: ClientRegistration,
+        tokens: &TokenManager,
+    ) -> Result<TokenResponse, TokenErrorResponse> {
+        if !client.allows_grant_type(&GrantType::DeviceCode) {
+            return Err(oauth_err(
+                "unauthorized_client",
+                "Client is not authorized to use device_code grant type",
+            ));
+        }
+        let Some(device_code) = req.device_code.as_deref().filter(|s| !s.trim().is_empty()) else {
+            return Err(oauth_err("invalid_request", "device_code is required"));
+        };
+        let invalid_grant = || oauth_err("invalid_grant", "device_code is invalid or already used");
+        let storage_failed = |_| oauth_err("server_error", "device authorization lookup failed");
+
+        let now = Utc::now();
+        let Some(session) = self
+            .devices
+            .find(device_code)
+            .await
+            .map_err(storage_failed)?
+        else {
+            return Err(invalid_grant());
+        };
+        if session.client_id != client_id {
+            tracing::warn!(
+                client_id = %client_id,
+                "device_code was issued to a different client"
+            );
+            return Err(invalid_grant());
+        }
+        if now >= session.expires_at {
+            return Err(oauth_err("expired_token", "device_code has expired"));
+        }
+
+        match session.status.as_str() {
+            "pending" => {
+                let previous = self
+                    .devices
+                    .record_poll(device_code, now)
+                    .await
+                    .map_err(storage_failed)?;
+                let interval = Duration::seconds(i64::from(session.interval_secs));
+                return match previous {
+                    Some(Some(last_polled_at)) if now < last_polled_at + interval => Err(
+                        oauth_err("slow_down", "device_code is being polled too frequently"),
+                    ),
+                    // `None`: decided between the read above and this poll -- the client's next
+                    // poll redeems it.
+                    _ => Err(oauth_err(
+                        "authorization_pending",
+                        "the user has not yet completed the authorization request",
+                    )),
+                };
+            }
+            "approved" | "denied" => {}
+            _ => return Err(invalid_grant()),
+        }
+
+        let Some(claimed) = self
+            .devices
+            .consume(device_code, now)
+            .await
+            .map_err(storage_failed)?
+        else {
+            return Err(invalid_grant());
+        };
+        let Some(subject) = claimed
+            .subject
+            .clone()
+            .filter(|_| claimed.status == "approved")
+        else {
+            return Err(oauth_err(
+                "access_denied",
+                "the user denied the authorization request",
+            ));
+        };
+
+        let context = self
+            .resolve_session_context(&subject, claimed.project_id.as_deref())
+            .await?;
+        let requested_scope = Some(claimed.scope).filter(|s| !s.trim().is_empty());
+        let granted_scopes =
+            grant_scopes(&requested_scope, &self.cfg.allowed_scopes, &client.scopes);
+
+        self.mint_session(
+            SessionMint {
+                client_id: &client_id,
+                owner: KeyOwner {
+                    subject,
+                    email: claimed.email,
+                    email_verified: claimed.email_verified,
+                },
+                context,
+                granted_scopes,
+                auth_time: claimed.auth_time,
+                nonce: claimed.nonce,
+            },
+            tokens,
+            "device-code grant issued access token",
+        )
+        .await
+    }
+
+    /// Resolves who a browser leg's Keycloak sign-in authenticated: the caller has just redeemed
+    /// Keycloak's authorization code for `upstream_access_token` (and an `upstream_id_token` when
+    /// Keycloak returned one). Shared by the device grant's verification page and `/authorize`.
+    ///
+    /// The access token is validated through the same JWKS-backed `validate_bearer_token` the
+    /// exchange grant runs on a `subject_token`, and `sub`/`email`/`email_verified` are read off
+    /// it, never minted (ADR-0012 Decision 5). `auth_time` is read off the `id_token` first, the
+    /// token OIDC Core defines it on, falling back to the access token; it is never defaulted to
+    /// "now" (Decision 6). The `id_token` is decoded without a signature check of its own: it
+    /// came straight back from Keycloak's token endpoint over TLS on this request, which OIDC
+    /// Core §3.1.3.7 accepts in place of one, and `sub` is never taken from it.
+    ///
+    /// `Err(OpError::InvalidCode)` when the access token does not validate.
+    pub async fn verify_upstream_login(
+        &self,
+        upstream_access_token: &str,
+        upstream_id_token: Option<&str>,
+    ) -> Result<DeviceApproval, OpError> {
+        let token_info = match self
+            .bearer
+            .validate_bearer_token(upstream_access_token)
+            .await
+        {
+            Ok(info) if info.active => info,
+            _ => {
+                tracing::warn!("browser sign-in: upstream access token did not validate");
+                return Err(OpError::InvalidCode);
+            }
+        };
+        let (email, email_verified) = decode_email(upstream_access_token);
+        let auth_time = upstream_id_token
+            .and_then(|id_token| decode_auth_time_and_nonce(id_token).0)
+            .or_else(|| decode_auth_time_and_nonce(upstream_access_token).0);
+        Ok(DeviceApproval {
+            subject: token_info.sub,
+            email,
+            email_verified,
+            auth_time,
         })
     }
 
+    /// Completes the device grant's browser leg: the pending session `state` names is approved
+    /// for the user [`Self::verify_upstream_login`] resolves.
+    ///
+    /// `Err(OpError::InvalidCode)` when the token does not validate or `state` no longer names a
+    /// `pending` session; the session is left exactly as it was, so a Keycloak outage or a bad
+    /// callback can only ever leave a pairing pending, never approve it.
+    pub async fn approve_device_verification(
+        &self,
+        state: &str,
+        upstream_access_token: &str,
+        upstream_id_token: Option<&str>,
+    ) -> Result<DeviceAuthorizationRow, OpError> {
+        let approval = self
+            .verify_upstream_login(upstream_access_token, upstream_id_token)
+            .await?;
+        self.devices
+            .approve(state, approval, Utc::now())
+            .await?
+            .ok_or(OpError::InvalidCode)
+    }
+
+    /// The `authorization_code` grant: redeems a code `/authorize` issued. Dispatched by
+    /// `token_exchange::token_endpoint` itself for the same reason as [`Self::handle_device_code`]
+    /// -- `handle_token`'s built-in arm mints without this service's claims (and panics on a
+    /// request with no `code`). Client authentication has already happened by the time this runs.
+    ///
+    /// The code is consumed first, through a single-use CAS
+    /// (`StoreRepo::consume_authorization_code`), and only then checked, so a code presented with
+    /// the wrong client, redirect URI, or PKCE verifier is burned rather than left redeemable for
+    /// a second guess (RFC 6749 §10.5). Every mismatch answers the same `invalid_grant`. PKCE is
+    /// S256-only and mandatory: a code row cannot exist without a challenge.
+    pub async fn handle_authorization_code(
+        &self,
+        req: TokenRequest,
+        client_id: String,
+        client: ClientRegistration,
+        tokens: &TokenManager,
+    ) -> Result<TokenResponse, TokenErrorResponse> {
+        if !client.allows_grant_type(&GrantType::AuthorizationCode) {
+            return Err(oauth_err(
+                "unauthorized_client",
+                "Client is not authorized to use authorization_code grant type",
+            ));
+        }
+        let Some(code) = req.code.as_deref().filter(|s| !s.trim().is_empty()) else {
+            return Err(oauth_err("invalid_request", "code is required"));
+        };
+        let invalid_grant = || {
+            oauth_err(
+                "invalid_grant",
+                "authorization code is invalid or already used",
+            )
+        };
+
+        let Some(claimed) = self
+            .codes
+            .consume(code, Utc::now())
+            .await
+            .map_err(|_| oauth_err("server_error", "authorization code lookup failed"))?
+        else {
+            return Err(invalid_grant());
+        };
+        if claimed.client_id != client_id {
+            tracing::warn!(
+                client_id = %client_id,
+                "authorization code was issued to a different client"
+            );
+            return Err(invalid_grant());
+        }
+        if req.redirect_uri.as_deref() != Some(claimed.redirect_uri.as_str()) {
+            tracing::warn!(client_id = %client_id, "authorization code redirect_uri mismatch");
+            return Err(invalid_grant());
+        }
+        let verified = claimed.code_challenge_method == PKCE_METHOD_S256
+            && req
+                .code_verifier
+                .as_deref()
+                .is_some_and(|verifier| pkce_s256_matches(verifier, &claimed.code_challenge));
+        if !verified {
+            tracing::warn!(client_id = %client_id, "authorization code PKCE verification failed");
+            return Err(invalid_grant());
+        }
+
+        let context = self
+            .resolve_session_context(&claimed.subject, claimed.project_id.as_deref())
+            .await?;
+        let requested_scope = Some(claimed.scope).filter(|s| !s.trim().is_empty());
+        let granted_scopes =
+            grant_scopes(&requested_scope, &self.cfg.allowed_scopes, &client.scopes);
+
+        self.mint_session(
+            SessionMint {
+                client_id: &client_id,
+                owner: KeyOwner {
+                    subject: claimed.subject,
+                    email: claimed.email,
+                    email_verified: claimed.email_verified,
+                },
+                context,
+                granted_scopes,
+                auth_time: claimed.auth_time,
+                nonce: claimed.nonce,
+            },
+            tokens,
+            "authorization-code grant issued access token",
+        )
+        .await
+    }
+
     /// The `refresh_token` grant (ADR-0011, Decision 1): re-mints access + id_token symmetrically
     /// with the exchange grant above, through the same signing calls, which is what fixes the
     /// phase-1-era `mint_from_refresh` email-dropping bug by construction (there is only one
@@ -739,7 +1068,9 @@ impl TokenExchangeOpStore {
         let expires_in_secs = self.cfg.access_ttl_seconds.max(0) as u64;
         let scope_str = old_row.scope.clone();
 
-        let budget_tier = self.resolve_budget_tier(&context.account_id, now).await;
+        let budget_tier = self
+            .resolve_budget_tier(&context.account_id, &context.project_id, now)
+            .await;
         let quota_tier = self
             .resolve_quota_tier(&context.project_id, &old_row.subject)
             .await?;
@@ -876,6 +1207,37 @@ impl TokenExchangeOpStore {
     }
 }
 
+/// What a session-starting grant has resolved by the time it mints: who the subject is, which
+/// project the session is sealed to, and what the upstream login said about it. See
+/// [`TokenExchangeOpStore::mint_session`].
+struct SessionMint<'a> {
+    client_id: &'a str,
+    owner: KeyOwner,
+    context: ResolvedContext,
+    granted_scopes: Vec<String>,
+    auth_time: Option<i64>,
+    /// Reflected verbatim into the `id_token`, never synthesized (ADR-0011 Decision 7, ADR-0012
+    /// Decision 6).
+    nonce: Option<String>,
+}
+
+/// RFC 7636 §4.6 `S256`: `BASE64URL(SHA256(ASCII(code_verifier))) == code_challenge`. The
+/// verifier's own syntax (43-128 unreserved characters) is checked too, so an empty or truncated
+/// verifier never reaches the hash comparison.
+fn pkce_s256_matches(verifier: &str, challenge: &str) -> bool {
+    use base64::Engine;
+    use sha2::{Digest, Sha256};
+    let well_formed = (43..=128).contains(&verifier.len())
+        && verifier
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~'));
+    if !well_formed {
+        return false;
+    }
+    let digest = Sha256::digest(verifier.as_bytes());
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(digest) == challenge
+}
+
 /// Builds the `Identity` a refresh-token row round-trips through `RefreshTokenStore` (see
 /// `refresh_store`'s doc comment for why `account_id`/`project_id`/`email_verified`/`auth_time`/
 /// `chain_id`/`chain_expires_at` live in `attributes`). Only used for the initial
@@ -996,9 +1358,17 @@ impl DeviceCodeStore for TokenExchangeOpStore {
 /// RFC 8693's own resource-indicator parameter) or reach for thread-local/global state, this
 /// wrapper is built fresh per HTTP request, closes over `project_id` parsed straight off that
 /// request's form body, and forwards everything else to the shared `Arc<TokenExchangeOpStore>`.
+///
+/// The device-authorization endpoint builds one the same way, for the same reason: its
+/// `project_id` and `nonce` ride on the request, and `DeviceCodeStore::store_device_code` (which
+/// `authkestra_op::handlers::device_authorization::handle_device_authorization` calls) has no room
+/// for either -- see `oauth2_op::device_store`. `/authorize/callback` does too, so the
+/// `AuthorizationCode` `authkestra_op::handlers::authorize::handle_authorize` stores carries the
+/// original request's `project_id` -- see `oauth2_op::code_store`.
 pub struct RequestScopedOpStore<'a> {
     pub inner: &'a TokenExchangeOpStore,
     pub project_id: Option<String>,
+    pub nonce: Option<String>,
 }
 
 #[async_trait]
@@ -1011,7 +1381,10 @@ impl ClientStore for RequestScopedOpStore<'_> {
 #[async_trait]
 impl AuthorizationCodeStore for RequestScopedOpStore<'_> {
     async fn store_code(&self, code: AuthorizationCode) -> Result<(), OpError> {
-        self.inner.store_code(code).await
+        self.inner
+            .codes
+            .store_scoped(code, self.project_id.clone())
+            .await
     }
 
     async fn consume_code(&self, code: &str) -> Result<Option<AuthorizationCode>, OpError> {
@@ -1041,7 +1414,10 @@ impl RefreshTokenStore for RequestScopedOpStore<'_> {
 #[async_trait]
 impl DeviceCodeStore for RequestScopedOpStore<'_> {
     async fn store_device_code(&self, session: DeviceCodeSession) -> Result<(), OpError> {
-        self.inner.store_device_code(session).await
+        self.inner
+            .devices
+            .store_scoped(session, self.project_id.clone(), self.nonce.clone())
+            .await
     }
 
     async fn get_device_code(
@@ -1110,44 +1486,3 @@ impl OpStore for RequestScopedOpStore<'_> {
             .await
     }
 }
-
-#[cfg(test)]
-mod budget_tier_wire_label_tests {
-    use super::budget_tier_wire_label;
-    use lightbridge_authz_budget::tier::BudgetTier;
-
-    /// The ADR-0015-shipped fail-closed floor ($6, `6_000_000` micros) -- the whole reason
-    /// `budget_tier_wire_label` exists: `BudgetTier` has no variant below `B15`'s $15, so a
-    /// caller that stamped `BudgetTier::B15.label()` here regardless of the configured floor
-    /// would misrepresent a $6 outage fallback as the $15 rung, silently granting more than the
-    /// policy actually authorizes on the one path (a budget-ledger outage) meant to be the most
-    /// conservative. This is the exact fail-open shape ADR-0015 Decision 6 exists to prevent.
-    #[test]
-    fn the_adr_0015_fail_closed_floor_gets_its_own_honest_label_not_the_nearest_legacy_rung() {
-        assert_eq!(budget_tier_wire_label(6_000_000), "b-6");
-        assert_ne!(
-            budget_tier_wire_label(6_000_000),
-            BudgetTier::B15.label(),
-            "a $6 floor must never be represented as the $15 rung"
-        );
-    }
-
-    /// Every legacy `BudgetTier` rung round-trips through its exact, pre-ADR-0015 label --
-    /// stamping the claim for an account that resolved through the real ledger (not the
-    /// fail-closed fallback) must not change shape for any account already on a known rung.
-    #[test]
-    fn every_legacy_rung_keeps_its_exact_pre_adr_0015_label() {
-        for tier in BudgetTier::ALL {
-            assert_eq!(budget_tier_wire_label(tier.amount().get()), tier.label());
-        }
-    }
-
-    /// An amount that matches neither a legacy rung nor a whole number of dollars still produces
-    /// a label (truncating division) rather than panicking -- no different in kind from the
-    /// legacy enum, which likewise had no representation for a non-whole-dollar amount. Documents
-    /// the behavior rather than asserting it is ideal.
-    #[test]
-    fn a_non_whole_dollar_amount_truncates_rather_than_panics() {
-        assert_eq!(budget_tier_wire_label(6_500_000), "b-6");
-    }
-}
@@ -5,18 +5,24 @@ use axum::routing::post;
 
 use crate::OpaState;
 use crate::handlers::idp::resolve_context;
-use crate::handlers::introspect::introspect_api_key;
+use crate::handlers::introspect::{introspect_api_key, introspect_api_keys_batch};
+use crate::handlers::leaked::report_leaked_credentials;
 use crate::middleware::basic_auth;
 
 /// Returns the OPA/Authorino validation router. Every route sits behind Basic auth; the IdP
 /// `resolve-context` endpoint lives here because it returns tenant context and must not be publicly
-/// reachable.
+/// reachable, and so does the leaked-credential report, which can revoke any key.
 pub fn opa_router(state: Arc<OpaState>) -> Router<Arc<OpaState>> {
     Router::new()
         .route(
             "/v1/authorino/validate/introspect",
             post(introspect_api_key),
         )
+        .route(
+            "/v1/authorino/validate/introspect/batch",
+            post(introspect_api_keys_batch),
+        )
         .route("/idp/v1/resolve-context", post(resolve_context))
+        .route("/v1/leaked-credentials", post(report_leaked_credentials))
         .layer(axum::middleware::from_fn_with_state(state, basic_auth))
 }
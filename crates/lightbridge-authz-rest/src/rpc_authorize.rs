@@ -176,6 +176,10 @@ pub(crate) fn required_permission(op_id: &str) -> Option<Permission> {
         // is its replacement write path, same coarse permission as `model.Project.update`, matching
         // `setProjectQuota`/`setProjectAllowedModels`'s own precedent immediately above.
         "procedure.setProjectModelPolicy" => ProjectUpdate,
+        // The dormant-key policy the `api_key_lifecycle` job applies: read at `project:read`, written
+        // at the same `project:update` as the other project settings above.
+        "procedure.getProjectKeyLifecyclePolicy" => ProjectRead,
+        "procedure.setProjectKeyLifecyclePolicy" => ProjectUpdate,
         // Roster management (ADR-0006). These replace the removed account-member procedures, and
         // the capability moved with them: `project:member`, not `account:member`. Note this is only
         // the coarse gate — the lead check ("the member row matching my subject must ALSO have
@@ -208,6 +212,7 @@ pub(crate) fn required_permission(op_id: &str) -> Option<Permission> {
         "model.ApiKey.delete" => ApiKeyDelete,
         "procedure.revokeApiKey" => ApiKeyRevoke,
         "procedure.rotateApiKey" => ApiKeyRotate,
+        "procedure.setApiKeyAllowedCidrs" => ApiKeyUpdate,
 
         // AccountSummary is the read-only dashboard aggregate this migration adds. It is gated at
         // `account:read` (same coarse capability as reading accounts). NB: in cratestack-pg 0.4.9 a
@@ -248,6 +253,11 @@ pub(crate) fn required_permission(op_id: &str) -> Option<Permission> {
         "procedure.revokeOwnSessions" => SessionRevokeOwn,
         "procedure.revokeSubjectSessions" => SessionRevoke,
 
+        // The `audit_events` trail of every hand-written mutation procedure. Its own admin-only
+        // permission rather than `budget:audit-read`: the trail spans accounts, projects, keys and
+        // sessions, not just the budget ledger, and is served by `authz-api`.
+        "procedure.listAuditEvents" => AuditRead,
+
         // Direct budget-balance/ledger reads. Self/admin split the same shape as the session-
         // revocation pair above: the "my own budget only" procedures take no target at all and
         // are gated at the narrower `budget:read-own`; the admin, arbitrary-target procedures are
@@ -307,6 +317,14 @@ pub const MAPPED_OP_ID_PERMISSIONS: &[(&str, Permission)] = &[
         Permission::ProjectUpdate,
     ),
     ("procedure.setProjectModelPolicy", Permission::ProjectUpdate),
+    (
+        "procedure.getProjectKeyLifecyclePolicy",
+        Permission::ProjectRead,
+    ),
+    (
+        "procedure.setProjectKeyLifecyclePolicy",
+        Permission::ProjectUpdate,
+    ),
     ("procedure.addProjectMember", Permission::ProjectMember),
     ("procedure.removeProjectMember", Permission::ProjectMember),
     ("procedure.listProjectRoster", Permission::ProjectMember),
@@ -324,6 +342,7 @@ pub const MAPPED_OP_ID_PERMISSIONS: &[(&str, Permission)] = &[
     ("model.ApiKey.delete", Permission::ApiKeyDelete),
     ("procedure.revokeApiKey", Permission::ApiKeyRevoke),
     ("procedure.rotateApiKey", Permission::ApiKeyRotate),
+    ("procedure.setApiKeyAllowedCidrs", Permission::ApiKeyUpdate),
     ("model.AccountSummary.list", Permission::AccountRead),
     ("model.AccountSummary.get", Permission::AccountRead),
     (
@@ -360,6 +379,7 @@ pub const MAPPED_OP_ID_PERMISSIONS: &[(&str, Permission)] = &[
     ),
     ("procedure.revokeOwnSessions", Permission::SessionRevokeOwn),
     ("procedure.revokeSubjectSessions", Permission::SessionRevoke),
+    ("procedure.listAuditEvents", Permission::AuditRead),
     ("procedure.getMyBudgetBalance", Permission::BudgetReadOwn),
     ("procedure.listMyBudgetGrants", Permission::BudgetReadOwn),
     (
@@ -379,7 +399,7 @@ pub const MAPPED_OP_ID_PERMISSIONS: &[(&str, Permission)] = &[
 /// The `auth().<field>` name `CratestackAuthProvider` bakes each [`Permission`]'s boolean grant
 /// into, and every generated `@allow`/`@@allow` clause in `authz.cstack` reads. Mechanically
 /// derived from [`Permission::as_str`]'s canonical `resource:action` string (splitting further on
-/// `-` for hyphenated actions like `read-own`) rather than a second hand-typed list of 31 names —
+/// `-` for hyphenated actions like `read-own`) rather than a second hand-typed list of 32 names —
 /// same single-source-of-truth reasoning as [`MAPPED_OP_ID_PERMISSIONS`] above. E.g.
 /// `"account:create"` -> `"permAccountCreate"`, `"budget:read-own"` -> `"permBudgetReadOwn"`.
 pub fn permission_field_name(permission: Permission) -> String {
@@ -698,6 +718,8 @@ mod tests {
                 "procedure.setProjectQuota",
                 "procedure.setProjectAllowedModels",
                 "procedure.setProjectModelPolicy",
+                "procedure.getProjectKeyLifecyclePolicy",
+                "procedure.setProjectKeyLifecyclePolicy",
                 "procedure.addProjectMember",
                 "procedure.removeProjectMember",
                 "procedure.listProjectRoster",
@@ -712,10 +734,12 @@ mod tests {
                 "model.ApiKey.delete",
                 "procedure.revokeApiKey",
                 "procedure.rotateApiKey",
+                "procedure.setApiKeyAllowedCidrs",
                 "model.AccountSummary.list",
                 "model.AccountSummary.get",
                 "procedure.revokeOwnSessions",
                 "procedure.revokeSubjectSessions",
+                "procedure.listAuditEvents",
             ])
             .collect();
         for op_id in all_mapped_op_ids {
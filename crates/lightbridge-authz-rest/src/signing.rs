@@ -15,7 +15,7 @@ use base64::Engine;
 use chrono::{DateTime, Duration, Utc};
 use lightbridge_authz_api_key::entities::signing_key_row::NewSigningKey;
 use lightbridge_authz_api_key::repo::StoreRepo;
-use lightbridge_authz_core::config::JwtSigning;
+use lightbridge_authz_core::config::{JwtSigning, SigningAlgorithm};
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::error::{Error, Result};
 use rand_core::OsRng;
@@ -26,8 +26,9 @@ use serde_json::Value;
 use sha2::{Digest, Sha256};
 use tower_http::cors::{Any, CorsLayer};
 
+use crate::key_encryption::{KeyEncryptor, key_encryptor, open_private_key, seal_private_key};
+
 const RSA_KEY_BITS: usize = 2048;
-const ALGORITHM: &str = "RS256";
 const TOKEN_TYP: &str = "Bearer";
 const TOKEN_SCOPE: &str = "profile email";
 
@@ -36,13 +37,22 @@ const TOKEN_SCOPE: &str = "profile email";
 /// an upstream Keycloak login, so the provider is always Keycloak regardless of `oauth2.type`.
 const IDENTITY_PROVIDER_ID: &str = "keycloak";
 
-/// A freshly generated RS256 signing key: PKCS#8 private PEM + the public JWK to publish.
+/// A freshly generated signing key: PKCS#8 private PEM + the public JWK to publish.
 pub struct GeneratedKey {
     pub kid: String,
+    pub algorithm: SigningAlgorithm,
     pub private_key_pem: String,
     pub public_jwk: serde_json::Value,
 }
 
+/// Generates a signing keypair of `algorithm` with a unique `kid`.
+pub fn generate_signing_key(algorithm: SigningAlgorithm) -> Result<GeneratedKey> {
+    match algorithm {
+        SigningAlgorithm::Rs256 => generate_rs256_key(),
+        SigningAlgorithm::EdDsa => generate_ed25519_key(),
+    }
+}
+
 /// Generates an RS256 signing keypair with a unique `kid`.
 pub fn generate_rs256_key() -> Result<GeneratedKey> {
     let private = RsaPrivateKey::new(&mut OsRng, RSA_KEY_BITS)
@@ -57,13 +67,41 @@ pub fn generate_rs256_key() -> Result<GeneratedKey> {
     let public_jwk = serde_json::json!({
         "kty": "RSA",
         "use": "sig",
-        "alg": ALGORITHM,
+        "alg": SigningAlgorithm::Rs256.as_str(),
         "kid": kid,
         "n": b64(public.n().to_bytes_be()),
         "e": b64(public.e().to_bytes_be()),
     });
     Ok(GeneratedKey {
         kid,
+        algorithm: SigningAlgorithm::Rs256,
+        private_key_pem: pem,
+        public_jwk,
+    })
+}
+
+/// Generates an Ed25519 (EdDSA) signing keypair with a unique `kid`. Its JWK is the RFC 8037
+/// `OKP` shape, and its tokens are roughly a third the size of RS256 ones.
+pub fn generate_ed25519_key() -> Result<GeneratedKey> {
+    use ed25519_dalek::pkcs8::EncodePrivateKey;
+    let private = ed25519_dalek::SigningKey::generate(&mut OsRng);
+    let pem = private
+        .to_pkcs8_pem(ed25519_dalek::pkcs8::spki::der::pem::LineEnding::LF)
+        .map_err(|e| Error::Server(format!("private key encoding failed: {e}")))?
+        .to_string();
+    let kid = cuid2();
+    let public_jwk = serde_json::json!({
+        "kty": "OKP",
+        "use": "sig",
+        "alg": SigningAlgorithm::EdDsa.as_str(),
+        "kid": kid,
+        "crv": "Ed25519",
+        "x": base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(private.verifying_key().to_bytes()),
+    });
+    Ok(GeneratedKey {
+        kid,
+        algorithm: SigningAlgorithm::EdDsa,
         private_key_pem: pem,
         public_jwk,
     })
@@ -73,7 +111,7 @@ impl GeneratedKey {
     fn into_candidate(self, created_at: DateTime<Utc>) -> NewSigningKey {
         NewSigningKey {
             kid: self.kid,
-            algorithm: ALGORITHM.to_string(),
+            algorithm: self.algorithm.as_str().to_string(),
             private_key_pem: self.private_key_pem,
             public_jwk: self.public_jwk,
             created_at,
@@ -81,8 +119,25 @@ impl GeneratedKey {
     }
 }
 
-/// Ensures an active signing key exists, generating one on first boot and auto-rotating when
-/// the active key is older than `max_key_age_days`. Idempotent and safe across replicas.
+/// Generates a key of `oauth2.signing.algorithm` and, with `oauth2.signing.key_encryption`
+/// configured, seals it (`crate::key_encryption`) so its private material is never written in the
+/// clear.
+pub(crate) async fn sealed_candidate(
+    cfg: &JwtSigning,
+    encryptor: Option<&dyn KeyEncryptor>,
+    created_at: DateTime<Utc>,
+) -> Result<NewSigningKey> {
+    let mut candidate = generate_signing_key(cfg.algorithm)?.into_candidate(created_at);
+    if let Some(encryptor) = encryptor {
+        candidate.private_key_pem =
+            seal_private_key(encryptor, &candidate.kid, &candidate.private_key_pem).await?;
+    }
+    Ok(candidate)
+}
+
+/// Ensures an active signing key exists, generating one on first boot. Idempotent and safe
+/// across replicas. Rotation is not done here: an overdue key is replaced by the
+/// `crate::signing_rotation` task, which pre-publishes its successor before it starts signing.
 ///
 /// ## Signing-key ownership (ADR-0012, "signing-key bootstrap")
 ///
@@ -107,11 +162,12 @@ impl GeneratedKey {
 /// `concurrent_bootstraps_from_multiple_services_produce_exactly_one_active_key`
 /// (`tests/signing_tests.rs`) for the proof against a real database with three concurrent callers.
 ///
-/// **What happens if services disagree on `max_key_age_days`.** Each caller computes its own
-/// rotation cutoff from its own `cfg.max_key_age_days` before taking the lock (see this
-/// function's body). If `authz-idp` is configured with a shorter value than `authz-api`,
-/// `authz-idp`'s check can decide `needs_rotation == true` earlier than `authz-api`'s would have
-/// on its own — but because rotation itself is still gated by the same advisory lock, this only
+/// **What happens if services disagree on `max_key_age_days`.** Each rotation task computes its
+/// own rotation cutoff from its own `cfg.max_key_age_days` before taking the lock (see
+/// `crate::signing_rotation::rotate_signing_keys`). If `authz-idp` is configured with a shorter
+/// value than `authz-api`, `authz-idp`'s task can pre-publish a successor earlier than
+/// `authz-api`'s would have on its own — but because rotation itself is still gated by the same
+/// advisory lock, and at most one key may be `pending`, this only
 /// changes *when* the next rotation happens, never whether more than one key ends up active.
 /// Disagreement is a rotation-*cadence* imprecision (a key might retire a few days earlier or
 /// later than any single service's own config alone would suggest), not a correctness hazard —
@@ -121,11 +177,20 @@ impl GeneratedKey {
 /// `.docker/authz/container.yaml` (`compose.yaml`), so `oauth2.signing.max_key_age_days` is
 /// always one value, not three. A deployment that ever does split this config per service
 /// inherits the cadence-only consequence above, not a safety one.
+///
+/// **Encryption at rest.** With `oauth2.signing.key_encryption` configured, the candidate is sealed
+/// (`crate::key_encryption`) before it is ever written, and the active key that comes back is
+/// opened once here, so a KEK missing from this service's config fails startup instead of the
+/// first signing call.
 pub async fn bootstrap_signing_key(repo: &StoreRepo, cfg: &JwtSigning) -> Result<()> {
-    let now = Utc::now();
-    let cutoff = now - Duration::days(cfg.max_key_age_days.max(1));
-    let candidate = generate_rs256_key()?.into_candidate(now);
-    let active = repo.ensure_active_signing_key(candidate, cutoff).await?;
+    let encryptor = key_encryptor(cfg.key_encryption.as_ref())?;
+    let candidate = sealed_candidate(cfg, encryptor.as_deref(), Utc::now()).await?;
+    // No active key is ever older than the earliest representable instant, so this only inserts
+    // into an empty table.
+    let active = repo
+        .ensure_active_signing_key(candidate, DateTime::<Utc>::MIN_UTC)
+        .await?;
+    open_private_key(encryptor.as_deref(), &active.kid, &active.private_key_pem).await?;
     tracing::info!(kid = %active.kid, "active api-key signing key ready");
     Ok(())
 }
@@ -150,6 +215,8 @@ pub struct ApiKeyJwtSigner {
     issuer: String,
     audience: Option<String>,
     ttl_seconds: i64,
+    /// Opens the stored private key when `oauth2.signing.key_encryption` is configured.
+    encryptor: Option<Arc<dyn KeyEncryptor>>,
 }
 
 impl std::fmt::Debug for ApiKeyJwtSigner {
@@ -325,6 +392,7 @@ impl ApiKeyJwtSigner {
             issuer: cfg.issuer.clone(),
             audience: cfg.audience.clone(),
             ttl_seconds: cfg.ttl_seconds,
+            encryptor: key_encryptor(cfg.key_encryption.as_ref())?,
         })
     }
 
@@ -336,19 +404,36 @@ impl ApiKeyJwtSigner {
     /// id token from it, so both tokens in a response are always signed by the same key. Key
     /// rotation is picked up per-call exactly as the previous hand-rolled `jsonwebtoken::encode`
     /// path did. `pub(crate)` rather than private: `oauth2_op` lives in this crate but a different
-    /// module.
+    /// module. A sealed key is opened transparently (`crate::key_encryption`). The manager signs
+    /// with the active key's own algorithm, whatever `oauth2.signing.algorithm` says now.
     pub(crate) async fn token_manager(&self) -> Result<TokenManager> {
         let active = self
             .repo
             .get_active_signing_key()
             .await?
             .ok_or_else(|| Error::Server("no active api-key signing key".to_string()))?;
-        TokenManager::new_asymmetric(
-            active.private_key_pem.as_bytes(),
-            Some(self.issuer.clone()),
-            Some(active.kid),
+        let pem = open_private_key(
+            self.encryptor.as_deref(),
+            &active.kid,
+            &active.private_key_pem,
         )
-        .map_err(|e| Error::Server(format!("invalid stored signing key: {e}")))
+        .await?;
+        let issuer = Some(self.issuer.clone());
+        let manager = match SigningAlgorithm::from_name(&active.algorithm) {
+            Some(SigningAlgorithm::Rs256) => {
+                TokenManager::new_asymmetric(pem.as_bytes(), issuer, Some(active.kid))
+            }
+            Some(SigningAlgorithm::EdDsa) => {
+                TokenManager::new_ed25519(pem.as_bytes(), issuer, Some(active.kid))
+            }
+            None => {
+                return Err(Error::Server(format!(
+                    "stored signing key {} has unsupported algorithm {}",
+                    active.kid, active.algorithm
+                )));
+            }
+        };
+        manager.map_err(|e| Error::Server(format!("invalid stored signing key: {e}")))
     }
 
     /// Signs an API-key JWT with the current active key for the given key/project/account. `owner`
@@ -428,7 +513,7 @@ impl ApiKeyJwtSigner {
 /// Converts the raw JSON JWKs this service stores (`signing_keys.public_jwk`) into
 /// `authkestra_engine::token::jwk::Jwk` for `JwksResponse`. Deliberately tolerant of a key that
 /// fails to parse (skips it rather than failing the whole response) -- every key here was minted
-/// by `generate_rs256_key` in this same process, so a parse failure would indicate stored-data
+/// by [`generate_signing_key`] in this same service, so a parse failure would indicate stored-data
 /// corruption, not a normal runtime condition worth a hard 500 on every other, still-good key.
 fn to_jwks(raw: Vec<Value>) -> Vec<authkestra_engine::token::jwk::Jwk> {
     raw.into_iter()
@@ -438,9 +523,10 @@ fn to_jwks(raw: Vec<Value>) -> Vec<authkestra_engine::token::jwk::Jwk> {
 
 /// Builds the OIDC discovery document via `authkestra_op::handlers::discovery::OidcDiscovery`
 /// (ADR-0011, Decision 9) rather than the previous hand-built `serde_json::json!`. `OidcDiscovery`
-/// models a full OP (authorization_code + device flows included), which this service structurally
-/// never runs (ADR-0011, Context -- no user store, no login flow). `userinfo_endpoint` is
-/// genuinely optional on the type and is nulled out below, since this service does not serve one.
+/// models a full OP (authorization_code + device flows included); this service runs only the
+/// parts its config mounts, and still owns no user store or login form of its own (ADR-0011,
+/// Context). `userinfo_endpoint` is genuinely optional on the type and is nulled out below, since
+/// this service does not serve one.
 ///
 /// The document is built from three **independent** gates, not one flag driving everything --
 /// this function used to conflate them, which is how `response_types_supported` ended up
@@ -464,22 +550,27 @@ fn to_jwks(raw: Vec<Value>) -> Vec<authkestra_engine::token::jwk::Jwk> {
 ///    `openid`/`offline_access` genuinely gate id_token/refresh-token issuance
 ///    (`oauth2.token_exchange.allowed_scopes`). Advertising scopes with no grant that honours them
 ///    would invent a capability this deployment does not have, same reasoning as `grant_types_supported`.
-/// 3. **Authorization endpoint** -- never advertised, in either state. This service has no
-///    `/authorize` route, no `AuthorizationCodeStore` beyond a permanent no-op stub, and never
-///    redirects a user-agent (token-exchange is a direct machine-to-machine POST/response, not a
-///    redirect flow) -- see ADR-0011, Context. So `authorization_endpoint`,
-///    `response_types_supported`, and `response_modes_supported` are all unconditionally
-///    empty/absent below, independent of `enabled`. Per OIDC Discovery 1.0 §3,
-///    `response_types_supported` is REQUIRED to be present as a JSON array, but the "MUST support
-///    code/id_token/id_token token" clause binds only "Dynamic OpenID Providers" (ones that also
-///    advertise a `registration_endpoint` for dynamic client registration); this deployment
-///    registers clients from static YAML only (ADR-0011, Decision 5) and serves no
-///    `registration_endpoint`, so an empty array here is spec-compliant, not merely tidy.
-///    `response_modes_supported` is OPTIONAL per the same section, so empty is unambiguously fine.
-///    `authorization_endpoint` itself is dropped from the serialized document entirely:
-///    `OidcDiscovery`'s field is a required `String` with no way to omit it via the type itself, so
-///    the value is removed post-serialization below (`obj.remove`), same pattern PR #301 used for
-///    `token_endpoint`.
+///    `device_authorization_supported` extends the same gate: when the RFC 8628 device grant is
+///    mounted (`oauth2.device_authorization.enabled`, which itself requires token exchange), the
+///    `device_code` grant type is listed and `device_authorization_endpoint` (RFC 8628 §4) is
+///    inserted post-serialization, since `OidcDiscovery` has no field for it.
+/// 3. **Authorization endpoint** -- advertised only when the brokered authorization-code flow is
+///    mounted (`authorization_code_supported`, i.e. `oauth2.authorization_code.enabled`, which
+///    itself requires token exchange; see `crate::authorize`). Then `authorization_endpoint` is
+///    `{issuer}/authorize`, `response_types_supported` is `["code"]`,
+///    `response_modes_supported` is `["query"]`, the `authorization_code` grant is listed, and
+///    `code_challenge_methods_supported` (RFC 8414 §2) is `["S256"]`, inserted post-serialization
+///    since `OidcDiscovery` has no field for it. Otherwise all of them are empty/absent. The device
+///    verification page's fixed hop to Keycloak's own login (`crate::device_authorization`) is
+///    not an OAuth authorization endpoint of this issuer and never turns any of this on. Per OIDC
+///    Discovery 1.0 §3, `response_types_supported` is REQUIRED to be present as a JSON array, but
+///    the "MUST support code/id_token/id_token token" clause binds only "Dynamic OpenID
+///    Providers" (ones that also advertise a `registration_endpoint` for dynamic client
+///    registration); this deployment registers clients from static YAML only (ADR-0011, Decision
+///    5) and serves no `registration_endpoint`, so an empty array (or just `code`) is
+///    spec-compliant, not merely tidy. `authorization_endpoint` itself needs no handling here:
+///    `OidcDiscovery::from_config` sets it exactly when `grant_types_supported` lists
+///    `authorization_code` and omits it from the serialized document otherwise.
 ///
 /// **`revocation_endpoint` is absent, on purpose, not an oversight -- it cannot be added here.**
 /// `POST /oauth2/revoke` (RFC 7009) is real, mounted, and live
@@ -496,12 +587,16 @@ fn discovery_document(
     issuer: &str,
     token_exchange_scopes: Option<&[String]>,
     private_key_jwt_supported: bool,
+    device_authorization_supported: bool,
+    authorization_code_supported: bool,
 ) -> serde_json::Value {
     let enabled = token_exchange_scopes.is_some();
+    let device_enabled = enabled && device_authorization_supported;
+    let authorize_enabled = enabled && authorization_code_supported;
     let scopes_supported = token_exchange_scopes
         .map(<[String]>::to_vec)
         .unwrap_or_default();
-    let grant_types_supported = if enabled {
+    let mut grant_types_supported = if enabled {
         vec![
             crate::token_exchange::TOKEN_EXCHANGE_GRANT.to_string(),
             crate::token_exchange::REFRESH_TOKEN_GRANT.to_string(),
@@ -509,13 +604,22 @@ fn discovery_document(
     } else {
         Vec::new()
     };
+    if device_enabled {
+        grant_types_supported.push(crate::token_exchange::DEVICE_CODE_GRANT.to_string());
+    }
+    let response_types_supported = if authorize_enabled {
+        grant_types_supported.push(crate::token_exchange::AUTHORIZATION_CODE_GRANT.to_string());
+        vec!["code".to_string()]
+    } else {
+        Vec::new()
+    };
 
     let op_config = OpConfig {
         issuer: issuer.to_string(),
         scopes_supported,
-        response_types_supported: Vec::new(),
+        response_types_supported,
         grant_types_supported,
-        id_token_signing_alg: ALGORITHM.to_string(),
+        id_token_signing_alg: SigningAlgorithm::Rs256.as_str().to_string(),
         authorization_code_ttl_secs: 0,
         access_token_ttl_secs: 0,
         device_code_ttl_secs: 0,
@@ -526,14 +630,21 @@ fn discovery_document(
     doc.jwks_uri = format!("{issuer}/.well-known/jwks.json");
     doc.token_endpoint = format!("{issuer}/oauth2/token");
     doc.userinfo_endpoint = None;
-    // `response_modes_supported` is unconditionally empty regardless of `enabled`: response modes
-    // (`query`/`fragment`/`form_post`) describe how an authorization *response* is delivered back
-    // to a browser redirect URI. This service never redirects a user-agent at all -- the
-    // token-exchange grant is a direct machine-to-machine POST/response, not a redirect flow -- so
-    // no response mode ever applies, on or off. `from_config` defaults this to `["query"]`
-    // (appropriate for the authorization_code flow it also models), which would misrepresent a
-    // capability this service never had regardless of token-exchange config.
-    doc.response_modes_supported = Vec::new();
+    // Every algorithm a stored key may have: during an RS256 -> EdDSA migration tokens of both
+    // are in circulation, and which one signs next depends on the active key, not on this config.
+    doc.id_token_signing_alg_values_supported = SigningAlgorithm::ALL
+        .iter()
+        .map(|alg| alg.as_str().to_string())
+        .collect();
+    // Response modes (`query`/`fragment`/`form_post`) describe how an authorization *response* is
+    // delivered back to a browser redirect URI. Only `/authorize` ever delivers one, and only as
+    // query parameters; the token-exchange grant is a direct machine-to-machine POST/response, and
+    // the device grant hands its code out of band. `from_config` defaults this to `["query"]`
+    // unconditionally, which would advertise a capability a deployment without `/authorize` does
+    // not have.
+    if !authorize_enabled {
+        doc.response_modes_supported = Vec::new();
+    }
     doc.token_endpoint_auth_methods_supported = if private_key_jwt_supported {
         vec!["none".to_string(), "private_key_jwt".to_string()]
     } else {
@@ -573,10 +684,21 @@ fn discovery_document(
     let mut value =
         serde_json::to_value(&doc).unwrap_or_else(|_| serde_json::json!({ "issuer": issuer }));
     if let Some(obj) = value.as_object_mut() {
-        obj.remove("authorization_endpoint");
+        if authorize_enabled {
+            obj.insert(
+                "code_challenge_methods_supported".to_string(),
+                serde_json::json!(["S256"]),
+            );
+        }
         if !enabled {
             obj.remove("token_endpoint");
         }
+        if device_enabled {
+            obj.insert(
+                "device_authorization_endpoint".to_string(),
+                serde_json::Value::String(format!("{issuer}/oauth2/device_authorization")),
+            );
+        }
     }
     value
 }
@@ -592,11 +714,15 @@ fn discovery_document(
 /// `oauth2.token_exchange` block is absent from config, which deserializes to `None` the same
 /// way). `discovery_document` drops `token_endpoint` from the disabled document entirely, matching
 /// the previous hand-built document -- see its doc comment for the full rationale.
+/// `device_authorization_supported` advertises the RFC 8628 endpoint and grant on top of it, and
+/// `authorization_code_supported` the `/authorize` endpoint, response type, and grant.
 pub fn well_known_router<S>(
     issuer: &str,
     repo: Arc<StoreRepo>,
     token_exchange_scopes: Option<Vec<String>>,
     private_key_jwt_supported: bool,
+    device_authorization_supported: bool,
+    authorization_code_supported: bool,
 ) -> Router<S>
 where
     S: Clone + Send + Sync + 'static,
@@ -616,6 +742,8 @@ where
                         &issuer,
                         scopes.as_deref(),
                         private_key_jwt_supported,
+                        device_authorization_supported,
+                        authorization_code_supported,
                     ))
                 }
             }),
@@ -0,0 +1,171 @@
+//! Signing-key rotation (`signing_keys`), run two ways:
+//!
+//! - [`spawn_signing_key_rotation`]: a background task every service that bootstraps a signing
+//!   key (`authz-api`, `authz-idp`, `lightbridge-mcp`) starts alongside its listener, running
+//!   [`rotate_signing_keys`] once at startup and then every [`SIGNING_KEY_ROTATION_INTERVAL`].
+//! - [`run_emergency_rotation`]: `lightbridge-authz signing-keys emergency-rotate`, for a key
+//!   that may have leaked.
+//!
+//! A scheduled rotation never signs with a key relying parties have not had a chance to fetch.
+//! The successor is inserted as `pending` `oauth2.signing.rotation_lead_seconds` before the active
+//! key reaches `max_key_age_days`. It is published in the JWKS from then on, and only becomes
+//! `active` once the lead time has passed. The key it replaces goes `stale` and stays published
+//! until every token it signed has expired ([`signing_key_retention`]), then it is deleted.
+//!
+//! An emergency rotation skips all of that on purpose: the active key is `revoked` (dropped from
+//! the JWKS at once, private material wiped) and a fresh key signs immediately, so every token the
+//! old key signed stops verifying as soon as relying parties refresh the JWKS.
+//!
+//! Every status change takes the same advisory lock as `bootstrap_signing_key`, so any number of
+//! replicas can run this concurrently.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_core::config::{JwtSigning, Oauth2};
+use lightbridge_authz_core::db::DbPoolTrait;
+use lightbridge_authz_core::error::{Error, Result};
+
+use crate::key_encryption::{KeyEncryptor, key_encryptor};
+use crate::signing::sealed_candidate;
+
+/// How often [`spawn_signing_key_rotation`] re-runs. Bounds how late a due pre-publication or
+/// promotion can happen; a run with nothing to do is two indexed reads and one delete.
+pub const SIGNING_KEY_ROTATION_INTERVAL: Duration = Duration::from_secs(300);
+
+/// What one [`rotate_signing_keys`] run changed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RotationReport {
+    /// Kid of the successor inserted as `pending`.
+    pub prepublished: Option<String>,
+    /// Kid of the pending key that became `active`.
+    pub promoted: Option<String>,
+    /// How many `stale`/`revoked` keys were deleted.
+    pub pruned: u64,
+}
+
+/// How long a retired key must stay published: the longest lifetime of any JWT it can have
+/// signed. That is `oauth2.signing.ttl_seconds` (the API-key JWT cap) or, when token exchange is
+/// enabled, its access-token TTL if longer. `id_token`s share the access-token TTL.
+pub fn signing_key_retention(oauth2: &Oauth2, signing: &JwtSigning) -> chrono::Duration {
+    let exchange = oauth2
+        .token_exchange
+        .as_ref()
+        .filter(|exchange| exchange.enabled)
+        .map_or(0, |exchange| exchange.access_ttl_seconds);
+    chrono::Duration::seconds(signing.ttl_seconds.max(exchange).max(0))
+}
+
+/// One rotation pass at `now`: pre-publish a successor when the active key is due, promote a
+/// pending key whose lead time has passed, and prune retired keys older than `retention`.
+pub async fn rotate_signing_keys(
+    repo: &StoreRepo,
+    cfg: &JwtSigning,
+    encryptor: Option<&dyn KeyEncryptor>,
+    retention: chrono::Duration,
+    now: DateTime<Utc>,
+) -> Result<RotationReport> {
+    let lead = chrono::Duration::seconds(cfg.rotation_lead_seconds.max(0));
+    let max_age = chrono::Duration::days(cfg.max_key_age_days.max(1));
+    // The successor must be published `lead` before the active key has signed for `max_age`.
+    let due_cutoff = now - max_age + lead;
+
+    let mut report = RotationReport::default();
+    // Cheap unlocked check first: generating an RSA key on every tick would be wasted work.
+    if prepublish_due(repo, due_cutoff).await? {
+        let candidate = sealed_candidate(cfg, encryptor, now).await?;
+        report.prepublished = repo
+            .prepublish_signing_key(candidate, now + lead, due_cutoff)
+            .await?
+            .map(|row| row.kid);
+    }
+    report.promoted = repo
+        .promote_pending_signing_key(now)
+        .await?
+        .map(|row| row.kid);
+    report.pruned = repo.prune_retired_signing_keys(now - retention).await?;
+    Ok(report)
+}
+
+async fn prepublish_due(repo: &StoreRepo, due_cutoff: DateTime<Utc>) -> Result<bool> {
+    if repo.get_pending_signing_key().await?.is_some() {
+        return Ok(false);
+    }
+    Ok(repo
+        .get_active_signing_key()
+        .await?
+        .is_some_and(|active| active.activates_at.unwrap_or(active.created_at) <= due_cutoff))
+}
+
+/// Runs [`rotate_signing_keys`] now and every [`SIGNING_KEY_ROTATION_INTERVAL`] for the life of
+/// the process. A failed run is logged and retried on the next tick, never fatal: the active key
+/// keeps signing until a run succeeds. Fails only if `oauth2.signing` is missing or its
+/// `key_encryption` KEKs cannot be loaded.
+pub fn spawn_signing_key_rotation(
+    repo: Arc<StoreRepo>,
+    oauth2: &Oauth2,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let cfg = signing_config(oauth2)?.clone();
+    let retention = signing_key_retention(oauth2, &cfg);
+    let encryptor = key_encryptor(cfg.key_encryption.as_ref())?;
+    Ok(tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(SIGNING_KEY_ROTATION_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            match rotate_signing_keys(&repo, &cfg, encryptor.as_deref(), retention, Utc::now())
+                .await
+            {
+                Ok(report) => log_report(&report),
+                Err(err) => tracing::warn!(
+                    error = %err,
+                    "signing-key rotation failed; retrying on the next tick"
+                ),
+            }
+        }
+    }))
+}
+
+/// Revokes the active signing key and activates a fresh one immediately. Returns the new key's
+/// kid and the revoked one's, if there was an active key.
+pub async fn run_emergency_rotation(
+    pool: Arc<dyn DbPoolTrait>,
+    oauth2: &Oauth2,
+) -> Result<(String, Option<String>)> {
+    let cfg = signing_config(oauth2)?;
+    let encryptor = key_encryptor(cfg.key_encryption.as_ref())?;
+    let candidate = sealed_candidate(cfg, encryptor.as_deref(), Utc::now()).await?;
+    let kid = candidate.kid.clone();
+    let revoked = StoreRepo::new(pool)
+        .revoke_active_signing_key(candidate)
+        .await?;
+    tracing::warn!(
+        kid = %kid,
+        revoked = revoked.as_deref().unwrap_or("none"),
+        "emergency signing-key rotation: previous key revoked"
+    );
+    Ok((kid, revoked))
+}
+
+fn signing_config(oauth2: &Oauth2) -> Result<&JwtSigning> {
+    oauth2.signing.as_ref().ok_or_else(|| {
+        Error::Server("oauth2.signing is required to rotate signing keys".to_string())
+    })
+}
+
+fn log_report(report: &RotationReport) {
+    if let Some(kid) = &report.prepublished {
+        tracing::info!(kid = %kid, "signing key pre-published as pending");
+    }
+    if let Some(kid) = &report.promoted {
+        tracing::info!(kid = %kid, "pending signing key promoted to active");
+    }
+    if report.pruned > 0 {
+        tracing::info!(
+            pruned = report.pruned,
+            "expired retired signing keys pruned"
+        );
+    }
+}
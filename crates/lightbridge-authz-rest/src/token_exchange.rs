@@ -2,16 +2,21 @@
 //! `authkestra_op::handlers::token::handle_token` (client authentication, grant dispatch) into
 //! axum rather than taking a dependency on `authkestra-axum`: that crate's `FromRef` bounds pull
 //! in its own `AxumError` wrapper and `tower_cookies` plus a full slate of handlers this service
-//! never routes (`/authorize`, `/device_authorization`, `/userinfo`, enrolment). The handler below
-//! is the ~15 lines that setup actually needs.
+//! never routes (`/userinfo`, enrolment -- the device-authorization and `/authorize` surfaces are
+//! hand-wired the same way, in `crate::device_authorization` and `crate::authorize`). The handler
+//! below is the ~15 lines that setup actually needs.
 //!
 //! Everything grant-type-specific (client auth already lives in `handle_token` itself; exchange/
-//! refresh minting lives in `oauth2_op::store::TokenExchangeOpStore`) -- this module is purely the
-//! HTTP boundary: request/response shapes and the `TokenErrorResponse.error` string -> `StatusCode`
-//! mapping RFC 6749 §5.2 leaves to the server.
+//! refresh/device-code minting lives in `oauth2_op::store::TokenExchangeOpStore`) -- this module
+//! is purely the HTTP boundary: request/response shapes and the `TokenErrorResponse.error` string
+//! -> `StatusCode` mapping RFC 6749 §5.2 leaves to the server. The two exceptions are the RFC 8628
+//! device-code grant and the `authorization_code` grant, which this module dispatches itself
+//! (`device_code_grant`, `authorization_code_grant`) because `handle_token`'s built-in arms for
+//! them cannot be overridden.
 
 use std::sync::Arc;
 
+use authkestra_engine::token::TokenManager;
 use authkestra_op::client::{ClientRegistration, ClientStore, TokenEndpointAuthMethod};
 use authkestra_op::client_assertion::{
     CLIENT_ASSERTION_TYPE_JWT_BEARER, peek_client_assertion_subject, verify_client_assertion,
@@ -28,8 +33,11 @@ use axum::{
     response::{IntoResponse, Response},
     routing::post,
 };
+use lightbridge_authz_core::config::{Oauth2AuthorizationCode, Oauth2DeviceAuthorization};
 use serde::{Deserialize, Serialize};
 
+use crate::authorize::AuthorizeState;
+use crate::device_authorization::DeviceAuthorizationState;
 use crate::oauth2_op::ACCESS_TOKEN_TYPE;
 use crate::oauth2_op::store::{RequestScopedOpStore, TokenExchangeOpStore};
 use crate::signing::ApiKeyJwtSigner;
@@ -38,15 +46,26 @@ use crate::signing::ApiKeyJwtSigner;
 /// `grant_types_supported` stays in lockstep with what this endpoint actually dispatches.
 pub(crate) const TOKEN_EXCHANGE_GRANT: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
 pub(crate) const REFRESH_TOKEN_GRANT: &str = "refresh_token";
+/// RFC 8628 §3.4. Dispatched by [`token_endpoint`] itself, ahead of `handle_token` -- see
+/// `TokenExchangeOpStore::handle_device_code` for why.
+pub(crate) const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+/// Dispatched by [`token_endpoint`] itself too -- see
+/// `TokenExchangeOpStore::handle_authorization_code`.
+pub(crate) const AUTHORIZATION_CODE_GRANT: &str = "authorization_code";
 
 /// Everything the native token-exchange endpoint needs: the self-signed-JWT signer (used only to
 /// build the per-request `TokenManager` `handle_token` requires), the OP-level config discovery
-/// also reads, and the shared `OpStore` implementation.
+/// also reads, and the shared `OpStore` implementation. `device_authorization` is set only when
+/// the RFC 8628 device grant is enabled ([`Self::with_device_authorization`]); it gates the
+/// grant on `/oauth2/token` and carries the state `build_idp_router` mounts the device routes
+/// with. `authorization_code` does the same for `/authorize` ([`Self::with_authorization_code`]).
 #[derive(Clone)]
 pub struct TokenExchangeState {
     signer: ApiKeyJwtSigner,
     op_config: OpConfig,
     op_store: Arc<TokenExchangeOpStore>,
+    device_authorization: Option<DeviceAuthorizationState>,
+    authorization_code: Option<AuthorizeState>,
 }
 
 impl TokenExchangeState {
@@ -59,8 +78,75 @@ impl TokenExchangeState {
             signer,
             op_config,
             op_store,
+            device_authorization: None,
+            authorization_code: None,
         }
     }
+
+    /// Enables the RFC 8628 device grant (ADR-0012) on top of this state: adds it to the OP
+    /// config's `grant_types_supported`, sets the device-code lifetime, and builds the state the
+    /// `/oauth2/device_authorization` and `/device` routes run on.
+    pub fn with_device_authorization(mut self, cfg: &Oauth2DeviceAuthorization) -> Self {
+        if !self
+            .op_config
+            .grant_types_supported
+            .iter()
+            .any(|g| g == DEVICE_CODE_GRANT)
+        {
+            self.op_config
+                .grant_types_supported
+                .push(DEVICE_CODE_GRANT.to_string());
+        }
+        self.op_config.device_code_ttl_secs = cfg.code_ttl_seconds.max(0) as u64;
+        self.device_authorization = Some(DeviceAuthorizationState::new(
+            self.op_config.clone(),
+            self.op_store.clone(),
+            cfg.upstream.clone(),
+        ));
+        self
+    }
+
+    pub fn device_authorization(&self) -> Option<&DeviceAuthorizationState> {
+        self.device_authorization.as_ref()
+    }
+
+    /// Enables the brokered authorization-code flow on top of this state: adds the grant to the
+    /// OP config's `grant_types_supported`, sets the code lifetime, and builds the state the
+    /// `/authorize` routes run on.
+    pub fn with_authorization_code(mut self, cfg: &Oauth2AuthorizationCode) -> Self {
+        if !self
+            .op_config
+            .grant_types_supported
+            .iter()
+            .any(|g| g == AUTHORIZATION_CODE_GRANT)
+        {
+            self.op_config
+                .grant_types_supported
+                .push(AUTHORIZATION_CODE_GRANT.to_string());
+        }
+        if !self
+            .op_config
+            .response_types_supported
+            .iter()
+            .any(|r| r == "code")
+        {
+            self.op_config
+                .response_types_supported
+                .push("code".to_string());
+        }
+        self.op_config.authorization_code_ttl_secs = cfg.code_ttl_seconds.max(0);
+        self.authorization_code = Some(AuthorizeState::new(
+            self.op_config.clone(),
+            self.op_store.clone(),
+            cfg.upstream.clone(),
+            cfg.request_ttl_seconds,
+        ));
+        self
+    }
+
+    pub fn authorization_code(&self) -> Option<&AuthorizeState> {
+        self.authorization_code.as_ref()
+    }
 }
 
 /// Public `/oauth2/token` and `/oauth2/revoke` routes. Public because the presented
@@ -150,10 +236,23 @@ struct TokenResponseBody {
     id_token: Option<String>,
 }
 
+/// `/oauth2/token`: [`dispatch_token_request`], counted in `authz_token_grants` by grant type and
+/// the status it answered with.
 async fn token_endpoint(
     State(state): State<TokenExchangeState>,
     headers: HeaderMap,
     Form(raw): Form<RawTokenRequest>,
+) -> Response {
+    let grant_type = raw.grant_type.clone();
+    let response = dispatch_token_request(&state, &headers, raw).await;
+    crate::metrics::record_token_grant(&grant_type, response.status());
+    response
+}
+
+async fn dispatch_token_request(
+    state: &TokenExchangeState,
+    headers: &HeaderMap,
+    raw: RawTokenRequest,
 ) -> Response {
     let auth_header = headers
         .get(header::AUTHORIZATION)
@@ -172,9 +271,17 @@ async fn token_endpoint(
         }
     };
 
+    if req.grant_type == DEVICE_CODE_GRANT {
+        return device_code_grant(state, req, auth_header, &tokens).await;
+    }
+    if req.grant_type == AUTHORIZATION_CODE_GRANT {
+        return authorization_code_grant(state, req, auth_header, &tokens).await;
+    }
+
     let scoped = RequestScopedOpStore {
         inner: state.op_store.as_ref(),
         project_id,
+        nonce: None,
     };
 
     match handle_token(req, auth_header, &state.op_config, &scoped, &tokens).await {
@@ -183,6 +290,105 @@ async fn token_endpoint(
     }
 }
 
+/// Authenticates the client of a grant this module dispatches itself, exactly as `handle_token`
+/// would -- through the same mirrors `/oauth2/revoke` uses -- before anything grant-specific is
+/// looked at.
+async fn authenticate_grant_client(
+    state: &TokenExchangeState,
+    req: &AkTokenRequest,
+    auth_header: Option<&str>,
+) -> Result<(String, ClientRegistration), ClientAuthError> {
+    let credential = extract_client_credential(
+        req.client_secret.as_deref(),
+        req.client_assertion.as_deref(),
+        req.client_assertion_type.as_deref(),
+        auth_header,
+    )?;
+    let client_id = resolve_credential_client_id(req.client_id.as_deref(), &credential)
+        .ok_or_else(invalid_client)?;
+    let client = match state.op_store.find_client(&client_id).await {
+        Ok(Some(client)) => client,
+        Ok(None) => return Err(invalid_client()),
+        Err(_) => {
+            return Err(ClientAuthError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "server_error",
+                "internal error",
+            ));
+        }
+    };
+    authenticate_endpoint_client(&client, &credential, &state.op_config, &state.op_store).await?;
+    Ok((client_id, client))
+}
+
+/// The RFC 8628 device-code grant, dispatched ahead of `handle_token` (see
+/// [`DEVICE_CODE_GRANT`]). Authenticates the client before anything about the device code is
+/// looked at, and answers `unsupported_grant_type` when the grant is not enabled on this
+/// deployment.
+async fn device_code_grant(
+    state: &TokenExchangeState,
+    req: AkTokenRequest,
+    auth_header: Option<&str>,
+    tokens: &TokenManager,
+) -> Response {
+    let (client_id, client) = match authenticate_grant_client(state, &req, auth_header).await {
+        Ok(authenticated) => authenticated,
+        Err(err) => return err.into_response(),
+    };
+    if state.device_authorization.is_none() {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "unsupported_grant_type",
+            "The device_code grant is not enabled on this authorization server",
+        );
+    }
+
+    match state
+        .op_store
+        .handle_device_code(req, client_id, client, tokens)
+        .await
+    {
+        Ok(resp) => success_response(resp),
+        // RFC 8628 §3.5: the user's refusal is an ordinary RFC 6749 §5.2 token error (400),
+        // unlike the exchange grant's non-member `access_denied` (403).
+        Err(err) if err.error == "access_denied" => {
+            oauth_error(StatusCode::BAD_REQUEST, &err.error, &err.error_description)
+        }
+        Err(err) => error_response(&err),
+    }
+}
+
+/// The `authorization_code` grant, dispatched ahead of `handle_token` (see
+/// [`AUTHORIZATION_CODE_GRANT`]) with the same client authentication and enablement gate as
+/// [`device_code_grant`].
+async fn authorization_code_grant(
+    state: &TokenExchangeState,
+    req: AkTokenRequest,
+    auth_header: Option<&str>,
+    tokens: &TokenManager,
+) -> Response {
+    let (client_id, client) = match authenticate_grant_client(state, &req, auth_header).await {
+        Ok(authenticated) => authenticated,
+        Err(err) => return err.into_response(),
+    };
+    if state.authorization_code.is_none() {
+        return oauth_error(
+            StatusCode::BAD_REQUEST,
+            "unsupported_grant_type",
+            "The authorization_code grant is not enabled on this authorization server",
+        );
+    }
+
+    match state
+        .op_store
+        .handle_authorization_code(req, client_id, client, tokens)
+        .await
+    {
+        Ok(resp) => success_response(resp),
+        Err(err) => error_response(&err),
+    }
+}
+
 fn success_response(resp: AkTokenResponse) -> Response {
     (
         StatusCode::OK,
@@ -199,7 +405,7 @@ fn success_response(resp: AkTokenResponse) -> Response {
         .into_response()
 }
 
-fn error_response(err: &AkTokenErrorResponse) -> Response {
+pub(crate) fn error_response(err: &AkTokenErrorResponse) -> Response {
     oauth_error(
         status_for_oauth_error(&err.error),
         &err.error,
@@ -208,7 +414,7 @@ fn error_response(err: &AkTokenErrorResponse) -> Response {
 }
 
 /// RFC 6749 §5.2 error body.
-fn oauth_error(status: StatusCode, error: &str, description: &str) -> Response {
+pub(crate) fn oauth_error(status: StatusCode, error: &str, description: &str) -> Response {
     (
         status,
         Json(serde_json::json!({
@@ -225,8 +431,9 @@ fn oauth_error(status: StatusCode, error: &str, description: &str) -> Response {
 /// credential) map to 401; `access_denied` (non-member project, an authorization outcome, not an
 /// authentication one) maps to 403; `server_error` maps to 500; everything else RFC 6749 §5.2
 /// defines (`invalid_request`, `invalid_grant`, `invalid_scope`, `unsupported_grant_type`,
-/// `unauthorized_client`, `invalid_target`) maps to 400.
-fn status_for_oauth_error(error: &str) -> StatusCode {
+/// `unauthorized_client`, `invalid_target`) maps to 400, as do RFC 8628 §3.5's polling responses
+/// (`authorization_pending`, `slow_down`, `expired_token`).
+pub(crate) fn status_for_oauth_error(error: &str) -> StatusCode {
     match error {
         "invalid_client" | "invalid_token" => StatusCode::UNAUTHORIZED,
         "access_denied" => StatusCode::FORBIDDEN,
@@ -256,33 +463,39 @@ struct RevokeRequest {
     client_assertion_type: Option<String>,
 }
 
-/// The client-authentication credential a `/oauth2/revoke` request presents. A mirror of
-/// `authkestra_op::handlers::token::PresentedCredential` (`pub(crate)` to `authkestra-op`, and so
-/// unreachable from this crate), narrowed to the two methods any client registered in this
-/// deployment ever uses -- see `oauth2_op::client_store::to_registration`: every configured
+// --- Client authentication outside `handle_token` ------------------------------------------
+//
+// `/oauth2/revoke` and the device-code grant both authenticate the client themselves rather than
+// through `handle_token` (the former is not a token request at all; the latter is dispatched
+// here directly -- see `token_endpoint`), so both go through the mirrors below.
+
+/// The client-authentication credential a `/oauth2/revoke` or device-code request presents. A
+/// mirror of `authkestra_op::handlers::token::PresentedCredential` (`pub(crate)` to
+/// `authkestra-op`, and so unreachable from this crate), narrowed to the two methods any client
+/// registered in this deployment ever uses -- see `oauth2_op::client_store::to_registration`: every configured
 /// client is `NoAuth` (public) or `PrivateKeyJwt` (confidential), and `client_secret_hash` is
 /// always `None`, so a presented `client_secret` (Basic or POST) can never verify regardless of
 /// which registration it is checked against. `Secret` exists so a presented-but-doomed-to-fail
 /// secret is still routed through the same "at most one credential" and "unknown method ->
 /// invalid_client" logic real upstream code applies, rather than silently ignored.
-enum RevokeCredential {
+enum ClientCredential {
     NoCredential,
     Secret { client_id: Option<String> },
     Assertion(String),
 }
 
-/// A `/oauth2/revoke` failure, kept small and `Response`-free until the final conversion at the
-/// handler boundary (`RevokeError::into_response`) -- returning `axum::response::Response`
+/// A client-authentication failure outside `handle_token`, kept small and `Response`-free until the final conversion at the
+/// handler boundary (`ClientAuthError::into_response`) -- returning `axum::response::Response`
 /// directly from a `Result::Err` trips `clippy::result_large_err` (a `Response` is well over the
 /// 128-byte threshold), the same reason `authkestra_op::handlers::token`'s own error path uses a
 /// small `TokenErrorResponse` struct instead of building a `Response` early.
-struct RevokeError {
+struct ClientAuthError {
     status: StatusCode,
     error: &'static str,
     description: String,
 }
 
-impl RevokeError {
+impl ClientAuthError {
     fn new(status: StatusCode, error: &'static str, description: impl Into<String>) -> Self {
         Self {
             status,
@@ -296,8 +509,8 @@ impl RevokeError {
     }
 }
 
-fn invalid_client() -> RevokeError {
-    RevokeError::new(
+fn invalid_client() -> ClientAuthError {
+    ClientAuthError::new(
         StatusCode::UNAUTHORIZED,
         "invalid_client",
         "Client authentication failed",
@@ -305,17 +518,17 @@ fn invalid_client() -> RevokeError {
 }
 
 /// Mirrors `authkestra_op::handlers::token::extract_credential` for the subset of
-/// [`RevokeCredential`] this deployment's clients ever present. Returns `Err` for a malformed
+/// [`ClientCredential`] this deployment's clients ever present. Returns `Err` for a malformed
 /// *request* -- more than one credential presented at once (RFC 6749 §2.3 / RFC 7521 §4.2), or a
 /// `client_assertion` with a missing/wrong `client_assertion_type` -- which is distinct from a
 /// malformed *token value*, the case RFC 7009 §2.2 requires to be a bare 200 (see
 /// `revoke_endpoint`).
-fn extract_revoke_credential(
+fn extract_client_credential(
     client_secret: Option<&str>,
     client_assertion: Option<&str>,
     client_assertion_type: Option<&str>,
     auth_header: Option<&str>,
-) -> Result<RevokeCredential, RevokeError> {
+) -> Result<ClientCredential, ClientAuthError> {
     let basic = auth_header
         .and_then(|auth| auth.strip_prefix("Basic "))
         .and_then(|stripped| {
@@ -333,7 +546,7 @@ fn extract_revoke_credential(
         Some(assertion) => match client_assertion_type {
             Some(CLIENT_ASSERTION_TYPE_JWT_BEARER) => Some(assertion.to_string()),
             _ => {
-                return Err(RevokeError::new(
+                return Err(ClientAuthError::new(
                     StatusCode::BAD_REQUEST,
                     "invalid_request",
                     format!("client_assertion_type must be {CLIENT_ASSERTION_TYPE_JWT_BEARER}"),
@@ -346,7 +559,7 @@ fn extract_revoke_credential(
     let presented =
         u8::from(basic.is_some()) + u8::from(post.is_some()) + u8::from(assertion.is_some());
     if presented > 1 {
-        return Err(RevokeError::new(
+        return Err(ClientAuthError::new(
             StatusCode::BAD_REQUEST,
             "invalid_request",
             "Only one client authentication method may be used per request",
@@ -354,25 +567,25 @@ fn extract_revoke_credential(
     }
 
     Ok(match (basic, post, assertion) {
-        (Some(client_id), _, _) => RevokeCredential::Secret {
+        (Some(client_id), _, _) => ClientCredential::Secret {
             client_id: Some(client_id),
         },
-        (_, Some(_), _) => RevokeCredential::Secret { client_id: None },
-        (_, _, Some(assertion)) => RevokeCredential::Assertion(assertion),
-        (None, None, None) => RevokeCredential::NoCredential,
+        (_, Some(_), _) => ClientCredential::Secret { client_id: None },
+        (_, _, Some(assertion)) => ClientCredential::Assertion(assertion),
+        (None, None, None) => ClientCredential::NoCredential,
     })
 }
 
 /// Mirrors `authkestra_op::handlers::token::resolve_client_id`.
-fn resolve_revoke_client_id(
+fn resolve_credential_client_id(
     req_client_id: Option<&str>,
-    credential: &RevokeCredential,
+    credential: &ClientCredential,
 ) -> Option<String> {
     match credential {
-        RevokeCredential::Secret {
+        ClientCredential::Secret {
             client_id: Some(id),
         } => Some(id.clone()),
-        RevokeCredential::Assertion(assertion) => req_client_id
+        ClientCredential::Assertion(assertion) => req_client_id
             .map(str::to_string)
             .or_else(|| peek_client_assertion_subject(assertion)),
         _ => req_client_id.map(str::to_string),
@@ -380,19 +593,19 @@ fn resolve_revoke_client_id(
 }
 
 /// Mirrors `authkestra_op::handlers::token::authenticate_client` for the two methods any client
-/// in this deployment ever registers (see [`RevokeCredential`]'s doc comment). Any other
+/// in this deployment ever registers (see [`ClientCredential`]'s doc comment). Any other
 /// combination -- a presented secret, or a method/credential mismatch -- is an authentication
-/// failure. This is the one case RFC 7009 §2.2 carves out as NOT a bare 200: client-authentication
-/// failure is the only outcome this endpoint reports as an error.
-async fn authenticate_revoke_client(
+/// failure. For `/oauth2/revoke` this is the one case RFC 7009 §2.2 carves out as NOT a bare 200:
+/// client-authentication failure is the only outcome that endpoint reports as an error.
+async fn authenticate_endpoint_client(
     client: &ClientRegistration,
-    credential: &RevokeCredential,
+    credential: &ClientCredential,
     op_config: &OpConfig,
     op_store: &TokenExchangeOpStore,
-) -> Result<(), RevokeError> {
+) -> Result<(), ClientAuthError> {
     match (client.token_endpoint_auth_method, credential) {
-        (Some(TokenEndpointAuthMethod::NoAuth), RevokeCredential::NoCredential) => Ok(()),
-        (Some(TokenEndpointAuthMethod::PrivateKeyJwt), RevokeCredential::Assertion(assertion)) => {
+        (Some(TokenEndpointAuthMethod::NoAuth), ClientCredential::NoCredential) => Ok(()),
+        (Some(TokenEndpointAuthMethod::PrivateKeyJwt), ClientCredential::Assertion(assertion)) => {
             let verified = verify_client_assertion(
                 assertion,
                 client,
@@ -411,7 +624,7 @@ async fn authenticate_revoke_client(
                     );
                     Err(invalid_client())
                 }
-                Err(_) => Err(RevokeError::new(
+                Err(_) => Err(ClientAuthError::new(
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "server_error",
                     "internal error",
@@ -458,7 +671,7 @@ async fn revoke_endpoint(
         "revocation request received"
     );
 
-    let credential = match extract_revoke_credential(
+    let credential = match extract_client_credential(
         raw.client_secret.as_deref(),
         raw.client_assertion.as_deref(),
         raw.client_assertion_type.as_deref(),
@@ -468,7 +681,8 @@ async fn revoke_endpoint(
         Err(err) => return err.into_response(),
     };
 
-    let Some(client_id) = resolve_revoke_client_id(raw.client_id.as_deref(), &credential) else {
+    let Some(client_id) = resolve_credential_client_id(raw.client_id.as_deref(), &credential)
+    else {
         return invalid_client().into_response();
     };
 
@@ -485,7 +699,7 @@ async fn revoke_endpoint(
     };
 
     if let Err(err) =
-        authenticate_revoke_client(&client, &credential, &state.op_config, &state.op_store).await
+        authenticate_endpoint_client(&client, &credential, &state.op_config, &state.op_store).await
     {
         return err.into_response();
     }
@@ -60,6 +60,8 @@ fn create_input(expires_at: Option<chrono::DateTime<Utc>>) -> CreateApiKey {
         name: "k".to_string(),
         expires_at,
         billing_plan: "free".to_string(),
+        allowed_models: None,
+        scopes: None,
     }
 }
 
@@ -167,6 +169,8 @@ async fn rotate_api_key_reaches_the_lookup_first() {
                 name: None,
                 expires_at: None,
                 grace_period_seconds: None,
+                allowed_models: None,
+                scopes: None,
             },
         )
         .await
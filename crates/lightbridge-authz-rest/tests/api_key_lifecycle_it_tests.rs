@@ -0,0 +1,248 @@
+// Integration tests are their own crates, so clippy's `allow-unwrap-in-tests`
+// (clippy.toml) does not reach their free helper functions. Unwrapping in a test
+// is a deliberate assertion that the setup held; the workspace gate stays `deny`
+// for shipping code.
+#![allow(clippy::unwrap_used)]
+
+//! Live-database coverage for the `api_key_lifecycle` job: a scan records expiring and dormant
+//! keys in the outbox, revokes dormant keys only under a project's opt-in policy, and hands the
+//! events to a signed webhook, keeping the ones it refused for the next run. Also covers
+//! `setProjectKeyLifecyclePolicy`'s input validation on `AuthzStoreImpl`.
+//!
+//! Gated behind `it-tests` / `just it-tests` (needs a migrated Postgres via `DATABASE_URL`).
+#![cfg(feature = "it-tests")]
+
+use chrono::{Duration, Utc};
+use httpmock::{Method::POST, MockServer};
+use lightbridge_authz_api_key::entities::new_api_key_row::NewApiKeyRow;
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_core::config::ApiKeyLifecycle;
+use lightbridge_authz_core::crypto::hmac_sha256_hex;
+use lightbridge_authz_core::cuid::cuid2;
+use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
+use lightbridge_authz_core::error::Error;
+use lightbridge_authz_core::{ApiKeyStatus, CreateAccount, CreateProject};
+use lightbridge_authz_rest::api_key_lifecycle::{
+    ApiKeyLifecycleJob, ApiKeyLifecycleNotifier, WebhookNotifier,
+};
+use lightbridge_authz_rest::handlers::AuthzStoreImpl;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+const SECRET: &[u8] = b"lifecycle-webhook-secret";
+
+fn core_pool(pool: PgPool) -> Arc<dyn DbPoolTrait> {
+    Arc::new(DbPool::from_pool(pool))
+}
+
+/// An owner with one project holding one never-used key per `(created_days_ago, expires_in_days)`.
+async fn seed(repo: &StoreRepo, keys: &[(i64, i64)]) -> (String, String, Vec<String>) {
+    let subject = format!("owner-{}", cuid2());
+    let account = repo
+        .create_account(
+            &subject,
+            CreateAccount {
+                default_quota: None,
+            },
+        )
+        .await
+        .unwrap();
+    let project = repo
+        .create_project(
+            &subject,
+            &account.id,
+            CreateProject {
+                name: "proj".to_string(),
+                allowed_models: None,
+                default_limits: None,
+                billing_plan: "free".to_string(),
+                billing_identity: format!("bill-{}", cuid2()),
+                project_quota: None,
+            },
+            cuid2(),
+        )
+        .await
+        .unwrap();
+    let now = Utc::now();
+    let mut ids = Vec::new();
+    for (created_days_ago, expires_in_days) in keys {
+        let key = repo
+            .create_api_key(
+                &subject,
+                NewApiKeyRow {
+                    id: cuid2(),
+                    project_id: project.id.clone(),
+                    name: "key".to_string(),
+                    key_prefix: "lbk_test".to_string(),
+                    key_hash: format!("hash_{}", cuid2()),
+                    created_at: now - Duration::days(*created_days_ago),
+                    expires_at: Some(now + Duration::days(*expires_in_days)),
+                    status: ApiKeyStatus::Active.to_string(),
+                    last_used_at: None,
+                    last_ip: None,
+                    revoked_at: None,
+                    billing_plan: "free".to_string(),
+                    allowed_models: None,
+                    scopes: None,
+                    allowed_cidrs: None,
+                },
+            )
+            .await
+            .unwrap();
+        ids.push(key.id);
+    }
+    (subject, project.id, ids)
+}
+
+fn lifecycle_config() -> ApiKeyLifecycle {
+    ApiKeyLifecycle {
+        dormant_after_days: Some(90),
+        ..ApiKeyLifecycle::default()
+    }
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn a_scan_revokes_opted_in_dormant_keys_and_delivers_signed_events(pool: PgPool) {
+    let core = core_pool(pool);
+    let repo = Arc::new(StoreRepo::new(core.clone()));
+    // One key expiring in two days, one unused for 120 days.
+    let (subject, project_id, ids) = seed(&repo, &[(0, 2), (120, 30)]).await;
+    repo.set_project_key_lifecycle_policy(&subject, &project_id, None, true)
+        .await
+        .unwrap();
+
+    let webhook = MockServer::start_async().await;
+    let hook = webhook
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/hooks/keys")
+                .header_exists("x-lightbridge-signature");
+            then.status(204);
+        })
+        .await;
+    let notifier = WebhookNotifier::new(webhook.url("/hooks/keys"), Some(SECRET.to_vec())).unwrap();
+    let job = ApiKeyLifecycleJob::new(
+        repo.clone(),
+        &lifecycle_config(),
+        Some(Arc::new(notifier)),
+        None,
+    )
+    .unwrap();
+
+    let run = job.run_once(Utc::now()).await.unwrap();
+    assert_eq!(run.expiring, 1);
+    assert_eq!(run.revoked, vec![ids[1].clone()]);
+    assert_eq!((run.delivered, run.failed), (2, 0));
+    hook.assert_calls_async(2).await;
+
+    let key = repo.get_api_key(&subject, &ids[1]).await.unwrap().unwrap();
+    assert_eq!(key.status, ApiKeyStatus::Revoked);
+    assert!(
+        repo.list_undelivered_api_key_lifecycle_events(10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Nothing new to find or deliver on the next run.
+    let run = job.run_once(Utc::now()).await.unwrap();
+    assert_eq!((run.expiring, run.delivered), (0, 0));
+    assert!(run.revoked.is_empty());
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn a_refused_delivery_stays_in_the_outbox(pool: PgPool) {
+    let core = core_pool(pool);
+    let repo = Arc::new(StoreRepo::new(core.clone()));
+    let (_, _, ids) = seed(&repo, &[(0, 1)]).await;
+
+    let webhook = MockServer::start_async().await;
+    webhook
+        .mock_async(|when, then| {
+            when.method(POST).path("/hooks/keys");
+            then.status(503);
+        })
+        .await;
+    let job = ApiKeyLifecycleJob::new(
+        repo.clone(),
+        &lifecycle_config(),
+        Some(Arc::new(
+            WebhookNotifier::new(webhook.url("/hooks/keys"), None).unwrap(),
+        )),
+        None,
+    )
+    .unwrap();
+
+    let run = job.run_once(Utc::now()).await.unwrap();
+    assert_eq!((run.delivered, run.failed), (0, 1));
+    let pending = repo
+        .list_undelivered_api_key_lifecycle_events(10)
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].api_key_id, ids[0]);
+    assert_eq!(pending[0].delivery_attempts, 1);
+    assert!(pending[0].last_error.as_deref().unwrap().contains("503"));
+}
+
+#[tokio::test]
+async fn webhook_signature_is_hmac_sha256_of_the_exact_body() {
+    let webhook = MockServer::start_async().await;
+    let event = lightbridge_authz_rest::api_key_lifecycle::ApiKeyLifecycleEvent {
+        id: 7,
+        event_type: "expiring".to_string(),
+        api_key_id: "key_1".to_string(),
+        project_id: "proj_1".to_string(),
+        threshold_days: 3,
+        occurs_at: Utc::now(),
+        created_at: Utc::now(),
+    };
+    let body = serde_json::to_vec(&event).unwrap();
+    let hook = webhook
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/hooks/keys")
+                .header(
+                    "x-lightbridge-signature",
+                    format!("sha256={}", hmac_sha256_hex(SECRET, &body)),
+                )
+                .body(String::from_utf8(body.clone()).unwrap());
+            then.status(200);
+        })
+        .await;
+
+    WebhookNotifier::new(webhook.url("/hooks/keys"), Some(SECRET.to_vec()))
+        .unwrap()
+        .notify(&event)
+        .await
+        .unwrap();
+    hook.assert_async().await;
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn set_project_key_lifecycle_policy_rejects_non_positive_days(pool: PgPool) {
+    let core = core_pool(pool);
+    let repo = StoreRepo::new(core.clone());
+    let (subject, project_id, _) = seed(&repo, &[]).await;
+    let store = AuthzStoreImpl::with_pool(core);
+
+    for days in [0, -1, i64::from(i32::MAX) + 1] {
+        let err = store
+            .set_project_key_lifecycle_policy(&subject, &project_id, Some(days), true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::BadRequest(_)), "{days}: {err:?}");
+    }
+    let policy = store
+        .set_project_key_lifecycle_policy(&subject, &project_id, Some(30), true)
+        .await
+        .unwrap();
+    assert_eq!(policy.dormant_after_days, Some(30));
+    assert_eq!(
+        store
+            .get_project_key_lifecycle_policy(&subject, &project_id)
+            .await
+            .unwrap(),
+        policy
+    );
+}
@@ -0,0 +1,458 @@
+// Integration tests are their own crates, so clippy's `allow-unwrap-in-tests`
+// (clippy.toml) does not reach their free helper functions. Unwrapping in a test
+// is a deliberate assertion that the setup held; the workspace gate stays `deny`
+// for shipping code.
+#![allow(clippy::unwrap_used)]
+#![cfg(feature = "it-tests")]
+
+//! The brokered authorization-code + PKCE flow end to end -- `GET /authorize`, the Keycloak
+//! callback at `/authorize/callback` (the upstream token endpoint is an `httpmock` server), and
+//! the `authorization_code` grant on `/oauth2/token`. Public clients only, so no Redis is ever
+//! dialled.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{HeaderMap, Request, StatusCode, header};
+use httpmock::{Method::POST, MockServer};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode};
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_bearer::{BearerTokenServiceTrait, TokenInfo};
+use lightbridge_authz_budget::decision::{Decision, PolicyEngine};
+use lightbridge_authz_budget::error::BudgetError;
+use lightbridge_authz_budget::facts::Facts;
+use lightbridge_authz_budget::tier::TierLadder;
+use lightbridge_authz_core::async_trait;
+use lightbridge_authz_core::config::{
+    JwtSigning, Oauth2AuthorizationCode, Oauth2TokenExchange, Oauth2Upstream, OauthClient,
+    OauthClientType, SigningAlgorithm,
+};
+use lightbridge_authz_core::cuid::cuid2;
+use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
+use lightbridge_authz_core::{CreateAccount, CreateProject};
+use lightbridge_authz_rest::authorize::authorize_router;
+use lightbridge_authz_rest::oauth2_op::client_assertion_store::RedisClientAssertionStore;
+use lightbridge_authz_rest::oauth2_op::client_store::ConfigClientStore;
+use lightbridge_authz_rest::oauth2_op::store::TokenExchangeOpStore;
+use lightbridge_authz_rest::signing::{ApiKeyJwtSigner, bootstrap_signing_key};
+use lightbridge_authz_rest::token_exchange::{TokenExchangeState, token_exchange_router};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+const ISSUER: &str = "https://authz.example.test";
+const SUBJECT: &str = "kc-browser-user";
+const PROJECT_ID: &str = "proj_browser";
+const CLIENT_ID: &str = "lightbridge-web";
+const REDIRECT_URI: &str = "https://app.example.test/callback";
+const VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+// Never dialled: every client in this file is public, so no assertion jti is ever tracked.
+const UNREACHABLE_REDIS_URL: &str = "redis://127.0.0.1:1";
+
+/// Stands in for Keycloak's JWKS validation of the access token the callback redeems.
+struct MockBearer;
+
+#[async_trait]
+impl BearerTokenServiceTrait for MockBearer {
+    async fn validate_bearer_token(&self, _token: &str) -> anyhow::Result<TokenInfo> {
+        Ok(TokenInfo {
+            active: true,
+            sub: SUBJECT.to_string(),
+            exp: 0,
+            aud: vec![],
+            roles: vec![],
+            permissions: Default::default(),
+            caller_kind: None,
+            access_token: String::new(),
+        })
+    }
+}
+
+/// The ADR-0015 shipped defaults; `evaluate` is never reached from the mint path.
+#[derive(Debug)]
+struct FixedPolicyEngine;
+
+#[async_trait]
+impl PolicyEngine for FixedPolicyEngine {
+    async fn evaluate(
+        &self,
+        _facts: &Facts,
+        _requested_amount_micros: i64,
+    ) -> Result<Decision, BudgetError> {
+        unreachable!("minting never calls PolicyEngine::evaluate")
+    }
+
+    fn allowed_amounts_micros(&self) -> Vec<i64> {
+        vec![6_000_000, 15_000_000, 30_000_000]
+    }
+
+    fn starting_amount_micros(&self) -> i64 {
+        15_000_000
+    }
+
+    fn fail_closed_floor_micros(&self) -> i64 {
+        6_000_000
+    }
+
+    fn tier_ladder(&self) -> TierLadder {
+        TierLadder::legacy()
+    }
+}
+
+fn signing_cfg() -> JwtSigning {
+    JwtSigning {
+        issuer: ISSUER.to_string(),
+        audience: None,
+        ttl_seconds: 7_776_000,
+        max_key_age_days: 30,
+        rotation_lead_seconds: 86_400,
+        algorithm: SigningAlgorithm::Rs256,
+        key_encryption: None,
+    }
+}
+
+fn exchange_cfg() -> Oauth2TokenExchange {
+    Oauth2TokenExchange {
+        enabled: true,
+        access_ttl_seconds: 900,
+        refresh_ttl_seconds: 2_592_000,
+        allowed_scopes: vec!["openid".to_string(), "offline_access".to_string()],
+        refresh_absolute_ttl_seconds: 7_776_000,
+    }
+}
+
+fn authorization_code_cfg(upstream: &MockServer) -> Oauth2AuthorizationCode {
+    Oauth2AuthorizationCode {
+        enabled: true,
+        code_ttl_seconds: 60,
+        request_ttl_seconds: 600,
+        upstream: Oauth2Upstream {
+            authorization_endpoint: upstream.url("/auth"),
+            token_endpoint: upstream.url("/token"),
+            client_id: "authz-browser".to_string(),
+            client_secret: Some("kc-secret".to_string()),
+        },
+    }
+}
+
+fn web_client() -> OauthClient {
+    OauthClient {
+        client_id: CLIENT_ID.to_string(),
+        client_type: OauthClientType::Public,
+        scopes: exchange_cfg().allowed_scopes,
+        grant_types: vec![
+            "authorization_code".to_string(),
+            "refresh_token".to_string(),
+        ],
+        allowed_audiences: vec![CLIENT_ID.to_string()],
+        redirect_uris: vec![REDIRECT_URI.to_string()],
+        jwks: None,
+    }
+}
+
+fn repo(pool: PgPool) -> Arc<StoreRepo> {
+    let pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::from_pool(pool));
+    Arc::new(StoreRepo::new(pool))
+}
+
+async fn seed(repo: &StoreRepo) {
+    repo.create_account(
+        SUBJECT,
+        CreateAccount {
+            default_quota: None,
+        },
+    )
+    .await
+    .expect("seed account");
+    repo.create_project(
+        SUBJECT,
+        SUBJECT,
+        CreateProject {
+            name: "browser-project".to_string(),
+            allowed_models: Some(vec!["gpt-4.1-mini".to_string()]),
+            default_limits: None,
+            billing_plan: "free".to_string(),
+            billing_identity: format!("bill-{}", cuid2()),
+            project_quota: None,
+        },
+        PROJECT_ID.to_string(),
+    )
+    .await
+    .expect("seed project");
+    bootstrap_signing_key(repo, &signing_cfg()).await.unwrap();
+}
+
+/// The `authz-idp` composition `build_idp_router` mounts when the authorization-code flow is
+/// enabled.
+fn app(repo: Arc<StoreRepo>, cfg: Oauth2AuthorizationCode) -> Router {
+    let signer = ApiKeyJwtSigner::from_config(&signing_cfg(), repo.clone()).unwrap();
+    let exchange = exchange_cfg();
+    let op_config = authkestra_op::config::OpConfig {
+        issuer: ISSUER.to_string(),
+        scopes_supported: exchange.allowed_scopes.clone(),
+        response_types_supported: vec!["token".to_string()],
+        grant_types_supported: vec![
+            "urn:ietf:params:oauth:grant-type:token-exchange".to_string(),
+            "refresh_token".to_string(),
+        ],
+        id_token_signing_alg: "RS256".to_string(),
+        authorization_code_ttl_secs: 0,
+        access_token_ttl_secs: 900,
+        device_code_ttl_secs: 0,
+        token_exchange_enabled: true,
+    };
+    let op_store = Arc::new(TokenExchangeOpStore::new(
+        ConfigClientStore::from_config(&[web_client()]),
+        RedisClientAssertionStore::connect(UNREACHABLE_REDIS_URL, None, "test:authorize-jti:")
+            .expect("lazy connection manager always builds"),
+        repo.clone(),
+        repo.clone(),
+        Arc::new(lightbridge_authz_budget::repo::BudgetRepo::new(
+            repo.pool.clone(),
+        )),
+        Arc::new(FixedPolicyEngine),
+        Arc::new(MockBearer),
+        exchange,
+    ));
+    let state = TokenExchangeState::new(signer, op_config, op_store).with_authorization_code(&cfg);
+    let authorize_state = state
+        .authorization_code()
+        .expect("authorization-code flow enabled")
+        .clone();
+    Router::new()
+        .merge(authorize_router(authorize_state))
+        .merge(token_exchange_router(state))
+}
+
+async fn send(app: &Router, request: Request<Body>) -> (StatusCode, HeaderMap, String) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, headers, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::builder().uri(uri).body(Body::empty()).unwrap()
+}
+
+fn challenge(verifier: &str) -> String {
+    use base64::Engine;
+    use sha2::{Digest, Sha256};
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier))
+}
+
+fn authorize_uri(redirect_uri: &str) -> String {
+    let mut url = reqwest::Url::parse(&format!("{ISSUER}/authorize")).unwrap();
+    url.query_pairs_mut()
+        .append_pair("client_id", CLIENT_ID)
+        .append_pair("redirect_uri", redirect_uri)
+        .append_pair("response_type", "code")
+        .append_pair("scope", "openid offline_access")
+        .append_pair("state", "client-state")
+        .append_pair("code_challenge", &challenge(VERIFIER))
+        .append_pair("code_challenge_method", "S256")
+        .append_pair("nonce", "n-123")
+        .append_pair("project_id", PROJECT_ID);
+    format!("/authorize?{}", url.query().unwrap())
+}
+
+fn query_of(location: &str) -> HashMap<String, String> {
+    reqwest::Url::parse(location)
+        .unwrap()
+        .query_pairs()
+        .into_owned()
+        .collect()
+}
+
+/// Reads `name=value` off the response's `Set-Cookie` headers.
+fn set_cookie(headers: &HeaderMap, name: &str) -> String {
+    headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find_map(|v| {
+            v.split(';')
+                .next()
+                .and_then(|pair| pair.strip_prefix(&format!("{name}=")))
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| panic!("no {name} cookie set"))
+}
+
+fn fake_jwt(claims: &Value) -> String {
+    use base64::Engine;
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(claims.to_string());
+    format!("h.{payload}.s")
+}
+
+async fn mock_keycloak_token(upstream: &MockServer) -> httpmock::Mock<'_> {
+    upstream
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/token")
+                .body_includes("grant_type=authorization_code")
+                .body_includes("code=kc-code")
+                .body_includes("code_verifier=")
+                .body_includes("client_secret=kc-secret");
+            then.status(200).json_body(json!({
+                "access_token": fake_jwt(&json!({
+                    "sub": SUBJECT,
+                    "email": "web@example.test",
+                    "email_verified": true,
+                })),
+                "id_token": fake_jwt(&json!({ "sub": SUBJECT, "auth_time": 1_760_000_000 })),
+                "token_type": "Bearer",
+            }));
+        })
+        .await
+}
+
+/// Drives `/authorize` and the Keycloak callback, returning this service's code from the final
+/// redirect to the client.
+async fn sign_in(app: &Router) -> String {
+    let (status, headers, body) = send(app, get(&authorize_uri(REDIRECT_URI))).await;
+    assert_eq!(status, StatusCode::SEE_OTHER, "{body}");
+    let upstream_query = query_of(headers[header::LOCATION].to_str().unwrap());
+    assert_eq!(upstream_query["code_challenge_method"], "S256");
+    assert_eq!(
+        upstream_query["redirect_uri"],
+        format!("{ISSUER}/authorize/callback")
+    );
+    let oauth_state = upstream_query["state"].clone();
+    let cookie = set_cookie(&headers, "lb_authorize_state");
+    assert_eq!(cookie, oauth_state);
+
+    let mut callback = get(&format!(
+        "/authorize/callback?code=kc-code&state={oauth_state}"
+    ));
+    callback.headers_mut().insert(
+        header::COOKIE,
+        format!("lb_authorize_state={cookie}").parse().unwrap(),
+    );
+    let (status, headers, body) = send(app, callback).await;
+    assert_eq!(status, StatusCode::SEE_OTHER, "{body}");
+    let location = headers[header::LOCATION].to_str().unwrap();
+    assert!(location.starts_with(REDIRECT_URI), "{location}");
+    let client_query = query_of(location);
+    assert_eq!(client_query["state"], "client-state");
+    client_query["code"].clone()
+}
+
+async fn redeem(app: &Router, code: &str, verifier: &str) -> (StatusCode, Value) {
+    let mut form = reqwest::Url::parse(ISSUER).unwrap();
+    form.query_pairs_mut()
+        .append_pair("grant_type", "authorization_code")
+        .append_pair("client_id", CLIENT_ID)
+        .append_pair("code", code)
+        .append_pair("code_verifier", verifier)
+        .append_pair("redirect_uri", REDIRECT_URI);
+    let (status, _, body) = send(
+        app,
+        Request::builder()
+            .method("POST")
+            .uri("/oauth2/token")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form.query().unwrap().to_string()))
+            .unwrap(),
+    )
+    .await;
+    (status, serde_json::from_str(&body).unwrap())
+}
+
+async fn verify_token(repo: &StoreRepo, token: &str) -> Value {
+    let jwks = repo.list_verification_jwks().await.unwrap();
+    let jwk = jwks.first().expect("an active signing key");
+    let key =
+        DecodingKey::from_rsa_components(jwk["n"].as_str().unwrap(), jwk["e"].as_str().unwrap())
+            .unwrap();
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_audience(&[CLIENT_ID]);
+    validation.set_issuer(&[ISSUER]);
+    decode::<Value>(token, &key, &validation).unwrap().claims
+}
+
+/// The request's core flow: sign in through Keycloak -> redeem the code with the PKCE verifier
+/// for tokens carrying the Keycloak identity, project, and nonce -> the code is spent.
+#[sqlx::test(migrations = "../../migrations")]
+async fn signed_in_user_redeems_the_code_once_for_project_scoped_tokens(pool: PgPool) {
+    let repo = repo(pool);
+    seed(&repo).await;
+    let upstream = MockServer::start_async().await;
+    let token_mock = mock_keycloak_token(&upstream).await;
+    let app = app(repo.clone(), authorization_code_cfg(&upstream));
+
+    let code = sign_in(&app).await;
+    token_mock.assert_calls_async(1).await;
+
+    let (status, tokens) = redeem(&app, &code, VERIFIER).await;
+    assert_eq!(status, StatusCode::OK, "body: {tokens}");
+    let access = verify_token(&repo, tokens["access_token"].as_str().unwrap()).await;
+    assert_eq!(access["sub"], SUBJECT);
+    assert_eq!(access["project_id"], PROJECT_ID);
+    assert_eq!(access["email"], "web@example.test");
+    let id_token = verify_token(&repo, tokens["id_token"].as_str().unwrap()).await;
+    assert_eq!(id_token["nonce"], "n-123");
+    assert_eq!(id_token["auth_time"], 1_760_000_000);
+    assert!(tokens["refresh_token"].is_string(), "{tokens}");
+
+    let (status, body) = redeem(&app, &code, VERIFIER).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn wrong_verifier_is_refused_and_burns_the_code(pool: PgPool) {
+    let repo = repo(pool);
+    seed(&repo).await;
+    let upstream = MockServer::start_async().await;
+    mock_keycloak_token(&upstream).await;
+    let app = app(repo.clone(), authorization_code_cfg(&upstream));
+
+    let code = sign_in(&app).await;
+    let (status, body) = redeem(&app, &code, &"x".repeat(43)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+
+    let (_, body) = redeem(&app, &code, VERIFIER).await;
+    assert_eq!(body["error"], "invalid_grant");
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn unregistered_redirect_uri_is_never_redirected_to(pool: PgPool) {
+    let repo = repo(pool);
+    seed(&repo).await;
+    let upstream = MockServer::start_async().await;
+    let app = app(repo.clone(), authorization_code_cfg(&upstream));
+
+    let (status, headers, html) =
+        send(&app, get(&authorize_uri("https://evil.example.test/cb"))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(headers.get(header::LOCATION).is_none());
+    assert!(html.contains("Invalid sign-in request"), "{html}");
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn callback_from_another_browser_is_refused(pool: PgPool) {
+    let repo = repo(pool);
+    seed(&repo).await;
+    let upstream = MockServer::start_async().await;
+    let token_mock = mock_keycloak_token(&upstream).await;
+    let app = app(repo.clone(), authorization_code_cfg(&upstream));
+
+    let (_, headers, _) = send(&app, get(&authorize_uri(REDIRECT_URI))).await;
+    let oauth_state = query_of(headers[header::LOCATION].to_str().unwrap())["state"].clone();
+
+    let mut callback = get(&format!(
+        "/authorize/callback?code=kc-code&state={oauth_state}"
+    ));
+    callback
+        .headers_mut()
+        .insert(header::COOKIE, "lb_authorize_state=forged".parse().unwrap());
+    let (status, headers, _) = send(&app, callback).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(headers.get(header::LOCATION).is_none());
+    token_mock.assert_calls_async(0).await;
+}
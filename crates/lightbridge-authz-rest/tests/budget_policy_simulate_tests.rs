@@ -418,3 +418,63 @@ async fn malformed_rule_data_is_rejected(pool: PgPool) {
         "a rejected simulation must not write anything either"
     );
 }
+
+/// The plan/tier/calendar facts and set conditions are accepted end to end: a scenario carrying
+/// `billing_plan`/`day_of_period` evaluates against a rule combining `in` and `not`.
+#[sqlx::test(migrations = "../../migrations")]
+async fn simulation_evaluates_plan_and_calendar_conditions(pool: PgPool) {
+    let (procedures, ctx) = procedures_and_ctx(pool, "tester-simulate-plan").await;
+    let db = lazy_cratestack_db();
+    let rule_data = r#"{
+      "policy_revision": "budget-policy-plan-v1",
+      "rules": [
+        {
+          "id": "enterprise-outside-month-end",
+          "condition": { "type": "all", "conditions": [
+            { "type": "in", "field": "billing_plan", "values": ["enterprise"] },
+            { "type": "not", "condition": { "type": "threshold", "field": "days_remaining_in_period", "operator": "lt", "value": 2 } }
+          ] },
+          "effect": "auto_approve",
+          "reason_code": "enterprise_plan"
+        }
+      ],
+      "default_effect": "manual_review",
+      "default_reason_code": "default_reason",
+      "allowed_amounts_micros": [6000000, 15000000, 30000000],
+      "starting_amount_micros": 15000000,
+      "fail_closed_floor_micros": 6000000
+    }"#;
+    let scenario = |day: u32| {
+        format!(
+            r#"{{
+              "effective_balance_micros": 0,
+              "self_service_grant_count": 5,
+              "spend_this_period": {{ "status": "known", "amount_micros": 0 }},
+              "spend_last_period": {{ "status": "known", "amount_micros": 0 }},
+              "billing_plan": "enterprise",
+              "day_of_period": {day},
+              "days_in_period": 30
+            }}"#
+        )
+    };
+
+    let mid_month = simulate(
+        &procedures,
+        &db,
+        &ctx,
+        simulate_args(rule_data, &scenario(10), "5000000"),
+    )
+    .await
+    .expect("a valid simulation must succeed");
+    assert_eq!(mid_month.effect, "auto_approve");
+
+    let month_end = simulate(
+        &procedures,
+        &db,
+        &ctx,
+        simulate_args(rule_data, &scenario(29), "5000000"),
+    )
+    .await
+    .expect("a valid simulation must succeed");
+    assert_eq!(month_end.effect, "manual_review");
+}
@@ -48,7 +48,6 @@ use lightbridge_authz_budget::repo::{BudgetRepo, GrantRequest};
 use lightbridge_authz_budget::review::ReviewService;
 use lightbridge_authz_budget::source::GrantSource;
 use lightbridge_authz_budget::spend::UnavailableSpendReader;
-use lightbridge_authz_budget::tier::BudgetTier;
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
 use lightbridge_authz_rest::Procedures;
@@ -355,14 +354,14 @@ fn reject_args(
 /// pre-seed an account's self-service grant history so a subsequent `requestBudgetRefill` call
 /// exercises the "allowance exhausted" branch, mirroring
 /// `lightbridge-authz-budget`'s own `refill_service_tests.rs::exhausting_unaided_allowance_routes_to_pending_review`.
-async fn seed_self_service_grant(budget_repo: &BudgetRepo, account_id: &str, tier: BudgetTier) {
+async fn seed_self_service_grant(budget_repo: &BudgetRepo, account_id: &str, amount_micros: i64) {
     budget_repo
         .grant(GrantRequest {
             budget_account_id: account_id.to_string(),
             account_id: account_id.to_string(),
             project_id: None,
             period: Period::parse(PERIOD).expect("valid period"),
-            amount_micros: tier.amount().get(),
+            amount_micros,
             source: GrantSource::SelfService,
             actor_id: None,
             reason: None,
@@ -400,8 +399,8 @@ async fn request_refill_auto_approves_and_the_response_reflects_it(pool: PgPool)
         output.grantId.is_some(),
         "an auto-approved request must carry the grant it produced"
     );
-    // ADR-0015: `requestedTier` is a best-effort `BudgetTier` label for the requested amount, not
-    // a server-chosen "next rung" -- `15000000` maps exactly to `b-15`.
+    // ADR-0015: `requestedTier` is a display label for the requested amount, not a server-chosen
+    // "next rung" -- `15000000` is exactly the active ladder's `b-15`.
     assert_eq!(output.requestedTier, "b-15");
     assert_eq!(output.approvedAmountMicros.as_deref(), Some("15000000"));
 }
@@ -416,8 +415,8 @@ async fn request_refill_exhausting_allowance_returns_pending_review(pool: PgPool
     // The seeded default policy auto-approves only the first two self-service refills per period
     // (`self_service_grant_count < 2`); pre-seed exactly that many so the next request is the
     // third and must route to `pending_review`.
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B15).await;
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B30).await;
+    seed_self_service_grant(&budget_repo, &account_id, 15_000_000).await;
+    seed_self_service_grant(&budget_repo, &account_id, 30_000_000).await;
 
     let output = request_refill(
         &procedures,
@@ -441,7 +440,7 @@ async fn request_refill_exhausting_allowance_returns_pending_review(pool: PgPool
 
 /// ADR-0015: the caller names the amount directly, checked against the active policy's
 /// `allowed_amounts_micros` ($6/$15/$30 in the ADR-0015 seed migration). `$6` is the new floor,
-/// below any `BudgetTier` variant.
+/// below every rung of the seeded (legacy) tier ladder.
 #[sqlx::test(migrations = "../../migrations")]
 async fn request_refill_with_a_named_amount_in_the_offered_set_auto_approves(pool: PgPool) {
     let account_id = cuid2();
@@ -461,8 +460,8 @@ async fn request_refill_with_a_named_amount_in_the_offered_set_auto_approves(poo
     assert_eq!(output.status, "auto_approved");
     assert_eq!(output.approvedAmountMicros.as_deref(), Some("6000000"));
     assert_eq!(
-        output.requestedTier, "b-15",
-        "$6 has no exact BudgetTier label, falls back to b-15"
+        output.requestedTier, "b-6",
+        "$6 is on no rung, so its label is synthesized rather than claiming the $15 rung"
     );
 }
 
@@ -556,8 +555,8 @@ async fn list_pending_returns_queued_requests(pool: PgPool) {
     let (procedures, ctx, budget_repo) = procedures_and_ctx(pool, &account_id).await;
     let db = lazy_cratestack_db();
 
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B15).await;
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B30).await;
+    seed_self_service_grant(&budget_repo, &account_id, 15_000_000).await;
+    seed_self_service_grant(&budget_repo, &account_id, 30_000_000).await;
 
     let queued = request_refill(
         &procedures,
@@ -600,8 +599,8 @@ async fn approve_grants_and_updates_status(pool: PgPool) {
     let reviewer_ctx = ctx_for(&reviewer_id);
     let db = lazy_cratestack_db();
 
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B15).await;
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B30).await;
+    seed_self_service_grant(&budget_repo, &account_id, 15_000_000).await;
+    seed_self_service_grant(&budget_repo, &account_id, 30_000_000).await;
 
     let queued = request_refill(
         &procedures,
@@ -635,8 +634,8 @@ async fn reject_without_a_reason_is_rejected_at_the_schema_or_procedure_layer(po
     let reviewer_ctx = ctx_for(&reviewer_id);
     let db = lazy_cratestack_db();
 
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B15).await;
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B30).await;
+    seed_self_service_grant(&budget_repo, &account_id, 15_000_000).await;
+    seed_self_service_grant(&budget_repo, &account_id, 30_000_000).await;
 
     let queued = request_refill(
         &procedures,
@@ -680,8 +679,8 @@ async fn reject_with_a_reason_succeeds_and_records_it(pool: PgPool) {
     let reviewer_ctx = ctx_for(&reviewer_id);
     let db = lazy_cratestack_db();
 
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B15).await;
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B30).await;
+    seed_self_service_grant(&budget_repo, &account_id, 15_000_000).await;
+    seed_self_service_grant(&budget_repo, &account_id, 30_000_000).await;
 
     let queued = request_refill(
         &procedures,
@@ -724,8 +723,8 @@ async fn list_pending_augmentation_requests_paginates_oldest_first(pool: PgPool)
 
     // Exhaust the auto-approve allowance once, then every further call queues -- three distinct
     // pending rows for one account, oldest to newest in call order.
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B15).await;
-    seed_self_service_grant(&budget_repo, &account_id, BudgetTier::B30).await;
+    seed_self_service_grant(&budget_repo, &account_id, 15_000_000).await;
+    seed_self_service_grant(&budget_repo, &account_id, 30_000_000).await;
 
     let mut queued_ids = Vec::new();
     for _ in 0..3 {
@@ -908,6 +907,24 @@ async fn get_my_budget_refill_ladder_returns_the_active_policys_offered_amounts(
         status.allowedAmountsMicros,
         vec!["6000000", "15000000", "30000000"]
     );
+    // The seeded policy has no `tier_ladder`, so it serves the legacy seven rungs.
+    let tiers: Vec<(&str, &str)> = status
+        .tiers
+        .iter()
+        .map(|rung| (rung.label.as_str(), rung.amountMicros.as_str()))
+        .collect();
+    assert_eq!(
+        tiers,
+        vec![
+            ("b-15", "15000000"),
+            ("b-30", "30000000"),
+            ("b-60", "60000000"),
+            ("b-120", "120000000"),
+            ("b-250", "250000000"),
+            ("b-500", "500000000"),
+            ("b-1000", "1000000000"),
+        ]
+    );
 
     let refill = request_refill(
         &procedures,
@@ -921,11 +938,12 @@ async fn get_my_budget_refill_ladder_returns_the_active_policys_offered_amounts(
 }
 
 /// #387's own regression guard: the pre-ADR-0015 `currentTier`/`currentTierAmountMicros`/
-/// `nextTier`/`nextTierAmountMicros`/`ladder` fields (and the `BudgetLadderRung` type) must be
-/// genuinely absent from the wire response, not merely unused in Rust -- serializes the real,
-/// procedure-produced `Output` the same way the RPC codec would and inspects the actual bytes, so
-/// a future re-introduction of any of these fields on the schema type would be caught even though
-/// nothing in this test file references them by name as a struct field.
+/// `nextTier`/`nextTierAmountMicros`/`ladder` fields must be genuinely absent from the wire
+/// response, not merely unused in Rust -- serializes the real, procedure-produced `Output` the
+/// same way the RPC codec would and inspects the actual top-level keys, so a future
+/// re-introduction of any of these fields on the schema type would be caught even though nothing
+/// in this test file references them by name as a struct field. (`tiers`' rungs legitimately
+/// carry `label`/`amountMicros` again, now read from policy data rather than the removed enum.)
 #[sqlx::test(migrations = "../../migrations")]
 async fn get_my_budget_refill_ladder_response_never_serializes_the_removed_legacy_fields(
     pool: PgPool,
@@ -939,7 +957,10 @@ async fn get_my_budget_refill_ladder_response_never_serializes_the_removed_legac
         .await
         .expect("a fresh account's ladder status must succeed");
 
-    let wire = serde_json::to_string(&status).expect("the response type must serialize");
+    let wire = serde_json::to_value(&status).expect("the response type must serialize");
+    let fields = wire
+        .as_object()
+        .expect("the response serializes as an object");
 
     for legacy_field in [
         "currentTier",
@@ -947,15 +968,14 @@ async fn get_my_budget_refill_ladder_response_never_serializes_the_removed_legac
         "nextTier",
         "nextTierAmountMicros",
         "ladder",
-        "amountMicros",
     ] {
         assert!(
-            !wire.contains(legacy_field),
+            !fields.contains_key(legacy_field),
             "the wire response must never contain the removed field `{legacy_field}`: {wire}"
         );
     }
     assert!(
-        wire.contains("allowedAmountsMicros"),
+        fields.contains_key("allowedAmountsMicros"),
         "the wire response must still carry allowedAmountsMicros: {wire}"
     );
 }
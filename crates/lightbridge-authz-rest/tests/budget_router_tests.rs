@@ -167,7 +167,7 @@ async fn rpc_call<T: serde::Serialize + ?Sized>(
 
 #[tokio::test]
 async fn health_probes_report_ok() {
-    for uri in ["/", "/healthz", "/healthz/startup"] {
+    for uri in ["/", "/healthz", "/healthz/startup", "/metrics"] {
         let router = build_router(admin_bearer());
         assert_eq!(get(router, uri).await, StatusCode::OK, "probe {uri}");
     }
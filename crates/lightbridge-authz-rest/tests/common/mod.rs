@@ -116,6 +116,8 @@ pub fn external_oauth2() -> Oauth2 {
         audience: None,
         signing: None,
         token_exchange: None,
+        device_authorization: None,
+        authorization_code: None,
         rbac: Default::default(),
         clients: Vec::new(),
     }
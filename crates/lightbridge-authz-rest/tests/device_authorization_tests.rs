@@ -0,0 +1,540 @@
+// Integration tests are their own crates, so clippy's `allow-unwrap-in-tests`
+// (clippy.toml) does not reach their free helper functions. Unwrapping in a test
+// is a deliberate assertion that the setup held; the workspace gate stays `deny`
+// for shipping code.
+#![allow(clippy::unwrap_used)]
+#![cfg(feature = "it-tests")]
+
+//! ADR-0012: the RFC 8628 device authorization grant end to end -- `POST
+//! /oauth2/device_authorization`, the `/device` verification page and its Keycloak callback (the
+//! upstream token endpoint is an `httpmock` server), and the `device_code` polls on
+//! `/oauth2/token`. Public clients only, so no Redis is ever dialled.
+
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use httpmock::{Method::POST, MockServer};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode};
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_bearer::{BearerTokenServiceTrait, TokenInfo};
+use lightbridge_authz_budget::decision::{Decision, PolicyEngine};
+use lightbridge_authz_budget::error::BudgetError;
+use lightbridge_authz_budget::facts::Facts;
+use lightbridge_authz_budget::tier::TierLadder;
+use lightbridge_authz_core::async_trait;
+use lightbridge_authz_core::config::{
+    JwtSigning, Oauth2DeviceAuthorization, Oauth2TokenExchange, Oauth2Upstream, OauthClient,
+    OauthClientType, SigningAlgorithm,
+};
+use lightbridge_authz_core::cuid::cuid2;
+use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
+use lightbridge_authz_core::{CreateAccount, CreateProject};
+use lightbridge_authz_rest::device_authorization::device_authorization_router;
+use lightbridge_authz_rest::oauth2_op::client_assertion_store::RedisClientAssertionStore;
+use lightbridge_authz_rest::oauth2_op::client_store::ConfigClientStore;
+use lightbridge_authz_rest::oauth2_op::store::TokenExchangeOpStore;
+use lightbridge_authz_rest::signing::{ApiKeyJwtSigner, bootstrap_signing_key};
+use lightbridge_authz_rest::token_exchange::{TokenExchangeState, token_exchange_router};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+const ISSUER: &str = "https://authz.example.test";
+const SUBJECT: &str = "kc-device-user";
+const PROJECT_ID: &str = "proj_device";
+const CLIENT_ID: &str = "lightbridge-cli";
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+// Never dialled: every client in this file is public, so no assertion jti is ever tracked.
+const UNREACHABLE_REDIS_URL: &str = "redis://127.0.0.1:1";
+
+/// Stands in for Keycloak's JWKS validation of the access token the callback redeems.
+struct MockBearer;
+
+#[async_trait]
+impl BearerTokenServiceTrait for MockBearer {
+    async fn validate_bearer_token(&self, _token: &str) -> anyhow::Result<TokenInfo> {
+        Ok(TokenInfo {
+            active: true,
+            sub: SUBJECT.to_string(),
+            exp: 0,
+            aud: vec![],
+            roles: vec![],
+            permissions: Default::default(),
+            caller_kind: None,
+            access_token: String::new(),
+        })
+    }
+}
+
+/// The ADR-0015 shipped defaults; `evaluate` is never reached from the mint path.
+#[derive(Debug)]
+struct FixedPolicyEngine;
+
+#[async_trait]
+impl PolicyEngine for FixedPolicyEngine {
+    async fn evaluate(
+        &self,
+        _facts: &Facts,
+        _requested_amount_micros: i64,
+    ) -> Result<Decision, BudgetError> {
+        unreachable!("minting never calls PolicyEngine::evaluate")
+    }
+
+    fn allowed_amounts_micros(&self) -> Vec<i64> {
+        vec![6_000_000, 15_000_000, 30_000_000]
+    }
+
+    fn starting_amount_micros(&self) -> i64 {
+        15_000_000
+    }
+
+    fn fail_closed_floor_micros(&self) -> i64 {
+        6_000_000
+    }
+
+    fn tier_ladder(&self) -> TierLadder {
+        TierLadder::legacy()
+    }
+}
+
+fn signing_cfg() -> JwtSigning {
+    JwtSigning {
+        issuer: ISSUER.to_string(),
+        audience: None,
+        ttl_seconds: 7_776_000,
+        max_key_age_days: 30,
+        rotation_lead_seconds: 86_400,
+        algorithm: SigningAlgorithm::Rs256,
+        key_encryption: None,
+    }
+}
+
+fn exchange_cfg() -> Oauth2TokenExchange {
+    Oauth2TokenExchange {
+        enabled: true,
+        access_ttl_seconds: 900,
+        refresh_ttl_seconds: 2_592_000,
+        allowed_scopes: vec!["openid".to_string(), "offline_access".to_string()],
+        refresh_absolute_ttl_seconds: 7_776_000,
+    }
+}
+
+fn device_cfg(upstream: &MockServer, code_ttl_seconds: i64) -> Oauth2DeviceAuthorization {
+    Oauth2DeviceAuthorization {
+        enabled: true,
+        code_ttl_seconds,
+        upstream: Oauth2Upstream {
+            authorization_endpoint: upstream.url("/auth"),
+            token_endpoint: upstream.url("/token"),
+            client_id: "authz-device".to_string(),
+            client_secret: Some("kc-secret".to_string()),
+        },
+    }
+}
+
+fn cli_client(grant_types: &[&str]) -> OauthClient {
+    OauthClient {
+        client_id: CLIENT_ID.to_string(),
+        client_type: OauthClientType::Public,
+        scopes: exchange_cfg().allowed_scopes,
+        grant_types: grant_types.iter().map(|g| g.to_string()).collect(),
+        allowed_audiences: vec![CLIENT_ID.to_string()],
+        redirect_uris: Vec::new(),
+        jwks: None,
+    }
+}
+
+fn repo(pool: PgPool) -> Arc<StoreRepo> {
+    let pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::from_pool(pool));
+    Arc::new(StoreRepo::new(pool))
+}
+
+async fn seed(repo: &StoreRepo) {
+    repo.create_account(
+        SUBJECT,
+        CreateAccount {
+            default_quota: None,
+        },
+    )
+    .await
+    .expect("seed account");
+    repo.create_project(
+        SUBJECT,
+        SUBJECT,
+        CreateProject {
+            name: "device-project".to_string(),
+            allowed_models: Some(vec!["gpt-4.1-mini".to_string()]),
+            default_limits: None,
+            billing_plan: "free".to_string(),
+            billing_identity: format!("bill-{}", cuid2()),
+            project_quota: None,
+        },
+        PROJECT_ID.to_string(),
+    )
+    .await
+    .expect("seed project");
+    bootstrap_signing_key(repo, &signing_cfg()).await.unwrap();
+}
+
+/// The `authz-idp` composition `build_idp_router` mounts when the device grant is enabled.
+fn app(repo: Arc<StoreRepo>, client: OauthClient, device: Oauth2DeviceAuthorization) -> Router {
+    let signer = ApiKeyJwtSigner::from_config(&signing_cfg(), repo.clone()).unwrap();
+    let cfg = exchange_cfg();
+    let op_config = authkestra_op::config::OpConfig {
+        issuer: ISSUER.to_string(),
+        scopes_supported: cfg.allowed_scopes.clone(),
+        response_types_supported: vec!["token".to_string()],
+        grant_types_supported: vec![
+            "urn:ietf:params:oauth:grant-type:token-exchange".to_string(),
+            "refresh_token".to_string(),
+        ],
+        id_token_signing_alg: "RS256".to_string(),
+        authorization_code_ttl_secs: 0,
+        access_token_ttl_secs: 900,
+        device_code_ttl_secs: 0,
+        token_exchange_enabled: true,
+    };
+    let op_store = Arc::new(TokenExchangeOpStore::new(
+        ConfigClientStore::from_config(&[client]),
+        RedisClientAssertionStore::connect(UNREACHABLE_REDIS_URL, None, "test:device-jti:")
+            .expect("lazy connection manager always builds"),
+        repo.clone(),
+        repo.clone(),
+        Arc::new(lightbridge_authz_budget::repo::BudgetRepo::new(
+            repo.pool.clone(),
+        )),
+        Arc::new(FixedPolicyEngine),
+        Arc::new(MockBearer),
+        cfg,
+    ));
+    let state =
+        TokenExchangeState::new(signer, op_config, op_store).with_device_authorization(&device);
+    let device_state = state
+        .device_authorization()
+        .expect("device grant enabled")
+        .clone();
+    Router::new()
+        .merge(device_authorization_router(device_state))
+        .merge(token_exchange_router(state))
+}
+
+async fn send(app: &Router, request: Request<Body>) -> (StatusCode, axum::http::HeaderMap, String) {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, headers, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+fn form_post(uri: &str, body: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+async fn start_pairing(app: &Router) -> Value {
+    let (status, headers, body) = send(
+        app,
+        form_post(
+            "/oauth2/device_authorization",
+            &format!(
+                "client_id={CLIENT_ID}&scope=openid%20offline_access&project_id={PROJECT_ID}&nonce=n-123"
+            ),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+    assert_eq!(headers[header::CACHE_CONTROL], "no-store");
+    serde_json::from_str(&body).unwrap()
+}
+
+async fn poll(app: &Router, device_code: &str) -> (StatusCode, Value) {
+    let (status, _, body) = send(
+        app,
+        form_post(
+            "/oauth2/token",
+            &format!(
+                "grant_type={DEVICE_CODE_GRANT}&client_id={CLIENT_ID}&device_code={device_code}"
+            ),
+        ),
+    )
+    .await;
+    (status, serde_json::from_str(&body).unwrap())
+}
+
+/// Reads `name=value` off the response's `Set-Cookie` headers.
+fn set_cookie(headers: &axum::http::HeaderMap, name: &str) -> String {
+    headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find_map(|v| {
+            v.split(';')
+                .next()
+                .and_then(|pair| pair.strip_prefix(&format!("{name}=")))
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| panic!("no {name} cookie set"))
+}
+
+fn hidden_field(html: &str, name: &str) -> String {
+    let marker = format!("name=\"{name}\" value=\"");
+    let start = html.find(&marker).expect("hidden field present") + marker.len();
+    html[start..].split('"').next().unwrap().to_string()
+}
+
+/// Confirms the user code on `/device` and returns the CSRF token/cookie pair the decision form
+/// posts back.
+async fn confirm_page(app: &Router, user_code: &str) -> (String, String) {
+    let (status, headers, html) = send(
+        app,
+        Request::builder()
+            .uri(format!("/device?user_code={user_code}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{html}");
+    assert!(html.contains(CLIENT_ID), "{html}");
+    assert_eq!(headers[header::X_FRAME_OPTIONS], "DENY");
+    assert!(
+        headers[header::CONTENT_SECURITY_POLICY]
+            .to_str()
+            .unwrap()
+            .contains("frame-ancestors 'none'")
+    );
+    (
+        hidden_field(&html, "csrf"),
+        set_cookie(&headers, "lb_device_csrf"),
+    )
+}
+
+async fn decide(
+    app: &Router,
+    user_code: &str,
+    action: &str,
+    csrf: &str,
+    cookie: &str,
+) -> (StatusCode, axum::http::HeaderMap, String) {
+    let mut request = form_post(
+        "/device",
+        &format!("user_code={user_code}&action={action}&csrf={csrf}"),
+    );
+    request.headers_mut().insert(
+        header::COOKIE,
+        format!("lb_device_csrf={cookie}").parse().unwrap(),
+    );
+    send(app, request).await
+}
+
+fn fake_jwt(claims: &Value) -> String {
+    use base64::Engine;
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(claims.to_string());
+    format!("h.{payload}.s")
+}
+
+async fn verify_token(repo: &StoreRepo, token: &str) -> Value {
+    let jwks = repo.list_verification_jwks().await.unwrap();
+    let jwk = jwks.first().expect("an active signing key");
+    let key =
+        DecodingKey::from_rsa_components(jwk["n"].as_str().unwrap(), jwk["e"].as_str().unwrap())
+            .unwrap();
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_audience(&[CLIENT_ID]);
+    validation.set_issuer(&[ISSUER]);
+    decode::<Value>(token, &key, &validation).unwrap().claims
+}
+
+/// The request's core flow: pending -> slow_down -> the user approves through Keycloak -> one poll
+/// mints tokens carrying the Keycloak identity, project, and nonce -> the device code is spent.
+#[sqlx::test(migrations = "../../migrations")]
+async fn approved_pairing_mints_once_for_the_keycloak_subject(pool: PgPool) {
+    let repo = repo(pool);
+    seed(&repo).await;
+    let upstream = MockServer::start_async().await;
+    let token_mock = upstream
+        .mock_async(|when, then| {
+            when.method(POST)
+                .path("/token")
+                .body_includes("grant_type=authorization_code")
+                .body_includes("code=kc-code")
+                .body_includes("code_verifier=")
+                .body_includes("client_secret=kc-secret");
+            then.status(200).json_body(json!({
+                "access_token": fake_jwt(&json!({
+                    "sub": SUBJECT,
+                    "email": "dev@example.test",
+                    "email_verified": true,
+                })),
+                "id_token": fake_jwt(&json!({ "sub": SUBJECT, "auth_time": 1_760_000_000 })),
+                "token_type": "Bearer",
+            }));
+        })
+        .await;
+    let app = app(
+        repo.clone(),
+        cli_client(&[DEVICE_CODE_GRANT, "refresh_token"]),
+        device_cfg(&upstream, 600),
+    );
+
+    let pairing = start_pairing(&app).await;
+    let device_code = pairing["device_code"].as_str().unwrap().to_string();
+    let user_code = pairing["user_code"].as_str().unwrap().to_string();
+    assert_eq!(pairing["verification_uri"], format!("{ISSUER}/device"));
+    assert_eq!(
+        pairing["verification_uri_complete"],
+        format!("{ISSUER}/device?user_code={user_code}")
+    );
+    assert_eq!(pairing["interval"], 5);
+
+    let (status, body) = poll(&app, &device_code).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "authorization_pending");
+    let (_, body) = poll(&app, &device_code).await;
+    assert_eq!(body["error"], "slow_down");
+
+    // Typed the way a user might: lowercase.
+    let (csrf, cookie) = confirm_page(&app, &user_code.to_lowercase()).await;
+    let (status, headers, _) = decide(&app, &user_code, "approve", &csrf, &cookie).await;
+    assert_eq!(status, StatusCode::SEE_OTHER);
+    let location = reqwest::Url::parse(headers[header::LOCATION].to_str().unwrap()).unwrap();
+    assert!(location.as_str().starts_with(&upstream.url("/auth")));
+    let query: std::collections::HashMap<_, _> = location.query_pairs().into_owned().collect();
+    assert_eq!(query["code_challenge_method"], "S256");
+    assert_eq!(query["redirect_uri"], format!("{ISSUER}/device/callback"));
+    let oauth_state = query["state"].clone();
+    let state_cookie = set_cookie(&headers, "lb_device_state");
+    assert_eq!(state_cookie, oauth_state);
+
+    // A callback from a browser that never confirmed the code is refused and approves nothing.
+    let (status, _, _) = send(
+        &app,
+        Request::builder()
+            .uri(format!("/device/callback?code=kc-code&state={oauth_state}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    token_mock.assert_calls_async(0).await;
+
+    let (status, _, html) = send(
+        &app,
+        Request::builder()
+            .uri(format!("/device/callback?code=kc-code&state={oauth_state}"))
+            .header(header::COOKIE, format!("lb_device_state={state_cookie}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{html}");
+    token_mock.assert_calls_async(1).await;
+
+    let (status, tokens) = poll(&app, &device_code).await;
+    assert_eq!(status, StatusCode::OK, "body: {tokens}");
+    let access = verify_token(&repo, tokens["access_token"].as_str().unwrap()).await;
+    assert_eq!(access["sub"], SUBJECT);
+    assert_eq!(access["project_id"], PROJECT_ID);
+    assert_eq!(access["email"], "dev@example.test");
+    let id_token = verify_token(&repo, tokens["id_token"].as_str().unwrap()).await;
+    assert_eq!(id_token["nonce"], "n-123");
+    assert_eq!(id_token["auth_time"], 1_760_000_000);
+    assert!(tokens["refresh_token"].is_string(), "{tokens}");
+
+    let (status, body) = poll(&app, &device_code).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "invalid_grant");
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn denied_pairing_is_refused_and_spent(pool: PgPool) {
+    let repo = repo(pool);
+    seed(&repo).await;
+    let upstream = MockServer::start_async().await;
+    let app = app(
+        repo.clone(),
+        cli_client(&[DEVICE_CODE_GRANT]),
+        device_cfg(&upstream, 600),
+    );
+
+    let pairing = start_pairing(&app).await;
+    let device_code = pairing["device_code"].as_str().unwrap();
+    let user_code = pairing["user_code"].as_str().unwrap();
+    let (csrf, cookie) = confirm_page(&app, user_code).await;
+
+    // A decision without the matching CSRF cookie changes nothing.
+    let (status, _, _) = decide(&app, user_code, "deny", &csrf, "forged").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (_, body) = poll(&app, device_code).await;
+    assert_eq!(body["error"], "authorization_pending");
+
+    let (status, _, _) = decide(&app, user_code, "deny", &csrf, &cookie).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = poll(&app, device_code).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "access_denied");
+    let (_, body) = poll(&app, device_code).await;
+    assert_eq!(body["error"], "invalid_grant");
+
+    // The decided code no longer resolves on the verification page.
+    let (status, _, _) = send(
+        &app,
+        Request::builder()
+            .uri(format!("/device?user_code={user_code}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn expired_device_code_reports_expired_token(pool: PgPool) {
+    let repo = repo(pool.clone());
+    seed(&repo).await;
+    let upstream = MockServer::start_async().await;
+    let app = app(
+        repo.clone(),
+        cli_client(&[DEVICE_CODE_GRANT]),
+        device_cfg(&upstream, 600),
+    );
+
+    let pairing = start_pairing(&app).await;
+    sqlx::query("UPDATE device_authorizations SET expires_at = now() - interval '1 second'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (status, body) = poll(&app, pairing["device_code"].as_str().unwrap()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "expired_token");
+}
+
+#[sqlx::test(migrations = "../../migrations")]
+async fn client_without_the_device_grant_is_refused(pool: PgPool) {
+    let repo = repo(pool);
+    seed(&repo).await;
+    let upstream = MockServer::start_async().await;
+    let app = app(
+        repo.clone(),
+        cli_client(&["refresh_token"]),
+        device_cfg(&upstream, 600),
+    );
+
+    let (status, _, body) = send(
+        &app,
+        form_post(
+            "/oauth2/device_authorization",
+            &format!("client_id={CLIENT_ID}&scope=openid"),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+    let body: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["error"], "unauthorized_client");
+}
@@ -22,7 +22,9 @@ use axum::body::{Body, to_bytes};
 use axum::http::{Request, StatusCode};
 use lightbridge_authz_api_key::repo::StoreRepo;
 use lightbridge_authz_core::async_trait;
-use lightbridge_authz_core::config::{IdpServer, JwtSigning, Oauth2, Oauth2Type, Tls};
+use lightbridge_authz_core::config::{
+    IdpServer, JwtSigning, Oauth2, Oauth2Type, SigningAlgorithm, Tls,
+};
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
 use lightbridge_authz_rest::{build_idp_router, start_idp_server};
 use tower::ServiceExt;
@@ -51,6 +53,9 @@ fn signing_cfg() -> JwtSigning {
         audience: Some("lightbridge-api-key".to_string()),
         ttl_seconds: 7_776_000,
         max_key_age_days: 30,
+        rotation_lead_seconds: 86_400,
+        algorithm: SigningAlgorithm::Rs256,
+        key_encryption: None,
     }
 }
 
@@ -67,6 +72,8 @@ fn external_oauth2() -> Oauth2 {
         audience: None,
         signing: None,
         token_exchange: None,
+        device_authorization: None,
+        authorization_code: None,
         rbac: Default::default(),
         clients: Vec::new(),
     }
@@ -89,7 +96,7 @@ async fn build_idp_router_probes_behave_like_the_other_servers_including_db_unav
     let signing_repo = Arc::new(StoreRepo::new(pool.clone()));
     let router = build_idp_router(&self_signed_oauth2(), signing_repo, None, pool);
 
-    for path in ["/", "/healthz", "/healthz/startup"] {
+    for path in ["/", "/healthz", "/healthz/startup", "/metrics"] {
         let response = router
             .clone()
             .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
@@ -178,6 +185,10 @@ impl lightbridge_authz_budget::PolicyEngine for UnreachablePolicyEngine {
     fn fail_closed_floor_micros(&self) -> i64 {
         unreachable!("neither test below reaches resolve_budget_tier's fail-closed fallback")
     }
+
+    fn tier_ladder(&self) -> lightbridge_authz_budget::TierLadder {
+        unreachable!("neither test below reaches resolve_budget_tier's ladder read")
+    }
 }
 
 /// Builds a fully offline `TokenExchangeState`: `ApiKeyJwtSigner::from_config` and
@@ -0,0 +1,188 @@
+// Integration tests are their own crates, so clippy's `allow-unwrap-in-tests`
+// (clippy.toml) does not reach their free helper functions. Unwrapping in a test
+// is a deliberate assertion that the setup held; the workspace gate stays `deny`
+// for shipping code.
+#![allow(clippy::unwrap_used)]
+
+//! Live-database coverage for which `AuthzStoreImpl` mutations publish an introspection
+//! [`Invalidation`]. The publisher is pointed at an in-process listener that speaks just enough
+//! RESP to record every `PUBLISH` -- what is asserted is the event a real `authz-opa` subscriber
+//! would receive, not Redis itself. Gated behind `it-tests` (needs a migrated Postgres via
+//! `DATABASE_URL`), like `quota_tier_it_tests.rs`, whose seeding this mirrors.
+#![cfg(feature = "it-tests")]
+
+use lightbridge_authz_api_key::repo::StoreRepo;
+use lightbridge_authz_core::config::{QuotaTier, QuotaTiers};
+use lightbridge_authz_core::cuid::cuid2;
+use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
+use lightbridge_authz_core::{CreateAccount, CreateProject};
+use lightbridge_authz_rest::handlers::AuthzStoreImpl;
+use lightbridge_authz_rest::introspection_cache::{
+    INVALIDATION_CHANNEL, Invalidation, InvalidationPublisher,
+};
+use sqlx::PgPool;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+type Published = Arc<Mutex<Vec<Invalidation>>>;
+
+/// Accepts RESP connections, answers every command `+OK` (`PUBLISH` gets `:1`), and records each
+/// event published on [`INVALIDATION_CHANNEL`]. Returns the `redis://` URL to connect to.
+async fn spawn_recording_redis(published: Published) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("redis://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let published = published.clone();
+            tokio::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut read = BufReader::new(read);
+                while let Some(command) = read_command(&mut read).await {
+                    let reply: &[u8] = if command.first().map(|c| c.eq_ignore_ascii_case("PUBLISH"))
+                        == Some(true)
+                    {
+                        if command.get(1).map(String::as_str) == Some(INVALIDATION_CHANNEL) {
+                            published
+                                .lock()
+                                .unwrap()
+                                .push(serde_json::from_str(&command[2]).unwrap());
+                        }
+                        b":1\r\n"
+                    } else {
+                        b"+OK\r\n"
+                    };
+                    if write.write_all(reply).await.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    url
+}
+
+async fn read_command<R: AsyncBufReadExt + Unpin>(read: &mut R) -> Option<Vec<String>> {
+    let mut line = String::new();
+    if read.read_line(&mut line).await.ok()? == 0 {
+        return None;
+    }
+    let count: usize = line.trim().strip_prefix('*')?.parse().ok()?;
+    let mut args = Vec::with_capacity(count);
+    for _ in 0..count {
+        line.clear();
+        read.read_line(&mut line).await.ok()?;
+        let len: usize = line.trim().strip_prefix('$')?.parse().ok()?;
+        let mut buf = vec![0; len + 2];
+        read.read_exact(&mut buf).await.ok()?;
+        buf.truncate(len);
+        args.push(String::from_utf8(buf).ok()?);
+    }
+    Some(args)
+}
+
+fn configured_tiers() -> QuotaTiers {
+    QuotaTiers {
+        tiers: vec![QuotaTier {
+            id: "gold".to_string(),
+            name: "Gold".to_string(),
+        }],
+    }
+}
+
+/// A project owned by a fresh lead, plus a second account (not yet on the roster).
+async fn seed_project(core: Arc<dyn DbPoolTrait>) -> (String, String, String) {
+    let repo = StoreRepo::new(core);
+    let lead_subject = format!("lead-{}", cuid2());
+    let target_subject = format!("target-{}", cuid2());
+
+    let lead_account = repo
+        .create_account(
+            &lead_subject,
+            CreateAccount {
+                default_quota: None,
+            },
+        )
+        .await
+        .expect("lead account creation");
+    repo.create_account(
+        &target_subject,
+        CreateAccount {
+            default_quota: None,
+        },
+    )
+    .await
+    .expect("target account creation");
+    let project = repo
+        .create_project(
+            &lead_subject,
+            &lead_account.id,
+            CreateProject {
+                name: "proj".to_string(),
+                allowed_models: None,
+                default_limits: None,
+                billing_plan: "free".to_string(),
+                billing_identity: format!("bill-{}", cuid2()),
+                project_quota: None,
+            },
+            cuid2(),
+        )
+        .await
+        .expect("project creation");
+
+    (lead_subject, target_subject, project.id)
+}
+
+/// A member's role and tier are what a cached introspection stamps as `x-project-role`/
+/// `x-quota-tier`, so every roster change must evict the project.
+#[sqlx::test(migrations = "../../migrations")]
+async fn every_roster_change_publishes_a_project_invalidation(pool: PgPool) {
+    let published: Published = Arc::new(Mutex::new(Vec::new()));
+    let url = spawn_recording_redis(published.clone()).await;
+    let core: Arc<dyn DbPoolTrait> = Arc::new(DbPool::from_pool(pool));
+    let store = AuthzStoreImpl::with_pool(core.clone())
+        .with_quota_tiers(configured_tiers())
+        .with_introspection_invalidation(InvalidationPublisher::connect(&url, None).unwrap());
+    let (lead, target, project_id) = seed_project(core).await;
+
+    store
+        .add_project_member(&lead, &project_id, &target, Some("member"))
+        .await
+        .unwrap();
+    store
+        .set_project_member_role(&lead, &project_id, &target, "lead")
+        .await
+        .unwrap();
+    store
+        .set_project_member_quota_tier(&lead, &project_id, &target, Some("gold"))
+        .await
+        .unwrap();
+    store
+        .remove_project_member(&lead, &project_id, &target)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *published.lock().unwrap(),
+        vec![Invalidation::Project(project_id); 4]
+    );
+}
+
+/// A refused roster change commits nothing, so it must publish nothing either.
+#[sqlx::test(migrations = "../../migrations")]
+async fn a_refused_roster_change_publishes_nothing(pool: PgPool) {
+    let published: Published = Arc::new(Mutex::new(Vec::new()));
+    let url = spawn_recording_redis(published.clone()).await;
+    let core: Arc<dyn DbPoolTrait> = Arc::new(DbPool::from_pool(pool));
+    let store = AuthzStoreImpl::with_pool(core.clone())
+        .with_introspection_invalidation(InvalidationPublisher::connect(&url, None).unwrap());
+    let (_, target, project_id) = seed_project(core).await;
+
+    store
+        .add_project_member(&target, &project_id, &target, Some("lead"))
+        .await
+        .expect_err("a non-member cannot add themselves");
+
+    assert!(published.lock().unwrap().is_empty());
+}
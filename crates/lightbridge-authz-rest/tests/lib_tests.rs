@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
 use lightbridge_authz_core::config::{
-    ApiKeyExpiry, ApiServer, BasicAuth, Billing, BillingPlan, ModelCatalog, Oauth2, Oauth2Type,
-    OpaServer, QuotaTiers, Redis, Tls,
+    ApiKeyExpiry, ApiServer, BasicAuth, Billing, BillingPlan, ExtAuthzServer, IntrospectionBudget,
+    IntrospectionCache, ModelCatalog, Oauth2, Oauth2Type, OpaServer, QuotaTiers, Redis, Tls,
 };
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
 use sqlx::postgres::PgPoolOptions;
@@ -79,6 +79,8 @@ fn external_oauth2() -> Oauth2 {
         audience: None,
         signing: None,
         token_exchange: None,
+        device_authorization: None,
+        authorization_code: None,
         rbac: Default::default(),
         clients: Vec::new(),
     }
@@ -108,6 +110,8 @@ async fn start_api_server_fails_fast_when_tls_certs_are_missing() {
         &sample_api_key_expiry(),
         &sample_redis(),
         &None,
+        &None,
+        &None,
     )
     .await;
     assert!(
@@ -132,6 +136,11 @@ async fn start_opa_server_fails_fast_when_tls_certs_are_missing() {
         lazy_pool(),
         &sample_billing(),
         &external_oauth2(),
+        &None,
+        &None,
+        &None,
+        &None,
+        &None,
     )
     .await;
     assert!(
@@ -140,12 +149,43 @@ async fn start_opa_server_fails_fast_when_tls_certs_are_missing() {
     );
 }
 
+#[tokio::test]
+async fn start_extauthz_server_fails_fast_when_tls_certs_are_missing() {
+    let extauthz = ExtAuthzServer {
+        address: "127.0.0.1".to_string(),
+        port: 0,
+        tls: bad_tls(),
+    };
+    let result = lightbridge_authz_rest::extauthz::start_extauthz_server(
+        &extauthz,
+        lazy_pool(),
+        &BasicAuth {
+            username: "authorino".to_string(),
+            password: "change-me".to_string(),
+        },
+        &sample_billing(),
+        &external_oauth2(),
+        &Some(IntrospectionCache::default()),
+        &None,
+        &None,
+        &None,
+        &None,
+    )
+    .await;
+    let err = result.expect_err("missing TLS cert paths must surface as an error");
+    assert!(
+        !format!("{err}").to_lowercase().contains("redis"),
+        "authz-extauthz, like authz-opa, must never fail for a redis-shaped reason: got {err}"
+    );
+}
+
 /// Regression guard for the "authz-opa is freed from the mandatory-Redis requirement" half of
-/// AGENTS.md's "Redis is a mandatory dependency" house rule: `start_opa_server` doesn't even take
-/// a `redis` parameter (unlike `start_api_server`/`start_idp_server`/`start_budget_server`, which
-/// all now hard-require `Config.redis`), so it must run its whole startup sequence to completion
-/// with no Redis configured anywhere, failing only for the TLS reason this test deliberately
-/// induces -- never anything Redis-shaped.
+/// AGENTS.md's "Redis is a mandatory dependency" house rule: `start_opa_server`'s `redis` is only
+/// ever the optional introspection-cache invalidation feed (unlike `start_api_server`/
+/// `start_idp_server`/`start_budget_server`, which all hard-require `Config.redis`), so it must
+/// run its whole startup sequence to completion with no Redis configured anywhere -- even with the
+/// introspection cache turned on -- failing only for the TLS reason this test deliberately induces
+/// -- never anything Redis-shaped.
 #[tokio::test]
 async fn start_opa_server_starts_fine_with_no_redis_configured() {
     let opa = OpaServer {
@@ -162,6 +202,11 @@ async fn start_opa_server_starts_fine_with_no_redis_configured() {
         lazy_pool(),
         &sample_billing(),
         &external_oauth2(),
+        &Some(IntrospectionCache::default()),
+        &None,
+        &None,
+        &None,
+        &None,
     )
     .await;
     let err = result.expect_err("missing TLS cert paths must surface as an error");
@@ -171,6 +216,38 @@ async fn start_opa_server_starts_fine_with_no_redis_configured() {
     );
 }
 
+/// Enforcing budget exhaustion with no usage service would read every spend as unavailable and so
+/// never deny anything -- startup must refuse that rather than run with enforcement silently off.
+#[tokio::test]
+async fn start_opa_server_rejects_budget_enforcement_without_usage_service() {
+    let opa = OpaServer {
+        address: "127.0.0.1".to_string(),
+        port: 0,
+        tls: bad_tls(),
+        basic_auth: BasicAuth {
+            username: "authorino".to_string(),
+            password: "change-me".to_string(),
+        },
+    };
+    let result = lightbridge_authz_rest::start_opa_server(
+        &opa,
+        lazy_pool(),
+        &sample_billing(),
+        &external_oauth2(),
+        &None,
+        &None,
+        &Some(IntrospectionBudget { enforce: true }),
+        &None,
+        &None,
+    )
+    .await;
+    let err = result.expect_err("enforcement without usage_service must fail startup");
+    assert!(
+        format!("{err}").contains("usage_service"),
+        "the error must name the missing block: got {err}"
+    );
+}
+
 /// `oauth2.type: self` with a missing `signing` block must fail before ever touching the
 /// database (the `ok_or_else` short-circuits ahead of `bootstrap_signing_key`), so this stays
 /// fully offline like the tests above.
@@ -195,6 +272,8 @@ async fn start_api_server_rejects_self_signed_oauth2_without_signing_block() {
         &sample_api_key_expiry(),
         &sample_redis(),
         &None,
+        &None,
+        &None,
     )
     .await;
     assert!(
@@ -229,6 +308,8 @@ async fn start_api_server_warns_when_dev_cors_is_enabled() {
         &sample_api_key_expiry(),
         &sample_redis(),
         &None,
+        &None,
+        &None,
     )
     .await;
     unsafe {
@@ -248,12 +329,23 @@ mod db {
     use lightbridge_authz_api::schema;
     use lightbridge_authz_api_key::repo::StoreRepo;
     use lightbridge_authz_bearer::BearerTokenServiceTrait;
-    use lightbridge_authz_core::config::{BudgetServer, JwtSigning, Oauth2Issuance};
+    use lightbridge_authz_core::config::{ApiKeyHashing, ApiKeyPepper};
+    use lightbridge_authz_core::config::{
+        BudgetServer, JwtSigning, Oauth2Issuance, SigningAlgorithm,
+    };
+    use lightbridge_authz_core::crypto::{
+        ApiKeySecretFormat, encode_api_key_secret, verify_api_key_secret,
+    };
     use lightbridge_authz_core::cuid::cuid2;
-    use lightbridge_authz_core::{CreateAccount, CreateApiKey, CreateProject};
-    use lightbridge_authz_rest::OpaRepoTrait;
+    use lightbridge_authz_core::{
+        ApiKeyHasher, CreateAccount, CreateApiKey, CreateProject, hash_api_key,
+    };
+    use lightbridge_authz_rest::api_key_usage::ApiKeyUsageTracker;
     use lightbridge_authz_rest::handlers::AuthzStoreImpl;
+    use lightbridge_authz_rest::handlers::leaked::revoke_leaked_credentials;
+    use lightbridge_authz_rest::handlers::opa::validate_api_key_context;
     use lightbridge_authz_rest::ratelimit_redis::build_redis_rate_limit_store;
+    use lightbridge_authz_rest::{OpaRepoTrait, OpaState};
     use sqlx::PgPool;
 
     /// Unreachable but syntactically valid -- AGENTS.md's "Redis is a mandatory dependency" house
@@ -329,6 +421,9 @@ mod db {
             audience: None,
             ttl_seconds: 7_776_000,
             max_key_age_days: 30,
+            rotation_lead_seconds: 86_400,
+            algorithm: SigningAlgorithm::Rs256,
+            key_encryption: None,
         }
     }
 
@@ -362,6 +457,8 @@ mod db {
             &sample_api_key_expiry(),
             &sample_redis(),
             &None,
+            &None,
+            &None,
         )
         .await;
         assert!(
@@ -401,6 +498,8 @@ mod db {
             &sample_api_key_expiry(),
             &None,
             &None,
+            &None,
+            &None,
         )
         .await
         .expect_err("authz-api must refuse to start with no redis config");
@@ -432,6 +531,8 @@ mod db {
             &sample_api_key_expiry(),
             &unreachable_redis(),
             &None,
+            &None,
+            &None,
         )
         .await;
         let err = result.expect_err("missing TLS cert paths must surface as an error");
@@ -629,6 +730,8 @@ mod db {
                     // `AuthzStoreImpl::create_api_key` now rejects `None` outright.
                     expires_at: Some(chrono::Utc::now() + chrono::Duration::days(30)),
                     billing_plan: "free".to_string(),
+                    allowed_models: None,
+                    scopes: None,
                 },
             )
             .await
@@ -637,15 +740,39 @@ mod db {
 
         let trait_object: Arc<dyn OpaRepoTrait> = repo(pool);
 
+        // Two uses coalesce into one pending entry, and the flush writes the latest of them.
+        let usage = ApiKeyUsageTracker::default();
+        let used_at = chrono::Utc::now();
+        usage.record_at(&api_key.id, used_at - chrono::Duration::seconds(5), None);
+        usage.record_at(&api_key.id, used_at, Some("127.0.0.1".to_string()));
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage.flush(trait_object.as_ref()).await.unwrap(), 1);
+        assert!(usage.is_empty());
         let updated = trait_object
-            .record_api_key_usage(&api_key.id, Some("127.0.0.1".to_string()))
+            .get_api_key_by_id(&api_key.id)
             .await
-            .unwrap();
-        assert_eq!(updated.id, api_key.id);
+            .unwrap()
+            .expect("api key should exist");
         assert_eq!(updated.last_ip.as_deref(), Some("127.0.0.1"));
+        let last_used_at = updated.last_used_at.expect("usage should be recorded");
+        assert!((last_used_at - used_at).num_milliseconds().abs() < 1);
+
+        // A use older than the stored one (another replica flushed later) never moves it back.
+        usage.record_at(
+            &api_key.id,
+            used_at - chrono::Duration::minutes(1),
+            Some("10.0.0.1".to_string()),
+        );
+        assert_eq!(usage.flush(trait_object.as_ref()).await.unwrap(), 0);
+        let unchanged = trait_object
+            .get_api_key_by_id(&api_key.id)
+            .await
+            .unwrap()
+            .expect("api key should exist");
+        assert_eq!(unchanged.last_ip.as_deref(), Some("127.0.0.1"));
 
         let validation = trait_object
-            .find_api_key_validation_by_hash(&api_key.key_hash)
+            .find_api_key_validation_by_hash(std::slice::from_ref(&api_key.key_hash))
             .await
             .unwrap()
             .expect("validation row should exist");
@@ -686,4 +813,221 @@ mod db {
         assert_eq!(resolved.account_id, account.id);
         assert_eq!(resolved.project_id, project.id);
     }
+
+    /// `api_key_hashing` turned on over existing keys: a key stored under the legacy unpeppered
+    /// hash still validates, is re-hashed under the active pepper on that use, and keys issued
+    /// from then on are stored peppered.
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn legacy_hashed_key_validates_under_a_pepper_and_is_upgraded(pool: PgPool) {
+        let db_pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::from_pool(pool.clone()));
+        let pepper_path = std::env::temp_dir().join(format!("authz-pepper-{}", cuid2()));
+        std::fs::write(&pepper_path, "p".repeat(32)).unwrap();
+        let hasher = Arc::new(
+            ApiKeyHasher::from_config(Some(&ApiKeyHashing {
+                active: ApiKeyPepper {
+                    id: "p1".to_string(),
+                    file: Some(pepper_path.to_string_lossy().into_owned()),
+                    env: None,
+                },
+                previous: Vec::new(),
+            }))
+            .unwrap(),
+        );
+        let legacy_store =
+            AuthzStoreImpl::with_pool(db_pool.clone()).with_billing(sample_billing());
+        let peppered_store = legacy_store.clone().with_api_key_hasher(hasher.clone());
+        let subject = "owner-pepper";
+
+        let account = legacy_store
+            .create_account(
+                subject,
+                CreateAccount {
+                    default_quota: None,
+                },
+            )
+            .await
+            .unwrap();
+        let project = repo(pool.clone())
+            .create_project(
+                subject,
+                &account.id,
+                CreateProject {
+                    name: "pepper-project".to_string(),
+                    allowed_models: None,
+                    default_limits: None,
+                    billing_plan: "free".to_string(),
+                    billing_identity: format!("bill-{}", cuid2()),
+                    project_quota: None,
+                },
+                cuid2(),
+            )
+            .await
+            .unwrap();
+        let new_key = |name: &str| CreateApiKey {
+            name: name.to_string(),
+            expires_at: Some(chrono::Utc::now() + chrono::Duration::days(30)),
+            billing_plan: "free".to_string(),
+            allowed_models: None,
+            scopes: None,
+        };
+        let legacy = legacy_store
+            .create_api_key(subject, None, &project.id, new_key("legacy"))
+            .await
+            .unwrap();
+        assert_eq!(legacy.api_key.key_hash, hash_api_key(&legacy.secret));
+
+        let state = Arc::new(OpaState {
+            repo: repo(pool.clone()),
+            basic_auth: BasicAuth {
+                username: "authorino".to_string(),
+                password: "change-me".to_string(),
+            },
+            billing: Arc::new(sample_billing()),
+            api_key_audience: None,
+            introspection_cache: None,
+            introspection_budget: None,
+            api_key_hasher: hasher.clone(),
+            api_key_usage: Arc::new(ApiKeyUsageTracker::default()),
+        });
+        let stored_hash = || async {
+            sqlx::query_scalar::<_, String>("SELECT key_hash FROM api_keys WHERE id = $1")
+                .bind(&legacy.api_key.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        };
+
+        for _ in 0..2 {
+            let validated = validate_api_key_context(&state, &legacy.secret, None)
+                .await
+                .unwrap()
+                .expect("the legacy-hashed key must keep validating");
+            assert_eq!(validated.api_key.id, legacy.api_key.id);
+            assert_eq!(stored_hash().await, hasher.hash(&legacy.secret));
+        }
+
+        let peppered = peppered_store
+            .create_api_key(subject, None, &project.id, new_key("peppered"))
+            .await
+            .unwrap();
+        assert!(peppered.api_key.key_hash.starts_with("v1:p1:"));
+        assert!(
+            validate_api_key_context(&state, &peppered.secret, None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        let _ = std::fs::remove_file(&pepper_path);
+    }
+
+    /// A secret-scanner report: a live key is revoked with the reporter recorded, a well-formed but
+    /// unknown secret and a checksum-failing one are both "not real", and a repeat report of the
+    /// now-revoked key is real but revokes nothing.
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn leaked_credential_report_revokes_only_real_live_keys(pool: PgPool) {
+        let db_pool: Arc<dyn DbPoolTrait> = Arc::new(DbPool::from_pool(pool.clone()));
+        let store = AuthzStoreImpl::with_pool(db_pool).with_billing(sample_billing());
+        let subject = "owner-leaked";
+        let account = store
+            .create_account(
+                subject,
+                CreateAccount {
+                    default_quota: None,
+                },
+            )
+            .await
+            .unwrap();
+        let project = repo(pool.clone())
+            .create_project(
+                subject,
+                &account.id,
+                CreateProject {
+                    name: "leaked-project".to_string(),
+                    allowed_models: None,
+                    default_limits: None,
+                    billing_plan: "free".to_string(),
+                    billing_identity: format!("bill-{}", cuid2()),
+                    project_quota: None,
+                },
+                cuid2(),
+            )
+            .await
+            .unwrap();
+        let leaked = store
+            .create_api_key(
+                subject,
+                None,
+                &project.id,
+                CreateApiKey {
+                    name: "leaked".to_string(),
+                    expires_at: Some(chrono::Utc::now() + chrono::Duration::days(30)),
+                    billing_plan: "free".to_string(),
+                    allowed_models: None,
+                    scopes: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            verify_api_key_secret(&leaked.secret),
+            ApiKeySecretFormat::Checksummed
+        );
+
+        let state = Arc::new(OpaState {
+            repo: repo(pool.clone()),
+            basic_auth: BasicAuth {
+                username: "authorino".to_string(),
+                password: "change-me".to_string(),
+            },
+            billing: Arc::new(sample_billing()),
+            api_key_audience: None,
+            introspection_cache: None,
+            introspection_budget: None,
+            api_key_hasher: Arc::new(ApiKeyHasher::default()),
+            api_key_usage: Arc::new(ApiKeyUsageTracker::default()),
+        });
+        let unknown = encode_api_key_secret(&[7u8; 32]);
+        let mut mangled = leaked.secret.clone();
+        let last = if mangled.pop() == Some('0') { '1' } else { '0' };
+        mangled.push(last);
+
+        let results = revoke_leaked_credentials(
+            &state,
+            "github-secret-scanning",
+            &[
+                leaked.secret.clone(),
+                unknown,
+                mangled,
+                leaked.secret.clone(),
+            ],
+        )
+        .await
+        .unwrap();
+        let real_revoked: Vec<(bool, bool)> = results.iter().map(|r| (r.real, r.revoked)).collect();
+        assert_eq!(
+            real_revoked,
+            vec![(true, true), (false, false), (false, false), (true, true)]
+        );
+
+        let (status, reason): (String, Option<String>) =
+            sqlx::query_as("SELECT status, revocation_reason FROM api_keys WHERE id = $1")
+                .bind(&leaked.api_key.id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status, "revoked");
+        assert_eq!(
+            reason.as_deref(),
+            Some("leaked: reported by github-secret-scanning")
+        );
+
+        let again = revoke_leaked_credentials(
+            &state,
+            "another-scanner",
+            std::slice::from_ref(&leaked.secret),
+        )
+        .await
+        .unwrap();
+        assert_eq!((again[0].real, again[0].revoked), (true, false));
+    }
 }
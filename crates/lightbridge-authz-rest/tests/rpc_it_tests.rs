@@ -44,10 +44,12 @@ use cratestack_core::CratestackCodec;
 use lightbridge_authz_api::schema;
 use lightbridge_authz_api_key::repo::StoreRepo;
 use lightbridge_authz_bearer::BearerTokenServiceTrait;
+use lightbridge_authz_core::ApiKeyHasher;
 use lightbridge_authz_core::authz::Permission;
 use lightbridge_authz_core::config::{BasicAuth, Billing, BillingPlan};
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
+use lightbridge_authz_rest::api_key_usage::ApiKeyUsageTracker;
 use lightbridge_authz_rest::auth_provider::CratestackAuthProvider;
 use lightbridge_authz_rest::handlers::AuthzStoreImpl;
 use lightbridge_authz_rest::ratelimit_redis::build_redis_rate_limit_store;
@@ -1271,6 +1273,10 @@ fn opa_state(core: Arc<dyn DbPoolTrait>) -> Arc<OpaState> {
         },
         billing: Arc::new(billing()),
         api_key_audience: None,
+        introspection_cache: None,
+        introspection_budget: None,
+        api_key_hasher: Arc::new(ApiKeyHasher::default()),
+        api_key_usage: Arc::new(ApiKeyUsageTracker::default()),
     })
 }
 
@@ -1363,6 +1369,115 @@ async fn soft_deleted_api_key_is_excluded_and_fails_opa_validation() {
     );
 }
 
+/// Introspect `secret` as if forwarded from `client_ip`; returns the `active` flag.
+async fn introspect_active_from(router: &Router, secret: &str, client_ip: &str) -> bool {
+    let creds = base64::engine::general_purpose::STANDARD.encode("authorino:secret");
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/authorino/validate/introspect")
+                .header("authorization", format!("Basic {creds}"))
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(Body::from(format!("token={secret}&client_ip={client_ip}")))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    body["active"].as_bool().unwrap_or(false)
+}
+
+/// `setApiKeyAllowedCidrs` end to end: entries are normalized on write, enforced by the real
+/// `api_key_validation` view read, carried over by `rotateApiKey`, and cleared by `null`.
+#[tokio::test]
+async fn api_key_ip_allowlist_is_enforced_survives_rotation_and_clears_with_null() {
+    let subject = format!("owner-cidr-{}", cuid2());
+    let ctx = setup(admin_bearer(&subject)).await;
+    let r = &ctx.router;
+    let opa = opa_router(ctx.core.clone());
+
+    let account_id = create_account(r, "admin", &format!("tenant-cidr-{}", cuid2())).await;
+    let project_id = create_project(r, "admin", &account_id, "proj-cidr").await;
+    let (key_id, secret) = create_api_key(r, "admin", &project_id, "k-cidr").await;
+
+    let cidrs = serde_json::to_value(Json(CValue::List(vec![
+        CValue::String("10.1.2.3/8".to_string()),
+        CValue::String("192.0.2.7".to_string()),
+    ])))
+    .expect("Json<Value> serializes");
+    let (status, body) = rpc_call(
+        r.clone(),
+        "procedure.setApiKeyAllowedCidrs",
+        Wire::Cbor,
+        &json!({ "args": { "keyId": key_id, "allowedCidrs": cidrs } }),
+        Some("admin"),
+    )
+    .await;
+    assert!(
+        status.is_success(),
+        "setApiKeyAllowedCidrs: {status} {}",
+        String::from_utf8_lossy(&body)
+    );
+    assert_eq!(
+        json_body(&body)["allowedCidrs"],
+        json!(["10.0.0.0/8", "192.0.2.7/32"])
+    );
+
+    assert!(introspect_active_from(&opa, &secret, "10.200.0.1").await);
+    assert!(introspect_active_from(&opa, &secret, "192.0.2.7").await);
+    assert!(!introspect_active_from(&opa, &secret, "198.51.100.1").await);
+    assert!(
+        !introspect_active(&opa, &secret).await,
+        "a restricted key must fail closed when no client address is forwarded"
+    );
+
+    let (status, body) = rpc_call(
+        r.clone(),
+        "procedure.rotateApiKey",
+        Wire::Cbor,
+        &json!({ "args": { "keyId": key_id, "expiresAt": near_future_expiry() } }),
+        Some("admin"),
+    )
+    .await;
+    assert!(
+        status.is_success(),
+        "rotateApiKey: {status} {}",
+        String::from_utf8_lossy(&body)
+    );
+    let rotated = json_body(&body);
+    let rotated_id = rotated["apiKey"]["id"]
+        .as_str()
+        .expect("key id")
+        .to_string();
+    let rotated_secret = rotated["secret"].as_str().expect("secret").to_string();
+    assert_eq!(
+        rotated["apiKey"]["allowedCidrs"],
+        json!(["10.0.0.0/8", "192.0.2.7/32"]),
+        "rotation must not silently drop the predecessor's network restriction"
+    );
+    assert!(!introspect_active_from(&opa, &rotated_secret, "198.51.100.1").await);
+
+    let (status, body) = rpc_call(
+        r.clone(),
+        "procedure.setApiKeyAllowedCidrs",
+        Wire::Cbor,
+        &json!({ "args": { "keyId": rotated_id, "allowedCidrs": null } }),
+        Some("admin"),
+    )
+    .await;
+    assert!(
+        status.is_success(),
+        "clear allowedCidrs: {status} {}",
+        String::from_utf8_lossy(&body)
+    );
+    assert!(json_body(&body)["allowedCidrs"].is_null());
+    assert!(introspect_active_from(&opa, &rotated_secret, "198.51.100.1").await);
+}
+
 /// PATH B REGRESSION -- guards a fail-open, not a cosmetic response-shape bug.
 ///
 /// `list_and_get_recover_from_legacy_cratestack_tagged_value_json` above covers Path A: the
@@ -2627,6 +2742,97 @@ async fn revoke_subject_sessions_reports_zero_when_nothing_is_active() {
     assert_eq!(parsed["revokedCount"], 0);
 }
 
+/// `revokeSubjectSessions` lands in the `audit_events` trail attributed to the admin who called it,
+/// one `update` per session it revoked, and `listAuditEvents` itself is refused without
+/// `audit:read`. The seeding `INSERT`s ran outside any procedure, so they are not in the trail.
+#[tokio::test]
+async fn list_audit_events_returns_the_trail_of_a_mutation_procedure() {
+    use lightbridge_authz_core::authz::{Permission, PermissionSet};
+
+    let admin_subject = format!("audit-admin-{}", cuid2());
+    let target = format!("audited-offboard-{}", cuid2());
+    let bearer: Arc<dyn BearerTokenServiceTrait> = Arc::new(
+        MapBearer::new()
+            .with("admin", token_info(&admin_subject, admin_perms()))
+            .with(
+                "operator",
+                token_info(
+                    &format!("audit-operator-{}", cuid2()),
+                    PermissionSet::from_iter([Permission::SessionRevoke]),
+                ),
+            ),
+    );
+    let ctx = setup(bearer).await;
+    let r = &ctx.router;
+
+    let session_a = seed_active_session(&ctx.verify, &target).await;
+    let session_b = seed_active_session(&ctx.verify, &target).await;
+    let (status, _) = rpc_call(
+        r.clone(),
+        "procedure.revokeSubjectSessions",
+        Wire::Cbor,
+        &json!({ "args": { "accountId": target } }),
+        Some("admin"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let list = json!({ "args": {
+        "actorSubject": admin_subject,
+        "targetType": "exchange_refresh_tokens",
+    } });
+    let (status, _) = rpc_call(
+        r.clone(),
+        "procedure.listAuditEvents",
+        Wire::Cbor,
+        &list,
+        Some("operator"),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::FORBIDDEN,
+        "session:revoke must not also grant audit:read"
+    );
+
+    let (status, body) = rpc_call(
+        r.clone(),
+        "procedure.listAuditEvents",
+        Wire::Cbor,
+        &list,
+        Some("admin"),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::OK,
+        "body: {}",
+        String::from_utf8_lossy(&body)
+    );
+    let parsed = as_json(Wire::Cbor, &body);
+    let entries = parsed["entries"].as_array().expect("entries is a list");
+    assert_eq!(entries.len(), 2, "one entry per revoked session: {parsed}");
+    let mut target_ids: Vec<&str> = entries
+        .iter()
+        .map(|e| e["targetId"].as_str().expect("targetId is a string"))
+        .collect();
+    target_ids.sort_unstable();
+    let mut expected = vec![session_a.as_str(), session_b.as_str()];
+    expected.sort_unstable();
+    assert_eq!(target_ids, expected);
+    for entry in entries {
+        assert_eq!(entry["opId"], "procedure.revokeSubjectSessions");
+        assert_eq!(entry["action"], "update");
+        assert_eq!(entry["before"]["status"], "active");
+        assert_eq!(entry["after"]["status"], "revoked");
+        assert!(
+            entry["after"].get("token_hash").is_none(),
+            "refresh-token hashes never reach the trail: {entry}"
+        );
+    }
+    assert!(parsed["nextCursor"].is_null());
+}
+
 // ---------------------------------------------------------------------------------------------
 // Section: self-provisioning -- lightbridge-viewer/lightbridge-editor must be able to create their
 // own account (#219: the account row must exist before `project_members.account_id`'s FK to
@@ -261,7 +261,7 @@ async fn get(router: Router, uri: &str) -> StatusCode {
 
 #[tokio::test]
 async fn health_probes_report_ok() {
-    for uri in ["/", "/healthz", "/healthz/startup"] {
+    for uri in ["/", "/healthz", "/healthz/startup", "/metrics"] {
         let router = build_router(admin_bearer(), false);
         assert_eq!(get(router, uri).await, StatusCode::OK, "probe {uri}");
     }
@@ -5,9 +5,11 @@
 #![allow(clippy::unwrap_used)]
 
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
-use lightbridge_authz_core::config::JwtSigning;
+use lightbridge_authz_core::config::{JwtSigning, SigningAlgorithm};
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
-use lightbridge_authz_rest::signing::{ApiKeyJwtSigner, capped_expiry, generate_rs256_key};
+use lightbridge_authz_rest::signing::{
+    ApiKeyJwtSigner, capped_expiry, generate_ed25519_key, generate_rs256_key,
+};
 use serde::{Deserialize, Serialize};
 use sqlx::postgres::PgPoolOptions;
 use std::sync::Arc;
@@ -20,6 +22,9 @@ fn signing_cfg(ttl: i64) -> JwtSigning {
         audience: Some("lightbridge-api-key".to_string()),
         ttl_seconds: ttl,
         max_key_age_days: 30,
+        rotation_lead_seconds: 86_400,
+        algorithm: SigningAlgorithm::Rs256,
+        key_encryption: None,
     }
 }
 
@@ -71,6 +76,35 @@ fn keygen_produces_matched_keypair_and_jwk() {
     assert_eq!(data.claims.sub, "s");
 }
 
+/// Same as above for Ed25519: the PKCS#8 PEM signs, the RFC 8037 `OKP` JWK verifies.
+#[test]
+fn ed25519_keygen_produces_matched_keypair_and_okp_jwk() {
+    let key = generate_ed25519_key().expect("keygen");
+    assert_eq!(key.public_jwk["kid"], key.kid);
+    assert_eq!(key.public_jwk["alg"], "EdDSA");
+    assert_eq!(key.public_jwk["kty"], "OKP");
+    assert_eq!(key.public_jwk["crv"], "Ed25519");
+
+    let encoding = EncodingKey::from_ed_pem(key.private_key_pem.as_bytes()).expect("encoding key");
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some(key.kid.clone());
+    let token = encode(
+        &header,
+        &Probe {
+            sub: "s".to_string(),
+            exp: 4102444800,
+        },
+        &encoding,
+    )
+    .expect("sign");
+
+    let jwk: jsonwebtoken::jwk::Jwk = serde_json::from_value(key.public_jwk).unwrap();
+    let decoding = DecodingKey::from_jwk(&jwk).expect("decoding key");
+    let data = decode::<Probe>(&token, &decoding, &Validation::new(Algorithm::EdDSA))
+        .expect("verify against jwk");
+    assert_eq!(data.claims.sub, "s");
+}
+
 #[tokio::test]
 async fn from_config_builds_signer_for_valid_config() {
     assert!(ApiKeyJwtSigner::from_config(&signing_cfg(3600), lazy_repo()).is_ok());
@@ -108,7 +142,7 @@ async fn well_known_serves_cors_headers() {
     use lightbridge_authz_rest::signing::well_known_router;
     use tower::ServiceExt;
 
-    let response = well_known_router::<()>(ISSUER, lazy_repo(), None, false)
+    let response = well_known_router::<()>(ISSUER, lazy_repo(), None, false, false, false)
         .oneshot(
             Request::builder()
                 .uri("/.well-known/openid-configuration")
@@ -136,7 +170,7 @@ async fn jwks_endpoint_returns_server_error_when_repo_is_unreachable() {
     use serde_json::Value;
     use tower::ServiceExt;
 
-    let response = well_known_router::<()>(ISSUER, lazy_repo(), None, false)
+    let response = well_known_router::<()>(ISSUER, lazy_repo(), None, false, false, false)
         .oneshot(
             Request::builder()
                 .uri("/.well-known/jwks.json")
@@ -226,10 +260,10 @@ fn at_hash_changes_with_the_access_token() {
 /// values must stay in lockstep with what `token_exchange::TOKEN_EXCHANGE_GRANT`/
 /// `REFRESH_TOKEN_GRANT` and `handle_token`'s real dispatch accept (see `token_exchange.rs`), not
 /// just "non-empty". `response_types_supported` is asserted empty here too, not merely omitted
-/// from this list -- see `discovery_never_advertises_response_types_or_modes` below for why that
-/// must hold regardless of the token-exchange gate, and for the regression this guards (this
-/// service served `["token", "id_token", "id_token token"]` here in production once token-exchange
-/// was enabled, claiming an authorization endpoint that has never existed).
+/// from this list -- see `discovery_advertises_response_types_and_modes_only_with_authorize`
+/// below for why token exchange alone must never turn it on, and for the regression this guards
+/// (this service served `["token", "id_token", "id_token token"]` here in production once
+/// token-exchange was enabled, claiming an authorization endpoint that did not exist).
 #[tokio::test]
 async fn discovery_advertises_exact_token_exchange_metadata_when_enabled() {
     use axum::body::{Body, to_bytes};
@@ -244,7 +278,7 @@ async fn discovery_advertises_exact_token_exchange_metadata_when_enabled() {
         "email".to_string(),
         "offline_access".to_string(),
     ];
-    let discovery = well_known_router::<()>(ISSUER, lazy_repo(), Some(scopes), false)
+    let discovery = well_known_router::<()>(ISSUER, lazy_repo(), Some(scopes), false, false, false)
         .oneshot(
             Request::builder()
                 .uri("/.well-known/openid-configuration")
@@ -289,6 +323,64 @@ async fn discovery_advertises_exact_token_exchange_metadata_when_enabled() {
     );
 }
 
+/// The RFC 8628 device grant (ADR-0012) extends the same grant-surface gate: the `device_code`
+/// grant type and `device_authorization_endpoint` (RFC 8628 §4) are advertised only when it is
+/// mounted, and never without token exchange, whose `/oauth2/token` redeems it.
+#[tokio::test]
+async fn discovery_advertises_device_authorization_only_alongside_token_exchange() {
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use lightbridge_authz_rest::signing::well_known_router;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    async fn fetch(scopes: Option<Vec<String>>, device: bool) -> Value {
+        let response = well_known_router::<()>(ISSUER, lazy_repo(), scopes, false, device, false)
+            .oneshot(
+                Request::builder()
+                    .uri("/.well-known/openid-configuration")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    let enabled = fetch(Some(vec!["openid".to_string()]), true).await;
+    assert_eq!(
+        enabled["grant_types_supported"],
+        json!([
+            "urn:ietf:params:oauth:grant-type:token-exchange",
+            "refresh_token",
+            "urn:ietf:params:oauth:grant-type:device_code",
+        ]),
+        "{enabled}"
+    );
+    assert_eq!(
+        enabled["device_authorization_endpoint"],
+        format!("{ISSUER}/oauth2/device_authorization"),
+        "{enabled}"
+    );
+    assert!(
+        enabled.get("authorization_endpoint").is_none(),
+        "the device grant adds no authorization endpoint of this issuer: {enabled}"
+    );
+
+    let without_exchange = fetch(None, true).await;
+    assert!(
+        without_exchange
+            .get("device_authorization_endpoint")
+            .is_none(),
+        "{without_exchange}"
+    );
+    assert_eq!(without_exchange["grant_types_supported"], json!([]));
+
+    let off = fetch(Some(vec!["openid".to_string()]), false).await;
+    assert!(off.get("device_authorization_endpoint").is_none(), "{off}");
+}
+
 /// Companion to the "enabled" case above: when token-exchange is off (`token_exchange_scopes` is
 /// `None` -- either `oauth2.token_exchange.enabled: false`, or the block is absent from config
 /// entirely, as in the live regression), the document must not claim capabilities the server
@@ -312,7 +404,7 @@ async fn discovery_advertises_no_grants_when_exchange_disabled() {
     use serde_json::Value;
     use tower::ServiceExt;
 
-    let discovery = well_known_router::<()>(ISSUER, lazy_repo(), None, false)
+    let discovery = well_known_router::<()>(ISSUER, lazy_repo(), None, false, false, false)
         .oneshot(
             Request::builder()
                 .uri("/.well-known/openid-configuration")
@@ -364,8 +456,8 @@ async fn discovery_advertises_no_grants_when_exchange_disabled() {
     );
     assert_eq!(
         payload["id_token_signing_alg_values_supported"],
-        serde_json::json!(["RS256"]),
-        "the signing algorithm is fixed by this deployment's key material, not by whether \
+        serde_json::json!(["RS256", "EdDSA"]),
+        "the signing algorithms are fixed by this deployment's key material, not by whether \
          token-exchange is enabled: {payload}"
     );
 }
@@ -389,7 +481,7 @@ async fn discovery_omits_token_endpoint_when_exchange_disabled() {
     use serde_json::Value;
     use tower::ServiceExt;
 
-    let discovery = well_known_router::<()>(ISSUER, lazy_repo(), None, false)
+    let discovery = well_known_router::<()>(ISSUER, lazy_repo(), None, false, false, false)
         .oneshot(
             Request::builder()
                 .uri("/.well-known/openid-configuration")
@@ -423,7 +515,7 @@ async fn discovery_advertises_private_key_jwt_only_when_a_confidential_client_is
     use serde_json::{Value, json};
     use tower::ServiceExt;
 
-    let discovery = well_known_router::<()>(ISSUER, lazy_repo(), None, true)
+    let discovery = well_known_router::<()>(ISSUER, lazy_repo(), None, true, false, false)
         .oneshot(
             Request::builder()
                 .uri("/.well-known/openid-configuration")
@@ -447,69 +539,100 @@ async fn discovery_advertises_private_key_jwt_only_when_a_confidential_client_is
     );
 }
 
-/// This service never serves `/authorize` (ADR-0011, Context) regardless of whether
-/// `oauth2.token_exchange.enabled` is on: token-exchange is a direct token-endpoint grant
-/// (RFC 8693), not a redirect-based authorization flow. `response_types_supported` and
-/// `response_modes_supported` describe the authorization endpoint (OIDC Discovery 1.0 §3 --
-/// `response_types_supported` is REQUIRED to be present as a JSON array, but the spec's
-/// "MUST support code/id_token/id_token token" clause binds only "Dynamic OpenID Providers",
-/// meaning ones that also advertise a `registration_endpoint`; this deployment has none, so an
-/// empty array is spec-compliant, not merely tidy). So both must stay empty/absent on *both*
-/// sides of the token-exchange gate -- unlike `grant_types_supported`/`scopes_supported`/
-/// `token_endpoint`, which correctly describe the token-endpoint grant surface and are gated on
-/// it. Checked against both states in one test specifically because the enabled/disabled tests
-/// above each only prove one side; a gate wired to the wrong field (as `response_types_supported`
+/// `response_types_supported`/`response_modes_supported`/`authorization_endpoint` describe the
+/// authorization endpoint (OIDC Discovery 1.0 §3), so they are gated on `/authorize` being
+/// mounted -- never on token exchange alone. Token exchange is a direct token-endpoint grant
+/// (RFC 8693), not a redirect-based flow; `response_types_supported` is REQUIRED to be present as
+/// a JSON array, but the spec's "MUST support code/id_token/id_token token" clause binds only
+/// "Dynamic OpenID Providers", meaning ones that also advertise a `registration_endpoint`; this
+/// deployment has none, so an empty array is spec-compliant, not merely tidy. Checked against
+/// every state in one test because a gate wired to the wrong field (as `response_types_supported`
 /// was before this test was added -- it flipped to `["token", "id_token", "id_token token"]`
 /// purely because `oauth2.token_exchange.enabled` went from `false` to `true` in production) would
 /// still pass a test that only checks one state.
 #[tokio::test]
-async fn discovery_never_advertises_response_types_or_modes() {
+async fn discovery_advertises_response_types_and_modes_only_with_authorize() {
     use axum::body::{Body, to_bytes};
     use axum::http::{Request, StatusCode};
     use lightbridge_authz_rest::signing::well_known_router;
-    use serde_json::Value;
+    use serde_json::{Value, json};
     use tower::ServiceExt;
 
-    for (label, scopes) in [
-        ("disabled", None),
-        (
-            "enabled",
-            Some(vec!["openid".to_string(), "offline_access".to_string()]),
-        ),
+    let exchange_scopes = || Some(vec!["openid".to_string(), "offline_access".to_string()]);
+    for (label, scopes, authorize, advertised) in [
+        ("disabled", None, false, false),
+        ("authorize without exchange", None, true, false),
+        ("exchange only", exchange_scopes(), false, false),
+        ("exchange and authorize", exchange_scopes(), true, true),
     ] {
-        let discovery = well_known_router::<()>(ISSUER, lazy_repo(), scopes, false)
-            .oneshot(
-                Request::builder()
-                    .uri("/.well-known/openid-configuration")
-                    .body(Body::empty())
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
+        let discovery =
+            well_known_router::<()>(ISSUER, lazy_repo(), scopes, false, false, authorize)
+                .oneshot(
+                    Request::builder()
+                        .uri("/.well-known/openid-configuration")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
         assert_eq!(discovery.status(), StatusCode::OK);
         let body = to_bytes(discovery.into_body(), usize::MAX).await.unwrap();
         let payload: Value = serde_json::from_slice(&body).unwrap();
 
-        assert!(
-            payload["response_types_supported"]
-                .as_array()
-                .unwrap()
-                .is_empty(),
-            "[{label}] no authorization endpoint exists in this deployment -- \
-             response_types_supported must stay empty: {payload}"
-        );
-        assert!(
-            payload["response_modes_supported"]
-                .as_array()
-                .unwrap()
-                .is_empty(),
-            "[{label}] no redirect-based flow is ever served -- response_modes_supported must \
-             stay empty: {payload}"
-        );
-        assert!(
-            payload.get("authorization_endpoint").is_none(),
-            "[{label}] authorization_endpoint must stay absent: {payload}"
-        );
+        if advertised {
+            assert_eq!(
+                payload["authorization_endpoint"],
+                format!("{ISSUER}/authorize"),
+                "[{label}] {payload}"
+            );
+            assert_eq!(
+                payload["response_types_supported"],
+                json!(["code"]),
+                "[{label}]"
+            );
+            assert_eq!(
+                payload["response_modes_supported"],
+                json!(["query"]),
+                "[{label}]"
+            );
+            assert_eq!(
+                payload["code_challenge_methods_supported"],
+                json!(["S256"]),
+                "[{label}] PKCE is S256-only: {payload}"
+            );
+            assert!(
+                payload["grant_types_supported"]
+                    .as_array()
+                    .unwrap()
+                    .contains(&json!("authorization_code")),
+                "[{label}] {payload}"
+            );
+        } else {
+            assert!(
+                payload["response_types_supported"]
+                    .as_array()
+                    .unwrap()
+                    .is_empty(),
+                "[{label}] no authorization endpoint is mounted -- \
+                 response_types_supported must stay empty: {payload}"
+            );
+            assert!(
+                payload["response_modes_supported"]
+                    .as_array()
+                    .unwrap()
+                    .is_empty(),
+                "[{label}] no redirect-based flow is served -- response_modes_supported must \
+                 stay empty: {payload}"
+            );
+            assert!(
+                payload.get("authorization_endpoint").is_none(),
+                "[{label}] authorization_endpoint must stay absent: {payload}"
+            );
+            assert!(
+                payload.get("code_challenge_methods_supported").is_none(),
+                "[{label}] {payload}"
+            );
+        }
     }
 }
 
@@ -522,12 +645,17 @@ mod db {
     // `it-tests`-gated module, so a file-level import reads as unused on a build without the
     // feature (which is what `cargo fix` acted on) while being required with it.
     use lightbridge_authz_core::config::{
-        ApiKeyExpiry, Billing, BillingPlan, ModelCatalog, Oauth2, QuotaTiers,
+        ApiKeyExpiry, Billing, BillingPlan, KeyEncryptionKey, ModelCatalog, Oauth2, QuotaTiers,
+        SigningKeyEncryption,
     };
     use lightbridge_authz_core::cuid::cuid2;
     use lightbridge_authz_core::{CreateAccount, CreateApiKey, CreateProject};
     use lightbridge_authz_rest::handlers::AuthzStoreImpl;
+    use lightbridge_authz_rest::key_encryption::{
+        LocalKeyEncryptor, ReencryptReport, reencrypt_signing_keys,
+    };
     use lightbridge_authz_rest::signing::{KeyOwner, bootstrap_signing_key};
+    use lightbridge_authz_rest::signing_rotation::{RotationReport, rotate_signing_keys};
     use serde_json::Value;
     use sqlx::PgPool;
 
@@ -718,6 +846,269 @@ mod db {
         );
     }
 
+    /// Writes a random KEK to a throwaway file and returns its `key_encryption` entry.
+    fn kek_file(id: &str) -> KeyEncryptionKey {
+        use base64::Engine;
+        use rand_core::{OsRng, RngCore};
+        let path = std::env::temp_dir().join(format!("lightbridge-kek-{}", cuid2()));
+        let mut key = [0u8; 32];
+        OsRng.fill_bytes(&mut key);
+        std::fs::write(&path, base64::engine::general_purpose::STANDARD.encode(key)).unwrap();
+        KeyEncryptionKey {
+            id: id.to_string(),
+            file: Some(path.to_string_lossy().into_owned()),
+            env: None,
+        }
+    }
+
+    fn encrypted_cfg(active: &KeyEncryptionKey, previous: &[&KeyEncryptionKey]) -> JwtSigning {
+        JwtSigning {
+            key_encryption: Some(SigningKeyEncryption {
+                active: active.clone(),
+                previous: previous.iter().map(|kek| (*kek).clone()).collect(),
+            }),
+            ..signing_cfg(3600)
+        }
+    }
+
+    async fn sign_with(
+        cfg: &JwtSigning,
+        repo: &Arc<StoreRepo>,
+    ) -> lightbridge_authz_core::Result<String> {
+        let signer = ApiKeyJwtSigner::from_config(cfg, repo.clone())?;
+        let signed = signer
+            .sign(
+                &KeyOwner::default(),
+                "key_1",
+                "proj_1",
+                "acct_1",
+                None,
+                Utc::now(),
+                None,
+            )
+            .await?;
+        Ok(signed.token)
+    }
+
+    /// Envelope encryption end to end: a plaintext key from before encryption was enabled is
+    /// sealed by `reencrypt`, signing opens it transparently, and a KEK rotation re-seals it
+    /// under the new KEK, after which the old KEK alone can no longer open it.
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn signing_keys_are_sealed_at_rest_and_survive_a_kek_rotation(pool: PgPool) {
+        let repo = repo(pool);
+        bootstrap_signing_key(&repo, &signing_cfg(3600))
+            .await
+            .unwrap();
+        let plaintext = repo.get_active_signing_key().await.unwrap().unwrap();
+        assert!(plaintext.private_key_pem.starts_with("-----BEGIN"));
+
+        let kek_1 = kek_file("kek-1");
+        let first = encrypted_cfg(&kek_1, &[]);
+        // A plaintext row still signs with encryption enabled, before it is migrated.
+        sign_with(&first, &repo).await.unwrap();
+        let encryptor =
+            LocalKeyEncryptor::from_config(first.key_encryption.as_ref().unwrap()).unwrap();
+        let report = reencrypt_signing_keys(&repo, &encryptor).await.unwrap();
+        assert_eq!(report.reencrypted, 1);
+
+        let sealed = repo.get_active_signing_key().await.unwrap().unwrap();
+        assert_eq!(sealed.kid, plaintext.kid);
+        assert!(sealed.private_key_pem.starts_with("lbenc:v1:kek-1:"));
+        assert!(!sealed.private_key_pem.contains("PRIVATE KEY"));
+        let token = sign_with(&first, &repo).await.unwrap();
+        verify_against(&sealed.public_jwk, &token);
+        assert!(sign_with(&signing_cfg(3600), &repo).await.is_err());
+
+        // Rotation: the new KEK is active, the old one kept only until the re-encryption.
+        let kek_2 = kek_file("kek-2");
+        let rotating = encrypted_cfg(&kek_2, &[&kek_1]);
+        let encryptor =
+            LocalKeyEncryptor::from_config(rotating.key_encryption.as_ref().unwrap()).unwrap();
+        assert_eq!(
+            reencrypt_signing_keys(&repo, &encryptor).await.unwrap(),
+            ReencryptReport {
+                reencrypted: 1,
+                unchanged: 0
+            }
+        );
+        assert_eq!(
+            reencrypt_signing_keys(&repo, &encryptor).await.unwrap(),
+            ReencryptReport {
+                reencrypted: 0,
+                unchanged: 1
+            }
+        );
+
+        let rotated = encrypted_cfg(&kek_2, &[]);
+        let token = sign_with(&rotated, &repo).await.unwrap();
+        verify_against(&sealed.public_jwk, &token);
+        // A service still configured with only the retired KEK fails at startup, not at signing.
+        assert!(bootstrap_signing_key(&repo, &first).await.is_err());
+        bootstrap_signing_key(&repo, &rotated).await.unwrap();
+        assert_eq!(repo.list_verification_jwks().await.unwrap().len(), 1);
+    }
+
+    fn jwks_kids(jwks: &[Value]) -> Vec<String> {
+        jwks.iter()
+            .map(|jwk| jwk["kid"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    /// Scheduled rotation end to end, with the clock passed in: the successor is published as
+    /// `pending` a lead time before the active key is due, is promoted only once that lead time
+    /// has passed, and the stale key is pruned once every token it signed has expired.
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn rotation_prepublishes_promotes_and_prunes(pool: PgPool) {
+        let repo = repo(pool);
+        let cfg = signing_cfg(3600);
+        let retention = Duration::seconds(cfg.ttl_seconds);
+        bootstrap_signing_key(&repo, &cfg).await.unwrap();
+        let old = repo.get_active_signing_key().await.unwrap().unwrap();
+        let due = old.created_at + Duration::days(cfg.max_key_age_days)
+            - Duration::seconds(cfg.rotation_lead_seconds);
+
+        let idle = rotate_signing_keys(&repo, &cfg, None, retention, due - Duration::minutes(1))
+            .await
+            .unwrap();
+        assert_eq!(idle, RotationReport::default());
+
+        let published = rotate_signing_keys(&repo, &cfg, None, retention, due)
+            .await
+            .unwrap();
+        let pending = published.prepublished.clone().expect("pre-published");
+        assert_eq!(published.promoted, None);
+        let jwks = repo.list_verification_jwks().await.unwrap();
+        assert_eq!(jwks_kids(&jwks), vec![old.kid.clone(), pending.clone()]);
+        assert_eq!(
+            repo.get_active_signing_key().await.unwrap().unwrap().kid,
+            old.kid,
+            "a pending key must not sign before its lead time has passed"
+        );
+        // Ticking again before activation changes nothing: at most one key is ever pending.
+        assert_eq!(
+            rotate_signing_keys(&repo, &cfg, None, retention, due + Duration::hours(1))
+                .await
+                .unwrap(),
+            RotationReport::default()
+        );
+
+        let activation = due + Duration::seconds(cfg.rotation_lead_seconds);
+        let promoted = rotate_signing_keys(&repo, &cfg, None, retention, activation)
+            .await
+            .unwrap();
+        assert_eq!(promoted.promoted.as_deref(), Some(pending.as_str()));
+        let token = sign_with(&cfg, &repo).await.unwrap();
+        verify_against(&jwks[1], &token);
+        assert_eq!(repo.list_verification_jwks().await.unwrap().len(), 2);
+
+        let pruned = rotate_signing_keys(&repo, &cfg, None, retention, activation + retention)
+            .await
+            .unwrap();
+        assert_eq!(pruned.pruned, 1);
+        assert_eq!(
+            jwks_kids(&repo.list_verification_jwks().await.unwrap()),
+            vec![pending]
+        );
+    }
+
+    /// Switching `algorithm` to EdDSA migrates at the next rotation: the Ed25519 successor is
+    /// pre-published next to the RS256 key, signs once promoted, and the JWKS stays mixed until
+    /// the RS256 key is pruned, so tokens of both algorithms keep verifying.
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn switching_to_eddsa_rotates_in_an_ed25519_key_alongside_rs256(pool: PgPool) {
+        let repo = repo(pool);
+        bootstrap_signing_key(&repo, &signing_cfg(3600))
+            .await
+            .unwrap();
+        let rsa = repo.get_active_signing_key().await.unwrap().unwrap();
+        assert_eq!(rsa.algorithm, "RS256");
+        let rsa_token = sign_with(&signing_cfg(3600), &repo).await.unwrap();
+
+        let cfg = JwtSigning {
+            algorithm: SigningAlgorithm::EdDsa,
+            ..signing_cfg(3600)
+        };
+        // A changed algorithm alone never replaces a key that is not yet due.
+        bootstrap_signing_key(&repo, &cfg).await.unwrap();
+        assert_eq!(
+            repo.get_active_signing_key().await.unwrap().unwrap().kid,
+            rsa.kid
+        );
+        let activation = rsa.created_at + Duration::days(cfg.max_key_age_days);
+        let retention = Duration::seconds(cfg.ttl_seconds);
+        let due = activation - Duration::seconds(cfg.rotation_lead_seconds);
+        rotate_signing_keys(&repo, &cfg, None, retention, due)
+            .await
+            .unwrap();
+        rotate_signing_keys(&repo, &cfg, None, retention, activation)
+            .await
+            .unwrap();
+
+        let ed = repo.get_active_signing_key().await.unwrap().unwrap();
+        assert_eq!(ed.algorithm, "EdDSA");
+        let jwks = repo.list_verification_jwks().await.unwrap();
+        let kty: Vec<&str> = jwks
+            .iter()
+            .map(|jwk| jwk["kty"].as_str().unwrap())
+            .collect();
+        assert_eq!(kty, vec!["OKP", "RSA"]);
+
+        let ed_token = sign_with(&cfg, &repo).await.unwrap();
+        let header = jsonwebtoken::decode_header(&ed_token).unwrap();
+        assert_eq!(header.alg, Algorithm::EdDSA);
+        assert_eq!(header.kid.as_deref(), Some(ed.kid.as_str()));
+        assert!(ed_token.len() < rsa_token.len());
+        let jwk: jsonwebtoken::jwk::Jwk = serde_json::from_value(ed.public_jwk).unwrap();
+        let mut validation = Validation::new(Algorithm::EdDSA);
+        validation.set_audience(&["lightbridge-api-key"]);
+        decode::<ApiKeyClaims>(
+            &ed_token,
+            &DecodingKey::from_jwk(&jwk).unwrap(),
+            &validation,
+        )
+        .unwrap();
+        verify_against(&rsa.public_jwk, &rsa_token);
+    }
+
+    /// Emergency rotation drops the revoked key from the JWKS at once and wipes its private
+    /// material, so the tokens it signed stop verifying; the fresh key signs immediately.
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn emergency_rotation_revokes_the_active_key(pool: PgPool) {
+        let repo = repo(pool);
+        let cfg = signing_cfg(3600);
+        bootstrap_signing_key(&repo, &cfg).await.unwrap();
+        let old = repo.get_active_signing_key().await.unwrap().unwrap();
+
+        let candidate = generate_rs256_key().unwrap();
+        let revoked = repo
+            .revoke_active_signing_key(
+                lightbridge_authz_api_key::entities::signing_key_row::NewSigningKey {
+                    kid: candidate.kid.clone(),
+                    algorithm: "RS256".to_string(),
+                    private_key_pem: candidate.private_key_pem,
+                    public_jwk: candidate.public_jwk.clone(),
+                    created_at: Utc::now(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(revoked.as_deref(), Some(old.kid.as_str()));
+
+        let jwks = repo.list_verification_jwks().await.unwrap();
+        assert_eq!(jwks_kids(&jwks), vec![candidate.kid.clone()]);
+        let token = sign_with(&cfg, &repo).await.unwrap();
+        verify_against(&candidate.public_jwk, &token);
+        let row = repo
+            .list_signing_keys()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|row| row.kid == old.kid)
+            .unwrap();
+        assert_eq!(row.status, "revoked");
+        assert!(row.private_key_pem.is_empty());
+    }
+
     /// The exact claim shape `signing.rs`'s hand-rolled `jsonwebtoken::encode` produced before
     /// ADR-0011 replaced it with `TokenManager` -- reconstructed here (not imported: the real
     /// struct is gone) so this test is a genuine diff against the old wire contract, not a
@@ -947,6 +1338,8 @@ mod db {
             audience: None,
             signing: Some(signing_cfg(3600)),
             token_exchange: None,
+            device_authorization: None,
+            authorization_code: None,
             rbac: Default::default(),
             clients: Vec::new(),
         }
@@ -1025,6 +1418,8 @@ mod db {
                     // far enough out to never become the binding constraint here.
                     expires_at: Some(Utc::now() + Duration::days(30)),
                     billing_plan: "free".to_string(),
+                    allowed_models: None,
+                    scopes: None,
                 },
             )
             .await
@@ -1066,7 +1461,7 @@ mod db {
         .await
         .unwrap();
 
-        let jwks = well_known_router::<()>(ISSUER, repo.clone(), None, false)
+        let jwks = well_known_router::<()>(ISSUER, repo.clone(), None, false, false, false)
             .oneshot(
                 Request::builder()
                     .uri("/.well-known/jwks.json")
@@ -1086,7 +1481,7 @@ mod db {
         assert_eq!(payload["keys"][0]["alg"], "RS256");
 
         let scopes = vec!["openid".to_string(), "offline_access".to_string()];
-        let discovery = well_known_router::<()>(ISSUER, repo, Some(scopes), false)
+        let discovery = well_known_router::<()>(ISSUER, repo, Some(scopes), false, false, false)
             .oneshot(
                 Request::builder()
                     .uri("/.well-known/openid-configuration")
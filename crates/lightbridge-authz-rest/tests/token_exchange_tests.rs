@@ -37,10 +37,10 @@ use lightbridge_authz_budget::repo::{BudgetRepo, GrantRequest};
 use lightbridge_authz_budget::review::ReviewService;
 use lightbridge_authz_budget::source::GrantSource;
 use lightbridge_authz_budget::spend::UnavailableSpendReader;
-use lightbridge_authz_budget::tier::BudgetTier;
+use lightbridge_authz_budget::tier::{BudgetTier, TierLadder};
 use lightbridge_authz_core::async_trait;
 use lightbridge_authz_core::config::{
-    JwtSigning, Oauth2TokenExchange, OauthClient, OauthClientType,
+    JwtSigning, Oauth2TokenExchange, OauthClient, OauthClientType, SigningAlgorithm,
 };
 use lightbridge_authz_core::cuid::cuid2;
 use lightbridge_authz_core::db::{DbPool, DbPoolTrait};
@@ -145,6 +145,9 @@ fn signing_cfg() -> JwtSigning {
         audience: None,
         ttl_seconds: 7_776_000,
         max_key_age_days: 30,
+        rotation_lead_seconds: 86_400,
+        algorithm: SigningAlgorithm::Rs256,
+        key_encryption: None,
     }
 }
 
@@ -181,6 +184,7 @@ fn public_client(client_id: &str) -> OauthClient {
         scopes: client_scopes(),
         grant_types: client_grant_types(),
         allowed_audiences: vec![client_id.to_string()],
+        redirect_uris: Vec::new(),
         jwks: None,
     }
 }
@@ -203,6 +207,7 @@ fn confidential_client(client_id: &str) -> ConfidentialClientFixture {
         scopes: client_scopes(),
         grant_types: client_grant_types(),
         allowed_audiences: vec![client_id.to_string()],
+        redirect_uris: Vec::new(),
         jwks: Some(jwks),
     };
     ConfidentialClientFixture {
@@ -2789,8 +2794,8 @@ async fn replaying_an_explicitly_revoked_token_does_not_trigger_the_reuse_cascad
 // outage must never omit the claim and must never fail the token exchange/refresh itself.
 //
 // ADR-0015 Decision 6 moved WHAT that fail-closed fallback resolves to off the compile-time
-// `BudgetTier::B15` constant and onto the active policy document's `fail_closed_floor_micros`
-// (shipped default: $6, below `B15`'s $15) -- see `FixedPolicyEngine`/`default_policy_engine`
+// `b-15` rung and onto the active policy document's `fail_closed_floor_micros` (shipped default:
+// $6, below `b-15`'s $15) -- see `FixedPolicyEngine`/`default_policy_engine`
 // below and `TokenExchangeOpStore::resolve_budget_tier`'s own doc comment.
 // ============================================================================================
 
@@ -2804,6 +2809,7 @@ struct FixedPolicyEngine {
     allowed_amounts_micros: Vec<i64>,
     starting_amount_micros: i64,
     fail_closed_floor_micros: i64,
+    tier_ladder: TierLadder,
 }
 
 #[async_trait]
@@ -2827,6 +2833,10 @@ impl PolicyEngine for FixedPolicyEngine {
     fn fail_closed_floor_micros(&self) -> i64 {
         self.fail_closed_floor_micros
     }
+
+    fn tier_ladder(&self) -> TierLadder {
+        self.tier_ladder.clone()
+    }
 }
 
 /// The ADR-0015 shipped defaults ($6/$15/$30 offered, $15 starting, $6 fail-closed floor --
@@ -2838,6 +2848,7 @@ fn default_policy_engine() -> Arc<dyn PolicyEngine> {
         allowed_amounts_micros: vec![6_000_000, 15_000_000, 30_000_000],
         starting_amount_micros: 15_000_000,
         fail_closed_floor_micros: 6_000_000,
+        tier_ladder: TierLadder::legacy(),
     })
 }
 
@@ -2863,7 +2874,7 @@ async fn seed_budget_grant(
     budget_repo: &BudgetRepo,
     account_id: &str,
     period: &str,
-    tier: BudgetTier,
+    amount_micros: i64,
 ) {
     budget_repo
         .grant(GrantRequest {
@@ -2871,7 +2882,7 @@ async fn seed_budget_grant(
             account_id: account_id.to_string(),
             project_id: None,
             period: Period::parse(period).expect("valid period"),
-            amount_micros: tier.amount().get(),
+            amount_micros,
             source: GrantSource::Admin,
             actor_id: None,
             reason: None,
@@ -2921,7 +2932,56 @@ async fn token_exchange_stamps_the_lowest_rung_when_the_account_has_no_grant_yet
     );
 }
 
-/// Proves the claim genuinely reads the ledger, not just its own `B15` default -- the same
+/// ADR-0008's "the billing plan determines the starting rung": with no grant yet, the claim is
+/// the rung the active ladder maps the project's billing plan (`free`, see `seed`) to, and a
+/// rung that exists only on that ladder is stamped by its own label.
+#[sqlx::test(migrations = "../../migrations")]
+async fn token_exchange_stamps_the_billing_plans_starting_rung_from_the_active_ladder(
+    pool: PgPool,
+) {
+    let repo = repo(pool);
+    bootstrap_signing_key(&repo, &signing_cfg()).await.unwrap();
+    seed(&repo).await;
+
+    let mut tiers = TierLadder::legacy().tiers().to_vec();
+    tiers.push(BudgetTier::new("b-2000", 2_000_000_000).expect("valid tier"));
+    let ladder = TierLadder::new(tiers, [("free".to_string(), "b-2000".to_string())].into())
+        .expect("valid ladder");
+    let state = state_with_cfg_and_budget_repo(
+        repo.clone(),
+        repo.clone(),
+        Arc::new(BudgetRepo::new(repo.pool.clone())),
+        Arc::new(FixedPolicyEngine {
+            allowed_amounts_micros: vec![6_000_000, 15_000_000, 30_000_000],
+            starting_amount_micros: 15_000_000,
+            fail_closed_floor_micros: 6_000_000,
+            tier_ladder: ladder,
+        }),
+        Arc::new(MockBearer::new(true, vec![PUBLIC_CLIENT_ID.to_string()])),
+        vec![public_client(PUBLIC_CLIENT_ID)],
+        &redis_url(),
+        exchange_cfg(),
+    );
+
+    let (status, body) = post_token(
+        state,
+        &format!(
+            "grant_type={TOKEN_EXCHANGE_GRANT}&client_id={PUBLIC_CLIENT_ID}&subject_token=x&project_id={PROJECT_ID}"
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "body: {body}");
+
+    let claims = decode_access_token_claims(
+        &repo,
+        body["access_token"].as_str().unwrap(),
+        PUBLIC_CLIENT_ID,
+    )
+    .await;
+    assert_eq!(claims["budget_tier"], "b-2000", "claims: {claims:?}");
+}
+
+/// Proves the claim genuinely reads the ledger, not just its own `b-15` default -- the same
 /// correctness bar `current_tier_resolves_the_most_recent_qualifying_grant` pins at the
 /// `BudgetRepo` layer, exercised here end-to-end through the actual minted JWT.
 #[sqlx::test(migrations = "../../migrations")]
@@ -2934,7 +2994,7 @@ async fn token_exchange_stamps_the_accounts_real_current_tier(pool: PgPool) {
         &budget_repo,
         ACCOUNT_ID,
         &current_period_string(),
-        BudgetTier::B120,
+        120_000_000,
     )
     .await;
 
@@ -2967,7 +3027,7 @@ async fn refresh_re_resolves_the_budget_tier_live_rather_than_copying_the_old_cl
     seed(&repo).await;
     let budget_repo = BudgetRepo::new(repo.pool.clone());
     let period = current_period_string();
-    seed_budget_grant(&budget_repo, ACCOUNT_ID, &period, BudgetTier::B15).await;
+    seed_budget_grant(&budget_repo, ACCOUNT_ID, &period, 15_000_000).await;
 
     let (status, body) = post_token(
         state(repo.clone(), true),
@@ -2993,7 +3053,7 @@ async fn refresh_re_resolves_the_budget_tier_live_rather_than_copying_the_old_cl
     // A refill lands between the exchange and the refresh -- exactly the ADR-0014 scenario: the
     // claim must catch up on the next refresh (bounded by access-token TTL / refresh timing),
     // not require a fresh login.
-    seed_budget_grant(&budget_repo, ACCOUNT_ID, &period, BudgetTier::B60).await;
+    seed_budget_grant(&budget_repo, ACCOUNT_ID, &period, 60_000_000).await;
 
     let (status, body) = post_token(
         state(repo.clone(), true),
@@ -3024,9 +3084,9 @@ async fn refresh_re_resolves_the_budget_tier_live_rather_than_copying_the_old_cl
 /// reason wouldn't be mistaken for this fail-closed path specifically), then swapping only the
 /// budget repo for a dead one and re-asserting.
 ///
-/// Deliberately uses a floor ($9, `9_000_000`) that matches neither a legacy `BudgetTier` rung
+/// Deliberately uses a floor ($9, `9_000_000`) that matches neither a legacy ladder rung
 /// nor the ADR-0015-shipped $6 default -- if this assertion ever passed against a hard-coded
-/// `B15`/$6 fallback instead of genuinely reading `PolicyEngine::fail_closed_floor_micros()` off
+/// `b-15`/$6 fallback instead of genuinely reading `PolicyEngine::fail_closed_floor_micros()` off
 /// the engine this test supplies, it would fail loudly rather than accidentally match.
 #[sqlx::test(migrations = "../../migrations")]
 async fn budget_tier_claim_survives_a_budget_ledger_outage_on_exchange(pool: PgPool) {
@@ -3042,6 +3102,7 @@ async fn budget_tier_claim_survives_a_budget_ledger_outage_on_exchange(pool: PgP
             allowed_amounts_micros: vec![9_000_000],
             starting_amount_micros: 15_000_000,
             fail_closed_floor_micros: 9_000_000,
+            tier_ladder: TierLadder::legacy(),
         }),
         Arc::new(MockBearer::new(true, vec![PUBLIC_CLIENT_ID.to_string()])),
         vec![public_client(PUBLIC_CLIENT_ID)],
@@ -3092,7 +3153,7 @@ async fn budget_tier_claim_survives_a_budget_ledger_outage_on_refresh(pool: PgPo
         &budget_repo,
         ACCOUNT_ID,
         &current_period_string(),
-        BudgetTier::B250,
+        250_000_000,
     )
     .await;
 
@@ -3115,6 +3176,7 @@ async fn budget_tier_claim_survives_a_budget_ledger_outage_on_refresh(pool: PgPo
             allowed_amounts_micros: vec![9_000_000],
             starting_amount_micros: 15_000_000,
             fail_closed_floor_micros: 9_000_000,
+            tier_ladder: TierLadder::legacy(),
         }),
         Arc::new(MockBearer::new(true, vec![PUBLIC_CLIENT_ID.to_string()])),
         vec![public_client(PUBLIC_CLIENT_ID)],
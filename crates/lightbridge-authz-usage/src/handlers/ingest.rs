@@ -1,4 +1,5 @@
 use crate::UsageState;
+use crate::instrumentation::record_ingest;
 use crate::models::IngestResponse;
 use crate::repo::UsageEvent;
 use axum::http::header::CONTENT_ENCODING;
@@ -116,9 +117,14 @@ pub async fn ingest_traces(
     headers: HeaderMap,
     body: Bytes,
 ) -> Result<(StatusCode, Json<IngestResponse>)> {
-    let payload = decode_trace_request(&headers, &body)?;
-    let events = extract_trace_events(payload);
-    let accepted_events = persist_events(&state, "trace", &events).await?;
+    let accepted_events = record_ingest(
+        "trace",
+        async {
+            let payload = decode_trace_request(&headers, &body)?;
+            persist_events(&state, "trace", &extract_trace_events(payload)).await
+        }
+        .await,
+    )?;
 
     info!("accepted {} trace events", accepted_events);
 
@@ -144,9 +150,14 @@ pub async fn ingest_metrics(
     headers: HeaderMap,
     body: Bytes,
 ) -> Result<(StatusCode, Json<IngestResponse>)> {
-    let payload = decode_metrics_request(&headers, &body)?;
-    let events = extract_metric_events(payload);
-    let accepted_events = persist_events(&state, "metric", &events).await?;
+    let accepted_events = record_ingest(
+        "metric",
+        async {
+            let payload = decode_metrics_request(&headers, &body)?;
+            persist_events(&state, "metric", &extract_metric_events(payload)).await
+        }
+        .await,
+    )?;
 
     info!("accepted {} metric events", accepted_events);
 
@@ -172,9 +183,14 @@ pub async fn ingest_logs(
     headers: HeaderMap,
     body: Bytes,
 ) -> Result<(StatusCode, Json<IngestResponse>)> {
-    let payload = decode_logs_request(&headers, &body)?;
-    let events = extract_log_events(payload);
-    let accepted_events = persist_events(&state, "log", &events).await?;
+    let accepted_events = record_ingest(
+        "log",
+        async {
+            let payload = decode_logs_request(&headers, &body)?;
+            persist_events(&state, "log", &extract_log_events(payload)).await
+        }
+        .await,
+    )?;
 
     info!("accepted {} log events", accepted_events);
 
@@ -1,7 +1,42 @@
 use crate::config::UsageConfig;
+use lightbridge_authz_core::Result;
 use lightbridge_authz_core::tracing::TracingConfig;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Counter;
+use std::sync::LazyLock;
 use tracing::info;
 
+/// Built on first use, which is always after `init_tracing` installed the provider.
+static INGEST_METRICS: LazyLock<IngestMetrics> = LazyLock::new(|| {
+    let meter = lightbridge_authz_core::metrics::meter();
+    IngestMetrics {
+        ingested: meter
+            .u64_counter("authz_otlp_events_ingested")
+            .with_description("Usage events accepted from OTLP exports, by signal")
+            .build(),
+        rejected: meter
+            .u64_counter("authz_otlp_requests_rejected")
+            .with_description("OTLP export requests refused whole, by signal")
+            .build(),
+    }
+});
+
+struct IngestMetrics {
+    ingested: Counter<u64>,
+    rejected: Counter<u64>,
+}
+
+/// Counts one ingest request on `/metrics` and hands its result back. A refused export is
+/// counted per request, not per event: an undecodable body has no events to count.
+pub fn record_ingest(signal_type: &'static str, result: Result<usize>) -> Result<usize> {
+    let signal = [KeyValue::new("signal", signal_type)];
+    match &result {
+        Ok(accepted) => INGEST_METRICS.ingested.add(*accepted as u64, &signal),
+        Err(_) => INGEST_METRICS.rejected.add(1, &signal),
+    }
+    result
+}
+
 impl TracingConfig for UsageConfig {
     fn logging_level(&self) -> &str {
         &self.logging.level
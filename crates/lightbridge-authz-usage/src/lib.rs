@@ -83,6 +83,10 @@ fn health_routes(readiness_pool: Arc<dyn DbPoolTrait>) -> Router<Arc<UsageState>
                 async move { readiness_handler(readiness_pool).await }
             }),
         )
+        .route(
+            "/metrics",
+            get(lightbridge_authz_core::metrics::metrics_handler),
+        )
 }
 
 /// Assembles the ingest listener's router (public probes, Swagger docs, OTEL ingest only --
@@ -0,0 +1,26 @@
+-- Per-key permission surface narrower than the owning project. Until now an API key inherited
+-- its project's `allowed_models`/`model_policy` wholesale and had no restrictions of its own, so
+-- pinning (say) a CI key to one cheap model meant creating a separate project just for it.
+--
+-- Two nullable JSONB columns, same storage shape as `projects.allowed_models` (a JSON array of
+-- strings, SQL NULL -- never the jsonb `null` literal, see
+-- `20260723000001_normalize_allowed_models_json_null.sql` -- for "unset"):
+--   - `allowed_models`: a key-level model subset. NULL means "no key-level restriction"; the
+--     project's own policy applies unchanged. A non-NULL list is only ever NARROWING -- the
+--     effective set reported by introspection is the intersection with the project's policy
+--     (`ApiKey::effective_model_access`, `crates/lightbridge-authz-core/src/dto.rs`), so a later
+--     project-level tightening still wins over whatever was valid when the key was minted.
+--   - `scopes`: named capabilities (`inference`, `embeddings`, `batch`). NULL means "all scopes".
+--
+-- Both are validated at write time (`AuthzStoreImpl::create_api_key`/`rotate_api_key`) against
+-- the operator-configured model catalogue, the project's `model_policy`, and the closed scope
+-- vocabulary (`ApiKeyScope::parse_strict`). No `CHECK` constraint on either: the scope vocabulary
+-- is expected to grow, and a JSON-array shape check would buy nothing the write path does not
+-- already enforce.
+--
+-- Plain nullable `ADD COLUMN`s with no default: a catalog-only change on Postgres 11+, no table
+-- rewrite, and every existing key reads back NULL/NULL -- exactly its current "inherit the
+-- project" behavior. Deploying this migration alone changes nothing observable.
+ALTER TABLE api_keys
+    ADD COLUMN allowed_models JSONB,
+    ADD COLUMN scopes JSONB;
@@ -0,0 +1,50 @@
+-- Per-key IP allowlist. `record_api_key_usage` has long stored the caller's `last_ip`, but a key
+-- could not be restricted to a network; this adds the column that lets it be.
+--
+-- `allowed_cidrs` is a nullable JSONB array of canonical CIDR strings (`10.0.0.0/8`,
+-- `192.0.2.7/32`, `2001:db8::/32`), same storage shape as the key-level `allowed_models`/`scopes`
+-- columns added by `20261017000001_api_keys_scopes_and_models.sql`. SQL NULL means "no network
+-- restriction" -- every existing key's current behavior -- so adding it changes nothing
+-- observable. Written only through `setApiKeyAllowedCidrs`, which canonicalises every entry
+-- (`lightbridge_authz_core::cidr::normalize_cidr`) and rejects anything that does not parse.
+--
+-- Deliberately NOT a Postgres `cidr[]`: the check needs the caller's address, which the database
+-- never sees (it arrives on the introspection request), so containment is evaluated in
+-- application code (`ApiKeyValidation::apply_ip_allowlist`) and the native type would buy nothing
+-- but a second encoding to keep in sync with the other key-level lists.
+ALTER TABLE api_keys
+    ADD COLUMN allowed_cidrs JSONB;
+
+-- Surfaced through the validation view so the network check rides the existing single indexed
+-- read instead of adding a round trip. The view's own `effective_status` CASE is untouched: it
+-- cannot evaluate the allowlist without the caller's address, so `ip_not_allowed` is resolved by
+-- the application after this row is read. APPENDED at the end of the column list, per the
+-- `CREATE OR REPLACE VIEW` constraint documented in `20260731000001_api_keys_owner_account.sql`.
+CREATE OR REPLACE VIEW api_key_validation AS
+SELECT
+    k.id            AS api_key_id,
+    k.key_hash      AS key_hash,
+    k.project_id    AS project_id,
+    p.account_id    AS account_id,
+    k.status        AS api_key_status,
+    p.status        AS project_status,
+    a.status        AS account_status,
+    k.expires_at    AS expires_at,
+    CASE
+        WHEN k.status <> 'active'                                    THEN 'key_revoked'
+        WHEN k.expires_at IS NOT NULL AND k.expires_at <= now()      THEN 'key_expired'
+        WHEN p.status <> 'active'                                    THEN 'project_suspended'
+        WHEN a.status <> 'active'                                    THEN 'account_suspended'
+        ELSE 'active'
+    END             AS effective_status,
+    k.owner_account_id AS owner_account_id,
+    pm.role         AS owner_role,
+    pm.quota_tier   AS owner_quota_tier,
+    k.allowed_cidrs AS allowed_cidrs
+FROM api_keys k
+JOIN projects p ON p.id = k.project_id
+JOIN accounts a ON a.id = p.account_id
+LEFT JOIN project_members pm
+       ON pm.project_id = k.project_id
+      AND pm.account_id = k.owner_account_id
+WHERE k.deleted_at IS NULL;
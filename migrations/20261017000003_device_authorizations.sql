@@ -0,0 +1,61 @@
+-- RFC 8628 device-authorization pairing sessions (ADR-0012, Decision 7). One row per
+-- `POST /oauth2/device_authorization`, from `pending` through the browser leg's Keycloak sign-in
+-- (`approved`) or the user's refusal (`denied`), to the single poll that redeems it (`consumed`).
+-- `expired` is never written by the poll path -- an expired row is simply refused on read -- but is
+-- a valid terminal state for an operator sweep.
+--
+-- `device_code_hash`: SHA-256 of the device code (`lightbridge_authz_core::crypto::hash_api_key`,
+-- the same convention `exchange_refresh_tokens.token_hash` uses). The device code is a bearer
+-- secret the CLI polls with; the plaintext is never persisted.
+--
+-- `user_code`: the short code the user types at `/device`. A display/pairing code, not a row
+-- identifier (ADR-0012 Decision 7), so it is stored as issued and matched case-insensitively by
+-- the application after normalisation. Only unique among `pending` rows, since that is the only
+-- state the verification page ever looks it up in.
+--
+-- `project_id`/`nonce`: optional extensions carried on the device-authorization request itself
+-- (`project_id` mirrors the token endpoint's own extension; `nonce` is reflected verbatim into the
+-- issued `id_token`, ADR-0012 Decision 6). Both are NULL when the client did not send them.
+--
+-- `verification_state_hash`/`verification_code_verifier`: the browser leg's OAuth `state` (hashed)
+-- and PKCE verifier toward Keycloak, written when the user confirms the code and cleared again on
+-- approval so a replayed callback cannot approve twice.
+--
+-- `subject`/`email`/`email_verified`/`auth_time`: copied off the Keycloak tokens on approval, never
+-- present on a `pending` row (ADR-0012 Decision 5 -- `sub` is read, never minted).
+--
+-- ADR-0038 exception: the redeeming poll claims an `approved`/`denied` row with
+-- `SELECT ... FOR UPDATE` and flips it to `consumed` in the same statement
+-- (`StoreRepo::consume_device_authorization`), mirroring `exchange_refresh_tokens`' single-use
+-- CAS -- not an ordinary cratestack CRUD model.
+CREATE TABLE device_authorizations (
+    id TEXT PRIMARY KEY,
+    device_code_hash TEXT NOT NULL UNIQUE,
+    user_code TEXT NOT NULL,
+    client_id TEXT NOT NULL,
+    scope TEXT NOT NULL DEFAULT '',
+    project_id TEXT,
+    nonce TEXT,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'approved', 'denied', 'expired', 'consumed')),
+    subject TEXT,
+    email TEXT,
+    email_verified BOOLEAN,
+    auth_time BIGINT,
+    verification_state_hash TEXT,
+    verification_code_verifier TEXT,
+    interval_secs INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    last_polled_at TIMESTAMPTZ,
+    CHECK (status <> 'approved' OR subject IS NOT NULL)
+);
+
+CREATE UNIQUE INDEX idx_device_authorizations_pending_user_code
+    ON device_authorizations (user_code)
+    WHERE status = 'pending';
+CREATE UNIQUE INDEX idx_device_authorizations_verification_state
+    ON device_authorizations (verification_state_hash)
+    WHERE verification_state_hash IS NOT NULL;
+CREATE INDEX idx_device_authorizations_expires_at
+    ON device_authorizations (expires_at);
@@ -0,0 +1,61 @@
+-- Brokered authorization-code + PKCE flow on `authz-idp` (`/authorize`). Two tables, one per leg.
+--
+-- `authorization_requests`: one row per `GET /authorize` that passed client/redirect-URI
+-- validation, held while the browser is away signing in at Keycloak. Keyed by the hashed `state`
+-- this service sent to Keycloak (`state_hash`, SHA-256 via
+-- `lightbridge_authz_core::crypto::hash_api_key`), never by the client's own `client_state`,
+-- which is echoed back to the client verbatim and may be absent or reused by a careless client.
+-- `upstream_code_verifier` is the PKCE verifier toward Keycloak. The callback takes the row with
+-- `DELETE ... RETURNING` (`StoreRepo::take_authorization_request`), so a replayed callback finds
+-- nothing.
+--
+-- `authorization_codes`: the codes `/authorize/callback` hands the client. `code_hash` is the
+-- SHA-256 of the code (the plaintext is a bearer secret and is never persisted);
+-- `code_challenge`/`code_challenge_method` are the client's own PKCE challenge (S256 only) the
+-- token endpoint checks the `code_verifier` against; `subject`/`email`/`email_verified`/
+-- `auth_time` are copied off the Keycloak tokens, never minted. `project_id`/`nonce` are the
+-- request's own extensions, exactly as on `device_authorizations`.
+--
+-- ADR-0038 exception: redemption is a single-use CAS (`UPDATE ... SET consumed_at WHERE
+-- consumed_at IS NULL AND expires_at > now RETURNING`, `StoreRepo::consume_authorization_code`),
+-- mirroring `device_authorizations` -- not an ordinary cratestack CRUD model.
+CREATE TABLE authorization_requests (
+    id TEXT PRIMARY KEY,
+    state_hash TEXT NOT NULL UNIQUE,
+    client_id TEXT NOT NULL,
+    redirect_uri TEXT NOT NULL,
+    scope TEXT NOT NULL DEFAULT '',
+    client_state TEXT,
+    code_challenge TEXT NOT NULL,
+    code_challenge_method TEXT NOT NULL CHECK (code_challenge_method = 'S256'),
+    nonce TEXT,
+    project_id TEXT,
+    upstream_code_verifier TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_authorization_requests_expires_at
+    ON authorization_requests (expires_at);
+
+CREATE TABLE authorization_codes (
+    id TEXT PRIMARY KEY,
+    code_hash TEXT NOT NULL UNIQUE,
+    client_id TEXT NOT NULL,
+    redirect_uri TEXT NOT NULL,
+    scope TEXT NOT NULL DEFAULT '',
+    code_challenge TEXT NOT NULL,
+    code_challenge_method TEXT NOT NULL CHECK (code_challenge_method = 'S256'),
+    nonce TEXT,
+    project_id TEXT,
+    subject TEXT NOT NULL,
+    email TEXT,
+    email_verified BOOLEAN,
+    auth_time BIGINT,
+    created_at TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_authorization_codes_expires_at
+    ON authorization_codes (expires_at);
@@ -0,0 +1,16 @@
+-- Background signing-key rotation (`lightbridge_authz_rest::signing_rotation`).
+--
+-- A rotated-in key is first inserted as `pending`: published in the JWKS but not yet signing,
+-- until `activates_at`, so relying parties have fetched it before the first token it signs
+-- reaches them. `revoked` is the emergency rotation's end state: dropped from the JWKS at once
+-- and its private material wiped, so every token it signed stops verifying.
+--
+-- Lifecycle: pending -> active -> stale -> (deleted once every token it signed has expired), or
+-- active -> revoked.
+ALTER TABLE signing_keys ADD COLUMN activates_at TIMESTAMPTZ;
+
+ALTER TABLE signing_keys
+    ADD CONSTRAINT signing_keys_status_check
+    CHECK (status IN ('pending', 'active', 'stale', 'revoked'));
+
+CREATE UNIQUE INDEX idx_signing_keys_single_pending ON signing_keys (status) WHERE status = 'pending';
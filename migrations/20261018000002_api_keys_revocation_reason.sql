@@ -0,0 +1,9 @@
+-- Why an API key was revoked, when the revocation came with one. Set by the leaked-credential
+-- report endpoint (`POST /v1/leaked-credentials` on authz-opa), which revokes every live key a
+-- secret scanner reports and records who reported it here, so an operator looking at a key that
+-- stopped working can tell a leak from a manual revoke.
+--
+-- Nullable, and written by `set_api_key_status` on every status change: a plain `revokeApiKey`
+-- leaves it NULL, and reactivating a key clears it.
+ALTER TABLE api_keys
+    ADD COLUMN revocation_reason TEXT;
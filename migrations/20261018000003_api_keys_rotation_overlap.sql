@@ -0,0 +1,55 @@
+-- Dual-key overlap for `rotateApiKey`'s `grace_period_seconds`. Until now a grace period only
+-- shortened the old key's `expires_at`, so nothing recorded that the key had been rotated, which
+-- key replaced it, or that it was due to go.
+--
+-- `rotating_until` is the hard cut-off of a grace-period rotation. The old key stays
+-- `status = 'active'` and introspects active up to that instant, then resolves `key_rotated`, and
+-- the `authz-api` sweeper (`lightbridge_authz_rest::api_key_rotation`) flips it to `revoked` with
+-- `revocation_reason = 'rotated'`. NULL on every key that is not mid-rotation, so adding it changes
+-- nothing observable for existing keys.
+--
+-- `successor_id`/`predecessor_id` link the two keys of a rotation both ways, so a key listing shows
+-- which deployments still present the old key. Set on every rotation, grace period or not.
+-- `ON DELETE SET NULL` only matters for a hard delete; keys are soft-deleted (`deleted_at`).
+ALTER TABLE api_keys
+    ADD COLUMN rotating_until TIMESTAMPTZ,
+    ADD COLUMN successor_id TEXT REFERENCES api_keys(id) ON DELETE SET NULL,
+    ADD COLUMN predecessor_id TEXT REFERENCES api_keys(id) ON DELETE SET NULL;
+
+-- The sweeper's scan: only keys still inside (or just past) an overlap.
+CREATE INDEX api_keys_rotating_until_idx
+    ON api_keys (rotating_until)
+    WHERE rotating_until IS NOT NULL AND status = 'active';
+
+-- `key_rotated` sits right after `key_revoked`, so a rotated key past its cut-off resolves inactive
+-- even before the sweeper has run. Column list unchanged, per the `CREATE OR REPLACE VIEW`
+-- constraint documented in `20260731000001_api_keys_owner_account.sql`.
+CREATE OR REPLACE VIEW api_key_validation AS
+SELECT
+    k.id            AS api_key_id,
+    k.key_hash      AS key_hash,
+    k.project_id    AS project_id,
+    p.account_id    AS account_id,
+    k.status        AS api_key_status,
+    p.status        AS project_status,
+    a.status        AS account_status,
+    k.expires_at    AS expires_at,
+    CASE
+        WHEN k.status <> 'active'                                    THEN 'key_revoked'
+        WHEN k.rotating_until IS NOT NULL AND k.rotating_until <= now() THEN 'key_rotated'
+        WHEN k.expires_at IS NOT NULL AND k.expires_at <= now()      THEN 'key_expired'
+        WHEN p.status <> 'active'                                    THEN 'project_suspended'
+        WHEN a.status <> 'active'                                    THEN 'account_suspended'
+        ELSE 'active'
+    END             AS effective_status,
+    k.owner_account_id AS owner_account_id,
+    pm.role         AS owner_role,
+    pm.quota_tier   AS owner_quota_tier,
+    k.allowed_cidrs AS allowed_cidrs
+FROM api_keys k
+JOIN projects p ON p.id = k.project_id
+JOIN accounts a ON a.id = p.account_id
+LEFT JOIN project_members pm
+       ON pm.project_id = k.project_id
+      AND pm.account_id = k.owner_account_id
+WHERE k.deleted_at IS NULL;
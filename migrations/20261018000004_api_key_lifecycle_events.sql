@@ -0,0 +1,52 @@
+-- Expiry warnings and dormant-key detection for API keys. `api_key_expiry` forces every key to
+-- expire and `api_key_validation` resolves `key_expired` once it has, but nothing warned anyone
+-- beforehand or cleaned up keys nobody uses. The `authz-api` lifecycle job
+-- (`lightbridge_authz_rest::api_key_lifecycle`, `Config.api_key_lifecycle`) now scans for both.
+--
+-- `api_key_lifecycle_events` is the job's outbox. Every finding is inserted here first; the
+-- configured notifier (a webhook) then delivers undelivered rows and stamps `delivered_at`. With
+-- no notifier configured the table itself is the integration point: a consumer polls
+-- `delivered_at IS NULL` and stamps the rows it has handled.
+--
+--   event_type      `expiring` (expires within `threshold_days`), `dormant` (unused for
+--                   `threshold_days`), or `dormant_revoked` (dormant, and revoked under its
+--                   project's policy).
+--   occurs_at       the instant the event is about: `expires_at` for `expiring`, the last use
+--                   (or creation, if never used) for the dormant kinds.
+--
+-- The unique constraint is what makes a scan idempotent across runs and replicas: the same key,
+-- kind, window and instant is recorded once. Extending a key's expiry or using a dormant key
+-- changes `occurs_at`, so the key is warned about again if it later re-enters a window.
+CREATE TABLE api_key_lifecycle_events (
+    id              BIGSERIAL PRIMARY KEY,
+    api_key_id      TEXT NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    project_id      TEXT NOT NULL,
+    event_type      TEXT NOT NULL
+                    CHECK (event_type IN ('expiring', 'dormant', 'dormant_revoked')),
+    threshold_days  INTEGER NOT NULL CHECK (threshold_days > 0),
+    occurs_at       TIMESTAMPTZ NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    delivered_at    TIMESTAMPTZ,
+    delivery_attempts INTEGER NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    UNIQUE (api_key_id, event_type, threshold_days, occurs_at)
+);
+
+-- The delivery scan: oldest undelivered first.
+CREATE INDEX api_key_lifecycle_events_undelivered_idx
+    ON api_key_lifecycle_events (id)
+    WHERE delivered_at IS NULL;
+
+-- Per-project dormant-key policy, set through `setProjectKeyLifecyclePolicy`. A project without a
+-- row uses `api_key_lifecycle.dormant_after_days` and never auto-revokes: revoking keys is opt-in
+-- per project, never a deployment-wide default.
+--
+--   dormant_after_days   overrides the deployment default for this project; NULL inherits it.
+--   auto_revoke_dormant  revoke a key found dormant (reason `dormant`) instead of only reporting
+--                        it. Needs an effective `dormant_after_days` to do anything.
+CREATE TABLE project_key_lifecycle_policies (
+    project_id          TEXT PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    dormant_after_days  INTEGER CHECK (dormant_after_days > 0),
+    auto_revoke_dormant BOOLEAN NOT NULL DEFAULT false,
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT now()
+);
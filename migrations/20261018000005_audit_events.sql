@@ -0,0 +1,156 @@
+-- An audit trail for the hand-written mutation procedures (`addProjectMember`,
+-- `setProjectModelPolicy`, `disableAccount`, `revokeSubjectSessions`, `activateBudgetPolicy`,
+-- `deleteAccountPermanently`, ...). Cratestack's `@@audit` only sees its generated model CRUD
+-- (`cratestack_audit`); these procedures write through the repositories' own sqlx and, apart from
+-- the budget ledger, left no record of who changed what.
+--
+-- Rows are written by the `audit_row_change` trigger below, not by the application, so a row is
+-- audited in the same transaction as the write that produced it -- a rolled-back mutation leaves no
+-- audit row, and a committed one cannot be missing its row. The procedure layer supplies the
+-- caller through transaction-local settings (`lightbridge_authz_core::audit::begin`):
+--
+--   lightbridge.audit_actor       the authenticated subject
+--   lightbridge.audit_op_id       the RPC op-id, e.g. `procedure.addProjectMember`
+--   lightbridge.audit_request_id  the request's W3C `traceparent`, or empty
+--
+-- A write without `lightbridge.audit_op_id` (background jobs, `last_used_at` stamping on the
+-- introspection path, cratestack's CRUD) is not recorded. The trigger's `WHEN` clause checks it
+-- before the function is even called, so unaudited writes pay for one `current_setting` only.
+--
+--   target_type   the table written
+--   target_id     the row's key; composite keys are joined with ':' in key-column order
+--   action        `insert`, `update` or `delete`
+--   before/after  the row as JSON before and after the write (NULL for the missing side), with
+--                 secret material (`key_hash`, `token_hash`) removed
+--
+-- One procedure call may write several rows -- a cascading `deleteAccountPermanently` records every
+-- project, member and key it removed -- all sharing its actor, op-id and request id.
+CREATE TABLE audit_events (
+    id              BIGSERIAL PRIMARY KEY,
+    actor_subject   TEXT NOT NULL,
+    op_id           TEXT NOT NULL,
+    request_id      TEXT,
+    target_type     TEXT NOT NULL,
+    target_id       TEXT NOT NULL,
+    action          TEXT NOT NULL CHECK (action IN ('insert', 'update', 'delete')),
+    before          JSONB,
+    after           JSONB,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+
+-- `listAuditEvents` filters by actor, by target, or by time alone, and pages by `created_at`
+-- (ADR-0039). `clock_timestamp()` rather than `now()`: every row of one procedure call shares a
+-- transaction, and `now()` would give them all the same timestamp for a cursor to fall between.
+CREATE INDEX audit_events_actor_idx ON audit_events (actor_subject, created_at DESC);
+CREATE INDEX audit_events_target_idx ON audit_events (target_type, target_id, created_at DESC);
+CREATE INDEX audit_events_created_at_idx ON audit_events (created_at DESC);
+
+-- Append-only, enforced the same way as `budget_grants` (ADR-0009): the trigger fires for every
+-- role, including a superuser.
+CREATE OR REPLACE FUNCTION audit_events_forbid_mutation() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_events is append-only: % is not permitted (id=%)', TG_OP, OLD.id;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_events_no_update
+    BEFORE UPDATE ON audit_events
+    FOR EACH ROW EXECUTE FUNCTION audit_events_forbid_mutation();
+
+CREATE TRIGGER audit_events_no_delete
+    BEFORE DELETE ON audit_events
+    FOR EACH ROW EXECUTE FUNCTION audit_events_forbid_mutation();
+
+REVOKE UPDATE, DELETE ON audit_events FROM PUBLIC;
+
+-- Trigger arguments: the key columns (comma-separated), then any columns to drop from the
+-- snapshots.
+CREATE OR REPLACE FUNCTION audit_row_change() RETURNS trigger AS $$
+DECLARE
+    old_row JSONB;
+    new_row JSONB;
+    target  TEXT;
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        old_row := to_jsonb(OLD);
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        new_row := to_jsonb(NEW);
+    END IF;
+    FOR i IN 1 .. TG_NARGS - 1 LOOP
+        old_row := old_row - TG_ARGV[i];
+        new_row := new_row - TG_ARGV[i];
+    END LOOP;
+    IF old_row = new_row THEN
+        RETURN NULL;
+    END IF;
+
+    SELECT string_agg(COALESCE(new_row, old_row) ->> key.col, ':' ORDER BY key.ord)
+    INTO target
+    FROM unnest(string_to_array(TG_ARGV[0], ',')) WITH ORDINALITY AS key(col, ord);
+
+    INSERT INTO audit_events
+      (actor_subject, op_id, request_id, target_type, target_id, action, before, after)
+    VALUES (
+      current_setting('lightbridge.audit_actor', true),
+      current_setting('lightbridge.audit_op_id', true),
+      NULLIF(current_setting('lightbridge.audit_request_id', true), ''),
+      TG_TABLE_NAME,
+      target,
+      lower(TG_OP),
+      old_row,
+      new_row
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER accounts_audit
+    AFTER INSERT OR UPDATE OR DELETE ON accounts
+    FOR EACH ROW WHEN (current_setting('lightbridge.audit_op_id', true) <> '')
+    EXECUTE FUNCTION audit_row_change('id');
+
+CREATE TRIGGER projects_audit
+    AFTER INSERT OR UPDATE OR DELETE ON projects
+    FOR EACH ROW WHEN (current_setting('lightbridge.audit_op_id', true) <> '')
+    EXECUTE FUNCTION audit_row_change('id');
+
+CREATE TRIGGER project_members_audit
+    AFTER INSERT OR UPDATE OR DELETE ON project_members
+    FOR EACH ROW WHEN (current_setting('lightbridge.audit_op_id', true) <> '')
+    EXECUTE FUNCTION audit_row_change('project_id,account_id');
+
+CREATE TRIGGER project_key_lifecycle_policies_audit
+    AFTER INSERT OR UPDATE OR DELETE ON project_key_lifecycle_policies
+    FOR EACH ROW WHEN (current_setting('lightbridge.audit_op_id', true) <> '')
+    EXECUTE FUNCTION audit_row_change('project_id');
+
+CREATE TRIGGER api_keys_audit
+    AFTER INSERT OR UPDATE OR DELETE ON api_keys
+    FOR EACH ROW WHEN (current_setting('lightbridge.audit_op_id', true) <> '')
+    EXECUTE FUNCTION audit_row_change('id', 'key_hash');
+
+CREATE TRIGGER exchange_refresh_tokens_audit
+    AFTER INSERT OR UPDATE OR DELETE ON exchange_refresh_tokens
+    FOR EACH ROW WHEN (current_setting('lightbridge.audit_op_id', true) <> '')
+    EXECUTE FUNCTION audit_row_change('id', 'token_hash');
+
+CREATE TRIGGER budget_policy_sets_audit
+    AFTER INSERT OR UPDATE OR DELETE ON budget_policy_sets
+    FOR EACH ROW WHEN (current_setting('lightbridge.audit_op_id', true) <> '')
+    EXECUTE FUNCTION audit_row_change('id');
+
+CREATE TRIGGER budget_policy_revisions_audit
+    AFTER INSERT OR UPDATE OR DELETE ON budget_policy_revisions
+    FOR EACH ROW WHEN (current_setting('lightbridge.audit_op_id', true) <> '')
+    EXECUTE FUNCTION audit_row_change('id');
+
+CREATE TRIGGER budget_augmentation_requests_audit
+    AFTER INSERT OR UPDATE OR DELETE ON budget_augmentation_requests
+    FOR EACH ROW WHEN (current_setting('lightbridge.audit_op_id', true) <> '')
+    EXECUTE FUNCTION audit_row_change('id');
+
+CREATE TRIGGER budget_grants_audit
+    AFTER INSERT ON budget_grants
+    FOR EACH ROW WHEN (current_setting('lightbridge.audit_op_id', true) <> '')
+    EXECUTE FUNCTION audit_row_change('id');